@@ -16,6 +16,7 @@
 
 mod arrow_util;
 mod store;
+mod store_edit;
 mod store_gc;
 mod store_read;
 mod store_stats;
@@ -34,6 +35,7 @@ pub use self::arrow_util::ArrayExt;
 pub use self::store::{
     DataStore, DataStoreConfig, IndexBucket, IndexRowNr, IndexTable, RowIndex, RowIndexKind,
 };
+pub use self::store_edit::{EditError, EditResult};
 pub use self::store_gc::GarbageCollectionTarget;
 pub use self::store_read::{LatestAtQuery, RangeQuery};
 pub use self::store_stats::DataStoreStats;
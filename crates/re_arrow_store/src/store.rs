@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::num::NonZeroU64;
 use std::sync::atomic::AtomicU64;
 
@@ -54,7 +54,7 @@ pub enum RowIndexKind {
 /// associated with a component name.
 ///
 /// See [`DataStore::latest_at`], [`DataStore::range`] & [`DataStore::get`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RowIndex(pub(crate) NonZeroU64);
 
 impl RowIndex {
@@ -73,6 +73,11 @@ impl RowIndex {
         self.0.get() & !Self::KIND_MASK
     }
 
+    /// The index of the row `n` rows further down the same table.
+    pub(crate) fn offset(self, n: usize) -> Self {
+        Self::from_u63(self.kind(), self.as_u64() + n as u64)
+    }
+
     pub(crate) fn kind(self) -> RowIndexKind {
         match self.0.get() & Self::KIND_MASK > 0 {
             false => RowIndexKind::Temporal,
@@ -387,6 +392,15 @@ impl DataStore {
         Ok(())
     }
 
+    /// All the entities we have any data for, timeless or not.
+    pub fn entity_paths(&self) -> BTreeSet<EntityPath> {
+        crate::profile_function!();
+
+        let temporal = self.indices.values().map(|table| &table.ent_path);
+        let timeless = self.timeless_indices.values().map(|table| &table.ent_path);
+        temporal.chain(timeless).cloned().collect()
+    }
+
     /// The oldest time for which we have any data.
     ///
     /// Ignores timeless data.
@@ -1050,17 +1064,15 @@ pub struct PersistentComponentTable {
     /// ```
     ///
     /// This can contain any number of chunks, depending on how the data was inserted (e.g. single
-    /// insertions vs. batches).
-    ///
-    /// Note that, as of today, we do not actually support batched insertion nor do we support
-    /// chunks of non-unit length (batches are inserted on a per-row basis internally).
-    /// As a result, chunks always contain one and only one row's worth of data, at least until
-    /// the bucket is compacted one or more times.
-    /// See also #589.
+    /// insertions vs. batches): a multi-row bundle ends up as a single chunk.
     //
     // TODO(cmc): compact timeless tables once in a while
     pub(crate) chunks: Vec<Box<dyn Array>>,
 
+    /// The row number of the first row of each chunk, i.e. the running sum of the length of the
+    /// previous chunks.
+    pub(crate) chunk_row_offsets: Vec<u64>,
+
     /// The total number of rows present in this bucket, across all chunks.
     pub(crate) total_rows: u64,
 
@@ -1078,6 +1090,7 @@ impl std::fmt::Display for PersistentComponentTable {
             name,
             datatype,
             chunks,
+            chunk_row_offsets: _,
             total_rows,
             total_size_bytes,
         } = self;
@@ -1126,6 +1139,14 @@ impl PersistentComponentTable {
             }
         }
 
+        // There must be one row offset per chunk
+        ensure!(
+            self.chunk_row_offsets.len() == self.chunks.len(),
+            "expected one row offset per chunk, got {} for {} chunks",
+            self.chunk_row_offsets.len(),
+            self.chunks.len(),
+        );
+
         Ok(())
     }
 }
@@ -1339,17 +1360,16 @@ pub struct ComponentBucket {
     /// ```
     ///
     /// During the active lifespan of the bucket, this can contain any number of chunks,
-    /// depending on how the data was inserted (e.g. single insertions vs. batches).
+    /// depending on how the data was inserted (e.g. single insertions vs. batches): a multi-row
+    /// bundle ends up as a single chunk.
     /// All of these chunks get compacted into one contiguous array when the bucket is archived,
     /// i.e. when the bucket is full and a new one is created.
-    ///
-    /// Note that, as of today, we do not actually support batched insertion nor do we support
-    /// chunks of non-unit length (batches are inserted on a per-row basis internally).
-    /// As a result, chunks always contain one and only one row's worth of data, at least until
-    /// the bucket is archived and compacted.
-    /// See also #589.
     pub(crate) chunks: Vec<Box<dyn Array>>,
 
+    /// The row number of the first row of each chunk, relative to [`Self::row_offset`], i.e. the
+    /// running sum of the length of the previous chunks.
+    pub(crate) chunk_row_offsets: Vec<u64>,
+
     /// The total number of rows present in this bucket, across all chunks.
     pub(crate) total_rows: u64,
 
@@ -1376,13 +1396,11 @@ impl std::fmt::Display for ComponentBucket {
             // - all buckets that follow are lazily instantiated when data get inserted
             //
             // TODO(#439): is that still true with deletion?
-            // TODO(#589): support for non-unit-length chunks
             self.row_offset
                 + self
-                    .chunks
-                    .len()
+                    .total_rows()
                     .checked_sub(1)
-                    .expect("buckets are never empty") as u64,
+                    .expect("buckets are never empty"),
         ))?;
 
         f.write_fmt(format_args!("archived: {}\n", self.archived))?;
@@ -1424,6 +1442,14 @@ impl ComponentBucket {
             }
         }
 
+        // There must be one row offset per chunk
+        ensure!(
+            self.chunk_row_offsets.len() == self.chunks.len(),
+            "expected one row offset per chunk, got {} for {} chunks",
+            self.chunk_row_offsets.len(),
+            self.chunks.len(),
+        );
+
         Ok(())
     }
 }
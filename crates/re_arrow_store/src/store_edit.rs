@@ -0,0 +1,185 @@
+use re_log::debug;
+use re_log_types::{EntityPath, Timeline};
+
+use crate::DataStore;
+
+// ---
+
+#[derive(thiserror::Error, Debug)]
+pub enum EditError {
+    #[error("Cannot move {from} to {to}: the root entity can't be moved, nor moved onto")]
+    RootEntity { from: EntityPath, to: EntityPath },
+
+    #[error("Cannot move {from} to {to}: there is already data at {to}")]
+    DestinationNotEmpty { from: EntityPath, to: EntityPath },
+}
+
+pub type EditResult<T> = ::std::result::Result<T, EditError>;
+
+/// Is `ent_path` either `root` or one of its descendants?
+fn is_in_subtree(ent_path: &EntityPath, root: &EntityPath) -> bool {
+    ent_path == root || ent_path.is_descendant_of(root)
+}
+
+impl DataStore {
+    /// Permanently drops all the data logged at `ent_path` and any of its descendants,
+    /// timeless or not.
+    ///
+    /// This only affects indices: the component data that was only referenced by the dropped
+    /// indices becomes unreachable, and is freed by the garbage collector later on.
+    ///
+    /// Returns the number of index tables that were dropped.
+    pub fn drop_entity_subtree(&mut self, ent_path: &EntityPath) -> usize {
+        crate::profile_function!();
+
+        let num_tables = self.indices.len() + self.timeless_indices.len();
+        self.indices
+            .retain(|_, table| !is_in_subtree(&table.ent_path, ent_path));
+        self.timeless_indices
+            .retain(|_, table| !is_in_subtree(&table.ent_path, ent_path));
+        let num_dropped = num_tables - self.indices.len() - self.timeless_indices.len();
+
+        debug!(kind = "drop", %ent_path, num_dropped, "dropped entity subtree");
+
+        num_dropped
+    }
+
+    /// Permanently drops all the data logged on `timeline` at `ent_path` and any of its
+    /// descendants.
+    ///
+    /// Data that was also logged on other timelines can still be found on those.
+    /// Timeless data is unaffected.
+    ///
+    /// Returns the number of index tables that were dropped.
+    pub fn drop_timeline(&mut self, timeline: &Timeline, ent_path: &EntityPath) -> usize {
+        crate::profile_function!();
+
+        let num_tables = self.indices.len();
+        self.indices.retain(|(table_timeline, _), table| {
+            table_timeline != timeline || !is_in_subtree(&table.ent_path, ent_path)
+        });
+        let num_dropped = num_tables - self.indices.len();
+
+        debug!(
+            kind = "drop",
+            timeline = %timeline.name(),
+            %ent_path,
+            num_dropped,
+            "dropped timeline"
+        );
+
+        num_dropped
+    }
+
+    /// Moves all the data logged at `from` and any of its descendants to `to`, e.g. to rename
+    /// or reparent an entity.
+    ///
+    /// `from/a/b` ends up at `to/a/b`.
+    ///
+    /// Fails without modifying anything if [`Self::check_rename_entity_subtree`] fails.
+    pub fn rename_entity_subtree(&mut self, from: &EntityPath, to: &EntityPath) -> EditResult<()> {
+        crate::profile_function!();
+
+        // Check everything first, so we don't leave the store half-renamed.
+        self.check_rename_entity_subtree(from, to)?;
+
+        if from == to {
+            return Ok(());
+        }
+
+        let new_path = |ent_path: &EntityPath| renamed_path(ent_path, from, to);
+
+        let moved_keys = self
+            .indices
+            .iter()
+            .filter_map(|(key, table)| is_in_subtree(&table.ent_path, from).then_some(*key))
+            .collect::<Vec<_>>();
+        let moved = moved_keys
+            .into_iter()
+            .filter_map(|key| self.indices.remove(&key))
+            .collect::<Vec<_>>();
+        for mut table in moved {
+            table.ent_path = new_path(&table.ent_path);
+            self.indices
+                .insert((table.timeline, table.ent_path.hash()), table);
+        }
+
+        let moved_keys = self
+            .timeless_indices
+            .iter()
+            .filter_map(|(key, table)| is_in_subtree(&table.ent_path, from).then_some(*key))
+            .collect::<Vec<_>>();
+        let moved = moved_keys
+            .into_iter()
+            .filter_map(|key| self.timeless_indices.remove(&key))
+            .collect::<Vec<_>>();
+        for mut table in moved {
+            table.ent_path = new_path(&table.ent_path);
+            self.timeless_indices.insert(table.ent_path.hash(), table);
+        }
+
+        debug!(kind = "rename", %from, %to, "moved entity subtree");
+
+        #[cfg(debug_assertions)]
+        self.sanity_check().unwrap();
+
+        Ok(())
+    }
+
+    /// Would [`Self::rename_entity_subtree`] succeed?
+    ///
+    /// Neither `from` nor `to` can be the root, and none of the destination entities can already
+    /// have data.
+    pub fn check_rename_entity_subtree(
+        &self,
+        from: &EntityPath,
+        to: &EntityPath,
+    ) -> EditResult<()> {
+        if from.is_root() || to.is_root() {
+            return Err(EditError::RootEntity {
+                from: from.clone(),
+                to: to.clone(),
+            });
+        }
+        if from == to {
+            return Ok(());
+        }
+
+        let moved_hashes = self
+            .indices
+            .values()
+            .map(|table| &table.ent_path)
+            .chain(self.timeless_indices.values().map(|table| &table.ent_path))
+            .filter(|ent_path| is_in_subtree(ent_path, from))
+            .map(|ent_path| renamed_path(ent_path, from, to).hash())
+            .collect::<ahash::HashSet<_>>();
+        let destination_not_empty = self
+            .indices
+            .iter()
+            .map(|((_, ent_path_hash), table)| (ent_path_hash, &table.ent_path))
+            .chain(
+                self.timeless_indices
+                    .iter()
+                    .map(|(ent_path_hash, table)| (ent_path_hash, &table.ent_path)),
+            )
+            .any(|(ent_path_hash, ent_path)| {
+                !is_in_subtree(ent_path, from) && moved_hashes.contains(ent_path_hash)
+            });
+        if destination_not_empty {
+            return Err(EditError::DestinationNotEmpty {
+                from: from.clone(),
+                to: to.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Where `ent_path` ends up when moving `from` to `to`: `from/a/b` becomes `to/a/b`.
+fn renamed_path(ent_path: &EntityPath, from: &EntityPath, to: &EntityPath) -> EntityPath {
+    to.iter()
+        .chain(ent_path.iter().skip(from.len()))
+        .cloned()
+        .collect()
+}
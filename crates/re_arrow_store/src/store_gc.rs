@@ -39,6 +39,10 @@ impl DataStore {
     /// and very simple from an implementation standpoint.
     /// The tradeoff is that the given `primary_timeline` is expected to roughly follow insertion
     /// order, otherwise the behaviour is essentially undefined.
+    ///
+    /// There is no way to retain the data of specific entities, e.g. the ones matching an
+    /// [`re_log_types::EntityPathPattern`]: a component bucket holds the rows of all entities
+    /// for that component, so it can only be dropped as a whole.
     pub fn gc(
         &mut self,
         target: GarbageCollectionTarget,
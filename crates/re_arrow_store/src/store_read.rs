@@ -1121,11 +1121,7 @@ impl PersistentComponentTable {
     pub fn get(&self, row_idx: RowIndex) -> Box<dyn Array> {
         crate::profile_function!();
 
-        self.chunks[row_idx.as_u64() as usize]
-            .as_any()
-            .downcast_ref::<ListArray<i32>>()
-            .unwrap()
-            .value(0)
+        row_in_chunks(&self.chunks, &self.chunk_row_offsets, row_idx.as_u64()).unwrap()
     }
 }
 
@@ -1186,23 +1182,8 @@ impl ComponentBucket {
     /// Returns a shallow clone of the row data present at the given `row_idx`.
     pub fn get(&self, row_idx: RowIndex) -> Option<Box<dyn Array>> {
         let row_idx = row_idx.as_u64() - self.row_offset;
-        // This has to be safe to unwrap, otherwise it would never have made it past insertion.
-        if self.archived {
-            debug_assert_eq!(self.chunks.len(), 1);
-            let list = self.chunks[0]
-                .as_any()
-                .downcast_ref::<ListArray<i32>>()
-                .unwrap();
-            (row_idx < list.len() as u64).then(|| list.value(row_idx as _))
-        } else {
-            self.chunks.get(row_idx as usize).map(|chunk| {
-                chunk
-                    .as_any()
-                    .downcast_ref::<ListArray<i32>>()
-                    .unwrap()
-                    .value(0)
-            })
-        }
+        debug_assert!(!self.archived || self.chunks.len() == 1);
+        row_in_chunks(&self.chunks, &self.chunk_row_offsets, row_idx)
     }
 
     /// Returns a shallow clone of all the chunks in this bucket.
@@ -1217,3 +1198,22 @@ impl ComponentBucket {
         self.time_ranges.iter()
     }
 }
+
+/// Returns a shallow clone of the `row_nr`-th row across all `chunks`, `None` if out of bounds.
+///
+/// `chunk_row_offsets` holds the row number of the first row of each chunk.
+fn row_in_chunks(
+    chunks: &[Box<dyn Array>],
+    chunk_row_offsets: &[u64],
+    row_nr: u64,
+) -> Option<Box<dyn Array>> {
+    let chunk_nr = chunk_row_offsets
+        .partition_point(|&offset| offset <= row_nr)
+        .checked_sub(1)?;
+    let list = chunks[chunk_nr]
+        .as_any()
+        .downcast_ref::<ListArray<i32>>()
+        .unwrap();
+    let row_nr = (row_nr - chunk_row_offsets[chunk_nr]) as usize;
+    (row_nr < list.len()).then(|| list.value(row_nr))
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use arrow2::{
     array::{new_empty_array, Array, ListArray, UInt64Array},
     datatypes::DataType,
@@ -8,7 +10,7 @@ use parking_lot::RwLock;
 
 use re_log::{debug, trace};
 use re_log_types::{
-    msg_bundle::{wrap_in_listarray, ComponentBundle, MsgBundle},
+    msg_bundle::{wrap_in_listarray, Component as _, ComponentBundle, MsgBundle},
     ComponentName, EntityPath, MsgId, TimeInt, TimePoint, TimeRange, Timeline,
 };
 
@@ -23,8 +25,11 @@ use crate::{
 #[derive(thiserror::Error, Debug)]
 pub enum WriteError {
     // Batches
-    #[error("Cannot insert more than 1 row at a time, got {0}")]
-    MoreThanOneRow(usize),
+    #[error("Expected one time point per row, got {num_time_points} for {num_rows} rows")]
+    MismatchedTimePoints {
+        num_rows: usize,
+        num_time_points: usize,
+    },
 
     #[error("All components must have the same number of rows, got {0:?}")]
     MismatchedRows(Vec<(ComponentName, usize)>),
@@ -63,8 +68,12 @@ impl DataStore {
     /// Inserts a [`MsgBundle`]'s worth of components into the datastore.
     ///
     /// * All components across the bundle must share the same number of rows.
+    /// * There must be exactly one time point per row.
     /// * All components within a single row must share the same number of instances.
     ///
+    /// Multi-row bundles are ingested in one go: every component is stored as a single chunk,
+    /// while each row is indexed at its own time point.
+    ///
     /// If the bundle doesn't carry a payload for the cluster key, one will be auto-generated
     /// based on the length of the components in the payload, in the form of an array of
     /// monotonically increasing u64s going from `0` to `N-1`.
@@ -75,7 +84,7 @@ impl DataStore {
         let MsgBundle {
             msg_id,
             entity_path: ent_path,
-            time_point,
+            time_points,
             components: bundles,
         } = msg;
 
@@ -89,9 +98,7 @@ impl DataStore {
         let num_rows = bundles[0].num_rows();
 
         // Effectively the same thing as having a non-unit length batch, except it's really not
-        // worth more than an assertion since:
-        // - A) `MsgBundle` should already guarantee this
-        // - B) this limitation should be gone soon enough
+        // worth more than an assertion since `MsgBundle` should already guarantee this.
         debug_assert!(
             msg.components
                 .iter()
@@ -99,9 +106,12 @@ impl DataStore {
                 .all_unique(),
             "cannot insert same component multiple times, this is equivalent to multiple rows",
         );
-        // Batches cannot contain more than 1 row at the moment.
-        if num_rows != 1 {
-            return Err(WriteError::MoreThanOneRow(num_rows));
+        // Every row needs its own time point.
+        if time_points.len() != num_rows {
+            return Err(WriteError::MismatchedTimePoints {
+                num_rows,
+                num_time_points: time_points.len(),
+            });
         }
         // Components must share the same number of rows.
         if !bundles.iter().all(|bundle| bundle.num_rows() == num_rows) {
@@ -117,8 +127,10 @@ impl DataStore {
             kind = "insert",
             id = self.insert_id,
             cluster_key = %self.cluster_key,
-            timelines = ?time_point.iter()
-                .map(|(timeline, time)| (timeline.name(), timeline.typ().format(*time)))
+            timelines = ?time_points.iter()
+                .map(|time_point| time_point.iter()
+                    .map(|(timeline, time)| (timeline.name(), timeline.typ().format(*time)))
+                    .collect::<Vec<_>>())
                 .collect::<Vec<_>>(),
             entity = %ent_path,
             components = ?bundles.iter().map(|bundle| bundle.name()).collect::<Vec<_>>(),
@@ -131,260 +143,231 @@ impl DataStore {
             .find_position(|bundle| bundle.name() == self.cluster_key)
             .map(|(pos, _)| pos);
 
-        if time_point.is_timeless() {
-            let mut row_indices = IntMap::default();
-
-            // TODO(#589): support for batched row component insertions
-            for row_nr in 0..num_rows {
-                self.insert_timeless_row(row_nr, cluster_comp_pos, bundles, &mut row_indices)?;
-            }
+        // Consecutive rows that are either all timeless or all temporal are inserted in one go,
+        // as a single chunk per component, while each row still gets its own index entries.
+        let mut row_start = 0;
+        while row_start < num_rows {
+            let is_timeless = time_points[row_start].is_timeless();
+            let row_end = time_points[row_start..]
+                .iter()
+                .position(|time_point| time_point.is_timeless() != is_timeless)
+                .map_or(num_rows, |len| row_start + len);
 
-            let index = self
-                .timeless_indices
-                .entry(ent_path_hash)
-                .or_insert_with(|| PersistentIndexTable::new(self.cluster_key, ent_path.clone()));
-            index.insert(&row_indices)?;
-        } else {
-            let mut row_indices = IntMap::default();
-
-            // TODO(#589): support for batched row component insertions
-            for row_nr in 0..num_rows {
-                self.insert_row(
-                    time_point,
-                    row_nr,
-                    cluster_comp_pos,
-                    bundles,
-                    &mut row_indices,
-                )?;
+            let components = bundles
+                .iter()
+                .map(|bundle| {
+                    (
+                        bundle.name(),
+                        rows_slice(bundle.value_list(), row_start, row_end),
+                    )
+                })
+                .collect_vec();
+            let rows_time_points = &time_points[row_start..row_end];
+            let rows_indices = self.insert_rows(rows_time_points, cluster_comp_pos, &components)?;
+
+            for (time_point, row_indices) in rows_time_points.iter().zip(&rows_indices) {
+                if is_timeless {
+                    let index = self
+                        .timeless_indices
+                        .entry(ent_path_hash)
+                        .or_insert_with(|| {
+                            PersistentIndexTable::new(self.cluster_key, ent_path.clone())
+                        });
+                    index.insert(row_indices)?;
+                } else {
+                    for (timeline, time) in time_point.iter() {
+                        let ent_path = ent_path.clone(); // shallow
+                        let index = self
+                            .indices
+                            .entry((*timeline, ent_path_hash))
+                            .or_insert_with(|| {
+                                IndexTable::new(self.cluster_key, *timeline, ent_path)
+                            });
+                        index.insert(&self.config, *time, row_indices)?;
+                    }
+                }
             }
 
-            for (timeline, time) in time_point.iter() {
-                let ent_path = ent_path.clone(); // shallow
-                let index = self
-                    .indices
-                    .entry((*timeline, ent_path_hash))
-                    .or_insert_with(|| IndexTable::new(self.cluster_key, *timeline, ent_path));
-                index.insert(&self.config, *time, &row_indices)?;
-            }
+            row_start = row_end;
         }
 
         // This is valuable information, even for a timeless timepoint!
-        self.messages.insert(*msg_id, time_point.clone());
+        // Every row is known by its own `MsgId`, both to queries and to the garbage collector.
+        let row_msg_ids = row_msg_ids(bundles);
+        for (row_nr, time_point) in time_points.iter().enumerate() {
+            let row_msg_id = row_msg_ids.get(row_nr).copied().flatten();
+            self.messages
+                .insert(row_msg_id.unwrap_or(*msg_id), time_point.clone());
+        }
 
         Ok(())
     }
 
-    fn insert_timeless_row(
+    /// Inserts rows that are either all timeless or all temporal, pushing each component as a
+    /// single chunk into its table.
+    ///
+    /// `components` holds the rows of every component, one row per time point.
+    /// Returns the row indices of each row, in order.
+    fn insert_rows(
         &mut self,
-        row_nr: usize,
+        time_points: &[TimePoint],
         cluster_comp_pos: Option<usize>,
-        components: &[ComponentBundle],
-        row_indices: &mut IntMap<ComponentName, RowIndex>,
-    ) -> WriteResult<()> {
+        components: &[(ComponentName, Cow<'_, ListArray<i32>>)],
+    ) -> WriteResult<Vec<IntMap<ComponentName, RowIndex>>> {
         crate::profile_function!();
 
-        let (cluster_row_idx, cluster_len) = self.get_or_create_cluster_component(
-            row_nr,
-            cluster_comp_pos,
-            components,
-            &TimePoint::default(),
-        )?;
-
-        // Always insert the cluster component.
-        row_indices.insert(self.cluster_key, cluster_row_idx);
+        let is_timeless = time_points[0].is_timeless();
+
+        let (cluster_row_indices, cluster_lens) =
+            self.get_or_create_cluster_component(time_points, cluster_comp_pos, components)?;
+
+        let mut rows_indices = cluster_row_indices
+            .into_iter()
+            .map(|cluster_row_idx| {
+                let mut row_indices = IntMap::default();
+
+                // Always insert the cluster component.
+                row_indices.insert(self.cluster_key, cluster_row_idx);
+
+                if self.config.store_insert_ids {
+                    // Store the ID of the write request alongside the data.
+                    //
+                    // This is _not_ an actual `RowIndex`, there isn't even a component table
+                    // associated with insert IDs!
+                    // We're just abusing the fact that any value we push here as a `RowIndex`
+                    // will end up as-is in the index.
+                    row_indices.insert(
+                        Self::insert_id_key(),
+                        RowIndex::from_u63(RowIndexKind::Temporal, self.insert_id),
+                    );
+                }
 
-        if self.config.store_insert_ids {
-            // Store the ID of the write request alongside the data.
-            //
-            // This is _not_ an actual `RowIndex`, there isn't even a component table associated
-            // with insert IDs!
-            // We're just abusing the fact that any value we push here as a `RowIndex` will end up
-            // as-is in the index.
-            row_indices.insert(
-                Self::insert_id_key(),
-                RowIndex::from_u63(RowIndexKind::Temporal, self.insert_id),
-            );
-        }
+                row_indices
+            })
+            .collect_vec();
 
-        for bundle in components
+        for (name, rows) in components
             .iter()
-            .filter(|bundle| bundle.name() != self.cluster_key)
+            .filter(|(name, _)| *name != self.cluster_key)
         {
-            let (name, rows) = (bundle.name(), bundle.value_list());
-
-            // Unwrapping a ListArray is somewhat costly, especially considering we're just
-            // gonna rewrap it again in a minute... so we'd rather just slice it to a list of
-            // one instead.
-            //
-            // let rows_single = rows.slice(row_nr, 1);
-            //
-            // Except it turns out that slicing is _extremely_ costly!
-            // So use the fact that `rows` is always of unit-length for now.
-            let rows_single = rows;
-
-            let num_instances = rows_single.offsets().lengths().next().unwrap();
-            if num_instances != cluster_len {
-                return Err(WriteError::MismatchedInstances {
-                    cluster_comp: self.cluster_key,
-                    cluster_comp_nb_instances: cluster_len,
-                    key: name,
-                    num_instances,
-                });
+            // TODO(#440): support for splats
+            for (num_instances, &cluster_len) in rows.offsets().lengths().zip(&cluster_lens) {
+                if num_instances != cluster_len {
+                    return Err(WriteError::MismatchedInstances {
+                        cluster_comp: self.cluster_key,
+                        cluster_comp_nb_instances: cluster_len,
+                        key: *name,
+                        num_instances,
+                    });
+                }
             }
 
-            let table = self
-                .timeless_components
-                .entry(bundle.name())
-                .or_insert_with(|| {
+            let first_row_idx = if is_timeless {
+                let table = self.timeless_components.entry(*name).or_insert_with(|| {
                     PersistentComponentTable::new(
-                        name,
-                        ListArray::<i32>::get_child_type(rows_single.data_type()),
+                        *name,
+                        ListArray::<i32>::get_child_type(rows.data_type()),
                     )
                 });
-
-            let row_idx = table.push(rows_single);
-            row_indices.insert(name, row_idx);
-        }
-
-        Ok(())
-    }
-
-    fn insert_row(
-        &mut self,
-        time_point: &TimePoint,
-        row_nr: usize,
-        cluster_comp_pos: Option<usize>,
-        components: &[ComponentBundle],
-        row_indices: &mut IntMap<ComponentName, RowIndex>,
-    ) -> WriteResult<()> {
-        crate::profile_function!();
-
-        let (cluster_row_idx, cluster_len) =
-            self.get_or_create_cluster_component(row_nr, cluster_comp_pos, components, time_point)?;
-
-        // Always insert the cluster component.
-        row_indices.insert(self.cluster_key, cluster_row_idx);
-
-        if self.config.store_insert_ids {
-            // Store the ID of the write request alongside the data.
-            //
-            // This is _not_ an actual `RowIndex`, there isn't even a component table associated
-            // with insert IDs!
-            // We're just abusing the fact that any value we push here as a `RowIndex` will end up
-            // as-is in the index.
-            row_indices.insert(
-                Self::insert_id_key(),
-                RowIndex::from_u63(RowIndexKind::Temporal, self.insert_id),
-            );
-        }
-
-        for bundle in components
-            .iter()
-            .filter(|bundle| bundle.name() != self.cluster_key)
-        {
-            let (name, rows) = (bundle.name(), bundle.value_list());
-
-            // Unwrapping a ListArray is somewhat costly, especially considering we're just
-            // gonna rewrap it again in a minute... so we'd rather just slice it to a list of
-            // one instead.
-            //
-            // let rows_single = rows.slice(row_nr, 1);
-            //
-            // Except it turns out that slicing is _extremely_ costly!
-            // So use the fact that `rows` is always of unit-length for now.
-            let rows_single = rows;
-
-            // TODO(#440): support for splats
-            let num_instances = rows_single.offsets().lengths().next().unwrap();
-            if num_instances != cluster_len {
-                return Err(WriteError::MismatchedInstances {
-                    cluster_comp: self.cluster_key,
-                    cluster_comp_nb_instances: cluster_len,
-                    key: name,
-                    num_instances,
+                table.push(&**rows)
+            } else {
+                let table = self.components.entry(*name).or_insert_with(|| {
+                    ComponentTable::new(*name, ListArray::<i32>::get_child_type(rows.data_type()))
                 });
-            }
-
-            let table = self.components.entry(bundle.name()).or_insert_with(|| {
-                ComponentTable::new(
-                    name,
-                    ListArray::<i32>::get_child_type(rows_single.data_type()),
-                )
-            });
+                table.push(&self.config, time_points, &**rows)
+            };
 
-            let row_idx = table.push(&self.config, time_point, rows_single);
-            row_indices.insert(name, row_idx);
+            for (row_nr, row_indices) in rows_indices.iter_mut().enumerate() {
+                row_indices.insert(*name, first_row_idx.offset(row_nr));
+            }
         }
 
-        Ok(())
+        Ok(rows_indices)
     }
 
-    /// Tries to find the cluster component for the current row, or creates it if the caller hasn't
+    /// Tries to find the cluster component for the given rows, or creates it if the caller hasn't
     /// specified any.
     ///
+    /// Returns the row index and the number of instances of each row.
+    ///
     /// When creating an auto-generated cluster component of a specific length for the first time,
     /// this will keep track of its assigned row index and re-use it later on as a mean of
     /// deduplication.
     fn get_or_create_cluster_component(
         &mut self,
-        _row_nr: usize,
+        time_points: &[TimePoint],
         cluster_comp_pos: Option<usize>,
-        components: &[ComponentBundle],
-        time_point: &TimePoint,
-    ) -> WriteResult<(RowIndex, usize)> {
+        components: &[(ComponentName, Cow<'_, ListArray<i32>>)],
+    ) -> WriteResult<(Vec<RowIndex>, Vec<usize>)> {
         crate::profile_function!();
 
-        enum ClusterData<'a> {
-            Cached(RowIndex),
-            GenData(Box<dyn Array>),
-            UserData(&'a ListArray<i32>),
-        }
-
-        let (cluster_len, cluster_data) = if let Some(cluster_comp_pos) = cluster_comp_pos {
+        if let Some(cluster_comp_pos) = cluster_comp_pos {
             // We found a component with a name matching the cluster key's, let's make sure it's
             // valid (dense, sorted, no duplicates) and use that if so.
 
-            let cluster_comp = &components[cluster_comp_pos];
-            let data = cluster_comp.value_list().values(); // abusing the fact that num_rows==1
-            let len = data.len();
-
-            // Clustering component must be dense.
-            if !data.is_dense() {
-                return Err(WriteError::SparseClusteringComponent(data.clone()));
-            }
-            // Clustering component must be sorted and not contain any duplicates.
-            if !data.is_sorted_and_unique()? {
-                return Err(WriteError::InvalidClusteringComponent(data.clone()));
+            let (_, cluster_comp) = &components[cluster_comp_pos];
+            for data in cluster_comp.iter().flatten() {
+                // Clustering component must be dense.
+                if !data.is_dense() {
+                    return Err(WriteError::SparseClusteringComponent(data));
+                }
+                // Clustering component must be sorted and not contain any duplicates.
+                if !data.is_sorted_and_unique()? {
+                    return Err(WriteError::InvalidClusteringComponent(data));
+                }
             }
+            let cluster_lens = cluster_comp.offsets().lengths().collect_vec();
 
-            (len, ClusterData::UserData(cluster_comp.value_list()))
-        } else {
-            // The caller has not specified any cluster component, and so we'll have to generate
-            // one... unless we've already generated one of this exact length in the past,
-            // in which case we can simply re-use that row index.
-
-            // Use the length of any other component in the batch, they are guaranteed to all
-            // share the same length at this point anyway.
-            let len = components.first().map_or(0, |comp| {
-                comp.value_list().offsets().lengths().next().unwrap()
-            });
-
-            if let Some(row_idx) = self.cluster_comp_cache.get(&len) {
-                // Cache hit! Re-use that row index.
-                (len, ClusterData::Cached(*row_idx))
+            // Insert this cluster component in the right tables, just like any other component.
+            let first_row_idx = if time_points[0].is_timeless() {
+                let table = self
+                    .timeless_components
+                    .entry(self.cluster_key)
+                    .or_insert_with(|| {
+                        PersistentComponentTable::new(
+                            self.cluster_key,
+                            ListArray::<i32>::get_child_type(cluster_comp.data_type()),
+                        )
+                    });
+                table.push(&**cluster_comp)
             } else {
-                // Cache miss! Craft a new u64 array from the ground up.
+                let table = self.components.entry(self.cluster_key).or_insert_with(|| {
+                    ComponentTable::new(
+                        self.cluster_key,
+                        ListArray::<i32>::get_child_type(cluster_comp.data_type()),
+                    )
+                });
+                table.push(&self.config, time_points, &**cluster_comp)
+            };
+
+            let cluster_row_indices = (0..cluster_lens.len())
+                .map(|row_nr| first_row_idx.offset(row_nr))
+                .collect();
+            return Ok((cluster_row_indices, cluster_lens));
+        }
+
+        // The caller has not specified any cluster component, and so we'll have to generate
+        // one for each length... unless we've already generated one of this exact length in the
+        // past, in which case we can simply re-use that row index.
+
+        // Use the lengths of any other component in the batch, they are guaranteed to all
+        // share the same lengths at this point anyway.
+        let cluster_lens = components.first().map_or_else(
+            || vec![0; time_points.len()],
+            |(_, rows)| rows.offsets().lengths().collect_vec(),
+        );
+
+        let cluster_row_indices = cluster_lens
+            .iter()
+            .map(|&len| {
+                if let Some(row_idx) = self.cluster_comp_cache.get(&len) {
+                    // Cache hit! Re-use that row index.
+                    return *row_idx;
+                }
+
+                // Cache miss! Craft a new u64 array from the ground up, and store it forever.
                 let data = UInt64Array::from_vec((0..len as u64).collect_vec()).boxed();
                 let data = wrap_in_listarray(data).to_boxed();
-                (len, ClusterData::GenData(data))
-            }
-        };
-
-        match cluster_data {
-            ClusterData::Cached(row_idx) => Ok((row_idx, cluster_len)),
-            ClusterData::GenData(data) => {
-                // We had to generate a cluster component of the given length for the first time,
-                // let's store it forever.
 
                 let table = self
                     .timeless_components
@@ -397,38 +380,13 @@ impl DataStore {
                     });
                 let row_idx = table.push(&*data);
 
-                self.cluster_comp_cache.insert(cluster_len, row_idx);
+                self.cluster_comp_cache.insert(len, row_idx);
 
-                Ok((row_idx, cluster_len))
-            }
-            ClusterData::UserData(data) => {
-                // If we didn't hit the cache, then we have to insert this cluster component in
-                // the right tables, just like any other component.
-
-                let row_idx = if time_point.is_timeless() {
-                    let table = self
-                        .timeless_components
-                        .entry(self.cluster_key)
-                        .or_insert_with(|| {
-                            PersistentComponentTable::new(
-                                self.cluster_key,
-                                ListArray::<i32>::get_child_type(data.data_type()),
-                            )
-                        });
-                    table.push(data)
-                } else {
-                    let table = self.components.entry(self.cluster_key).or_insert_with(|| {
-                        ComponentTable::new(
-                            self.cluster_key,
-                            ListArray::<i32>::get_child_type(data.data_type()),
-                        )
-                    });
-                    table.push(&self.config, time_point, data)
-                };
+                row_idx
+            })
+            .collect();
 
-                Ok((row_idx, cluster_len))
-            }
-        }
+        Ok((cluster_row_indices, cluster_lens))
     }
 
     pub fn clear_msg_metadata(&mut self, drop_msg_ids: &ahash::HashSet<MsgId>) {
@@ -439,6 +397,41 @@ impl DataStore {
     }
 }
 
+/// Returns the `MsgId` of every row, if the bundle carries them.
+///
+/// Rows without any instances don't have one.
+fn row_msg_ids(components: &[ComponentBundle]) -> Vec<Option<MsgId>> {
+    use re_log_types::external::arrow2_convert::deserialize::arrow_array_deserialize_iterator;
+
+    let Some(bundle) = components.iter().find(|bundle| bundle.name() == MsgId::name()) else {
+        return Vec::new();
+    };
+
+    bundle
+        .value_list()
+        .iter()
+        .map(|row| {
+            let row = row?;
+            arrow_array_deserialize_iterator::<Option<MsgId>>(row.as_ref())
+                .ok()?
+                .next()
+                .flatten()
+        })
+        .collect()
+}
+
+/// Returns rows `row_start..row_end` of `rows`.
+///
+/// Slicing turns out to be surprisingly costly, so whole lists (by far the most common case) are
+/// passed through as-is.
+fn rows_slice(rows: &ListArray<i32>, row_start: usize, row_end: usize) -> Cow<'_, ListArray<i32>> {
+    if row_start == 0 && row_end == rows.len() {
+        Cow::Borrowed(rows)
+    } else {
+        Cow::Owned(rows.slice(row_start, row_end - row_start))
+    }
+}
+
 // --- Persistent Indices ---
 
 impl PersistentIndexTable {
@@ -979,6 +972,7 @@ impl PersistentComponentTable {
     fn new(name: ComponentName, datatype: &DataType) -> Self {
         // TODO(cmc): think about this when implementing deletion.
         let chunks = vec![wrap_in_listarray(new_empty_array(datatype.clone())).to_boxed()];
+        let chunk_row_offsets = vec![0];
         let total_rows = chunks.iter().map(|values| values.len() as u64).sum();
         let total_size_bytes = chunks
             .iter()
@@ -989,49 +983,49 @@ impl PersistentComponentTable {
             name,
             datatype: datatype.clone(),
             chunks,
+            chunk_row_offsets,
             total_rows,
             total_size_bytes,
         }
     }
 
-    /// Pushes `rows_single` to the end of the bucket, returning the _global_ `RowIndex` of the
-    /// freshly added row.
+    /// Pushes `rows` to the end of the table as a single chunk, returning the _global_
+    /// `RowIndex` of the first freshly added row.
     ///
-    /// `rows_single` must be a unit-length list of arrays of structs,
-    /// i.e. `ListArray<StructArray>`:
-    /// - the list layer corresponds to the different rows (always unit-length for now),
-    /// - the array layer corresponds to the different instances within that single row,
+    /// The following rows are found at the following indices, see [`RowIndex::offset`].
+    ///
+    /// `rows` must be a list of arrays of structs, i.e. `ListArray<StructArray>`:
+    /// - the list layer corresponds to the different rows,
+    /// - the array layer corresponds to the different instances within a single row,
     /// - and finally the struct layer holds the components themselves.
     /// E.g.:
     /// ```text
-    /// [[{x: 8.687487, y: 1.9590926}, {x: 2.0559108, y: 0.1494348}, {x: 7.09219, y: 0.9616637}]]
+    /// [
+    ///   [{x: 8.687487, y: 1.9590926}, {x: 2.0559108, y: 0.1494348}, {x: 7.09219, y: 0.9616637}],
+    ///   [{x: 7.158843, y: 0.68897724}, {x: 8.934421, y: 2.8420508}],
+    /// ]
     /// ```
-    //
-    // TODO(#589): support for batched row component insertions
-    pub fn push(&mut self, rows_single: &dyn Array) -> RowIndex {
+    pub fn push(&mut self, rows: &dyn Array) -> RowIndex {
         crate::profile_function!();
 
         debug_assert!(
-            ListArray::<i32>::get_child_type(rows_single.data_type()) == &self.datatype,
+            ListArray::<i32>::get_child_type(rows.data_type()) == &self.datatype,
             "trying to insert data of the wrong datatype in a component table, \
                 expected {:?}, got {:?}",
             &self.datatype,
-            ListArray::<i32>::get_child_type(rows_single.data_type()),
-        );
-        debug_assert!(
-            rows_single.len() == 1,
-            "batched row component insertions are not supported yet"
+            ListArray::<i32>::get_child_type(rows.data_type()),
         );
 
-        self.total_rows += 1;
+        let row_idx = self.total_rows;
+
+        self.total_rows += rows.len() as u64;
         // Warning: this is surprisingly costly!
-        self.total_size_bytes +=
-            arrow2::compute::aggregate::estimated_bytes_size(rows_single) as u64;
+        self.total_size_bytes += arrow2::compute::aggregate::estimated_bytes_size(rows) as u64;
 
-        // TODO(#589): support for non-unit-length chunks
-        self.chunks.push(rows_single.to_boxed()); // shallow
+        self.chunks.push(rows.to_boxed()); // shallow
+        self.chunk_row_offsets.push(row_idx);
 
-        RowIndex::from_u63(RowIndexKind::Timeless, self.chunks.len() as u64 - 1)
+        RowIndex::from_u63(RowIndexKind::Timeless, row_idx)
     }
 }
 
@@ -1050,39 +1044,39 @@ impl ComponentTable {
         }
     }
 
-    /// Finds the appropriate bucket in this component table and pushes `rows_single` at the
-    /// end of it, returning the _global_ `RowIndex` for this new row.
+    /// Finds the appropriate bucket in this component table and pushes `rows` at the end of it
+    /// as a single chunk, returning the _global_ `RowIndex` of the first freshly added row.
     ///
-    /// `rows_single` must be a unit-length list of arrays of structs,
-    /// i.e. `ListArray<StructArray>`:
-    /// - the list layer corresponds to the different rows (always unit-length for now),
-    /// - the array layer corresponds to the different instances within that single row,
+    /// The following rows are found at the following indices, see [`RowIndex::offset`].
+    /// There must be one time point per row.
+    ///
+    /// `rows` must be a list of arrays of structs, i.e. `ListArray<StructArray>`:
+    /// - the list layer corresponds to the different rows,
+    /// - the array layer corresponds to the different instances within a single row,
     /// - and finally the struct layer holds the components themselves.
     /// E.g.:
     /// ```text
-    /// [[{x: 8.687487, y: 1.9590926}, {x: 2.0559108, y: 0.1494348}, {x: 7.09219, y: 0.9616637}]]
+    /// [
+    ///   [{x: 8.687487, y: 1.9590926}, {x: 2.0559108, y: 0.1494348}, {x: 7.09219, y: 0.9616637}],
+    ///   [{x: 7.158843, y: 0.68897724}, {x: 8.934421, y: 2.8420508}],
+    /// ]
     /// ```
-    //
-    // TODO(#589): support for batched row component insertions
     pub fn push(
         &mut self,
         config: &DataStoreConfig,
-        time_point: &TimePoint,
-        rows_single: &dyn Array,
+        time_points: &[TimePoint],
+        rows: &dyn Array,
     ) -> RowIndex {
         crate::profile_function!();
 
         debug_assert!(
-            ListArray::<i32>::get_child_type(rows_single.data_type()) == &self.datatype,
+            ListArray::<i32>::get_child_type(rows.data_type()) == &self.datatype,
             "trying to insert data of the wrong datatype in a component table, \
                 expected {:?}, got {:?}",
             &self.datatype,
-            ListArray::<i32>::get_child_type(rows_single.data_type()),
-        );
-        debug_assert!(
-            rows_single.len() == 1,
-            "batched row component insertions are not supported yet"
+            ListArray::<i32>::get_child_type(rows.data_type()),
         );
+        debug_assert_eq!(time_points.len(), rows.len());
 
         // All component tables spawn with an initial bucket at row offset 0, thus this cannot
         // fail.
@@ -1123,16 +1117,19 @@ impl ComponentTable {
         let active_bucket = self.buckets.back_mut().unwrap();
         let row_idx = RowIndex::from_u63(
             RowIndexKind::Temporal,
-            active_bucket.push(time_point, rows_single) + active_bucket.row_offset,
+            active_bucket.push(time_points, rows) + active_bucket.row_offset,
         );
 
         trace!(
             kind = "insert",
-            timelines = ?time_point.into_iter()
-                .map(|(timeline, time)| (timeline.name(), timeline.typ().format(*time)))
+            timelines = ?time_points.iter()
+                .map(|time_point| time_point.iter()
+                    .map(|(timeline, time)| (timeline.name(), timeline.typ().format(*time)))
+                    .collect::<Vec<_>>())
                 .collect::<Vec<_>>(),
             component = self.name.as_str(),
             %row_idx,
+            num_rows = rows.len(),
             "pushed into component table"
         );
 
@@ -1153,6 +1150,7 @@ impl ComponentBucket {
         } else {
             vec![]
         };
+        let chunk_row_offsets = if chunks.is_empty() { vec![] } else { vec![0] };
 
         let total_rows = chunks.iter().map(|values| values.len() as u64).sum();
         let total_size_bytes = chunks
@@ -1166,33 +1164,22 @@ impl ComponentBucket {
             archived: false,
             time_ranges: Default::default(),
             chunks,
+            chunk_row_offsets,
             total_rows,
             total_size_bytes,
         }
     }
 
-    /// Pushes `rows_single` to the end of the bucket, returning the _local_ index of the
-    /// freshly added row.
+    /// Pushes `rows` to the end of the bucket as a single chunk, returning the _local_ index of
+    /// the first freshly added row.
     ///
-    /// `rows_single` must be a unit-length list of arrays of structs,
-    /// i.e. `ListArray<StructArray>`:
-    /// - the list layer corresponds to the different rows (always unit-length for now),
-    /// - the array layer corresponds to the different instances within that single row,
-    /// - and finally the struct layer holds the components themselves.
-    /// E.g.:
-    /// ```text
-    /// [[{x: 8.687487, y: 1.9590926}, {x: 2.0559108, y: 0.1494348}, {x: 7.09219, y: 0.9616637}]]
-    /// ```
-    pub fn push(&mut self, time_point: &TimePoint, rows_single: &dyn Array) -> u64 {
+    /// There must be one time point per row.
+    /// See [`ComponentTable::push`] for the expected layout of `rows`.
+    pub fn push(&mut self, time_points: &[TimePoint], rows: &dyn Array) -> u64 {
         crate::profile_function!();
 
-        debug_assert!(
-            rows_single.len() == 1,
-            "batched row component insertions are not supported yet"
-        );
-
         // Keep track of all affected time ranges, for garbage collection purposes.
-        for (timeline, &time) in time_point {
+        for (timeline, &time) in time_points.iter().flatten() {
             self.time_ranges
                 .entry(*timeline)
                 .and_modify(|range| {
@@ -1201,15 +1188,16 @@ impl ComponentBucket {
                 .or_insert_with(|| TimeRange::new(time, time));
         }
 
-        self.total_rows += 1;
+        let row_idx = self.total_rows;
+
+        self.total_rows += rows.len() as u64;
         // Warning: this is surprisingly costly!
-        self.total_size_bytes +=
-            arrow2::compute::aggregate::estimated_bytes_size(rows_single) as u64;
+        self.total_size_bytes += arrow2::compute::aggregate::estimated_bytes_size(rows) as u64;
 
-        // TODO(#589): support for non-unit-length chunks
-        self.chunks.push(rows_single.to_boxed()); // shallow
+        self.chunks.push(rows.to_boxed()); // shallow
+        self.chunk_row_offsets.push(row_idx);
 
-        self.chunks.len() as u64 - 1
+        row_idx
     }
 
     /// Archives the bucket as a new one is about to take its place.
@@ -1243,6 +1231,7 @@ impl ComponentBucket {
                 arrow2::compute::aggregate::estimated_bytes_size(&*values) as u64;
 
             self.chunks = vec![values];
+            self.chunk_row_offsets = vec![0];
         }
 
         self.archived = true;
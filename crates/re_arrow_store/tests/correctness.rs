@@ -57,10 +57,11 @@ fn write_errors() {
 
         // The first component of the bundle determines the number of rows for all other
         // components in there (since it has to match for all of them), so in this case we get a
-        // `MoreThanOneRow` error as the first component is > 1 row.
+        // `MismatchedTimePoints` error as the first component is 2 rows but there's only a
+        // single time point.
         assert!(matches!(
             store.insert(&bundle),
-            Err(WriteError::MoreThanOneRow(_)),
+            Err(WriteError::MismatchedTimePoints { .. }),
         ));
     }
 
@@ -159,6 +160,101 @@ fn write_errors() {
 
 // ---
 
+#[test]
+fn batched_insert() {
+    init_logs();
+
+    for config in re_arrow_store::test_util::all_configs() {
+        let mut store = DataStore::new(InstanceKey::name(), config.clone());
+        batched_insert_impl(&mut store);
+    }
+}
+
+fn batched_insert_impl(store: &mut DataStore) {
+    let ent_path = EntityPath::from("this/that");
+    let timeline_frame_nr = Timeline::new("frame_nr", TimeType::Sequence);
+
+    let bundles = [(1i64, 2), (2, 3), (3, 4)]
+        .into_iter()
+        .map(|(frame_nr, num_instances)| {
+            test_bundle!(ent_path @ [build_frame_nr(frame_nr.into())] => [
+                build_some_point2d(num_instances),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let bundle = re_log_types::msg_bundle::MsgBundle::try_concat(&bundles).unwrap();
+    assert_eq!(3, bundle.num_rows());
+
+    store.insert(&bundle).unwrap();
+    store.sanity_check().unwrap();
+
+    // Every row is indexed at its own time.
+    for (frame_nr, expected_num_instances) in [(1i64, 2), (2, 3), (3, 4)] {
+        let row_indices = store
+            .latest_at(
+                &LatestAtQuery::new(timeline_frame_nr, frame_nr.into()),
+                &ent_path,
+                Point2D::name(),
+                &[Point2D::name()],
+            )
+            .unwrap();
+        let rows = store.get(&[Point2D::name()], &row_indices);
+        let points = rows[0].as_ref().unwrap();
+        assert_eq!(expected_num_instances, points.len());
+    }
+
+    // Every row is known by its own `MsgId`.
+    for row in &bundles {
+        assert_eq!(
+            Some(&row.time_points[0]),
+            store.get_msg_metadata(&row.msg_id)
+        );
+    }
+}
+
+#[test]
+fn batched_insert_timeless_and_temporal() {
+    init_logs();
+
+    for config in re_arrow_store::test_util::all_configs() {
+        let mut store = DataStore::new(InstanceKey::name(), config.clone());
+        batched_insert_timeless_and_temporal_impl(&mut store);
+    }
+}
+
+fn batched_insert_timeless_and_temporal_impl(store: &mut DataStore) {
+    let ent_path = EntityPath::from("this/that");
+    let timeline_frame_nr = Timeline::new("frame_nr", TimeType::Sequence);
+
+    // Timeless rows in the middle of the batch end up in their own chunk.
+    let bundles = [
+        test_bundle!(ent_path @ [build_frame_nr(1.into())] => [build_some_point2d(2)]),
+        test_bundle!(ent_path @ [] => [build_some_point2d(3)]),
+        test_bundle!(ent_path @ [build_frame_nr(2.into())] => [build_some_point2d(4)]),
+    ];
+    let bundle = re_log_types::msg_bundle::MsgBundle::try_concat(&bundles).unwrap();
+
+    store.insert(&bundle).unwrap();
+    store.sanity_check().unwrap();
+
+    // Nothing temporal yet at frame #0: only the timeless row shows up.
+    for (frame_nr, expected_num_instances) in [(0i64, 3), (1, 2), (2, 4)] {
+        let row_indices = store
+            .latest_at(
+                &LatestAtQuery::new(timeline_frame_nr, frame_nr.into()),
+                &ent_path,
+                Point2D::name(),
+                &[Point2D::name()],
+            )
+            .unwrap();
+        let rows = store.get(&[Point2D::name()], &row_indices);
+        let points = rows[0].as_ref().unwrap();
+        assert_eq!(expected_num_instances, points.len());
+    }
+}
+
+// ---
+
 #[test]
 fn latest_at_emptiness_edge_cases() {
     init_logs();
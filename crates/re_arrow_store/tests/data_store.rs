@@ -300,7 +300,7 @@ fn latest_at_impl(store: &mut DataStore) {
 
         // insert timeless
         let mut bundle_timeless = bundle.clone();
-        bundle_timeless.time_point = Default::default();
+        bundle_timeless.time_points = vec![Default::default()];
         store.insert(&bundle_timeless).unwrap();
     };
 
@@ -405,7 +405,7 @@ fn range_impl(store: &mut DataStore) {
 
         // insert timeless
         let mut bundle_timeless = bundle.clone();
-        bundle_timeless.time_point = Default::default();
+        bundle_timeless.time_points = vec![Default::default()];
         store.insert(&bundle_timeless).unwrap();
     };
 
@@ -968,6 +968,76 @@ fn gc_impl(store: &mut DataStore) {
     }
 }
 
+// --- Edits ---
+
+#[test]
+fn edits() {
+    init_logs();
+
+    for config in re_arrow_store::test_util::all_configs() {
+        let mut store = DataStore::new(InstanceKey::name(), config.clone());
+        edits_impl(&mut store);
+    }
+}
+
+fn edits_impl(store: &mut DataStore) {
+    let frame_nr = Timeline::new("frame_nr", TimeType::Sequence);
+    let ent_paths = ["robot", "robot/arm", "robot/arm/hand", "world"].map(EntityPath::from);
+
+    for ent_path in &ent_paths {
+        let bundle = test_bundle!(ent_path @ [build_frame_nr(1.into())] => [build_some_point2d(2)]);
+        store.insert(&bundle).unwrap();
+        let mut bundle_timeless = bundle.clone();
+        bundle_timeless.time_points = vec![Default::default()];
+        store.insert(&bundle_timeless).unwrap();
+    }
+
+    let has_points = |store: &DataStore, ent_path: &str| {
+        store
+            .latest_at(
+                &LatestAtQuery::new(frame_nr, 1.into()),
+                &EntityPath::from(ent_path),
+                Point2D::name(),
+                &[Point2D::name()],
+            )
+            .is_some()
+    };
+
+    // Can't move on top of existing data.
+    assert!(store
+        .rename_entity_subtree(&"robot/arm".into(), &"world".into())
+        .is_err());
+    // Can't move the root, nor onto it.
+    assert!(store
+        .rename_entity_subtree(&EntityPath::root(), &"world/root".into())
+        .is_err());
+    assert!(store
+        .rename_entity_subtree(&"robot/arm".into(), &EntityPath::root())
+        .is_err());
+    assert!(has_points(store, "robot/arm/hand"));
+
+    store
+        .rename_entity_subtree(&"robot/arm".into(), &"world/arm".into())
+        .unwrap();
+    assert!(!has_points(store, "robot/arm"));
+    assert!(!has_points(store, "robot/arm/hand"));
+    assert!(has_points(store, "world/arm"));
+    assert!(has_points(store, "world/arm/hand"));
+    assert!(has_points(store, "robot"));
+
+    assert_eq!(store.drop_timeline(&frame_nr, &"world/arm".into()), 2);
+    assert!(has_points(store, "world/arm")); // timeless data is still there
+    assert_eq!(store.drop_entity_subtree(&"world".into()), 4);
+    assert!(!has_points(store, "world/arm"));
+    assert!(!has_points(store, "world"));
+
+    assert_eq!(
+        store.entity_paths().into_iter().collect::<Vec<_>>(),
+        vec![EntityPath::from("robot")]
+    );
+    store.sanity_check().unwrap();
+}
+
 // ---
 
 pub fn init_logs() {
@@ -47,6 +47,11 @@ pub struct EntityProperties {
     pub visible_history: ExtraQueryHistory,
     pub interactive: bool,
 
+    /// Opacity in `[0, 1]`, applied to meshes, points and images in spatial views.
+    ///
+    /// Multiplies with the opacity of the parent entities.
+    pub opacity: f32,
+
     /// Enable color mapping?
     ///
     /// See [`Self::color_mapper`] to select an actual mapping.
@@ -72,6 +77,15 @@ pub struct EntityProperties {
     pub backproject_scale: EditableAutoValue<f32>,
     /// Used to scale the radii of the points in the resulting point cloud.
     pub backproject_radius_scale: EditableAutoValue<f32>,
+
+    /// How rank 3 tensors are rendered.
+    ///
+    /// Only applies to tensors shaped like a volume when in a spatial view, using 3D navigation.
+    pub volume_render_mode: EditableAutoValue<VolumeRenderMode>,
+    /// Values below this fraction of a volume's value range are fully transparent.
+    ///
+    /// Also hides the cells of voxel grids below this fraction of their value range.
+    pub volume_threshold: EditableAutoValue<f32>,
 }
 
 #[cfg(feature = "serde")]
@@ -82,6 +96,7 @@ impl EntityProperties {
             visible: self.visible && child.visible,
             visible_history: self.visible_history.with_child(&child.visible_history),
             interactive: self.interactive && child.interactive,
+            opacity: self.opacity * child.opacity,
 
             color_mapping: self.color_mapping || child.color_mapping,
             color_mapper: self.color_mapper.or(&child.color_mapper).clone(),
@@ -101,6 +116,12 @@ impl EntityProperties {
                 .backproject_radius_scale
                 .or(&child.backproject_radius_scale)
                 .clone(),
+
+            volume_render_mode: self
+                .volume_render_mode
+                .or(&child.volume_render_mode)
+                .clone(),
+            volume_threshold: self.volume_threshold.or(&child.volume_threshold).clone(),
         }
     }
 }
@@ -112,6 +133,7 @@ impl Default for EntityProperties {
             visible: true,
             visible_history: ExtraQueryHistory::default(),
             interactive: true,
+            opacity: 1.0,
             color_mapping: false,
             color_mapper: EditableAutoValue::default(),
             pinhole_image_plane_distance: EditableAutoValue::default(),
@@ -119,6 +141,8 @@ impl Default for EntityProperties {
             backproject_pinhole_ent_path: None,
             backproject_scale: EditableAutoValue::default(),
             backproject_radius_scale: EditableAutoValue::default(),
+            volume_render_mode: EditableAutoValue::default(),
+            volume_threshold: EditableAutoValue::default(),
         }
     }
 }
@@ -157,6 +181,24 @@ pub enum ColorMap {
     Plasma,
     Magma,
     Inferno,
+    CoolWarm,
+    RdBu,
+    Twilight,
+}
+
+impl ColorMap {
+    /// All color maps, in the order they should be presented to users.
+    pub const ALL: [ColorMap; 9] = [
+        ColorMap::Grayscale,
+        ColorMap::Turbo,
+        ColorMap::Viridis,
+        ColorMap::Plasma,
+        ColorMap::Magma,
+        ColorMap::Inferno,
+        ColorMap::CoolWarm,
+        ColorMap::RdBu,
+        ColorMap::Twilight,
+    ];
 }
 
 impl std::fmt::Display for ColorMap {
@@ -168,6 +210,9 @@ impl std::fmt::Display for ColorMap {
             ColorMap::Plasma => "Plasma",
             ColorMap::Magma => "Magma",
             ColorMap::Inferno => "Inferno",
+            ColorMap::CoolWarm => "CoolWarm",
+            ColorMap::RdBu => "RdBu",
+            ColorMap::Twilight => "Twilight",
         })
     }
 }
@@ -177,6 +222,9 @@ impl std::fmt::Display for ColorMap {
 pub enum ColorMapper {
     /// Use a well-known color map, pre-implemented as a wgsl module.
     ColorMap(ColorMap),
+
+    /// Use the [`re_log_types::component_types::ColorLut`] logged to the entity.
+    Lut,
     // TODO(cmc): support textures.
     // TODO(cmc): support custom transfer functions.
 }
@@ -185,6 +233,7 @@ impl std::fmt::Display for ColorMapper {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ColorMapper::ColorMap(colormap) => colormap.fmt(f),
+            ColorMapper::Lut => f.write_str("Custom LUT"),
         }
     }
 }
@@ -197,6 +246,47 @@ impl Default for ColorMapper {
 
 // ----------------------------------------------------------------------------
 
+/// How the values along a ray through a volume are turned into a color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum VolumeRenderMode {
+    /// Accumulate color mapped values, treating them as density.
+    Composite,
+
+    /// Maximum intensity projection, i.e. the maximum value along each ray.
+    MaxIntensity,
+
+    /// Surface where the volume crosses the given fraction of its value range.
+    Isosurface { iso_value: f32 },
+}
+
+impl VolumeRenderMode {
+    /// All modes, as listed in the selection panel. Isosurfaces start out at half the value range.
+    pub const ALL: [VolumeRenderMode; 3] = [
+        VolumeRenderMode::Composite,
+        VolumeRenderMode::MaxIntensity,
+        VolumeRenderMode::Isosurface { iso_value: 0.5 },
+    ];
+}
+
+impl std::fmt::Display for VolumeRenderMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            VolumeRenderMode::Composite => "Composite",
+            VolumeRenderMode::MaxIntensity => "Max intensity",
+            VolumeRenderMode::Isosurface { .. } => "Isosurface",
+        })
+    }
+}
+
+impl Default for VolumeRenderMode {
+    fn default() -> Self {
+        Self::Composite
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Get the latest value for a given [`re_log_types::msg_bundle::Component`].
 ///
 /// This assumes that the row we get from the store only contains a single instance for this
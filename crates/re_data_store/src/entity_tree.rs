@@ -1,9 +1,9 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{btree_map, BTreeMap, BTreeSet};
 
 use itertools::Itertools;
 use re_log_types::{
-    ComponentName, ComponentPath, EntityPath, EntityPathPart, MsgId, PathOp, TimeInt, TimePoint,
-    Timeline,
+    ComponentName, ComponentPath, EntityPath, EntityPathPart, EntityPathPattern, MsgId, PathOp,
+    TimeInt, TimePoint, Timeline,
 };
 
 // ----------------------------------------------------------------------------
@@ -34,6 +34,34 @@ impl TimeHistogramPerTimeline {
     ) -> impl ExactSizeIterator<Item = (&Timeline, &mut BTreeMap<TimeInt, usize>)> {
         self.0.iter_mut()
     }
+
+    fn add(&mut self, other: &Self) {
+        for (timeline, histogram) in &other.0 {
+            let counts = self.0.entry(*timeline).or_default();
+            for (time, count) in histogram {
+                *counts.entry(*time).or_default() += count;
+            }
+        }
+    }
+
+    fn remove(&mut self, other: &Self) {
+        for (timeline, histogram) in &other.0 {
+            let Some(counts) = self.0.get_mut(timeline) else {
+                continue;
+            };
+            for (time, count) in histogram {
+                if let Some(existing) = counts.get_mut(time) {
+                    *existing = existing.saturating_sub(*count);
+                    if *existing == 0 {
+                        counts.remove(time);
+                    }
+                }
+            }
+            if counts.is_empty() {
+                self.0.remove(timeline);
+            }
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -63,6 +91,18 @@ impl TimesPerTimeline {
         }
     }
 
+    /// Forget about the times for which there is no data anymore, e.g. after dropping
+    /// an entity.
+    pub fn retain_logged(&mut self, prefix_times: &TimeHistogramPerTimeline) {
+        self.0.retain(|timeline, time_set| {
+            let Some(histogram) = prefix_times.get(timeline) else {
+                return false;
+            };
+            time_set.retain(|time| histogram.contains_key(time));
+            !time_set.is_empty()
+        });
+    }
+
     pub fn has_timeline(&self, timeline: &Timeline) -> bool {
         self.0.contains_key(timeline)
     }
@@ -177,12 +217,16 @@ impl EntityTree {
     ) -> Vec<ComponentPath> {
         crate::profile_function!();
 
+        if path_op.rewrites_history() {
+            // These don't add to the tree, see `EntityDb::add_path_op`.
+            return vec![];
+        }
+
         let entity_path = path_op.entity_path();
 
         // Look up the leaf at which we will execute the path operation
         let leaf = self.create_subtrees_recursively(entity_path.as_slice(), 0, time_point);
 
-        // TODO(jleibs): Refactor this as separate functions
         match path_op {
             PathOp::ClearComponents(entity_path) => {
                 // Track that any future fields need a Null at the right
@@ -225,9 +269,157 @@ impl EntityTree {
                 }
                 results
             }
+            PathOp::DropRecursive(_) | PathOp::Rename { .. } | PathOp::DropTimeline { .. } => {
+                vec![] // see above
+            }
         }
     }
 
+    /// Removes the subtree at `path`, and everything it contributed to its ancestors.
+    ///
+    /// Returns the removed subtree, if any. The root itself can't be removed, but all its
+    /// children can.
+    pub fn remove_subtree(&mut self, path: &EntityPath) -> Option<Self> {
+        fn remove_recursive(this: &mut EntityTree, path: &[EntityPathPart]) -> Option<EntityTree> {
+            let removed = match path {
+                [] => return None,
+                [last] => this.children.remove(last)?,
+                [first, rest @ ..] => remove_recursive(this.children.get_mut(first)?, rest)?,
+            };
+            this.prefix_times.remove(&removed.prefix_times);
+            this.num_timeless_messages = this
+                .num_timeless_messages
+                .saturating_sub(removed.num_timeless_messages);
+            Some(removed)
+        }
+
+        remove_recursive(self, path.as_slice())
+    }
+
+    /// Inserts a subtree that was previously removed with [`Self::remove_subtree`] at `path`,
+    /// e.g. to rename or reparent it.
+    ///
+    /// Anything already at `path` is kept: the two trees are merged.
+    pub fn insert_subtree(&mut self, path: &EntityPath, mut subtree: Self) {
+        fn insert_recursive(
+            this: &mut EntityTree,
+            full_path: &[EntityPathPart],
+            depth: usize,
+            subtree: EntityTree,
+        ) {
+            this.prefix_times.add(&subtree.prefix_times);
+            this.num_timeless_messages += subtree.num_timeless_messages;
+
+            match &full_path[depth..] {
+                [] => {}
+                [last] => match this.children.entry(last.clone()) {
+                    btree_map::Entry::Vacant(entry) => {
+                        entry.insert(subtree);
+                    }
+                    btree_map::Entry::Occupied(entry) => entry.into_mut().merge(subtree),
+                },
+                [first, ..] => {
+                    let child = this.children.entry(first.clone()).or_insert_with(|| {
+                        EntityTree::new(
+                            full_path[..depth + 1].into(),
+                            this.recursive_clears.clone(),
+                        )
+                    });
+                    insert_recursive(child, full_path, depth + 1, subtree);
+                }
+            }
+        }
+
+        if path.is_root() {
+            return; // can't replace the root
+        }
+        subtree.set_path_recursively(path.clone());
+        insert_recursive(self, path.as_slice(), 0, subtree);
+    }
+
+    /// Adds everything in `other`, a tree at the same path, to this one.
+    fn merge(&mut self, other: Self) {
+        let Self {
+            path: _,
+            children,
+            prefix_times,
+            num_timeless_messages,
+            nonrecursive_clears,
+            recursive_clears,
+            components,
+        } = other;
+
+        self.prefix_times.add(&prefix_times);
+        self.num_timeless_messages += num_timeless_messages;
+        self.nonrecursive_clears.extend(nonrecursive_clears);
+        self.recursive_clears.extend(recursive_clears);
+
+        for (component_name, stats) in components {
+            match self.components.entry(component_name) {
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(stats);
+                }
+                btree_map::Entry::Occupied(entry) => {
+                    let existing = entry.into_mut();
+                    existing.times.add(&stats.times);
+                    existing.num_timeless_messages += stats.num_timeless_messages;
+                }
+            }
+        }
+
+        for (part, child) in children {
+            match self.children.entry(part) {
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(child);
+                }
+                btree_map::Entry::Occupied(entry) => entry.into_mut().merge(child),
+            }
+        }
+    }
+
+    fn set_path_recursively(&mut self, path: EntityPath) {
+        for (part, child) in &mut self.children {
+            child.set_path_recursively(path.iter().chain(std::iter::once(part)).cloned().collect());
+        }
+        self.path = path;
+    }
+
+    /// Forgets about all the times on the given timeline, at `path` and below.
+    pub fn drop_timeline(&mut self, path: &EntityPath, timeline: &Timeline) {
+        fn drop_all(this: &mut EntityTree, timeline: &Timeline) {
+            this.prefix_times.0.remove(timeline);
+            for component in this.components.values_mut() {
+                component.times.0.remove(timeline);
+            }
+            for child in this.children.values_mut() {
+                drop_all(child, timeline);
+            }
+        }
+
+        fn drop_recursive(
+            this: &mut EntityTree,
+            path: &[EntityPathPart],
+            timeline: &Timeline,
+        ) -> Option<BTreeMap<TimeInt, usize>> {
+            let dropped = match path {
+                [] => {
+                    let dropped = this.prefix_times.0.get(timeline).cloned();
+                    drop_all(this, timeline);
+                    return dropped;
+                }
+                [first, rest @ ..] => {
+                    drop_recursive(this.children.get_mut(first)?, rest, timeline)?
+                }
+            };
+            this.prefix_times.remove(&TimeHistogramPerTimeline(
+                std::iter::once((*timeline, dropped.clone())).collect(),
+            ));
+            Some(dropped)
+        }
+
+        drop_recursive(self, path.as_slice(), timeline);
+    }
+
     fn create_subtrees_recursively(
         &mut self,
         full_path: &[EntityPathPart],
@@ -327,6 +519,23 @@ impl EntityTree {
             child.visit_children_recursively(visitor);
         }
     }
+
+    /// Visits all the entities matching the pattern, skipping the subtrees that can't match.
+    pub fn visit_matching(
+        &self,
+        pattern: &EntityPathPattern,
+        visitor: &mut impl FnMut(&EntityPath),
+    ) {
+        if !pattern.may_match_subtree(&self.path) {
+            return;
+        }
+        if pattern.matches(&self.path) {
+            visitor(&self.path);
+        }
+        for child in self.children.values() {
+            child.visit_matching(pattern, visitor);
+        }
+    }
 }
 
 #[derive(Default)]
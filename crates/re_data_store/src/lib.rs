@@ -10,11 +10,13 @@ pub mod entity_properties;
 pub mod entity_tree;
 mod instance_path;
 pub mod log_db;
+mod timeline_mapping;
 
 pub use entity_properties::*;
 pub use entity_tree::*;
 pub use instance_path::*;
 pub use log_db::LogDb;
+pub use timeline_mapping::{TimelineMapping, TimelineOffsets};
 
 use re_log_types::msg_bundle;
 
@@ -6,10 +6,10 @@ use re_log_types::{
     external::arrow2_convert::deserialize::arrow_array_deserialize_iterator,
     msg_bundle::{Component as _, ComponentBundle, MsgBundle},
     ArrowMsg, BeginRecordingMsg, ComponentPath, EntityPath, EntityPathHash, EntityPathOpMsg,
-    LogMsg, MsgId, PathOp, RecordingId, RecordingInfo, TimePoint, Timeline,
+    LogMsg, MsgId, PathOp, RecordingId, RecordingInfo, TimePoint, TimeUnits, Timeline,
 };
 
-use crate::{Error, TimesPerTimeline};
+use crate::{Error, TimelineMapping, TimesPerTimeline};
 
 // ----------------------------------------------------------------------------
 
@@ -21,6 +21,9 @@ pub struct EntityDb {
     /// Used for time control
     pub times_per_timeline: TimesPerTimeline,
 
+    /// Used to convert times between timelines, e.g. when switching timeline.
+    pub timeline_mapping: TimelineMapping,
+
     /// A tree-view (split on path components) of the entities.
     pub tree: crate::EntityTree,
 
@@ -33,6 +36,7 @@ impl Default for EntityDb {
         Self {
             entity_path_from_hash: Default::default(),
             times_per_timeline: Default::default(),
+            timeline_mapping: Default::default(),
             tree: crate::EntityTree::root(),
             data_store: re_arrow_store::DataStore::new(
                 InstanceKey::name(),
@@ -61,8 +65,11 @@ impl EntityDb {
     fn try_add_arrow_data_msg(&mut self, msg: &ArrowMsg) -> Result<(), Error> {
         let msg_bundle = MsgBundle::try_from(msg).map_err(Error::MsgBundleError)?;
 
-        for (&timeline, &time_int) in msg_bundle.time_point.iter() {
-            self.times_per_timeline.insert(timeline, time_int);
+        for time_point in &msg_bundle.time_points {
+            for (&timeline, &time_int) in time_point.iter() {
+                self.times_per_timeline.insert(timeline, time_int);
+            }
+            self.timeline_mapping.add_time_point(time_point);
         }
 
         self.register_entity_path(&msg_bundle.entity_path);
@@ -73,9 +80,11 @@ impl EntityDb {
             if component.name() == MsgId::name() {
                 continue;
             }
-            let pending_clears = self
-                .tree
-                .add_data_msg(&msg_bundle.time_point, &component_path);
+            let pending_clears = msg_bundle
+                .time_points
+                .iter()
+                .flat_map(|time_point| self.tree.add_data_msg(time_point, &component_path))
+                .collect::<Vec<_>>();
 
             for (msg_id, time_point) in pending_clears {
                 // Create and insert an empty component into the arrow store
@@ -99,6 +108,27 @@ impl EntityDb {
     }
 
     fn add_path_op(&mut self, msg_id: MsgId, time_point: &TimePoint, path_op: &PathOp) {
+        match path_op {
+            PathOp::ClearComponents(_) | PathOp::ClearRecursive(_) => {}
+            PathOp::DropRecursive(entity_path) => {
+                self.drop_entity_subtree(entity_path);
+                return;
+            }
+            PathOp::Rename { from, to } => {
+                if let Err(err) = self.rename_entity_subtree(from, to) {
+                    re_log::warn!("Ignoring rename: {err}");
+                }
+                return;
+            }
+            PathOp::DropTimeline {
+                entity_path,
+                timeline,
+            } => {
+                self.drop_timeline(entity_path, timeline);
+                return;
+            }
+        }
+
         let cleared_paths = self.tree.add_path_op(msg_id, time_point, path_op);
 
         for component_path in cleared_paths {
@@ -123,6 +153,69 @@ impl EntityDb {
         }
     }
 
+    /// Permanently drops all the data at `entity_path` and below.
+    fn drop_entity_subtree(&mut self, entity_path: &EntityPath) {
+        crate::profile_function!();
+
+        self.data_store.drop_entity_subtree(entity_path);
+        let removed = if entity_path.is_root() {
+            // The root can't be removed from the tree, so start over with an empty one.
+            Some(std::mem::replace(&mut self.tree, crate::EntityTree::root()))
+        } else {
+            self.tree.remove_subtree(entity_path)
+        };
+        if let Some(removed) = removed {
+            removed.visit_children_recursively(&mut |path: &EntityPath| {
+                self.entity_path_from_hash.remove(&path.hash());
+            });
+        }
+        self.on_history_rewritten();
+    }
+
+    /// Moves all the data at `from` and below to `to`.
+    fn rename_entity_subtree(
+        &mut self,
+        from: &EntityPath,
+        to: &EntityPath,
+    ) -> Result<(), re_arrow_store::EditError> {
+        crate::profile_function!();
+
+        self.data_store.rename_entity_subtree(from, to)?;
+
+        if let Some(subtree) = self.tree.remove_subtree(from) {
+            subtree.visit_children_recursively(&mut |path: &EntityPath| {
+                let new_path: EntityPath = to
+                    .iter()
+                    .chain(path.iter().skip(from.len()))
+                    .cloned()
+                    .collect();
+                self.entity_path_from_hash.remove(&path.hash());
+                self.entity_path_from_hash
+                    .entry(new_path.hash())
+                    .or_insert(new_path);
+            });
+            self.tree.insert_subtree(to, subtree);
+        }
+        Ok(())
+    }
+
+    /// Permanently drops all the data logged on `timeline` at `entity_path` and below.
+    fn drop_timeline(&mut self, entity_path: &EntityPath, timeline: &Timeline) {
+        crate::profile_function!();
+
+        self.data_store.drop_timeline(timeline, entity_path);
+        self.tree.drop_timeline(entity_path, timeline);
+        self.on_history_rewritten();
+    }
+
+    /// Keep the time bookkeeping in sync with the tree after data was dropped.
+    fn on_history_rewritten(&mut self) {
+        self.times_per_timeline
+            .retain_logged(&self.tree.prefix_times);
+        self.timeline_mapping
+            .retain_timelines(self.tree.prefix_times.timelines());
+    }
+
     pub fn purge(
         &mut self,
         cutoff_times: &std::collections::BTreeMap<Timeline, TimeInt>,
@@ -133,6 +226,7 @@ impl EntityDb {
         let Self {
             entity_path_from_hash: _,
             times_per_timeline,
+            timeline_mapping,
             tree,
             data_store: _, // purged before this function is called
         } = self;
@@ -142,6 +236,11 @@ impl EntityDb {
             times_per_timeline.purge(cutoff_times);
         }
 
+        {
+            crate::profile_scope!("timeline_mapping");
+            timeline_mapping.purge(cutoff_times);
+        }
+
         {
             crate::profile_scope!("tree");
             tree.purge(cutoff_times, drop_msg_ids);
@@ -186,6 +285,15 @@ impl LogDb {
         }
     }
 
+    /// How the values of the given timeline should be interpreted and displayed.
+    pub fn timeline_units(&self, timeline: &Timeline) -> TimeUnits {
+        self.recording_info
+            .as_ref()
+            .and_then(|info| info.timeline_units.get(timeline.name()))
+            .copied()
+            .unwrap_or_else(|| TimeUnits::default_for(timeline.typ()))
+    }
+
     pub fn timelines(&self) -> impl ExactSizeIterator<Item = &Timeline> {
         self.times_per_timeline().timelines()
     }
@@ -194,6 +302,10 @@ impl LogDb {
         &self.entity_db.times_per_timeline
     }
 
+    pub fn timeline_mapping(&self) -> &TimelineMapping {
+        &self.entity_db.timeline_mapping
+    }
+
     pub fn num_timeless_messages(&self) -> usize {
         self.entity_db.tree.num_timeless_messages()
     }
@@ -292,3 +404,46 @@ impl LogDb {
         entity_db.purge(&cutoff_times, &drop_msg_ids);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use re_log_types::component_types::Point2D;
+
+    use super::*;
+
+    fn log_point(log_db: &mut LogDb, entity_path: &EntityPath) {
+        let msg_bundle = MsgBundle::new(
+            MsgId::random(),
+            entity_path.clone(),
+            [(Timeline::new_sequence("frame_nr"), 1.into())].into(),
+            vec![vec![Point2D::new(1.0, 2.0)].try_into().unwrap()],
+        );
+        log_db
+            .add(LogMsg::ArrowMsg(msg_bundle.try_into().unwrap()))
+            .unwrap();
+    }
+
+    #[test]
+    fn drop_root() {
+        let mut log_db = LogDb::default();
+        log_point(&mut log_db, &EntityPath::root());
+        log_point(&mut log_db, &EntityPath::from("world/points"));
+        assert!(!log_db.entity_db.tree.components.is_empty());
+
+        log_db
+            .add(LogMsg::EntityPathOpMsg(EntityPathOpMsg {
+                msg_id: MsgId::random(),
+                time_point: [(Timeline::new_sequence("frame_nr"), 2.into())].into(),
+                path_op: PathOp::DropRecursive(EntityPath::root()),
+            }))
+            .unwrap();
+
+        let entity_db = &log_db.entity_db;
+        assert!(entity_db.tree.path.is_root());
+        assert!(entity_db.tree.children.is_empty());
+        assert!(entity_db.tree.components.is_empty());
+        assert!(entity_db.entity_path_from_hash.is_empty());
+        assert!(entity_db.data_store.entity_paths().is_empty());
+        assert_eq!(log_db.timelines().len(), 0);
+    }
+}
@@ -0,0 +1,400 @@
+use std::collections::BTreeMap;
+
+use re_log_types::{TimeInt, TimePoint, TimeReal, TimeType, Timeline};
+
+/// User-defined offset of each timeline, e.g. to correct the clock skew between two machines.
+///
+/// The offset is how far ahead the clock of that timeline is, so that
+/// `true time = logged time - offset`.
+pub type TimelineOffsets = BTreeMap<Timeline, TimeInt>;
+
+/// Converts times between timelines, e.g. from `frame_nr` to `log_time`.
+///
+/// The mapping is derived from the [`TimePoint`]s that were logged on several timelines at
+/// once: every such [`TimePoint`] is a sample of the mapping, and we interpolate linearly
+/// in between samples.
+///
+/// The mapping is kept monotonic: samples that would make time go backwards on the other
+/// timeline (e.g. a frame number that was reset) are ignored.
+///
+/// Samples that the linear interpolation already predicts are dropped as they come in (within
+/// [`TimelineMapping::tolerance`]), so that timelines advancing in lockstep, e.g. `frame_nr` and
+/// `log_time` at a steady frame rate, only cost a handful of samples however long the recording.
+#[derive(Default)]
+pub struct TimelineMapping {
+    /// For each pair of timelines `(from, to)`: the co-logged times, from `from` to `to`.
+    samples: BTreeMap<(Timeline, Timeline), Samples>,
+
+    /// How many samples were ignored because they weren't monotonic.
+    num_non_monotonic: usize,
+}
+
+impl TimelineMapping {
+    /// Adds all the pairs of timelines in this [`TimePoint`] as samples of the mapping.
+    pub fn add_time_point(&mut self, time_point: &TimePoint) {
+        if time_point.timelines().len() < 2 {
+            return;
+        }
+
+        for (from, from_time) in time_point.iter() {
+            for (to, to_time) in time_point.iter() {
+                if from == to {
+                    continue;
+                }
+
+                let samples = self.samples.entry((*from, *to)).or_default();
+                if samples.times.contains_key(from_time) {
+                    continue; // keep the first sample
+                }
+
+                let is_monotonic = samples
+                    .times
+                    .range(..*from_time)
+                    .next_back()
+                    .map_or(true, |(_, prev)| prev <= to_time)
+                    && samples
+                        .times
+                        .range(*from_time..)
+                        .next()
+                        .map_or(true, |(_, next)| to_time <= next);
+
+                if is_monotonic {
+                    samples.insert(*from_time, *to_time, Self::tolerance(to));
+                } else {
+                    self.num_non_monotonic += 1;
+                }
+            }
+        }
+    }
+
+    /// How far off the interpolated time on the `to` timeline may be at a dropped sample.
+    ///
+    /// For sequences, that's less than half a step, so that rounding still gives back the
+    /// logged value.
+    fn tolerance(to: &Timeline) -> f64 {
+        match to.typ() {
+            TimeType::Time => 1e6, // 1ms
+            TimeType::Sequence => 0.49,
+        }
+    }
+
+    /// How many co-logged times were ignored because they would have made the mapping
+    /// non-monotonic.
+    pub fn num_non_monotonic(&self) -> usize {
+        self.num_non_monotonic
+    }
+
+    /// Can we convert times from one timeline to the other?
+    pub fn can_convert(&self, from: &Timeline, to: &Timeline) -> bool {
+        from == to
+            || self.samples.contains_key(&(*from, *to))
+            || (from.typ() == TimeType::Time && to.typ() == TimeType::Time)
+    }
+
+    /// Converts `time` on the `from` timeline to the corresponding time on the `to` timeline.
+    ///
+    /// Timelines that were logged together are converted using the co-logged times, which
+    /// already take any clock skew into account.
+    /// Other temporal timelines are assumed to share the same clock, corrected by `offsets`.
+    ///
+    /// Returns `None` if there is no known relation between the two timelines.
+    pub fn convert(
+        &self,
+        from: &Timeline,
+        to: &Timeline,
+        time: TimeReal,
+        offsets: &TimelineOffsets,
+    ) -> Option<TimeReal> {
+        if from == to {
+            return Some(time);
+        }
+
+        let is_temporal = from.typ() == TimeType::Time && to.typ() == TimeType::Time;
+
+        if let Some(samples) = self.samples.get(&(*from, *to)) {
+            let before = samples.times.range(..=time.floor()).next_back();
+            let after = samples.times.range(time.ceil()..).next();
+
+            match (before, after) {
+                (Some((&from0, &to0)), Some((&from1, &to1))) => {
+                    if from0 == from1 {
+                        return Some(to0.into());
+                    }
+                    let t = (time - from0).as_f64() / (from1 - from0).as_f64();
+                    return Some(to0 + TimeReal::from((to1 - to0).as_f64() * t));
+                }
+                (Some((&from0, &to0)), None) | (None, Some((&from0, &to0))) => {
+                    // Outside of the known range: extrapolate at the same rate if we can,
+                    // otherwise stick to the closest sample.
+                    return Some(if is_temporal {
+                        to0 + (time - from0)
+                    } else {
+                        to0.into()
+                    });
+                }
+                (None, None) => {}
+            }
+        }
+
+        if is_temporal {
+            let offset =
+                |timeline: &Timeline| offsets.get(timeline).copied().unwrap_or(TimeInt::from(0));
+            Some(time - offset(from) + offset(to))
+        } else {
+            None
+        }
+    }
+
+    /// Forget about the timelines that aren't in the given list, e.g. after dropping one.
+    pub fn retain_timelines<'a>(&mut self, timelines: impl Iterator<Item = &'a Timeline>) {
+        let timelines = timelines.collect::<std::collections::BTreeSet<_>>();
+        self.samples
+            .retain(|(from, to), _| timelines.contains(from) && timelines.contains(to));
+    }
+
+    /// Forget about the samples from before the given cutoff times.
+    pub fn purge(&mut self, cutoff_times: &BTreeMap<Timeline, TimeInt>) {
+        for ((from, _), samples) in &mut self.samples {
+            if let Some(cutoff_time) = cutoff_times.get(from) {
+                samples.times.retain(|time, _| cutoff_time <= time);
+                samples.slopes = None;
+            }
+        }
+    }
+}
+
+/// The co-logged times of a pair of timelines.
+#[derive(Default)]
+struct Samples {
+    /// From the time on the `from` timeline to the time on the `to` timeline.
+    times: BTreeMap<TimeInt, TimeInt>,
+
+    /// The range of slopes of the lines from the second-to-last sample that pass within the
+    /// tolerance of the last sample, and of all the samples that were dropped in between.
+    ///
+    /// `None` if that isn't known, e.g. after a sample was inserted before the last one.
+    slopes: Option<(f64, f64)>,
+}
+
+impl Samples {
+    /// Inserts a monotonic sample, dropping the last one if the line to the new one passes
+    /// close enough to it (and to the ones dropped before it).
+    fn insert(&mut self, from_time: TimeInt, to_time: TimeInt, tolerance: f64) {
+        let mut last_two = self.times.iter().rev().map(|(from, to)| (*from, *to));
+        let last = last_two.next();
+        let second_to_last = last_two.next();
+
+        let Some(last) = last.filter(|(last_from, _)| *last_from < from_time) else {
+            // Not appending: don't bother thinning.
+            self.times.insert(from_time, to_time);
+            self.slopes = None;
+            return;
+        };
+
+        if let (Some(anchor), Some((min_slope, max_slope))) = (second_to_last, self.slopes) {
+            let new_slope = slope(anchor, (from_time, to_time), 0.0);
+            if min_slope <= new_slope && new_slope <= max_slope {
+                self.times.remove(&last.0);
+                self.times.insert(from_time, to_time);
+                self.slopes = Some((
+                    min_slope.max(slope(anchor, (from_time, to_time), -tolerance)),
+                    max_slope.min(slope(anchor, (from_time, to_time), tolerance)),
+                ));
+                return;
+            }
+        }
+
+        self.times.insert(from_time, to_time);
+        self.slopes = Some((
+            slope(last, (from_time, to_time), -tolerance),
+            slope(last, (from_time, to_time), tolerance),
+        ));
+    }
+}
+
+/// The slope of the line from `a` to `b`, with `b` moved by `offset` on the `to` timeline.
+fn slope(a: (TimeInt, TimeInt), b: (TimeInt, TimeInt), offset: f64) -> f64 {
+    ((b.1 - a.1).as_i64() as f64 + offset) / (b.0 - a.0).as_i64() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_nr() -> Timeline {
+        Timeline::new_sequence("frame_nr")
+    }
+
+    fn log_time() -> Timeline {
+        Timeline::new_temporal("log_time")
+    }
+
+    fn sensor_time() -> Timeline {
+        Timeline::new_temporal("sensor_time")
+    }
+
+    const SEC: i64 = 1_000_000_000;
+
+    fn convert(
+        mapping: &TimelineMapping,
+        from: Timeline,
+        to: Timeline,
+        time: f64,
+        offsets: &TimelineOffsets,
+    ) -> Option<f64> {
+        mapping
+            .convert(&from, &to, time.into(), offsets)
+            .map(TimeReal::as_f64)
+    }
+
+    #[test]
+    fn add_time_point() {
+        let mut mapping = TimelineMapping::default();
+
+        // Nothing to relate on a single timeline.
+        mapping.add_time_point(&[(frame_nr(), 0.into())].into());
+        assert!(!mapping.can_convert(&frame_nr(), &log_time()));
+
+        mapping.add_time_point(&[(frame_nr(), 0.into()), (log_time(), 0.into())].into());
+        assert!(mapping.can_convert(&frame_nr(), &log_time()));
+        assert!(mapping.can_convert(&log_time(), &frame_nr()));
+        assert!(!mapping.can_convert(&frame_nr(), &Timeline::new_sequence("other")));
+    }
+
+    #[test]
+    fn interpolation() {
+        let mut mapping = TimelineMapping::default();
+        mapping.add_time_point(&[(frame_nr(), 0.into()), (log_time(), 0.into())].into());
+        mapping.add_time_point(&[(frame_nr(), 10.into()), (log_time(), SEC.into())].into());
+        mapping.add_time_point(&[(frame_nr(), 20.into()), (log_time(), (3 * SEC).into())].into());
+
+        let offsets = TimelineOffsets::default();
+        let to_log_time = |frame| convert(&mapping, frame_nr(), log_time(), frame, &offsets);
+        assert_eq!(Some(0.5 * SEC as f64), to_log_time(5.0));
+        assert_eq!(Some(2.0 * SEC as f64), to_log_time(15.0));
+
+        let to_frame_nr = |time| convert(&mapping, log_time(), frame_nr(), time, &offsets);
+        assert_eq!(Some(5.0), to_frame_nr(0.5 * SEC as f64));
+        assert_eq!(Some(15.0), to_frame_nr(2.0 * SEC as f64));
+    }
+
+    #[test]
+    fn extrapolation() {
+        let mut mapping = TimelineMapping::default();
+        mapping.add_time_point(&[(frame_nr(), 10.into()), (log_time(), SEC.into())].into());
+        mapping.add_time_point(&[(log_time(), (2 * SEC).into()), (sensor_time(), 0.into())].into());
+
+        let offsets = TimelineOffsets::default();
+
+        // Sequences stick to the closest sample.
+        assert_eq!(
+            Some(SEC as f64),
+            convert(&mapping, frame_nr(), log_time(), 0.0, &offsets)
+        );
+        assert_eq!(
+            Some(10.0),
+            convert(&mapping, log_time(), frame_nr(), 5.0 * SEC as f64, &offsets)
+        );
+
+        // Temporal timelines keep going at the same rate.
+        assert_eq!(
+            Some(SEC as f64),
+            convert(
+                &mapping,
+                log_time(),
+                sensor_time(),
+                3.0 * SEC as f64,
+                &offsets
+            )
+        );
+    }
+
+    #[test]
+    fn offsets() {
+        let mapping = TimelineMapping::default();
+        let offsets = [(sensor_time(), TimeInt::from(SEC))].into_iter().collect();
+
+        // Temporal timelines that were never logged together share the same clock.
+        assert!(mapping.can_convert(&log_time(), &sensor_time()));
+        assert_eq!(
+            Some(3.0 * SEC as f64),
+            convert(
+                &mapping,
+                log_time(),
+                sensor_time(),
+                2.0 * SEC as f64,
+                &offsets
+            )
+        );
+        assert_eq!(
+            Some(SEC as f64),
+            convert(
+                &mapping,
+                sensor_time(),
+                log_time(),
+                2.0 * SEC as f64,
+                &offsets
+            )
+        );
+
+        // Sequences can't be related that way.
+        assert_eq!(
+            None,
+            convert(&mapping, frame_nr(), log_time(), 0.0, &offsets)
+        );
+    }
+
+    #[test]
+    fn non_monotonic() {
+        let mut mapping = TimelineMapping::default();
+        mapping.add_time_point(&[(frame_nr(), 0.into()), (log_time(), SEC.into())].into());
+        mapping.add_time_point(&[(frame_nr(), 10.into()), (log_time(), (2 * SEC).into())].into());
+        assert_eq!(0, mapping.num_non_monotonic());
+
+        // The frame number was reset: ignored, in both directions.
+        mapping.add_time_point(&[(frame_nr(), 5.into()), (log_time(), (3 * SEC).into())].into());
+        assert_eq!(2, mapping.num_non_monotonic());
+
+        let offsets = TimelineOffsets::default();
+        assert_eq!(
+            Some(1.5 * SEC as f64),
+            convert(&mapping, frame_nr(), log_time(), 5.0, &offsets)
+        );
+    }
+
+    #[test]
+    fn thinning() {
+        let mut mapping = TimelineMapping::default();
+
+        // A steady frame rate, with some jitter well within the tolerance.
+        for frame in 0..1000 {
+            let jitter = (frame % 3 - 1) * 1000;
+            let time = frame * SEC / 30 + jitter;
+            mapping.add_time_point(&[(frame_nr(), frame.into()), (log_time(), time.into())].into());
+        }
+        assert!(mapping.samples[&(frame_nr(), log_time())].times.len() <= 3);
+        assert!(mapping.samples[&(log_time(), frame_nr())].times.len() <= 3);
+
+        // The frame rate doubles: the samples around the change are kept.
+        for frame in 1000..2000 {
+            let time = 1000 * SEC / 30 + (frame - 1000) * SEC / 60;
+            mapping.add_time_point(&[(frame_nr(), frame.into()), (log_time(), time.into())].into());
+        }
+        assert!(mapping.samples[&(frame_nr(), log_time())].times.len() <= 5);
+
+        let offsets = TimelineOffsets::default();
+        for frame in (0..2000).step_by(7) {
+            let expected = if frame < 1000 {
+                frame * SEC / 30
+            } else {
+                1000 * SEC / 30 + (frame - 1000) * SEC / 60
+            };
+            let converted =
+                convert(&mapping, frame_nr(), log_time(), frame as f64, &offsets).unwrap();
+            assert!(
+                (converted - expected as f64).abs() <= 1e6 + 1000.0,
+                "frame {frame}: {converted} vs. {expected}"
+            );
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! We have custom implementations of [`serde::Serialize`] and [`serde::Deserialize`] that wraps
 //! the inner Arrow serialization of [`Schema`] and [`Chunk`].
 
-use crate::{MsgId, TimePoint};
+use crate::{EntityPath, MsgId, TimePoint};
 use arrow2::{array::Array, chunk::Chunk, datatypes::Schema};
 
 /// Message containing an Arrow payload
@@ -21,9 +21,27 @@ pub struct ArrowMsg {
 }
 
 impl ArrowMsg {
-    pub fn time_point(&self) -> Result<TimePoint, crate::msg_bundle::MsgBundleError> {
+    /// The entity this message is about.
+    pub fn entity_path(&self) -> Result<EntityPath, crate::msg_bundle::MsgBundleError> {
+        crate::msg_bundle::extract_entity_path(&self.schema)
+    }
+
+    /// One [`TimePoint`] per row.
+    pub fn time_points(&self) -> Result<Vec<TimePoint>, crate::msg_bundle::MsgBundleError> {
         crate::msg_bundle::extract_timelines(&self.schema, &self.chunk)
     }
+
+    /// Keeps only the rows for which `keep` is true, `keep` having one entry per row.
+    ///
+    /// The [`MsgId`] of the message stays the same, every row keeps its own `MsgId` component.
+    pub fn filter_rows(&self, keep: &[bool]) -> arrow2::error::Result<Self> {
+        let mask = arrow2::array::BooleanArray::from_slice(keep);
+        Ok(Self {
+            msg_id: self.msg_id,
+            schema: self.schema.clone(),
+            chunk: arrow2::compute::filter::filter_chunk(&self.chunk, &mask)?,
+        })
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -117,7 +135,7 @@ mod tests {
     use super::{ArrowMsg, Chunk, MsgId, Schema};
     use crate::{
         datagen::{build_frame_nr, build_some_point2d, build_some_rects},
-        msg_bundle::try_build_msg_bundle2,
+        msg_bundle::{try_build_msg_bundle2, MsgBundle},
     };
 
     #[test]
@@ -175,4 +193,33 @@ mod tests {
         let msg_out: ArrowMsg = rmp_serde::from_slice(&buf).unwrap();
         assert_eq!(msg_in, msg_out);
     }
+
+    #[test]
+    fn test_roundtrip_multi_row_payload() {
+        let bundles = (0..3i64)
+            .map(|frame_nr| {
+                try_build_msg_bundle2(
+                    MsgId::random(),
+                    "world/rects",
+                    [build_frame_nr(frame_nr.into())],
+                    (build_some_point2d(2), build_some_rects(2)),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let bundle = MsgBundle::try_concat(&bundles).unwrap();
+        assert_eq!(bundle.num_rows(), 3);
+
+        let msg: ArrowMsg = bundle.try_into().unwrap();
+        assert_eq!(msg.time_points().unwrap().len(), 3);
+
+        let bundle_out = MsgBundle::try_from(&msg).unwrap();
+        assert_eq!(bundle_out.num_rows(), 3);
+        assert_eq!(
+            bundle_out.time_points,
+            (0..3i64)
+                .map(|frame_nr| [build_frame_nr(frame_nr.into())].into())
+                .collect::<Vec<crate::TimePoint>>()
+        );
+    }
 }
@@ -0,0 +1,58 @@
+use arrow2_convert::{ArrowDeserialize, ArrowField, ArrowSerialize};
+
+use crate::msg_bundle::Component;
+
+use super::ColorRGBA;
+
+/// A custom color map, given as a lookup table of colors.
+///
+/// The colors are evenly spaced over the normalized range `[0, 1]`,
+/// values in between are linearly interpolated.
+/// Used instead of the built-in color maps for color mapped entities, e.g. depth images.
+///
+/// ```
+/// use re_log_types::component_types::ColorLut;
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field};
+///
+/// assert_eq!(
+///     ColorLut::data_type(),
+///     DataType::Struct(vec![Field::new(
+///         "colors",
+///         DataType::List(Box::new(Field::new("item", DataType::UInt32, false))),
+///         false
+///     )])
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, ArrowField, ArrowSerialize, ArrowDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ColorLut {
+    pub colors: Vec<ColorRGBA>,
+}
+
+impl Component for ColorLut {
+    #[inline]
+    fn name() -> crate::ComponentName {
+        "rerun.color_lut".into()
+    }
+}
+
+#[test]
+fn test_color_lut_roundtrip() {
+    use arrow2::array::Array;
+    use arrow2_convert::{deserialize::TryIntoCollection, serialize::TryIntoArrow};
+
+    let luts_in = vec![
+        ColorLut {
+            colors: vec![
+                ColorRGBA::from_rgb(0, 0, 255),
+                ColorRGBA::from_rgb(255, 255, 255),
+                ColorRGBA::from_rgb(255, 0, 0),
+            ],
+        },
+        ColorLut { colors: vec![] },
+    ];
+    let array: Box<dyn Array> = luts_in.try_into_arrow().unwrap();
+    let luts_out: Vec<ColorLut> = TryIntoCollection::try_into_collection(array).unwrap();
+    assert_eq!(luts_in, luts_out);
+}
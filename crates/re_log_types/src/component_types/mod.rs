@@ -20,6 +20,7 @@ mod arrow;
 mod bbox;
 mod class_id;
 mod color;
+mod color_lut;
 pub mod context;
 pub mod coordinates;
 mod instance_key;
@@ -39,11 +40,13 @@ mod tensor;
 mod text_entry;
 mod transform;
 mod vec;
+mod voxel_grid;
 
 pub use arrow::Arrow3D;
 pub use bbox::Box3D;
 pub use class_id::ClassId;
 pub use color::ColorRGBA;
+pub use color_lut::ColorLut;
 pub use context::{AnnotationContext, AnnotationInfo, ClassDescription};
 pub use coordinates::ViewCoordinates;
 pub use instance_key::InstanceKey;
@@ -62,17 +65,19 @@ pub use size::Size3D;
 pub use tensor::{
     Tensor, TensorCastError, TensorData, TensorDataMeaning, TensorDimension, TensorId, TensorTrait,
 };
-pub use text_entry::TextEntry;
+pub use text_entry::{TextEntry, TextEntryField};
 pub use transform::{Pinhole, Rigid3, Transform};
 pub use vec::{Vec2D, Vec3D, Vec4D};
+pub use voxel_grid::{SparseVoxelCells, VoxelCells, VoxelGrid};
 
 lazy_static! {
     //TODO(john): use a run-time type registry
-    static ref FIELDS: [Field; 26] = [
+    static ref FIELDS: [Field; 28] = [
         <AnnotationContext as Component>::field(),
         <Arrow3D as Component>::field(),
         <Box3D as Component>::field(),
         <ClassId as Component>::field(),
+        <ColorLut as Component>::field(),
         <ColorRGBA as Component>::field(),
         <InstanceKey as Component>::field(),
         <KeypointId as Component>::field(),
@@ -95,6 +100,7 @@ lazy_static! {
         <Vec2D as Component>::field(),
         <Vec3D as Component>::field(),
         <ViewCoordinates as Component>::field(),
+        <VoxelGrid as Component>::field(),
     ];
 }
 
@@ -12,6 +12,7 @@ pub trait TensorTrait {
     fn shape(&self) -> &[TensorDimension];
     fn num_dim(&self) -> usize;
     fn is_shaped_like_an_image(&self) -> bool;
+    fn is_shaped_like_a_volume(&self) -> bool;
     fn is_vector(&self) -> bool;
     fn meaning(&self) -> TensorDataMeaning;
     fn get(&self, index: &[u64]) -> Option<TensorElement>;
@@ -353,6 +354,14 @@ impl TensorTrait for Tensor {
             }
     }
 
+    /// Rank 3 tensors that aren't images, e.g. a CT scan or an occupancy grid.
+    fn is_shaped_like_a_volume(&self) -> bool {
+        self.num_dim() == 3
+            && !self.is_shaped_like_an_image()
+            && !matches!(self.data, TensorData::JPEG(_))
+            && self.shape.iter().all(|dim| dim.size > 1)
+    }
+
     #[inline]
     fn is_vector(&self) -> bool {
         let shape = &self.shape;
@@ -728,6 +737,26 @@ fn test_arrow() {
     assert_eq!(tensors_in, tensors_out);
 }
 
+#[test]
+fn test_is_shaped_like_a_volume() {
+    let tensor = |shape: &[u64]| Tensor {
+        tensor_id: TensorId::random(),
+        shape: shape
+            .iter()
+            .map(|&size| TensorDimension { size, name: None })
+            .collect(),
+        data: TensorData::U8(vec![0; shape.iter().product::<u64>() as usize]),
+        meaning: TensorDataMeaning::Unknown,
+        meter: None,
+    };
+
+    assert!(tensor(&[16, 16, 16]).is_shaped_like_a_volume());
+    assert!(!tensor(&[16, 16, 3]).is_shaped_like_a_volume());
+    assert!(!tensor(&[16, 16]).is_shaped_like_a_volume());
+    assert!(!tensor(&[1, 16, 16]).is_shaped_like_a_volume());
+    assert!(!tensor(&[16, 16, 16, 16]).is_shaped_like_a_volume());
+}
+
 #[test]
 fn test_concat_and_slice() {
     use crate::msg_bundle::wrap_in_listarray;
@@ -1,8 +1,30 @@
-use arrow2_convert::{ArrowDeserialize, ArrowField, ArrowSerialize};
+use arrow2::{
+    array::{new_null_array, Array, ListArray, StructArray},
+    datatypes::{DataType, Field},
+};
+use arrow2_convert::{field::ArrowField as _, ArrowDeserialize, ArrowField, ArrowSerialize};
 
 use crate::msg_bundle::Component;
 
-/// A text entry component, comprised of a text body and its log level.
+/// A structured key/value pair attached to a [`TextEntry`], e.g. a field of a `tracing` event.
+#[derive(Clone, Debug, ArrowField, ArrowSerialize, ArrowDeserialize, PartialEq, Eq)]
+pub struct TextEntryField {
+    pub key: String,
+    pub value: String,
+}
+
+impl TextEntryField {
+    /// Returns the value of the field with the given key among `fields`, if any.
+    pub fn find<'a>(fields: &'a [Self], key: &str) -> Option<&'a str> {
+        fields
+            .iter()
+            .find(|field| field.key == key)
+            .map(|field| field.value.as_str())
+    }
+}
+
+/// A text entry component, comprised of a text body, its log level and any number of
+/// structured key/value fields.
 ///
 /// ```
 /// use re_log_types::component_types::TextEntry;
@@ -14,6 +36,18 @@ use crate::msg_bundle::Component;
 ///     DataType::Struct(vec![
 ///         Field::new("body", DataType::Utf8, false),
 ///         Field::new("level", DataType::Utf8, true),
+///         Field::new(
+///             "fields",
+///             DataType::List(Box::new(Field::new(
+///                 "item",
+///                 DataType::Struct(vec![
+///                     Field::new("key", DataType::Utf8, false),
+///                     Field::new("value", DataType::Utf8, false),
+///                 ]),
+///                 false,
+///             ))),
+///             true,
+///         ),
 ///     ])
 /// );
 /// ```
@@ -21,6 +55,12 @@ use crate::msg_bundle::Component;
 pub struct TextEntry {
     pub body: String,
     pub level: Option<String>,
+
+    /// Structured key/value pairs, shown as extra columns in the text view.
+    ///
+    /// Nullable, so that entries logged before structured fields existed can still be read,
+    /// see [`Self::upgrade_legacy_rows`].
+    pub fields: Option<Vec<TextEntryField>>,
 }
 
 impl TextEntry {
@@ -29,6 +69,7 @@ impl TextEntry {
         Self {
             body: body.into(),
             level,
+            fields: None,
         }
     }
 
@@ -37,7 +78,58 @@ impl TextEntry {
         Self {
             body: body.into(),
             level: None,
+            fields: None,
+        }
+    }
+
+    /// Attaches a structured key/value field to this entry.
+    #[inline]
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields
+            .get_or_insert_with(Vec::new)
+            .push(TextEntryField {
+                key: key.into(),
+                value: value.into(),
+            });
+        self
+    }
+
+    /// Returns the value of the field with the given key, if any.
+    pub fn field(&self, key: &str) -> Option<&str> {
+        TextEntryField::find(self.fields.as_deref().unwrap_or_default(), key)
+    }
+
+    /// Upgrades rows of text entries logged before structured fields existed, i.e. with only a
+    /// `body` and a `level`, to the current datatype, without any fields.
+    ///
+    /// Returns `None` if `rows` don't hold such legacy text entries.
+    pub fn upgrade_legacy_rows(rows: &ListArray<i32>) -> Option<ListArray<i32>> {
+        let legacy_data_type = DataType::Struct(vec![
+            Field::new("body", DataType::Utf8, false),
+            Field::new("level", DataType::Utf8, true),
+        ]);
+        if rows.values().data_type() != &legacy_data_type {
+            return None;
         }
+
+        let entries = rows.values().as_any().downcast_ref::<StructArray>()?;
+        let [body, level] = entries.values() else { return None; };
+        let fields = new_null_array(
+            <Vec<TextEntryField> as arrow2_convert::field::ArrowField>::data_type(),
+            entries.len(),
+        );
+        let entries = StructArray::new(
+            Self::data_type(),
+            vec![body.clone(), level.clone(), fields],
+            entries.validity().cloned(),
+        );
+
+        Some(ListArray::new(
+            ListArray::<i32>::default_datatype(Self::data_type()),
+            rows.offsets().clone(),
+            entries.boxed(),
+            rows.validity().cloned(),
+        ))
     }
 }
 
@@ -47,3 +139,58 @@ impl Component for TextEntry {
         "rerun.text_entry".into()
     }
 }
+
+#[test]
+fn test_text_entry_roundtrip() {
+    use arrow2_convert::{deserialize::TryIntoCollection, serialize::TryIntoArrow};
+
+    let entries_in = vec![
+        TextEntry::new("hello", Some("INFO".into())),
+        TextEntry::from_body("world")
+            .with_field("robot", "r2d2")
+            .with_field("attempt", "3"),
+    ];
+    let array: Box<dyn Array> = entries_in.try_into_arrow().unwrap();
+    let entries_out: Vec<TextEntry> = TryIntoCollection::try_into_collection(array).unwrap();
+    assert_eq!(entries_in, entries_out);
+    assert_eq!(entries_out[1].field("attempt"), Some("3"));
+}
+
+#[test]
+fn test_text_entry_upgrade_legacy_rows() {
+    use arrow2_convert::{deserialize::TryIntoCollection, serialize::TryIntoArrow};
+
+    /// A [`TextEntry`] as it was logged before structured fields existed.
+    #[derive(ArrowField, ArrowSerialize)]
+    struct LegacyTextEntry {
+        body: String,
+        level: Option<String>,
+    }
+
+    let legacy_entries = vec![
+        LegacyTextEntry {
+            body: "hello".into(),
+            level: Some("INFO".into()),
+        },
+        LegacyTextEntry {
+            body: "world".into(),
+            level: None,
+        },
+    ];
+    let array: Box<dyn Array> = legacy_entries.try_into_arrow().unwrap();
+    let rows = crate::msg_bundle::wrap_in_listarray(array);
+
+    let upgraded = TextEntry::upgrade_legacy_rows(&rows).unwrap();
+    let entries_out: Vec<TextEntry> =
+        TryIntoCollection::try_into_collection(upgraded.values().clone()).unwrap();
+    assert_eq!(
+        vec![
+            TextEntry::new("hello", Some("INFO".into())),
+            TextEntry::from_body("world"),
+        ],
+        entries_out
+    );
+
+    // Current rows are left alone.
+    assert!(TextEntry::upgrade_legacy_rows(&upgraded).is_none());
+}
@@ -0,0 +1,213 @@
+use arrow2_convert::{ArrowDeserialize, ArrowField, ArrowSerialize};
+
+use crate::msg_bundle::Component;
+
+use super::{FixedSizeArrayField, Vec3D};
+
+/// Cells of a [`VoxelGrid`] that are only given where they are occupied.
+///
+/// ```
+/// use re_log_types::component_types::SparseVoxelCells;
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field};
+///
+/// assert_eq!(
+///     SparseVoxelCells::data_type(),
+///     DataType::Struct(vec![
+///         Field::new("indices", DataType::List(Box::new(Field::new("item", DataType::UInt32, false))), false),
+///         Field::new("values", DataType::List(Box::new(Field::new("item", DataType::Float32, false))), false),
+///     ])
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, ArrowField, ArrowSerialize, ArrowDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SparseVoxelCells {
+    /// Linear index of each occupied cell, see [`VoxelGrid::linear_index`].
+    pub indices: Vec<u32>,
+
+    /// One value per index.
+    pub values: Vec<f32>,
+}
+
+/// The cells of a [`VoxelGrid`].
+///
+/// ```
+/// use re_log_types::component_types::{SparseVoxelCells, VoxelCells};
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field, UnionMode};
+///
+/// assert_eq!(
+///     VoxelCells::data_type(),
+///     DataType::Union(vec![
+///         Field::new("Dense", DataType::List(Box::new(Field::new("item", DataType::Float32, false))), false),
+///         Field::new("Sparse", SparseVoxelCells::data_type(), false),
+///     ], None, UnionMode::Dense)
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, ArrowField, ArrowSerialize, ArrowDeserialize)]
+#[arrow_field(type = "dense")]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum VoxelCells {
+    /// One value per cell, x varying fastest, then y, then z.
+    ///
+    /// `NaN` marks an empty cell.
+    Dense(Vec<f32>),
+
+    /// Only the occupied cells, everything else is empty.
+    Sparse(SparseVoxelCells),
+}
+
+/// A regular grid of axis aligned cells with a value each, e.g. an occupancy map or a costmap.
+///
+/// 2D grids, like costmaps, have a single layer along z.
+///
+/// ```
+/// use re_log_types::component_types::{VoxelCells, VoxelGrid};
+/// use arrow2_convert::field::ArrowField;
+/// use arrow2::datatypes::{DataType, Field};
+///
+/// assert_eq!(
+///     VoxelGrid::data_type(),
+///     DataType::Struct(vec![
+///         Field::new("origin", DataType::FixedSizeList(Box::new(Field::new("item", DataType::Float32, false)), 3), false),
+///         Field::new("voxel_size", DataType::Float32, false),
+///         Field::new("dimensions", DataType::FixedSizeList(Box::new(Field::new("item", DataType::UInt32, false)), 3), false),
+///         Field::new("cells", VoxelCells::data_type(), false),
+///     ])
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, ArrowField, ArrowSerialize, ArrowDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct VoxelGrid {
+    /// The corner of the first cell, i.e. the minimum of the grid's bounding box.
+    pub origin: Vec3D,
+
+    /// Edge length of every cell.
+    pub voxel_size: f32,
+
+    /// Number of cells along x, y and z.
+    #[arrow_field(type = "FixedSizeArrayField<u32,3>")]
+    pub dimensions: [u32; 3],
+
+    pub cells: VoxelCells,
+}
+
+impl VoxelGrid {
+    /// Total number of cells, occupied or not.
+    #[inline]
+    pub fn num_cells(&self) -> u64 {
+        let [x, y, z] = self.dimensions;
+        x as u64 * y as u64 * z as u64
+    }
+
+    /// Index of the cell at `[x, y, z]` in [`VoxelCells::Dense`], x varying fastest.
+    #[inline]
+    pub fn linear_index(&self, [x, y, z]: [u32; 3]) -> u64 {
+        let [size_x, size_y, _] = self.dimensions;
+        x as u64 + size_x as u64 * (y as u64 + size_y as u64 * z as u64)
+    }
+
+    /// Inverse of [`Self::linear_index`].
+    #[inline]
+    pub fn cell_coordinates(&self, index: u64) -> [u32; 3] {
+        let [size_x, size_y, _] = self.dimensions;
+        let (size_x, size_y) = (size_x as u64, size_y as u64);
+        [
+            (index % size_x) as u32,
+            (index / size_x % size_y) as u32,
+            (index / (size_x * size_y)) as u32,
+        ]
+    }
+
+    /// Iterates over all occupied cells as their coordinates and value.
+    ///
+    /// Cells that are `NaN` or out of bounds are skipped.
+    pub fn iter_occupied(&self) -> impl Iterator<Item = ([u32; 3], f32)> + '_ {
+        let num_cells = self.num_cells();
+        let occupied: Box<dyn Iterator<Item = (u64, f32)> + '_> = match &self.cells {
+            VoxelCells::Dense(values) => Box::new(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| (index as u64, *value)),
+            ),
+            VoxelCells::Sparse(cells) => Box::new(
+                cells
+                    .indices
+                    .iter()
+                    .zip(&cells.values)
+                    .map(|(index, value)| (*index as u64, *value)),
+            ),
+        };
+        occupied
+            .filter(move |(index, value)| *index < num_cells && !value.is_nan())
+            .map(|(index, value)| (self.cell_coordinates(index), value))
+    }
+}
+
+impl Component for VoxelGrid {
+    #[inline]
+    fn name() -> crate::ComponentName {
+        "rerun.voxel_grid".into()
+    }
+}
+
+#[test]
+fn test_voxel_grid_roundtrip() {
+    use arrow2::array::Array;
+    use arrow2_convert::{deserialize::TryIntoCollection, serialize::TryIntoArrow};
+
+    let grids_in = vec![
+        VoxelGrid {
+            origin: Vec3D([-1.0, -1.0, 0.0]),
+            voxel_size: 0.5,
+            dimensions: [2, 2, 1],
+            cells: VoxelCells::Dense(vec![0.0, 0.25, f32::NAN, 1.0]),
+        },
+        VoxelGrid {
+            origin: Vec3D([0.0, 0.0, 0.0]),
+            voxel_size: 0.1,
+            dimensions: [100, 100, 10],
+            cells: VoxelCells::Sparse(SparseVoxelCells {
+                indices: vec![0, 42, 99_999],
+                values: vec![1.0, 1.0, 0.5],
+            }),
+        },
+    ];
+    let array: Box<dyn Array> = grids_in.try_into_arrow().unwrap();
+    let grids_out: Vec<VoxelGrid> = TryIntoCollection::try_into_collection(array).unwrap();
+
+    // `NaN != NaN`, so compare the occupied cells instead.
+    assert_eq!(grids_in.len(), grids_out.len());
+    for (grid_in, grid_out) in grids_in.iter().zip(&grids_out) {
+        assert_eq!(grid_in.origin, grid_out.origin);
+        assert_eq!(grid_in.dimensions, grid_out.dimensions);
+        assert_eq!(
+            grid_in.iter_occupied().collect::<Vec<_>>(),
+            grid_out.iter_occupied().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn test_voxel_grid_iter_occupied() {
+    let grid = VoxelGrid {
+        origin: Vec3D([0.0, 0.0, 0.0]),
+        voxel_size: 1.0,
+        dimensions: [3, 2, 2],
+        cells: VoxelCells::Sparse(SparseVoxelCells {
+            indices: vec![0, 4, 11, 12],
+            values: vec![1.0, 2.0, f32::NAN, 4.0],
+        }),
+    };
+    // Index 12 is out of bounds and 11 is empty.
+    assert_eq!(
+        grid.iter_occupied().collect::<Vec<_>>(),
+        vec![([0, 0, 0], 1.0), ([1, 1, 0], 2.0)]
+    );
+    assert_eq!(grid.linear_index([1, 1, 0]), 4);
+    assert_eq!(
+        grid.cell_coordinates(grid.linear_index([2, 1, 1])),
+        [2, 1, 1]
+    );
+}
@@ -235,6 +235,14 @@ fn test_encode_decode() {
             recording_source: crate::RecordingSource::RustSdk {
                 rust_version: env!("CARGO_PKG_RUST_VERSION").into(),
             },
+            properties: [("robot_id".to_owned(), "42".to_owned())].into(),
+            timeline_units: [(
+                crate::TimelineName::from("sim_tick"),
+                crate::TimeUnits::Ticks {
+                    ticks_per_second: 60,
+                },
+            )]
+            .into(),
         },
     })];
 
@@ -47,7 +47,7 @@ pub use self::data::*;
 pub use self::index::*;
 pub use self::path::*;
 pub use self::time::{Duration, Time};
-pub use self::time_point::{TimeInt, TimePoint, TimeType, Timeline, TimelineName};
+pub use self::time_point::{TimeInt, TimePoint, TimeType, TimeUnits, Timeline, TimelineName};
 pub use self::time_range::{TimeRange, TimeRangeF};
 pub use self::time_real::TimeReal;
 
@@ -221,6 +221,16 @@ pub struct RecordingInfo {
     pub started: Time,
 
     pub recording_source: RecordingSource,
+
+    /// Arbitrary user-defined properties, e.g. a git hash, a robot id or a scenario name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub properties: std::collections::BTreeMap<String, String>,
+
+    /// How to interpret and display the values of each timeline.
+    ///
+    /// Timelines missing from here use [`TimeUnits::default_for`] their [`TimeType`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timeline_units: std::collections::BTreeMap<TimelineName, TimeUnits>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -291,8 +301,10 @@ pub struct EntityPathOpMsg {
 
     /// Time information (when it was logged, when it was received, …).
     ///
-    /// If this is empty, no operation will be performed as we
+    /// If this is empty, no clear will be performed as we
     /// cannot be timeless in a meaningful way.
+    /// Operations that rewrite history (see [`PathOp::rewrites_history`]) apply at all
+    /// times, and are usually timeless.
     pub time_point: TimePoint,
 
     /// What operation.
@@ -308,6 +320,26 @@ pub enum PathOp {
 
     /// Clear all the components of an `[EntityPath]` and any descendants.
     ClearRecursive(EntityPath),
+
+    /// Permanently drop all the data of an [`EntityPath`] and any descendants, at all times.
+    ///
+    /// Unlike [`Self::ClearRecursive`], this rewrites history: the data is gone from the
+    /// past too.
+    DropRecursive(EntityPath),
+
+    /// Move all the data of an [`EntityPath`] and any descendants to another path, at all
+    /// times, e.g. to rename or reparent it.
+    ///
+    /// `from/a/b` ends up at `to/a/b`. Fails if there is already data at the destination.
+    Rename { from: EntityPath, to: EntityPath },
+
+    /// Permanently drop all the data of an [`EntityPath`] and any descendants on one timeline.
+    ///
+    /// Use [`EntityPath::root`] to drop the whole timeline.
+    DropTimeline {
+        entity_path: EntityPath,
+        timeline: Timeline,
+    },
 }
 
 impl PathOp {
@@ -321,7 +353,21 @@ impl PathOp {
 
     pub fn entity_path(&self) -> &EntityPath {
         match &self {
-            PathOp::ClearComponents(path) | PathOp::ClearRecursive(path) => path,
+            PathOp::ClearComponents(path)
+            | PathOp::ClearRecursive(path)
+            | PathOp::DropRecursive(path)
+            | PathOp::Rename { from: path, .. }
+            | PathOp::DropTimeline {
+                entity_path: path, ..
+            } => path,
+        }
+    }
+
+    /// Does this rewrite the data that was logged so far, rather than add to it?
+    pub fn rewrites_history(&self) -> bool {
+        match self {
+            PathOp::ClearComponents(_) | PathOp::ClearRecursive(_) => false,
+            PathOp::DropRecursive(_) | PathOp::Rename { .. } | PathOp::DropTimeline { .. } => true,
         }
     }
 }
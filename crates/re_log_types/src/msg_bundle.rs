@@ -50,8 +50,17 @@ pub enum MsgBundleError {
     #[error("Expected component values to be `StructArray`s")]
     BadComponentValues,
 
-    #[error("Expect a single TimePoint, but found more than one")]
-    MultipleTimepoints,
+    #[error("Expected one TimePoint per row, got {num_time_points} for {num_rows} rows")]
+    MismatchedTimePoints {
+        num_rows: usize,
+        num_time_points: usize,
+    },
+
+    #[error("Cannot concatenate an empty list of bundles")]
+    EmptyBatch,
+
+    #[error("Only bundles for the same entity with the same components can be concatenated")]
+    IncompatibleBundles,
 
     #[error(transparent)]
     PathParseError(#[from] PathParseError),
@@ -67,7 +76,8 @@ pub enum MsgBundleError {
 pub type Result<T> = std::result::Result<T, MsgBundleError>;
 
 use crate::{
-    parse_entity_path, ArrowMsg, ComponentName, EntityPath, MsgId, PathParseError, TimePoint,
+    component_types::TextEntry, parse_entity_path, ArrowMsg, ComponentName, EntityPath, MsgId,
+    PathParseError, TimePoint,
 };
 
 //TODO(john) get rid of this eventually
@@ -201,8 +211,6 @@ impl ComponentBundle {
     }
 
     /// Returns the number of _rows_ in this bundle, i.e. the length of the bundle.
-    ///
-    /// Currently always 1 as we don't yet support batch insertions.
     #[inline]
     pub fn num_rows(&self) -> usize {
         self.value.len()
@@ -260,6 +268,9 @@ impl<C: SerializableComponent> TryFrom<&Vec<C>> for ComponentBundle {
 
 /// A `MsgBundle` holds data necessary for composing a single log message.
 ///
+/// A bundle can hold several rows for the same entity, each with its own [`TimePoint`],
+/// see [`MsgBundle::try_concat`].
+///
 /// # Example
 ///
 /// Create a `MsgBundle` and add a component consisting of 2 [`crate::component_types::Rect2D`] values:
@@ -309,12 +320,15 @@ pub struct MsgBundle {
     /// A unique id per [`crate::LogMsg`].
     pub msg_id: MsgId,
     pub entity_path: EntityPath,
-    pub time_point: TimePoint,
+
+    /// One [`TimePoint`] per row.
+    pub time_points: Vec<TimePoint>,
+
     pub components: Vec<ComponentBundle>,
 }
 
 impl MsgBundle {
-    /// Create a new `MsgBundle` with a pre-built Vec of [`ComponentBundle`] components.
+    /// Create a new single-row `MsgBundle` with a pre-built Vec of [`ComponentBundle`] components.
     ///
     /// The `MsgId` will automatically be appended as a component to the given `bundles`, allowing
     /// the backend to keep track of the origin of any row of data.
@@ -327,7 +341,7 @@ impl MsgBundle {
         let mut this = Self {
             msg_id,
             entity_path,
-            time_point,
+            time_points: vec![time_point],
             components,
         };
 
@@ -341,6 +355,62 @@ impl MsgBundle {
         this
     }
 
+    /// Concatenates the rows of several bundles into a single multi-row bundle.
+    ///
+    /// All bundles must be for the same entity and carry the same set of components (in any
+    /// order). The resulting bundle uses the `MsgId` of the first one; the per-row `MsgId`
+    /// components are kept as-is.
+    pub fn try_concat(bundles: &[MsgBundle]) -> Result<Self> {
+        let [first, rest @ ..] = bundles else {
+            return Err(MsgBundleError::EmptyBatch);
+        };
+        if rest.is_empty() {
+            return Ok(first.clone());
+        }
+
+        let is_compatible = |bundle: &MsgBundle| {
+            bundle.entity_path == first.entity_path
+                && bundle.components.len() == first.components.len()
+                && bundle
+                    .components
+                    .iter()
+                    .all(|component| first.find_component(&component.name).is_some())
+        };
+        if !rest.iter().all(is_compatible) {
+            return Err(MsgBundleError::IncompatibleBundles);
+        }
+
+        let components = first
+            .components
+            .iter()
+            .map(|component| {
+                let values = bundles
+                    .iter()
+                    .map(|bundle| {
+                        // Presence was checked above.
+                        let idx = bundle.find_component(&component.name).unwrap();
+                        &bundle.components[idx].value as &dyn Array
+                    })
+                    .collect::<Vec<_>>();
+                let value = arrow2::compute::concatenate::concatenate(&values)?;
+                Ok(ComponentBundle::new_from_boxed(
+                    component.name,
+                    value.as_ref(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            msg_id: first.msg_id,
+            entity_path: first.entity_path.clone(),
+            time_points: bundles
+                .iter()
+                .flat_map(|bundle| bundle.time_points.iter().cloned())
+                .collect(),
+            components,
+        })
+    }
+
     /// Try to append a collection of `Component` onto the `MessageBundle`.
     ///
     /// This first converts the component collection into an Arrow array, and then wraps it in a [`ListArray`].
@@ -371,9 +441,8 @@ impl MsgBundle {
     /// Returns the number of _rows_ for each component collections in this bundle, i.e. the
     /// length of each component collections.
     ///
-    /// All component collections within a `MsgBundle` must share the same number of rows!
-    ///
-    /// Currently always 1 as we don't yet support batch insertions.
+    /// All component collections within a `MsgBundle` must share the same number of rows, which
+    /// must match the number of [`TimePoint`]s.
     #[inline]
     pub fn num_rows(&self) -> usize {
         self.components
@@ -384,9 +453,8 @@ impl MsgBundle {
     /// Returns the number of _instances_ for a given `row` in the bundle, i.e. the length of a
     /// specific row within the bundle.
     ///
-    /// Since we don't yet support batch insertions and all components within a single row must
-    /// have the same number of instances, we simply pick the value for the first component
-    /// collection.
+    /// All components within a single row must have the same number of instances, so we simply
+    /// pick the value for the first component collection.
     #[inline]
     pub fn num_instances(&self, row: usize) -> Option<usize> {
         self.components
@@ -413,7 +481,7 @@ impl std::fmt::Display for MsgBundle {
         let table = re_format::arrow::format_table(values, names);
         f.write_fmt(format_args!(
             "MsgBundle '{}' @ {:?}:\n{table}",
-            self.entity_path, self.time_point
+            self.entity_path, self.time_points
         ))
     }
 }
@@ -461,21 +529,14 @@ impl TryFrom<&ArrowMsg> for MsgBundle {
             chunk,
         } = msg;
 
-        let entity_path_cmp = schema
-            .metadata
-            .get(ENTITY_PATH_KEY)
-            .ok_or(MsgBundleError::MissingEntityPath)
-            .and_then(|path| {
-                parse_entity_path(path.as_str()).map_err(MsgBundleError::PathParseError)
-            })?;
-
-        let time_point = extract_timelines(schema, chunk)?;
+        let entity_path = extract_entity_path(schema)?;
+        let time_points = extract_timelines(schema, chunk)?;
         let components = extract_components(schema, chunk)?;
 
         Ok(Self {
             msg_id: *msg_id,
-            entity_path: entity_path_cmp.into(),
-            time_point,
+            entity_path,
+            time_points,
             components,
         })
     }
@@ -487,6 +548,13 @@ impl TryFrom<MsgBundle> for ArrowMsg {
     /// Build a single Arrow log message tuple from this `MsgBundle`. See the documentation on
     /// [`MsgBundle`] for details.
     fn try_from(bundle: MsgBundle) -> Result<Self> {
+        if !bundle.components.is_empty() && bundle.num_rows() != bundle.time_points.len() {
+            return Err(MsgBundleError::MismatchedTimePoints {
+                num_rows: bundle.num_rows(),
+                num_time_points: bundle.time_points.len(),
+            });
+        }
+
         let mut schema = Schema::default();
         let mut cols: Vec<Box<dyn Array>> = Vec::new();
 
@@ -495,7 +563,7 @@ impl TryFrom<MsgBundle> for ArrowMsg {
 
         // Build & pack timelines
         let timelines_field = Field::new(COL_TIMELINES, TimePoint::data_type(), false);
-        let timelines_col = [bundle.time_point].try_into_arrow()?;
+        let timelines_col = bundle.time_points.try_into_arrow()?;
 
         schema.fields.push(timelines_field);
         cols.push(timelines_col);
@@ -515,10 +583,20 @@ impl TryFrom<MsgBundle> for ArrowMsg {
     }
 }
 
-/// Extract a [`TimePoint`] from the "timelines" column. This function finds the "timelines" field
-/// in `chunk` and deserializes the values into a `TimePoint` using the
+/// Extract the [`EntityPath`] from the metadata of an [`ArrowMsg`]'s schema.
+pub fn extract_entity_path(schema: &Schema) -> Result<EntityPath> {
+    schema
+        .metadata
+        .get(ENTITY_PATH_KEY)
+        .ok_or(MsgBundleError::MissingEntityPath)
+        .and_then(|path| parse_entity_path(path.as_str()).map_err(MsgBundleError::PathParseError))
+        .map(Into::into)
+}
+
+/// Extract one [`TimePoint`] per row from the "timelines" column. This function finds the
+/// "timelines" field in `chunk` and deserializes the values into `TimePoint`s using the
 /// [`arrow2_convert::deserialize::ArrowDeserialize`] trait.
-pub fn extract_timelines(schema: &Schema, chunk: &Chunk<Box<dyn Array>>) -> Result<TimePoint> {
+pub fn extract_timelines(schema: &Schema, chunk: &Chunk<Box<dyn Array>>) -> Result<Vec<TimePoint>> {
     use arrow2_convert::deserialize::arrow_array_deserialize_iterator;
 
     let timelines = schema
@@ -528,19 +606,14 @@ pub fn extract_timelines(schema: &Schema, chunk: &Chunk<Box<dyn Array>>) -> Resu
         .and_then(|idx| chunk.columns().get(idx))
         .ok_or(MsgBundleError::MissingTimelinesField)?;
 
-    let mut timepoints_iter = arrow_array_deserialize_iterator::<TimePoint>(timelines.as_ref())?;
-
-    // We take only the first result of the iterator because at this time we only support *single*
-    // row messages. At some point in the future we can support batching with this.
-    let timepoint = timepoints_iter
-        .next()
-        .ok_or(MsgBundleError::NoRowsInTimeline)?;
+    let time_points: Vec<TimePoint> =
+        arrow_array_deserialize_iterator::<TimePoint>(timelines.as_ref())?.collect();
 
-    if timepoints_iter.next().is_some() {
-        return Err(MsgBundleError::MultipleTimepoints);
+    if time_points.is_empty() {
+        return Err(MsgBundleError::NoRowsInTimeline);
     }
 
-    Ok(timepoint)
+    Ok(time_points)
 }
 
 /// Extract a vector of `ComponentBundle` from the message. This is necessary since the
@@ -566,10 +639,16 @@ fn extract_components(
         .iter()
         .zip(components.values())
         .map(|(field, component)| {
-            ComponentBundle::new_from_boxed(
+            let mut bundle = ComponentBundle::new_from_boxed(
                 ComponentName::from(field.name.as_str()),
                 component.as_ref(),
-            )
+            );
+            if bundle.name == TextEntry::name() {
+                if let Some(value) = TextEntry::upgrade_legacy_rows(&bundle.value) {
+                    bundle.value = value;
+                }
+            }
+            bundle
         })
         .collect())
 }
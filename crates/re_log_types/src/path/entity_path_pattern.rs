@@ -0,0 +1,198 @@
+use crate::path::{parse_entity_path, PathParseError};
+use crate::{EntityPath, EntityPathPart};
+
+/// One part of an [`EntityPathPattern`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum EntityPathPatternPart {
+    /// Matches exactly this part.
+    Exact(EntityPathPart),
+
+    /// `*`: matches any single part.
+    AnyPart,
+
+    /// `**`: matches any number of parts, including none.
+    AnyDepth,
+}
+
+impl std::fmt::Display for EntityPathPatternPart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(part) => part.fmt(f),
+            Self::AnyPart => f.write_str("*"),
+            Self::AnyDepth => f.write_str("**"),
+        }
+    }
+}
+
+/// A glob-like pattern matching any number of [`EntityPath`]s, e.g. `robot/*/camera/**`.
+///
+/// * `*` matches any single part, e.g. `robot/*/camera` matches `robot/42/camera`.
+/// * `**` matches any number of parts, including none, e.g. `robot/**` matches `robot`,
+///   `robot/42` and `robot/42/camera`.
+///
+/// A leading slash is allowed, so `/robot/*` is the same as `robot/*`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EntityPathPattern {
+    parts: Vec<EntityPathPatternPart>,
+}
+
+impl EntityPathPattern {
+    /// Parses a pattern, e.g. `robot/*/camera/**`.
+    pub fn parse(pattern: &str) -> Result<Self, PathParseError> {
+        let pattern = match pattern.strip_prefix('/') {
+            Some("") | None => pattern,
+            Some(pattern) => pattern,
+        };
+
+        let parts = parse_entity_path(pattern)?
+            .into_iter()
+            .map(|part| match &part {
+                EntityPathPart::Name(name) if name.as_str() == "*" => {
+                    EntityPathPatternPart::AnyPart
+                }
+                EntityPathPart::Name(name) if name.as_str() == "**" => {
+                    EntityPathPatternPart::AnyDepth
+                }
+                _ => EntityPathPatternPart::Exact(part),
+            })
+            .collect();
+
+        Ok(Self { parts })
+    }
+
+    /// Matches exactly this one path.
+    pub fn exact(path: &EntityPath) -> Self {
+        Self {
+            parts: path
+                .iter()
+                .cloned()
+                .map(EntityPathPatternPart::Exact)
+                .collect(),
+        }
+    }
+
+    /// Matches this path and all its descendants, i.e. `path/**`.
+    pub fn subtree(path: &EntityPath) -> Self {
+        let mut pattern = Self::exact(path);
+        pattern.parts.push(EntityPathPatternPart::AnyDepth);
+        pattern
+    }
+
+    pub fn parts(&self) -> &[EntityPathPatternPart] {
+        &self.parts
+    }
+
+    /// Does the pattern match this path?
+    pub fn matches(&self, path: &EntityPath) -> bool {
+        matches_parts(&self.parts, path.as_slice())
+    }
+
+    /// Could the pattern match this path, or any of its descendants?
+    ///
+    /// Useful to prune the search when walking a tree of entities.
+    pub fn may_match_subtree(&self, path: &EntityPath) -> bool {
+        may_match_subtree_parts(&self.parts, path.as_slice())
+    }
+}
+
+fn matches_parts(pattern: &[EntityPathPatternPart], path: &[EntityPathPart]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((EntityPathPatternPart::AnyDepth, rest)) => {
+            (0..=path.len()).any(|skip| matches_parts(rest, &path[skip..]))
+        }
+        Some((EntityPathPatternPart::AnyPart, rest)) => {
+            !path.is_empty() && matches_parts(rest, &path[1..])
+        }
+        Some((EntityPathPatternPart::Exact(part), rest)) => {
+            path.first() == Some(part) && matches_parts(rest, &path[1..])
+        }
+    }
+}
+
+fn may_match_subtree_parts(pattern: &[EntityPathPatternPart], path: &[EntityPathPart]) -> bool {
+    let Some((first, rest)) = path.split_first() else {
+        return true; // the remaining pattern can be matched by ourselves or our descendants
+    };
+    match pattern.split_first() {
+        None => false,
+        Some((EntityPathPatternPart::AnyDepth, _)) => true,
+        Some((EntityPathPatternPart::AnyPart, pattern_rest)) => {
+            may_match_subtree_parts(pattern_rest, rest)
+        }
+        Some((EntityPathPatternPart::Exact(part), pattern_rest)) => {
+            part == first && may_match_subtree_parts(pattern_rest, rest)
+        }
+    }
+}
+
+impl std::str::FromStr for EntityPathPattern {
+    type Err = PathParseError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Self::parse(pattern)
+    }
+}
+
+impl std::fmt::Display for EntityPathPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.parts.is_empty() {
+            return f.write_str("/");
+        }
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            part.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        EntityPathPattern::parse(pattern)
+            .unwrap()
+            .matches(&EntityPath::from(path))
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        assert!(matches("/robot/*/camera/**", "robot/42/camera"));
+        assert!(matches("/robot/*/camera/**", "robot/42/camera/left/points"));
+        assert!(!matches("/robot/*/camera/**", "robot/camera"));
+        assert!(!matches("/robot/*/camera/**", "robot/42/lidar"));
+
+        assert!(matches("**/points", "points"));
+        assert!(matches("**/points", "world/robot/points"));
+        assert!(!matches("**/points", "world/robot/points/x"));
+
+        assert!(matches("**", "/"));
+        assert!(matches("/", "/"));
+        assert!(!matches("/", "world"));
+    }
+
+    #[test]
+    fn test_may_match_subtree() {
+        let pattern = EntityPathPattern::parse("robot/*/camera").unwrap();
+        assert!(pattern.may_match_subtree(&EntityPath::root()));
+        assert!(pattern.may_match_subtree(&EntityPath::from("robot/42")));
+        assert!(!pattern.may_match_subtree(&EntityPath::from("robot/42/lidar")));
+        assert!(!pattern.may_match_subtree(&EntityPath::from("robot/42/camera/left")));
+    }
+
+    #[test]
+    fn test_pattern_display() {
+        for pattern in ["robot/*/camera/**", "/", "**"] {
+            assert_eq!(
+                EntityPathPattern::parse(pattern).unwrap().to_string(),
+                pattern
+            );
+        }
+    }
+}
@@ -65,24 +65,41 @@ impl Time {
 
     /// Human-readable formatting
     pub fn format(&self) -> String {
+        self.format_with_utc_offset(0)
+    }
+
+    /// Human-readable formatting, in the timezone `utc_offset_minutes` away from UTC.
+    ///
+    /// Relative times are formatted the same way regardless of the offset.
+    pub fn format_with_utc_offset(&self, utc_offset_minutes: i32) -> String {
         let nanos_since_epoch = self.nanos_since_epoch();
 
         if let Some(datetime) = self.to_datetime() {
+            let offset = time::UtcOffset::from_whole_seconds(utc_offset_minutes.saturating_mul(60))
+                .unwrap_or(time::UtcOffset::UTC);
+            let datetime = datetime.to_offset(offset);
+            let zone = if offset.is_utc() {
+                "Z"
+            } else {
+                "[offset_hour sign:mandatory]:[offset_minute]"
+            };
+
             let is_whole_second = nanos_since_epoch % 1_000_000_000 == 0;
             let is_whole_millisecond = nanos_since_epoch % 1_000_000 == 0;
 
             let time_format = if is_whole_second {
-                "[hour]:[minute]:[second]Z"
+                format!("[hour]:[minute]:[second]{zone}")
             } else if is_whole_millisecond {
-                "[hour]:[minute]:[second].[subsecond digits:3]Z"
+                format!("[hour]:[minute]:[second].[subsecond digits:3]{zone}")
             } else {
-                "[hour]:[minute]:[second].[subsecond digits:6]Z"
+                format!("[hour]:[minute]:[second].[subsecond digits:6]{zone}")
             };
 
-            let date_is_today = datetime.date() == OffsetDateTime::now_utc().date();
+            let date_is_today =
+                datetime.date() == OffsetDateTime::now_utc().to_offset(offset).date();
             let date_format = format!("[year]-[month]-[day] {time_format}");
             let parsed_format = if date_is_today {
-                time::format_description::parse(time_format).unwrap()
+                time::format_description::parse(&time_format).unwrap()
             } else {
                 time::format_description::parse(&date_format).unwrap()
             };
@@ -184,6 +201,19 @@ mod tests {
         assert_eq!(&datetime.format(), "2022-02-28 22:35:42Z");
     }
 
+    #[test]
+    fn test_formatting_with_utc_offset() {
+        let datetime = Time::try_from(datetime!(2022-02-28 22:35:42 UTC)).unwrap();
+        assert_eq!(
+            &datetime.format_with_utc_offset(90),
+            "2022-03-01 00:05:42+01:30"
+        );
+        assert_eq!(
+            &datetime.format_with_utc_offset(-300),
+            "2022-02-28 17:35:42-05:00"
+        );
+    }
+
     #[test]
     fn test_formatting_whole_millisecond_for_datetime() {
         let datetime = Time::try_from(datetime!(2022-02-28 22:35:42.069 UTC)).unwrap();
@@ -2,12 +2,14 @@ use std::collections::{btree_map, BTreeMap};
 
 mod arrow;
 mod time_int;
+mod time_units;
 mod timeline;
 
 use crate::{time::Time, TimeRange};
 
 // Re-exports
 pub use time_int::TimeInt;
+pub use time_units::TimeUnits;
 pub use timeline::{Timeline, TimelineName};
 
 /// A point in time on any number of [`Timeline`]s.
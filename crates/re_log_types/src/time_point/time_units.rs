@@ -0,0 +1,121 @@
+use crate::{Duration, Time, TimeInt, TimeReal, TimeType};
+
+/// How the values of a [`crate::Timeline`] should be interpreted and displayed.
+///
+/// This is display metadata only: the values are always stored as [`TimeInt`]s,
+/// and the [`TimeType`] of the timeline is unaffected.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimeUnits {
+    /// Plain sequence numbers without any known rate, e.g. frame indices.
+    Sequence,
+
+    /// Discrete ticks at a fixed rate, e.g. the steps of a simulation.
+    ///
+    /// Shown as tick numbers, together with the elapsed time.
+    Ticks { ticks_per_second: u64 },
+
+    /// Time elapsed since some arbitrary origin, e.g. hardware timestamps in µs since boot
+    /// (`ticks_per_second: 1_000_000`).
+    ///
+    /// Shown as a duration.
+    Elapsed { ticks_per_second: u64 },
+
+    /// Nanoseconds since the Unix epoch, shown as a date and time in the given timezone.
+    WallClock { utc_offset_minutes: i32 },
+}
+
+impl TimeUnits {
+    /// The units we assume when none were logged for a timeline.
+    pub fn default_for(time_type: TimeType) -> Self {
+        match time_type {
+            TimeType::Time => Self::WallClock {
+                utc_offset_minutes: 0,
+            },
+            TimeType::Sequence => Self::Sequence,
+        }
+    }
+
+    /// Microseconds since boot, a common format for hardware timestamps.
+    pub fn micros_since_boot() -> Self {
+        Self::Elapsed {
+            ticks_per_second: 1_000_000,
+        }
+    }
+
+    /// How many [`TimeInt`] per second of real time, if known.
+    ///
+    /// Used to play back timelines in real time.
+    pub fn ticks_per_second(&self) -> Option<f64> {
+        match self {
+            Self::Sequence => None,
+            Self::Ticks { ticks_per_second } | Self::Elapsed { ticks_per_second } => {
+                (*ticks_per_second > 0).then_some(*ticks_per_second as f64)
+            }
+            Self::WallClock { .. } => Some(1e9),
+        }
+    }
+
+    /// Converts a number of ticks into a [`Duration`], if the rate is known.
+    pub fn ticks_to_duration(&self, ticks: f64) -> Option<Duration> {
+        self.ticks_per_second()
+            .map(|ticks_per_second| Duration::from_nanos((ticks * 1e9 / ticks_per_second) as i64))
+    }
+
+    pub fn format(&self, time_int: TimeInt) -> String {
+        if time_int <= TimeInt::BEGINNING {
+            "-∞".into()
+        } else if time_int >= TimeInt::MAX {
+            "+∞".into()
+        } else {
+            match self {
+                Self::Sequence => format!("#{}", time_int.as_i64()),
+                Self::Ticks { .. } => {
+                    let ticks = time_int.as_i64();
+                    match self.ticks_to_duration(ticks as f64) {
+                        Some(duration) => format!("#{ticks} ({duration})"),
+                        None => format!("#{ticks}"),
+                    }
+                }
+                Self::Elapsed { .. } => self
+                    .ticks_to_duration(time_int.as_i64() as f64)
+                    .map_or_else(|| format!("#{}", time_int.as_i64()), |d| d.to_string()),
+                Self::WallClock { utc_offset_minutes } => {
+                    Time::from(time_int).format_with_utc_offset(*utc_offset_minutes)
+                }
+            }
+        }
+    }
+
+    /// Formats the length of a time range.
+    pub fn format_duration(&self, duration: TimeReal) -> String {
+        match self {
+            Self::Sequence => duration.round().as_i64().to_string(), // TODO(emilk): show real part?
+            Self::Ticks { .. } => format!("{} ticks", duration.round().as_i64()),
+            Self::Elapsed { .. } | Self::WallClock { .. } => self
+                .ticks_to_duration(duration.as_f64())
+                .map_or_else(|| duration.round().as_i64().to_string(), |d| d.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_units() {
+        let ticks = TimeUnits::Ticks {
+            ticks_per_second: 60,
+        };
+        assert_eq!(ticks.format(TimeInt::from(90)), "#90 (1.500s)");
+        assert_eq!(ticks.format_duration(TimeReal::from(120)), "120 ticks");
+
+        let since_boot = TimeUnits::micros_since_boot();
+        assert_eq!(since_boot.format(TimeInt::from(2_500_000)), "2.500s");
+        assert_eq!(since_boot.ticks_per_second(), Some(1e6));
+
+        assert_eq!(TimeUnits::Sequence.format(TimeInt::from(42)), "#42");
+        assert_eq!(TimeUnits::Sequence.ticks_per_second(), None);
+    }
+}
@@ -1,7 +1,7 @@
 use std::{collections::BTreeMap, marker::PhantomData};
 
 use arrow2::array::{Array, MutableArray, PrimitiveArray};
-use re_arrow_store::ArrayExt;
+use re_arrow_store::{ArrayExt, RowIndex};
 use re_format::arrow;
 use re_log_types::{
     component_types::InstanceKey,
@@ -26,6 +26,9 @@ pub struct ComponentWithInstances {
     // TODO(jleibs): Remove optional once the store guarantees this will always exist
     pub(crate) instance_keys: Option<Box<dyn Array>>,
     pub(crate) values: Box<dyn Array>,
+
+    /// The row of the store this data was read from, `None` if it didn't come from a store.
+    pub(crate) row_index: Option<RowIndex>,
 }
 
 impl ComponentWithInstances {
@@ -33,6 +36,12 @@ impl ComponentWithInstances {
         self.name
     }
 
+    /// The row of the store this data was read from, `None` if it didn't come from a store.
+    #[inline]
+    pub fn row_index(&self) -> Option<RowIndex> {
+        self.row_index
+    }
+
     /// Number of values. 1 for splats.
     pub fn len(&self) -> usize {
         self.values.len()
@@ -151,6 +160,7 @@ impl ComponentWithInstances {
             name: C::name(),
             instance_keys,
             values,
+            row_index: None,
         })
     }
 }
@@ -286,6 +296,22 @@ where
     pub fn num_instances(&self) -> usize {
         self.primary.len()
     }
+
+    /// Store row of the primary component.
+    #[inline]
+    pub fn primary_row_index(&self) -> Option<RowIndex> {
+        self.primary.row_index
+    }
+
+    /// Store rows of the primary and all joined components.
+    ///
+    /// Views of the same entity with equal row indices contain the same data,
+    /// which makes them a good key for caching data derived from a view.
+    pub fn row_indices(&self) -> impl Iterator<Item = RowIndex> + '_ {
+        std::iter::once(&self.primary)
+            .chain(self.components.values())
+            .filter_map(|component| component.row_index)
+    }
 }
 
 impl<Primary: SerializableComponent + DeserializableComponent> EntityView<Primary>
@@ -319,6 +345,21 @@ where
             .map_or(false, |c| !c.is_empty())
     }
 
+    /// Look up the value of a component, the primary included, for a single instance.
+    pub fn lookup<C: DeserializableComponent>(&self, instance_key: &InstanceKey) -> crate::Result<C>
+    where
+        for<'b> &'b C::ArrayType: IntoIterator,
+    {
+        if C::name() == Primary::name() {
+            self.primary.lookup(instance_key)
+        } else {
+            self.components
+                .get(&C::name())
+                .ok_or(QueryError::ComponentNotFound)?
+                .lookup(instance_key)
+        }
+    }
+
     /// Iterate over the values of a `Component`.
     ///
     /// Always produces an iterator of length `self.primary.len()`
@@ -16,7 +16,9 @@ pub mod visit;
 pub mod dataframe_util;
 
 pub use self::entity_view::{ComponentWithInstances, EntityView};
-pub use self::query::{get_component_with_instances, query_entity_with_primary};
+pub use self::query::{
+    get_component_with_instances, query_entity_with_primary, query_matching_entities_with_primary,
+};
 pub use self::range::range_entity_with_primary;
 pub use self::util::query_primary_with_history;
 
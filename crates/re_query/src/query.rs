@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use re_arrow_store::{DataStore, LatestAtQuery};
 use re_log_types::{
     component_types::InstanceKey, msg_bundle::Component, ComponentName, EntityPath,
+    EntityPathPattern,
 };
 
 use crate::{ComponentWithInstances, EntityView, QueryError};
@@ -61,6 +62,7 @@ pub fn get_component_with_instances(
         name: component,
         instance_keys: results[0].take(),
         values: results[1].take().ok_or(QueryError::PrimaryNotFound)?,
+        row_index: row_indices[1],
     })
 }
 
@@ -145,6 +147,49 @@ pub fn query_entity_with_primary<Primary: Component>(
     })
 }
 
+/// Retrieves an [`EntityView`] for every entity matching `pattern`.
+///
+/// Entities that don't have the `Primary` component at the time of the query are skipped.
+///
+/// ```
+/// # use re_arrow_store::LatestAtQuery;
+/// # use re_log_types::{EntityPathPattern, Timeline, component_types::{ColorRGBA, Point2D}, msg_bundle::Component};
+/// # let store = re_query::__populate_example_store();
+///
+/// let pattern = EntityPathPattern::parse("**").unwrap();
+/// let query = LatestAtQuery::new(Timeline::new_sequence("frame_nr"), 123.into());
+///
+/// for (ent_path, entity_view) in re_query::query_matching_entities_with_primary::<Point2D>(
+///   &store,
+///   &query,
+///   &pattern,
+///   &[ColorRGBA::name()],
+/// ) {
+///   assert_eq!(ent_path, "point".into());
+///   assert_eq!(entity_view.num_instances(), 2);
+/// }
+/// ```
+pub fn query_matching_entities_with_primary<'a, Primary: Component>(
+    store: &'a DataStore,
+    query: &'a LatestAtQuery,
+    pattern: &EntityPathPattern,
+    components: &'a [ComponentName],
+) -> impl Iterator<Item = (EntityPath, EntityView<Primary>)> + 'a {
+    crate::profile_function!();
+
+    let ent_paths = store
+        .entity_paths()
+        .into_iter()
+        .filter(|ent_path| pattern.matches(ent_path))
+        .collect::<Vec<_>>();
+
+    ent_paths.into_iter().filter_map(move |ent_path| {
+        query_entity_with_primary(store, query, &ent_path, components)
+            .ok()
+            .map(|entity_view| (ent_path, entity_view))
+    })
+}
+
 /// Helper used to create an example store we can use for querying in doctests
 pub fn __populate_example_store() -> DataStore {
     use re_log_types::{
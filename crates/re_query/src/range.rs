@@ -105,6 +105,7 @@ pub fn range_entity_with_primary<'a, Primary: Component + 'a, const N: usize>(
                                     name: components[i],
                                     instance_keys: instance_keys.clone(), // shallow
                                     values: res.clone(),                  // shallow
+                                    row_index: row_indices[i],
                                 }
                             })
                         })
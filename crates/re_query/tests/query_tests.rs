@@ -8,9 +8,9 @@ use re_log_types::{
     msg_bundle::try_build_msg_bundle1,
     msg_bundle::try_build_msg_bundle2,
     msg_bundle::Component,
-    MsgId,
+    EntityPathPattern, MsgId,
 };
-use re_query::query_entity_with_primary;
+use re_query::{query_entity_with_primary, query_matching_entities_with_primary};
 
 #[test]
 fn simple_query() {
@@ -341,3 +341,91 @@ fn splatted_query() {
         let _used = entity_view;
     }
 }
+
+#[test]
+fn matching_entities_query() {
+    let mut store = DataStore::new(InstanceKey::name(), Default::default());
+
+    let timepoint = [build_frame_nr(123.into())];
+    let points = vec![Point2D { x: 1.0, y: 2.0 }];
+
+    for ent_path in [
+        "robot/1/points",
+        "robot/2/points",
+        "robot/2/lidar",
+        "world/points",
+    ] {
+        let bundle = try_build_msg_bundle1(MsgId::random(), ent_path, timepoint, &points).unwrap();
+        store.insert(&bundle).unwrap();
+    }
+
+    let timeline_query = re_arrow_store::LatestAtQuery::new(timepoint[0].0, timepoint[0].1);
+    let pattern = EntityPathPattern::parse("/robot/*/points").unwrap();
+
+    let ent_paths = query_matching_entities_with_primary::<Point2D>(
+        &store,
+        &timeline_query,
+        &pattern,
+        &[ColorRGBA::name()],
+    )
+    .map(|(ent_path, entity_view)| {
+        assert_eq!(entity_view.num_instances(), 1);
+        ent_path.to_string()
+    })
+    .collect::<Vec<_>>();
+
+    assert_eq!(ent_paths, vec!["robot/1/points", "robot/2/points"]);
+}
+
+#[test]
+fn row_indices_query() {
+    let mut store = DataStore::new(InstanceKey::name(), Default::default());
+
+    let ent_path = "point";
+    let points = vec![Point2D { x: 1.0, y: 2.0 }, Point2D { x: 3.0, y: 4.0 }];
+    let bundle = try_build_msg_bundle1(
+        MsgId::random(),
+        ent_path,
+        [build_frame_nr(1.into())],
+        &points,
+    )
+    .unwrap();
+    store.insert(&bundle).unwrap();
+
+    let query_row_indices = |frame_nr: i64| {
+        let timepoint = build_frame_nr(frame_nr.into());
+        let timeline_query = re_arrow_store::LatestAtQuery::new(timepoint.0, timepoint.1);
+        query_entity_with_primary::<Point2D>(
+            &store,
+            &timeline_query,
+            &ent_path.into(),
+            &[ColorRGBA::name()],
+        )
+        .unwrap()
+        .row_indices()
+        .collect::<Vec<_>>()
+    };
+
+    let row_indices_before_colors = query_row_indices(2);
+    assert_eq!(row_indices_before_colors.len(), 1);
+
+    // Same data, same rows.
+    assert_eq!(query_row_indices(1), row_indices_before_colors);
+
+    // Joining in another component adds its row.
+    let color_instances = vec![InstanceKey(1)];
+    let colors = vec![ColorRGBA(0xff000000)];
+    let bundle = try_build_msg_bundle2(
+        MsgId::random(),
+        ent_path,
+        [build_frame_nr(3.into())],
+        (color_instances, colors),
+    )
+    .unwrap();
+    store.insert(&bundle).unwrap();
+
+    let row_indices_after_colors = query_row_indices(3);
+    assert_eq!(row_indices_after_colors.len(), 2);
+    assert_eq!(row_indices_after_colors[0], row_indices_before_colors[0]);
+    assert_eq!(query_row_indices(2), row_indices_before_colors);
+}
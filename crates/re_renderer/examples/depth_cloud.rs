@@ -179,6 +179,9 @@ impl RenderDepthClouds {
                 depth_dimensions: depth.dimensions,
                 depth_data: depth.data.clone(),
                 colormap: re_renderer::ColorMap::ColorMapTurbo,
+                colormap_lut: None,
+                outline_mask_id: Default::default(),
+                picking_layer_id: Default::default(),
             }],
         )
         .unwrap();
@@ -328,6 +331,7 @@ impl framework::Example for RenderDepthClouds {
                 texture_filter_minification: re_renderer::renderer::TextureFilterMin::Linear,
                 multiplicative_tint: Rgba::from_white_alpha(0.5),
                 depth_offset: -1,
+                outline_mask: Default::default(),
             }],
         )
         .unwrap();
@@ -62,6 +62,9 @@ fn build_mesh_instances(
                         *p,
                     ) * model_mesh_instances.world_from_mesh,
                     additive_tint: *c,
+                    opacity: 1.0,
+                    outline_mask_ids: Default::default(),
+                    picking_layer_id: Default::default(),
                 },
             )
         })
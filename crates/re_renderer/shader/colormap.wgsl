@@ -8,6 +8,9 @@ const COLORMAP_VIRIDIS: u32 = 2u;
 const COLORMAP_PLASMA:  u32 = 3u;
 const COLORMAP_MAGMA:   u32 = 4u;
 const COLORMAP_INFERNO: u32 = 5u;
+const COLORMAP_COOLWARM: u32 = 6u;
+const COLORMAP_RDBU:     u32 = 7u;
+const COLORMAP_TWILIGHT: u32 = 8u;
 
 fn colormap_srgb(which: u32, t: f32) -> Vec3 {
     if which == COLORMAP_TURBO {
@@ -20,6 +23,12 @@ fn colormap_srgb(which: u32, t: f32) -> Vec3 {
         return colormap_magma_srgb(t);
     } else if which == COLORMAP_INFERNO {
         return colormap_inferno_srgb(t);
+    } else if which == COLORMAP_COOLWARM {
+        return colormap_coolwarm_srgb(t);
+    } else if which == COLORMAP_RDBU {
+        return colormap_rdbu_srgb(t);
+    } else if which == COLORMAP_TWILIGHT {
+        return colormap_twilight_srgb(t);
     } else { // assume grayscale
         return linear_from_srgb(Vec3(t));
     }
@@ -128,3 +137,86 @@ fn colormap_inferno_srgb(t: f32) -> Vec3 {
     let t = saturate(t);
     return c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6)))));
 }
+
+// --- Lookup table color maps ---
+
+/// Returns a normalized sRGB color from a lookup table of evenly spaced colors in the first row
+/// of `lut`, assuming `t` is normalized (it will be saturated no matter what).
+///
+/// Mirrors `colormap_lut_srgb` in `colormap.rs`.
+fn colormap_lut_srgb(lut: texture_2d<f32>, t: f32) -> Vec3 {
+    let num_colors = textureDimensions(lut).x;
+    let x = saturate(t) * f32(max(num_colors - 1, 0));
+    let i = min(i32(x), max(num_colors - 2, 0));
+    let a = textureLoad(lut, IVec2(i, 0), 0).rgb;
+    let b = textureLoad(lut, IVec2(min(i + 1, num_colors - 1), 0), 0).rgb;
+    return mix(a, b, x - f32(i));
+}
+
+// Diverging color map by Kenneth Moreland, sampled from its smooth cool-warm definition.
+// See https://www.kennethmoreland.com/color-maps/
+
+/// Returns a normalized sRGB color from the diverging CoolWarm color map, assuming `t` is
+/// normalized (it will be saturated no matter what).
+fn colormap_coolwarm_srgb(t: f32) -> Vec3 {
+    var lut = array<Vec3, 9>(
+        Vec3(0.2314, 0.2980, 0.7529),
+        Vec3(0.3843, 0.5098, 0.9176),
+        Vec3(0.5529, 0.6902, 0.9961),
+        Vec3(0.7216, 0.8157, 0.9765),
+        Vec3(0.8667, 0.8667, 0.8667),
+        Vec3(0.9608, 0.7686, 0.6784),
+        Vec3(0.9569, 0.6039, 0.4824),
+        Vec3(0.8706, 0.3765, 0.3020),
+        Vec3(0.7059, 0.0157, 0.1490)
+    );
+    let x = saturate(t) * 8.0;
+    let i = min(u32(x), 7u);
+    return mix(lut[i], lut[i + 1u], x - f32(i));
+}
+
+// Diverging color map from ColorBrewer, by Cynthia Brewer.
+// See https://colorbrewer2.org/, licensed under Apache-2.0.
+
+/// Returns a normalized sRGB color from the diverging red to blue color map, assuming `t` is
+/// normalized (it will be saturated no matter what).
+fn colormap_rdbu_srgb(t: f32) -> Vec3 {
+    var lut = array<Vec3, 11>(
+        Vec3(0.4039, 0.0000, 0.1216),
+        Vec3(0.6980, 0.0941, 0.1686),
+        Vec3(0.8392, 0.3765, 0.3020),
+        Vec3(0.9569, 0.6471, 0.5098),
+        Vec3(0.9922, 0.8588, 0.7804),
+        Vec3(0.9686, 0.9686, 0.9686),
+        Vec3(0.8196, 0.8980, 0.9412),
+        Vec3(0.5725, 0.7725, 0.8706),
+        Vec3(0.2627, 0.5765, 0.7647),
+        Vec3(0.1294, 0.4000, 0.6745),
+        Vec3(0.0196, 0.1882, 0.3804)
+    );
+    let x = saturate(t) * 10.0;
+    let i = min(u32(x), 9u);
+    return mix(lut[i], lut[i + 1u], x - f32(i));
+}
+
+// Cyclic color map, sampled from matplotlib's twilight by Bastian Bechtold.
+// See https://github.com/bastibe/twilight, licensed under MIT.
+
+/// Returns a normalized sRGB color from the cyclic Twilight color map, assuming `t` is
+/// normalized (it will be saturated no matter what).
+fn colormap_twilight_srgb(t: f32) -> Vec3 {
+    var lut = array<Vec3, 9>(
+        Vec3(0.8863, 0.8510, 0.8863),
+        Vec3(0.6392, 0.7176, 0.8039),
+        Vec3(0.4078, 0.5176, 0.7608),
+        Vec3(0.3608, 0.2784, 0.6667),
+        Vec3(0.1843, 0.0784, 0.2157),
+        Vec3(0.4902, 0.1490, 0.3490),
+        Vec3(0.7216, 0.3451, 0.3059),
+        Vec3(0.8039, 0.6275, 0.5490),
+        Vec3(0.8863, 0.8510, 0.8863)
+    );
+    let x = saturate(t) * 8.0;
+    let i = min(u32(x), 7u);
+    return mix(lut[i], lut[i + 1u], x - f32(i));
+}
@@ -1,6 +1,7 @@
 #import <./types.wgsl>
 #import <./utils/srgb.wgsl>
 #import <./global_bindings.wgsl>
+#import <./outlines/outlines_from_voronoi.wgsl>
 
 struct VertexOutput {
     @builtin(position) position: Vec4,
@@ -10,6 +11,12 @@ struct VertexOutput {
 @group(1) @binding(0)
 var input_texture: texture_2d<f32>;
 
+@group(1) @binding(1)
+var outline_voronoi_texture: texture_2d<f32>;
+
+@group(1) @binding(2)
+var<uniform> outline_config: OutlineConfigUniformBuffer;
+
 @fragment
 fn main(in: VertexOutput) -> @location(0) Vec4 {
     // Note that we can't use a simple textureLoad using @builtin(position) here despite the lack of filtering.
@@ -19,6 +26,14 @@ fn main(in: VertexOutput) -> @location(0) Vec4 {
     // TODO(andreas): Do something meaningful with values above 1
     input = clamp(input, ZERO.xyz, ONE.xyz);
 
+    // Outlines are drawn on top. If disabled, both layer colors are fully transparent.
+    let voronoi_resolution = Vec2(textureDimensions(outline_voronoi_texture));
+    let pixel_coord = in.texcoord * voronoi_resolution;
+    let voronoi = textureLoad(outline_voronoi_texture, IVec2(pixel_coord), 0);
+    let outline = outline_opacities(voronoi, floor(pixel_coord) + Vec2(0.5), outline_config.outline_radius_pixel);
+    input = mix(input, outline_config.color_layer_a.rgb, outline.x * outline_config.color_layer_a.a);
+    input = mix(input, outline_config.color_layer_b.rgb, outline.y * outline_config.color_layer_b.a);
+
     // Convert to srgb - this is necessary since the final eframe output does *not* have an srgb format.
     // Note that the input here is assumed to be linear - if the input texture was an srgb texture it would have been converted on load.
     return Vec4(srgb_from_linear(input), 1.0);
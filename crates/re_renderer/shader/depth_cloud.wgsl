@@ -28,7 +28,12 @@ fn compute_point_data(quad_idx: i32) -> PointData {
     let norm_linear_depth = textureLoad(depth_texture, texcoords, 0).x;
 
     // TODO(cmc): albedo textures
-    let color = Vec4(colormap_srgb(depth_cloud_info.colormap, norm_linear_depth), 1.0);
+    var color: Vec4;
+    if depth_cloud_info.use_colormap_lut != 0u {
+        color = Vec4(colormap_lut_srgb(colormap_lut, norm_linear_depth), 1.0);
+    } else {
+        color = Vec4(colormap_srgb(depth_cloud_info.colormap, norm_linear_depth), 1.0);
+    }
 
     // TODO(cmc): This assumes a pinhole camera; need to support other kinds at some point.
     let intrinsics = depth_cloud_info.depth_camera_intrinsics;
@@ -69,6 +74,18 @@ struct DepthCloudInfo {
 
     /// Configures color mapping mode, see `colormap.wgsl`.
     colormap: u32,
+    /// If non-zero, `colormap_lut` is used instead of `colormap`.
+    use_colormap_lut: u32,
+    colormap_row_pad0: u32,
+    colormap_row_pad1: u32,
+
+    /// Outline mask id for the outline mask pass.
+    outline_mask_id: UVec2,
+    outline_mask_id_row_pad0: u32,
+    outline_mask_id_row_pad1: u32,
+
+    /// Picking layer id for the picking layer pass, the same for all points.
+    picking_layer_id: UVec4,
 };
 @group(1) @binding(0)
 var<uniform> depth_cloud_info: DepthCloudInfo;
@@ -76,6 +93,10 @@ var<uniform> depth_cloud_info: DepthCloudInfo;
 @group(1) @binding(1)
 var depth_texture: texture_2d<f32>;
 
+/// Custom color lookup table, see `colormap_lut_srgb`.
+@group(1) @binding(2)
+var colormap_lut: texture_2d<f32>;
+
 struct VertexOut {
     @builtin(position) pos_in_clip: Vec4,
     @location(0) pos_in_world: Vec3,
@@ -104,8 +125,7 @@ fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOut {
     return out;
 }
 
-@fragment
-fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+fn coverage(in: VertexOut) -> f32 {
     // There's easier ways to compute anti-aliasing for when we are in ortho mode since it's
     // just circles.
     // But it's very nice to have mostly the same code path and this gives us the sphere world
@@ -122,7 +142,27 @@ fn fs_main(in: VertexOut) -> @location(0) Vec4 {
     if smallest_distance_to_sphere > pixel_world_size {
         discard;
     }
-    let coverage = 1.0 - saturate(smallest_distance_to_sphere / pixel_world_size);
+    return 1.0 - saturate(smallest_distance_to_sphere / pixel_world_size);
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+    return vec4(in.point_color.rgb, coverage(in));
+}
 
-    return vec4(in.point_color.rgb, coverage);
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> @location(0) UVec2 {
+    // Only the round cutout of the point is part of the mask.
+    if coverage(in) < 0.5 {
+        discard;
+    }
+    return depth_cloud_info.outline_mask_id;
+}
+
+@fragment
+fn fs_main_picking_layer(in: VertexOut) -> @location(0) UVec4 {
+    if coverage(in) < 0.5 {
+        discard;
+    }
+    return depth_cloud_info.picking_layer_id;
 }
@@ -30,6 +30,28 @@ struct FrameUniformBuffer {
 
     /// Factor used to compute depth offsets, see `depth_offset.wgsl`.
     depth_offset_factor: f32,
+    depth_offset_factor_row_pad0: f32,
+    depth_offset_factor_row_pad1: f32,
+    depth_offset_factor_row_pad2: f32,
+
+    /// Normalized direction towards the key light in world space.
+    key_light_direction: Vec3,
+
+    /// Intensity of the uniform ambient light.
+    ambient_intensity: f32,
+
+    /// Linear color of the key light multiplied with its intensity.
+    key_light_radiance: Vec3,
+
+    /// World space distance positions are moved along their normal before looking them up in the shadow map.
+    shadow_normal_offset: f32,
+
+    /// Transforms world positions to shadow map texture coordinates in `xy` and shadow map depth in `z`.
+    shadow_uv_from_world: Mat4,
+
+    /// Size of a shadow map texel in texture coordinates.
+    /// Zero if there are no shadows.
+    shadow_map_texel_size: f32,
 };
 @group(0) @binding(0)
 var<uniform> frame: FrameUniformBuffer;
@@ -39,3 +61,9 @@ var nearest_sampler: sampler;
 
 @group(0) @binding(2)
 var trilinear_sampler: sampler;
+
+@group(0) @binding(3)
+var shadow_map: texture_depth_2d;
+
+@group(0) @binding(4)
+var shadow_map_sampler: sampler_comparison;
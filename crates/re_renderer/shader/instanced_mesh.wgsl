@@ -2,6 +2,8 @@
 #import <./global_bindings.wgsl>
 #import <./mesh_vertex.wgsl>
 #import <./utils/srgb.wgsl>
+#import <./utils/oit.wgsl>
+#import <./utils/lighting.wgsl>
 
 @group(1) @binding(0)
 var albedo_texture: texture_2d<f32>;
@@ -18,6 +20,10 @@ struct VertexOut {
     @location(0) texcoord: Vec2,
     @location(1) normal_world_space: Vec3,
     @location(2) additive_tint_rgb: Vec3,
+    @location(3) opacity: f32,
+    @location(4) @interpolate(flat) outline_mask_ids: UVec2,
+    @location(5) @interpolate(flat) picking_layer_id: UVec4,
+    @location(6) world_position: Vec3,
 };
 
 @vertex
@@ -38,21 +44,44 @@ fn vs_main(in_vertex: VertexIn, in_instance: InstanceIn) -> VertexOut {
     out.texcoord = in_vertex.texcoord;
     out.normal_world_space = world_normal;
     out.additive_tint_rgb = linear_from_srgb(in_instance.additive_tint_srgb.rgb);
+    out.opacity = in_instance.opacity;
+    out.outline_mask_ids = in_instance.outline_mask_ids;
+    out.picking_layer_id = in_instance.picking_layer_id;
+    out.world_position = world_position;
 
     return out;
 }
 
-@fragment
-fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+fn shade(in: VertexOut) -> Vec3 {
     let albedo = textureSample(albedo_texture, trilinear_sampler, in.texcoord).rgb
                  * material.albedo_factor.rgb + in.additive_tint_rgb;
 
-    // Hardcoded lambert lighting. TODO(andreas): Some microfacet model.
-    let light_dir = normalize(vec3(1.0, 2.0, 0.0)); // TODO(andreas): proper lighting
+    // TODO(andreas): Some microfacet model.
     let normal = normalize(in.normal_world_space);
-    let shading = clamp(dot(normal, light_dir), 0.0, 1.0) + 0.2;
+    return shade_lambert(albedo, in.world_position, normal);
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+    return Vec4(shade(in), 1.0);
+}
+
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> @location(0) UVec2 {
+    return in.outline_mask_ids;
+}
+
+// Depth only, see `ShadowMapProcessor`.
+@fragment
+fn fs_main_shadow_map(in: VertexOut) {
+}
 
-    let radiance = albedo * shading;
+@fragment
+fn fs_main_picking_layer(in: VertexOut) -> @location(0) UVec4 {
+    return in.picking_layer_id;
+}
 
-    return Vec4(radiance, 1.0);
+@fragment
+fn fs_main_transparent(in: VertexOut) -> OitOutput {
+    return oit_output(shade(in), material.albedo_factor.a * in.opacity, in.position.z);
 }
@@ -0,0 +1,105 @@
+//! Draws the background & glyph quads of text labels as camera facing billboards.
+//!
+//! See `src/renderer/labels.rs` for more documentation.
+
+#import <./global_bindings.wgsl>
+#import <./types.wgsl>
+#import <./utils/camera.wgsl>
+#import <./utils/size.wgsl>
+#import <./utils/srgb.wgsl>
+
+/// Coverage of all glyphs, see `GlyphAtlas`.
+@group(1) @binding(0)
+var glyph_atlas: texture_2d<f32>;
+
+/// Depth buffer of the main pass, used to hide labels whose anchor is occluded.
+@group(1) @binding(1)
+var depth_texture: texture_depth_multisampled_2d;
+
+// Keep in sync with `gpu_data::LabelQuad` in `labels.rs`.
+struct QuadIn {
+    @location(0) anchor: Vec3,
+    /// Height of a line of text, see `Size`.
+    @location(1) size: f32,
+    /// Min & max of the quad in line heights relative to the anchor, y pointing down.
+    @location(2) rect: Vec4,
+    /// Min & max texture coordinates in the glyph atlas, negative for the background.
+    @location(3) texcoords: Vec4,
+    /// sRGB color with unmultiplied alpha.
+    @location(4) color: Vec4,
+    /// Pixel coordinates of the anchor in the main pass & the depth something needs to exceed to hide the label.
+    @location(5) occlusion_test: Vec3,
+    @location(6) outline_mask_ids: UVec2,
+    @location(7) picking_layer_id: UVec4,
+};
+
+struct VertexOut {
+    @builtin(position) position: Vec4,
+    @location(0) texcoord: Vec2,
+    @location(1) @interpolate(flat) color: Vec4,
+    @location(2) @interpolate(flat) outline_mask_ids: UVec2,
+    @location(3) @interpolate(flat) picking_layer_id: UVec4,
+};
+
+fn is_anchor_occluded(occlusion_test: Vec3) -> bool {
+    let max_coord = IVec2(textureDimensions(depth_texture)) - IVec2(1, 1);
+    let coord = clamp(IVec2(occlusion_test.xy), IVec2(0, 0), max_coord);
+    // Reverse-z, i.e. larger depth is closer to the camera.
+    return textureLoad(depth_texture, coord, 0) > occlusion_test.z;
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_idx: u32, quad: QuadIn) -> VertexOut {
+    // Two triangles spanning the unit square.
+    var corners = array<Vec2, 6>(
+        Vec2(0.0, 0.0), Vec2(1.0, 0.0), Vec2(0.0, 1.0),
+        Vec2(0.0, 1.0), Vec2(1.0, 0.0), Vec2(1.0, 1.0)
+    );
+
+    var out: VertexOut;
+
+    // Collapsing all vertices of a triangle to the same position discards it.
+    if is_anchor_occluded(quad.occlusion_test) {
+        out.position = ZERO;
+        return out;
+    }
+
+    let corner = corners[vertex_idx];
+    let line_height = unresolved_size_to_world(quad.size, distance(frame.camera_position, quad.anchor), frame.auto_size_points);
+    let local_position = mix(quad.rect.xy, quad.rect.zw, corner) * line_height;
+
+    // Rows of view_from_world are the camera axes in world space.
+    let camera_right = Vec3(frame.view_from_world[0].x, frame.view_from_world[1].x, frame.view_from_world[2].x);
+    let camera_up = Vec3(frame.view_from_world[0].y, frame.view_from_world[1].y, frame.view_from_world[2].y);
+    let world_position = quad.anchor + camera_right * local_position.x - camera_up * local_position.y;
+
+    out.position = frame.projection_from_world * Vec4(world_position, 1.0);
+    out.texcoord = mix(quad.texcoords.xy, quad.texcoords.zw, corner);
+    out.color = linear_from_srgba(quad.color);
+    out.outline_mask_ids = quad.outline_mask_ids;
+    out.picking_layer_id = quad.picking_layer_id;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+    var coverage = 1.0;
+    if in.texcoord.x >= 0.0 {
+        coverage = textureSampleLevel(glyph_atlas, trilinear_sampler, in.texcoord, 0.0).r;
+    }
+    let alpha = in.color.a * coverage;
+    return Vec4(in.color.rgb * alpha, alpha);
+}
+
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> @location(0) UVec2 {
+    if all(in.outline_mask_ids == UVec2(0u, 0u)) {
+        discard;
+    }
+    return in.outline_mask_ids;
+}
+
+@fragment
+fn fs_main_picking_layer(in: VertexOut) -> @location(0) UVec4 {
+    return in.picking_layer_id;
+}
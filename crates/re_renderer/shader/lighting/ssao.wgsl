@@ -0,0 +1,93 @@
+#import <../types.wgsl>
+#import <../global_bindings.wgsl>
+
+// Keep in sync with `gpu_data::SsaoUniformBuffer` in lighting.rs.
+struct SsaoUniformBuffer {
+    view_from_projection: Mat4,
+    radius: f32,
+    radius_row_pad0: f32,
+    radius_row_pad1: f32,
+    radius_row_pad2: f32,
+    intensity: f32,
+};
+@group(1) @binding(0)
+var depth_texture: texture_depth_multisampled_2d;
+@group(1) @binding(1)
+var<uniform> uniforms: SsaoUniformBuffer;
+
+struct VertexOutput {
+    @builtin(position) position: Vec4,
+    @location(0) texcoord: Vec2,
+};
+
+const NUM_SAMPLES: i32 = 16;
+const GOLDEN_ANGLE: f32 = 2.39996323;
+
+fn load_depth(coord: IVec2) -> f32 {
+    let max_coord = IVec2(textureDimensions(depth_texture)) - IVec2(1, 1);
+    return textureLoad(depth_texture, clamp(coord, IVec2(0, 0), max_coord), 0);
+}
+
+fn view_position_from_depth(pixel: Vec2, depth: f32) -> Vec3 {
+    let resolution = Vec2(textureDimensions(depth_texture));
+    let ndc = Vec2(pixel.x / resolution.x * 2.0 - 1.0, 1.0 - pixel.y / resolution.y * 2.0);
+    let position = uniforms.view_from_projection * Vec4(ndc, depth, 1.0);
+    return position.xyz / position.w;
+}
+
+fn view_position(coord: IVec2) -> Vec3 {
+    return view_position_from_depth(Vec2(coord) + Vec2(0.5), load_depth(coord));
+}
+
+@fragment
+fn main(in: VertexOutput) -> @location(0) Vec4 {
+    let coord = IVec2(in.position.xy);
+    let depth = load_depth(coord);
+    if depth == 0.0 {
+        return Vec4(1.0); // Background, 0.0 == far since we're using reverse-z
+    }
+    let center = view_position_from_depth(in.position.xy, depth);
+
+    // Reconstruct the normal from the neighbors,
+    // using the closer neighbor on each axis to avoid smearing across depth discontinuities.
+    let left = view_position(coord - IVec2(1, 0));
+    let right = view_position(coord + IVec2(1, 0));
+    let up = view_position(coord - IVec2(0, 1));
+    let down = view_position(coord + IVec2(0, 1));
+    let dx = select(right - center, center - left, distance(center, left) < distance(center, right));
+    let dy = select(down - center, center - up, distance(center, up) < distance(center, down));
+    let normal = normalize(cross(dy, dx));
+
+    // Rotate the sample kernel in a 4x4 pattern, the noise is blurred away in `ssao_apply.wgsl`.
+    let angle = f32((coord.x & 3) + (coord.y & 3) * 4) * (6.2831853 / 16.0);
+    let random_direction = Vec3(cos(angle), sin(angle), 0.0);
+    let tangent = normalize(random_direction - normal * dot(random_direction, normal));
+    let bitangent = cross(normal, tangent);
+
+    let resolution = Vec2(textureDimensions(depth_texture));
+    var occlusion = 0.0;
+    for (var i = 0; i < NUM_SAMPLES; i += 1) {
+        // Cosine weighted spiral over the hemisphere, with more samples close to the center.
+        let t = (f32(i) + 0.5) / f32(NUM_SAMPLES);
+        let phi = f32(i) * GOLDEN_ANGLE;
+        let direction = Vec3(cos(phi) * sqrt(t), sin(phi) * sqrt(t), sqrt(1.0 - t));
+        let offset = (tangent * direction.x + bitangent * direction.y + normal * direction.z)
+                     * (uniforms.radius * mix(0.1, 1.0, t * t));
+        let sample_position = center + offset + normal * (uniforms.radius * 0.02);
+
+        let sample_projected = frame.projection_from_view * Vec4(sample_position, 1.0);
+        let sample_ndc = sample_projected.xyz / sample_projected.w;
+        let sample_pixel = Vec2(sample_ndc.x * 0.5 + 0.5, 0.5 - sample_ndc.y * 0.5) * resolution;
+        let sample_coord = IVec2(floor(sample_pixel));
+        let scene_depth = load_depth(sample_coord);
+
+        // Comparing reverse-z depth works the same for all projections: larger means closer to the camera.
+        // Geometry much further away than the radius shouldn't occlude.
+        let scene_position = view_position_from_depth(Vec2(sample_coord) + Vec2(0.5), scene_depth);
+        let range_check = smoothstep(0.0, 1.0, uniforms.radius / distance(center, scene_position));
+        occlusion += select(0.0, range_check, scene_depth > sample_ndc.z);
+    }
+
+    let visibility = 1.0 - occlusion / f32(NUM_SAMPLES) * uniforms.intensity;
+    return Vec4(visibility, 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,26 @@
+#import <../types.wgsl>
+
+@group(0) @binding(0)
+var occlusion_texture: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: Vec4,
+    @location(0) texcoord: Vec2,
+};
+
+@fragment
+fn main(in: VertexOutput) -> @location(0) Vec4 {
+    // 4x4 box blur, covering exactly one tile of the kernel rotation pattern in `ssao.wgsl`.
+    let coord = IVec2(in.position.xy);
+    let max_coord = IVec2(textureDimensions(occlusion_texture)) - IVec2(1, 1);
+
+    var visibility = 0.0;
+    for (var y = -2; y < 2; y += 1) {
+        for (var x = -2; x < 2; x += 1) {
+            visibility += textureLoad(occlusion_texture, clamp(coord + IVec2(x, y), IVec2(0, 0), max_coord), 0).r;
+        }
+    }
+
+    // Multiplied onto the main target by the blend state.
+    return Vec4(Vec3(visibility / 16.0), 1.0);
+}
@@ -10,9 +10,15 @@
 var line_strip_texture: texture_2d<f32>;
 @group(1) @binding(1)
 var position_data_texture: texture_2d<u32>;
+@group(1) @binding(2)
+var picking_instance_id_texture: texture_2d<u32>;
 
 struct BatchUniformBuffer {
     world_from_obj: Mat4,
+    outline_mask_ids: UVec2,
+    outline_mask_ids_row_pad0: u32,
+    outline_mask_ids_row_pad1: u32,
+    picking_object_id: UVec2,
 };
 @group(2) @binding(0)
 var<uniform> batch: BatchUniformBuffer;
@@ -55,6 +61,9 @@ struct VertexOut {
 
     @location(5) @interpolate(flat)
     currently_active_flags: u32,
+
+    @location(6) @interpolate(flat)
+    picking_instance_id: UVec2,
 };
 
 struct LineStripData {
@@ -62,6 +71,7 @@ struct LineStripData {
     unresolved_radius: f32,
     stippling: f32,
     flags: u32,
+    picking_instance_id: UVec2,
 }
 
 // Read and unpack line strip data at a given location
@@ -77,6 +87,7 @@ fn read_strip_data(idx: u32) -> LineStripData {
     data.unresolved_radius = unpack2x16float(raw_data.y).y;
     data.flags = ((raw_data.y >> 8u) & 0xFFu);
     data.stippling = f32((raw_data.y >> 16u) & 0xFFu) * (1.0 / 255.0);
+    data.picking_instance_id = textureLoad(picking_instance_id_texture, IVec2(idx % POSITION_DATA_TEXTURE_SIZE, idx / POSITION_DATA_TEXTURE_SIZE), 0).xy;
     return data;
 }
 
@@ -218,13 +229,12 @@ fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOut {
     out.color = strip_data.color;
     out.active_radius = active_radius;
     out.currently_active_flags = currently_active_flags;
+    out.picking_instance_id = strip_data.picking_instance_id;
 
     return out;
 }
 
-@fragment
-fn fs_main(in: VertexOut) -> @location(0) Vec4 {
-
+fn compute_coverage(in: VertexOut) -> f32 {
     var coverage = 1.0;
     if has_any_flag(in.currently_active_flags, CAP_START_ROUND | CAP_END_ROUND) {
         let distance_to_skeleton = length(in.position_world - in.closest_strip_position);
@@ -239,6 +249,29 @@ fn fs_main(in: VertexOut) -> @location(0) Vec4 {
         }
         coverage = 1.0 - saturate((signed_distance_to_border + half_pixel_world_size) / pixel_world_size);
     }
+    return coverage;
+}
+
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> @location(0) UVec2 {
+    if compute_coverage(in) < 0.5 {
+        discard;
+    }
+    return batch.outline_mask_ids;
+}
+
+@fragment
+fn fs_main_picking_layer(in: VertexOut) -> @location(0) UVec4 {
+    if compute_coverage(in) < 0.5 {
+        discard;
+    }
+    return UVec4(batch.picking_object_id, in.picking_instance_id);
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+
+    let coverage = compute_coverage(in);
 
     // TODO(andreas): lighting setup
     var shading = 1.0;
@@ -0,0 +1,28 @@
+#import <./types.wgsl>
+#import <./global_bindings.wgsl>
+
+struct VertexOutput {
+    @builtin(position) position: Vec4,
+    @location(0) texcoord: Vec2,
+};
+
+@group(1) @binding(0)
+var accumulation_texture: texture_2d<f32>;
+
+@group(1) @binding(1)
+var revealage_texture: texture_2d<f32>;
+
+@fragment
+fn main(in: VertexOutput) -> @location(0) Vec4 {
+    // See `composite.wgsl` for why we sample instead of loading.
+    let revealage = textureSample(revealage_texture, nearest_sampler, in.texcoord).r;
+    if revealage >= 1.0 {
+        // No transparent fragment here.
+        discard;
+    }
+    let accumulation = textureSample(accumulation_texture, nearest_sampler, in.texcoord);
+
+    // Weighted average of all colors, blended on top of the opaque scene with the combined coverage.
+    let average_color = accumulation.rgb / clamp(accumulation.a, 1e-4, 5e4);
+    return Vec4(average_color, 1.0 - revealage);
+}
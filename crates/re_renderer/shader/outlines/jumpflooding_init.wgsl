@@ -0,0 +1,42 @@
+#import <../types.wgsl>
+#import <./outlines_from_voronoi.wgsl>
+
+@group(0) @binding(0)
+var mask_texture: texture_multisampled_2d<u32>;
+
+struct VertexOutput {
+    @builtin(position) position: Vec4,
+    @location(0) texcoord: Vec2,
+};
+
+// A pixel is a seed for a layer if it has a *lower* mask id than one of its direct neighbors.
+// I.e. the seeds are just outside of the masked area, or on the boundary between two different ids.
+fn is_seed(center: u32, coord: IVec2, max_coord: IVec2, layer: i32) -> bool {
+    let offsets = array<IVec2, 4>(IVec2(1, 0), IVec2(-1, 0), IVec2(0, 1), IVec2(0, -1));
+    for (var i = 0; i < 4; i += 1) {
+        let neighbor_coord = clamp(coord + offsets[i], IVec2(0, 0), max_coord);
+        let neighbor = textureLoad(mask_texture, neighbor_coord, 0)[layer];
+        if center < neighbor {
+            return true;
+        }
+    }
+    return false;
+}
+
+@fragment
+fn main(in: VertexOutput) -> @location(0) Vec4 {
+    let coord = IVec2(in.position.xy);
+    let max_coord = IVec2(textureDimensions(mask_texture)) - IVec2(1, 1);
+    let center = textureLoad(mask_texture, coord, 0).xy;
+
+    var voronoi = Vec4(INVALID_SEED_POSITION, INVALID_SEED_POSITION);
+    if is_seed(center.x, coord, max_coord, 0) {
+        voronoi.x = in.position.x;
+        voronoi.y = in.position.y;
+    }
+    if is_seed(center.y, coord, max_coord, 1) {
+        voronoi.z = in.position.x;
+        voronoi.w = in.position.y;
+    }
+    return voronoi;
+}
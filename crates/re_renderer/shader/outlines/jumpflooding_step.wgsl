@@ -0,0 +1,51 @@
+#import <../types.wgsl>
+#import <./outlines_from_voronoi.wgsl>
+
+@group(0) @binding(0)
+var voronoi_texture: texture_2d<f32>;
+
+// Keep in sync with `gpu_data::FloodingUniformBuffer` in outlines.rs.
+struct FloodingUniformBuffer {
+    step_width: u32,
+};
+@group(0) @binding(1)
+var<uniform> uniforms: FloodingUniformBuffer;
+
+struct VertexOutput {
+    @builtin(position) position: Vec4,
+    @location(0) texcoord: Vec2,
+};
+
+@fragment
+fn main(in: VertexOutput) -> @location(0) Vec4 {
+    let pixel_center = in.position.xy;
+    let coord = IVec2(pixel_center);
+    let max_coord = IVec2(textureDimensions(voronoi_texture)) - IVec2(1, 1);
+
+    var closest_a = INVALID_SEED_POSITION;
+    var closest_b = INVALID_SEED_POSITION;
+    var closest_distance_sq = Vec2(f32max, f32max);
+
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let sample_coord = clamp(coord + IVec2(x, y) * i32(uniforms.step_width), IVec2(0, 0), max_coord);
+            let candidates = textureLoad(voronoi_texture, sample_coord, 0);
+
+            let to_a = candidates.xy - pixel_center;
+            let distance_sq_a = dot(to_a, to_a);
+            if distance_sq_a < closest_distance_sq.x {
+                closest_distance_sq.x = distance_sq_a;
+                closest_a = candidates.xy;
+            }
+
+            let to_b = candidates.zw - pixel_center;
+            let distance_sq_b = dot(to_b, to_b);
+            if distance_sq_b < closest_distance_sq.y {
+                closest_distance_sq.y = distance_sq_b;
+                closest_b = candidates.zw;
+            }
+        }
+    }
+
+    return Vec4(closest_a, closest_b);
+}
@@ -0,0 +1,22 @@
+// Shared by the jump flooding passes and the compositor.
+
+#import <../types.wgsl>
+
+// Position that marks "no closest edge found yet".
+// Far enough away to never produce an outline, but not infinite to keep distance computations sane.
+const INVALID_SEED_POSITION = Vec2(-1e15, -1e15);
+
+// Keep in sync with `OutlineConfig` in outlines.rs.
+struct OutlineConfigUniformBuffer {
+    color_layer_a: Vec4,
+    color_layer_b: Vec4,
+    outline_radius_pixel: f32,
+};
+
+// Returns the opacity of the outlines of layer a & b at a given pixel,
+// given the closest edge positions (xy for layer a, zw for layer b) computed by jump flooding.
+fn outline_opacities(voronoi: Vec4, pixel_center: Vec2, outline_radius_pixel: f32) -> Vec2 {
+    let distances = Vec2(distance(voronoi.xy, pixel_center), distance(voronoi.zw, pixel_center));
+    // One pixel of anti-aliasing at the outer rim.
+    return saturate(Vec2(outline_radius_pixel + 0.5) - distances);
+}
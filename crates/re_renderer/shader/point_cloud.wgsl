@@ -4,15 +4,29 @@
 #import <./utils/flags.wgsl>
 #import <./utils/size.wgsl>
 #import <./utils/sphere_quad.wgsl>
+#import <./utils/oit.wgsl>
 
 @group(1) @binding(0)
 var position_data_texture: texture_2d<f32>;
 @group(1) @binding(1)
 var color_texture: texture_2d<f32>;
+@group(1) @binding(2)
+var picking_instance_id_texture: texture_2d<u32>;
 
 struct BatchUniformBuffer {
     world_from_obj: Mat4,
     flags: u32,
+    flags_row_pad0: u32,
+    flags_row_pad1: u32,
+    flags_row_pad2: u32,
+    opacity: f32,
+    opacity_row_pad0: f32,
+    opacity_row_pad1: f32,
+    opacity_row_pad2: f32,
+    outline_mask_ids: UVec2,
+    outline_mask_ids_row_pad0: u32,
+    outline_mask_ids_row_pad1: u32,
+    picking_object_id: UVec2,
 };
 @group(2) @binding(0)
 var<uniform> batch: BatchUniformBuffer;
@@ -31,12 +45,14 @@ struct VertexOut {
     @location(1) world_position: Vec3,
     @location(2) point_center: Vec3,
     @location(3) radius: f32,
+    @location(4) @interpolate(flat) picking_instance_id: UVec2,
 };
 
 struct PointData {
     pos: Vec3,
     unresolved_radius: f32,
-    color: Vec4
+    color: Vec4,
+    picking_instance_id: UVec2,
 }
 
 // Read and unpack data at a given location
@@ -44,12 +60,14 @@ fn read_data(idx: i32) -> PointData {
     let coord = IVec2(i32(idx % TEXTURE_SIZE), idx / TEXTURE_SIZE);
     let position_data = textureLoad(position_data_texture, coord, 0);
     let color = textureLoad(color_texture, coord, 0);
+    let picking_instance_id = textureLoad(picking_instance_id_texture, coord, 0).xy;
 
     var data: PointData;
     let pos_4d = batch.world_from_obj * Vec4(position_data.xyz, 1.0);
     data.pos = pos_4d.xyz / pos_4d.w;
     data.unresolved_radius = position_data.w;
     data.color = color;
+    data.picking_instance_id = picking_instance_id;
     return data;
 }
 
@@ -70,12 +88,13 @@ fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOut {
     out.radius = quad.point_resolved_radius;
     out.world_position = quad.pos_in_world;
     out.point_center = point_data.pos;
+    out.picking_instance_id = point_data.picking_instance_id;
 
     return out;
 }
 
-@fragment
-fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+// Returns the shaded color and the coverage in alpha.
+fn shade_point(in: VertexOut) -> Vec4 {
     // There's easier ways to compute anti-aliasing for when we are in ortho mode since it's just circles.
     // But it's very nice to have mostly the same code path and this gives us the sphere world position along the way.
     let ray = camera_ray_to_world_pos(in.world_position);
@@ -102,3 +121,33 @@ fn fs_main(in: VertexOut) -> @location(0) Vec4 {
     }
     return vec4(in.color.rgb * shading, coverage);
 }
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+    return shade_point(in);
+}
+
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> @location(0) UVec2 {
+    // Only the round cutout of the point is part of the mask.
+    let coverage = shade_point(in).a;
+    if coverage < 0.5 {
+        discard;
+    }
+    return batch.outline_mask_ids;
+}
+
+@fragment
+fn fs_main_picking_layer(in: VertexOut) -> @location(0) UVec4 {
+    let coverage = shade_point(in).a;
+    if coverage < 0.5 {
+        discard;
+    }
+    return UVec4(batch.picking_object_id, in.picking_instance_id);
+}
+
+@fragment
+fn fs_main_transparent(in: VertexOut) -> OitOutput {
+    let color = shade_point(in);
+    return oit_output(color.rgb, color.a * batch.opacity, in.position.z);
+}
@@ -1,6 +1,7 @@
 #import <./types.wgsl>
 #import <./global_bindings.wgsl>
 #import <./utils/depth_offset.wgsl>
+#import <./utils/oit.wgsl>
 
 struct UniformBuffer {
     /// Top left corner position in world space.
@@ -10,8 +11,9 @@ struct UniformBuffer {
     /// Vector that spans up the rectangle from its top left corner along the v axis of the texture.
     extent_v: Vec3,
     depth_offset: f32,
-    /// Tint multiplied with the texture color.
+    /// Tint multiplied with the texture color, premultiplied alpha.
     multiplicative_tint: Vec4,
+    outline_mask: UVec2,
 };
 @group(1) @binding(0)
 var<uniform> rect_info: UniformBuffer;
@@ -46,3 +48,14 @@ fn fs_main(in: VertexOut) -> @location(0) Vec4 {
     let texture_color = textureSample(texture, texture_sampler, in.texcoord);
     return texture_color * rect_info.multiplicative_tint;
 }
+
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> @location(0) UVec2 {
+    return rect_info.outline_mask;
+}
+
+@fragment
+fn fs_main_transparent(in: VertexOut) -> OitOutput {
+    let texture_color = textureSample(texture, texture_sampler, in.texcoord);
+    return oit_output_premultiplied(texture_color * rect_info.multiplicative_tint, in.position.z);
+}
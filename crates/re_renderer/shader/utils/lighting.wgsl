@@ -0,0 +1,32 @@
+#import <../types.wgsl>
+#import <../global_bindings.wgsl>
+
+// Fraction of the key light that reaches the given world position, 1.0 if there are no shadows.
+fn key_light_visibility(world_position: Vec3, normal: Vec3) -> f32 {
+    if frame.shadow_map_texel_size == 0.0 {
+        return 1.0;
+    }
+
+    // Moving along the normal avoids surfaces shadowing themselves ("shadow acne").
+    let shadow_position = frame.shadow_uv_from_world * Vec4(world_position + normal * frame.shadow_normal_offset, 1.0);
+    if any(shadow_position.xy < Vec2(0.0)) || any(shadow_position.xy > Vec2(1.0)) {
+        return 1.0;
+    }
+
+    // 3x3 percentage closer filtering, on top of the bilinear filtering of the comparison sampler.
+    var visibility = 0.0;
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let uv = shadow_position.xy + Vec2(f32(x), f32(y)) * frame.shadow_map_texel_size;
+            visibility += textureSampleCompareLevel(shadow_map, shadow_map_sampler, uv, shadow_position.z);
+        }
+    }
+    return visibility / 9.0;
+}
+
+// Lambertian diffuse shading with the key light & ambient term of the frame.
+fn shade_lambert(albedo: Vec3, world_position: Vec3, normal: Vec3) -> Vec3 {
+    let n_dot_l = clamp(dot(normal, frame.key_light_direction), 0.0, 1.0);
+    let key_light = frame.key_light_radiance * (n_dot_l * key_light_visibility(world_position, normal));
+    return albedo * (key_light + Vec3(frame.ambient_intensity));
+}
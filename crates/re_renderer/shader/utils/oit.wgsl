@@ -0,0 +1,38 @@
+// Weighted blended order-independent transparency,
+// see https://jcgt.org/published/0002/02/09/ and `DrawPhase::Transparent`.
+
+#import <../types.wgsl>
+
+// Keep in sync with `ViewBuilder::OIT_RENDER_TARGETS`.
+struct OitOutput {
+    // Weighted sum of premultiplied colors (rgb) and weighted sum of alphas (a).
+    @location(0) accumulation: Vec4,
+    // Product of (1 - alpha) of all fragments.
+    @location(1) revealage: f32,
+};
+
+// Fragments closer to the camera get a higher weight, approximating the result of sorting.
+//
+// `depth` is the fragment's depth buffer value, i.e. `@builtin(position).z`
+// (reverse-z: 1.0 is near, 0.0 is far).
+fn oit_weight(depth: f32, alpha: f32) -> f32 {
+    // Equation (7) from the paper, adapted to reverse-z.
+    let closeness = 0.1 + 0.9 * depth;
+    return clamp(pow(min(1.0, alpha * 10.0) + 0.01, 3.0) * 1e8 * pow(closeness, 3.0), 1e-2, 3e3);
+}
+
+// Produces the output for a fragment of the given premultiplied color.
+//
+// `depth` is the fragment's depth buffer value, i.e. `@builtin(position).z`.
+fn oit_output_premultiplied(color: Vec4, depth: f32) -> OitOutput {
+    let weight = oit_weight(depth, color.a);
+    var out: OitOutput;
+    out.accumulation = color * weight;
+    out.revealage = color.a;
+    return out;
+}
+
+// Produces the output for a fragment of the given linear color and (non-premultiplied) alpha.
+fn oit_output(color: Vec3, alpha: f32, depth: f32) -> OitOutput {
+    return oit_output_premultiplied(Vec4(color * alpha, alpha), depth);
+}
@@ -0,0 +1,306 @@
+//! Ray marches through a 3D texture.
+//!
+//! See `src/renderer/volumes.rs` for more documentation.
+
+#import <./colormap.wgsl>
+#import <./global_bindings.wgsl>
+#import <./types.wgsl>
+#import <./utils/camera.wgsl>
+#import <./utils/lighting.wgsl>
+#import <./utils/oit.wgsl>
+
+// NOTE: Keep in sync with `VolumeRenderMode` in `volumes.rs`!
+const RENDER_MODE_COMPOSITE:     u32 = 0u;
+const RENDER_MODE_MAX_INTENSITY: u32 = 1u;
+const RENDER_MODE_ISOSURFACE:    u32 = 2u;
+
+// Keep in sync with `gpu_data::VolumeInfoUBO` in `volumes.rs`.
+struct VolumeInfo {
+    /// Maps the unit cube to world space.
+    world_from_volume: Mat4,
+    volume_from_world: Mat4,
+
+    /// Number of voxels along x, y and z.
+    dimensions: UVec3,
+    render_mode: u32,
+
+    /// Configures color mapping mode, see `colormap.wgsl`.
+    colormap: u32,
+    /// If non-zero, `colormap_lut` is used instead of `colormap`.
+    use_colormap_lut: u32,
+    colormap_row_pad0: u32,
+    colormap_row_pad1: u32,
+
+    /// Values below are fully transparent.
+    threshold: f32,
+    opacity: f32,
+    iso_value: f32,
+    params_row_pad0: f32,
+
+    /// Outline mask id for the outline mask pass.
+    outline_mask_id: UVec2,
+    outline_mask_id_row_pad0: u32,
+    outline_mask_id_row_pad1: u32,
+
+    /// Picking layer id for the picking layer pass, the same for the entire volume.
+    picking_layer_id: UVec4,
+};
+@group(1) @binding(0)
+var<uniform> volume_info: VolumeInfo;
+
+@group(1) @binding(1)
+var volume_texture: texture_3d<f32>;
+
+@group(1) @binding(2)
+var volume_sampler: sampler;
+
+/// Custom color lookup table, see `colormap_lut_srgb`.
+@group(1) @binding(3)
+var colormap_lut: texture_2d<f32>;
+
+// Upper bound of samples per ray, keeps huge volumes from stalling the gpu.
+const MAX_NUM_STEPS: u32 = 1024u;
+
+// Ray marching through a composite volume stops once it is this opaque.
+const OPACITY_CUTOFF: f32 = 0.99;
+
+// ---
+
+struct VertexOut {
+    @builtin(position) position: Vec4,
+    @location(0) world_position: Vec3,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_idx: u32) -> VertexOut {
+    // Unit cube as a triangle list, corners are indexed by their coordinates as bits (x | y << 1 | z << 2).
+    var cube_indices = array<u32, 36>(
+        0u, 4u, 6u, 0u, 6u, 2u,
+        1u, 3u, 7u, 1u, 7u, 5u,
+        0u, 1u, 5u, 0u, 5u, 4u,
+        2u, 6u, 7u, 2u, 7u, 3u,
+        0u, 2u, 3u, 0u, 3u, 1u,
+        4u, 5u, 7u, 4u, 7u, 6u
+    );
+    let corner = cube_indices[vertex_idx];
+    let volume_position = Vec3(f32(corner & 1u), f32((corner >> 1u) & 1u), f32((corner >> 2u) & 1u));
+    let world_position = (volume_info.world_from_volume * Vec4(volume_position, 1.0)).xyz;
+
+    var out: VertexOut;
+    out.position = frame.projection_from_world * Vec4(world_position, 1.0);
+    out.world_position = world_position;
+    return out;
+}
+
+// ---
+
+fn sample_volume(volume_position: Vec3) -> f32 {
+    return textureSampleLevel(volume_texture, volume_sampler, volume_position, 0.0).r;
+}
+
+fn transfer_color(value: f32) -> Vec3 {
+    if volume_info.use_colormap_lut != 0u {
+        return colormap_lut_srgb(colormap_lut, value);
+    } else {
+        return colormap_srgb(volume_info.colormap, value);
+    }
+}
+
+fn depth_at(world_position: Vec3) -> f32 {
+    let clip = frame.projection_from_world * Vec4(world_position, 1.0);
+    // Positions at (or behind) the camera are clamped to the near plane, which is 1.0 with reverse-z.
+    if clip.w <= 0.0 {
+        return 1.0;
+    }
+    return saturate(clip.z / clip.w);
+}
+
+struct MarchResult {
+    /// Premultiplied linear color.
+    color: Vec4,
+
+    /// Depth buffer value of the first visible sample.
+    depth: f32,
+
+    /// Whether anything was hit that should show up in outlines and picking.
+    hit: bool,
+};
+
+fn march(in: VertexOut) -> MarchResult {
+    var result: MarchResult;
+    result.color = ZERO;
+    result.depth = 0.0;
+    result.hit = false;
+
+    let ray = camera_ray_to_world_pos(in.world_position);
+
+    // Intersect the ray with the unit cube in volume space.
+    // Since the volume transform is affine, `t` is the same distance along the ray in both spaces.
+    let origin = (volume_info.volume_from_world * Vec4(ray.origin, 1.0)).xyz;
+    let direction = (volume_info.volume_from_world * Vec4(ray.direction, 0.0)).xyz;
+    let t0 = (Vec3(0.0) - origin) / direction;
+    let t1 = (Vec3(1.0) - origin) / direction;
+    let t_near = max(max(min(t0.x, t1.x), min(t0.y, t1.y)), min(t0.z, t1.z));
+    let t_far = min(min(max(t0.x, t1.x), max(t0.y, t1.y)), max(t0.z, t1.z));
+
+    // Every ray passes through two faces of the cube, only do the work for the exit.
+    // This doesn't depend on the winding of the faces, which flips for mirroring transforms.
+    let t_fragment = dot(in.world_position - ray.origin, ray.direction);
+    let t_start = max(t_near, 0.0);
+    if t_far <= t_start || abs(t_fragment - t_far) > abs(t_fragment - t_near) {
+        discard;
+    }
+
+    // About two samples per voxel.
+    let voxel_distance = length((t_far - t_start) * direction * Vec3(volume_info.dimensions));
+    let num_steps = clamp(u32(ceil(voxel_distance * 2.0)), 1u, MAX_NUM_STEPS);
+    let step = (t_far - t_start) / f32(num_steps);
+    let voxels_per_step = voxel_distance / f32(num_steps);
+
+    if volume_info.render_mode == RENDER_MODE_ISOSURFACE {
+        var previous_value = sample_volume(origin + direction * t_start);
+        for (var i = 1u; i <= num_steps; i += 1u) {
+            let t = t_start + step * f32(i);
+            let value = sample_volume(origin + direction * t);
+            if (previous_value < volume_info.iso_value) != (value < volume_info.iso_value) {
+                // Refine the hit between the last two samples.
+                let fraction = saturate((volume_info.iso_value - previous_value) / (value - previous_value));
+                let t_hit = t - step * (1.0 - fraction);
+                let volume_position = origin + direction * t_hit;
+
+                // Gradient with central differences, one voxel apart.
+                let offset = 1.0 / Vec3(volume_info.dimensions);
+                let gradient = Vec3(
+                    sample_volume(volume_position + Vec3(offset.x, 0.0, 0.0)) - sample_volume(volume_position - Vec3(offset.x, 0.0, 0.0)),
+                    sample_volume(volume_position + Vec3(0.0, offset.y, 0.0)) - sample_volume(volume_position - Vec3(0.0, offset.y, 0.0)),
+                    sample_volume(volume_position + Vec3(0.0, 0.0, offset.z)) - sample_volume(volume_position - Vec3(0.0, 0.0, offset.z))
+                );
+                // Normals transform with the inverse transpose.
+                var normal = -ray.direction;
+                if any(gradient != Vec3(0.0)) {
+                    normal = normalize(-(transpose(volume_info.volume_from_world) * Vec4(gradient, 0.0)).xyz);
+                }
+                if dot(normal, ray.direction) > 0.0 {
+                    normal = -normal;
+                }
+
+                let world_position = ray.origin + ray.direction * t_hit;
+                let albedo = transfer_color(volume_info.iso_value);
+                result.color = Vec4(shade_lambert(albedo, world_position, normal), 1.0);
+                result.depth = depth_at(world_position);
+                result.hit = true;
+                return result;
+            }
+            previous_value = value;
+        }
+    } else if volume_info.render_mode == RENDER_MODE_MAX_INTENSITY {
+        var max_value = 0.0;
+        var t_max_value = t_start;
+        for (var i = 0u; i <= num_steps; i += 1u) {
+            let t = t_start + step * f32(i);
+            let value = sample_volume(origin + direction * t);
+            if value > max_value {
+                max_value = value;
+                t_max_value = t;
+            }
+        }
+        if max_value >= volume_info.threshold {
+            result.color = Vec4(transfer_color(max_value), 1.0) * volume_info.opacity;
+            result.depth = depth_at(ray.origin + ray.direction * t_max_value);
+            result.hit = true;
+        }
+    } else {
+        // Front to back compositing.
+        for (var i = 0u; i < num_steps && result.color.a < OPACITY_CUTOFF; i += 1u) {
+            let t = t_start + step * (f32(i) + 0.5);
+            let value = sample_volume(origin + direction * t);
+            if value < volume_info.threshold {
+                continue;
+            }
+            let density = saturate((value - volume_info.threshold) / max(1.0 - volume_info.threshold, f32eps));
+            // Opacity is given per voxel, correct it for the actual step length.
+            let alpha = 1.0 - pow(1.0 - saturate(density * volume_info.opacity), voxels_per_step);
+            if !result.hit {
+                result.depth = depth_at(ray.origin + ray.direction * t);
+                result.hit = true;
+            }
+            result.color += (1.0 - result.color.a) * Vec4(transfer_color(value) * alpha, alpha);
+        }
+    }
+
+    return result;
+}
+
+// ---
+
+struct OpaqueOutput {
+    @location(0) color: Vec4,
+    @builtin(frag_depth) depth: f32,
+};
+
+// Like `OitOutput`, but with the depth of the first visible sample instead of the depth of the cube face.
+struct TransparentOutput {
+    @location(0) accumulation: Vec4,
+    @location(1) revealage: f32,
+    @builtin(frag_depth) depth: f32,
+};
+
+struct OutlineMaskOutput {
+    @location(0) outline_mask_id: UVec2,
+    @builtin(frag_depth) depth: f32,
+};
+
+struct PickingLayerOutput {
+    @location(0) picking_layer_id: UVec4,
+    @builtin(frag_depth) depth: f32,
+};
+
+@fragment
+fn fs_main(in: VertexOut) -> OpaqueOutput {
+    let result = march(in);
+    if !result.hit {
+        discard;
+    }
+    var out: OpaqueOutput;
+    out.color = result.color;
+    out.depth = result.depth;
+    return out;
+}
+
+@fragment
+fn fs_main_transparent(in: VertexOut) -> TransparentOutput {
+    let result = march(in);
+    if !result.hit || result.color.a <= 0.0 {
+        discard;
+    }
+    let oit = oit_output_premultiplied(result.color, result.depth);
+    var out: TransparentOutput;
+    out.accumulation = oit.accumulation;
+    out.revealage = oit.revealage;
+    out.depth = result.depth;
+    return out;
+}
+
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> OutlineMaskOutput {
+    let result = march(in);
+    if !result.hit {
+        discard;
+    }
+    var out: OutlineMaskOutput;
+    out.outline_mask_id = volume_info.outline_mask_id;
+    out.depth = result.depth;
+    return out;
+}
+
+@fragment
+fn fs_main_picking_layer(in: VertexOut) -> PickingLayerOutput {
+    let result = march(in);
+    if !result.hit {
+        discard;
+    }
+    var out: PickingLayerOutput;
+    out.picking_layer_id = volume_info.picking_layer_id;
+    out.depth = result.depth;
+    return out;
+}
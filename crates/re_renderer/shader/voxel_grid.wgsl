@@ -0,0 +1,136 @@
+//! Draws every occupied cell of a voxel grid as an instanced cube.
+//!
+//! See `src/renderer/voxel_grids.rs` for more documentation.
+
+#import <./colormap.wgsl>
+#import <./global_bindings.wgsl>
+#import <./types.wgsl>
+#import <./utils/camera.wgsl>
+#import <./utils/lighting.wgsl>
+#import <./utils/oit.wgsl>
+#import <./utils/srgb.wgsl>
+
+// Keep in sync with `gpu_data::VoxelGridInfoUBO` in `voxel_grids.rs`.
+struct VoxelGridInfo {
+    /// Maps cell indices to world space.
+    world_from_grid: Mat4,
+    world_from_grid_normal: Mat3,
+
+    /// Configures color mapping mode, see `colormap.wgsl`.
+    colormap: u32,
+    /// If non-zero, `colormap_lut` is used instead of `colormap`.
+    use_colormap_lut: u32,
+    colormap_row_pad0: u32,
+    colormap_row_pad1: u32,
+
+    /// Cells below are not drawn.
+    threshold: f32,
+    opacity: f32,
+    params_row_pad0: f32,
+    params_row_pad1: f32,
+
+    /// Outline mask id for the outline mask pass.
+    outline_mask_id: UVec2,
+    outline_mask_id_row_pad0: u32,
+    outline_mask_id_row_pad1: u32,
+
+    /// Picking layer id for the picking layer pass, the same for the entire grid.
+    picking_layer_id: UVec4,
+};
+@group(1) @binding(0)
+var<uniform> voxel_grid_info: VoxelGridInfo;
+
+/// Custom color lookup table, see `colormap_lut_srgb`.
+@group(1) @binding(1)
+var colormap_lut: texture_2d<f32>;
+
+// Keep in sync with `VoxelCell` in `voxel_grid_manager.rs`.
+struct CellIn {
+    @location(0) index: UVec3,
+    @location(1) value: f32,
+};
+
+struct VertexOut {
+    @builtin(position) position: Vec4,
+    @location(0) world_position: Vec3,
+    @location(1) @interpolate(flat) normal: Vec3,
+    @location(2) @interpolate(flat) color: Vec3,
+};
+
+fn cell_color(value: f32) -> Vec3 {
+    if voxel_grid_info.use_colormap_lut != 0u {
+        return colormap_lut_srgb(colormap_lut, value);
+    } else {
+        return colormap_srgb(voxel_grid_info.colormap, value);
+    }
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_idx: u32, cell: CellIn) -> VertexOut {
+    // Unit cube as a triangle list, corners are indexed by their coordinates as bits (x | y << 1 | z << 2).
+    // Each consecutive six vertices make up a face, see `face_normals`.
+    var cube_indices = array<u32, 36>(
+        0u, 4u, 6u, 0u, 6u, 2u,
+        1u, 3u, 7u, 1u, 7u, 5u,
+        0u, 1u, 5u, 0u, 5u, 4u,
+        2u, 6u, 7u, 2u, 7u, 3u,
+        0u, 2u, 3u, 0u, 3u, 1u,
+        4u, 5u, 7u, 4u, 7u, 6u
+    );
+    var face_normals = array<Vec3, 6>(-X, X, -Y, Y, -Z, Z);
+
+    var out: VertexOut;
+
+    // Collapsing all vertices of a triangle to the same position discards it.
+    if !(cell.value >= voxel_grid_info.threshold) {
+        out.position = ZERO;
+        return out;
+    }
+
+    let face = vertex_idx / 6u;
+    let corner = cube_indices[vertex_idx];
+    let grid_position = Vec3(cell.index) + Vec3(f32(corner & 1u), f32((corner >> 1u) & 1u), f32((corner >> 2u) & 1u));
+    let world_position = (voxel_grid_info.world_from_grid * Vec4(grid_position, 1.0)).xyz;
+    let normal = normalize(voxel_grid_info.world_from_grid_normal * face_normals[face]);
+
+    // Back faces would be hidden by the front faces of the same cube anyways.
+    let face_center = (voxel_grid_info.world_from_grid * Vec4(Vec3(cell.index) + Vec3(0.5) + face_normals[face] * 0.5, 1.0)).xyz;
+    var to_camera = -frame.camera_forward;
+    if is_camera_perspective() {
+        to_camera = frame.camera_position - face_center;
+    }
+    if dot(normal, to_camera) <= 0.0 {
+        out.position = ZERO;
+        return out;
+    }
+
+    out.position = frame.projection_from_world * Vec4(world_position, 1.0);
+    out.world_position = world_position;
+    out.normal = normal;
+    out.color = linear_from_srgb(cell_color(cell.value));
+    return out;
+}
+
+fn shade(in: VertexOut) -> Vec3 {
+    return shade_lambert(in.color, in.world_position, in.normal);
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) Vec4 {
+    return Vec4(shade(in), 1.0);
+}
+
+@fragment
+fn fs_main_transparent(in: VertexOut) -> OitOutput {
+    return oit_output(shade(in), voxel_grid_info.opacity, in.position.z);
+}
+
+@fragment
+fn fs_main_outline_mask(in: VertexOut) -> @location(0) UVec2 {
+    return voxel_grid_info.outline_mask_id;
+}
+
+@fragment
+fn fs_main_picking_layer(in: VertexOut) -> @location(0) UVec4 {
+    return voxel_grid_info.picking_layer_id;
+}
@@ -0,0 +1,287 @@
+use std::sync::mpsc;
+
+use crate::wgpu_resources::{BufferDesc, GpuBuffer, GpuBufferPool};
+
+/// Identifier used to retrieve the data of a readback once it arrived on the CPU.
+pub type GpuReadbackIdentifier = u64;
+
+/// Arbitrary data that is passed along with a readback and handed back once its data is available.
+pub type GpuReadbackUserData = Box<dyn std::any::Any + Send + Sync>;
+
+/// A buffer that GPU data can be copied to, to be read back on the CPU in a later frame.
+///
+/// Several textures can be copied into the same buffer, each copy starts where the previous one ended.
+pub struct GpuReadbackBuffer {
+    buffer: GpuBuffer,
+
+    /// Starting at this offset the buffer hasn't been written yet.
+    unused_offset: wgpu::BufferAddress,
+}
+
+impl GpuReadbackBuffer {
+    /// Schedules a copy of the given texture region into this buffer.
+    ///
+    /// Every row in the buffer is padded to [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`],
+    /// see [`GpuReadbackBuffer::padded_bytes_per_row`].
+    pub fn read_texture2d(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        source: wgpu::ImageCopyTexture<'_>,
+        bytes_per_texel: u32,
+        copy_extent: glam::UVec2,
+    ) {
+        let bytes_per_row = Self::padded_bytes_per_row(copy_extent.x, bytes_per_texel);
+        let copy_size = (bytes_per_row * copy_extent.y) as wgpu::BufferAddress;
+
+        // Wgpu does validation as well, but in debug mode we want to panic if the copy doesn't fit.
+        debug_assert!(
+            self.unused_offset + copy_size <= self.buffer.size(),
+            "Readback buffer has a size of {}, can't write {copy_size} bytes with an offset of {}!",
+            self.buffer.size(),
+            self.unused_offset
+        );
+
+        encoder.copy_texture_to_buffer(
+            source,
+            wgpu::ImageCopyBuffer {
+                buffer: &self.buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: self.unused_offset,
+                    bytes_per_row: std::num::NonZeroU32::new(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: copy_extent.x,
+                height: copy_extent.y,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        self.unused_offset += copy_size;
+    }
+
+    /// Size of a row of a texture copy in bytes, including the padding required for buffer copies.
+    pub fn padded_bytes_per_row(width: u32, bytes_per_texel: u32) -> u32 {
+        wgpu::util::align_to(width * bytes_per_texel, wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReadbackState {
+    /// Copies into the buffer may still be recorded, the command encoder wasn't submitted yet.
+    Scheduled,
+
+    /// Mapping was requested after the submission, waiting for the GPU to finish.
+    Mapping,
+
+    /// Buffer is mapped and its data is ready to be read.
+    Mapped,
+}
+
+struct PendingReadback {
+    /// Unique id of this readback, used to identify it in the map callback.
+    serial: u64,
+
+    identifier: GpuReadbackIdentifier,
+    user_data: GpuReadbackUserData,
+    buffer: GpuBuffer,
+    frame_index: u64,
+    state: ReadbackState,
+}
+
+/// Manages buffers for reading data back from the GPU.
+///
+/// In contrast to [`super::CpuWriteGpuReadBelt`], every readback gets its own buffer from the buffer pool.
+/// Readbacks are typically small and rare (e.g. a few pixels under the mouse cursor),
+/// so sub-allocation isn't worth the complexity.
+///
+/// Data arrives with a delay of at least one frame, since we can only map a buffer after
+/// the command buffer copying to it was submitted.
+/// Use [`GpuReadbackBelt::readback_data`] to poll for it.
+pub struct GpuReadbackBelt {
+    pending: Vec<PendingReadback>,
+    next_serial: u64,
+
+    /// Frame index passed on the last [`GpuReadbackBelt::after_queue_submit`].
+    frame_index: u64,
+
+    /// When mapping a buffer finished, the map callback sends the serial of its readback here,
+    /// along with whether it succeeded.
+    ///
+    /// Note that we shouldn't use SyncSender since this can block the Sender if a buffer is full,
+    /// which means that in a single threaded situation (Web!) we might deadlock.
+    sender: mpsc::Sender<(u64, Result<(), wgpu::BufferAsyncError>)>,
+    receiver: mpsc::Receiver<(u64, Result<(), wgpu::BufferAsyncError>)>,
+}
+
+impl Default for GpuReadbackBelt {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            pending: Vec::new(),
+            next_serial: 0,
+            frame_index: 0,
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl GpuReadbackBelt {
+    /// Readbacks that arrived but weren't retrieved for this many frames are discarded.
+    const MAX_UNRETRIEVED_FRAMES: u64 = 8;
+
+    /// Allocates a buffer to copy at most `size_in_bytes` into.
+    ///
+    /// Its data can be retrieved with the given `identifier` once the GPU is done with it.
+    pub fn allocate(
+        &mut self,
+        device: &wgpu::Device,
+        buffer_pool: &GpuBufferPool,
+        size_in_bytes: wgpu::BufferAddress,
+        identifier: GpuReadbackIdentifier,
+        user_data: GpuReadbackUserData,
+    ) -> GpuReadbackBuffer {
+        let buffer = buffer_pool.alloc(
+            device,
+            &BufferDesc {
+                label: "GpuReadbackBelt buffer".into(),
+                size: wgpu::util::align_to(size_in_bytes, wgpu::COPY_BUFFER_ALIGNMENT),
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+
+        self.pending.push(PendingReadback {
+            serial: self.next_serial,
+            identifier,
+            user_data,
+            buffer: buffer.clone(),
+            frame_index: self.frame_index,
+            state: ReadbackState::Scheduled,
+        });
+        self.next_serial += 1;
+
+        GpuReadbackBuffer {
+            buffer,
+            unused_offset: 0,
+        }
+    }
+
+    /// Requests mapping of all buffers that were scheduled so far.
+    ///
+    /// This must only be called after the command encoder(s) used in [`GpuReadbackBuffer`]
+    /// copy operations are submitted. Additional calls are harmless.
+    pub fn after_queue_submit(&mut self, frame_index: u64) {
+        self.frame_index = frame_index;
+        self.receive_mapped();
+
+        // Drop data nobody asked for in a long time.
+        self.pending.retain(|readback| {
+            let stale = readback.state == ReadbackState::Mapped
+                && readback.frame_index + Self::MAX_UNRETRIEVED_FRAMES < frame_index;
+            if stale {
+                readback.buffer.unmap();
+            }
+            !stale
+        });
+
+        for readback in &mut self.pending {
+            if readback.state != ReadbackState::Scheduled {
+                continue;
+            }
+            readback.state = ReadbackState::Mapping;
+
+            let sender = self.sender.clone();
+            let serial = readback.serial;
+            readback
+                .buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    let _ = sender.send((serial, result));
+                });
+        }
+    }
+
+    /// Retrieves the newest available data for the given identifier.
+    ///
+    /// Calls `callback` with the data and the user data passed on allocation if any arrived since the last call.
+    /// Older readbacks with the same identifier that arrived as well are discarded.
+    pub fn readback_data<T: bytemuck::Pod, R>(
+        &mut self,
+        identifier: GpuReadbackIdentifier,
+        callback: impl FnOnce(&[T], GpuReadbackUserData) -> R,
+    ) -> Option<R> {
+        self.receive_mapped();
+
+        let newest_index = self.pending.iter().rposition(|readback| {
+            readback.identifier == identifier && readback.state == ReadbackState::Mapped
+        })?;
+        let newest_serial = self.pending[newest_index].serial;
+
+        let mut result = None;
+        let mut callback = Some(callback);
+        self.pending.retain_mut(|readback| {
+            if readback.identifier != identifier
+                || readback.state != ReadbackState::Mapped
+                || readback.serial > newest_serial
+            {
+                return true;
+            }
+
+            if readback.serial == newest_serial {
+                let user_data = std::mem::replace(&mut readback.user_data, Box::new(()));
+                // There's no alignment guarantee for mapped memory, see https://github.com/gfx-rs/wgpu/issues/3508
+                // Readbacks are small, so just copy the data out instead of risking a misaligned cast.
+                let data: Vec<T> =
+                    bytemuck::pod_collect_to_vec(&readback.buffer.slice(..).get_mapped_range());
+                if let Some(callback) = callback.take() {
+                    result = Some(callback(&data, user_data));
+                }
+            }
+
+            readback.buffer.unmap();
+            false
+        });
+
+        result
+    }
+
+    /// Whether there is a readback for the given identifier whose data wasn't retrieved yet,
+    /// i.e. calling [`Self::readback_data`] in a later frame may yield data.
+    pub fn has_pending(&self, identifier: GpuReadbackIdentifier) -> bool {
+        self.pending
+            .iter()
+            .any(|readback| readback.identifier == identifier)
+    }
+
+    fn receive_mapped(&mut self) {
+        while let Ok((serial, result)) = self.receiver.try_recv() {
+            let Some(index) = self
+                .pending
+                .iter()
+                .position(|readback| readback.serial == serial)
+            else {
+                continue;
+            };
+
+            match result {
+                Ok(()) => self.pending[index].state = ReadbackState::Mapped,
+                Err(err) => {
+                    // Nothing to unmap, the buffer goes back to the pool.
+                    re_log::warn_once!("Failed to map a readback buffer: {err}");
+                    self.pending.remove(index);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for GpuReadbackBelt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GpuReadbackBelt")
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -4,9 +4,13 @@
 //! follows some more complex strategy for efficient re-use and sub-allocation of wgpu resources.
 
 mod cpu_write_gpu_read_belt;
+mod gpu_readback_belt;
 mod uniform_buffer_fill;
 
 pub use cpu_write_gpu_read_belt::{CpuWriteGpuReadBelt, CpuWriteGpuReadBuffer};
+pub use gpu_readback_belt::{
+    GpuReadbackBelt, GpuReadbackBuffer, GpuReadbackIdentifier, GpuReadbackUserData,
+};
 pub use uniform_buffer_fill::{
     create_and_fill_uniform_buffer, create_and_fill_uniform_buffer_batch,
 };
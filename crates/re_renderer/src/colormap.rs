@@ -5,7 +5,7 @@ use glam::{Vec2, Vec3A, Vec4, Vec4Swizzles};
 // ---
 
 // NOTE: Keep in sync with `colormap.wgsl`!
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u32)]
 pub enum ColorMap {
     Grayscale = 0,
@@ -14,6 +14,37 @@ pub enum ColorMap {
     ColorMapPlasma = 3,
     ColorMapMagma = 4,
     ColorMapInferno = 5,
+    ColorMapCoolWarm = 6,
+    ColorMapRdBu = 7,
+    ColorMapTwilight = 8,
+}
+
+/// What kind of values a [`ColorMap`] is meant for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMapKind {
+    /// Lightness increases monotonically, for values going from low to high.
+    Sequential,
+
+    /// Two hues diverging from a neutral center, for values around a meaningful midpoint.
+    Diverging,
+
+    /// Both ends have the same color, for periodic values like angles.
+    Cyclic,
+}
+
+impl ColorMap {
+    pub fn kind(self) -> ColorMapKind {
+        match self {
+            ColorMap::Grayscale
+            | ColorMap::ColorMapTurbo
+            | ColorMap::ColorMapViridis
+            | ColorMap::ColorMapPlasma
+            | ColorMap::ColorMapMagma
+            | ColorMap::ColorMapInferno => ColorMapKind::Sequential,
+            ColorMap::ColorMapCoolWarm | ColorMap::ColorMapRdBu => ColorMapKind::Diverging,
+            ColorMap::ColorMapTwilight => ColorMapKind::Cyclic,
+        }
+    }
 }
 
 pub fn colormap_srgb(which: ColorMap, t: f32) -> [u8; 4] {
@@ -24,6 +55,9 @@ pub fn colormap_srgb(which: ColorMap, t: f32) -> [u8; 4] {
         ColorMap::ColorMapPlasma => colormap_plasma_srgb(t),
         ColorMap::ColorMapMagma => colormap_magma_srgb(t),
         ColorMap::ColorMapInferno => colormap_inferno_srgb(t),
+        ColorMap::ColorMapCoolWarm => colormap_coolwarm_srgb(t),
+        ColorMap::ColorMapRdBu => colormap_rdbu_srgb(t),
+        ColorMap::ColorMapTwilight => colormap_twilight_srgb(t),
     }
 }
 
@@ -158,3 +192,85 @@ pub fn colormap_inferno_srgb(t: f32) -> [u8; 4] {
     let c = c * 255.0;
     [c.x as u8, c.y as u8, c.z as u8, 255]
 }
+
+// --- Lookup table color maps ---
+
+/// Returns an sRGB color from a lookup table of evenly spaced sRGB colors, assuming `t` is normalized.
+///
+/// Colors in between entries are linearly interpolated in sRGB space, like `colormap_lut_srgb`
+/// in `colormap.wgsl` does.
+pub fn colormap_lut_srgb(lut: &[[u8; 4]], t: f32) -> [u8; 4] {
+    debug_assert!((0.0..=1.0).contains(&t));
+
+    match lut {
+        [] => [0, 0, 0, 255],
+        [color] => *color,
+        _ => {
+            let x = t.clamp(0.0, 1.0) * (lut.len() - 1) as f32;
+            let i = (x as usize).min(lut.len() - 2);
+            let (a, b, f) = (lut[i], lut[i + 1], x - i as f32);
+            std::array::from_fn(|c| (a[c] as f32 + (b[c] as f32 - a[c] as f32) * f + 0.5) as u8)
+        }
+    }
+}
+
+// Diverging color map by Kenneth Moreland, sampled from its smooth cool-warm definition.
+// See https://www.kennethmoreland.com/color-maps/
+
+/// Returns sRGB from the diverging CoolWarm color map, assuming `t` is normalized.
+pub fn colormap_coolwarm_srgb(t: f32) -> [u8; 4] {
+    const LUT: [[u8; 4]; 9] = [
+        [59, 76, 192, 255],
+        [98, 130, 234, 255],
+        [141, 176, 254, 255],
+        [184, 208, 249, 255],
+        [221, 221, 221, 255],
+        [245, 196, 173, 255],
+        [244, 154, 123, 255],
+        [222, 96, 77, 255],
+        [180, 4, 38, 255],
+    ];
+    colormap_lut_srgb(&LUT, t)
+}
+
+// Diverging color map from ColorBrewer, by Cynthia Brewer.
+// See https://colorbrewer2.org/, licensed under Apache-2.0.
+
+/// Returns sRGB from the diverging red to blue color map, assuming `t` is normalized.
+pub fn colormap_rdbu_srgb(t: f32) -> [u8; 4] {
+    const LUT: [[u8; 4]; 11] = [
+        [103, 0, 31, 255],
+        [178, 24, 43, 255],
+        [214, 96, 77, 255],
+        [244, 165, 130, 255],
+        [253, 219, 199, 255],
+        [247, 247, 247, 255],
+        [209, 229, 240, 255],
+        [146, 197, 222, 255],
+        [67, 147, 195, 255],
+        [33, 102, 172, 255],
+        [5, 48, 97, 255],
+    ];
+    colormap_lut_srgb(&LUT, t)
+}
+
+// Cyclic color map, sampled from matplotlib's twilight by Bastian Bechtold.
+// See https://github.com/bastibe/twilight, licensed under MIT.
+
+/// Returns sRGB from the cyclic Twilight color map, assuming `t` is normalized.
+///
+/// Both `t = 0` and `t = 1` map to the same color.
+pub fn colormap_twilight_srgb(t: f32) -> [u8; 4] {
+    const LUT: [[u8; 4]; 9] = [
+        [226, 217, 226, 255],
+        [163, 183, 205, 255],
+        [104, 132, 194, 255],
+        [92, 71, 170, 255],
+        [47, 20, 55, 255],
+        [125, 38, 89, 255],
+        [184, 88, 78, 255],
+        [205, 160, 140, 255],
+        [226, 217, 226, 255],
+    ];
+    colormap_lut_srgb(&LUT, t)
+}
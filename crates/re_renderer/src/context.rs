@@ -4,11 +4,14 @@ use parking_lot::{Mutex, RwLock};
 use type_map::concurrent::{self, TypeMap};
 
 use crate::{
-    allocator::CpuWriteGpuReadBelt,
+    allocator::{CpuWriteGpuReadBelt, GpuReadbackBelt},
     config::RenderContextConfig,
     global_bindings::GlobalBindings,
+    glyph_atlas::GlyphAtlas,
     renderer::Renderer,
-    resource_managers::{MeshManager, TextureManager2D},
+    resource_managers::{
+        MeshManager, PointCloudManager, TextureManager2D, VolumeManager, VoxelGridManager,
+    },
     wgpu_resources::WgpuResourcePools,
     FileResolver, FileServer, FileSystem, RecommendedFileResolver,
 };
@@ -26,8 +29,13 @@ pub struct RenderContext {
     pub(crate) err_tracker: std::sync::Arc<crate::error_tracker::ErrorTracker>,
 
     pub mesh_manager: RwLock<MeshManager>,
+    pub point_cloud_manager: RwLock<PointCloudManager>,
+    pub volume_manager: RwLock<VolumeManager>,
+    pub voxel_grid_manager: RwLock<VoxelGridManager>,
+    pub glyph_atlas: RwLock<GlyphAtlas>,
     pub texture_manager_2d: TextureManager2D,
     pub cpu_write_gpu_read_belt: Mutex<CpuWriteGpuReadBelt>,
+    pub gpu_readback_belt: Mutex<GpuReadbackBelt>,
 
     /// List of unfinished queue submission via this context.
     ///
@@ -165,6 +173,13 @@ impl RenderContext {
             &device,
             &mut resolver,
         )));
+        let point_cloud_manager =
+            RwLock::new(PointCloudManager::new(renderers.get_mut().get_or_create(
+                &shared_renderer_data,
+                &mut gpu_resources,
+                &device,
+                &mut resolver,
+            )));
         let texture_manager_2d =
             TextureManager2D::new(device.clone(), queue.clone(), &mut gpu_resources.textures);
 
@@ -185,8 +200,13 @@ impl RenderContext {
             gpu_resources,
 
             mesh_manager,
+            point_cloud_manager,
+            volume_manager: Default::default(),
+            voxel_grid_manager: Default::default(),
+            glyph_atlas: Default::default(),
             texture_manager_2d,
             cpu_write_gpu_read_belt: Mutex::new(CpuWriteGpuReadBelt::new(Self::CPU_WRITE_GPU_READ_BELT_DEFAULT_CHUNK_SIZE.unwrap())),
+            gpu_readback_belt: Mutex::new(GpuReadbackBelt::default()),
 
             resolver,
 
@@ -217,6 +237,10 @@ impl RenderContext {
                 newest_submission_to_wait_for,
             ));
         }
+
+        // Make sure map callbacks are called, e.g. for `GpuReadbackBelt`.
+        // (no-op on the web, where the browser takes care of this)
+        self.device.poll(wgpu::Maintain::Poll);
     }
 
     /// Call this at the beginning of a new frame.
@@ -243,6 +267,11 @@ impl RenderContext {
         };
         let frame_index = self.active_frame.frame_index;
 
+        // Readback buffers of the previous frame were submitted by now, request their mapping.
+        self.gpu_readback_belt
+            .lock()
+            .after_queue_submit(frame_index);
+
         // Tick the error tracker so that it knows when to reset!
         // Note that we're ticking on begin_frame rather than raw frames, which
         // makes a world of difference when we're in a poisoned state.
@@ -258,6 +287,10 @@ impl RenderContext {
         }
 
         self.mesh_manager.get_mut().begin_frame(frame_index);
+        self.point_cloud_manager.get_mut().begin_frame(frame_index);
+        self.volume_manager.get_mut().begin_frame(frame_index);
+        self.voxel_grid_manager.get_mut().begin_frame(frame_index);
+        self.glyph_atlas.get_mut().begin_frame();
         self.texture_manager_2d.begin_frame(frame_index);
 
         {
@@ -1,8 +1,9 @@
 use crate::{
+    lighting::ShadowMapProcessor,
     wgpu_buffer_types,
     wgpu_resources::{
         BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
-        GpuSamplerHandle, SamplerDesc, WgpuResourcePools,
+        GpuSamplerHandle, GpuTexture, SamplerDesc, TextureDesc, WgpuResourcePools,
     },
 };
 
@@ -47,12 +48,37 @@ pub(crate) struct FrameUniformBuffer {
 
     /// Factor used to compute depth offsets, see `depth_offset.wgsl`.
     pub depth_offset_factor: wgpu_buffer_types::F32RowPadded,
+
+    /// Normalized direction towards the key light in world space.
+    pub key_light_direction: glam::Vec3,
+
+    /// Intensity of the uniform ambient light.
+    pub ambient_intensity: f32,
+
+    /// Linear color of the key light multiplied with its intensity.
+    pub key_light_radiance: glam::Vec3,
+
+    /// World space distance positions are moved along their normal before looking them up in the shadow map.
+    pub shadow_normal_offset: f32,
+
+    /// Transforms world positions to shadow map texture coordinates in `xy` and shadow map depth in `z`.
+    pub shadow_uv_from_world: wgpu_buffer_types::Mat4,
+
+    /// Size of a shadow map texel in texture coordinates.
+    /// Zero if there are no shadows.
+    pub shadow_map_texel_size: wgpu_buffer_types::F32RowPadded,
+
+    pub end_padding: [wgpu_buffer_types::PaddingRow; 32 - 23],
 }
 
 pub(crate) struct GlobalBindings {
     pub(crate) layout: GpuBindGroupLayoutHandle,
     nearest_neighbor_sampler: GpuSamplerHandle,
     trilinear_sampler: GpuSamplerHandle,
+    shadow_map_sampler: GpuSamplerHandle,
+
+    /// Bound instead of a shadow map if there is none.
+    placeholder_shadow_map: GpuTexture,
 }
 
 impl GlobalBindings {
@@ -93,6 +119,24 @@ impl GlobalBindings {
                             ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                             count: None,
                         },
+                        // Shadow map of the key light.
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Depth,
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        // Comparison sampler for the shadow map.
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 4,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                            count: None,
+                        },
                     ],
                 },
             ),
@@ -119,16 +163,51 @@ impl GlobalBindings {
                     ..Default::default()
                 },
             ),
+            shadow_map_sampler: pools.samplers.get_or_create(
+                device,
+                &SamplerDesc {
+                    label: "shadow map".into(),
+                    mag_filter: wgpu::FilterMode::Linear,
+                    min_filter: wgpu::FilterMode::Linear,
+                    address_mode_u: wgpu::AddressMode::ClampToEdge,
+                    address_mode_v: wgpu::AddressMode::ClampToEdge,
+                    address_mode_w: wgpu::AddressMode::ClampToEdge,
+                    // Reverse-z: lit if the position is at least as close to the light as the shadow map entry.
+                    compare: Some(wgpu::CompareFunction::GreaterEqual),
+                    ..Default::default()
+                },
+            ),
+            // Never sampled since `shadow_map_texel_size` is zero without shadows.
+            placeholder_shadow_map: pools.textures.alloc(
+                device,
+                &TextureDesc {
+                    label: "placeholder shadow map".into(),
+                    size: wgpu::Extent3d {
+                        width: 1,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: ShadowMapProcessor::SHADOW_MAP_FORMAT,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                },
+            ),
         }
     }
 
     /// Creates a bind group that follows the global bind group layout.
+    ///
+    /// Binds a placeholder if no shadow map is passed.
     pub fn create_bind_group(
         &self,
         pools: &mut WgpuResourcePools,
         device: &wgpu::Device,
         frame_uniform_buffer_binding: BindGroupEntry,
+        shadow_map: Option<&GpuTexture>,
     ) -> GpuBindGroup {
+        let shadow_map = shadow_map.unwrap_or(&self.placeholder_shadow_map);
         pools.bind_groups.alloc(
             device,
             pools,
@@ -139,6 +218,8 @@ impl GlobalBindings {
                     frame_uniform_buffer_binding,
                     BindGroupEntry::Sampler(self.nearest_neighbor_sampler),
                     BindGroupEntry::Sampler(self.trilinear_sampler),
+                    BindGroupEntry::DefaultTextureView(shadow_map.handle),
+                    BindGroupEntry::Sampler(self.shadow_map_sampler),
                 ],
                 layout: self.layout,
             },
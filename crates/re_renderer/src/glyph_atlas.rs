@@ -0,0 +1,342 @@
+//! Rasterizes glyphs of a single font on demand into a texture atlas, used to draw text labels.
+//!
+//! Glyphs are rasterized once at [`GlyphAtlas::RASTER_HEIGHT`] and scaled with bilinear filtering when drawn.
+//! The atlas is packed with simple shelves (rows of glyphs, each as high as its highest glyph).
+//! Once it is full, new glyphs are skipped for the rest of the frame and the atlas is cleared
+//! at the beginning of the next one, so that it only ends up holding the glyphs still in use.
+
+use ab_glyph::{Font as _, ScaleFont as _};
+
+use crate::wgpu_resources::{GpuTexture, GpuTexturePool, TextureDesc};
+
+/// A glyph's location in the atlas.
+#[derive(Clone, Copy)]
+struct AtlasGlyph {
+    /// Top left corner in the atlas, in texels.
+    atlas_min: glam::UVec2,
+    size: glam::UVec2,
+
+    /// Offset of the glyph's top left corner from the pen position on the baseline, in pixels of the raster height.
+    offset: glam::Vec2,
+}
+
+/// A single glyph of a laid out text.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GlyphQuad {
+    /// Rectangle of the glyph in line heights, relative to the top center of the text, y pointing down.
+    pub min: glam::Vec2,
+    pub max: glam::Vec2,
+
+    /// Texture coordinates in the atlas.
+    pub uv_min: glam::Vec2,
+    pub uv_max: glam::Vec2,
+}
+
+/// Result of [`GlyphAtlas::layout`].
+pub(crate) struct TextLayout {
+    pub glyphs: Vec<GlyphQuad>,
+
+    /// Width and height of the text, in line heights.
+    pub size: glam::Vec2,
+}
+
+/// Glyphs of a single font, rasterized into a texture.
+///
+/// There is no font by default, text can only be laid out once one was set with [`GlyphAtlas::set_font`].
+pub struct GlyphAtlas {
+    font: Option<ab_glyph::FontArc>,
+    glyphs: ahash::HashMap<ab_glyph::GlyphId, Option<AtlasGlyph>>,
+
+    /// Coverage of all glyphs, [`GlyphAtlas::SIZE`] squared.
+    coverage: Vec<u8>,
+
+    /// Top left corner of where the next glyph goes.
+    shelf_cursor: glam::UVec2,
+
+    /// Height of the highest glyph in the current shelf.
+    shelf_height: u32,
+
+    /// A glyph didn't fit in, the atlas is cleared on the next frame.
+    is_full: bool,
+
+    /// `coverage` changed since the last upload.
+    is_dirty: bool,
+    texture: Option<GpuTexture>,
+}
+
+impl Default for GlyphAtlas {
+    fn default() -> Self {
+        Self {
+            font: None,
+            glyphs: Default::default(),
+            coverage: vec![0; (Self::SIZE * Self::SIZE) as usize],
+            shelf_cursor: glam::UVec2::ZERO,
+            shelf_height: 0,
+            is_full: false,
+            is_dirty: true,
+            texture: None,
+        }
+    }
+}
+
+impl GlyphAtlas {
+    /// Width and height of the atlas texture.
+    pub const SIZE: u32 = 1024;
+
+    /// Height of a line of text in pixels when it's rasterized into the atlas.
+    pub const RASTER_HEIGHT: f32 = 32.0;
+
+    /// Empty texels around every glyph, so that bilinear filtering doesn't pick up the neighbors.
+    const PADDING: u32 = 1;
+
+    /// Sets the font for all text, given as the contents of a TrueType or OpenType file.
+    ///
+    /// Clears all previously rasterized glyphs.
+    pub fn set_font(&mut self, font_data: Vec<u8>) -> Result<(), ab_glyph::InvalidFont> {
+        self.font = Some(ab_glyph::FontArc::try_from_vec(font_data)?);
+        self.clear();
+        Ok(())
+    }
+
+    /// Whether a font was set with [`GlyphAtlas::set_font`].
+    pub fn has_font(&self) -> bool {
+        self.font.is_some()
+    }
+
+    fn clear(&mut self) {
+        self.glyphs.clear();
+        self.coverage.fill(0);
+        self.shelf_cursor = glam::UVec2::ZERO;
+        self.shelf_height = 0;
+        self.is_full = false;
+        self.is_dirty = true;
+    }
+
+    pub(crate) fn begin_frame(&mut self) {
+        if self.is_full {
+            re_log::debug!("Glyph atlas is full, clearing it");
+            self.clear();
+        }
+    }
+
+    /// Lays out the given text, rasterizing all glyphs that aren't in the atlas yet.
+    ///
+    /// Lines are separated by `\n` and centered horizontally.
+    /// Returns `None` if there is no font.
+    pub(crate) fn layout(&mut self, text: &str) -> Option<TextLayout> {
+        // Cloned since rasterizing glyphs needs `self` mutably, it's only a reference count.
+        let font_arc = self.font.clone()?;
+        let font = font_arc.as_scaled(Self::RASTER_HEIGHT);
+        let line_height = font.height();
+        let line_advance = (font.height() + font.line_gap()) / line_height;
+
+        let mut glyphs = Vec::with_capacity(text.len());
+        let mut width = 0.0_f32;
+        let mut num_lines = 0;
+
+        for (line_index, line) in text.lines().enumerate() {
+            num_lines += 1;
+
+            let first_glyph_in_line = glyphs.len();
+            let top = line_index as f32 * line_advance;
+            let mut pen_x = 0.0;
+            let mut previous = None;
+
+            for c in line.chars() {
+                let glyph_id = font.glyph_id(c);
+                if let Some(previous) = previous {
+                    pen_x += font.kern(previous, glyph_id);
+                }
+                previous = Some(glyph_id);
+
+                if let Some(glyph) = self.glyph(glyph_id) {
+                    let min = glam::vec2(pen_x, font.ascent()) + glyph.offset;
+                    let uv_min = glyph.atlas_min.as_vec2() / Self::SIZE as f32;
+                    glyphs.push(GlyphQuad {
+                        min: min / line_height + glam::vec2(0.0, top),
+                        max: (min + glyph.size.as_vec2()) / line_height + glam::vec2(0.0, top),
+                        uv_min,
+                        uv_max: uv_min + glyph.size.as_vec2() / Self::SIZE as f32,
+                    });
+                }
+                pen_x += font.h_advance(glyph_id);
+            }
+
+            // Center the line around x = 0.
+            let line_width = pen_x / line_height;
+            for glyph in &mut glyphs[first_glyph_in_line..] {
+                glyph.min.x -= line_width * 0.5;
+                glyph.max.x -= line_width * 0.5;
+            }
+            width = width.max(line_width);
+        }
+
+        let height = if num_lines == 0 {
+            0.0
+        } else {
+            (num_lines - 1) as f32 * line_advance + 1.0
+        };
+
+        Some(TextLayout {
+            glyphs,
+            size: glam::vec2(width, height),
+        })
+    }
+
+    /// Returns the glyph's location in the atlas, rasterizing it if needed.
+    ///
+    /// `None` for glyphs without any outline, like spaces, and glyphs that didn't fit into the atlas.
+    fn glyph(&mut self, glyph_id: ab_glyph::GlyphId) -> Option<AtlasGlyph> {
+        if let Some(glyph) = self.glyphs.get(&glyph_id) {
+            return *glyph;
+        }
+
+        let font = self.font.as_ref()?;
+        let Some(outline) = font.outline_glyph(glyph_id.with_scale(Self::RASTER_HEIGHT)) else {
+            self.glyphs.insert(glyph_id, None);
+            return None;
+        };
+        let bounds = outline.px_bounds();
+        let size = glam::uvec2(bounds.width() as u32, bounds.height() as u32);
+
+        // Start a new shelf if the glyph doesn't fit into the current one.
+        if self.shelf_cursor.x + size.x + Self::PADDING > Self::SIZE {
+            self.shelf_cursor = glam::uvec2(0, self.shelf_cursor.y + self.shelf_height);
+            self.shelf_height = 0;
+        }
+        if self.shelf_cursor.x + size.x + Self::PADDING > Self::SIZE
+            || self.shelf_cursor.y + size.y + Self::PADDING > Self::SIZE
+        {
+            re_log::warn_once!("Glyph atlas is full, some text is missing glyphs");
+            self.is_full = true;
+            return None;
+        }
+
+        let atlas_min = self.shelf_cursor + glam::UVec2::splat(Self::PADDING);
+        outline.draw(|x, y, coverage| {
+            let index = (atlas_min.y + y) * Self::SIZE + atlas_min.x + x;
+            self.coverage[index as usize] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+        });
+        self.shelf_cursor.x += size.x + Self::PADDING;
+        self.shelf_height = self.shelf_height.max(size.y + Self::PADDING);
+        self.is_dirty = true;
+
+        let glyph = AtlasGlyph {
+            atlas_min,
+            size,
+            offset: glam::vec2(bounds.min.x, bounds.min.y),
+        };
+        self.glyphs.insert(glyph_id, Some(glyph));
+        Some(glyph)
+    }
+
+    /// Returns the atlas texture, uploading all glyphs that were added since the last call.
+    pub(crate) fn texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        textures: &GpuTexturePool,
+    ) -> GpuTexture {
+        let texture = self.texture.get_or_insert_with(|| {
+            textures.alloc(
+                device,
+                &TextureDesc {
+                    label: "glyph atlas".into(),
+                    size: wgpu::Extent3d {
+                        width: Self::SIZE,
+                        height: Self::SIZE,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::R8Unorm,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                },
+            )
+        });
+
+        if self.is_dirty {
+            crate::profile_scope!("write_texture");
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &texture.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &self.coverage,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: std::num::NonZeroU32::new(Self::SIZE),
+                    rows_per_image: None,
+                },
+                wgpu::Extent3d {
+                    width: Self::SIZE,
+                    height: Self::SIZE,
+                    depth_or_array_layers: 1,
+                },
+            );
+            self.is_dirty = false;
+        }
+
+        texture.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GlyphAtlas;
+
+    fn atlas_with_font() -> GlyphAtlas {
+        let font_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../re_ui/data/Inter-Medium.otf"
+        );
+        let mut atlas = GlyphAtlas::default();
+        atlas.set_font(std::fs::read(font_path).unwrap()).unwrap();
+        atlas
+    }
+
+    #[test]
+    fn no_layout_without_font() {
+        let mut atlas = GlyphAtlas::default();
+        assert!(atlas.layout("text").is_none());
+    }
+
+    #[test]
+    fn glyphs_are_rasterized_once() {
+        let mut atlas = atlas_with_font();
+
+        let layout = atlas.layout("AA A").unwrap();
+        assert_eq!(layout.glyphs.len(), 3, "spaces have no glyph");
+        assert_eq!(layout.glyphs[0].uv_min, layout.glyphs[1].uv_min);
+        assert_eq!(atlas.glyphs.len(), 2, "'A' and ' '");
+    }
+
+    #[test]
+    fn full_atlas_is_cleared_on_next_frame() {
+        let mut atlas = atlas_with_font();
+        atlas.layout("A").unwrap();
+
+        // Pretend the atlas is filled up to its last row, so no other glyph fits in.
+        atlas.shelf_cursor = glam::uvec2(0, GlyphAtlas::SIZE - 1);
+        let layout = atlas.layout("AB").unwrap();
+        assert_eq!(layout.glyphs.len(), 1, "'B' doesn't fit anymore");
+        assert!(atlas.is_full);
+
+        // Stays full for the rest of the frame.
+        atlas.layout("B").unwrap();
+        assert!(atlas.is_full);
+
+        atlas.begin_frame();
+        assert!(!atlas.is_full);
+        assert!(atlas.glyphs.is_empty());
+        assert_eq!(atlas.shelf_cursor, glam::UVec2::ZERO);
+        assert!(atlas.coverage.iter().all(|coverage| *coverage == 0));
+
+        let layout = atlas.layout("AB").unwrap();
+        assert_eq!(layout.glyphs.len(), 2);
+        assert!(!atlas.is_full);
+    }
+}
@@ -290,6 +290,9 @@ fn gather_instances_recursive(
                 mesh: Some(mesh.clone()),
                 world_from_mesh: transform,
                 additive_tint: Color32::TRANSPARENT,
+                opacity: 1.0,
+                outline_mask_ids: Default::default(),
+                picking_layer_id: Default::default(),
             });
         }
     }
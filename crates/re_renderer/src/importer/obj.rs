@@ -75,6 +75,9 @@ pub fn load_obj_from_buffer(
                 mesh: Some(Arc::new(mesh)),
                 world_from_mesh: glam::Affine3A::IDENTITY,
                 additive_tint: Color32::TRANSPARENT,
+                opacity: 1.0,
+                outline_mask_ids: Default::default(),
+                picking_layer_id: Default::default(),
             }
         })
         .collect())
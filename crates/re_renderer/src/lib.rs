@@ -20,21 +20,36 @@ mod context;
 mod debug_label;
 mod depth_offset;
 mod global_bindings;
+mod glyph_atlas;
+mod lighting;
 mod line_strip_builder;
+mod outlines;
+mod picking_layer;
 mod point_cloud_builder;
+mod point_cloud_lod;
 mod size;
 mod wgpu_buffer_types;
 mod wgpu_resources;
 
+pub use allocator::GpuReadbackIdentifier;
 pub use colormap::{
-    colormap_inferno_srgb, colormap_magma_srgb, colormap_plasma_srgb, colormap_srgb,
-    colormap_turbo_srgb, colormap_viridis_srgb, grayscale_srgb, ColorMap,
+    colormap_coolwarm_srgb, colormap_inferno_srgb, colormap_lut_srgb, colormap_magma_srgb,
+    colormap_plasma_srgb, colormap_rdbu_srgb, colormap_srgb, colormap_turbo_srgb,
+    colormap_twilight_srgb, colormap_viridis_srgb, grayscale_srgb, ColorMap, ColorMapKind,
 };
 pub use context::RenderContext;
 pub use debug_label::DebugLabel;
 pub use depth_offset::DepthOffset;
-pub use line_strip_builder::{LineStripBuilder, LineStripSeriesBuilder};
+pub use glyph_atlas::GlyphAtlas;
+pub use lighting::{LightingConfig, ShadowConfig, SsaoConfig};
+pub use line_strip_builder::{LineBatchBuilder, LineStripBuilder, LineStripSeriesBuilder};
+pub use outlines::{OutlineConfig, OutlineMaskPreference};
+pub use picking_layer::{
+    PickingLayerId, PickingLayerInstanceId, PickingLayerObjectId, PickingLayerProcessor,
+    PickingLayerRect, PickingResult,
+};
 pub use point_cloud_builder::{PointCloudBatchBuilder, PointCloudBuilder};
+pub use point_cloud_lod::PointCloudLodView;
 pub use size::Size;
 pub use view_builder::AutoSizeConfig;
 pub use wgpu_resources::WgpuResourcePoolStatistics;
@@ -0,0 +1,563 @@
+//! Lighting of shaded geometry: key light, ambient term, shadows & screen space ambient occlusion.
+//!
+//! How it works:
+//! =================
+//! The key light & ambient term are part of the frame uniform buffer, shaders that shade geometry
+//! (currently only meshes) use `shade_lambert` from `utils/lighting.wgsl`.
+//!
+//! Shadows:
+//! If enabled, all meshes are drawn a second time in [`crate::renderer::DrawPhase::ShadowMap`]
+//! into the depth-only shadow map, using an orthographic projection along the key light direction
+//! that covers the bounding sphere given by [`ShadowConfig`].
+//! The shadow map is part of the global bindings and sampled with 3x3 percentage closer filtering.
+//!
+//! Screen space ambient occlusion (SSAO):
+//! After all opaque geometry was drawn, a full screen pass estimates per pixel how much of the
+//! hemisphere around the surface is occluded by sampling the depth buffer
+//! (see [Crytek's original](https://developer.nvidia.com/gpugems/gpugems3/part-ii-light-and-shadows/chapter-12-high-quality-ambient-occlusion)
+//! and [John Chapman's hemisphere variant](https://john-chapman-graphics.blogspot.com/2013/01/ssao-tutorial.html)).
+//! The sample kernel is rotated per pixel in a 4x4 pattern, a second pass blurs the noise away
+//! and multiplies the result onto the main target.
+//! Semi-transparent geometry is drawn afterwards and is therefore not affected.
+
+use smallvec::smallvec;
+
+use crate::{
+    allocator::create_and_fill_uniform_buffer,
+    global_bindings::FrameUniformBuffer,
+    include_file,
+    view_builder::ViewBuilder,
+    wgpu_resources::{
+        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuRenderPipelineHandle,
+        GpuTexture, PipelineLayoutDesc, PoolError, RenderPipelineDesc, ShaderModuleDesc,
+        TextureDesc, WgpuResourcePools,
+    },
+    DebugLabel, RenderContext, Rgba,
+};
+
+/// Configures how shaded geometry in a view is lit.
+///
+/// The default is a white key light from above plus a constant ambient term,
+/// without shadows or ambient occlusion.
+#[derive(Clone, Debug)]
+pub struct LightingConfig {
+    /// Direction from the scene *towards* the key light in world space.
+    ///
+    /// Does not need to be normalized.
+    pub key_light_direction: glam::Vec3,
+
+    /// Color of the key light.
+    pub key_light_color: Rgba,
+
+    /// Intensity the key light color is multiplied with.
+    pub key_light_intensity: f32,
+
+    /// Intensity of the uniform ambient light.
+    pub ambient_intensity: f32,
+
+    /// If set, the key light casts shadows.
+    pub shadows: Option<ShadowConfig>,
+
+    /// If set, screen space ambient occlusion darkens creases and corners.
+    pub ssao: Option<SsaoConfig>,
+}
+
+impl Default for LightingConfig {
+    fn default() -> Self {
+        Self {
+            key_light_direction: glam::vec3(1.0, 2.0, 0.0),
+            key_light_color: Rgba::WHITE,
+            key_light_intensity: 1.0,
+            ambient_intensity: 0.2,
+            shadows: None,
+            ssao: None,
+        }
+    }
+}
+
+/// Configures the shadow map of the key light.
+#[derive(Clone, Debug)]
+pub struct ShadowConfig {
+    /// Center of a world space bounding sphere around everything that should cast and receive shadows.
+    pub bounds_center: glam::Vec3,
+
+    /// Radius of the bounding sphere around everything that should cast and receive shadows.
+    ///
+    /// The larger the radius, the coarser the shadows.
+    pub bounds_radius: f32,
+
+    /// Width & height of the shadow map in texels.
+    pub resolution: u32,
+}
+
+/// Configures screen space ambient occlusion.
+#[derive(Clone, Debug)]
+pub struct SsaoConfig {
+    /// World space radius around every pixel in which occluders are searched.
+    ///
+    /// Should be about the size of the details that should cast occlusion.
+    pub radius: f32,
+
+    /// How strongly occlusion darkens, 0.0 has no effect, 1.0 makes fully occluded pixels black.
+    pub intensity: f32,
+}
+
+impl Default for SsaoConfig {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            intensity: 1.0,
+        }
+    }
+}
+
+pub(crate) mod gpu_data {
+    use crate::wgpu_buffer_types;
+
+    /// Keep in sync with `lighting/ssao.wgsl`
+    #[repr(C, align(256))]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct SsaoUniformBuffer {
+        pub view_from_projection: wgpu_buffer_types::Mat4,
+        pub radius: wgpu_buffer_types::F32RowPadded,
+        pub intensity: wgpu_buffer_types::F32RowPadded,
+        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 6],
+    }
+}
+
+/// Orthographic projection of the key light covering the shadow bounds.
+pub(crate) struct ShadowProjection {
+    pub view_from_world: glam::Mat4,
+    pub projection_from_view: glam::Mat4,
+}
+
+impl ShadowProjection {
+    pub fn new(config: &ShadowConfig, key_light_direction: glam::Vec3) -> Self {
+        let direction = key_light_direction.normalize_or_zero();
+        let direction = if direction == glam::Vec3::ZERO {
+            glam::Vec3::Z
+        } else {
+            direction
+        };
+        // Small margin so that geometry touching the bounds doesn't get clipped.
+        let radius = config.bounds_radius.max(f32::EPSILON) * 1.01;
+        let eye = config.bounds_center + direction * radius;
+
+        Self {
+            view_from_world: glam::Mat4::look_at_rh(
+                eye,
+                config.bounds_center,
+                direction.any_orthonormal_vector(),
+            ),
+            // Note that we inverse z (by swapping near and far plane) to be consistent with the main view.
+            projection_from_view: glam::Mat4::orthographic_rh(
+                -radius,
+                radius,
+                -radius,
+                radius,
+                2.0 * radius,
+                0.0,
+            ),
+        }
+    }
+
+    /// Transforms world positions to shadow map texture coordinates in `xy` and shadow map depth in `z`.
+    pub fn shadow_uv_from_world(&self) -> glam::Mat4 {
+        let uv_from_projection = glam::Mat4::from_translation(glam::vec3(0.5, 0.5, 0.0))
+            * glam::Mat4::from_scale(glam::vec3(0.5, -0.5, 1.0));
+        uv_from_projection * self.projection_from_view * self.view_from_world
+    }
+}
+
+/// Owns the shadow map of a single view.
+pub(crate) struct ShadowMapProcessor {
+    label: DebugLabel,
+    shadow_map: GpuTexture,
+
+    /// Global bindings for the shadow map pass, with the light's projection as camera.
+    pub bind_group_0: GpuBindGroup,
+}
+
+impl ShadowMapProcessor {
+    pub const SHADOW_MAP_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+    /// Depth state that any [`wgpu::RenderPipeline`] drawing to the shadow map needs to use.
+    pub const SHADOW_MAP_DEPTH_STATE: Option<wgpu::DepthStencilState> =
+        ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE;
+
+    /// Multisample state that any [`wgpu::RenderPipeline`] drawing to the shadow map needs to use.
+    pub const SHADOW_MAP_MSAA_STATE: wgpu::MultisampleState = wgpu::MultisampleState {
+        count: 1,
+        mask: !0,
+        alpha_to_coverage_enabled: false,
+    };
+
+    pub fn new(
+        ctx: &mut RenderContext,
+        config: &ShadowConfig,
+        projection: &ShadowProjection,
+        view_name: &DebugLabel,
+        frame_uniform_buffer_content: &FrameUniformBuffer,
+    ) -> Self {
+        crate::profile_function!();
+
+        let resolution = config.resolution.max(1);
+        let shadow_map = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &TextureDesc {
+                label: format!("{view_name:?} - shadow map").into(),
+                size: wgpu::Extent3d {
+                    width: resolution,
+                    height: resolution,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: Self::SHADOW_MAP_MSAA_STATE.count,
+                dimension: wgpu::TextureDimension::D2,
+                format: Self::SHADOW_MAP_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+            },
+        );
+
+        let view_from_world = projection.view_from_world;
+        let world_from_view = view_from_world.inverse();
+        let frame_uniform_buffer = create_and_fill_uniform_buffer(
+            ctx,
+            format!("{view_name:?} - shadow map frame uniform buffer").into(),
+            FrameUniformBuffer {
+                view_from_world: glam::Affine3A::from_mat4(view_from_world).into(),
+                projection_from_view: projection.projection_from_view.into(),
+                projection_from_world: (projection.projection_from_view * view_from_world).into(),
+                camera_position: world_from_view.w_axis.truncate(),
+                camera_forward: -world_from_view.z_axis.truncate(),
+                tan_half_fov: glam::vec2(f32::INFINITY, f32::INFINITY).into(),
+                pixel_world_size_from_camera_distance: 2.0
+                    / projection.projection_from_view.y_axis.y
+                    / resolution as f32,
+                ..*frame_uniform_buffer_content
+            },
+        );
+        // The shadow map can't be sampled while it is drawn to, so this uses the placeholder.
+        let bind_group_0 = ctx.shared_renderer_data.global_bindings.create_bind_group(
+            &mut ctx.gpu_resources,
+            &ctx.device,
+            frame_uniform_buffer,
+            None,
+        );
+
+        Self {
+            label: view_name.clone(),
+            shadow_map,
+            bind_group_0,
+        }
+    }
+
+    pub fn shadow_map(&self) -> &GpuTexture {
+        &self.shadow_map
+    }
+
+    pub fn begin_render_pass<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+    ) -> wgpu::RenderPass<'a> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: DebugLabel::from(format!("{:?} - shadow map pass", self.label)).get(),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.shadow_map.default_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0.0), // 0.0 == far since we're using reverse-z
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        })
+    }
+}
+
+/// Owns the targets & passes for screen space ambient occlusion of a single view.
+pub(crate) struct SsaoProcessor {
+    label: DebugLabel,
+
+    occlusion_texture: GpuTexture,
+
+    bind_group_occlusion: GpuBindGroup,
+    bind_group_apply: GpuBindGroup,
+
+    render_pipeline_occlusion: GpuRenderPipelineHandle,
+    render_pipeline_apply: GpuRenderPipelineHandle,
+}
+
+impl SsaoProcessor {
+    /// Ambient visibility, i.e. one minus occlusion.
+    pub const OCCLUSION_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+
+    pub fn new(
+        ctx: &mut RenderContext,
+        config: &SsaoConfig,
+        view_name: &DebugLabel,
+        resolution_in_pixel: [u32; 2],
+        depth_buffer: &GpuTexture,
+        projection_from_view: glam::Mat4,
+    ) -> Self {
+        crate::profile_function!();
+
+        let occlusion_texture = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &TextureDesc {
+                label: format!("{view_name:?} - ssao occlusion").into(),
+                size: wgpu::Extent3d {
+                    width: resolution_in_pixel[0],
+                    height: resolution_in_pixel[1],
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: Self::OCCLUSION_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+            },
+        );
+
+        let uniform_buffer = create_and_fill_uniform_buffer(
+            ctx,
+            format!("{view_name:?} - ssao uniform buffer").into(),
+            gpu_data::SsaoUniformBuffer {
+                view_from_projection: projection_from_view.inverse().into(),
+                radius: config.radius.into(),
+                intensity: config.intensity.clamp(0.0, 1.0).into(),
+                end_padding: Default::default(),
+            },
+        );
+
+        let global_bindings_layout = ctx.shared_renderer_data.global_bindings.layout;
+        let pools = &mut ctx.gpu_resources;
+        let bind_group_layout_occlusion = pools.bind_group_layouts.get_or_create(
+            &ctx.device,
+            &BindGroupLayoutDesc {
+                label: "ssao - occlusion".into(),
+                entries: vec![
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: true,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: (std::mem::size_of::<gpu_data::SsaoUniformBuffer>()
+                                as u64)
+                                .try_into()
+                                .ok(),
+                        },
+                        count: None,
+                    },
+                ],
+            },
+        );
+        let bind_group_layout_apply = pools.bind_group_layouts.get_or_create(
+            &ctx.device,
+            &BindGroupLayoutDesc {
+                label: "ssao - apply".into(),
+                entries: vec![wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                }],
+            },
+        );
+
+        let screen_triangle_vertex_shader = pools.shader_modules.get_or_create(
+            &ctx.device,
+            &mut ctx.resolver,
+            &ShaderModuleDesc {
+                label: "screen_triangle (vertex)".into(),
+                source: include_file!("../shader/screen_triangle.wgsl"),
+            },
+        );
+        let render_pipeline_occlusion = pools.render_pipelines.get_or_create(
+            &ctx.device,
+            &RenderPipelineDesc {
+                label: "ssao - occlusion".into(),
+                pipeline_layout: pools.pipeline_layouts.get_or_create(
+                    &ctx.device,
+                    &PipelineLayoutDesc {
+                        label: "ssao - occlusion".into(),
+                        entries: vec![global_bindings_layout, bind_group_layout_occlusion],
+                    },
+                    &pools.bind_group_layouts,
+                ),
+                vertex_entrypoint: "main".into(),
+                vertex_handle: screen_triangle_vertex_shader,
+                fragment_entrypoint: "main".into(),
+                fragment_handle: pools.shader_modules.get_or_create(
+                    &ctx.device,
+                    &mut ctx.resolver,
+                    &ShaderModuleDesc {
+                        label: "ssao (fragment)".into(),
+                        source: include_file!("../shader/lighting/ssao.wgsl"),
+                    },
+                ),
+                vertex_buffers: smallvec![],
+                render_targets: smallvec![Some(Self::OCCLUSION_FORMAT.into())],
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_apply = pools.render_pipelines.get_or_create(
+            &ctx.device,
+            &RenderPipelineDesc {
+                label: "ssao - apply".into(),
+                pipeline_layout: pools.pipeline_layouts.get_or_create(
+                    &ctx.device,
+                    &PipelineLayoutDesc {
+                        label: "ssao - apply".into(),
+                        entries: vec![bind_group_layout_apply],
+                    },
+                    &pools.bind_group_layouts,
+                ),
+                vertex_entrypoint: "main".into(),
+                vertex_handle: screen_triangle_vertex_shader,
+                fragment_entrypoint: "main".into(),
+                fragment_handle: pools.shader_modules.get_or_create(
+                    &ctx.device,
+                    &mut ctx.resolver,
+                    &ShaderModuleDesc {
+                        label: "ssao_apply (fragment)".into(),
+                        source: include_file!("../shader/lighting/ssao_apply.wgsl"),
+                    },
+                ),
+                vertex_buffers: smallvec![],
+                // Multiplies the blurred ambient visibility onto the main target.
+                render_targets: smallvec![Some(wgpu::ColorTargetState {
+                    format: ViewBuilder::MAIN_TARGET_COLOR_FORMAT,
+                    blend: Some(wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::Zero,
+                            dst_factor: wgpu::BlendFactor::Src,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::Zero,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                    }),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+
+        let bind_group_occlusion = ctx.gpu_resources.bind_groups.alloc(
+            &ctx.device,
+            &ctx.gpu_resources,
+            &BindGroupDesc {
+                label: "ssao - occlusion".into(),
+                entries: smallvec![
+                    BindGroupEntry::DefaultTextureView(depth_buffer.handle),
+                    uniform_buffer
+                ],
+                layout: bind_group_layout_occlusion,
+            },
+        );
+        let bind_group_apply = ctx.gpu_resources.bind_groups.alloc(
+            &ctx.device,
+            &ctx.gpu_resources,
+            &BindGroupDesc {
+                label: "ssao - apply".into(),
+                entries: smallvec![BindGroupEntry::DefaultTextureView(occlusion_texture.handle)],
+                layout: bind_group_layout_apply,
+            },
+        );
+
+        Self {
+            label: view_name.clone(),
+            occlusion_texture,
+            bind_group_occlusion,
+            bind_group_apply,
+            render_pipeline_occlusion,
+            render_pipeline_apply,
+        }
+    }
+
+    /// Computes ambient occlusion from the depth buffer and darkens the resolved main target accordingly.
+    ///
+    /// Needs to run after the depth buffer was filled, but before the main target is used as a texture.
+    pub fn apply(
+        &self,
+        pools: &WgpuResourcePools,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group_0: &GpuBindGroup,
+        main_target_resolved: &GpuTexture,
+    ) -> Result<(), PoolError> {
+        crate::profile_function!();
+
+        let pipeline_occlusion = pools
+            .render_pipelines
+            .get_resource(self.render_pipeline_occlusion)?;
+        let pipeline_apply = pools
+            .render_pipelines
+            .get_resource(self.render_pipeline_apply)?;
+
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: DebugLabel::from(format!("{:?} - ssao occlusion", self.label)).get(),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.occlusion_texture.default_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE), // Overwritten by the full screen triangle.
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            pass.set_pipeline(pipeline_occlusion);
+            pass.set_bind_group(0, bind_group_0, &[]);
+            pass.set_bind_group(1, &self.bind_group_occlusion, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: DebugLabel::from(format!("{:?} - ssao apply", self.label)).get(),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &main_target_resolved.default_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            pass.set_pipeline(pipeline_apply);
+            pass.set_bind_group(0, &self.bind_group_apply, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
+use std::ops::Range;
+
 use crate::{
     renderer::{LineBatchInfo, LineDrawData, LineStripFlags, LineStripInfo, LineVertex},
-    Color32, DebugLabel, Size,
+    Color32, DebugLabel, OutlineMaskPreference, PickingLayerInstanceId, PickingLayerObjectId, Size,
 };
 
 /// Builder for a vector of line strips, making it easy to create [`crate::renderer::LineDrawData`].
@@ -33,6 +35,9 @@ where
             label: label.into(),
             world_from_obj: glam::Mat4::IDENTITY,
             line_vertex_count: 0,
+            overall_outline_mask_ids: OutlineMaskPreference::NONE,
+            additional_outline_mask_ids_vertex_ranges: Vec::new(),
+            picking_object_id: PickingLayerObjectId::default(),
         });
 
         LineBatchBuilder(self)
@@ -135,6 +140,41 @@ where
         self
     }
 
+    /// Sets an outline mask for every strip in the batch.
+    #[inline]
+    pub fn outline_mask_ids(mut self, outline_mask_ids: OutlineMaskPreference) -> Self {
+        self.batch_mut().overall_outline_mask_ids = outline_mask_ids;
+        self
+    }
+
+    /// Sets the picking object id for every strip in the batch.
+    #[inline]
+    pub fn picking_object_id(mut self, picking_object_id: PickingLayerObjectId) -> Self {
+        self.batch_mut().picking_object_id = picking_object_id;
+        self
+    }
+
+    /// Creates a builder for all strips added since `old_strip_count`,
+    /// whose vertices started at `vertex_range_start` within the batch.
+    fn strip_builder(
+        &mut self,
+        old_strip_count: usize,
+        vertex_range_start: u32,
+    ) -> LineStripBuilder<'_, PerStripUserData> {
+        let batch = self
+            .0
+            .batches
+            .last_mut()
+            .expect("batch should have been added on LineBatchBuilder creation");
+
+        LineStripBuilder {
+            strips: &mut self.0.strips[old_strip_count..],
+            user_data: &mut self.0.strip_user_data[old_strip_count..],
+            vertex_range: vertex_range_start..batch.line_vertex_count,
+            outline_mask_ranges: &mut batch.additional_outline_mask_ids_vertex_ranges,
+        }
+    }
+
     /// Adds a 3D series of line connected points.
     pub fn add_strip(
         &mut self,
@@ -142,6 +182,7 @@ where
     ) -> LineStripBuilder<'_, PerStripUserData> {
         let old_len = self.0.strips.len();
         let strip_index = old_len as _;
+        let vertex_range_start = self.batch_mut().line_vertex_count;
 
         self.add_vertices(points, strip_index);
 
@@ -149,10 +190,7 @@ where
         self.0.strips.push(LineStripInfo::default());
         self.0.strip_user_data.push(PerStripUserData::default());
 
-        LineStripBuilder {
-            strips: &mut self.0.strips[old_len..],
-            user_data: &mut self.0.strip_user_data[old_len..],
-        }
+        self.strip_builder(old_len, vertex_range_start)
     }
 
     /// Adds a single 3D line segment connecting two points.
@@ -171,6 +209,7 @@ where
         segments: impl Iterator<Item = (glam::Vec3, glam::Vec3)>,
     ) -> LineStripBuilder<'_, PerStripUserData> {
         let mut num_strips = self.0.strips.len() as u32;
+        let vertex_range_start = self.batch_mut().line_vertex_count;
 
         // It's tempting to assign the same strip to all vertices, after all they share
         // color/radius/tag properties.
@@ -191,10 +230,7 @@ where
             .strip_user_data
             .extend(std::iter::repeat(PerStripUserData::default()).take(num_strips_added));
 
-        LineStripBuilder {
-            strips: &mut self.0.strips[old_len..],
-            user_data: &mut self.0.strip_user_data[old_len..],
-        }
+        self.strip_builder(old_len, vertex_range_start)
     }
 
     /// Add box outlines from a unit cube transformed by `transform`.
@@ -347,6 +383,10 @@ where
 pub struct LineStripBuilder<'a, PerStripUserData> {
     strips: &'a mut [LineStripInfo],
     user_data: &'a mut [PerStripUserData],
+
+    /// Range of the vertices of these strips, relative to the start of the batch.
+    vertex_range: Range<u32>,
+    outline_mask_ranges: &'a mut Vec<(Range<u32>, OutlineMaskPreference)>,
 }
 
 impl<'a, PerStripUserData> LineStripBuilder<'a, PerStripUserData>
@@ -377,6 +417,25 @@ where
         self
     }
 
+    /// Sets an outline mask for every strip this builder adds.
+    ///
+    /// Takes precedence over [`LineBatchBuilder::outline_mask_ids`].
+    #[inline]
+    pub fn outline_mask_ids(self, outline_mask_ids: OutlineMaskPreference) -> Self {
+        self.outline_mask_ranges
+            .push((self.vertex_range.clone(), outline_mask_ids));
+        self
+    }
+
+    /// Sets the picking instance id for every strip this builder adds.
+    #[inline]
+    pub fn picking_instance_id(self, picking_instance_id: PickingLayerInstanceId) -> Self {
+        for strip in self.strips.iter_mut() {
+            strip.picking_instance_id = picking_instance_id;
+        }
+        self
+    }
+
     /// Adds user data for every strip this builder adds.
     ///
     /// User data is currently not available on the GPU.
@@ -97,6 +97,9 @@ pub struct Material {
     pub albedo: GpuTexture2DHandle,
 
     /// Factor applied to the decoded albedo color.
+    ///
+    /// An alpha below 1.0 makes the material semi-transparent,
+    /// see [`crate::renderer::DrawPhase::Transparent`].
     pub albedo_multiplier: Rgba,
 }
 
@@ -124,6 +127,9 @@ pub(crate) struct GpuMaterial {
     pub index_range: Range<u32>,
 
     pub bind_group: GpuBindGroup,
+
+    /// Whether the albedo multiplier makes this material semi-transparent.
+    pub is_transparent: bool,
 }
 
 pub(crate) mod gpu_data {
@@ -246,6 +252,7 @@ impl GpuMesh {
                 materials.push(GpuMaterial {
                     index_range: material.index_range.clone(),
                     bind_group,
+                    is_transparent: material.albedo_multiplier.a() < 1.0,
                 });
             }
             materials
@@ -0,0 +1,497 @@
+//! Outlines around selected & hovered objects, as a screen space post-process.
+//!
+//! How it works:
+//! =================
+//! All renderers that support outlines draw the objects that have an [`OutlineMaskPreference`]
+//! a second time in [`crate::renderer::DrawPhase::OutlineMask`], writing two 8 bit mask ids
+//! (one per outline layer, e.g. selection & hover) into a separate MSAA mask target.
+//! This pass has its own depth buffer, so outlines show up even for occluded objects.
+//!
+//! From the mask, we determine seed pixels: pixels with a lower mask id than one of their neighbors,
+//! i.e. pixels just outside of a masked object.
+//! Then, the [jump flooding algorithm](https://www.comp.nus.edu.sg/~tants/jfa.html) computes for
+//! every pixel the position of the closest seed in `log2(outline radius)` full screen passes.
+//!
+//! Finally, the compositor draws the outline colors wherever the distance to the closest seed is
+//! below the outline radius.
+//!
+//! Both layers are processed in the same passes, the result of layer a is in `xy`, the one of layer b in `zw`.
+
+use smallvec::smallvec;
+
+use crate::{
+    allocator::create_and_fill_uniform_buffer_batch,
+    include_file,
+    view_builder::ViewBuilder,
+    wgpu_resources::{
+        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuRenderPipelineHandle,
+        GpuTexture, PipelineLayoutDesc, PoolError, RenderPipelineDesc, ShaderModuleDesc,
+        TextureDesc, WgpuResourcePools,
+    },
+    DebugLabel, RenderContext, Rgba,
+};
+
+/// Mask ids an object is drawn with into the two outline layers.
+///
+/// Zero means "no outline" on the respective layer.
+/// Objects with the same non-zero id in a layer share a single outline, objects with different ids
+/// get an outline on their shared border as well.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OutlineMaskPreference(pub Option<[u8; 2]>);
+
+impl OutlineMaskPreference {
+    /// Not part of any outline.
+    pub const NONE: OutlineMaskPreference = OutlineMaskPreference(None);
+
+    #[inline]
+    pub fn some(layer_a: u8, layer_b: u8) -> Self {
+        OutlineMaskPreference(Some([layer_a, layer_b]))
+    }
+
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Uses the ids of `other` wherever this doesn't specify an id.
+    #[inline]
+    pub fn with_fallback_to(self, other: Self) -> Self {
+        match (self.0, other.0) {
+            (Some([a, b]), Some([other_a, other_b])) => Self::some(
+                if a == 0 { other_a } else { a },
+                if b == 0 { other_b } else { b },
+            ),
+            (Some(_), None) => self,
+            (None, _) => other,
+        }
+    }
+
+    /// Mask ids as written to the mask target.
+    #[inline]
+    pub(crate) fn ids(&self) -> [u8; 2] {
+        self.0.unwrap_or_default()
+    }
+}
+
+/// Configures color & size of the outlines of a view.
+#[derive(Clone, Debug)]
+pub struct OutlineConfig {
+    /// Outline radius in pixels, measured from the edge of the object.
+    ///
+    /// Large radii make the outline computation more expensive.
+    pub outline_radius_pixel: f32,
+
+    /// Premultiplied RGBA color of the first outline layer.
+    pub color_layer_a: Rgba,
+
+    /// Premultiplied RGBA color of the second outline layer.
+    ///
+    /// Drawn on top of the first layer.
+    pub color_layer_b: Rgba,
+}
+
+pub(crate) mod gpu_data {
+    use crate::wgpu_buffer_types;
+
+    /// Keep in sync with `outlines_from_voronoi.wgsl`
+    #[repr(C, align(256))]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct OutlineConfigUniformBuffer {
+        pub color_layer_a: wgpu_buffer_types::Vec4,
+        pub color_layer_b: wgpu_buffer_types::Vec4,
+        pub outline_radius_pixel: wgpu_buffer_types::F32RowPadded,
+        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 3],
+    }
+
+    impl OutlineConfigUniformBuffer {
+        /// Config that draws no outlines at all.
+        pub fn disabled() -> Self {
+            Self {
+                color_layer_a: crate::Rgba::TRANSPARENT.into(),
+                color_layer_b: crate::Rgba::TRANSPARENT.into(),
+                outline_radius_pixel: 0.0.into(),
+                end_padding: Default::default(),
+            }
+        }
+    }
+
+    /// Keep in sync with `jumpflooding_step.wgsl`
+    #[repr(C, align(256))]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct FloodingUniformBuffer {
+        pub step_width: wgpu_buffer_types::U32RowPadded,
+        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 1],
+    }
+}
+
+/// Owns the targets & passes for drawing outlines of a single view.
+pub(crate) struct OutlineMaskProcessor {
+    label: DebugLabel,
+
+    mask_texture: GpuTexture,
+    mask_depth: GpuTexture,
+
+    /// Ping-pong targets of the jump flooding passes.
+    voronoi_textures: [GpuTexture; 2],
+
+    bind_group_jumpflooding_init: GpuBindGroup,
+    bind_group_jumpflooding_steps: Vec<GpuBindGroup>,
+
+    render_pipeline_jumpflooding_init: GpuRenderPipelineHandle,
+    render_pipeline_jumpflooding_step: GpuRenderPipelineHandle,
+}
+
+impl OutlineMaskProcessor {
+    /// Format of the mask target, one channel per outline layer.
+    pub const MASK_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg8Uint;
+
+    /// Format of the depth buffer used while drawing the mask.
+    pub const MASK_DEPTH_FORMAT: wgpu::TextureFormat = ViewBuilder::MAIN_TARGET_DEPTH_FORMAT;
+
+    /// Closest seed positions in pixels.
+    ///
+    /// Needs full precision, half floats can't represent pixel positions on large targets.
+    pub const VORONOI_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba32Float;
+
+    /// Multisample state that any [`wgpu::RenderPipeline`] drawing to the mask needs to use.
+    pub const MASK_MSAA_STATE: wgpu::MultisampleState = ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE;
+
+    /// Depth state that any [`wgpu::RenderPipeline`] drawing to the mask needs to use.
+    ///
+    /// Uses `GreaterEqual` so that parts of an object that are drawn again with a different mask
+    /// (e.g. a single selected point of a point cloud) override the previous mask.
+    pub const MASK_DEPTH_STATE: Option<wgpu::DepthStencilState> = Some(wgpu::DepthStencilState {
+        format: Self::MASK_DEPTH_FORMAT,
+        depth_compare: wgpu::CompareFunction::GreaterEqual,
+        depth_write_enabled: true,
+        stencil: wgpu::StencilState {
+            front: wgpu::StencilFaceState::IGNORE,
+            back: wgpu::StencilFaceState::IGNORE,
+            read_mask: 0,
+            write_mask: 0,
+        },
+        bias: wgpu::DepthBiasState {
+            constant: 0,
+            slope_scale: 0.0,
+            clamp: 0.0,
+        },
+    });
+
+    pub fn new(
+        ctx: &mut RenderContext,
+        config: &OutlineConfig,
+        view_name: &DebugLabel,
+        resolution_in_pixel: [u32; 2],
+    ) -> Self {
+        crate::profile_function!();
+
+        let label = format!("{view_name:?} - outlines").into();
+
+        let mask_desc = TextureDesc {
+            label: format!("{view_name:?} - outline mask").into(),
+            size: wgpu::Extent3d {
+                width: resolution_in_pixel[0],
+                height: resolution_in_pixel[1],
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: Self::MASK_MSAA_STATE.count,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::MASK_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        };
+        let mask_texture = ctx.gpu_resources.textures.alloc(&ctx.device, &mask_desc);
+        let mask_depth = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &TextureDesc {
+                label: format!("{view_name:?} - outline mask depth").into(),
+                format: Self::MASK_DEPTH_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                ..mask_desc
+            },
+        );
+
+        let voronoi_desc = TextureDesc {
+            label: format!("{view_name:?} - outline voronoi ping").into(),
+            sample_count: 1,
+            format: Self::VORONOI_FORMAT,
+            ..mask_desc
+        };
+        let voronoi_textures = [
+            ctx.gpu_resources.textures.alloc(&ctx.device, &voronoi_desc),
+            ctx.gpu_resources.textures.alloc(
+                &ctx.device,
+                &TextureDesc {
+                    label: format!("{view_name:?} - outline voronoi pong").into(),
+                    ..voronoi_desc
+                },
+            ),
+        ];
+
+        // Step widths from the largest power of two covering the radius down to 1.
+        // Seeds further away than the radius are irrelevant.
+        let max_step_width =
+            (config.outline_radius_pixel.max(1.0).ceil() as u32).next_power_of_two();
+        let step_widths = std::iter::successors(Some(max_step_width), |w| (*w > 1).then(|| w / 2));
+
+        let pools = &mut ctx.gpu_resources;
+        let bind_group_layout_init = pools.bind_group_layouts.get_or_create(
+            &ctx.device,
+            &BindGroupLayoutDesc {
+                label: "outlines - jumpflooding init".into(),
+                entries: vec![wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Uint,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: true,
+                    },
+                    count: None,
+                }],
+            },
+        );
+        let bind_group_layout_step = pools.bind_group_layouts.get_or_create(
+            &ctx.device,
+            &BindGroupLayoutDesc {
+                label: "outlines - jumpflooding step".into(),
+                entries: vec![
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size:
+                                (std::mem::size_of::<gpu_data::FloodingUniformBuffer>() as u64)
+                                    .try_into()
+                                    .ok(),
+                        },
+                        count: None,
+                    },
+                ],
+            },
+        );
+
+        let screen_triangle_vertex_shader = pools.shader_modules.get_or_create(
+            &ctx.device,
+            &mut ctx.resolver,
+            &ShaderModuleDesc {
+                label: "screen_triangle (vertex)".into(),
+                source: include_file!("../shader/screen_triangle.wgsl"),
+            },
+        );
+        let render_pipeline_jumpflooding_init = pools.render_pipelines.get_or_create(
+            &ctx.device,
+            &RenderPipelineDesc {
+                label: "outlines - jumpflooding init".into(),
+                pipeline_layout: pools.pipeline_layouts.get_or_create(
+                    &ctx.device,
+                    &PipelineLayoutDesc {
+                        label: "outlines - jumpflooding init".into(),
+                        entries: vec![bind_group_layout_init],
+                    },
+                    &pools.bind_group_layouts,
+                ),
+                vertex_entrypoint: "main".into(),
+                vertex_handle: screen_triangle_vertex_shader,
+                fragment_entrypoint: "main".into(),
+                fragment_handle: pools.shader_modules.get_or_create(
+                    &ctx.device,
+                    &mut ctx.resolver,
+                    &ShaderModuleDesc {
+                        label: "jumpflooding_init (fragment)".into(),
+                        source: include_file!("../shader/outlines/jumpflooding_init.wgsl"),
+                    },
+                ),
+                vertex_buffers: smallvec![],
+                render_targets: smallvec![Some(Self::VORONOI_FORMAT.into())],
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_jumpflooding_step = pools.render_pipelines.get_or_create(
+            &ctx.device,
+            &RenderPipelineDesc {
+                label: "outlines - jumpflooding step".into(),
+                pipeline_layout: pools.pipeline_layouts.get_or_create(
+                    &ctx.device,
+                    &PipelineLayoutDesc {
+                        label: "outlines - jumpflooding step".into(),
+                        entries: vec![bind_group_layout_step],
+                    },
+                    &pools.bind_group_layouts,
+                ),
+                vertex_entrypoint: "main".into(),
+                vertex_handle: screen_triangle_vertex_shader,
+                fragment_entrypoint: "main".into(),
+                fragment_handle: pools.shader_modules.get_or_create(
+                    &ctx.device,
+                    &mut ctx.resolver,
+                    &ShaderModuleDesc {
+                        label: "jumpflooding_step (fragment)".into(),
+                        source: include_file!("../shader/outlines/jumpflooding_step.wgsl"),
+                    },
+                ),
+                vertex_buffers: smallvec![],
+                render_targets: smallvec![Some(Self::VORONOI_FORMAT.into())],
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+
+        let bind_group_jumpflooding_init = ctx.gpu_resources.bind_groups.alloc(
+            &ctx.device,
+            &ctx.gpu_resources,
+            &BindGroupDesc {
+                label: "outlines - jumpflooding init".into(),
+                entries: smallvec![BindGroupEntry::DefaultTextureView(mask_texture.handle)],
+                layout: bind_group_layout_init,
+            },
+        );
+
+        let step_uniform_buffers = create_and_fill_uniform_buffer_batch(
+            ctx,
+            "outlines - jumpflooding steps".into(),
+            step_widths.map(|step_width| gpu_data::FloodingUniformBuffer {
+                step_width: step_width.into(),
+                end_padding: Default::default(),
+            }),
+        );
+        // Step i reads from the target step i-1 wrote to, the init pass writes to the first target.
+        let bind_group_jumpflooding_steps = step_uniform_buffers
+            .into_iter()
+            .enumerate()
+            .map(|(i, uniform_buffer)| {
+                ctx.gpu_resources.bind_groups.alloc(
+                    &ctx.device,
+                    &ctx.gpu_resources,
+                    &BindGroupDesc {
+                        label: format!("outlines - jumpflooding step {i}").into(),
+                        entries: smallvec![
+                            BindGroupEntry::DefaultTextureView(voronoi_textures[i % 2].handle),
+                            uniform_buffer
+                        ],
+                        layout: bind_group_layout_step,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            label,
+            mask_texture,
+            mask_depth,
+            voronoi_textures,
+            bind_group_jumpflooding_init,
+            bind_group_jumpflooding_steps,
+            render_pipeline_jumpflooding_init,
+            render_pipeline_jumpflooding_step,
+        }
+    }
+
+    /// Texture with the closest seed positions after all passes in [`Self::compute_outlines`] ran.
+    pub fn final_voronoi_texture(&self) -> &GpuTexture {
+        &self.voronoi_textures[self.bind_group_jumpflooding_steps.len() % 2]
+    }
+
+    pub fn start_mask_render_pass<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+    ) -> wgpu::RenderPass<'a> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: DebugLabel::from(format!("{:?} - mask pass", self.label)).get(),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &self.mask_texture.default_view,
+                resolve_target: None, // Integer textures can't be resolved.
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.mask_depth.default_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0.0), // 0.0 == far since we're using reverse-z
+                    store: false,
+                }),
+                stencil_ops: None,
+            }),
+        })
+    }
+
+    /// Runs the jump flooding passes on the mask drawn during the pass started by [`Self::start_mask_render_pass`].
+    pub fn compute_outlines(
+        &self,
+        pools: &WgpuResourcePools,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Result<(), PoolError> {
+        crate::profile_function!();
+
+        let pipeline_init = pools
+            .render_pipelines
+            .get_resource(self.render_pipeline_jumpflooding_init)?;
+        let pipeline_step = pools
+            .render_pipelines
+            .get_resource(self.render_pipeline_jumpflooding_step)?;
+
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: DebugLabel::from(format!("{:?} - jumpflooding init", self.label)).get(),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.voronoi_textures[0].default_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT), // Overwritten by the full screen triangle.
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            pass.set_pipeline(pipeline_init);
+            pass.set_bind_group(0, &self.bind_group_jumpflooding_init, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        for (i, bind_group) in self.bind_group_jumpflooding_steps.iter().enumerate() {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: DebugLabel::from(format!("{:?} - jumpflooding step {i}", self.label)).get(),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.voronoi_textures[(i + 1) % 2].default_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT), // Overwritten by the full screen triangle.
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            pass.set_pipeline(pipeline_step);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,413 @@
+//! GPU based picking.
+//!
+//! How it works:
+//! =================
+//! All renderers that support picking draw their objects a second time in
+//! [`crate::renderer::DrawPhase::PickingLayer`], writing a [`PickingLayerId`] per pixel to an integer target
+//! (plus depth to a depth target).
+//! Only a small rectangle of the view is drawn in this phase, typically a few pixels around the mouse cursor:
+//! the projection matrix is adjusted so that the rectangle covers the entire picking target.
+//! This makes picking cost the same regardless of how large the view is.
+//!
+//! Both targets are then copied to a readback buffer of the [`crate::allocator::GpuReadbackBelt`].
+//! Data arrives on the CPU a few frames later and can be polled with [`PickingLayerProcessor::next_readback_result`].
+
+use crate::{
+    allocator::{create_and_fill_uniform_buffer, GpuReadbackBuffer, GpuReadbackIdentifier},
+    global_bindings::FrameUniformBuffer,
+    view_builder::ViewBuilder,
+    wgpu_resources::{GpuBindGroup, GpuTexture, TextureDesc},
+    DebugLabel, RenderContext,
+};
+
+use parking_lot::Mutex;
+
+/// Id of an object, i.e. a collection of instances, in the picking layer.
+///
+/// Zero means "nothing", everything that isn't drawn in the picking layer reads back as zero.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PickingLayerObjectId(pub u64);
+
+/// Id of an instance within an object in the picking layer.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PickingLayerInstanceId(pub u64);
+
+/// Combination of object & instance id, as written to the picking layer.
+///
+/// Mirrors the `UVec4` written by the `fs_main_picking_layer` entry points of all renderers:
+/// `xy` is the object id, `zw` the instance id, lower 32 bits first.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PickingLayerId {
+    pub object: PickingLayerObjectId,
+    pub instance: PickingLayerInstanceId,
+}
+
+impl PickingLayerId {
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.object.0 == 0
+    }
+}
+
+impl From<PickingLayerObjectId> for [u32; 2] {
+    #[inline]
+    fn from(id: PickingLayerObjectId) -> Self {
+        bytemuck::cast(id.0)
+    }
+}
+
+impl From<PickingLayerInstanceId> for [u32; 2] {
+    #[inline]
+    fn from(id: PickingLayerInstanceId) -> Self {
+        bytemuck::cast(id.0)
+    }
+}
+
+impl From<PickingLayerId> for [u32; 4] {
+    #[inline]
+    fn from(id: PickingLayerId) -> Self {
+        bytemuck::cast(id)
+    }
+}
+
+/// Rectangle of the view that is drawn to the picking layer, in pixels of the view's target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PickingLayerRect {
+    /// Top left corner, may be outside of the view.
+    pub left_top: glam::IVec2,
+
+    /// Size of the rectangle, at least one pixel in each direction.
+    pub extent: glam::UVec2,
+}
+
+impl PickingLayerRect {
+    /// A square of `2 * radius + 1` pixels centered at the given pixel.
+    pub fn from_center_and_radius(center: glam::IVec2, radius: u32) -> Self {
+        Self {
+            left_top: center - glam::IVec2::splat(radius as i32),
+            extent: glam::UVec2::splat(2 * radius + 1),
+        }
+    }
+
+    #[inline]
+    pub fn center(&self) -> glam::IVec2 {
+        self.left_top + (self.extent / 2).as_ivec2()
+    }
+}
+
+/// Picking data that arrived on the CPU.
+pub struct PickingResult<T: 'static + Send + Sync> {
+    /// User data passed on scheduling via [`ViewBuilder::schedule_picking_rect`].
+    pub user_data: T,
+
+    /// The rectangle this data was picked from.
+    pub rect: PickingLayerRect,
+
+    /// Picking ids of all pixels in the rectangle, row by row.
+    pub picking_id_data: Vec<PickingLayerId>,
+
+    /// Depth of all pixels in the rectangle, row by row.
+    ///
+    /// Given in normalized device coordinates, i.e. `0.0` is infinitely far away since we're using reverse-z.
+    pub picking_depth_data: Vec<f32>,
+
+    /// Transforms from normalized device coordinates of the picking rectangle to world space.
+    world_from_cropped_projection: glam::Mat4,
+}
+
+impl<T: 'static + Send + Sync> PickingResult<T> {
+    /// Picking id at a position relative to the top left corner of the picking rectangle.
+    #[inline]
+    pub fn picked_id(&self, pos_on_picking_rect: glam::UVec2) -> PickingLayerId {
+        self.picking_id_data[self.index(pos_on_picking_rect)]
+    }
+
+    /// World position of the picked surface at a position relative to the top left corner of the picking rectangle.
+    ///
+    /// Not finite if nothing was drawn at that position.
+    pub fn picked_world_position(&self, pos_on_picking_rect: glam::UVec2) -> glam::Vec3 {
+        let depth = self.picking_depth_data[self.index(pos_on_picking_rect)];
+        let pixel_center = pos_on_picking_rect.as_vec2() + glam::Vec2::splat(0.5);
+        let ndc = glam::vec2(
+            pixel_center.x / self.rect.extent.x as f32 * 2.0 - 1.0,
+            1.0 - pixel_center.y / self.rect.extent.y as f32 * 2.0,
+        );
+        self.world_from_cropped_projection
+            .project_point3(ndc.extend(depth))
+    }
+
+    #[inline]
+    fn index(&self, pos_on_picking_rect: glam::UVec2) -> usize {
+        (pos_on_picking_rect.x + pos_on_picking_rect.y * self.rect.extent.x) as usize
+    }
+}
+
+/// Type used as user data on the gpu readback belt.
+struct ReadbackBeltMetadata<T: 'static + Send + Sync> {
+    picking_rect: PickingLayerRect,
+    world_from_cropped_projection: glam::Mat4,
+    user_data: T,
+}
+
+/// Owns the targets & readback of the picking layer of a single view.
+pub struct PickingLayerProcessor {
+    label: DebugLabel,
+
+    picking_target: GpuTexture,
+    picking_depth: GpuTexture,
+    extent: glam::UVec2,
+
+    /// Global bindings with a projection that maps the picking rectangle to the entire picking target.
+    pub(crate) bind_group_0: GpuBindGroup,
+
+    readback_buffer: Mutex<GpuReadbackBuffer>,
+}
+
+impl PickingLayerProcessor {
+    /// Format of the picking target, see [`PickingLayerId`].
+    pub const PICKING_LAYER_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba32Uint;
+
+    /// Format of the depth buffer used while drawing the picking layer.
+    pub const PICKING_LAYER_DEPTH_FORMAT: wgpu::TextureFormat =
+        ViewBuilder::MAIN_TARGET_DEPTH_FORMAT;
+
+    /// Multisample state that any [`wgpu::RenderPipeline`] drawing to the picking layer needs to use.
+    ///
+    /// Integer ids can't be blended or resolved, so there's no MSAA.
+    pub const PICKING_LAYER_MSAA_STATE: wgpu::MultisampleState = wgpu::MultisampleState {
+        count: 1,
+        mask: !0,
+        alpha_to_coverage_enabled: false,
+    };
+
+    /// Depth state that any [`wgpu::RenderPipeline`] drawing to the picking layer needs to use.
+    pub const PICKING_LAYER_DEPTH_STATE: Option<wgpu::DepthStencilState> =
+        ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE;
+
+    const BYTES_PER_ID: u32 = std::mem::size_of::<PickingLayerId>() as u32;
+    const BYTES_PER_DEPTH: u32 = std::mem::size_of::<f32>() as u32;
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new<T: 'static + Send + Sync>(
+        ctx: &mut RenderContext,
+        view_name: &DebugLabel,
+        screen_resolution: glam::UVec2,
+        picking_rect: PickingLayerRect,
+        frame_uniform_buffer_content: &FrameUniformBuffer,
+        readback_identifier: GpuReadbackIdentifier,
+        readback_user_data: T,
+    ) -> Self {
+        crate::profile_function!();
+
+        let extent = picking_rect.extent.max(glam::UVec2::ONE);
+        let picking_rect = PickingLayerRect {
+            left_top: picking_rect.left_top,
+            extent,
+        };
+
+        let picking_target_desc = TextureDesc {
+            label: format!("{view_name:?} - picking layer").into(),
+            size: wgpu::Extent3d {
+                width: extent.x,
+                height: extent.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: Self::PICKING_LAYER_MSAA_STATE.count,
+            dimension: wgpu::TextureDimension::D2,
+            format: Self::PICKING_LAYER_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        };
+        let picking_target = ctx
+            .gpu_resources
+            .textures
+            .alloc(&ctx.device, &picking_target_desc);
+        let picking_depth = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &TextureDesc {
+                label: format!("{view_name:?} - picking layer depth").into(),
+                format: Self::PICKING_LAYER_DEPTH_FORMAT,
+                ..picking_target_desc
+            },
+        );
+
+        // Crop the projection to the picking rectangle:
+        // Scale & translate in normalized device coordinates such that the rectangle covers [-1, 1].
+        let rect_min = picking_rect.left_top.as_vec2();
+        let rect_max = rect_min + extent.as_vec2();
+        let screen_resolution = screen_resolution.as_vec2();
+        let ndc_min = glam::vec2(
+            rect_min.x / screen_resolution.x * 2.0 - 1.0,
+            1.0 - rect_max.y / screen_resolution.y * 2.0,
+        );
+        let ndc_max = glam::vec2(
+            rect_max.x / screen_resolution.x * 2.0 - 1.0,
+            1.0 - rect_min.y / screen_resolution.y * 2.0,
+        );
+        let scale = 2.0 / (ndc_max - ndc_min);
+        let translation = -(ndc_max + ndc_min) / (ndc_max - ndc_min);
+        let cropped_projection_from_projection =
+            glam::Mat4::from_translation(translation.extend(0.0))
+                * glam::Mat4::from_scale(scale.extend(1.0));
+
+        let projection_from_view: glam::Mat4 =
+            frame_uniform_buffer_content.projection_from_view.into();
+        let projection_from_world: glam::Mat4 =
+            frame_uniform_buffer_content.projection_from_world.into();
+        let cropped_projection_from_world =
+            cropped_projection_from_projection * projection_from_world;
+
+        let frame_uniform_buffer = create_and_fill_uniform_buffer(
+            ctx,
+            format!("{view_name:?} - picking layer frame uniform buffer").into(),
+            FrameUniformBuffer {
+                projection_from_view: (cropped_projection_from_projection * projection_from_view)
+                    .into(),
+                projection_from_world: cropped_projection_from_world.into(),
+                ..*frame_uniform_buffer_content
+            },
+        );
+        let bind_group_0 = ctx.shared_renderer_data.global_bindings.create_bind_group(
+            &mut ctx.gpu_resources,
+            &ctx.device,
+            frame_uniform_buffer,
+            None,
+        );
+
+        let buffer_size = (GpuReadbackBuffer::padded_bytes_per_row(extent.x, Self::BYTES_PER_ID)
+            + GpuReadbackBuffer::padded_bytes_per_row(extent.x, Self::BYTES_PER_DEPTH))
+            as u64
+            * extent.y as u64;
+        let readback_buffer = ctx.gpu_readback_belt.lock().allocate(
+            &ctx.device,
+            &ctx.gpu_resources.buffers,
+            buffer_size,
+            readback_identifier,
+            Box::new(ReadbackBeltMetadata {
+                picking_rect,
+                world_from_cropped_projection: cropped_projection_from_world.inverse(),
+                user_data: readback_user_data,
+            }),
+        );
+
+        Self {
+            label: view_name.clone(),
+            picking_target,
+            picking_depth,
+            extent,
+            bind_group_0,
+            readback_buffer: Mutex::new(readback_buffer),
+        }
+    }
+
+    pub(crate) fn begin_render_pass<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+    ) -> wgpu::RenderPass<'a> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: DebugLabel::from(format!("{:?} - picking layer pass", self.label)).get(),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &self.picking_target.default_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT), // Zero is "nothing".
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.picking_depth.default_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0.0), // 0.0 == far since we're using reverse-z
+                    store: true,                    // Read back along with the ids.
+                }),
+                stencil_ops: None,
+            }),
+        })
+    }
+
+    /// Schedules the copy of the picking layer drawn during the pass started by [`Self::begin_render_pass`] to the readback buffer.
+    pub(crate) fn end_render_pass(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut readback_buffer = self.readback_buffer.lock();
+        readback_buffer.read_texture2d(
+            encoder,
+            wgpu::ImageCopyTexture {
+                texture: &self.picking_target.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            Self::BYTES_PER_ID,
+            self.extent,
+        );
+        readback_buffer.read_texture2d(
+            encoder,
+            wgpu::ImageCopyTexture {
+                texture: &self.picking_depth.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::DepthOnly,
+            },
+            Self::BYTES_PER_DEPTH,
+            self.extent,
+        );
+    }
+
+    /// Returns the newest picking result for the given identifier that arrived on the CPU, if any.
+    ///
+    /// `T` has to be the type of the user data passed to [`ViewBuilder::schedule_picking_rect`].
+    pub fn next_readback_result<T: 'static + Send + Sync>(
+        ctx: &RenderContext,
+        identifier: GpuReadbackIdentifier,
+    ) -> Option<PickingResult<T>> {
+        ctx.gpu_readback_belt
+            .lock()
+            .readback_data::<u8, _>(identifier, |data, metadata| {
+                let metadata = metadata.downcast::<ReadbackBeltMetadata<T>>().ok()?;
+                let extent = metadata.picking_rect.extent;
+
+                let id_row_size =
+                    GpuReadbackBuffer::padded_bytes_per_row(extent.x, Self::BYTES_PER_ID) as usize;
+                let depth_row_size =
+                    GpuReadbackBuffer::padded_bytes_per_row(extent.x, Self::BYTES_PER_DEPTH)
+                        as usize;
+                let (id_data, depth_data) = data.split_at(id_row_size * extent.y as usize);
+
+                // Strip the row padding.
+                let picking_id_data = id_data
+                    .chunks_exact(id_row_size)
+                    .flat_map(|row| {
+                        bytemuck::pod_collect_to_vec::<u8, PickingLayerId>(
+                            &row[..(extent.x * Self::BYTES_PER_ID) as usize],
+                        )
+                    })
+                    .collect();
+                let picking_depth_data = depth_data
+                    .chunks_exact(depth_row_size)
+                    .flat_map(|row| {
+                        bytemuck::pod_collect_to_vec::<u8, f32>(
+                            &row[..(extent.x * Self::BYTES_PER_DEPTH) as usize],
+                        )
+                    })
+                    .collect();
+
+                Some(PickingResult {
+                    user_data: metadata.user_data,
+                    rect: metadata.picking_rect,
+                    picking_id_data,
+                    picking_depth_data,
+                    world_from_cropped_projection: metadata.world_from_cropped_projection,
+                })
+            })
+            .flatten()
+    }
+
+    /// Whether a picking result for the given identifier is still on its way to the CPU.
+    ///
+    /// Results arrive a few frames after scheduling, so a repaint is needed to pick them up.
+    pub fn has_pending_readback(ctx: &RenderContext, identifier: GpuReadbackIdentifier) -> bool {
+        ctx.gpu_readback_belt.lock().has_pending(identifier)
+    }
+}
@@ -4,7 +4,8 @@ use crate::{
         PointCloudBatchFlags, PointCloudBatchInfo, PointCloudDrawData, PointCloudDrawDataError,
         PointCloudVertex,
     },
-    Color32, DebugLabel, RenderContext, Size,
+    Color32, DebugLabel, OutlineMaskPreference, PickingLayerInstanceId, PickingLayerObjectId,
+    RenderContext, Size,
 };
 
 /// Builder for point clouds, making it easy to create [`crate::renderer::PointCloudDrawData`].
@@ -13,6 +14,7 @@ pub struct PointCloudBuilder<PerPointUserData> {
     pub vertices: Vec<PointCloudVertex>,
 
     pub(crate) color_buffer: CpuWriteGpuReadBuffer<Color32>,
+    pub(crate) picking_instance_ids_buffer: CpuWriteGpuReadBuffer<PickingLayerInstanceId>,
     pub user_data: Vec<PerPointUserData>,
 
     pub(crate) batches: Vec<PointCloudBatchInfo>,
@@ -31,10 +33,19 @@ where
             &ctx.gpu_resources.buffers,
             PointCloudDrawData::MAX_NUM_POINTS,
         );
+        let picking_instance_ids_buffer = ctx
+            .cpu_write_gpu_read_belt
+            .lock()
+            .allocate::<PickingLayerInstanceId>(
+                &ctx.device,
+                &ctx.gpu_resources.buffers,
+                PointCloudDrawData::MAX_NUM_POINTS,
+            );
 
         Self {
             vertices: Vec::with_capacity(RESERVE_SIZE),
             color_buffer,
+            picking_instance_ids_buffer,
             user_data: Vec::with_capacity(RESERVE_SIZE),
             batches: Vec::with_capacity(16),
         }
@@ -50,7 +61,11 @@ where
             label: label.into(),
             world_from_obj: glam::Mat4::IDENTITY,
             flags: PointCloudBatchFlags::ENABLE_SHADING,
+            opacity: 1.0,
             point_count: 0,
+            overall_outline_mask_ids: OutlineMaskPreference::NONE,
+            additional_outline_mask_ids_vertex_ranges: Vec::new(),
+            picking_object_id: PickingLayerObjectId::default(),
         });
 
         PointCloudBatchBuilder(self)
@@ -150,6 +165,42 @@ where
         self
     }
 
+    /// Sets the opacity for the *entire* batch, see [`PointCloudBatchInfo::opacity`].
+    #[inline]
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.batch_mut().opacity = opacity;
+        self
+    }
+
+    /// Sets an outline mask for every point in the batch.
+    #[inline]
+    pub fn outline_mask_ids(mut self, outline_mask_ids: OutlineMaskPreference) -> Self {
+        self.batch_mut().overall_outline_mask_ids = outline_mask_ids;
+        self
+    }
+
+    /// Sets an outline mask for a range of points, relative to the start of the batch.
+    ///
+    /// Takes precedence over [`Self::outline_mask_ids`] and previously pushed ranges.
+    #[inline]
+    pub fn push_additional_outline_mask_ids_for_range(
+        mut self,
+        range: std::ops::Range<u32>,
+        outline_mask_ids: OutlineMaskPreference,
+    ) -> Self {
+        self.batch_mut()
+            .additional_outline_mask_ids_vertex_ranges
+            .push((range, outline_mask_ids));
+        self
+    }
+
+    /// Sets the picking object id for every point in the batch.
+    #[inline]
+    pub fn picking_object_id(mut self, picking_object_id: PickingLayerObjectId) -> Self {
+        self.batch_mut().picking_object_id = picking_object_id;
+        self
+    }
+
     /// Each time we `add_points`, or upon builder drop, make sure that we
     /// fill in any additional colors and user-data to have matched vectors.
     fn extend_defaults(&mut self) {
@@ -160,6 +211,14 @@ where
             );
         }
 
+        if self.0.picking_instance_ids_buffer.num_written() < self.0.vertices.len() {
+            self.0
+                .picking_instance_ids_buffer
+                .extend(std::iter::repeat(PickingLayerInstanceId::default()).take(
+                    self.0.vertices.len() - self.0.picking_instance_ids_buffer.num_written(),
+                ));
+        }
+
         if self.0.user_data.len() < self.0.vertices.len() {
             self.0.user_data.extend(
                 std::iter::repeat(PerPointUserData::default())
@@ -214,6 +273,7 @@ where
             vertices: &mut self.0.vertices[new_range],
             max_points,
             colors: &mut self.0.color_buffer,
+            picking_instance_ids: &mut self.0.picking_instance_ids_buffer,
             user_data: &mut self.0.user_data,
         }
     }
@@ -235,6 +295,7 @@ where
         PointBuilder {
             vertex: self.0.vertices.last_mut().unwrap(),
             color: &mut self.0.color_buffer,
+            picking_instance_id: &mut self.0.picking_instance_ids_buffer,
             user_data: self.0.user_data.last_mut().unwrap(),
         }
     }
@@ -270,6 +331,7 @@ where
 pub struct PointBuilder<'a, PerPointUserData> {
     vertex: &'a mut PointCloudVertex,
     color: &'a mut CpuWriteGpuReadBuffer<Color32>,
+    picking_instance_id: &'a mut CpuWriteGpuReadBuffer<PickingLayerInstanceId>,
     user_data: &'a mut PerPointUserData,
 }
 
@@ -290,6 +352,13 @@ where
         self
     }
 
+    /// This mustn't call this more than once.
+    #[inline]
+    pub fn picking_instance_id(self, picking_instance_id: PickingLayerInstanceId) -> Self {
+        self.picking_instance_id.push(picking_instance_id);
+        self
+    }
+
     pub fn user_data(self, data: PerPointUserData) -> Self {
         *self.user_data = data;
         self
@@ -304,6 +373,7 @@ pub struct PointsBuilder<'a, PerPointUserData> {
     // the data to if provided.
     max_points: usize,
     colors: &'a mut CpuWriteGpuReadBuffer<Color32>,
+    picking_instance_ids: &'a mut CpuWriteGpuReadBuffer<PickingLayerInstanceId>,
     user_data: &'a mut Vec<PerPointUserData>,
 }
 
@@ -342,6 +412,24 @@ where
         self
     }
 
+    /// Assigns picking instance ids to all points.
+    ///
+    /// This mustn't call this more than once.
+    ///
+    /// If the iterator doesn't cover all points, some will not be assigned.
+    /// If the iterator provides more values than there are points, the extra values will be ignored.
+    #[inline]
+    pub fn picking_instance_ids(
+        self,
+        picking_instance_ids: impl Iterator<Item = PickingLayerInstanceId>,
+    ) -> Self {
+        crate::profile_function!();
+        self.picking_instance_ids.extend(
+            picking_instance_ids.take(self.max_points - self.picking_instance_ids.num_written()),
+        );
+        self
+    }
+
     /// Assigns user data for all points in this builder.
     ///
     /// This mustn't call this more than once.
@@ -0,0 +1,398 @@
+//! Level of detail for large, static point clouds.
+//!
+//! How it works:
+//! =================
+//! When a static point cloud is created, its points are sorted into an octree.
+//! Every node keeps a random subsample of up to [`NODE_CAPACITY`] points of its cell,
+//! all remaining points are passed on to its children.
+//! Points are then reordered such that the points of every node form a contiguous range.
+//! This way, any selection of nodes can be drawn straight from the point data textures.
+//!
+//! Every frame, nodes are selected on the CPU, coarsest first:
+//! Nodes outside of the view frustum are skipped and the children of a node are only visited
+//! if the node's points are spaced further apart on screen than [`PointCloudLodView::target_point_spacing`].
+//! Since coarse nodes are visited first, stopping once [`PointCloudLodView::point_budget`] is reached
+//! still leaves a coarse, but complete picture.
+
+use std::{collections::BinaryHeap, ops::Range};
+
+use smallvec::SmallVec;
+
+/// Maximum number of points a node keeps for itself before passing points on to its children.
+const NODE_CAPACITY: usize = 16 * 1024;
+
+/// Nodes at this depth keep all their points, no matter how many there are.
+///
+/// Guards against endless subdivision of (nearly) coinciding points.
+const MAX_DEPTH: u32 = 20;
+
+/// View dependent parameters for choosing which points of static point clouds get drawn.
+///
+/// Retrieved from [`crate::view_builder::ViewBuilder::point_cloud_lod_view`].
+#[derive(Clone, Copy, Debug)]
+pub struct PointCloudLodView {
+    pub projection_from_world: glam::Mat4,
+    pub camera_position: glam::Vec3,
+
+    /// How wide a pixel is in world space at unit distance from the camera.
+    ///
+    /// For orthographic projections this is the width of a pixel in world space, independent of distance.
+    pub pixel_world_size_from_camera_distance: f32,
+    pub is_orthographic: bool,
+
+    /// Desired distance between neighboring points on screen, in pixels.
+    ///
+    /// Octree nodes whose points are further apart than this get refined.
+    pub target_point_spacing: f32,
+
+    /// Maximum number of points drawn for all static point clouds passed to
+    /// [`crate::renderer::PointCloudDrawData::new_static`] together.
+    pub point_budget: u32,
+}
+
+impl PointCloudLodView {
+    pub const DEFAULT_TARGET_POINT_SPACING: f32 = 2.0;
+    pub const DEFAULT_POINT_BUDGET: u32 = 8 * 1024 * 1024;
+}
+
+pub(crate) struct OctreeNode {
+    /// Bounding box of all points of this node and its descendants, in object space.
+    bounding_box: macaw::BoundingBox,
+
+    /// Approximate distance between neighboring points of this node, in object space.
+    point_spacing: f32,
+
+    /// Points of this node, indexing into the reordered points.
+    point_range: Range<u32>,
+
+    children: SmallVec<[u32; 8]>,
+}
+
+/// Octree over the points of a static point cloud.
+pub(crate) struct PointCloudOctree {
+    /// All nodes, the root node comes first.
+    nodes: Vec<OctreeNode>,
+}
+
+impl PointCloudOctree {
+    /// Sorts the given points into an octree.
+    ///
+    /// Returns the octree along with the order in which the points need to be stored:
+    /// The n-th stored point is `positions[order[n]]`.
+    pub fn build(positions: &[glam::Vec3]) -> (Self, Vec<u32>) {
+        crate::profile_function!();
+
+        let mut builder = OctreeBuilder {
+            positions,
+            order: Vec::with_capacity(positions.len()),
+            nodes: Vec::new(),
+            random_state: 0x9E37_79B9_7F4A_7C15,
+        };
+
+        if !positions.is_empty() {
+            let bounding_box = macaw::BoundingBox::from_points(positions.iter().copied());
+            let mut indices = (0..positions.len() as u32).collect::<Vec<_>>();
+            builder.build_node(
+                &mut indices,
+                bounding_box.min,
+                bounding_box.size().max_element(),
+                0,
+            );
+        }
+
+        (
+            PointCloudOctree {
+                nodes: builder.nodes,
+            },
+            builder.order,
+        )
+    }
+
+    /// Bounding box of all points, in object space.
+    pub fn bounding_box(&self) -> macaw::BoundingBox {
+        self.nodes
+            .first()
+            .map_or(macaw::BoundingBox::nothing(), |root| root.bounding_box)
+    }
+}
+
+struct OctreeBuilder<'a> {
+    positions: &'a [glam::Vec3],
+    order: Vec<u32>,
+    nodes: Vec<OctreeNode>,
+    random_state: u64,
+}
+
+impl<'a> OctreeBuilder<'a> {
+    /// xorshift64, we only need the subsamples to be spatially well distributed.
+    fn next_random(&mut self) -> u64 {
+        self.random_state ^= self.random_state << 13;
+        self.random_state ^= self.random_state >> 7;
+        self.random_state ^= self.random_state << 17;
+        self.random_state
+    }
+
+    /// Creates a node for the cubic cell at `cell_min` containing the points `indices` and returns its index.
+    fn build_node(
+        &mut self,
+        indices: &mut [u32],
+        cell_min: glam::Vec3,
+        cell_size: f32,
+        depth: u32,
+    ) -> u32 {
+        let node_index = self.nodes.len();
+        self.nodes.push(OctreeNode {
+            bounding_box: macaw::BoundingBox::nothing(),
+            point_spacing: 0.0,
+            point_range: 0..0,
+            children: SmallVec::new(),
+        });
+
+        let num_own_points = if depth == MAX_DEPTH {
+            indices.len()
+        } else {
+            indices.len().min(NODE_CAPACITY)
+        };
+
+        // Pick a random subsample, so that the node covers its cell evenly regardless of the order points were passed in.
+        if num_own_points < indices.len() {
+            for i in 0..num_own_points {
+                let j = i + (self.next_random() % (indices.len() - i) as u64) as usize;
+                indices.swap(i, j);
+            }
+        }
+
+        let (own_points, remaining_points) = indices.split_at_mut(num_own_points);
+        let point_range_start = self.order.len() as u32;
+        self.order.extend_from_slice(own_points);
+
+        let positions = self.positions;
+        let mut bounding_box =
+            macaw::BoundingBox::from_points(own_points.iter().map(|&i| positions[i as usize]));
+
+        let half_cell_size = cell_size * 0.5;
+        let cell_center = cell_min + glam::Vec3::splat(half_cell_size);
+        let octant_bounds = partition_into_octants(positions, remaining_points, cell_center);
+
+        let mut children = SmallVec::new();
+        for octant in 0..8 {
+            let octant_range = octant_bounds[octant]..octant_bounds[octant + 1];
+            if octant_range.is_empty() {
+                continue;
+            }
+            let child_cell_min = cell_min
+                + glam::vec3(
+                    (octant & 1) as f32,
+                    ((octant >> 1) & 1) as f32,
+                    ((octant >> 2) & 1) as f32,
+                ) * half_cell_size;
+            let child_index = self.build_node(
+                &mut remaining_points[octant_range],
+                child_cell_min,
+                half_cell_size,
+                depth + 1,
+            );
+            bounding_box = bounding_box.union(self.nodes[child_index as usize].bounding_box);
+            children.push(child_index);
+        }
+
+        self.nodes[node_index] = OctreeNode {
+            bounding_box,
+            // Point clouds are typically sampled surfaces, so points spread over two dimensions of the cell.
+            point_spacing: cell_size / (num_own_points as f32).sqrt(),
+            point_range: point_range_start..(point_range_start + num_own_points as u32),
+            children,
+        };
+
+        node_index as u32
+    }
+}
+
+/// Reorders `indices` by octant of `center`, returns the bounds of every octant.
+///
+/// Octant `o` ends up at `bounds[o]..bounds[o + 1]`,
+/// where bit 0/1/2 of `o` is set for points on the upper side of `center` in x/y/z.
+fn partition_into_octants(
+    positions: &[glam::Vec3],
+    indices: &mut [u32],
+    center: glam::Vec3,
+) -> [usize; 9] {
+    fn partition(indices: &mut [u32], mut is_lower: impl FnMut(u32) -> bool) -> usize {
+        let mut split = 0;
+        for i in 0..indices.len() {
+            if is_lower(indices[i]) {
+                indices.swap(split, i);
+                split += 1;
+            }
+        }
+        split
+    }
+
+    let mut bounds = [0; 9];
+    bounds[8] = indices.len();
+    bounds[4] = partition(indices, |i| positions[i as usize].z < center.z);
+    for z in 0..2 {
+        let (start, end) = (bounds[z * 4], bounds[z * 4 + 4]);
+        bounds[z * 4 + 2] = start
+            + partition(&mut indices[start..end], |i| {
+                positions[i as usize].y < center.y
+            });
+    }
+    for zy in 0..4 {
+        let (start, end) = (bounds[zy * 2], bounds[zy * 2 + 2]);
+        bounds[zy * 2 + 1] = start
+            + partition(&mut indices[start..end], |i| {
+                positions[i as usize].x < center.x
+            });
+    }
+    bounds
+}
+
+/// Node waiting to be visited, ordered by its point spacing on screen.
+struct Candidate {
+    point_spacing_on_screen: f32,
+    cloud_index: usize,
+    node_index: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.point_spacing_on_screen
+            .total_cmp(&other.point_spacing_on_screen)
+    }
+}
+
+/// Selects the points to draw for several point clouds, each given by its octree and its `world_from_obj` transform.
+///
+/// Returns sorted, non-overlapping ranges of reordered points for each cloud, in the same order the clouds were passed in.
+pub(crate) fn select_points<'a>(
+    view: &PointCloudLodView,
+    clouds: impl Iterator<Item = (&'a PointCloudOctree, glam::Mat4)>,
+) -> Vec<Vec<Range<u32>>> {
+    crate::profile_function!();
+
+    struct CloudView<'a> {
+        octree: &'a PointCloudOctree,
+        world_from_obj: glam::Mat4,
+        projection_from_obj: glam::Mat4,
+        scale: f32,
+    }
+
+    impl<'a> CloudView<'a> {
+        fn candidate(
+            &self,
+            view: &PointCloudLodView,
+            cloud_index: usize,
+            node_index: u32,
+        ) -> Option<Candidate> {
+            let node = &self.octree.nodes[node_index as usize];
+            if !is_in_frustum(&self.projection_from_obj, &node.bounding_box) {
+                return None;
+            }
+
+            let pixel_world_size = if view.is_orthographic {
+                view.pixel_world_size_from_camera_distance
+            } else {
+                let center = self
+                    .world_from_obj
+                    .transform_point3(node.bounding_box.center());
+                let radius = node.bounding_box.size().length() * 0.5 * self.scale;
+                // Distance to the closest point of the node's bounding sphere.
+                let distance = (center.distance(view.camera_position) - radius).max(1e-6);
+                view.pixel_world_size_from_camera_distance * distance
+            };
+
+            Some(Candidate {
+                point_spacing_on_screen: node.point_spacing * self.scale / pixel_world_size,
+                cloud_index,
+                node_index,
+            })
+        }
+    }
+
+    let clouds = clouds
+        .map(|(octree, world_from_obj)| CloudView {
+            octree,
+            world_from_obj,
+            projection_from_obj: view.projection_from_world * world_from_obj,
+            scale: glam::Mat3::from_mat4(world_from_obj)
+                .determinant()
+                .abs()
+                .cbrt(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut candidates = BinaryHeap::new();
+    for (cloud_index, cloud) in clouds.iter().enumerate() {
+        if !cloud.octree.nodes.is_empty() {
+            candidates.extend(cloud.candidate(view, cloud_index, 0));
+        }
+    }
+
+    let mut selected_ranges = vec![Vec::new(); clouds.len()];
+    let mut remaining_budget = view.point_budget;
+    while let Some(candidate) = candidates.pop() {
+        let cloud = &clouds[candidate.cloud_index];
+        let node = &cloud.octree.nodes[candidate.node_index as usize];
+
+        let num_points = node.point_range.len() as u32;
+        if num_points > remaining_budget {
+            // A smaller node might still fit, but children are never drawn without their parent.
+            continue;
+        }
+        remaining_budget -= num_points;
+        selected_ranges[candidate.cloud_index].push(node.point_range.clone());
+
+        if candidate.point_spacing_on_screen > view.target_point_spacing {
+            for &child_index in &node.children {
+                candidates.extend(cloud.candidate(view, candidate.cloud_index, child_index));
+            }
+        }
+    }
+
+    // Nodes are stored depth first, so many of the selected ranges are adjacent.
+    for ranges in &mut selected_ranges {
+        ranges.sort_by_key(|range| range.start);
+        let mut merged_ranges: Vec<Range<u32>> = Vec::with_capacity(ranges.len());
+        for range in ranges.drain(..) {
+            match merged_ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => merged_ranges.push(range),
+            }
+        }
+        *ranges = merged_ranges;
+    }
+
+    selected_ranges
+}
+
+/// Conservative test whether a bounding box is (partially) inside the view frustum.
+///
+/// Assumes a reverse-z projection, i.e. the near plane is at depth 1.
+fn is_in_frustum(projection_from_obj: &glam::Mat4, bounding_box: &macaw::BoundingBox) -> bool {
+    let corners = bounding_box
+        .corners()
+        .map(|corner| *projection_from_obj * corner.extend(1.0));
+
+    let all_outside = |is_outside: fn(&glam::Vec4) -> bool| corners.iter().all(is_outside);
+    !(all_outside(|c| c.x < -c.w)
+        || all_outside(|c| c.x > c.w)
+        || all_outside(|c| c.y < -c.w)
+        || all_outside(|c| c.y > c.w)
+        || all_outside(|c| c.z > c.w)
+        || all_outside(|c| c.z < 0.0))
+}
@@ -1,6 +1,8 @@
 use crate::{
+    allocator::create_and_fill_uniform_buffer,
     context::SharedRendererData,
     include_file,
+    outlines::{gpu_data::OutlineConfigUniformBuffer, OutlineConfig},
     wgpu_resources::{
         BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
         GpuRenderPipelineHandle, GpuTexture, PipelineLayoutDesc, RenderPipelineDesc,
@@ -19,8 +21,8 @@ pub struct Compositor {
 
 #[derive(Clone)]
 pub struct CompositorDrawData {
-    /// [`GpuBindGroup`] pointing at the current image source and
-    /// a uniform buffer for describing a tonemapper/compositor configuration.
+    /// [`GpuBindGroup`] pointing at the current image source,
+    /// the outline distance field and a uniform buffer for describing the outlines.
     bind_group: GpuBindGroup,
 }
 
@@ -29,7 +31,28 @@ impl DrawData for CompositorDrawData {
 }
 
 impl CompositorDrawData {
-    pub fn new(ctx: &mut RenderContext, target: &GpuTexture) -> Self {
+    /// `outlines` is the final result of the outline jump flooding with the outline configuration, if any.
+    pub fn new(
+        ctx: &mut RenderContext,
+        target: &GpuTexture,
+        outlines: Option<(&GpuTexture, &OutlineConfig)>,
+    ) -> Self {
+        let (outline_voronoi_texture, outline_config) = match outlines {
+            Some((voronoi_texture, config)) => (
+                voronoi_texture,
+                OutlineConfigUniformBuffer {
+                    color_layer_a: config.color_layer_a.into(),
+                    color_layer_b: config.color_layer_b.into(),
+                    outline_radius_pixel: config.outline_radius_pixel.into(),
+                    end_padding: Default::default(),
+                },
+            ),
+            // The texture isn't used without outlines, but we still need to bind something.
+            None => (target, OutlineConfigUniformBuffer::disabled()),
+        };
+        let outline_config_buffer =
+            create_and_fill_uniform_buffer(ctx, "compositor outline config".into(), outline_config);
+
         let mut renderers = ctx.renderers.write();
         let compositor = renderers.get_or_create::<_, Compositor>(
             &ctx.shared_renderer_data,
@@ -43,7 +66,11 @@ impl CompositorDrawData {
                 &ctx.gpu_resources,
                 &BindGroupDesc {
                     label: "compositor".into(),
-                    entries: smallvec![BindGroupEntry::DefaultTextureView(target.handle)],
+                    entries: smallvec![
+                        BindGroupEntry::DefaultTextureView(target.handle),
+                        BindGroupEntry::DefaultTextureView(outline_voronoi_texture.handle),
+                        outline_config_buffer,
+                    ],
                     layout: compositor.bind_group_layout,
                 },
             ),
@@ -64,16 +91,41 @@ impl Renderer for Compositor {
             device,
             &BindGroupLayoutDesc {
                 label: "compositor".into(),
-                entries: vec![wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
+                entries: vec![
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: (std::mem::size_of::<OutlineConfigUniformBuffer>()
+                                as u64)
+                                .try_into()
+                                .ok(),
+                        },
+                        count: None,
                     },
-                    count: None,
-                }],
+                ],
             },
         );
 
@@ -17,14 +17,16 @@ use std::num::NonZeroU32;
 use crate::{
     allocator::create_and_fill_uniform_buffer_batch,
     include_file,
-    resource_managers::ResourceManagerError,
+    outlines::OutlineMaskProcessor,
+    picking_layer::PickingLayerProcessor,
+    resource_managers::{ResourceManagerError, Texture2DCreationDesc},
     view_builder::ViewBuilder,
     wgpu_resources::{
         BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
         GpuRenderPipelineHandle, GpuTexture, PipelineLayoutDesc, RenderPipelineDesc,
         ShaderModuleDesc, TextureDesc,
     },
-    ColorMap,
+    ColorMap, OutlineMaskPreference, PickingLayerId,
 };
 
 use super::{
@@ -43,9 +45,11 @@ mod gpu_data {
         pub depth_camera_extrinsics: crate::wgpu_buffer_types::Mat4,
         pub depth_camera_intrinsics: crate::wgpu_buffer_types::Mat3,
         pub radius_scale: crate::wgpu_buffer_types::F32RowPadded,
-        pub colormap: crate::wgpu_buffer_types::U32RowPadded,
+        pub colormap: crate::wgpu_buffer_types::UVec2RowPadded,
+        pub outline_mask_id: crate::wgpu_buffer_types::UVec2RowPadded,
+        pub picking_layer_id: [u32; 4],
 
-        pub end_padding: [crate::wgpu_buffer_types::PaddingRow; 16 - 9],
+        pub end_padding: [crate::wgpu_buffer_types::PaddingRow; 16 - 11],
     }
 }
 
@@ -94,6 +98,38 @@ pub struct DepthCloud {
 
     /// Configures color mapping mode.
     pub colormap: ColorMap,
+
+    /// Custom color lookup table of evenly spaced sRGB colors, used instead of [`Self::colormap`].
+    ///
+    /// Falls back to [`Self::colormap`] if empty or longer than [`DepthCloud::MAX_COLORMAP_LUT_SIZE`].
+    pub colormap_lut: Option<std::sync::Arc<[[u8; 4]]>>,
+
+    /// Whether and with which ids this depth cloud is drawn into the outline mask.
+    pub outline_mask_id: OutlineMaskPreference,
+
+    /// Picking id written for every point of this depth cloud.
+    pub picking_layer_id: PickingLayerId,
+}
+
+impl DepthCloud {
+    /// Maximum number of colors in [`Self::colormap_lut`].
+    ///
+    /// The lookup table is a single row texture, this is the smallest maximum texture size we support.
+    pub const MAX_COLORMAP_LUT_SIZE: usize = 2048;
+
+    fn valid_colormap_lut(&self) -> Option<&[[u8; 4]]> {
+        let lut = self.colormap_lut.as_deref()?;
+        if lut.is_empty() || lut.len() > Self::MAX_COLORMAP_LUT_SIZE {
+            re_log::warn_once!(
+                "Depth cloud color lookup tables need between 1 and {} colors, got {}",
+                Self::MAX_COLORMAP_LUT_SIZE,
+                lut.len()
+            );
+            None
+        } else {
+            Some(lut)
+        }
+    }
 }
 
 impl Default for DepthCloud {
@@ -105,14 +141,24 @@ impl Default for DepthCloud {
             depth_dimensions: glam::UVec2::ZERO,
             depth_data: DepthCloudDepthData::default(),
             colormap: ColorMap::ColorMapTurbo,
+            colormap_lut: None,
+            outline_mask_id: OutlineMaskPreference::NONE,
+            picking_layer_id: PickingLayerId::default(),
         }
     }
 }
 
+#[derive(Clone)]
+struct DepthCloudDrawInstance {
+    bind_group: GpuBindGroup,
+    num_points: u32,
+    render_outline_mask: bool,
+}
+
 #[derive(Clone)]
 pub struct DepthCloudDrawData {
     // Every single point clouds and their respective total number of points.
-    bind_groups: Vec<(u32, GpuBindGroup)>,
+    instances: Vec<DepthCloudDrawInstance>,
 }
 
 impl DrawData for DepthCloudDrawData {
@@ -139,7 +185,7 @@ impl DepthCloudDrawData {
 
         if depth_clouds.is_empty() {
             return Ok(DepthCloudDrawData {
-                bind_groups: Vec::new(),
+                instances: Vec::new(),
             });
         }
 
@@ -150,12 +196,18 @@ impl DepthCloudDrawData {
                 depth_camera_extrinsics: info.depth_camera_extrinsics.into(),
                 depth_camera_intrinsics: info.depth_camera_intrinsics.into(),
                 radius_scale: info.radius_scale.into(),
-                colormap: (info.colormap as u32).into(),
+                colormap: [
+                    info.colormap as u32,
+                    info.valid_colormap_lut().is_some() as u32,
+                ]
+                .into(),
+                outline_mask_id: info.outline_mask_id.ids().into(),
+                picking_layer_id: info.picking_layer_id.into(),
                 end_padding: Default::default(),
             }),
         );
 
-        let mut bind_groups = Vec::with_capacity(depth_clouds.len());
+        let mut instances = Vec::with_capacity(depth_clouds.len());
         for (depth_cloud, ubo) in depth_clouds.iter().zip(depth_cloud_ubos.into_iter()) {
             let depth_texture = match &depth_cloud.depth_data {
                 // On native, we can use D16 textures without issues, but they aren't supported on
@@ -182,9 +234,24 @@ impl DepthCloudDrawData {
                 }
             };
 
-            bind_groups.push((
-                depth_cloud.depth_dimensions.x * depth_cloud.depth_dimensions.y,
-                ctx.gpu_resources.bind_groups.alloc(
+            let colormap_lut = match depth_cloud.valid_colormap_lut() {
+                Some(lut) => ctx.texture_manager_2d.create(
+                    &mut ctx.gpu_resources.textures,
+                    &Texture2DCreationDesc {
+                        label: "depth_cloud_colormap_lut".into(),
+                        data: bytemuck::cast_slice(lut),
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        width: lut.len() as u32,
+                        height: 1,
+                    },
+                ),
+                None => ctx.texture_manager_2d.white_texture_handle().clone(),
+            };
+            let colormap_lut = ctx.texture_manager_2d.get(&colormap_lut)?;
+
+            instances.push(DepthCloudDrawInstance {
+                num_points: depth_cloud.depth_dimensions.x * depth_cloud.depth_dimensions.y,
+                bind_group: ctx.gpu_resources.bind_groups.alloc(
                     &ctx.device,
                     &ctx.gpu_resources,
                     &BindGroupDesc {
@@ -192,14 +259,16 @@ impl DepthCloudDrawData {
                         entries: smallvec![
                             ubo,
                             BindGroupEntry::DefaultTextureView(depth_texture.handle),
+                            BindGroupEntry::DefaultTextureView(colormap_lut.handle),
                         ],
                         layout: bg_layout,
                     },
                 ),
-            ));
+                render_outline_mask: depth_cloud.outline_mask_id.is_some(),
+            });
         }
 
-        Ok(DepthCloudDrawData { bind_groups })
+        Ok(DepthCloudDrawData { instances })
     }
 }
 
@@ -267,6 +336,8 @@ fn create_and_upload_texture<T: bytemuck::Pod>(
 
 pub struct DepthCloudRenderer {
     render_pipeline: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
+    render_pipeline_picking_layer: GpuRenderPipelineHandle,
     bind_group_layout: GpuBindGroupLayoutHandle,
 }
 
@@ -309,6 +380,16 @@ impl Renderer for DepthCloudRenderer {
                         },
                         count: None,
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
                 ],
             },
         );
@@ -331,28 +412,55 @@ impl Renderer for DepthCloudRenderer {
             },
         );
 
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "depth_cloud_rp".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+            vertex_buffers: smallvec![],
+            render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: wgpu::MultisampleState {
+                // We discard pixels to do the round cutout, therefore we need to
+                // calculate our own sampling mask.
+                alpha_to_coverage_enabled: true,
+                ..ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE
+            },
+        };
         let render_pipeline = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "depth_cloud_rp - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE,
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_picking_layer = pools.render_pipelines.get_or_create(
             device,
             &RenderPipelineDesc {
-                label: "depth_cloud_rp".into(),
-                pipeline_layout,
-                vertex_entrypoint: "vs_main".into(),
-                vertex_handle: shader_module,
-                fragment_entrypoint: "fs_main".into(),
-                fragment_handle: shader_module,
-                vertex_buffers: smallvec![],
-                render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    ..Default::default()
-                },
-                depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
-                multisample: wgpu::MultisampleState {
-                    // We discard pixels to do the round cutout, therefore we need to
-                    // calculate our own sampling mask.
-                    alpha_to_coverage_enabled: true,
-                    ..ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE
-                },
+                label: "depth_cloud_rp - picking layer".into(),
+                fragment_entrypoint: "fs_main_picking_layer".into(),
+                render_targets: smallvec![Some(PickingLayerProcessor::PICKING_LAYER_FORMAT.into())],
+                depth_stencil: PickingLayerProcessor::PICKING_LAYER_DEPTH_STATE,
+                multisample: PickingLayerProcessor::PICKING_LAYER_MSAA_STATE,
+                ..render_pipeline_desc
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
@@ -360,6 +468,8 @@ impl Renderer for DepthCloudRenderer {
 
         DepthCloudRenderer {
             render_pipeline,
+            render_pipeline_outline_mask,
+            render_pipeline_picking_layer,
             bind_group_layout,
         }
     }
@@ -367,23 +477,42 @@ impl Renderer for DepthCloudRenderer {
     fn draw<'a>(
         &self,
         pools: &'a WgpuResourcePools,
-        _phase: DrawPhase,
+        phase: DrawPhase,
         pass: &mut wgpu::RenderPass<'a>,
         draw_data: &'a Self::RendererDrawData,
     ) -> anyhow::Result<()> {
         crate::profile_function!();
-        if draw_data.bind_groups.is_empty() {
+
+        let draw_outline_mask = phase == DrawPhase::OutlineMask;
+        let mut instances = draw_data
+            .instances
+            .iter()
+            .filter(|instance| !draw_outline_mask || instance.render_outline_mask)
+            .peekable();
+        if instances.peek().is_none() {
             return Ok(());
         }
 
-        let pipeline = pools.render_pipelines.get_resource(self.render_pipeline)?;
+        let pipeline = pools.render_pipelines.get_resource(match phase {
+            DrawPhase::OutlineMask => self.render_pipeline_outline_mask,
+            DrawPhase::PickingLayer => self.render_pipeline_picking_layer,
+            _ => self.render_pipeline,
+        })?;
         pass.set_pipeline(pipeline);
 
-        for (num_points, bind_group) in &draw_data.bind_groups {
-            pass.set_bind_group(1, bind_group, &[]);
-            pass.draw(0..*num_points * 6, 0..1);
+        for instance in instances {
+            pass.set_bind_group(1, &instance.bind_group, &[]);
+            pass.draw(0..instance.num_points * 6, 0..1);
         }
 
         Ok(())
     }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[
+            DrawPhase::Opaque,
+            DrawPhase::OutlineMask,
+            DrawPhase::PickingLayer,
+        ]
+    }
 }
@@ -0,0 +1,654 @@
+//! Renderer for text labels anchored at positions in the scene.
+//!
+//! Glyphs come from the [`crate::GlyphAtlas`] of the [`RenderContext`], which needs a font to be set first.
+//!
+//! ## Implementation details
+//!
+//! Every label is a camera facing billboard made up of a background quad and one quad per glyph,
+//! all of them drawn with a single instanced draw call, one instance per quad.
+//! Labels are drawn on top of the scene in [`DrawPhase::Overlay`] which happens after all other geometry.
+//! Instead of depth testing every fragment, a label is hidden entirely
+//! if the depth buffer has something in front of its anchor (occlusion culling),
+//! which keeps labels readable even if their anchor is just barely visible.
+//!
+//! Label collision avoidance happens on the CPU when creating the draw data:
+//! Labels are placed greedily in order of priority and distance to the camera,
+//! skipping all labels that would overlap a label placed before.
+//! Since occlusion is only known on the GPU, occluded labels still take up space in this process.
+
+use smallvec::smallvec;
+
+use crate::{
+    include_file,
+    outlines::OutlineMaskProcessor,
+    picking_layer::PickingLayerProcessor,
+    view_builder::ViewBuilder,
+    wgpu_resources::{
+        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, BufferDesc, GpuBindGroup,
+        GpuBindGroupLayoutHandle, GpuBuffer, GpuRenderPipelineHandle, GpuTexture,
+        PipelineLayoutDesc, RenderPipelineDesc, ShaderModuleDesc,
+    },
+    Color32, OutlineMaskPreference, PickingLayerId, Size,
+};
+
+use super::{
+    DrawData, DrawPhase, FileResolver, FileSystem, RenderContext, Renderer, SharedRendererData,
+    WgpuResourcePools,
+};
+
+mod gpu_data {
+    use crate::wgpu_resources::VertexBufferLayout;
+
+    /// A glyph or background quad of a label.
+    ///
+    /// Keep in sync with `QuadIn` in `labels.wgsl`.
+    #[repr(C)]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct LabelQuad {
+        pub anchor: glam::Vec3,
+
+        /// Height of a line of text, see [`crate::Size`].
+        pub size: f32,
+
+        /// Min & max of the quad in line heights relative to the anchor, y pointing down.
+        pub rect: [f32; 4],
+
+        /// Min & max texture coordinates in the glyph atlas, negative for the background.
+        pub texcoords: [f32; 4],
+        pub color: crate::Color32,
+
+        /// Pixel coordinates of the anchor & the depth something needs to exceed to hide the label.
+        pub occlusion_test: [f32; 3],
+        pub outline_mask_ids: [u32; 2],
+        pub picking_layer_id: [u32; 4],
+    }
+
+    pub fn quad_vertex_buffer_layout() -> VertexBufferLayout {
+        VertexBufferLayout {
+            array_stride: std::mem::size_of::<LabelQuad>() as _,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: VertexBufferLayout::attributes_from_formats(
+                0,
+                [
+                    wgpu::VertexFormat::Float32x3, // anchor
+                    wgpu::VertexFormat::Float32,   // size
+                    wgpu::VertexFormat::Float32x4, // rect
+                    wgpu::VertexFormat::Float32x4, // texcoords
+                    wgpu::VertexFormat::Unorm8x4,  // color
+                    wgpu::VertexFormat::Float32x3, // occlusion_test
+                    wgpu::VertexFormat::Uint32x2,  // outline_mask_ids
+                    wgpu::VertexFormat::Uint32x4,  // picking_layer_id
+                ]
+                .into_iter(),
+            ),
+        }
+    }
+}
+
+/// A text label anchored at a position in the scene, see [`LabelDrawData::new`].
+#[derive(Clone, Debug)]
+pub struct LabelInstance {
+    /// Text of the label, lines are separated by `\n`.
+    pub text: String,
+
+    /// The label is placed centered below this position.
+    pub anchor: glam::Vec3,
+
+    /// Height of a line of text, either in scene units or in ui points.
+    ///
+    /// [`Size::AUTO`] and [`Size::AUTO_LARGE`] use [`LabelInstance::DEFAULT_SIZE_POINTS`].
+    pub size: Size,
+
+    /// Color of the text, sRGB with unmultiplied alpha.
+    pub text_color: Color32,
+
+    /// Color of the rectangle behind the text, sRGB with unmultiplied alpha.
+    pub background_color: Color32,
+
+    /// Labels with a higher priority are placed first when avoiding collisions.
+    ///
+    /// Labels of the same priority are placed closest to the camera first.
+    pub priority: f32,
+
+    /// Whether and with which ids this label is drawn into the outline mask.
+    pub outline_mask_ids: OutlineMaskPreference,
+
+    /// Picking id written for every pixel covered by the label, including its background.
+    pub picking_layer_id: PickingLayerId,
+}
+
+impl LabelInstance {
+    /// Line height of labels with an automatic size, in ui points.
+    pub const DEFAULT_SIZE_POINTS: f32 = 14.0;
+}
+
+/// View dependent parameters for placing labels, retrieved from [`ViewBuilder::label_view`].
+#[derive(Clone)]
+pub struct LabelView {
+    pub projection_from_world: glam::Mat4,
+    pub camera_position: glam::Vec3,
+    pub camera_forward: glam::Vec3,
+
+    /// How wide a pixel is in world space at unit distance from the camera.
+    ///
+    /// For orthographic projections this is the width of a pixel in world space, independent of distance.
+    pub pixel_world_size_from_camera_distance: f32,
+    pub is_orthographic: bool,
+    pub pixels_from_point: f32,
+    pub resolution_in_pixel: [u32; 2],
+
+    /// If true, labels that would overlap labels placed before them are skipped.
+    pub avoid_collisions: bool,
+
+    /// Depth buffer of the view, labels whose anchor is behind it are hidden.
+    pub(crate) depth_buffer: GpuTexture,
+}
+
+impl LabelView {
+    /// How far in front of a label's anchor something needs to be to hide the label, in pixels.
+    ///
+    /// Labels are usually attached to something that is drawn itself, like a point,
+    /// whose surface is a bit in front of the anchor.
+    const OCCLUSION_TOLERANCE_PIXELS: f32 = 8.0;
+
+    fn pixel_world_size_at(&self, position: glam::Vec3) -> f32 {
+        if self.is_orthographic {
+            self.pixel_world_size_from_camera_distance
+        } else {
+            self.pixel_world_size_from_camera_distance * self.camera_position.distance(position)
+        }
+    }
+
+    /// Position of a world position in pixels along with its depth, `None` if it is behind the camera.
+    fn pixel_and_depth(&self, position: glam::Vec3) -> Option<glam::Vec3> {
+        let clip = self.projection_from_world * position.extend(1.0);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        let ndc = clip.truncate() / clip.w;
+        let resolution = glam::UVec2::from(self.resolution_in_pixel).as_vec2();
+        Some(glam::vec3(
+            (ndc.x * 0.5 + 0.5) * resolution.x,
+            (0.5 - ndc.y * 0.5) * resolution.y,
+            ndc.z,
+        ))
+    }
+}
+
+/// Background of a label in line heights: padding around the text and gap between anchor and background.
+const BACKGROUND_PADDING: glam::Vec2 = glam::Vec2::new(0.3, 0.15);
+const ANCHOR_GAP: f32 = 0.2;
+
+/// Accelerates overlap tests between placed labels by sorting them into a grid of screen cells.
+struct ScreenOccupancy {
+    cells: ahash::HashMap<[i32; 2], Vec<[glam::Vec2; 2]>>,
+}
+
+impl ScreenOccupancy {
+    const CELL_SIZE: f32 = 64.0;
+
+    fn cell_range(min: glam::Vec2, max: glam::Vec2) -> impl Iterator<Item = [i32; 2]> {
+        let min = (min / Self::CELL_SIZE).floor().as_ivec2();
+        let max = (max / Self::CELL_SIZE).floor().as_ivec2();
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| [x, y]))
+    }
+
+    fn overlaps(&self, min: glam::Vec2, max: glam::Vec2) -> bool {
+        Self::cell_range(min, max).any(|cell| {
+            self.cells.get(&cell).map_or(false, |rects| {
+                rects.iter().any(|[other_min, other_max]| {
+                    min.cmplt(*other_max).all() && other_min.cmplt(max).all()
+                })
+            })
+        })
+    }
+
+    fn insert(&mut self, min: glam::Vec2, max: glam::Vec2) {
+        for cell in Self::cell_range(min, max) {
+            self.cells.entry(cell).or_default().push([min, max]);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LabelDrawData {
+    bind_group: Option<GpuBindGroup>,
+    quad_buffer: Option<GpuBuffer>,
+    num_quads: u32,
+    render_outline_mask: bool,
+}
+
+impl DrawData for LabelDrawData {
+    type Renderer = LabelRenderer;
+}
+
+impl LabelDrawData {
+    /// Places all labels for the given view, skipping those that would overlap if requested.
+    ///
+    /// Draws nothing if the [`crate::GlyphAtlas`] has no font.
+    pub fn new(ctx: &mut RenderContext, view: &LabelView, labels: &[LabelInstance]) -> Self {
+        crate::profile_function!();
+
+        let bind_group_layout = ctx
+            .renderers
+            .write()
+            .get_or_create::<_, LabelRenderer>(
+                &ctx.shared_renderer_data,
+                &mut ctx.gpu_resources,
+                &ctx.device,
+                &mut ctx.resolver,
+            )
+            .bind_group_layout;
+
+        let empty = LabelDrawData {
+            bind_group: None,
+            quad_buffer: None,
+            num_quads: 0,
+            render_outline_mask: false,
+        };
+        if labels.is_empty() {
+            return empty;
+        }
+
+        let mut glyph_atlas = ctx.glyph_atlas.write();
+        if !glyph_atlas.has_font() {
+            re_log::warn_once!("No font was set for the glyph atlas, labels are not drawn");
+            return empty;
+        }
+
+        // Place the most important labels first.
+        let mut candidates = labels
+            .iter()
+            .filter_map(|label| {
+                let pixel_and_depth = view.pixel_and_depth(label.anchor)?;
+                Some((label, pixel_and_depth))
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(a, a_pixel), (b, b_pixel)| {
+            b.priority
+                .total_cmp(&a.priority)
+                .then(b_pixel.z.total_cmp(&a_pixel.z)) // Reverse-z, closest first.
+        });
+
+        let resolution = glam::UVec2::from(view.resolution_in_pixel).as_vec2();
+        let mut occupancy = ScreenOccupancy {
+            cells: Default::default(),
+        };
+        let mut num_placed_labels = 0;
+        let mut render_outline_mask = false;
+        let mut quads = Vec::new();
+
+        for (label, pixel_and_depth) in candidates {
+            let Some(layout) = glyph_atlas.layout(&label.text) else {
+                continue;
+            };
+
+            let size = if label.size.is_auto() {
+                Size::new_points(LabelInstance::DEFAULT_SIZE_POINTS)
+            } else {
+                label.size
+            };
+            let pixel_world_size = view.pixel_world_size_at(label.anchor);
+            let line_height_in_pixel = match size.points() {
+                Some(points) => points * view.pixels_from_point,
+                None => size.0 / pixel_world_size,
+            };
+
+            let background_min =
+                glam::vec2(-layout.size.x * 0.5 - BACKGROUND_PADDING.x, ANCHOR_GAP);
+            let background_max = glam::vec2(
+                layout.size.x * 0.5 + BACKGROUND_PADDING.x,
+                ANCHOR_GAP + layout.size.y + 2.0 * BACKGROUND_PADDING.y,
+            );
+            let min = pixel_and_depth.truncate() + background_min * line_height_in_pixel;
+            let max = pixel_and_depth.truncate() + background_max * line_height_in_pixel;
+
+            // Entirely off screen.
+            if max.cmplt(glam::Vec2::ZERO).any() || min.cmpgt(resolution).any() {
+                continue;
+            }
+            if view.avoid_collisions {
+                // What's off screen can't overlap.
+                let min = min.max(glam::Vec2::ZERO);
+                let max = max.min(resolution);
+                if occupancy.overlaps(min, max) {
+                    continue;
+                }
+                occupancy.insert(min, max);
+            }
+
+            let toward_camera = if view.is_orthographic {
+                -view.camera_forward
+            } else {
+                (view.camera_position - label.anchor).normalize_or_zero()
+            };
+            let occlusion_depth = view
+                .pixel_and_depth(
+                    label.anchor
+                        + toward_camera * LabelView::OCCLUSION_TOLERANCE_PIXELS * pixel_world_size,
+                )
+                .map_or(f32::INFINITY, |pixel_and_depth| pixel_and_depth.z);
+
+            let quad = gpu_data::LabelQuad {
+                anchor: label.anchor,
+                size: size.0,
+                rect: [
+                    background_min.x,
+                    background_min.y,
+                    background_max.x,
+                    background_max.y,
+                ],
+                texcoords: [-1.0; 4],
+                color: label.background_color,
+                occlusion_test: [pixel_and_depth.x, pixel_and_depth.y, occlusion_depth],
+                outline_mask_ids: label.outline_mask_ids.ids().map(u32::from),
+                picking_layer_id: label.picking_layer_id.into(),
+            };
+            quads.push(quad);
+
+            let text_offset = glam::vec2(0.0, ANCHOR_GAP + BACKGROUND_PADDING.y);
+            quads.extend(layout.glyphs.iter().map(|glyph| {
+                let min = glyph.min + text_offset;
+                let max = glyph.max + text_offset;
+                gpu_data::LabelQuad {
+                    rect: [min.x, min.y, max.x, max.y],
+                    texcoords: [
+                        glyph.uv_min.x,
+                        glyph.uv_min.y,
+                        glyph.uv_max.x,
+                        glyph.uv_max.y,
+                    ],
+                    color: label.text_color,
+                    ..quad
+                }
+            }));
+
+            num_placed_labels += 1;
+            render_outline_mask |= label.outline_mask_ids.is_some();
+        }
+
+        if quads.is_empty() {
+            return empty;
+        }
+        re_log::trace!("Placed {num_placed_labels} of {} labels", labels.len());
+
+        let glyph_atlas_texture =
+            glyph_atlas.texture(&ctx.device, &ctx.queue, &ctx.gpu_resources.textures);
+        drop(glyph_atlas);
+
+        let quad_buffer = ctx.gpu_resources.buffers.alloc(
+            &ctx.device,
+            &BufferDesc {
+                label: "label quads".into(),
+                size: std::mem::size_of_val(quads.as_slice()) as _,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+        ctx.queue
+            .write_buffer(&quad_buffer, 0, bytemuck::cast_slice(&quads));
+
+        let bind_group = ctx.gpu_resources.bind_groups.alloc(
+            &ctx.device,
+            &ctx.gpu_resources,
+            &BindGroupDesc {
+                label: "label_bg".into(),
+                entries: smallvec![
+                    BindGroupEntry::DefaultTextureView(glyph_atlas_texture.handle),
+                    BindGroupEntry::DefaultTextureView(view.depth_buffer.handle),
+                ],
+                layout: bind_group_layout,
+            },
+        );
+
+        LabelDrawData {
+            bind_group: Some(bind_group),
+            quad_buffer: Some(quad_buffer),
+            num_quads: quads.len() as u32,
+            render_outline_mask,
+        }
+    }
+}
+
+pub struct LabelRenderer {
+    render_pipeline_overlay: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
+    render_pipeline_picking_layer: GpuRenderPipelineHandle,
+    bind_group_layout: GpuBindGroupLayoutHandle,
+}
+
+impl Renderer for LabelRenderer {
+    type RendererDrawData = LabelDrawData;
+
+    fn create_renderer<Fs: FileSystem>(
+        shared_data: &SharedRendererData,
+        pools: &mut WgpuResourcePools,
+        device: &wgpu::Device,
+        resolver: &mut FileResolver<Fs>,
+    ) -> Self {
+        crate::profile_function!();
+
+        let bind_group_layout = pools.bind_group_layouts.get_or_create(
+            device,
+            &BindGroupLayoutDesc {
+                label: "label_bg_layout".into(),
+                entries: vec![
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: true,
+                        },
+                        count: None,
+                    },
+                ],
+            },
+        );
+
+        let pipeline_layout = pools.pipeline_layouts.get_or_create(
+            device,
+            &PipelineLayoutDesc {
+                label: "label_rp_layout".into(),
+                entries: vec![shared_data.global_bindings.layout, bind_group_layout],
+            },
+            &pools.bind_group_layouts,
+        );
+
+        let shader_module = pools.shader_modules.get_or_create(
+            device,
+            resolver,
+            &ShaderModuleDesc {
+                label: "labels".into(),
+                source: include_file!("../../shader/labels.wgsl"),
+            },
+        );
+
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "label_rp - overlay".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+            vertex_buffers: smallvec![gpu_data::quad_vertex_buffer_layout()],
+            render_targets: smallvec![Some(wgpu::ColorTargetState {
+                format: ViewBuilder::MAIN_TARGET_COLOR_FORMAT,
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                // Billboards always face the camera.
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+        };
+        let render_pipeline_overlay = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+
+        // Like in the overlay, labels are on top of everything in the outline mask & picking layer.
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "label_rp - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE.map(|state| {
+                    wgpu::DepthStencilState {
+                        depth_compare: wgpu::CompareFunction::Always,
+                        ..state
+                    }
+                }),
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_picking_layer = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "label_rp - picking layer".into(),
+                fragment_entrypoint: "fs_main_picking_layer".into(),
+                render_targets: smallvec![Some(PickingLayerProcessor::PICKING_LAYER_FORMAT.into())],
+                depth_stencil: PickingLayerProcessor::PICKING_LAYER_DEPTH_STATE.map(|state| {
+                    wgpu::DepthStencilState {
+                        depth_compare: wgpu::CompareFunction::Always,
+                        ..state
+                    }
+                }),
+                multisample: PickingLayerProcessor::PICKING_LAYER_MSAA_STATE,
+                ..render_pipeline_desc
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+
+        LabelRenderer {
+            render_pipeline_overlay,
+            render_pipeline_outline_mask,
+            render_pipeline_picking_layer,
+            bind_group_layout,
+        }
+    }
+
+    fn draw<'a>(
+        &self,
+        pools: &'a WgpuResourcePools,
+        phase: DrawPhase,
+        pass: &mut wgpu::RenderPass<'a>,
+        draw_data: &'a Self::RendererDrawData,
+    ) -> anyhow::Result<()> {
+        crate::profile_function!();
+
+        let (Some(bind_group), Some(quad_buffer)) =
+            (&draw_data.bind_group, &draw_data.quad_buffer) else {
+            return Ok(()); // No labels.
+        };
+        if phase == DrawPhase::OutlineMask && !draw_data.render_outline_mask {
+            return Ok(());
+        }
+
+        let pipeline = pools.render_pipelines.get_resource(match phase {
+            DrawPhase::OutlineMask => self.render_pipeline_outline_mask,
+            DrawPhase::PickingLayer => self.render_pipeline_picking_layer,
+            _ => self.render_pipeline_overlay,
+        })?;
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(1, bind_group, &[]);
+        pass.set_vertex_buffer(0, quad_buffer.slice(..));
+        pass.draw(0..6, 0..draw_data.num_quads);
+
+        Ok(())
+    }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[
+            DrawPhase::Overlay,
+            DrawPhase::OutlineMask,
+            DrawPhase::PickingLayer,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScreenOccupancy;
+
+    fn occupancy_with(rects: &[[glam::Vec2; 2]]) -> ScreenOccupancy {
+        let mut occupancy = ScreenOccupancy {
+            cells: Default::default(),
+        };
+        for [min, max] in rects {
+            occupancy.insert(*min, *max);
+        }
+        occupancy
+    }
+
+    #[test]
+    fn empty_occupancy_overlaps_nothing() {
+        let occupancy = occupancy_with(&[]);
+        assert!(!occupancy.overlaps(glam::vec2(0.0, 0.0), glam::vec2(1000.0, 1000.0)));
+    }
+
+    #[test]
+    fn overlaps_inserted_rects() {
+        let occupancy = occupancy_with(&[[glam::vec2(10.0, 10.0), glam::vec2(50.0, 20.0)]]);
+
+        // Partially overlapping, containing and contained.
+        assert!(occupancy.overlaps(glam::vec2(40.0, 15.0), glam::vec2(60.0, 30.0)));
+        assert!(occupancy.overlaps(glam::vec2(0.0, 0.0), glam::vec2(100.0, 100.0)));
+        assert!(occupancy.overlaps(glam::vec2(20.0, 12.0), glam::vec2(30.0, 18.0)));
+
+        // Next to it, only touching edges doesn't count as overlapping.
+        assert!(!occupancy.overlaps(glam::vec2(50.0, 10.0), glam::vec2(60.0, 20.0)));
+        assert!(!occupancy.overlaps(glam::vec2(10.0, 20.0), glam::vec2(50.0, 30.0)));
+        assert!(!occupancy.overlaps(glam::vec2(0.0, 30.0), glam::vec2(60.0, 40.0)));
+    }
+
+    #[test]
+    fn rects_spanning_several_cells() {
+        let size = ScreenOccupancy::CELL_SIZE;
+
+        // Spans four cells around the origin, including negative ones.
+        let occupancy = occupancy_with(&[[
+            glam::Vec2::splat(-0.5 * size),
+            glam::Vec2::splat(0.5 * size),
+        ]]);
+        for corner in [
+            glam::vec2(-1.0, -1.0),
+            glam::vec2(1.0, -1.0),
+            glam::vec2(-1.0, 1.0),
+            glam::vec2(1.0, 1.0),
+        ] {
+            let min = corner * 0.4 * size;
+            assert!(occupancy.overlaps(min, min + glam::Vec2::ONE));
+        }
+
+        // Same cells, but outside of the rect.
+        assert!(!occupancy.overlaps(glam::Vec2::splat(0.6 * size), glam::Vec2::splat(0.9 * size)));
+
+        // Large rect covering many cells, overlapping only in one of them.
+        let occupancy =
+            occupancy_with(&[[glam::vec2(3.5 * size, 0.0), glam::vec2(3.6 * size, 1.0)]]);
+        assert!(occupancy.overlaps(glam::vec2(0.0, 0.0), glam::vec2(10.0 * size, 10.0 * size)));
+    }
+}
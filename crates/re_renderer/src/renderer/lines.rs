@@ -26,6 +26,7 @@
 //!
 //! Data is provided in two separate textures, the "position data texture" and the "line strip texture".
 //! The "line strip texture" contains packed information over properties that are global to a single strip (see `gpu_data::LineStripInfo`)
+//! Picking instance ids are stored per strip as well, in a third texture laid out like the "line strip texture".
 //! Data in the "position data texture" is laid out a follows (see `gpu_data::PositionData`):
 //! ```raw
 //!                   ___________________________________________________________________
@@ -116,6 +117,8 @@ use smallvec::smallvec;
 use crate::{
     allocator::create_and_fill_uniform_buffer_batch,
     include_file,
+    outlines::OutlineMaskProcessor,
+    picking_layer::PickingLayerProcessor,
     size::Size,
     view_builder::ViewBuilder,
     wgpu_resources::{
@@ -123,7 +126,7 @@ use crate::{
         GpuRenderPipelineHandle, PipelineLayoutDesc, PoolError, RenderPipelineDesc,
         ShaderModuleDesc, TextureDesc,
     },
-    Color32, DebugLabel,
+    Color32, DebugLabel, OutlineMaskPreference, PickingLayerInstanceId, PickingLayerObjectId,
 };
 
 use super::{
@@ -164,8 +167,10 @@ pub mod gpu_data {
     #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
     pub struct BatchUniformBuffer {
         pub world_from_obj: wgpu_buffer_types::Mat4,
+        pub outline_mask_ids: wgpu_buffer_types::UVec2RowPadded,
+        pub picking_object_id: wgpu_buffer_types::UVec2RowPadded,
 
-        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 4],
+        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 6],
     }
 }
 
@@ -174,10 +179,19 @@ pub mod gpu_data {
 struct LineStripBatch {
     bind_group: GpuBindGroup,
     vertex_range: Range<u32>,
+
+    /// Whether this batch is drawn in [`DrawPhase::OutlineMask`].
+    has_outline: bool,
+
+    /// Batches for [`LineBatchInfo::additional_outline_mask_ids_vertex_ranges`] are only drawn
+    /// in [`DrawPhase::OutlineMask`].
+    outline_only: bool,
 }
 
 /// A line drawing operation. Encompasses several lines, each consisting of a list of positions.
-/// Expected to be recrated every frame.
+///
+/// Holds on to its gpu resources, so it can be kept around and drawn again in later frames
+/// as long as its lines don't change. Typically recreated every frame.
 #[derive(Clone)]
 pub struct LineDrawData {
     bind_group_all_lines: Option<GpuBindGroup>,
@@ -235,6 +249,20 @@ pub struct LineBatchInfo {
     /// The batch will start with the next vertex after the one the previous batch ended with.
     /// It is expected that this vertex is the first vertex of a new batch.
     pub line_vertex_count: u32,
+
+    /// Outline mask ids of all strips in this batch, unless overwritten by
+    /// [`Self::additional_outline_mask_ids_vertex_ranges`].
+    pub overall_outline_mask_ids: OutlineMaskPreference,
+
+    /// Ranges of line vertices, relative to the start of this batch, with different outline mask ids.
+    ///
+    /// Later ranges take precedence over earlier ones where they overlap.
+    pub additional_outline_mask_ids_vertex_ranges: Vec<(Range<u32>, OutlineMaskPreference)>,
+
+    /// Picking object id of all strips in this batch.
+    ///
+    /// Together with the per-strip picking instance id, this forms the [`crate::PickingLayerId`] of a strip.
+    pub picking_object_id: PickingLayerObjectId,
 }
 
 /// Style information for a line strip.
@@ -248,6 +276,9 @@ pub struct LineStripInfo {
 
     /// Additional properties for the linestrip.
     pub flags: LineStripFlags,
+
+    /// Picking instance id of the strip, see [`LineBatchInfo::picking_object_id`].
+    pub picking_instance_id: PickingLayerInstanceId,
     // Value from 0 to 1. 0 makes a line invisible, 1 is filled out, 0.5 is half dashes.
     // TODO(andreas): unsupported right now.
     //pub stippling: f32,
@@ -259,6 +290,7 @@ impl Default for LineStripInfo {
             radius: Size::AUTO,
             color: Color32::WHITE,
             flags: LineStripFlags::empty(),
+            picking_instance_id: PickingLayerInstanceId::default(),
         }
     }
 }
@@ -289,6 +321,13 @@ impl LineDrawData {
     /// TODO(#957): Get rid of this limit!.
     pub const MAX_NUM_STRIPS: usize = (LINE_STRIP_TEXTURE_SIZE * LINE_STRIP_TEXTURE_SIZE) as usize;
 
+    /// Gpu memory held by a [`LineDrawData`] with any lines in it, independent of the number of lines.
+    pub const GPU_MEMORY_USED: u64 = (POSITION_TEXTURE_SIZE * POSITION_TEXTURE_SIZE) as u64
+        * std::mem::size_of::<gpu_data::LineVertex>() as u64
+        + (LINE_STRIP_TEXTURE_SIZE * LINE_STRIP_TEXTURE_SIZE) as u64
+            * (std::mem::size_of::<gpu_data::LineStripInfo>()
+                + std::mem::size_of::<PickingLayerInstanceId>()) as u64;
+
     /// Transforms and uploads line strip data to be consumed by gpu.
     ///
     /// Try to bundle all line strips into a single draw data instance whenever possible.
@@ -320,6 +359,9 @@ impl LineDrawData {
             world_from_obj: glam::Mat4::IDENTITY,
             label: "all lines".into(),
             line_vertex_count: vertices.len() as _,
+            overall_outline_mask_ids: OutlineMaskPreference::NONE,
+            additional_outline_mask_ids_vertex_ranges: Vec::new(),
+            picking_object_id: PickingLayerObjectId::default(),
         }];
         let batches = if batches.is_empty() {
             &fallback_batches
@@ -338,6 +380,11 @@ impl LineDrawData {
                 % wgpu::COPY_BYTES_PER_ROW_ALIGNMENT,
             0
         );
+        static_assertions::const_assert_eq!(
+            LINE_STRIP_TEXTURE_SIZE * std::mem::size_of::<PickingLayerInstanceId>() as u32
+                % wgpu::COPY_BYTES_PER_ROW_ALIGNMENT,
+            0
+        );
 
         let vertices = if vertices.len() >= Self::MAX_NUM_VERTICES {
             re_log::error_once!("Reached maximum number of supported line vertices. Clamping down to {}, passed were {}.
@@ -383,20 +430,28 @@ impl LineDrawData {
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             },
         );
-        let line_strip_texture = ctx.gpu_resources.textures.alloc(
+        let line_strip_texture_desc = TextureDesc {
+            label: "line strips".into(),
+            size: wgpu::Extent3d {
+                width: LINE_STRIP_TEXTURE_SIZE,
+                height: LINE_STRIP_TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rg32Uint,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        };
+        let line_strip_texture = ctx
+            .gpu_resources
+            .textures
+            .alloc(&ctx.device, &line_strip_texture_desc);
+        let picking_instance_id_texture = ctx.gpu_resources.textures.alloc(
             &ctx.device,
             &TextureDesc {
-                label: "line strips".into(),
-                size: wgpu::Extent3d {
-                    width: LINE_STRIP_TEXTURE_SIZE,
-                    height: LINE_STRIP_TEXTURE_SIZE,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rg32Uint,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                label: "line strip picking instance ids".into(),
+                ..line_strip_texture_desc
             },
         );
 
@@ -434,6 +489,15 @@ impl LineDrawData {
             (wgpu::util::align_to(num_strips, LINE_STRIP_TEXTURE_SIZE) - num_strips) as usize,
         ));
 
+        let mut picking_instance_id_staging =
+            Vec::with_capacity(wgpu::util::align_to(num_strips, LINE_STRIP_TEXTURE_SIZE) as usize);
+        picking_instance_id_staging.extend(strips.iter().map(|strip| strip.picking_instance_id));
+        picking_instance_id_staging.extend(
+            std::iter::repeat(PickingLayerInstanceId::zeroed()).take(
+                (wgpu::util::align_to(num_strips, LINE_STRIP_TEXTURE_SIZE) - num_strips) as usize,
+            ),
+        );
+
         // Upload data from staging buffers to gpu.
         ctx.queue.write_texture(
             wgpu::ImageCopyTexture {
@@ -477,6 +541,27 @@ impl LineDrawData {
                 depth_or_array_layers: 1,
             },
         );
+        ctx.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &picking_instance_id_texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytemuck::cast_slice(&picking_instance_id_staging),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(
+                    LINE_STRIP_TEXTURE_SIZE * std::mem::size_of::<PickingLayerInstanceId>() as u32,
+                ),
+                rows_per_image: None,
+            },
+            wgpu::Extent3d {
+                width: LINE_STRIP_TEXTURE_SIZE,
+                height: (num_strips + LINE_STRIP_TEXTURE_SIZE - 1) / LINE_STRIP_TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+        );
 
         let bind_group_all_lines = ctx.gpu_resources.bind_groups.alloc(
             &ctx.device,
@@ -486,6 +571,7 @@ impl LineDrawData {
                 entries: smallvec![
                     BindGroupEntry::DefaultTextureView(position_data_texture.handle),
                     BindGroupEntry::DefaultTextureView(line_strip_texture.handle),
+                    BindGroupEntry::DefaultTextureView(picking_instance_id_texture.handle),
                 ],
                 layout: line_renderer.bind_group_layout_all_lines,
             },
@@ -494,20 +580,60 @@ impl LineDrawData {
         // Process batches
         let mut batches_internal = Vec::with_capacity(batches.len());
         {
+            let batch_uniform_buffer =
+                |batch_info: &LineBatchInfo, outline_mask_ids: OutlineMaskPreference| {
+                    gpu_data::BatchUniformBuffer {
+                        world_from_obj: batch_info.world_from_obj.into(),
+                        outline_mask_ids: outline_mask_ids.ids().into(),
+                        picking_object_id: <[u32; 2]>::from(batch_info.picking_object_id).into(),
+                        end_padding: Default::default(),
+                    }
+                };
+
             let uniform_buffer_bindings = create_and_fill_uniform_buffer_batch(
                 ctx,
                 "lines batch uniform buffers".into(),
-                batches
+                batches.iter().map(|batch_info| {
+                    batch_uniform_buffer(batch_info, batch_info.overall_outline_mask_ids)
+                }),
+            );
+
+            // Additional ranges are drawn on top of the whole batch in the outline mask phase.
+            // A range without outline is still needed if the batch as a whole has one, since it clears the mask again.
+            let additional_outline_ranges = batches
+                .iter()
+                .enumerate()
+                .flat_map(|(batch_index, batch_info)| {
+                    batch_info
+                        .additional_outline_mask_ids_vertex_ranges
+                        .iter()
+                        .filter(|(_, outline_mask_ids)| {
+                            outline_mask_ids.is_some()
+                                || batch_info.overall_outline_mask_ids.is_some()
+                        })
+                        .map(move |(range, outline_mask_ids)| {
+                            (batch_index, range.clone(), *outline_mask_ids)
+                        })
+                })
+                .collect::<Vec<_>>();
+            let mut additional_uniform_buffer_bindings = create_and_fill_uniform_buffer_batch(
+                ctx,
+                "lines batch uniform buffers - outline ranges".into(),
+                additional_outline_ranges
                     .iter()
-                    .map(|batch_info| gpu_data::BatchUniformBuffer {
-                        world_from_obj: batch_info.world_from_obj.into(),
-                        end_padding: Default::default(),
+                    .map(|(batch_index, _, outline_mask_ids)| {
+                        batch_uniform_buffer(&batches[*batch_index], *outline_mask_ids)
                     }),
-            );
+            )
+            .into_iter()
+            .zip(additional_outline_ranges.into_iter())
+            .peekable();
 
             let mut start_vertex_for_next_batch = 0;
-            for (batch_info, uniform_buffer_binding) in
-                batches.iter().zip(uniform_buffer_bindings.into_iter())
+            for (batch_index, (batch_info, uniform_buffer_binding)) in batches
+                .iter()
+                .zip(uniform_buffer_bindings.into_iter())
+                .enumerate()
             {
                 let line_vertex_range_end = (start_vertex_for_next_batch
                     + batch_info.line_vertex_count)
@@ -528,8 +654,39 @@ impl LineDrawData {
                     // Which is rather convenient because we need to ensure there are start and end triangles,
                     // so just from a number-of=vertices perspective this is correct already and the shader can take care of offsets.
                     vertex_range: (start_vertex_for_next_batch * 6)..(line_vertex_range_end * 6),
+                    has_outline: batch_info.overall_outline_mask_ids.is_some(),
+                    outline_only: false,
                 });
 
+                while let Some((uniform_buffer_binding, (_, range, _))) =
+                    additional_uniform_buffer_bindings
+                        .next_if(|(_, (range_batch_index, _, _))| *range_batch_index == batch_index)
+                {
+                    let range_start =
+                        (start_vertex_for_next_batch + range.start).min(line_vertex_range_end);
+                    let range_end =
+                        (start_vertex_for_next_batch + range.end).min(line_vertex_range_end);
+                    if range_start >= range_end {
+                        continue;
+                    }
+
+                    let bind_group = ctx.gpu_resources.bind_groups.alloc(
+                        &ctx.device,
+                        &ctx.gpu_resources,
+                        &BindGroupDesc {
+                            label: batch_info.label.clone().push_str(" - outline range"),
+                            entries: smallvec![uniform_buffer_binding],
+                            layout: line_renderer.bind_group_layout_batch,
+                        },
+                    );
+                    batches_internal.push(LineStripBatch {
+                        bind_group,
+                        vertex_range: (range_start * 6)..(range_end * 6),
+                        has_outline: true,
+                        outline_only: true,
+                    });
+                }
+
                 start_vertex_for_next_batch = line_vertex_range_end;
 
                 // Should happen only if the number of vertices was clamped.
@@ -548,6 +705,8 @@ impl LineDrawData {
 
 pub struct LineRenderer {
     render_pipeline: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
+    render_pipeline_picking_layer: GpuRenderPipelineHandle,
     bind_group_layout_all_lines: GpuBindGroupLayoutHandle,
     bind_group_layout_batch: GpuBindGroupLayoutHandle,
 }
@@ -586,6 +745,16 @@ impl Renderer for LineRenderer {
                         },
                         count: None,
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Uint,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
                 ],
             },
         );
@@ -596,7 +765,7 @@ impl Renderer for LineRenderer {
                 label: "line renderer - batch".into(),
                 entries: vec![wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -631,27 +800,54 @@ impl Renderer for LineRenderer {
             },
         );
 
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "LineRenderer".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+            vertex_buffers: smallvec![],
+            render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: wgpu::MultisampleState {
+                // We discard pixels to do the round cutout, therefore we need to calculate our own sampling mask.
+                alpha_to_coverage_enabled: true,
+                ..ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE
+            },
+        };
         let render_pipeline = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
             device,
             &RenderPipelineDesc {
-                label: "LineRenderer".into(),
-                pipeline_layout,
-                vertex_entrypoint: "vs_main".into(),
-                vertex_handle: shader_module,
-                fragment_entrypoint: "fs_main".into(),
-                fragment_handle: shader_module,
-                vertex_buffers: smallvec![],
-                render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    ..Default::default()
-                },
-                depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
-                multisample: wgpu::MultisampleState {
-                    // We discard pixels to do the round cutout, therefore we need to calculate our own sampling mask.
-                    alpha_to_coverage_enabled: true,
-                    ..ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE
-                },
+                label: "LineRenderer - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE,
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_picking_layer = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "LineRenderer - picking layer".into(),
+                fragment_entrypoint: "fs_main_picking_layer".into(),
+                render_targets: smallvec![Some(PickingLayerProcessor::PICKING_LAYER_FORMAT.into())],
+                depth_stencil: PickingLayerProcessor::PICKING_LAYER_DEPTH_STATE,
+                multisample: PickingLayerProcessor::PICKING_LAYER_MSAA_STATE,
+                ..render_pipeline_desc
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
@@ -659,6 +855,8 @@ impl Renderer for LineRenderer {
 
         LineRenderer {
             render_pipeline,
+            render_pipeline_outline_mask,
+            render_pipeline_picking_layer,
             bind_group_layout_all_lines,
             bind_group_layout_batch,
         }
@@ -667,24 +865,49 @@ impl Renderer for LineRenderer {
     fn draw<'a>(
         &self,
         pools: &'a WgpuResourcePools,
-        _phase: DrawPhase,
+        phase: DrawPhase,
         pass: &mut wgpu::RenderPass<'a>,
         draw_data: &'a Self::RendererDrawData,
     ) -> anyhow::Result<()> {
         let Some(bind_group_all_lines) = &draw_data.bind_group_all_lines else {
             return Ok(()); // No lines submitted.
         };
-        let pipeline = pools.render_pipelines.get_resource(self.render_pipeline)?;
+
+        let mut batches = draw_data
+            .batches
+            .iter()
+            .filter(|batch| match phase {
+                DrawPhase::OutlineMask => batch.has_outline,
+                _ => !batch.outline_only,
+            })
+            .peekable();
+        if batches.peek().is_none() {
+            return Ok(());
+        }
+
+        let pipeline = pools.render_pipelines.get_resource(match phase {
+            DrawPhase::OutlineMask => self.render_pipeline_outline_mask,
+            DrawPhase::PickingLayer => self.render_pipeline_picking_layer,
+            _ => self.render_pipeline,
+        })?;
 
         pass.set_pipeline(pipeline);
 
         pass.set_bind_group(1, bind_group_all_lines, &[]);
 
-        for batch in &draw_data.batches {
+        for batch in batches {
             pass.set_bind_group(2, &batch.bind_group, &[]);
             pass.draw(batch.vertex_range.clone(), 0..1);
         }
 
         Ok(())
     }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[
+            DrawPhase::Opaque,
+            DrawPhase::OutlineMask,
+            DrawPhase::PickingLayer,
+        ]
+    }
 }
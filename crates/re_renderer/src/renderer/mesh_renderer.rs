@@ -6,18 +6,21 @@
 use std::sync::Arc;
 
 use itertools::Itertools as _;
-use smallvec::smallvec;
+use smallvec::{smallvec, SmallVec};
 
 use crate::{
     include_file,
+    lighting::ShadowMapProcessor,
     mesh::{gpu_data::MaterialUniformBuffer, mesh_vertices, GpuMesh, Mesh},
+    outlines::OutlineMaskProcessor,
+    picking_layer::PickingLayerProcessor,
     resource_managers::GpuMeshHandle,
     view_builder::ViewBuilder,
     wgpu_resources::{
         BindGroupLayoutDesc, BufferDesc, GpuBindGroupLayoutHandle, GpuBuffer,
         GpuRenderPipelineHandle, PipelineLayoutDesc, RenderPipelineDesc, ShaderModuleDesc,
     },
-    Color32,
+    Color32, OutlineMaskPreference, PickingLayerId,
 };
 
 use super::{
@@ -47,6 +50,13 @@ mod gpu_data {
         pub world_from_mesh_normal_row_2: [f32; 3],
 
         pub additive_tint: Color32,
+
+        pub opacity: f32,
+
+        pub outline_mask_ids: [u8; 2],
+        pub _padding: [u8; 2],
+
+        pub picking_layer_id: [u32; 4],
     }
 
     impl InstanceData {
@@ -69,6 +79,12 @@ mod gpu_data {
                         wgpu::VertexFormat::Float32x3,
                         // Tint color
                         wgpu::VertexFormat::Unorm8x4,
+                        // Opacity
+                        wgpu::VertexFormat::Float32,
+                        // Outline mask ids
+                        wgpu::VertexFormat::Uint8x2,
+                        // Picking layer id
+                        wgpu::VertexFormat::Uint32x4,
                     ]
                     .into_iter(),
                 ),
@@ -81,6 +97,12 @@ mod gpu_data {
 struct MeshBatch {
     mesh: GpuMesh,
     count: u32,
+
+    /// All instances of this batch have an opacity below 1.0.
+    is_transparent: bool,
+
+    /// All instances of this batch have an [`OutlineMaskPreference`].
+    has_outline: bool,
 }
 
 #[derive(Clone)]
@@ -94,6 +116,17 @@ pub struct MeshDrawData {
 
 impl DrawData for MeshDrawData {
     type Renderer = MeshRenderer;
+
+    fn has_transparent(&self) -> bool {
+        self.batches.iter().any(|batch| {
+            batch.is_transparent
+                || batch
+                    .mesh
+                    .materials
+                    .iter()
+                    .any(|material| material.is_transparent)
+        })
+    }
 }
 
 pub struct MeshInstance {
@@ -109,6 +142,18 @@ pub struct MeshInstance {
     /// Per-instance (as opposed to per-material/mesh!) tint color that is added to the albedo texture.
     /// Alpha channel is currently unused.
     pub additive_tint: Color32,
+
+    /// Opacity of the whole instance, in `[0, 1]`.
+    ///
+    /// Instances with an opacity below 1.0 are drawn in [`DrawPhase::Transparent`].
+    /// They are also semi-transparent if the alpha of their material's albedo multiplier is below 1.0.
+    pub opacity: f32,
+
+    /// Whether and with which ids this instance is drawn into the outline mask.
+    pub outline_mask_ids: OutlineMaskPreference,
+
+    /// Id written to the picking layer for every pixel of this instance.
+    pub picking_layer_id: PickingLayerId,
 }
 
 impl MeshDrawData {
@@ -163,7 +208,15 @@ impl MeshDrawData {
                 bytemuck::cast_slice_mut(&mut instance_buffer_staging);
 
             let mut num_processed_instances = 0;
-            for (mesh, instances) in &instances.iter().group_by(|instance| &instance.gpu_mesh) {
+            for ((mesh, is_transparent, has_outline), instances) in
+                &instances.iter().group_by(|instance| {
+                    (
+                        &instance.gpu_mesh,
+                        instance.opacity < 1.0,
+                        instance.outline_mask_ids.is_some(),
+                    )
+                })
+            {
                 let mut count = 0;
                 for (instance, gpu_instance) in instances.zip(
                     instance_buffer_staging
@@ -196,9 +249,12 @@ impl MeshDrawData {
                         world_from_mesh_normal.row(2).to_array();
 
                     gpu_instance.additive_tint = instance.additive_tint;
+                    gpu_instance.opacity = instance.opacity.clamp(0.0, 1.0);
+                    gpu_instance.outline_mask_ids = instance.outline_mask_ids.ids();
+                    gpu_instance.picking_layer_id = instance.picking_layer_id.into();
                 }
                 num_processed_instances += count;
-                mesh_runs.push((mesh, count as u32));
+                mesh_runs.push((mesh, count as u32, is_transparent, has_outline));
             }
             assert_eq!(num_processed_instances, instances.len());
         }
@@ -206,13 +262,15 @@ impl MeshDrawData {
         // We resolve the meshes here already, so the actual draw call doesn't need to know about the MeshManager.
         let batches: Result<Vec<_>, _> = mesh_runs
             .into_iter()
-            .map(|(mesh_handle, count)| {
+            .map(|(mesh_handle, count, is_transparent, has_outline)| {
                 ctx.mesh_manager
                     .read()
                     .get(mesh_handle)
                     .map(|mesh| MeshBatch {
                         mesh: mesh.clone(),
                         count,
+                        is_transparent,
+                        has_outline,
                     })
             })
             .collect();
@@ -226,6 +284,10 @@ impl MeshDrawData {
 
 pub struct MeshRenderer {
     render_pipeline: GpuRenderPipelineHandle,
+    render_pipeline_transparent: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
+    render_pipeline_picking_layer: GpuRenderPipelineHandle,
+    render_pipeline_shadow_map: GpuRenderPipelineHandle,
     pub bind_group_layout: GpuBindGroupLayoutHandle,
 }
 
@@ -288,29 +350,81 @@ impl Renderer for MeshRenderer {
             },
         );
 
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "mesh renderer".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+
+            // Put instance vertex buffer on slot 0 since it doesn't change for several draws.
+            vertex_buffers: std::iter::once(gpu_data::InstanceData::vertex_buffer_layout())
+                .chain(mesh_vertices::vertex_buffer_layouts())
+                .collect(),
+
+            render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None, //Some(wgpu::Face::Back), // TODO(andreas): Need to specify from outside if mesh is CW or CCW?
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+        };
         let render_pipeline = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_transparent = pools.render_pipelines.get_or_create(
             device,
             &RenderPipelineDesc {
-                label: "mesh renderer".into(),
-                pipeline_layout,
-                vertex_entrypoint: "vs_main".into(),
-                vertex_handle: shader_module,
-                fragment_entrypoint: "fs_main".into(),
-                fragment_handle: shader_module,
-
-                // Put instance vertex buffer on slot 0 since it doesn't change for several draws.
-                vertex_buffers: std::iter::once(gpu_data::InstanceData::vertex_buffer_layout())
-                    .chain(mesh_vertices::vertex_buffer_layouts())
-                    .collect(),
-
-                render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    cull_mode: None, //Some(wgpu::Face::Back), // TODO(andreas): Need to specify from outside if mesh is CW or CCW?
-                    ..Default::default()
-                },
-                depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
-                multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+                label: "mesh renderer - transparent".into(),
+                fragment_entrypoint: "fs_main_transparent".into(),
+                render_targets: ViewBuilder::OIT_RENDER_TARGETS.into_iter().collect(),
+                depth_stencil: ViewBuilder::OIT_DEPTH_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "mesh renderer - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE,
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_picking_layer = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "mesh renderer - picking layer".into(),
+                fragment_entrypoint: "fs_main_picking_layer".into(),
+                render_targets: smallvec![Some(PickingLayerProcessor::PICKING_LAYER_FORMAT.into())],
+                depth_stencil: PickingLayerProcessor::PICKING_LAYER_DEPTH_STATE,
+                multisample: PickingLayerProcessor::PICKING_LAYER_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_shadow_map = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "mesh renderer - shadow map".into(),
+                fragment_entrypoint: "fs_main_shadow_map".into(),
+                render_targets: smallvec![],
+                depth_stencil: ShadowMapProcessor::SHADOW_MAP_DEPTH_STATE,
+                multisample: ShadowMapProcessor::SHADOW_MAP_MSAA_STATE,
+                ..render_pipeline_desc
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
@@ -318,6 +432,10 @@ impl Renderer for MeshRenderer {
 
         MeshRenderer {
             render_pipeline,
+            render_pipeline_transparent,
+            render_pipeline_outline_mask,
+            render_pipeline_picking_layer,
+            render_pipeline_shadow_map,
             bind_group_layout,
         }
     }
@@ -325,7 +443,7 @@ impl Renderer for MeshRenderer {
     fn draw<'a>(
         &self,
         pools: &'a WgpuResourcePools,
-        _phase: DrawPhase,
+        phase: DrawPhase,
         pass: &mut wgpu::RenderPass<'a>,
         draw_data: &'a Self::RendererDrawData,
     ) -> anyhow::Result<()> {
@@ -335,13 +453,39 @@ impl Renderer for MeshRenderer {
             return Ok(()); // Instance buffer was empty.
         };
 
-        let pipeline = pools.render_pipelines.get_resource(self.render_pipeline)?;
+        let draw_transparent = phase == DrawPhase::Transparent;
+        let pipeline = pools.render_pipelines.get_resource(match phase {
+            DrawPhase::Transparent => self.render_pipeline_transparent,
+            DrawPhase::OutlineMask => self.render_pipeline_outline_mask,
+            DrawPhase::PickingLayer => self.render_pipeline_picking_layer,
+            DrawPhase::ShadowMap => self.render_pipeline_shadow_map,
+            _ => self.render_pipeline,
+        })?;
         pass.set_pipeline(pipeline);
 
         pass.set_vertex_buffer(0, instance_buffer.slice(..));
         let mut instance_start_index = 0;
 
         for mesh_batch in &draw_data.batches {
+            let instance_range = instance_start_index..(instance_start_index + mesh_batch.count);
+            instance_start_index = instance_range.end;
+
+            let materials = mesh_batch
+                .mesh
+                .materials
+                .iter()
+                .filter(|material| match phase {
+                    DrawPhase::OutlineMask => mesh_batch.has_outline,
+                    // Semi-transparent meshes are pickable as well.
+                    DrawPhase::PickingLayer => true,
+                    // Also covers the shadow map: semi-transparent meshes don't cast shadows.
+                    _ => (mesh_batch.is_transparent || material.is_transparent) == draw_transparent,
+                })
+                .collect::<SmallVec<[_; 1]>>();
+            if materials.is_empty() {
+                continue;
+            }
+
             let vertex_buffer_combined = &mesh_batch.mesh.vertex_buffer_combined;
             let index_buffer = &mesh_batch.mesh.index_buffer;
 
@@ -358,19 +502,25 @@ impl Renderer for MeshRenderer {
                 wgpu::IndexFormat::Uint32,
             );
 
-            let instance_range = instance_start_index..(instance_start_index + mesh_batch.count);
-
-            for material in &mesh_batch.mesh.materials {
+            for material in materials {
                 debug_assert!(mesh_batch.count > 0);
 
                 pass.set_bind_group(1, &material.bind_group, &[]);
 
                 pass.draw_indexed(material.index_range.clone(), 0, instance_range.clone());
             }
-
-            instance_start_index = instance_range.end;
         }
 
         Ok(())
     }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[
+            DrawPhase::ShadowMap,
+            DrawPhase::Opaque,
+            DrawPhase::Transparent,
+            DrawPhase::OutlineMask,
+            DrawPhase::PickingLayer,
+        ]
+    }
 }
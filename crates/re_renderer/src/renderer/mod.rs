@@ -5,9 +5,13 @@ mod lines;
 pub use lines::{gpu_data::LineVertex, LineBatchInfo, LineDrawData, LineStripFlags, LineStripInfo};
 
 mod point_cloud;
+pub(crate) use point_cloud::{
+    create_static_data_textures, gpu_data::PositionData as PointCloudPositionData,
+    PointCloudRenderer,
+};
 pub use point_cloud::{
     PointCloudBatchFlags, PointCloudBatchInfo, PointCloudDrawData, PointCloudDrawDataError,
-    PointCloudVertex,
+    PointCloudVertex, StaticPointCloudInstance,
 };
 
 mod depth_cloud;
@@ -21,11 +25,21 @@ pub use test_triangle::TestTriangleDrawData;
 mod rectangles;
 pub use rectangles::{RectangleDrawData, TextureFilterMag, TextureFilterMin, TexturedRect};
 
+mod volumes;
+pub use volumes::{VolumeDrawData, VolumeInstance, VolumeRenderMode, VolumeRenderer};
+
+mod voxel_grids;
+pub use voxel_grids::{VoxelGridDrawData, VoxelGridInstance, VoxelGridRenderer};
+
+mod labels;
+pub use labels::{LabelDrawData, LabelInstance, LabelRenderer, LabelView};
+
 mod mesh_renderer;
 pub(crate) use mesh_renderer::MeshRenderer;
 pub use mesh_renderer::{MeshDrawData, MeshInstance};
 
 pub mod compositor;
+pub mod oit_compositor;
 
 use crate::{
     context::{RenderContext, SharedRendererData},
@@ -39,6 +53,13 @@ use crate::{
 /// TODO(andreas): Add a mechanism to validate this.
 pub trait DrawData {
     type Renderer: Renderer<RendererDrawData = Self> + Send + Sync;
+
+    /// Whether there is anything to draw in [`DrawPhase::Transparent`].
+    ///
+    /// The transparent pass and its targets are skipped for views where nothing is.
+    fn has_transparent(&self) -> bool {
+        false
+    }
 }
 
 /// A Renderer encapsulate the knowledge of how to render a certain kind of primitives.
@@ -75,10 +96,17 @@ pub trait Renderer {
 
 /// Determines a (very rough) order of rendering and describes the active [`wgpu::RenderPass`].
 ///
-/// Currently we do not support sorting *within* a rendering phase!
+/// We do not support sorting *within* a rendering phase!
 /// See [#702](https://github.com/rerun-io/rerun/issues/702)
+/// Semi-transparent geometry instead goes into [`DrawPhase::Transparent`] which doesn't need sorting.
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
 pub enum DrawPhase {
+    /// Shadow casters write their depth from the key light's point of view.
+    ///
+    /// Only active if the view's [`crate::LightingConfig`] has shadows enabled, runs before all other phases.
+    /// Pipelines draw depth only & use the depth & msaa state given by `ShadowMapProcessor`.
+    ShadowMap,
+
     /// Opaque objects, performing reads/writes to the depth buffer.
     ///
     /// Typically they are order independent, so everything uses this same index.
@@ -87,6 +115,40 @@ pub enum DrawPhase {
     /// Background, rendering where depth wasn't written.
     Background,
 
+    /// Semi-transparent objects, reading but not writing the depth buffer.
+    ///
+    /// Uses weighted blended order-independent transparency
+    /// ([McGuire & Bavoil 2013](https://jcgt.org/published/0002/02/09/)):
+    /// Instead of the main target, the pass has two render targets (see [`oit_compositor::OitProcessor`])
+    /// into which fragments are accumulated in arbitrary order.
+    /// Fragment shaders are expected to output `OitOutput` from `utils/oit.wgsl`,
+    /// and pipelines to use [`crate::view_builder::ViewBuilder::OIT_RENDER_TARGETS`] & [`crate::view_builder::ViewBuilder::OIT_DEPTH_STATE`].
+    Transparent,
+
+    /// Resolves the accumulated [`DrawPhase::Transparent`] fragments onto the main target.
+    ///
+    /// Drawn by [`oit_compositor::OitProcessor`] itself, only if anything is semi-transparent.
+    OitComposite,
+
+    /// Drawn on top of all other geometry onto the resolved main target, like text labels.
+    ///
+    /// There is neither depth attachment nor multisampling,
+    /// but the depth buffer of the previous passes can be bound as a texture.
+    Overlay,
+
+    /// Objects with an [`crate::OutlineMaskPreference`] write their mask ids for the outline post-process.
+    ///
+    /// Only active if the view has an [`crate::OutlineConfig`].
+    /// Pipelines need to write the mask format & use the depth & msaa state given by `OutlineMaskProcessor`.
+    OutlineMask,
+
+    /// Pickable objects write a [`crate::PickingLayerId`] for every pixel they cover.
+    ///
+    /// Only active if a picking rectangle was scheduled with [`crate::view_builder::ViewBuilder::schedule_picking_rect`].
+    /// Pipelines need to write [`crate::PickingLayerProcessor::PICKING_LAYER_FORMAT`] & use the depth & msaa state given by
+    /// [`crate::PickingLayerProcessor`].
+    PickingLayer,
+
     /// Drawn when compositing with the main target.
     Compositing,
 }
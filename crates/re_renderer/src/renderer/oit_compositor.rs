@@ -0,0 +1,322 @@
+//! Resolves the order-independent transparency targets onto the main target.
+//!
+//! See [`DrawPhase::Transparent`].
+
+use anyhow::Context as _;
+
+use crate::{
+    context::SharedRendererData,
+    include_file,
+    view_builder::ViewBuilder,
+    wgpu_resources::{
+        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
+        GpuRenderPipelineHandle, GpuTexture, PipelineLayoutDesc, RenderPipelineDesc,
+        ShaderModuleDesc, TextureDesc, WgpuResourcePools,
+    },
+    DebugLabel,
+};
+
+use super::{DrawData, DrawPhase, FileResolver, FileSystem, RenderContext, Renderer};
+
+use smallvec::smallvec;
+
+pub struct OitCompositor {
+    render_pipeline: GpuRenderPipelineHandle,
+    bind_group_layout: GpuBindGroupLayoutHandle,
+}
+
+#[derive(Clone)]
+pub struct OitCompositorDrawData {
+    /// [`GpuBindGroup`] pointing at the resolved accumulation & revealage targets.
+    bind_group: GpuBindGroup,
+}
+
+impl DrawData for OitCompositorDrawData {
+    type Renderer = OitCompositor;
+}
+
+/// Targets of [`DrawPhase::Transparent`] for a single view.
+///
+/// Only created by the [`ViewBuilder`] if anything in the view is semi-transparent,
+/// see [`DrawData::has_transparent`].
+pub struct OitProcessor {
+    label: DebugLabel,
+
+    accumulation_msaa: GpuTexture,
+    accumulation_resolved: GpuTexture,
+    revealage_msaa: GpuTexture,
+    revealage_resolved: GpuTexture,
+
+    composite_draw_data: OitCompositorDrawData,
+}
+
+impl OitProcessor {
+    /// Expects the [`OitCompositor`] to be already created, see [`Self::ensure_renderer`].
+    pub fn new(
+        ctx: &RenderContext,
+        view_name: &DebugLabel,
+        resolution_in_pixel: [u32; 2],
+    ) -> anyhow::Result<Self> {
+        crate::profile_function!();
+
+        let target = |name: &str, format, sample_count, usage| {
+            ctx.gpu_resources.textures.alloc(
+                &ctx.device,
+                &TextureDesc {
+                    label: format!("{view_name:?} - oit {name}").into(),
+                    size: wgpu::Extent3d {
+                        width: resolution_in_pixel[0],
+                        height: resolution_in_pixel[1],
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage,
+                },
+            )
+        };
+        let msaa_usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+        let resolved_usage =
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING;
+
+        let accumulation_msaa = target(
+            "accumulation",
+            ViewBuilder::OIT_ACCUMULATION_FORMAT,
+            ViewBuilder::MAIN_TARGET_SAMPLE_COUNT,
+            msaa_usage,
+        );
+        let accumulation_resolved = target(
+            "accumulation resolved",
+            ViewBuilder::OIT_ACCUMULATION_FORMAT,
+            1,
+            resolved_usage,
+        );
+        let revealage_msaa = target(
+            "revealage",
+            ViewBuilder::OIT_REVEALAGE_FORMAT,
+            ViewBuilder::MAIN_TARGET_SAMPLE_COUNT,
+            msaa_usage,
+        );
+        let revealage_resolved = target(
+            "revealage resolved",
+            ViewBuilder::OIT_REVEALAGE_FORMAT,
+            1,
+            resolved_usage,
+        );
+
+        let bind_group_layout = ctx
+            .renderers
+            .read()
+            .get::<OitCompositor>()
+            .context("OitCompositor wasn't created yet")?
+            .bind_group_layout;
+        let composite_draw_data = OitCompositorDrawData {
+            bind_group: ctx.gpu_resources.bind_groups.alloc(
+                &ctx.device,
+                &ctx.gpu_resources,
+                &BindGroupDesc {
+                    label: "oit compositor".into(),
+                    entries: smallvec![
+                        BindGroupEntry::DefaultTextureView(accumulation_resolved.handle),
+                        BindGroupEntry::DefaultTextureView(revealage_resolved.handle),
+                    ],
+                    layout: bind_group_layout,
+                },
+            ),
+        };
+
+        Ok(Self {
+            label: view_name.clone(),
+            accumulation_msaa,
+            accumulation_resolved,
+            revealage_msaa,
+            revealage_resolved,
+            composite_draw_data,
+        })
+    }
+
+    /// Creates the [`OitCompositor`] if it doesn't exist yet, so that [`Self::new`] can be called
+    /// while drawing, when the [`RenderContext`] is no longer mutable.
+    pub fn ensure_renderer(ctx: &mut RenderContext) {
+        ctx.renderers.write().get_or_create::<_, OitCompositor>(
+            &ctx.shared_renderer_data,
+            &mut ctx.gpu_resources,
+            &ctx.device,
+            &mut ctx.resolver,
+        );
+    }
+
+    /// Starts the pass for [`DrawPhase::Transparent`], testing against the opaque geometry's `depth_buffer`.
+    pub fn begin_render_pass<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+        depth_buffer: &'a GpuTexture,
+    ) -> wgpu::RenderPass<'a> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: DebugLabel::from(format!("{:?} - transparent pass", self.label)).get(),
+            color_attachments: &[
+                Some(wgpu::RenderPassColorAttachment {
+                    view: &self.accumulation_msaa.default_view,
+                    resolve_target: Some(&self.accumulation_resolved.default_view),
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: false,
+                    },
+                }),
+                Some(wgpu::RenderPassColorAttachment {
+                    view: &self.revealage_msaa.default_view,
+                    resolve_target: Some(&self.revealage_resolved.default_view),
+                    ops: wgpu::Operations {
+                        // Revealage of 1.0 == nothing in front of the opaque geometry.
+                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                        store: false,
+                    },
+                }),
+            ],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth_buffer.default_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: false,
+                }),
+                stencil_ops: None,
+            }),
+        })
+    }
+
+    /// Resolves what was drawn during the pass started by [`Self::begin_render_pass`] onto `main_target_resolved`.
+    pub fn composite(
+        &self,
+        ctx: &RenderContext,
+        encoder: &mut wgpu::CommandEncoder,
+        bind_group_0: &GpuBindGroup,
+        main_target_resolved: &GpuTexture,
+    ) -> anyhow::Result<()> {
+        let renderers = ctx.renderers.read();
+        let oit_compositor = renderers
+            .get::<OitCompositor>()
+            .context("OitCompositor wasn't created yet")?;
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: DebugLabel::from(format!("{:?} - oit composite pass", self.label)).get(),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &main_target_resolved.default_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+        pass.set_bind_group(0, bind_group_0, &[]);
+
+        oit_compositor.draw(
+            &ctx.gpu_resources,
+            DrawPhase::OitComposite,
+            &mut pass,
+            &self.composite_draw_data,
+        )
+    }
+}
+
+impl Renderer for OitCompositor {
+    type RendererDrawData = OitCompositorDrawData;
+
+    fn create_renderer<Fs: FileSystem>(
+        shared_data: &SharedRendererData,
+        pools: &mut WgpuResourcePools,
+        device: &wgpu::Device,
+        resolver: &mut FileResolver<Fs>,
+    ) -> Self {
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let bind_group_layout = pools.bind_group_layouts.get_or_create(
+            device,
+            &BindGroupLayoutDesc {
+                label: "oit compositor".into(),
+                entries: vec![texture_entry(0), texture_entry(1)],
+            },
+        );
+
+        let render_pipeline = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "oit compositor".into(),
+                pipeline_layout: pools.pipeline_layouts.get_or_create(
+                    device,
+                    &PipelineLayoutDesc {
+                        label: "oit compositor".into(),
+                        entries: vec![shared_data.global_bindings.layout, bind_group_layout],
+                    },
+                    &pools.bind_group_layouts,
+                ),
+                vertex_entrypoint: "main".into(),
+                vertex_handle: pools.shader_modules.get_or_create(
+                    device,
+                    resolver,
+                    &ShaderModuleDesc {
+                        label: "screen_triangle (vertex)".into(),
+                        source: include_file!("../../shader/screen_triangle.wgsl"),
+                    },
+                ),
+                fragment_entrypoint: "main".into(),
+                fragment_handle: pools.shader_modules.get_or_create(
+                    device,
+                    resolver,
+                    &ShaderModuleDesc {
+                        label: "oit_composite (fragment)".into(),
+                        source: include_file!("../../shader/oit_composite.wgsl"),
+                    },
+                ),
+                vertex_buffers: smallvec![],
+                // Drawn onto the already resolved main target.
+                render_targets: smallvec![Some(wgpu::ColorTargetState {
+                    format: ViewBuilder::MAIN_TARGET_COLOR_FORMAT,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+
+        OitCompositor {
+            render_pipeline,
+            bind_group_layout,
+        }
+    }
+
+    fn draw<'a>(
+        &self,
+        pools: &'a WgpuResourcePools,
+        _phase: DrawPhase,
+        pass: &mut wgpu::RenderPass<'a>,
+        draw_data: &'a OitCompositorDrawData,
+    ) -> anyhow::Result<()> {
+        let pipeline = pools.render_pipelines.get_resource(self.render_pipeline)?;
+
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(1, &draw_data.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[DrawPhase::OitComposite]
+    }
+}
@@ -11,6 +11,11 @@
 //!
 //! For WebGL compatibility, data is uploaded as textures. Color is stored in a separate srgb texture, meaning
 //! that srgb->linear conversion happens on texture load.
+//! Picking instance ids are stored in a third, integer texture that is only read in [`DrawPhase::PickingLayer`].
+//!
+//! Static point clouds, created via [`crate::resource_managers::PointCloudManager`], keep their data textures
+//! across frames and are drawn via [`PointCloudDrawData::new_static`].
+//! Only the parts selected by the level of detail octree (see `point_cloud_lod.rs`) are drawn.
 //!
 
 use std::{
@@ -18,7 +23,15 @@ use std::{
     ops::Range,
 };
 
-use crate::{allocator::create_and_fill_uniform_buffer_batch, DebugLabel, PointCloudBuilder};
+use crate::{
+    allocator::create_and_fill_uniform_buffer_batch,
+    outlines::OutlineMaskProcessor,
+    picking_layer::PickingLayerProcessor,
+    point_cloud_lod::{self, PointCloudLodView},
+    resource_managers::{GpuPointCloudHandle, ResourceManagerError},
+    Color32, DebugLabel, OutlineMaskPreference, PickingLayerInstanceId, PickingLayerObjectId,
+    PointCloudBuilder,
+};
 use bitflags::bitflags;
 use bytemuck::Zeroable;
 use itertools::Itertools;
@@ -29,8 +42,8 @@ use crate::{
     view_builder::ViewBuilder,
     wgpu_resources::{
         BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
-        GpuRenderPipelineHandle, PipelineLayoutDesc, RenderPipelineDesc, ShaderModuleDesc,
-        TextureDesc,
+        GpuRenderPipelineHandle, GpuTexture, PipelineLayoutDesc, RenderPipelineDesc,
+        ShaderModuleDesc, TextureDesc,
     },
     Size,
 };
@@ -52,7 +65,7 @@ bitflags! {
     }
 }
 
-mod gpu_data {
+pub(crate) mod gpu_data {
     use crate::{wgpu_buffer_types, Size};
 
     // Don't use `wgsl_buffer_types` since this data doesn't go into a buffer, so alignment rules don't apply like on buffers..
@@ -71,28 +84,46 @@ mod gpu_data {
         pub world_from_obj: wgpu_buffer_types::Mat4,
 
         pub flags: wgpu_buffer_types::U32RowPadded, // PointCloudBatchFlags
+        pub opacity: wgpu_buffer_types::F32RowPadded,
+        pub outline_mask_ids: wgpu_buffer_types::UVec2RowPadded,
+        pub picking_object_id: wgpu_buffer_types::UVec2RowPadded,
 
-        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 5],
+        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 8],
     }
 }
 
 /// Internal, ready to draw representation of [`PointCloudBatchInfo`]
 #[derive(Clone)]
 struct PointCloudBatch {
+    /// Data textures the [`Self::vertex_range`] refers to.
+    bind_group_all_points: GpuBindGroup,
     bind_group: GpuBindGroup,
     vertex_range: Range<u32>,
+    is_transparent: bool,
+
+    /// Whether this batch is drawn in [`DrawPhase::OutlineMask`].
+    has_outline: bool,
+
+    /// Batches for [`PointCloudBatchInfo::additional_outline_mask_ids_vertex_ranges`] are only drawn
+    /// in [`DrawPhase::OutlineMask`].
+    outline_only: bool,
 }
 
 /// A point cloud drawing operation.
 /// Expected to be recrated every frame.
 #[derive(Clone)]
 pub struct PointCloudDrawData {
-    bind_group_all_points: Option<GpuBindGroup>,
     batches: Vec<PointCloudBatch>,
 }
 
 impl DrawData for PointCloudDrawData {
     type Renderer = PointCloudRenderer;
+
+    fn has_transparent(&self) -> bool {
+        self.batches
+            .iter()
+            .any(|batch| batch.is_transparent && !batch.outline_only)
+    }
 }
 
 /// Data that is valid for a batch of point cloud points.
@@ -109,10 +140,29 @@ pub struct PointCloudBatchInfo {
     /// Additional properties of this point cloud batch.
     pub flags: PointCloudBatchFlags,
 
+    /// Opacity of all points in this batch, in `[0, 1]`.
+    ///
+    /// Batches with an opacity below 1.0 are drawn in [`DrawPhase::Transparent`].
+    pub opacity: f32,
+
     /// Number of points covered by this batch.
     ///
     /// The batch will start with the next point after the one the previous batch ended with.
     pub point_count: u32,
+
+    /// Outline mask ids of all points in this batch, unless overwritten by
+    /// [`Self::additional_outline_mask_ids_vertex_ranges`].
+    pub overall_outline_mask_ids: OutlineMaskPreference,
+
+    /// Ranges of points, relative to the start of this batch, with different outline mask ids.
+    ///
+    /// Later ranges take precedence over earlier ones where they overlap.
+    pub additional_outline_mask_ids_vertex_ranges: Vec<(Range<u32>, OutlineMaskPreference)>,
+
+    /// Picking object id of all points in this batch.
+    ///
+    /// Together with the per-point picking instance id, this forms the [`crate::PickingLayerId`] of a point.
+    pub picking_object_id: PickingLayerObjectId,
 }
 
 /// Description of a point cloud.
@@ -124,16 +174,40 @@ pub struct PointCloudVertex {
     pub radius: Size,
 }
 
+/// A static point cloud placed in the scene, see [`PointCloudDrawData::new_static`].
+#[derive(Clone)]
+pub struct StaticPointCloudInstance {
+    pub gpu_point_cloud: GpuPointCloudHandle,
+
+    /// Transformation applies to point positions.
+    pub world_from_obj: glam::Mat4,
+
+    /// Additional properties of this point cloud.
+    pub flags: PointCloudBatchFlags,
+
+    /// Opacity of all points, in `[0, 1]`.
+    pub opacity: f32,
+
+    /// Outline mask ids of all points.
+    pub outline_mask_ids: OutlineMaskPreference,
+
+    /// Picking object id of all points.
+    pub picking_object_id: PickingLayerObjectId,
+}
+
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
 pub enum PointCloudDrawDataError {
     #[error("Size of vertex & color array was not equal")]
     NumberOfColorsNotEqualNumberOfVertices,
+
+    #[error(transparent)]
+    ResourceManager(#[from] ResourceManagerError),
 }
 
 /// Textures are 2D since 1D textures are very limited in size (8k typically).
 /// Need to keep this value in sync with `point_cloud.wgsl`!
-/// We store `vec4<f32> + [u8;4]` = 20 bytes per texel.
-const DATA_TEXTURE_SIZE: u32 = 2048; // 2ki x 2ki = 4 Mi = 80 MiB
+/// We store `vec4<f32> + [u8;4] + [u32;2]` = 28 bytes per texel.
+const DATA_TEXTURE_SIZE: u32 = 2048; // 2ki x 2ki = 4 Mi = 112 MiB
 
 impl PointCloudDrawData {
     /// Maximum number of vertices per [`PointCloudDrawData`].
@@ -166,7 +240,6 @@ impl PointCloudDrawData {
 
         if vertices.is_empty() {
             return Ok(PointCloudDrawData {
-                bind_group_all_points: None,
                 batches: Vec::new(),
             });
         }
@@ -175,7 +248,11 @@ impl PointCloudDrawData {
             label: "all points".into(),
             world_from_obj: glam::Mat4::IDENTITY,
             flags: PointCloudBatchFlags::empty(),
+            opacity: 1.0,
             point_count: vertices.len() as _,
+            overall_outline_mask_ids: OutlineMaskPreference::NONE,
+            additional_outline_mask_ids_vertex_ranges: Vec::new(),
+            picking_object_id: Default::default(),
         }];
         let batches = if batches.is_empty() {
             &fallback_batches
@@ -194,6 +271,11 @@ impl PointCloudDrawData {
                 % wgpu::COPY_BYTES_PER_ROW_ALIGNMENT,
             0
         );
+        static_assertions::const_assert_eq!(
+            DATA_TEXTURE_SIZE * std::mem::size_of::<[u32; 2]>() as u32
+                % wgpu::COPY_BYTES_PER_ROW_ALIGNMENT,
+            0
+        );
 
         let vertices = if vertices.len() >= Self::MAX_NUM_POINTS {
             re_log::error_once!(
@@ -236,6 +318,15 @@ impl PointCloudDrawData {
                 ..position_data_texture_desc
             },
         );
+        let picking_instance_id_texture = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &TextureDesc {
+                label: "point cloud picking instance id data".into(),
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rg32Uint,
+                ..position_data_texture_desc
+            },
+        );
 
         // TODO(andreas): We want a staging-belt(-like) mechanism to upload data instead of the queue.
         //                  These staging buffers would be provided by the belt.
@@ -296,6 +387,19 @@ impl PointCloudDrawData {
             size,
         );
 
+        builder.picking_instance_ids_buffer.copy_to_texture(
+            ctx.active_frame.encoder.lock().get(),
+            wgpu::ImageCopyTexture {
+                texture: &picking_instance_id_texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            NonZeroU32::new(DATA_TEXTURE_SIZE * std::mem::size_of::<[u32; 2]>() as u32),
+            None,
+            size,
+        );
+
         let bind_group_all_points = ctx.gpu_resources.bind_groups.alloc(
             &ctx.device,
             &ctx.gpu_resources,
@@ -304,6 +408,7 @@ impl PointCloudDrawData {
                 entries: smallvec![
                     BindGroupEntry::DefaultTextureView(position_data_texture.handle),
                     BindGroupEntry::DefaultTextureView(color_texture.handle),
+                    BindGroupEntry::DefaultTextureView(picking_instance_id_texture.handle),
                 ],
                 layout: point_renderer.bind_group_layout_all_points,
             },
@@ -312,21 +417,62 @@ impl PointCloudDrawData {
         // Process batches
         let mut batches_internal = Vec::with_capacity(batches.len());
         {
+            let batch_uniform_buffer =
+                |batch_info: &PointCloudBatchInfo, outline_mask_ids: OutlineMaskPreference| {
+                    gpu_data::BatchUniformBuffer {
+                        world_from_obj: batch_info.world_from_obj.into(),
+                        flags: batch_info.flags.bits.into(),
+                        opacity: batch_info.opacity.clamp(0.0, 1.0).into(),
+                        outline_mask_ids: outline_mask_ids.ids().into(),
+                        picking_object_id: <[u32; 2]>::from(batch_info.picking_object_id).into(),
+                        end_padding: Default::default(),
+                    }
+                };
+
             let uniform_buffer_bindings = create_and_fill_uniform_buffer_batch(
                 ctx,
                 "point batch uniform buffers".into(),
-                batches
+                batches.iter().map(|batch_info| {
+                    batch_uniform_buffer(batch_info, batch_info.overall_outline_mask_ids)
+                }),
+            );
+
+            // Additional ranges are drawn on top of the whole batch in the outline mask phase.
+            // A range without outline is still needed if the batch as a whole has one, since it clears the mask again.
+            let additional_outline_ranges = batches
+                .iter()
+                .enumerate()
+                .flat_map(|(batch_index, batch_info)| {
+                    batch_info
+                        .additional_outline_mask_ids_vertex_ranges
+                        .iter()
+                        .filter(|(_, outline_mask_ids)| {
+                            outline_mask_ids.is_some()
+                                || batch_info.overall_outline_mask_ids.is_some()
+                        })
+                        .map(move |(range, outline_mask_ids)| {
+                            (batch_index, range.clone(), *outline_mask_ids)
+                        })
+                })
+                .collect_vec();
+            let mut additional_uniform_buffer_bindings = create_and_fill_uniform_buffer_batch(
+                ctx,
+                "point batch uniform buffers - outline ranges".into(),
+                additional_outline_ranges
                     .iter()
-                    .map(|batch_info| gpu_data::BatchUniformBuffer {
-                        world_from_obj: batch_info.world_from_obj.into(),
-                        flags: batch_info.flags.bits.into(),
-                        end_padding: Default::default(),
+                    .map(|(batch_index, _, outline_mask_ids)| {
+                        batch_uniform_buffer(&batches[*batch_index], *outline_mask_ids)
                     }),
-            );
+            )
+            .into_iter()
+            .zip(additional_outline_ranges.into_iter())
+            .peekable();
 
             let mut start_point_for_next_batch = 0;
-            for (batch_info, uniform_buffer_binding) in
-                batches.iter().zip(uniform_buffer_bindings.into_iter())
+            for (batch_index, (batch_info, uniform_buffer_binding)) in batches
+                .iter()
+                .zip(uniform_buffer_bindings.into_iter())
+                .enumerate()
             {
                 let bind_group = ctx.gpu_resources.bind_groups.alloc(
                     &ctx.device,
@@ -342,11 +488,46 @@ impl PointCloudDrawData {
                     .min(Self::MAX_NUM_POINTS as u32);
 
                 batches_internal.push(PointCloudBatch {
+                    bind_group_all_points: bind_group_all_points.clone(),
                     bind_group,
                     vertex_range: (start_point_for_next_batch * 6)
                         ..((start_point_for_next_batch + batch_info.point_count) * 6),
+                    is_transparent: batch_info.opacity < 1.0,
+                    has_outline: batch_info.overall_outline_mask_ids.is_some(),
+                    outline_only: false,
                 });
 
+                while let Some((uniform_buffer_binding, (_, range, _))) =
+                    additional_uniform_buffer_bindings
+                        .next_if(|(_, (range_batch_index, _, _))| *range_batch_index == batch_index)
+                {
+                    let range_start =
+                        (start_point_for_next_batch + range.start).min(point_vertex_range_end);
+                    let range_end =
+                        (start_point_for_next_batch + range.end).min(point_vertex_range_end);
+                    if range_start >= range_end {
+                        continue;
+                    }
+
+                    let bind_group = ctx.gpu_resources.bind_groups.alloc(
+                        &ctx.device,
+                        &ctx.gpu_resources,
+                        &BindGroupDesc {
+                            label: batch_info.label.clone().push_str(" - outline range"),
+                            entries: smallvec![uniform_buffer_binding],
+                            layout: point_renderer.bind_group_layout_batch,
+                        },
+                    );
+                    batches_internal.push(PointCloudBatch {
+                        bind_group_all_points: bind_group_all_points.clone(),
+                        bind_group,
+                        vertex_range: (range_start * 6)..(range_end * 6),
+                        is_transparent: false,
+                        has_outline: true,
+                        outline_only: true,
+                    });
+                }
+
                 start_point_for_next_batch = point_vertex_range_end;
 
                 // Should happen only if the number of vertices was clamped.
@@ -357,15 +538,233 @@ impl PointCloudDrawData {
         }
 
         Ok(PointCloudDrawData {
-            bind_group_all_points: Some(bind_group_all_points),
             batches: batches_internal,
         })
     }
+
+    /// Draws static point clouds, reusing their data from previous frames.
+    ///
+    /// Only the parts of the point clouds that are visible and detailed enough for the given view are drawn,
+    /// see [`PointCloudLodView`].
+    /// Since the selection depends on the view, this is meant to be recreated every frame as well.
+    pub fn new_static(
+        ctx: &mut RenderContext,
+        lod_view: &PointCloudLodView,
+        instances: &[StaticPointCloudInstance],
+    ) -> Result<Self, PointCloudDrawDataError> {
+        crate::profile_function!();
+
+        let mut renderers = ctx.renderers.write();
+        let point_renderer = renderers.get_or_create::<_, PointCloudRenderer>(
+            &ctx.shared_renderer_data,
+            &mut ctx.gpu_resources,
+            &ctx.device,
+            &mut ctx.resolver,
+        );
+
+        if instances.is_empty() {
+            return Ok(PointCloudDrawData {
+                batches: Vec::new(),
+            });
+        }
+
+        let point_cloud_manager = ctx.point_cloud_manager.read();
+        let point_clouds = instances
+            .iter()
+            .map(|instance| point_cloud_manager.get(&instance.gpu_point_cloud))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let selected_ranges = point_cloud_lod::select_points(
+            lod_view,
+            point_clouds
+                .iter()
+                .zip(instances.iter())
+                .map(|(point_cloud, instance)| (&*point_cloud.octree, instance.world_from_obj)),
+        );
+
+        let uniform_buffer_bindings = create_and_fill_uniform_buffer_batch(
+            ctx,
+            "static point cloud uniform buffers".into(),
+            instances
+                .iter()
+                .map(|instance| gpu_data::BatchUniformBuffer {
+                    world_from_obj: instance.world_from_obj.into(),
+                    flags: instance.flags.bits.into(),
+                    opacity: instance.opacity.clamp(0.0, 1.0).into(),
+                    outline_mask_ids: instance.outline_mask_ids.ids().into(),
+                    picking_object_id: <[u32; 2]>::from(instance.picking_object_id).into(),
+                    end_padding: Default::default(),
+                }),
+        );
+
+        let mut batches = Vec::new();
+        for (((instance, point_cloud), ranges), uniform_buffer_binding) in instances
+            .iter()
+            .zip(point_clouds.iter())
+            .zip(selected_ranges.into_iter())
+            .zip(uniform_buffer_bindings.into_iter())
+        {
+            if ranges.is_empty() {
+                continue;
+            }
+
+            let bind_group = ctx.gpu_resources.bind_groups.alloc(
+                &ctx.device,
+                &ctx.gpu_resources,
+                &BindGroupDesc {
+                    label: point_cloud.label.clone(),
+                    entries: smallvec![uniform_buffer_binding],
+                    layout: point_renderer.bind_group_layout_batch,
+                },
+            );
+
+            // Ranges may span several chunks of data textures, split them up accordingly.
+            let chunk_size = Self::MAX_NUM_POINTS as u32;
+            for range in ranges {
+                let mut start = range.start;
+                while start < range.end {
+                    let chunk_index = start / chunk_size;
+                    let chunk_start = chunk_index * chunk_size;
+                    let end = range.end.min(chunk_start + chunk_size);
+
+                    batches.push(PointCloudBatch {
+                        bind_group_all_points: point_cloud.chunks[chunk_index as usize].clone(),
+                        bind_group: bind_group.clone(),
+                        vertex_range: ((start - chunk_start) * 6)..((end - chunk_start) * 6),
+                        is_transparent: instance.opacity < 1.0,
+                        has_outline: instance.outline_mask_ids.is_some(),
+                        outline_only: false,
+                    });
+
+                    start = end;
+                }
+            }
+        }
+
+        Ok(PointCloudDrawData { batches })
+    }
+}
+
+/// Uploads point data into a set of data textures that stays alive as long as the returned bind group.
+///
+/// All slices need to have the same length of at most [`PointCloudDrawData::MAX_NUM_POINTS`].
+pub(crate) fn create_static_data_textures(
+    ctx: &RenderContext,
+    bind_group_layout_all_points: GpuBindGroupLayoutHandle,
+    label: &DebugLabel,
+    positions: &[gpu_data::PositionData],
+    colors: &[Color32],
+    picking_instance_ids: &[PickingLayerInstanceId],
+) -> GpuBindGroup {
+    crate::profile_function!();
+
+    debug_assert!(positions.len() <= PointCloudDrawData::MAX_NUM_POINTS);
+    debug_assert_eq!(positions.len(), colors.len());
+    debug_assert_eq!(positions.len(), picking_instance_ids.len());
+
+    // Always write full rows to keep the upload simple.
+    let num_rows = (positions.len() as u32 + DATA_TEXTURE_SIZE - 1) / DATA_TEXTURE_SIZE;
+    let num_points_zeroed = (num_rows * DATA_TEXTURE_SIZE) as usize - positions.len();
+    let size = wgpu::Extent3d {
+        width: DATA_TEXTURE_SIZE,
+        height: num_rows.max(1),
+        depth_or_array_layers: 1,
+    };
+
+    let upload = |format: wgpu::TextureFormat, data: &[u8], bytes_per_texel: u32| -> GpuTexture {
+        let texture = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &TextureDesc {
+                label: label.clone().push_str(" - static point cloud data"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            },
+        );
+        if !data.is_empty() {
+            ctx.queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &texture.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                data,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(DATA_TEXTURE_SIZE * bytes_per_texel),
+                    rows_per_image: None,
+                },
+                wgpu::Extent3d {
+                    height: num_rows,
+                    ..size
+                },
+            );
+        }
+        texture
+    };
+
+    let position_data_texture = {
+        let data = positions
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(gpu_data::PositionData::zeroed()).take(num_points_zeroed))
+            .collect_vec();
+        upload(
+            wgpu::TextureFormat::Rgba32Float,
+            bytemuck::cast_slice(&data),
+            std::mem::size_of::<gpu_data::PositionData>() as u32,
+        )
+    };
+    let color_texture = {
+        let data = colors
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(Color32::TRANSPARENT).take(num_points_zeroed))
+            .collect_vec();
+        upload(
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            bytemuck::cast_slice(&data),
+            std::mem::size_of::<[u8; 4]>() as u32,
+        )
+    };
+    let picking_instance_id_texture = {
+        let data = picking_instance_ids
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(PickingLayerInstanceId::default()).take(num_points_zeroed))
+            .collect_vec();
+        upload(
+            wgpu::TextureFormat::Rg32Uint,
+            bytemuck::cast_slice(&data),
+            std::mem::size_of::<[u32; 2]>() as u32,
+        )
+    };
+
+    ctx.gpu_resources.bind_groups.alloc(
+        &ctx.device,
+        &ctx.gpu_resources,
+        &BindGroupDesc {
+            label: label.clone(),
+            entries: smallvec![
+                BindGroupEntry::DefaultTextureView(position_data_texture.handle),
+                BindGroupEntry::DefaultTextureView(color_texture.handle),
+                BindGroupEntry::DefaultTextureView(picking_instance_id_texture.handle),
+            ],
+            layout: bind_group_layout_all_points,
+        },
+    )
 }
 
 pub struct PointCloudRenderer {
     render_pipeline: GpuRenderPipelineHandle,
-    bind_group_layout_all_points: GpuBindGroupLayoutHandle,
+    render_pipeline_transparent: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
+    render_pipeline_picking_layer: GpuRenderPipelineHandle,
+    pub(crate) bind_group_layout_all_points: GpuBindGroupLayoutHandle,
     bind_group_layout_batch: GpuBindGroupLayoutHandle,
 }
 
@@ -405,6 +804,16 @@ impl Renderer for PointCloudRenderer {
                         },
                         count: None,
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Uint,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
                 ],
             },
         );
@@ -450,28 +859,69 @@ impl Renderer for PointCloudRenderer {
             },
         );
 
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "point cloud".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+            vertex_buffers: smallvec![],
+            render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: wgpu::MultisampleState {
+                // We discard pixels to do the round cutout, therefore we need to calculate
+                // our own sampling mask.
+                alpha_to_coverage_enabled: true,
+                ..ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE
+            },
+        };
         let render_pipeline = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_transparent = pools.render_pipelines.get_or_create(
             device,
             &RenderPipelineDesc {
-                label: "point cloud".into(),
-                pipeline_layout,
-                vertex_entrypoint: "vs_main".into(),
-                vertex_handle: shader_module,
-                fragment_entrypoint: "fs_main".into(),
-                fragment_handle: shader_module,
-                vertex_buffers: smallvec![],
-                render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    ..Default::default()
-                },
-                depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
-                multisample: wgpu::MultisampleState {
-                    // We discard pixels to do the round cutout, therefore we need to calculate
-                    // our own sampling mask.
-                    alpha_to_coverage_enabled: true,
-                    ..ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE
-                },
+                label: "point cloud - transparent".into(),
+                fragment_entrypoint: "fs_main_transparent".into(),
+                render_targets: ViewBuilder::OIT_RENDER_TARGETS.into_iter().collect(),
+                depth_stencil: ViewBuilder::OIT_DEPTH_STATE,
+                // Coverage goes into the alpha that is accumulated instead.
+                multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "point cloud - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE,
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_picking_layer = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "point cloud - picking layer".into(),
+                fragment_entrypoint: "fs_main_picking_layer".into(),
+                render_targets: smallvec![Some(PickingLayerProcessor::PICKING_LAYER_FORMAT.into())],
+                depth_stencil: PickingLayerProcessor::PICKING_LAYER_DEPTH_STATE,
+                multisample: PickingLayerProcessor::PICKING_LAYER_MSAA_STATE,
+                ..render_pipeline_desc
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
@@ -479,6 +929,9 @@ impl Renderer for PointCloudRenderer {
 
         PointCloudRenderer {
             render_pipeline,
+            render_pipeline_transparent,
+            render_pipeline_outline_mask,
+            render_pipeline_picking_layer,
             bind_group_layout_all_points,
             bind_group_layout_batch,
         }
@@ -487,23 +940,56 @@ impl Renderer for PointCloudRenderer {
     fn draw<'a>(
         &self,
         pools: &'a WgpuResourcePools,
-        _phase: DrawPhase,
+        phase: DrawPhase,
         pass: &mut wgpu::RenderPass<'a>,
         draw_data: &'a Self::RendererDrawData,
     ) -> anyhow::Result<()> {
-        let Some(bind_group_all_points) = &draw_data.bind_group_all_points else {
-            return Ok(()); // No points submitted.
-        };
-        let pipeline = pools.render_pipelines.get_resource(self.render_pipeline)?;
+        let mut batches = draw_data
+            .batches
+            .iter()
+            .filter(|batch| match phase {
+                DrawPhase::OutlineMask => batch.has_outline,
+                // Semi-transparent points are pickable as well.
+                DrawPhase::PickingLayer => !batch.outline_only,
+                DrawPhase::Transparent => batch.is_transparent && !batch.outline_only,
+                _ => !batch.is_transparent && !batch.outline_only,
+            })
+            .peekable();
+        if batches.peek().is_none() {
+            return Ok(());
+        }
+
+        let pipeline = pools.render_pipelines.get_resource(match phase {
+            DrawPhase::Transparent => self.render_pipeline_transparent,
+            DrawPhase::OutlineMask => self.render_pipeline_outline_mask,
+            DrawPhase::PickingLayer => self.render_pipeline_picking_layer,
+            _ => self.render_pipeline,
+        })?;
 
         pass.set_pipeline(pipeline);
-        pass.set_bind_group(1, bind_group_all_points, &[]);
 
-        for batch in &draw_data.batches {
+        let mut bound_all_points: Option<&GpuBindGroup> = None;
+        for batch in batches {
+            // Static point clouds may use several sets of data textures, dynamic ones use just one.
+            if !bound_all_points.map_or(false, |bound| {
+                std::ptr::eq::<wgpu::BindGroup>(&**bound, &*batch.bind_group_all_points)
+            }) {
+                pass.set_bind_group(1, &batch.bind_group_all_points, &[]);
+                bound_all_points = Some(&batch.bind_group_all_points);
+            }
             pass.set_bind_group(2, &batch.bind_group, &[]);
             pass.draw(batch.vertex_range.clone(), 0..1);
         }
 
         Ok(())
     }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[
+            DrawPhase::Opaque,
+            DrawPhase::Transparent,
+            DrawPhase::OutlineMask,
+            DrawPhase::PickingLayer,
+        ]
+    }
 }
@@ -1,9 +1,10 @@
 //! Renderer that makes it easy to draw textured 2d rectangles with transparency
 //!
-//! Transparency: (TODO(andreas):)
-//! We're not performing any sorting on transparency yet, so the transparent rectangles pretty much
-//! only work correctly when they are directly layered in front of another opaque rectangle.
-//! We do *not* disable depth write.
+//! Transparency:
+//! Rectangles with a [`TexturedRect::multiplicative_tint`] alpha below 1.0 are drawn in
+//! [`DrawPhase::Transparent`] which needs no sorting and doesn't write depth.
+//! Transparency coming from the texture itself is blended in [`DrawPhase::Opaque`] which writes depth,
+//! so it pretty much only works correctly when directly layered in front of another opaque rectangle.
 //!
 //! Implementation details:
 //! We assume the standard usecase are individual textured rectangles.
@@ -16,6 +17,7 @@ use crate::{
     allocator::create_and_fill_uniform_buffer_batch,
     depth_offset::DepthOffset,
     include_file,
+    outlines::OutlineMaskProcessor,
     resource_managers::{GpuTexture2DHandle, ResourceManagerError},
     view_builder::ViewBuilder,
     wgpu_resources::{
@@ -23,7 +25,7 @@ use crate::{
         GpuRenderPipelineHandle, PipelineLayoutDesc, RenderPipelineDesc, SamplerDesc,
         ShaderModuleDesc,
     },
-    Rgba,
+    OutlineMaskPreference, Rgba,
 };
 
 use super::{
@@ -43,8 +45,9 @@ mod gpu_data {
         pub extent_v: wgpu_buffer_types::Vec3Unpadded,
         pub depth_offset: f32,
         pub multiplicative_tint: crate::Rgba,
+        pub outline_mask: wgpu_buffer_types::UVec2RowPadded,
 
-        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 4],
+        pub end_padding: [wgpu_buffer_types::PaddingRow; 16 - 5],
     }
 }
 
@@ -81,9 +84,14 @@ pub struct TexturedRect {
     pub texture_filter_minification: TextureFilterMin,
 
     /// Tint that is multiplied to the rect, supports pre-multiplied alpha.
+    ///
+    /// An alpha below 1.0 makes the rectangle semi-transparent, see [`DrawPhase::Transparent`].
     pub multiplicative_tint: Rgba,
 
     pub depth_offset: DepthOffset,
+
+    /// Whether and with which ids this rectangle is drawn into the outline mask.
+    pub outline_mask: OutlineMaskPreference,
 }
 
 impl Default for TexturedRect {
@@ -97,17 +105,24 @@ impl Default for TexturedRect {
             texture_filter_minification: TextureFilterMin::Linear,
             multiplicative_tint: Rgba::WHITE,
             depth_offset: 0,
+            outline_mask: OutlineMaskPreference::NONE,
         }
     }
 }
 
 #[derive(Clone)]
 pub struct RectangleDrawData {
-    bind_groups: Vec<GpuBindGroup>,
+    opaque_bind_groups: Vec<GpuBindGroup>,
+    transparent_bind_groups: Vec<GpuBindGroup>,
+    outline_mask_bind_groups: Vec<GpuBindGroup>,
 }
 
 impl DrawData for RectangleDrawData {
     type Renderer = RectangleRenderer;
+
+    fn has_transparent(&self) -> bool {
+        !self.transparent_bind_groups.is_empty()
+    }
 }
 
 impl RectangleDrawData {
@@ -127,7 +142,9 @@ impl RectangleDrawData {
 
         if rectangles.is_empty() {
             return Ok(RectangleDrawData {
-                bind_groups: Vec::new(),
+                opaque_bind_groups: Vec::new(),
+                transparent_bind_groups: Vec::new(),
+                outline_mask_bind_groups: Vec::new(),
             });
         }
 
@@ -140,11 +157,14 @@ impl RectangleDrawData {
                 extent_v: rectangle.extent_v.into(),
                 depth_offset: rectangle.depth_offset as f32,
                 multiplicative_tint: rectangle.multiplicative_tint,
+                outline_mask: rectangle.outline_mask.ids().into(),
                 end_padding: Default::default(),
             }),
         );
 
-        let mut bind_groups = Vec::with_capacity(rectangles.len());
+        let mut opaque_bind_groups = Vec::new();
+        let mut transparent_bind_groups = Vec::new();
+        let mut outline_mask_bind_groups = Vec::new();
         for (rectangle, uniform_buffer) in
             rectangles.iter().zip(uniform_buffer_bindings.into_iter())
         {
@@ -172,7 +192,7 @@ impl RectangleDrawData {
                 },
             );
 
-            bind_groups.push(ctx.gpu_resources.bind_groups.alloc(
+            let bind_group = ctx.gpu_resources.bind_groups.alloc(
                 &ctx.device,
                 &ctx.gpu_resources,
                 &BindGroupDesc {
@@ -184,15 +204,29 @@ impl RectangleDrawData {
                     ],
                     layout: rectangle_renderer.bind_group_layout,
                 },
-            ));
+            );
+            if rectangle.outline_mask.is_some() {
+                outline_mask_bind_groups.push(bind_group.clone());
+            }
+            if rectangle.multiplicative_tint.a() < 1.0 {
+                transparent_bind_groups.push(bind_group);
+            } else {
+                opaque_bind_groups.push(bind_group);
+            }
         }
 
-        Ok(RectangleDrawData { bind_groups })
+        Ok(RectangleDrawData {
+            opaque_bind_groups,
+            transparent_bind_groups,
+            outline_mask_bind_groups,
+        })
     }
 }
 
 pub struct RectangleRenderer {
     render_pipeline: GpuRenderPipelineHandle,
+    render_pipeline_transparent: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
     bind_group_layout: GpuBindGroupLayoutHandle,
 }
 
@@ -265,29 +299,54 @@ impl Renderer for RectangleRenderer {
             },
         );
 
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "rectangle".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+            vertex_buffers: smallvec![],
+            render_targets: smallvec![Some(wgpu::ColorTargetState {
+                format: ViewBuilder::MAIN_TARGET_COLOR_FORMAT,
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+        };
         let render_pipeline = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_transparent = pools.render_pipelines.get_or_create(
             device,
             &RenderPipelineDesc {
-                label: "rectangle".into(),
-                pipeline_layout,
-                vertex_entrypoint: "vs_main".into(),
-                vertex_handle: shader_module,
-                fragment_entrypoint: "fs_main".into(),
-                fragment_handle: shader_module,
-                vertex_buffers: smallvec![],
-                render_targets: smallvec![Some(wgpu::ColorTargetState {
-                    format: ViewBuilder::MAIN_TARGET_COLOR_FORMAT,
-                    // TODO(andreas): have two render pipelines, an opaque one and a transparent one. Transparent shouldn't write depth!
-                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleStrip,
-                    cull_mode: None,
-                    ..Default::default()
-                },
-                depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
-                multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+                label: "rectangle - transparent".into(),
+                fragment_entrypoint: "fs_main_transparent".into(),
+                render_targets: ViewBuilder::OIT_RENDER_TARGETS.into_iter().collect(),
+                depth_stencil: ViewBuilder::OIT_DEPTH_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "rectangle - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE,
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc
             },
             &pools.pipeline_layouts,
             &pools.shader_modules,
@@ -295,6 +354,8 @@ impl Renderer for RectangleRenderer {
 
         RectangleRenderer {
             render_pipeline,
+            render_pipeline_transparent,
+            render_pipeline_outline_mask,
             bind_group_layout,
         }
     }
@@ -302,19 +363,31 @@ impl Renderer for RectangleRenderer {
     fn draw<'a>(
         &self,
         pools: &'a WgpuResourcePools,
-        _phase: DrawPhase,
+        phase: DrawPhase,
         pass: &mut wgpu::RenderPass<'a>,
         draw_data: &'a Self::RendererDrawData,
     ) -> anyhow::Result<()> {
         crate::profile_function!();
-        if draw_data.bind_groups.is_empty() {
+
+        let (bind_groups, pipeline) = match phase {
+            DrawPhase::Transparent => (
+                &draw_data.transparent_bind_groups,
+                self.render_pipeline_transparent,
+            ),
+            DrawPhase::OutlineMask => (
+                &draw_data.outline_mask_bind_groups,
+                self.render_pipeline_outline_mask,
+            ),
+            _ => (&draw_data.opaque_bind_groups, self.render_pipeline),
+        };
+        if bind_groups.is_empty() {
             return Ok(());
         }
 
-        let pipeline = pools.render_pipelines.get_resource(self.render_pipeline)?;
+        let pipeline = pools.render_pipelines.get_resource(pipeline)?;
         pass.set_pipeline(pipeline);
 
-        for bind_group in &draw_data.bind_groups {
+        for bind_group in bind_groups {
             pass.set_bind_group(1, bind_group, &[]);
             pass.draw(0..4, 0..1);
         }
@@ -323,7 +396,10 @@ impl Renderer for RectangleRenderer {
     }
 
     fn participated_phases() -> &'static [DrawPhase] {
-        // TODO(andreas): This a hack. We have both opaque and transparent.
-        &[DrawPhase::Opaque]
+        &[
+            DrawPhase::Opaque,
+            DrawPhase::Transparent,
+            DrawPhase::OutlineMask,
+        ]
     }
 }
@@ -0,0 +1,489 @@
+//! Renderer that ray marches through volumes, i.e. 3D textures of scalar values.
+//!
+//! Volumes are uploaded once via [`crate::resource_managers::VolumeManager`].
+//!
+//! ## Implementation details
+//!
+//! Each volume is drawn as a cube, every fragment marches the camera ray through the 3D texture.
+//! Only the fragments of the faces where rays leave the cube do any work, this way the volume
+//! is drawn even when the camera is inside of it.
+//! All fragment shaders write the depth of the first visible sample,
+//! so volumes are occluded correctly by anything in front of them.
+//! Geometry inside of a volume doesn't occlude it, as we can't read the depth buffer while drawing.
+
+use smallvec::smallvec;
+
+use crate::{
+    allocator::create_and_fill_uniform_buffer_batch,
+    include_file,
+    outlines::OutlineMaskProcessor,
+    picking_layer::PickingLayerProcessor,
+    resource_managers::{GpuVolumeHandle, ResourceManagerError, Texture2DCreationDesc},
+    view_builder::ViewBuilder,
+    wgpu_resources::{
+        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
+        GpuRenderPipelineHandle, GpuSamplerHandle, PipelineLayoutDesc, RenderPipelineDesc,
+        SamplerDesc, ShaderModuleDesc,
+    },
+    ColorMap, OutlineMaskPreference, PickingLayerId,
+};
+
+use super::{
+    DrawData, DrawPhase, FileResolver, FileSystem, RenderContext, Renderer, SharedRendererData,
+    WgpuResourcePools,
+};
+
+mod gpu_data {
+    // - Keep in sync with mirror in volume.wgsl.
+    // - See `VolumeInstance` for documentation.
+    #[repr(C, align(256))]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct VolumeInfoUBO {
+        pub world_from_volume: crate::wgpu_buffer_types::Mat4,
+        pub volume_from_world: crate::wgpu_buffer_types::Mat4,
+        pub dimensions_and_render_mode: [u32; 4],
+        pub colormap: crate::wgpu_buffer_types::UVec2RowPadded,
+
+        /// Threshold, opacity, iso value & padding.
+        pub params: [f32; 4],
+        pub outline_mask_id: crate::wgpu_buffer_types::UVec2RowPadded,
+        pub picking_layer_id: [u32; 4],
+
+        pub end_padding: [crate::wgpu_buffer_types::PaddingRow; 16 - 13],
+    }
+}
+
+/// How values along a ray through a volume are turned into a color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VolumeRenderMode {
+    /// Accumulates color mapped values front to back, values are treated as density.
+    Composite,
+
+    /// Shows the color mapped maximum value along each ray.
+    MaxIntensity,
+
+    /// Opaque, lit surface where the volume crosses the given value.
+    Isosurface { iso_value: f32 },
+}
+
+impl VolumeRenderMode {
+    // NOTE: Keep in sync with `volume.wgsl`!
+    fn gpu_id(&self) -> u32 {
+        match self {
+            VolumeRenderMode::Composite => 0,
+            VolumeRenderMode::MaxIntensity => 1,
+            VolumeRenderMode::Isosurface { .. } => 2,
+        }
+    }
+
+    fn is_transparent(&self) -> bool {
+        !matches!(self, VolumeRenderMode::Isosurface { .. })
+    }
+}
+
+/// A volume placed in the scene, see [`VolumeDrawData::new`].
+#[derive(Clone)]
+pub struct VolumeInstance {
+    pub gpu_volume: GpuVolumeHandle,
+
+    /// Maps the unit cube to world space.
+    ///
+    /// Needs to be invertible.
+    pub world_from_volume: glam::Mat4,
+
+    pub render_mode: VolumeRenderMode,
+
+    /// Configures color mapping mode.
+    pub colormap: ColorMap,
+
+    /// Custom color lookup table of evenly spaced sRGB colors, used instead of [`Self::colormap`].
+    ///
+    /// Falls back to [`Self::colormap`] if empty or longer than [`VolumeInstance::MAX_COLORMAP_LUT_SIZE`].
+    pub colormap_lut: Option<std::sync::Arc<[[u8; 4]]>>,
+
+    /// Values below the threshold are fully transparent.
+    ///
+    /// Ignored for [`VolumeRenderMode::Isosurface`].
+    pub threshold: f32,
+
+    /// Opacity per voxel of the densest value for [`VolumeRenderMode::Composite`],
+    /// overall opacity for [`VolumeRenderMode::MaxIntensity`].
+    pub opacity: f32,
+
+    /// Whether and with which ids this volume is drawn into the outline mask.
+    pub outline_mask_ids: OutlineMaskPreference,
+
+    /// Picking id written for every pixel covered by the volume.
+    pub picking_layer_id: PickingLayerId,
+}
+
+impl VolumeInstance {
+    /// Maximum number of colors in [`Self::colormap_lut`].
+    pub const MAX_COLORMAP_LUT_SIZE: usize = 2048;
+
+    fn valid_colormap_lut(&self) -> Option<&[[u8; 4]]> {
+        let lut = self.colormap_lut.as_deref()?;
+        if lut.is_empty() || lut.len() > Self::MAX_COLORMAP_LUT_SIZE {
+            re_log::warn_once!(
+                "Volume color lookup tables need between 1 and {} colors, got {}",
+                Self::MAX_COLORMAP_LUT_SIZE,
+                lut.len()
+            );
+            None
+        } else {
+            Some(lut)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct VolumeDrawInstance {
+    bind_group: GpuBindGroup,
+    transparent: bool,
+    render_outline_mask: bool,
+}
+
+#[derive(Clone)]
+pub struct VolumeDrawData {
+    instances: Vec<VolumeDrawInstance>,
+}
+
+impl DrawData for VolumeDrawData {
+    type Renderer = VolumeRenderer;
+
+    fn has_transparent(&self) -> bool {
+        self.instances.iter().any(|instance| instance.transparent)
+    }
+}
+
+impl VolumeDrawData {
+    pub fn new(
+        ctx: &mut RenderContext,
+        instances: &[VolumeInstance],
+    ) -> Result<Self, ResourceManagerError> {
+        crate::profile_function!();
+
+        let (bind_group_layout, sampler) = {
+            let mut renderers = ctx.renderers.write();
+            let volume_renderer = renderers.get_or_create::<_, VolumeRenderer>(
+                &ctx.shared_renderer_data,
+                &mut ctx.gpu_resources,
+                &ctx.device,
+                &mut ctx.resolver,
+            );
+            (volume_renderer.bind_group_layout, volume_renderer.sampler)
+        };
+
+        if instances.is_empty() {
+            return Ok(VolumeDrawData {
+                instances: Vec::new(),
+            });
+        }
+
+        let volumes = {
+            let volume_manager = ctx.volume_manager.read();
+            instances
+                .iter()
+                .map(|instance| volume_manager.get(&instance.gpu_volume).cloned())
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let volume_ubos = create_and_fill_uniform_buffer_batch(
+            ctx,
+            "volume_ubos".into(),
+            instances
+                .iter()
+                .zip(volumes.iter())
+                .map(|(instance, volume)| {
+                    let iso_value = match instance.render_mode {
+                        VolumeRenderMode::Isosurface { iso_value } => iso_value,
+                        _ => 0.0,
+                    };
+                    gpu_data::VolumeInfoUBO {
+                        world_from_volume: instance.world_from_volume.into(),
+                        volume_from_world: instance.world_from_volume.inverse().into(),
+                        dimensions_and_render_mode: volume
+                            .dimensions
+                            .extend(instance.render_mode.gpu_id())
+                            .to_array(),
+                        colormap: [
+                            instance.colormap as u32,
+                            instance.valid_colormap_lut().is_some() as u32,
+                        ]
+                        .into(),
+                        params: [instance.threshold, instance.opacity, iso_value, 0.0],
+                        outline_mask_id: instance.outline_mask_ids.ids().into(),
+                        picking_layer_id: instance.picking_layer_id.into(),
+                        end_padding: Default::default(),
+                    }
+                }),
+        );
+
+        let mut draw_instances = Vec::with_capacity(instances.len());
+        for ((instance, volume), ubo) in instances
+            .iter()
+            .zip(volumes.iter())
+            .zip(volume_ubos.into_iter())
+        {
+            let colormap_lut = match instance.valid_colormap_lut() {
+                Some(lut) => ctx.texture_manager_2d.create(
+                    &mut ctx.gpu_resources.textures,
+                    &Texture2DCreationDesc {
+                        label: "volume_colormap_lut".into(),
+                        data: bytemuck::cast_slice(lut),
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        width: lut.len() as u32,
+                        height: 1,
+                    },
+                ),
+                None => ctx.texture_manager_2d.white_texture_handle().clone(),
+            };
+            let colormap_lut = ctx.texture_manager_2d.get(&colormap_lut)?;
+
+            draw_instances.push(VolumeDrawInstance {
+                bind_group: ctx.gpu_resources.bind_groups.alloc(
+                    &ctx.device,
+                    &ctx.gpu_resources,
+                    &BindGroupDesc {
+                        label: "volume_bg".into(),
+                        entries: smallvec![
+                            ubo,
+                            BindGroupEntry::DefaultTextureView(volume.texture.handle),
+                            BindGroupEntry::Sampler(sampler),
+                            BindGroupEntry::DefaultTextureView(colormap_lut.handle),
+                        ],
+                        layout: bind_group_layout,
+                    },
+                ),
+                transparent: instance.render_mode.is_transparent(),
+                render_outline_mask: instance.outline_mask_ids.is_some(),
+            });
+        }
+
+        Ok(VolumeDrawData {
+            instances: draw_instances,
+        })
+    }
+}
+
+pub struct VolumeRenderer {
+    render_pipeline_opaque: GpuRenderPipelineHandle,
+    render_pipeline_transparent: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
+    render_pipeline_picking_layer: GpuRenderPipelineHandle,
+    bind_group_layout: GpuBindGroupLayoutHandle,
+    sampler: GpuSamplerHandle,
+}
+
+impl Renderer for VolumeRenderer {
+    type RendererDrawData = VolumeDrawData;
+
+    fn create_renderer<Fs: FileSystem>(
+        shared_data: &SharedRendererData,
+        pools: &mut WgpuResourcePools,
+        device: &wgpu::Device,
+        resolver: &mut FileResolver<Fs>,
+    ) -> Self {
+        crate::profile_function!();
+
+        let bind_group_layout = pools.bind_group_layouts.get_or_create(
+            device,
+            &BindGroupLayoutDesc {
+                label: "volume_bg_layout".into(),
+                entries: vec![
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: (std::mem::size_of::<gpu_data::VolumeInfoUBO>()
+                                as u64)
+                                .try_into()
+                                .ok(),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D3,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                ],
+            },
+        );
+
+        let sampler = pools.samplers.get_or_create(
+            device,
+            &SamplerDesc {
+                label: "volume_sampler".into(),
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                mipmap_filter: wgpu::FilterMode::Nearest,
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                address_mode_w: wgpu::AddressMode::ClampToEdge,
+                ..Default::default()
+            },
+        );
+
+        let pipeline_layout = pools.pipeline_layouts.get_or_create(
+            device,
+            &PipelineLayoutDesc {
+                label: "volume_rp_layout".into(),
+                entries: vec![shared_data.global_bindings.layout, bind_group_layout],
+            },
+            &pools.bind_group_layouts,
+        );
+
+        let shader_module = pools.shader_modules.get_or_create(
+            device,
+            resolver,
+            &ShaderModuleDesc {
+                label: "volume".into(),
+                source: include_file!("../../shader/volume.wgsl"),
+            },
+        );
+
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "volume_rp - opaque".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+            vertex_buffers: smallvec![],
+            render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                // The shader picks the faces to use, see module docs.
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+        };
+        let render_pipeline_opaque = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_transparent = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "volume_rp - transparent".into(),
+                fragment_entrypoint: "fs_main_transparent".into(),
+                render_targets: ViewBuilder::OIT_RENDER_TARGETS.into_iter().collect(),
+                depth_stencil: ViewBuilder::OIT_DEPTH_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "volume_rp - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE,
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_picking_layer = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "volume_rp - picking layer".into(),
+                fragment_entrypoint: "fs_main_picking_layer".into(),
+                render_targets: smallvec![Some(PickingLayerProcessor::PICKING_LAYER_FORMAT.into())],
+                depth_stencil: PickingLayerProcessor::PICKING_LAYER_DEPTH_STATE,
+                multisample: PickingLayerProcessor::PICKING_LAYER_MSAA_STATE,
+                ..render_pipeline_desc
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+
+        VolumeRenderer {
+            render_pipeline_opaque,
+            render_pipeline_transparent,
+            render_pipeline_outline_mask,
+            render_pipeline_picking_layer,
+            bind_group_layout,
+            sampler,
+        }
+    }
+
+    fn draw<'a>(
+        &self,
+        pools: &'a WgpuResourcePools,
+        phase: DrawPhase,
+        pass: &mut wgpu::RenderPass<'a>,
+        draw_data: &'a Self::RendererDrawData,
+    ) -> anyhow::Result<()> {
+        crate::profile_function!();
+
+        let mut instances = draw_data
+            .instances
+            .iter()
+            .filter(|instance| match phase {
+                DrawPhase::Opaque => !instance.transparent,
+                DrawPhase::Transparent => instance.transparent,
+                DrawPhase::OutlineMask => instance.render_outline_mask,
+                _ => true,
+            })
+            .peekable();
+        if instances.peek().is_none() {
+            return Ok(());
+        }
+
+        let pipeline = pools.render_pipelines.get_resource(match phase {
+            DrawPhase::Transparent => self.render_pipeline_transparent,
+            DrawPhase::OutlineMask => self.render_pipeline_outline_mask,
+            DrawPhase::PickingLayer => self.render_pipeline_picking_layer,
+            _ => self.render_pipeline_opaque,
+        })?;
+        pass.set_pipeline(pipeline);
+
+        for instance in instances {
+            pass.set_bind_group(1, &instance.bind_group, &[]);
+            pass.draw(0..36, 0..1);
+        }
+
+        Ok(())
+    }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[
+            DrawPhase::Opaque,
+            DrawPhase::Transparent,
+            DrawPhase::OutlineMask,
+            DrawPhase::PickingLayer,
+        ]
+    }
+}
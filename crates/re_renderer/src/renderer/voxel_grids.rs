@@ -0,0 +1,441 @@
+//! Renderer for voxel grids, i.e. occupied cells of a regular grid drawn as color mapped cubes.
+//!
+//! Cells are uploaded once via [`crate::resource_managers::VoxelGridManager`].
+//!
+//! ## Implementation details
+//!
+//! All cells of a grid are drawn with a single instanced draw call of a cube,
+//! one instance per occupied cell.
+//! Faces pointing away from the camera and cells below the threshold are collapsed in the vertex shader,
+//! this is independent of the winding order which flips for mirroring transforms.
+
+use smallvec::smallvec;
+
+use crate::{
+    allocator::create_and_fill_uniform_buffer_batch,
+    include_file,
+    outlines::OutlineMaskProcessor,
+    picking_layer::PickingLayerProcessor,
+    resource_managers::{GpuVoxelGridHandle, ResourceManagerError, Texture2DCreationDesc},
+    view_builder::ViewBuilder,
+    wgpu_resources::{
+        BindGroupDesc, BindGroupEntry, BindGroupLayoutDesc, GpuBindGroup, GpuBindGroupLayoutHandle,
+        GpuBuffer, GpuRenderPipelineHandle, PipelineLayoutDesc, RenderPipelineDesc,
+        ShaderModuleDesc,
+    },
+    ColorMap, OutlineMaskPreference, PickingLayerId,
+};
+
+use super::{
+    DrawData, DrawPhase, FileResolver, FileSystem, RenderContext, Renderer, SharedRendererData,
+    WgpuResourcePools,
+};
+
+mod gpu_data {
+    use crate::wgpu_resources::VertexBufferLayout;
+
+    // - Keep in sync with mirror in voxel_grid.wgsl.
+    // - See `VoxelGridInstance` for documentation.
+    #[repr(C, align(256))]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct VoxelGridInfoUBO {
+        pub world_from_grid: crate::wgpu_buffer_types::Mat4,
+        pub world_from_grid_normal: crate::wgpu_buffer_types::Mat3,
+        pub colormap: crate::wgpu_buffer_types::UVec2RowPadded,
+
+        /// Threshold, opacity & padding.
+        pub params: [f32; 4],
+        pub outline_mask_id: crate::wgpu_buffer_types::UVec2RowPadded,
+        pub picking_layer_id: [u32; 4],
+
+        pub end_padding: [crate::wgpu_buffer_types::PaddingRow; 16 - 11],
+    }
+
+    pub fn cell_vertex_buffer_layout() -> VertexBufferLayout {
+        VertexBufferLayout {
+            array_stride: std::mem::size_of::<crate::resource_managers::VoxelCell>() as _,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: VertexBufferLayout::attributes_from_formats(
+                0,
+                [
+                    // Cell index
+                    wgpu::VertexFormat::Uint32x3,
+                    // Value
+                    wgpu::VertexFormat::Float32,
+                ]
+                .into_iter(),
+            ),
+        }
+    }
+}
+
+/// A voxel grid placed in the scene, see [`VoxelGridDrawData::new`].
+#[derive(Clone)]
+pub struct VoxelGridInstance {
+    pub gpu_voxel_grid: GpuVoxelGridHandle,
+
+    /// Maps cell indices to world space.
+    ///
+    /// The cell at index `[x, y, z]` spans from `[x, y, z]` to `[x + 1, y + 1, z + 1]`.
+    /// Needs to be invertible.
+    pub world_from_grid: glam::Mat4,
+
+    /// Configures color mapping mode.
+    pub colormap: ColorMap,
+
+    /// Custom color lookup table of evenly spaced sRGB colors, used instead of [`Self::colormap`].
+    ///
+    /// Falls back to [`Self::colormap`] if empty or longer than [`VoxelGridInstance::MAX_COLORMAP_LUT_SIZE`].
+    pub colormap_lut: Option<std::sync::Arc<[[u8; 4]]>>,
+
+    /// Cells with a (normalized) value below the threshold are not drawn.
+    pub threshold: f32,
+
+    /// Opacity of all cells, in `[0, 1]`.
+    ///
+    /// Grids with an opacity below 1.0 are drawn in [`DrawPhase::Transparent`].
+    pub opacity: f32,
+
+    /// Whether and with which ids this grid is drawn into the outline mask.
+    pub outline_mask_ids: OutlineMaskPreference,
+
+    /// Picking id written for every pixel covered by the grid.
+    pub picking_layer_id: PickingLayerId,
+}
+
+impl VoxelGridInstance {
+    /// Maximum number of colors in [`Self::colormap_lut`].
+    pub const MAX_COLORMAP_LUT_SIZE: usize = 2048;
+
+    fn valid_colormap_lut(&self) -> Option<&[[u8; 4]]> {
+        let lut = self.colormap_lut.as_deref()?;
+        if lut.is_empty() || lut.len() > Self::MAX_COLORMAP_LUT_SIZE {
+            re_log::warn_once!(
+                "Voxel grid color lookup tables need between 1 and {} colors, got {}",
+                Self::MAX_COLORMAP_LUT_SIZE,
+                lut.len()
+            );
+            None
+        } else {
+            Some(lut)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct VoxelGridDrawInstance {
+    bind_group: GpuBindGroup,
+    cell_buffer: GpuBuffer,
+    num_cells: u32,
+    transparent: bool,
+    render_outline_mask: bool,
+}
+
+#[derive(Clone)]
+pub struct VoxelGridDrawData {
+    instances: Vec<VoxelGridDrawInstance>,
+}
+
+impl DrawData for VoxelGridDrawData {
+    type Renderer = VoxelGridRenderer;
+
+    fn has_transparent(&self) -> bool {
+        self.instances.iter().any(|instance| instance.transparent)
+    }
+}
+
+impl VoxelGridDrawData {
+    pub fn new(
+        ctx: &mut RenderContext,
+        instances: &[VoxelGridInstance],
+    ) -> Result<Self, ResourceManagerError> {
+        crate::profile_function!();
+
+        let bind_group_layout = ctx
+            .renderers
+            .write()
+            .get_or_create::<_, VoxelGridRenderer>(
+                &ctx.shared_renderer_data,
+                &mut ctx.gpu_resources,
+                &ctx.device,
+                &mut ctx.resolver,
+            )
+            .bind_group_layout;
+
+        if instances.is_empty() {
+            return Ok(VoxelGridDrawData {
+                instances: Vec::new(),
+            });
+        }
+
+        let voxel_grids = {
+            let voxel_grid_manager = ctx.voxel_grid_manager.read();
+            instances
+                .iter()
+                .map(|instance| voxel_grid_manager.get(&instance.gpu_voxel_grid).cloned())
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let voxel_grid_ubos = create_and_fill_uniform_buffer_batch(
+            ctx,
+            "voxel_grid_ubos".into(),
+            instances.iter().map(|instance| gpu_data::VoxelGridInfoUBO {
+                world_from_grid: instance.world_from_grid.into(),
+                world_from_grid_normal: glam::Mat3::from_mat4(instance.world_from_grid)
+                    .inverse()
+                    .transpose()
+                    .into(),
+                colormap: [
+                    instance.colormap as u32,
+                    instance.valid_colormap_lut().is_some() as u32,
+                ]
+                .into(),
+                params: [
+                    instance.threshold,
+                    instance.opacity.clamp(0.0, 1.0),
+                    0.0,
+                    0.0,
+                ],
+                outline_mask_id: instance.outline_mask_ids.ids().into(),
+                picking_layer_id: instance.picking_layer_id.into(),
+                end_padding: Default::default(),
+            }),
+        );
+
+        let mut draw_instances = Vec::with_capacity(instances.len());
+        for ((instance, voxel_grid), ubo) in instances
+            .iter()
+            .zip(voxel_grids.into_iter())
+            .zip(voxel_grid_ubos.into_iter())
+        {
+            let colormap_lut = match instance.valid_colormap_lut() {
+                Some(lut) => ctx.texture_manager_2d.create(
+                    &mut ctx.gpu_resources.textures,
+                    &Texture2DCreationDesc {
+                        label: "voxel_grid_colormap_lut".into(),
+                        data: bytemuck::cast_slice(lut),
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        width: lut.len() as u32,
+                        height: 1,
+                    },
+                ),
+                None => ctx.texture_manager_2d.white_texture_handle().clone(),
+            };
+            let colormap_lut = ctx.texture_manager_2d.get(&colormap_lut)?;
+
+            draw_instances.push(VoxelGridDrawInstance {
+                bind_group: ctx.gpu_resources.bind_groups.alloc(
+                    &ctx.device,
+                    &ctx.gpu_resources,
+                    &BindGroupDesc {
+                        label: "voxel_grid_bg".into(),
+                        entries: smallvec![
+                            ubo,
+                            BindGroupEntry::DefaultTextureView(colormap_lut.handle),
+                        ],
+                        layout: bind_group_layout,
+                    },
+                ),
+                cell_buffer: voxel_grid.cell_buffer,
+                num_cells: voxel_grid.num_cells,
+                transparent: instance.opacity < 1.0,
+                render_outline_mask: instance.outline_mask_ids.is_some(),
+            });
+        }
+
+        Ok(VoxelGridDrawData {
+            instances: draw_instances,
+        })
+    }
+}
+
+pub struct VoxelGridRenderer {
+    render_pipeline_opaque: GpuRenderPipelineHandle,
+    render_pipeline_transparent: GpuRenderPipelineHandle,
+    render_pipeline_outline_mask: GpuRenderPipelineHandle,
+    render_pipeline_picking_layer: GpuRenderPipelineHandle,
+    bind_group_layout: GpuBindGroupLayoutHandle,
+}
+
+impl Renderer for VoxelGridRenderer {
+    type RendererDrawData = VoxelGridDrawData;
+
+    fn create_renderer<Fs: FileSystem>(
+        shared_data: &SharedRendererData,
+        pools: &mut WgpuResourcePools,
+        device: &wgpu::Device,
+        resolver: &mut FileResolver<Fs>,
+    ) -> Self {
+        crate::profile_function!();
+
+        let bind_group_layout = pools.bind_group_layouts.get_or_create(
+            device,
+            &BindGroupLayoutDesc {
+                label: "voxel_grid_bg_layout".into(),
+                entries: vec![
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: (std::mem::size_of::<gpu_data::VoxelGridInfoUBO>()
+                                as u64)
+                                .try_into()
+                                .ok(),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                ],
+            },
+        );
+
+        let pipeline_layout = pools.pipeline_layouts.get_or_create(
+            device,
+            &PipelineLayoutDesc {
+                label: "voxel_grid_rp_layout".into(),
+                entries: vec![shared_data.global_bindings.layout, bind_group_layout],
+            },
+            &pools.bind_group_layouts,
+        );
+
+        let shader_module = pools.shader_modules.get_or_create(
+            device,
+            resolver,
+            &ShaderModuleDesc {
+                label: "voxel_grid".into(),
+                source: include_file!("../../shader/voxel_grid.wgsl"),
+            },
+        );
+
+        let render_pipeline_desc = RenderPipelineDesc {
+            label: "voxel_grid_rp - opaque".into(),
+            pipeline_layout,
+            vertex_entrypoint: "vs_main".into(),
+            vertex_handle: shader_module,
+            fragment_entrypoint: "fs_main".into(),
+            fragment_handle: shader_module,
+            vertex_buffers: smallvec![gpu_data::cell_vertex_buffer_layout()],
+            render_targets: smallvec![Some(ViewBuilder::MAIN_TARGET_COLOR_FORMAT.into())],
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                // Back faces are collapsed in the vertex shader, see module docs.
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: ViewBuilder::MAIN_TARGET_DEFAULT_DEPTH_STATE,
+            multisample: ViewBuilder::MAIN_TARGET_DEFAULT_MSAA_STATE,
+        };
+        let render_pipeline_opaque = pools.render_pipelines.get_or_create(
+            device,
+            &render_pipeline_desc,
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_transparent = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "voxel_grid_rp - transparent".into(),
+                fragment_entrypoint: "fs_main_transparent".into(),
+                render_targets: ViewBuilder::OIT_RENDER_TARGETS.into_iter().collect(),
+                depth_stencil: ViewBuilder::OIT_DEPTH_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_outline_mask = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "voxel_grid_rp - outline mask".into(),
+                fragment_entrypoint: "fs_main_outline_mask".into(),
+                render_targets: smallvec![Some(OutlineMaskProcessor::MASK_FORMAT.into())],
+                depth_stencil: OutlineMaskProcessor::MASK_DEPTH_STATE,
+                multisample: OutlineMaskProcessor::MASK_MSAA_STATE,
+                ..render_pipeline_desc.clone()
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+        let render_pipeline_picking_layer = pools.render_pipelines.get_or_create(
+            device,
+            &RenderPipelineDesc {
+                label: "voxel_grid_rp - picking layer".into(),
+                fragment_entrypoint: "fs_main_picking_layer".into(),
+                render_targets: smallvec![Some(PickingLayerProcessor::PICKING_LAYER_FORMAT.into())],
+                depth_stencil: PickingLayerProcessor::PICKING_LAYER_DEPTH_STATE,
+                multisample: PickingLayerProcessor::PICKING_LAYER_MSAA_STATE,
+                ..render_pipeline_desc
+            },
+            &pools.pipeline_layouts,
+            &pools.shader_modules,
+        );
+
+        VoxelGridRenderer {
+            render_pipeline_opaque,
+            render_pipeline_transparent,
+            render_pipeline_outline_mask,
+            render_pipeline_picking_layer,
+            bind_group_layout,
+        }
+    }
+
+    fn draw<'a>(
+        &self,
+        pools: &'a WgpuResourcePools,
+        phase: DrawPhase,
+        pass: &mut wgpu::RenderPass<'a>,
+        draw_data: &'a Self::RendererDrawData,
+    ) -> anyhow::Result<()> {
+        crate::profile_function!();
+
+        let mut instances = draw_data
+            .instances
+            .iter()
+            .filter(|instance| match phase {
+                DrawPhase::Opaque => !instance.transparent,
+                DrawPhase::Transparent => instance.transparent,
+                DrawPhase::OutlineMask => instance.render_outline_mask,
+                _ => true,
+            })
+            .peekable();
+        if instances.peek().is_none() {
+            return Ok(());
+        }
+
+        let pipeline = pools.render_pipelines.get_resource(match phase {
+            DrawPhase::Transparent => self.render_pipeline_transparent,
+            DrawPhase::OutlineMask => self.render_pipeline_outline_mask,
+            DrawPhase::PickingLayer => self.render_pipeline_picking_layer,
+            _ => self.render_pipeline_opaque,
+        })?;
+        pass.set_pipeline(pipeline);
+
+        for instance in instances {
+            pass.set_bind_group(1, &instance.bind_group, &[]);
+            pass.set_vertex_buffer(0, instance.cell_buffer.slice(..));
+            pass.draw(0..36, 0..instance.num_cells);
+        }
+
+        Ok(())
+    }
+
+    fn participated_phases() -> &'static [DrawPhase] {
+        &[
+            DrawPhase::Opaque,
+            DrawPhase::Transparent,
+            DrawPhase::OutlineMask,
+            DrawPhase::PickingLayer,
+        ]
+    }
+}
@@ -9,8 +9,23 @@
 mod mesh_manager;
 pub use mesh_manager::{GpuMeshHandle, MeshManager};
 
+mod point_cloud_manager;
+pub use point_cloud_manager::{
+    GpuPointCloudHandle, PointCloudCreationDesc, PointCloudCreationError, PointCloudManager,
+};
+
 mod texture_manager;
 pub use texture_manager::{GpuTexture2DHandle, Texture2DCreationDesc, TextureManager2D};
 
+mod volume_manager;
+pub use volume_manager::{
+    GpuVolumeHandle, VolumeCreationDesc, VolumeCreationError, VolumeData, VolumeManager,
+};
+
+mod voxel_grid_manager;
+pub use voxel_grid_manager::{
+    GpuVoxelGridHandle, VoxelCell, VoxelGridCreationDesc, VoxelGridCreationError, VoxelGridManager,
+};
+
 mod resource_manager;
 pub use resource_manager::{ResourceHandle, ResourceLifeTime, ResourceManagerError};
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::{
+    point_cloud_lod::PointCloudOctree,
+    renderer::{
+        create_static_data_textures, PointCloudDrawData, PointCloudPositionData, PointCloudRenderer,
+    },
+    wgpu_resources::{GpuBindGroup, GpuBindGroupLayoutHandle},
+    Color32, DebugLabel, PickingLayerInstanceId, RenderContext, Size,
+};
+
+use super::{
+    resource_manager::ResourceManager, ResourceHandle, ResourceLifeTime, ResourceManagerError,
+};
+
+slotmap::new_key_type! { pub struct PointCloudHandleInner; }
+
+pub type GpuPointCloudHandle = ResourceHandle<PointCloudHandleInner>;
+
+/// Data required to create a static point cloud resource.
+///
+/// It is *not* stored along side the resulting point cloud resource!
+/// All slices need to have the same length.
+pub struct PointCloudCreationDesc<'a> {
+    pub label: DebugLabel,
+    pub positions: &'a [glam::Vec3],
+    pub radii: &'a [Size],
+    pub colors: &'a [Color32],
+    pub picking_instance_ids: &'a [PickingLayerInstanceId],
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PointCloudCreationError {
+    #[error("Expected {num_positions} radii, colors and picking instance ids, got {num_radii}, {num_colors} and {num_picking_instance_ids}")]
+    DataLengthMismatch {
+        num_positions: usize,
+        num_radii: usize,
+        num_colors: usize,
+        num_picking_instance_ids: usize,
+    },
+}
+
+/// Point cloud whose data stays on the gpu, ordered for level of detail selection.
+#[derive(Clone)]
+pub(crate) struct GpuPointCloud {
+    pub label: DebugLabel,
+    pub octree: Arc<PointCloudOctree>,
+
+    /// Data textures for consecutive chunks of [`PointCloudDrawData::MAX_NUM_POINTS`] reordered points.
+    pub chunks: Vec<GpuBindGroup>,
+}
+
+/// Manager for point clouds that don't change from frame to frame.
+///
+/// In contrast to [`crate::PointCloudBuilder`], data is uploaded only once on creation.
+/// Points are sorted into a level of detail octree, which allows drawing point clouds
+/// far beyond what could be uploaded every frame.
+pub struct PointCloudManager {
+    manager: ResourceManager<PointCloudHandleInner, GpuPointCloud>,
+    bind_group_layout_all_points: GpuBindGroupLayoutHandle,
+}
+
+impl PointCloudManager {
+    pub(crate) fn new(point_cloud_renderer: &PointCloudRenderer) -> Self {
+        PointCloudManager {
+            manager: Default::default(),
+            bind_group_layout_all_points: point_cloud_renderer.bind_group_layout_all_points,
+        }
+    }
+
+    /// Builds the level of detail octree and uploads all point data.
+    ///
+    /// This is expensive for large point clouds, typically done once and kept around with [`ResourceLifeTime::LongLived`].
+    pub fn create(
+        &mut self,
+        ctx: &RenderContext,
+        desc: &PointCloudCreationDesc<'_>,
+        lifetime: ResourceLifeTime,
+    ) -> Result<GpuPointCloudHandle, PointCloudCreationError> {
+        crate::profile_function!();
+
+        let num_positions = desc.positions.len();
+        if desc.radii.len() != num_positions
+            || desc.colors.len() != num_positions
+            || desc.picking_instance_ids.len() != num_positions
+        {
+            return Err(PointCloudCreationError::DataLengthMismatch {
+                num_positions,
+                num_radii: desc.radii.len(),
+                num_colors: desc.colors.len(),
+                num_picking_instance_ids: desc.picking_instance_ids.len(),
+            });
+        }
+
+        let (octree, order) = PointCloudOctree::build(desc.positions);
+
+        let chunks = order
+            .chunks(PointCloudDrawData::MAX_NUM_POINTS)
+            .map(|chunk_order| {
+                crate::profile_scope!("upload_chunk");
+                let positions = chunk_order
+                    .iter()
+                    .map(|&i| PointCloudPositionData {
+                        pos: desc.positions[i as usize],
+                        radius: desc.radii[i as usize],
+                    })
+                    .collect::<Vec<_>>();
+                let colors = chunk_order
+                    .iter()
+                    .map(|&i| desc.colors[i as usize])
+                    .collect::<Vec<_>>();
+                let picking_instance_ids = chunk_order
+                    .iter()
+                    .map(|&i| desc.picking_instance_ids[i as usize])
+                    .collect::<Vec<_>>();
+
+                create_static_data_textures(
+                    ctx,
+                    self.bind_group_layout_all_points,
+                    &desc.label,
+                    &positions,
+                    &colors,
+                    &picking_instance_ids,
+                )
+            })
+            .collect();
+
+        Ok(self.manager.store_resource(
+            GpuPointCloud {
+                label: desc.label.clone(),
+                octree: Arc::new(octree),
+                chunks,
+            },
+            lifetime,
+        ))
+    }
+
+    /// Bounding box of all points of a point cloud, in object space.
+    pub fn bounding_box(
+        &self,
+        handle: &GpuPointCloudHandle,
+    ) -> Result<macaw::BoundingBox, ResourceManagerError> {
+        Ok(self.manager.get(handle)?.octree.bounding_box())
+    }
+
+    /// Accesses a given resource.
+    pub(crate) fn get(
+        &self,
+        handle: &GpuPointCloudHandle,
+    ) -> Result<&GpuPointCloud, ResourceManagerError> {
+        self.manager.get(handle)
+    }
+
+    pub(crate) fn begin_frame(&mut self, frame_index: u64) {
+        self.manager.begin_frame(frame_index);
+    }
+}
@@ -0,0 +1,181 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    wgpu_resources::{GpuTexture, TextureDesc},
+    DebugLabel, RenderContext,
+};
+
+use super::{
+    resource_manager::ResourceManager, ResourceHandle, ResourceLifeTime, ResourceManagerError,
+};
+
+slotmap::new_key_type! { pub struct VolumeHandleInner; }
+
+pub type GpuVolumeHandle = ResourceHandle<VolumeHandleInner>;
+
+/// Scalar values of a volume, x varying fastest, then y, then z.
+///
+/// Values are expected to be normalized to `[0, 1]`, i.e. they are uploaded as-is.
+#[derive(Clone, Copy)]
+pub enum VolumeData<'a> {
+    /// Uploaded as `R8Unorm`, i.e. mapped to `[0, 1]` on the gpu.
+    U8(&'a [u8]),
+
+    /// Uploaded as `R16Float`.
+    F16(&'a [half::f16]),
+}
+
+impl<'a> VolumeData<'a> {
+    fn len(&self) -> usize {
+        match self {
+            VolumeData::U8(data) => data.len(),
+            VolumeData::F16(data) => data.len(),
+        }
+    }
+
+    fn format(&self) -> wgpu::TextureFormat {
+        match self {
+            VolumeData::U8(_) => wgpu::TextureFormat::R8Unorm,
+            VolumeData::F16(_) => wgpu::TextureFormat::R16Float,
+        }
+    }
+
+    fn as_bytes(&self) -> &'a [u8] {
+        match self {
+            VolumeData::U8(data) => data,
+            VolumeData::F16(data) => bytemuck::cast_slice(data),
+        }
+    }
+}
+
+/// Data required to create a volume resource.
+///
+/// It is *not* stored along side the resulting volume resource!
+pub struct VolumeCreationDesc<'a> {
+    pub label: DebugLabel,
+
+    /// Number of voxels along x, y and z.
+    pub dimensions: glam::UVec3,
+    pub data: VolumeData<'a>,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum VolumeCreationError {
+    #[error("Expected {expected} voxels for dimensions {dimensions}, got {actual}")]
+    DataLengthMismatch {
+        dimensions: glam::UVec3,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("Volume dimensions {dimensions} exceed the maximum 3D texture size of {max_size}")]
+    TooLarge {
+        dimensions: glam::UVec3,
+        max_size: u32,
+    },
+}
+
+/// Volume whose data stays on the gpu, stored in a 3D texture.
+#[derive(Clone)]
+pub(crate) struct GpuVolume {
+    pub texture: GpuTexture,
+
+    /// Number of voxels along x, y and z.
+    pub dimensions: glam::UVec3,
+}
+
+/// Manager for volumes that don't change from frame to frame.
+///
+/// Volumes are typically large, so they are uploaded only once on creation.
+/// Drawing them is done with [`crate::renderer::VolumeDrawData`].
+#[derive(Default)]
+pub struct VolumeManager {
+    manager: ResourceManager<VolumeHandleInner, GpuVolume>,
+}
+
+impl VolumeManager {
+    /// Uploads all voxels to a 3D texture.
+    pub fn create(
+        &mut self,
+        ctx: &RenderContext,
+        desc: &VolumeCreationDesc<'_>,
+        lifetime: ResourceLifeTime,
+    ) -> Result<GpuVolumeHandle, VolumeCreationError> {
+        crate::profile_function!();
+
+        let dimensions = desc.dimensions;
+        let expected = dimensions.x as usize * dimensions.y as usize * dimensions.z as usize;
+        if desc.data.len() != expected || expected == 0 {
+            return Err(VolumeCreationError::DataLengthMismatch {
+                dimensions,
+                expected,
+                actual: desc.data.len(),
+            });
+        }
+
+        let max_size = ctx.device.limits().max_texture_dimension_3d;
+        if dimensions.max_element() > max_size {
+            return Err(VolumeCreationError::TooLarge {
+                dimensions,
+                max_size,
+            });
+        }
+
+        let size = wgpu::Extent3d {
+            width: dimensions.x,
+            height: dimensions.y,
+            depth_or_array_layers: dimensions.z,
+        };
+        let format = desc.data.format();
+        let texture = ctx.gpu_resources.textures.alloc(
+            &ctx.device,
+            &TextureDesc {
+                label: desc.label.clone().push_str(" - volume"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D3,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            },
+        );
+
+        let bytes_per_row = dimensions.x * format.describe().block_size as u32;
+
+        crate::profile_scope!("write_texture");
+        ctx.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            desc.data.as_bytes(),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(NonZeroU32::new(bytes_per_row).expect("invalid bytes per row")),
+                rows_per_image: Some(
+                    NonZeroU32::new(dimensions.y).expect("invalid rows per image"),
+                ),
+            },
+            size,
+        );
+
+        Ok(self.manager.store_resource(
+            GpuVolume {
+                texture,
+                dimensions,
+            },
+            lifetime,
+        ))
+    }
+
+    /// Accesses a given resource.
+    pub(crate) fn get(&self, handle: &GpuVolumeHandle) -> Result<&GpuVolume, ResourceManagerError> {
+        self.manager.get(handle)
+    }
+
+    pub(crate) fn begin_frame(&mut self, frame_index: u64) {
+        self.manager.begin_frame(frame_index);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    wgpu_resources::{BufferDesc, GpuBuffer},
+    DebugLabel, RenderContext,
+};
+
+use super::{
+    resource_manager::ResourceManager, ResourceHandle, ResourceLifeTime, ResourceManagerError,
+};
+
+slotmap::new_key_type! { pub struct VoxelGridHandleInner; }
+
+pub type GpuVoxelGridHandle = ResourceHandle<VoxelGridHandleInner>;
+
+/// An occupied cell of a voxel grid.
+///
+/// Keep in sync with `voxel_grid.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct VoxelCell {
+    /// Index of the cell along x, y and z.
+    pub index: [u32; 3],
+
+    /// Value of the cell, normalized to `[0, 1]` for color mapping.
+    pub value: f32,
+}
+
+/// Data required to create a voxel grid resource.
+///
+/// It is *not* stored along side the resulting voxel grid resource!
+pub struct VoxelGridCreationDesc<'a> {
+    pub label: DebugLabel,
+
+    /// Only the occupied cells, empty cells are never drawn.
+    pub cells: &'a [VoxelCell],
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum VoxelGridCreationError {
+    #[error("Voxel grid has no occupied cells")]
+    NoCells,
+}
+
+/// Occupied cells of a voxel grid that stay on the gpu.
+#[derive(Clone)]
+pub(crate) struct GpuVoxelGrid {
+    /// Instance-stepped vertex buffer of [`VoxelCell`].
+    pub cell_buffer: GpuBuffer,
+    pub num_cells: u32,
+}
+
+/// Manager for voxel grids that don't change from frame to frame.
+///
+/// The occupied cells are uploaded only once on creation.
+/// Drawing them is done with [`crate::renderer::VoxelGridDrawData`].
+#[derive(Default)]
+pub struct VoxelGridManager {
+    manager: ResourceManager<VoxelGridHandleInner, GpuVoxelGrid>,
+}
+
+impl VoxelGridManager {
+    /// Uploads all cells to a vertex buffer.
+    pub fn create(
+        &mut self,
+        ctx: &RenderContext,
+        desc: &VoxelGridCreationDesc<'_>,
+        lifetime: ResourceLifeTime,
+    ) -> Result<GpuVoxelGridHandle, VoxelGridCreationError> {
+        crate::profile_function!();
+
+        if desc.cells.is_empty() {
+            return Err(VoxelGridCreationError::NoCells);
+        }
+
+        let cell_buffer = ctx.gpu_resources.buffers.alloc(
+            &ctx.device,
+            &BufferDesc {
+                label: desc.label.clone().push_str(" - voxel cells"),
+                size: std::mem::size_of_val(desc.cells) as _,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+
+        crate::profile_scope!("write_buffer");
+        ctx.queue
+            .write_buffer(&cell_buffer, 0, bytemuck::cast_slice(desc.cells));
+
+        Ok(self.manager.store_resource(
+            GpuVoxelGrid {
+                cell_buffer,
+                num_cells: desc.cells.len() as u32,
+            },
+            lifetime,
+        ))
+    }
+
+    /// Accesses a given resource.
+    pub(crate) fn get(
+        &self,
+        handle: &GpuVoxelGridHandle,
+    ) -> Result<&GpuVoxelGrid, ResourceManagerError> {
+        self.manager.get(handle)
+    }
+
+    pub(crate) fn begin_frame(&mut self, frame_index: u64) {
+        self.manager.begin_frame(frame_index);
+    }
+}
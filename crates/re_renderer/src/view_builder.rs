@@ -3,10 +3,17 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 
 use crate::{
-    allocator::create_and_fill_uniform_buffer,
+    allocator::{create_and_fill_uniform_buffer, GpuReadbackIdentifier},
     context::RenderContext,
     global_bindings::FrameUniformBuffer,
-    renderer::{compositor::CompositorDrawData, DrawData, DrawPhase, Renderer},
+    lighting::{LightingConfig, ShadowMapProcessor, ShadowProjection, SsaoProcessor},
+    outlines::{OutlineConfig, OutlineMaskProcessor},
+    picking_layer::{PickingLayerProcessor, PickingLayerRect},
+    point_cloud_lod::PointCloudLodView,
+    renderer::{
+        compositor::CompositorDrawData, oit_compositor::OitProcessor, DrawData, DrawPhase,
+        LabelView, Renderer,
+    },
     wgpu_resources::{GpuBindGroup, GpuTexture, TextureDesc},
     DebugLabel, Rgba, Size,
 };
@@ -25,6 +32,7 @@ struct QueuedDraw {
     draw_data: Box<dyn std::any::Any + std::marker::Send + std::marker::Sync>,
     renderer_name: &'static str,
     participated_phases: &'static [DrawPhase],
+    has_transparent: bool,
 }
 
 /// The highest level rendering block in `re_renderer`.
@@ -44,6 +52,19 @@ struct ViewTargetSetup {
     main_target_resolved: GpuTexture,
     depth_buffer: GpuTexture,
 
+    outline_mask_processor: Option<OutlineMaskProcessor>,
+    shadow_map_processor: Option<ShadowMapProcessor>,
+    ssao_processor: Option<SsaoProcessor>,
+
+    /// Set by [`ViewBuilder::schedule_picking_rect`].
+    picking_processor: Option<PickingLayerProcessor>,
+
+    /// Contents of the uniform buffer in `bind_group_0`, the picking layer derives its own from it.
+    frame_uniform_buffer_content: FrameUniformBuffer,
+
+    point_cloud_lod_view: PointCloudLodView,
+    label_view: LabelView,
+
     resolution_in_pixel: [u32; 2],
 }
 
@@ -141,6 +162,14 @@ pub struct TargetConfiguration {
 
     /// How [`Size::AUTO`] is interpreted.
     pub auto_size_config: AutoSizeConfig,
+
+    /// If set, objects with an [`crate::OutlineMaskPreference`] get outlined.
+    ///
+    /// Leave this at `None` if nothing is highlighted, this saves several full screen passes.
+    pub outline_config: Option<OutlineConfig>,
+
+    /// Key light, ambient term, shadows & ambient occlusion of shaded geometry.
+    pub lighting_config: LightingConfig,
 }
 
 impl Default for TargetConfiguration {
@@ -155,6 +184,8 @@ impl Default for TargetConfiguration {
             },
             pixels_from_point: 1.0,
             auto_size_config: Default::default(),
+            outline_config: None,
+            lighting_config: Default::default(),
         }
     }
 }
@@ -215,6 +246,67 @@ impl ViewBuilder {
             },
         });
 
+    /// Depth state for semi-transparent geometry in [`DrawPhase::Transparent`]:
+    /// Tests against the opaque geometry's depth, but doesn't write depth.
+    pub const OIT_DEPTH_STATE: Option<wgpu::DepthStencilState> = Some(wgpu::DepthStencilState {
+        format: Self::MAIN_TARGET_DEPTH_FORMAT,
+        depth_compare: wgpu::CompareFunction::Greater,
+        depth_write_enabled: false,
+        stencil: wgpu::StencilState {
+            front: wgpu::StencilFaceState::IGNORE,
+            back: wgpu::StencilFaceState::IGNORE,
+            read_mask: 0,
+            write_mask: 0,
+        },
+        bias: wgpu::DepthBiasState {
+            constant: 0,
+            slope_scale: 0.0,
+            clamp: 0.0,
+        },
+    });
+
+    /// Format of the weighted color & alpha sum of [`DrawPhase::Transparent`].
+    ///
+    /// Needs a float format since the weighted sums easily exceed 1.0.
+    pub const OIT_ACCUMULATION_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+    /// Format of the revealage (i.e. product of all `1 - alpha`) of [`DrawPhase::Transparent`].
+    pub const OIT_REVEALAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R16Float;
+
+    /// Render targets any [`wgpu::RenderPipeline`] drawing in [`DrawPhase::Transparent`] needs to use.
+    ///
+    /// Keep in sync with `OitOutput` in `utils/oit.wgsl`.
+    pub const OIT_RENDER_TARGETS: [Option<wgpu::ColorTargetState>; 2] = [
+        Some(wgpu::ColorTargetState {
+            format: Self::OIT_ACCUMULATION_FORMAT,
+            blend: Some(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            }),
+            write_mask: wgpu::ColorWrites::ALL,
+        }),
+        Some(wgpu::ColorTargetState {
+            format: Self::OIT_REVEALAGE_FORMAT,
+            blend: Some(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrc,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent::REPLACE,
+            }),
+            write_mask: wgpu::ColorWrites::RED,
+        }),
+    ];
+
     pub fn setup_view(
         &mut self,
         ctx: &mut RenderContext,
@@ -260,11 +352,33 @@ impl ViewBuilder {
             &TextureDesc {
                 label: format!("{:?} - depth buffer", config.name).into(),
                 format: Self::MAIN_TARGET_DEPTH_FORMAT,
+                // Screen space ambient occlusion & label occlusion culling read the depth buffer.
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
                 ..main_target_desc
             },
         );
 
-        self.queue_draw(&CompositorDrawData::new(ctx, &main_target_resolved));
+        // The targets of the transparent pass are only allocated while drawing, if needed.
+        OitProcessor::ensure_renderer(ctx);
+
+        let outline_mask_processor = config.outline_config.as_ref().map(|outline_config| {
+            OutlineMaskProcessor::new(
+                ctx,
+                outline_config,
+                &config.name,
+                config.resolution_in_pixel,
+            )
+        });
+
+        self.queue_draw(&CompositorDrawData::new(
+            ctx,
+            &main_target_resolved,
+            outline_mask_processor
+                .as_ref()
+                .map(|processor| processor.final_voronoi_texture())
+                .zip(config.outline_config.as_ref()),
+        ));
 
         let aspect_ratio =
             config.resolution_in_pixel[0] as f32 / config.resolution_in_pixel[1] as f32;
@@ -365,6 +479,15 @@ impl ViewBuilder {
         let camera_forward = -view_from_world.row(2).truncate();
         let projection_from_world = projection_from_view * view_from_world;
 
+        let point_cloud_lod_view = PointCloudLodView {
+            projection_from_world,
+            camera_position,
+            pixel_world_size_from_camera_distance,
+            is_orthographic: matches!(config.projection_from_view, Projection::Orthographic { .. }),
+            target_point_spacing: PointCloudLodView::DEFAULT_TARGET_POINT_SPACING,
+            point_budget: PointCloudLodView::DEFAULT_POINT_BUDGET,
+        };
+
         let auto_size_points = if config.auto_size_config.point_radius.is_auto() {
             Size::new_points(2.5)
         } else {
@@ -380,45 +503,146 @@ impl ViewBuilder {
         // Factor applied to depth offsets.
         let depth_offset_factor = 1.0e-08; // Value determined by experimentation. Quite close to the f32 machine epsilon but a bit lower.
 
+        let lighting = &config.lighting_config;
+        let shadow_projection = lighting.shadows.as_ref().map(|shadows| {
+            (
+                shadows,
+                ShadowProjection::new(shadows, lighting.key_light_direction),
+            )
+        });
+        let (shadow_uv_from_world, shadow_map_texel_size, shadow_normal_offset) =
+            match &shadow_projection {
+                Some((shadows, projection)) => {
+                    let resolution = shadows.resolution.max(1) as f32;
+                    let texel_world_size =
+                        2.0 / projection.projection_from_view.y_axis.y / resolution;
+                    (
+                        projection.shadow_uv_from_world(),
+                        1.0 / resolution,
+                        // Value determined by experimentation, enough to avoid self shadowing on slanted surfaces.
+                        texel_world_size * 1.5,
+                    )
+                }
+                None => (glam::Mat4::IDENTITY, 0.0, 0.0),
+            };
+        let key_light_color = lighting.key_light_color;
+        let key_light_radiance = glam::vec3(
+            key_light_color.r(),
+            key_light_color.g(),
+            key_light_color.b(),
+        ) * lighting.key_light_intensity;
+
         // Setup frame uniform buffer
+        let frame_uniform_buffer_content = FrameUniformBuffer {
+            view_from_world: glam::Affine3A::from_mat4(view_from_world).into(),
+            projection_from_view: projection_from_view.into(),
+            projection_from_world: projection_from_world.into(),
+            camera_position,
+            camera_forward,
+            tan_half_fov: tan_half_fov.into(),
+            pixel_world_size_from_camera_distance,
+            pixels_from_point: config.pixels_from_point,
+
+            auto_size_points: auto_size_points.0,
+            auto_size_lines: auto_size_lines.0,
+
+            depth_offset_factor: depth_offset_factor.into(),
+
+            key_light_direction: lighting.key_light_direction.normalize_or_zero(),
+            ambient_intensity: lighting.ambient_intensity,
+            key_light_radiance,
+            shadow_normal_offset,
+            shadow_uv_from_world: shadow_uv_from_world.into(),
+            shadow_map_texel_size: shadow_map_texel_size.into(),
+
+            end_padding: Default::default(),
+        };
         let frame_uniform_buffer = create_and_fill_uniform_buffer(
             ctx,
             format!("{:?} - frame uniform buffer", config.name).into(),
-            FrameUniformBuffer {
-                view_from_world: glam::Affine3A::from_mat4(view_from_world).into(),
-                projection_from_view: projection_from_view.into(),
-                projection_from_world: projection_from_world.into(),
-                camera_position,
-                camera_forward,
-                tan_half_fov: tan_half_fov.into(),
-                pixel_world_size_from_camera_distance,
-                pixels_from_point: config.pixels_from_point,
-
-                auto_size_points: auto_size_points.0,
-                auto_size_lines: auto_size_lines.0,
-
-                depth_offset_factor: depth_offset_factor.into(),
-            },
+            frame_uniform_buffer_content,
         );
 
+        let shadow_map_processor = shadow_projection.map(|(shadows, projection)| {
+            ShadowMapProcessor::new(
+                ctx,
+                shadows,
+                &projection,
+                &config.name,
+                &frame_uniform_buffer_content,
+            )
+        });
+        let ssao_processor = lighting.ssao.as_ref().map(|ssao| {
+            SsaoProcessor::new(
+                ctx,
+                ssao,
+                &config.name,
+                config.resolution_in_pixel,
+                &depth_buffer,
+                projection_from_view,
+            )
+        });
+
         let bind_group_0 = ctx.shared_renderer_data.global_bindings.create_bind_group(
             &mut ctx.gpu_resources,
             &ctx.device,
             frame_uniform_buffer,
+            shadow_map_processor
+                .as_ref()
+                .map(|processor| processor.shadow_map()),
         );
 
+        let label_view = LabelView {
+            projection_from_world,
+            camera_position,
+            camera_forward,
+            pixel_world_size_from_camera_distance,
+            is_orthographic: point_cloud_lod_view.is_orthographic,
+            pixels_from_point: config.pixels_from_point,
+            resolution_in_pixel: config.resolution_in_pixel,
+            avoid_collisions: true,
+            depth_buffer: depth_buffer.clone(),
+        };
+
         self.setup = Some(ViewTargetSetup {
             name: config.name,
             bind_group_0,
             main_target_msaa: hdr_render_target_msaa,
             main_target_resolved,
             depth_buffer,
+            outline_mask_processor,
+            shadow_map_processor,
+            ssao_processor,
+            picking_processor: None,
+            frame_uniform_buffer_content,
+            point_cloud_lod_view,
+            label_view,
             resolution_in_pixel: config.resolution_in_pixel,
         });
 
         Ok(self)
     }
 
+    /// View dependent parameters for drawing static point clouds via [`crate::renderer::PointCloudDrawData::new_static`].
+    ///
+    /// Uses the default point spacing and budget, adjust them on the returned value as needed.
+    pub fn point_cloud_lod_view(&self) -> anyhow::Result<PointCloudLodView> {
+        self.setup
+            .as_ref()
+            .map(|setup| setup.point_cloud_lod_view)
+            .context("ViewBuilder::setup_view wasn't called yet")
+    }
+
+    /// View dependent parameters for placing labels via [`crate::renderer::LabelDrawData::new`].
+    ///
+    /// Collision avoidance is enabled, disable it on the returned value as needed.
+    pub fn label_view(&self) -> anyhow::Result<LabelView> {
+        self.setup
+            .as_ref()
+            .map(|setup| setup.label_view.clone())
+            .context("ViewBuilder::setup_view wasn't called yet")
+    }
+
     fn draw_phase<'a>(
         &'a self,
         ctx: &'a RenderContext,
@@ -456,6 +680,7 @@ impl ViewBuilder {
             draw_data: Box::new(draw_data.clone()),
             renderer_name: std::any::type_name::<D::Renderer>(),
             participated_phases: D::Renderer::participated_phases(),
+            has_transparent: draw_data.has_transparent(),
         });
 
         self
@@ -480,6 +705,33 @@ impl ViewBuilder {
                 label: setup.name.clone().get(),
             });
 
+        let oit_processor = if self
+            .queued_draws
+            .iter()
+            .any(|queued_draw| queued_draw.has_transparent)
+        {
+            Some(OitProcessor::new(
+                ctx,
+                &setup.name,
+                setup.resolution_in_pixel,
+            )?)
+        } else {
+            None
+        };
+        let has_overlay = self.queued_draws.iter().any(|queued_draw| {
+            queued_draw
+                .participated_phases
+                .contains(&DrawPhase::Overlay)
+        });
+
+        if let Some(shadow_map_processor) = setup.shadow_map_processor.as_ref() {
+            crate::profile_scope!("view builder shadow map pass");
+
+            let mut pass = shadow_map_processor.begin_render_pass(&mut encoder);
+            pass.set_bind_group(0, &shadow_map_processor.bind_group_0, &[]);
+            self.draw_phase(ctx, DrawPhase::ShadowMap, &mut pass);
+        }
+
         {
             crate::profile_scope!("view builder main target pass");
 
@@ -504,9 +756,12 @@ impl ViewBuilder {
                     view: &setup.depth_buffer.default_view,
                     depth_ops: Some(wgpu::Operations {
                         load: wgpu::LoadOp::Clear(0.0), // 0.0 == far since we're using reverse-z
-                        // Don't care about depth results afterwards.
-                        // This can have be much better perf, especially on tiler gpus.
-                        store: false,
+                        // Only keep the depth if it's read afterwards: by the transparent pass,
+                        // ssao or overlays like labels.
+                        // Not storing it can be much better perf, especially on tiler gpus.
+                        store: oit_processor.is_some()
+                            || setup.ssao_processor.is_some()
+                            || has_overlay,
                     }),
                     stencil_ops: None,
                 }),
@@ -519,9 +774,113 @@ impl ViewBuilder {
             }
         }
 
+        if let Some(ssao_processor) = setup.ssao_processor.as_ref() {
+            crate::profile_scope!("view builder ssao passes");
+            ssao_processor.apply(
+                &ctx.gpu_resources,
+                &mut encoder,
+                &setup.bind_group_0,
+                &setup.main_target_resolved,
+            )?;
+        }
+
+        if let Some(oit_processor) = oit_processor.as_ref() {
+            crate::profile_scope!("view builder transparent passes");
+
+            {
+                let mut pass = oit_processor.begin_render_pass(&mut encoder, &setup.depth_buffer);
+                pass.set_bind_group(0, &setup.bind_group_0, &[]);
+                self.draw_phase(ctx, DrawPhase::Transparent, &mut pass);
+            }
+            oit_processor.composite(
+                ctx,
+                &mut encoder,
+                &setup.bind_group_0,
+                &setup.main_target_resolved,
+            )?;
+        }
+
+        if has_overlay {
+            crate::profile_scope!("view builder overlay pass");
+
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: DebugLabel::from(format!("{:?} - overlay pass", setup.name)).get(),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &setup.main_target_resolved.default_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                })],
+                // Overlays may read the depth buffer instead.
+                depth_stencil_attachment: None,
+            });
+
+            pass.set_bind_group(0, &setup.bind_group_0, &[]);
+            self.draw_phase(ctx, DrawPhase::Overlay, &mut pass);
+        }
+
+        if let Some(outline_mask_processor) = setup.outline_mask_processor.as_ref() {
+            crate::profile_scope!("view builder outline passes");
+
+            {
+                let mut pass = outline_mask_processor.start_mask_render_pass(&mut encoder);
+                pass.set_bind_group(0, &setup.bind_group_0, &[]);
+                self.draw_phase(ctx, DrawPhase::OutlineMask, &mut pass);
+            }
+            outline_mask_processor.compute_outlines(&ctx.gpu_resources, &mut encoder)?;
+        }
+
+        if let Some(picking_processor) = setup.picking_processor.as_ref() {
+            crate::profile_scope!("view builder picking layer pass");
+
+            {
+                let mut pass = picking_processor.begin_render_pass(&mut encoder);
+                pass.set_bind_group(0, &picking_processor.bind_group_0, &[]);
+                self.draw_phase(ctx, DrawPhase::PickingLayer, &mut pass);
+            }
+            picking_processor.end_render_pass(&mut encoder);
+        }
+
         Ok(encoder.finish())
     }
 
+    /// Draws the given rectangle of the view to the picking layer and reads it back to the CPU.
+    ///
+    /// The rectangle is given in pixels of the view's target and may be partially outside of the view.
+    /// The result arrives a few frames later, retrieve it with [`PickingLayerProcessor::next_readback_result`]
+    /// using the same `readback_identifier`.
+    /// `readback_user_data` is handed back along with the result.
+    ///
+    /// Only one picking rectangle can be scheduled per view, scheduling again replaces the previous one.
+    pub fn schedule_picking_rect<T: 'static + Send + Sync>(
+        &mut self,
+        ctx: &mut RenderContext,
+        picking_rect: PickingLayerRect,
+        readback_identifier: GpuReadbackIdentifier,
+        readback_user_data: T,
+    ) -> anyhow::Result<()> {
+        crate::profile_function!();
+
+        let setup = self
+            .setup
+            .as_mut()
+            .context("ViewBuilder::setup_view wasn't called yet")?;
+
+        setup.picking_processor = Some(PickingLayerProcessor::new(
+            ctx,
+            &setup.name,
+            glam::UVec2::from(setup.resolution_in_pixel),
+            picking_rect,
+            &setup.frame_uniform_buffer_content,
+            readback_identifier,
+            readback_user_data,
+        ));
+
+        Ok(())
+    }
+
     /// Composites the final result of a `ViewBuilder` to a given output `RenderPass`.
     ///
     /// The bound surface(s) on the `RenderPass` are expected to be the same format as specified on `Context` creation.
@@ -47,6 +47,39 @@ impl From<u32> for U32RowPadded {
     }
 }
 
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Zeroable, Pod)]
+pub struct UVec2RowPadded {
+    pub x: u32,
+    pub y: u32,
+    pub padding0: u32,
+    pub padding1: u32,
+}
+
+impl From<[u32; 2]> for UVec2RowPadded {
+    #[inline]
+    fn from(v: [u32; 2]) -> Self {
+        UVec2RowPadded {
+            x: v[0],
+            y: v[1],
+            padding0: 0,
+            padding1: 0,
+        }
+    }
+}
+
+impl From<[u8; 2]> for UVec2RowPadded {
+    #[inline]
+    fn from(v: [u8; 2]) -> Self {
+        UVec2RowPadded {
+            x: v[0] as u32,
+            y: v[1] as u32,
+            padding0: 0,
+            padding1: 0,
+        }
+    }
+}
+
 #[repr(C, align(8))]
 #[derive(Clone, Copy, Zeroable, Pod)]
 pub struct Vec2 {
@@ -218,6 +251,13 @@ impl From<glam::Mat4> for Mat4 {
     }
 }
 
+impl From<Mat4> for glam::Mat4 {
+    #[inline]
+    fn from(m: Mat4) -> Self {
+        glam::Mat4::from_cols_array(&bytemuck::cast(m))
+    }
+}
+
 #[repr(C, align(16))]
 #[derive(Clone, Copy, Zeroable, Pod)]
 pub struct Mat4x3 {
@@ -36,6 +36,9 @@ pub struct SamplerDesc {
 
     /// Valid values: 1, 2, 4, 8, and 16.
     pub anisotropy_clamp: Option<NonZeroU8>,
+
+    /// If set, this is a comparison sampler using the given function.
+    pub compare: Option<wgpu::CompareFunction>,
 }
 
 #[derive(Default)]
@@ -57,9 +60,9 @@ impl GpuSamplerPool {
                 lod_min_clamp: desc.lod_min_clamp.into(),
                 lod_max_clamp: desc.lod_max_clamp.into(),
                 anisotropy_clamp: desc.anisotropy_clamp,
+                compare: desc.compare,
 
                 // Unsupported
-                compare: None,
                 border_color: None,
             })
         })
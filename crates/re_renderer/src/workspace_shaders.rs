@@ -49,6 +49,24 @@ pub fn init() {
         fs.create_file(virtpath, content).unwrap();
     }
 
+    {
+        let virtpath = Path::new("shader/labels.wgsl");
+        let content = include_str!("../shader/labels.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
+    {
+        let virtpath = Path::new("shader/lighting/ssao.wgsl");
+        let content = include_str!("../shader/lighting/ssao.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
+    {
+        let virtpath = Path::new("shader/lighting/ssao_apply.wgsl");
+        let content = include_str!("../shader/lighting/ssao_apply.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
     {
         let virtpath = Path::new("shader/lines.wgsl");
         let content = include_str!("../shader/lines.wgsl").into();
@@ -61,6 +79,30 @@ pub fn init() {
         fs.create_file(virtpath, content).unwrap();
     }
 
+    {
+        let virtpath = Path::new("shader/oit_composite.wgsl");
+        let content = include_str!("../shader/oit_composite.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
+    {
+        let virtpath = Path::new("shader/outlines/jumpflooding_init.wgsl");
+        let content = include_str!("../shader/outlines/jumpflooding_init.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
+    {
+        let virtpath = Path::new("shader/outlines/jumpflooding_step.wgsl");
+        let content = include_str!("../shader/outlines/jumpflooding_step.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
+    {
+        let virtpath = Path::new("shader/outlines/outlines_from_voronoi.wgsl");
+        let content = include_str!("../shader/outlines/outlines_from_voronoi.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
     {
         let virtpath = Path::new("shader/point_cloud.wgsl");
         let content = include_str!("../shader/point_cloud.wgsl").into();
@@ -115,6 +157,18 @@ pub fn init() {
         fs.create_file(virtpath, content).unwrap();
     }
 
+    {
+        let virtpath = Path::new("shader/utils/lighting.wgsl");
+        let content = include_str!("../shader/utils/lighting.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
+    {
+        let virtpath = Path::new("shader/utils/oit.wgsl");
+        let content = include_str!("../shader/utils/oit.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
     {
         let virtpath = Path::new("shader/utils/quaternion.wgsl");
         let content = include_str!("../shader/utils/quaternion.wgsl").into();
@@ -138,4 +192,16 @@ pub fn init() {
         let content = include_str!("../shader/utils/srgb.wgsl").into();
         fs.create_file(virtpath, content).unwrap();
     }
+
+    {
+        let virtpath = Path::new("shader/volume.wgsl");
+        let content = include_str!("../shader/volume.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
+
+    {
+        let virtpath = Path::new("shader/voxel_grid.wgsl");
+        let content = include_str!("../shader/voxel_grid.wgsl").into();
+        fs.create_file(virtpath, content).unwrap();
+    }
 }
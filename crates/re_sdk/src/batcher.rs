@@ -0,0 +1,365 @@
+//! Client-side batching of [`MsgBundle`]s.
+//!
+//! Sending one arrow message per logged row is very wasteful when logging at high frequency
+//! (e.g. IMU data at 1kHz): most of the cost is in the per-message overhead of serialization,
+//! transport and insertion, rather than in the data itself.
+//!
+//! The [`Batcher`] instead accumulates consecutive bundles for the same entity, timelines and
+//! components, and concatenates them into larger multi-row bundles once any of the thresholds in
+//! [`BatcherConfig`] are reached.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use arrow2::compute::aggregate::estimated_bytes_size;
+
+use re_log_types::{msg_bundle::MsgBundle, ComponentName, EntityPath, Timeline};
+
+// ---
+
+/// Defines when the [`crate::Session`] flushes its pending batches.
+///
+/// Batches are flushed as soon as _any_ of the thresholds is reached, as well as on every call
+/// to [`crate::Session::flush`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatcherConfig {
+    /// Flush when the pending data reaches this size, in bytes.
+    ///
+    /// This is an estimate of the size of the arrow data, not of the encoded messages.
+    pub flush_num_bytes: u64,
+
+    /// Flush when this many rows are pending, across all entities.
+    pub flush_num_rows: u64,
+
+    /// Flush when the oldest pending row has been waiting for this long.
+    ///
+    /// The [`crate::Session`] keeps a thread around to enforce this, so that pending data gets
+    /// sent even if nothing else is logged.
+    pub flush_tick: Duration,
+}
+
+impl Default for BatcherConfig {
+    fn default() -> Self {
+        Self {
+            flush_num_bytes: 1024 * 1024,
+            flush_num_rows: 1024,
+            flush_tick: Duration::from_millis(50),
+        }
+    }
+}
+
+impl BatcherConfig {
+    /// Never batch anything: every message is sent as soon as it is logged.
+    pub const ALWAYS: Self = Self {
+        flush_num_bytes: 0,
+        flush_num_rows: 0,
+        flush_tick: Duration::ZERO,
+    };
+
+    /// Is batching effectively turned off?
+    pub fn is_always(&self) -> bool {
+        self.flush_num_bytes == 0 || self.flush_num_rows <= 1 || self.flush_tick.is_zero()
+    }
+}
+
+// ---
+
+/// Rows that will end up in the same multi-row [`MsgBundle`].
+///
+/// Bundles can only be concatenated if they share the same entity and the same set of
+/// components, and we also keep different sets of timelines apart so that the rows of a batch
+/// are always indexed the same way.
+type BatchKey = (EntityPath, Vec<Timeline>, Vec<ComponentName>);
+
+fn batch_key(bundle: &MsgBundle) -> BatchKey {
+    let mut timelines = bundle
+        .time_points
+        .iter()
+        .flat_map(|time_point| time_point.timelines().copied())
+        .collect::<Vec<_>>();
+    timelines.sort();
+    timelines.dedup();
+
+    let mut component_names = bundle
+        .components
+        .iter()
+        .map(|component| component.name())
+        .collect::<Vec<_>>();
+    component_names.sort();
+
+    (bundle.entity_path.clone(), timelines, component_names)
+}
+
+fn bundle_size_bytes(bundle: &MsgBundle) -> u64 {
+    bundle
+        .components
+        .iter()
+        .map(|component| estimated_bytes_size(component.value_list()) as u64)
+        .sum()
+}
+
+/// Sorts [`MsgBundle`]s into batches that can be concatenated, without reordering the bundles of
+/// any one entity.
+///
+/// Each entity has at most one open batch: a bundle with a different [`BatchKey`] closes it and
+/// opens a new one. Bundles of the same entity thus never overtake each other, e.g. the primary
+/// component of a row still comes after its splats (see #1215).
+#[derive(Default)]
+pub(crate) struct BatchQueue {
+    /// All batches, in the order in which they were opened.
+    batches: Vec<Vec<MsgBundle>>,
+    open_batch_per_entity: HashMap<EntityPath, (BatchKey, usize)>,
+}
+
+impl BatchQueue {
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    pub fn push(&mut self, bundle: MsgBundle) {
+        let key = batch_key(&bundle);
+        let batch_idx = match self.open_batch_per_entity.get(&bundle.entity_path) {
+            Some((open_key, batch_idx)) if *open_key == key => *batch_idx,
+            _ => {
+                self.batches.push(Vec::new());
+                let batch_idx = self.batches.len() - 1;
+                self.open_batch_per_entity
+                    .insert(bundle.entity_path.clone(), (key, batch_idx));
+                batch_idx
+            }
+        };
+        self.batches[batch_idx].push(bundle);
+    }
+
+    /// All batches, in the order in which they were opened, leaving the queue empty.
+    pub fn take(&mut self) -> Vec<Vec<MsgBundle>> {
+        self.open_batch_per_entity.clear();
+        std::mem::take(&mut self.batches)
+    }
+}
+
+/// Accumulates [`MsgBundle`]s until they need to be flushed, see [`BatcherConfig`].
+#[derive(Default)]
+pub(crate) struct Batcher {
+    config: BatcherConfig,
+
+    /// Pending batches.
+    queue: BatchQueue,
+
+    num_bytes: u64,
+    num_rows: u64,
+
+    /// When the oldest pending row was pushed.
+    oldest: Option<Instant>,
+}
+
+impl Batcher {
+    pub fn config(&self) -> BatcherConfig {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: BatcherConfig) {
+        self.config = config;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Queues up a bundle.
+    ///
+    /// Returns `true` if any of the flush thresholds has been reached.
+    pub fn push(&mut self, bundle: MsgBundle) -> bool {
+        self.num_bytes += bundle_size_bytes(&bundle);
+        self.num_rows += bundle.num_rows() as u64;
+        self.oldest.get_or_insert_with(Instant::now);
+
+        self.queue.push(bundle);
+
+        self.should_flush()
+    }
+
+    /// Has any of the flush thresholds been reached?
+    pub fn should_flush(&self) -> bool {
+        let Some(oldest) = self.oldest else { return false; };
+        self.config.is_always()
+            || self.num_bytes >= self.config.flush_num_bytes
+            || self.num_rows >= self.config.flush_num_rows
+            || oldest.elapsed() >= self.config.flush_tick
+    }
+
+    /// How long until [`Self::should_flush`] turns true on its own, `None` if nothing is pending.
+    pub fn time_until_flush(&self) -> Option<Duration> {
+        let oldest = self.oldest?;
+        if self.should_flush() {
+            Some(Duration::ZERO)
+        } else {
+            Some(self.config.flush_tick.saturating_sub(oldest.elapsed()))
+        }
+    }
+
+    /// Concatenates all pending batches, leaving the batcher empty.
+    ///
+    /// The bundles of each entity come out in the order in which they were pushed.
+    pub fn flush(&mut self) -> Vec<MsgBundle> {
+        self.num_bytes = 0;
+        self.num_rows = 0;
+        self.oldest = None;
+
+        let batches = self.queue.take();
+        let mut bundles = Vec::with_capacity(batches.len());
+        for batch in batches {
+            if batch.len() == 1 {
+                bundles.extend(batch);
+                continue;
+            }
+
+            match MsgBundle::try_concat(&batch) {
+                Ok(bundle) => bundles.push(bundle),
+                Err(err) => {
+                    // Most likely the same component was logged with different datatypes:
+                    // send the rows as they were instead.
+                    re_log::warn_once!("Failed to batch messages: {err}");
+                    bundles.extend(batch);
+                }
+            }
+        }
+        bundles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::components::{Label, Point2D};
+    use crate::time::{TimeType, Timeline};
+    use crate::{Component as _, MsgSender};
+
+    fn bundles(sender: MsgSender) -> Vec<MsgBundle> {
+        MsgSender::into_batched_messages([sender]).unwrap()
+    }
+
+    #[test]
+    fn batching() {
+        let timeline = Timeline::new("frame_nr", TimeType::Sequence);
+
+        let mut batcher = Batcher::default();
+        batcher.set_config(BatcherConfig {
+            flush_num_rows: 4,
+            flush_tick: Duration::MAX,
+            ..Default::default()
+        });
+
+        for frame_nr in 0..3 {
+            let sender = MsgSender::new("points")
+                .with_time(timeline, frame_nr)
+                .with_component(&[Point2D::new(1.0, 2.0)])
+                .unwrap();
+            for bundle in bundles(sender) {
+                assert!(!batcher.push(bundle));
+            }
+        }
+
+        // Different components: ends up in a separate batch.
+        let sender = MsgSender::new("points")
+            .with_time(timeline, 3)
+            .with_component(&[Label("label".into())])
+            .unwrap();
+        for bundle in bundles(sender) {
+            assert!(batcher.push(bundle));
+        }
+
+        let flushed = batcher.flush();
+        assert!(batcher.is_empty());
+        assert_eq!(2, flushed.len());
+        assert_eq!(3, flushed[0].num_rows());
+        assert_eq!(3, flushed[0].time_points.len());
+        assert_eq!(1, flushed[1].num_rows());
+    }
+
+    #[test]
+    fn keeps_order_per_entity() {
+        let timeline = Timeline::new("frame_nr", TimeType::Sequence);
+
+        let mut batcher = Batcher::default();
+        batcher.set_config(BatcherConfig {
+            flush_tick: Duration::MAX,
+            ..Default::default()
+        });
+
+        // Frame 1 only logs the primary component, frame 2 splats as well.
+        let frame_1 = MsgSender::new("points")
+            .with_time(timeline, 1)
+            .with_component(&[Point2D::new(1.0, 2.0)])
+            .unwrap();
+        let frame_2 = MsgSender::new("points")
+            .with_time(timeline, 2)
+            .with_component(&[Point2D::new(3.0, 4.0)])
+            .unwrap()
+            .with_splat(Label("label".into()))
+            .unwrap();
+        for sender in [frame_1, frame_2] {
+            for bundle in bundles(sender) {
+                batcher.push(bundle);
+            }
+        }
+
+        let flushed = batcher.flush();
+        let frames = flushed
+            .iter()
+            .map(|bundle| {
+                let has_primary = bundle
+                    .components
+                    .iter()
+                    .any(|component| component.name() == Point2D::name());
+                let frame_nr = bundle.time_points[0].get(&timeline).unwrap().as_i64();
+                (frame_nr, has_primary)
+            })
+            .collect::<Vec<_>>();
+        // The primary component of frame 2 still comes after its splats.
+        assert_eq!(vec![(1, true), (2, false), (2, true)], frames);
+    }
+
+    #[test]
+    fn time_until_flush() {
+        let mut batcher = Batcher::default();
+        batcher.set_config(BatcherConfig {
+            flush_tick: Duration::from_secs(3600),
+            ..Default::default()
+        });
+        assert_eq!(None, batcher.time_until_flush());
+
+        let sender = MsgSender::new("points")
+            .with_component(&[Point2D::new(1.0, 2.0)])
+            .unwrap();
+        for bundle in bundles(sender) {
+            assert!(!batcher.push(bundle));
+        }
+        let remaining = batcher.time_until_flush().unwrap();
+        assert!(Duration::ZERO < remaining && remaining <= Duration::from_secs(3600));
+
+        batcher.set_config(BatcherConfig {
+            flush_tick: Duration::ZERO,
+            ..Default::default()
+        });
+        assert_eq!(Some(Duration::ZERO), batcher.time_until_flush());
+
+        batcher.flush();
+        assert_eq!(None, batcher.time_until_flush());
+    }
+
+    #[test]
+    fn always() {
+        let mut batcher = Batcher::default();
+        batcher.set_config(BatcherConfig::ALWAYS);
+
+        let sender = MsgSender::new("points")
+            .with_component(&[Point2D::new(1.0, 2.0)])
+            .unwrap();
+        for bundle in bundles(sender) {
+            assert!(batcher.push(bundle));
+        }
+        assert_eq!(1, batcher.flush().len());
+    }
+}
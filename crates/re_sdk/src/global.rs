@@ -1,5 +1,10 @@
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+
 use crate::session::Session;
 
+static INSTANCE: OnceCell<Mutex<Session>> = OnceCell::new();
+
 /// Access a global [`Session`] singleton for convenient logging.
 ///
 /// By default, logging is enabled. To disable logging, call `set_enabled(false)` on the global `Session`, or
@@ -13,13 +18,42 @@ pub fn global_session() -> parking_lot::MutexGuard<'static, Session> {
 ///
 /// The given variable controls if Rerun is enabled by default.
 /// It can be overridden with the `RERUN` environment variable.
+///
+/// The global session is never dropped: whatever is still pending is sent when the process exits.
 pub fn global_session_with_default_enabled(
     default_enabled: bool,
 ) -> parking_lot::MutexGuard<'static, Session> {
-    use once_cell::sync::OnceCell;
-    use parking_lot::Mutex;
-    static INSTANCE: OnceCell<Mutex<Session>> = OnceCell::new();
-
-    let mutex = INSTANCE.get_or_init(|| Mutex::new(Session::with_default_enabled(default_enabled)));
+    let mutex = INSTANCE.get_or_init(|| {
+        flush_at_exit();
+        Mutex::new(Session::with_default_enabled(default_enabled))
+    });
     mutex.lock()
 }
+
+/// Flushes the global session when the process exits, since it is never dropped.
+#[cfg(not(target_arch = "wasm32"))]
+fn flush_at_exit() {
+    extern "C" fn flush_global_session() {
+        // Unwinding out of an `atexit` handler would abort.
+        std::panic::catch_unwind(|| {
+            let Some(mutex) = INSTANCE.get() else { return; };
+
+            // The session might be held by a thread that will never run again.
+            let Some(mut session) = mutex.try_lock_for(std::time::Duration::from_secs(1)) else {
+                re_log::warn!("Failed to flush the global session before exiting");
+                return;
+            };
+            session.drop_msgs_if_disconnected();
+            session.flush();
+        })
+        .ok();
+    }
+
+    // SAFETY: registering a plain function, that doesn't unwind.
+    if unsafe { libc::atexit(flush_global_session) } != 0 {
+        re_log::warn!("Failed to register the global session to be flushed on exit");
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn flush_at_exit() {}
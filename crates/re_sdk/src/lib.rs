@@ -7,6 +7,7 @@
 #![warn(missing_docs)] // Let's keep the this crate well-documented!
 
 // Send data to a rerun session
+mod batcher;
 #[cfg(not(target_arch = "wasm32"))]
 mod file_writer;
 mod global;
@@ -14,10 +15,15 @@ mod msg_sender;
 #[cfg(feature = "web_viewer")]
 mod remote_viewer_server;
 mod session;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
 
+pub use self::batcher::BatcherConfig;
 pub use self::global::{global_session, global_session_with_default_enabled};
 pub use self::msg_sender::{MsgSender, MsgSenderError};
-pub use self::session::Session;
+pub use self::session::{MemorySinkStorage, Session};
+#[cfg(feature = "tracing")]
+pub use self::tracing_layer::RerunLayer;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod clap;
@@ -25,7 +31,7 @@ pub mod clap;
 #[cfg(feature = "demo")]
 pub mod demo_util;
 
-pub use re_sdk_comms::default_server_addr;
+pub use re_sdk_comms::{default_server_addr, ClientOptions};
 
 /// Things directly related to logging.
 pub mod log {
@@ -37,7 +43,7 @@ pub mod log {
 
 /// Time-related types.
 pub mod time {
-    pub use re_log_types::{Time, TimeInt, TimePoint, TimeType, Timeline};
+    pub use re_log_types::{Time, TimeInt, TimePoint, TimeType, TimeUnits, Timeline};
 }
 
 /// These are the different _components_ you can log.
@@ -46,12 +52,12 @@ pub mod time {
 /// and can be used in [`MsgSender::with_component`].
 pub mod components {
     pub use re_log_types::component_types::{
-        AnnotationContext, AnnotationInfo, Arrow3D, Box3D, ClassDescription, ClassId, ColorRGBA,
-        EncodedMesh3D, InstanceKey, KeypointId, Label, LineStrip2D, LineStrip3D, Mat3x3, Mesh3D,
-        MeshFormat, MeshId, Pinhole, Point2D, Point3D, Quaternion, Radius, RawMesh3D, Rect2D,
-        Rigid3, Scalar, ScalarPlotProps, Size3D, Tensor, TensorData, TensorDataMeaning,
-        TensorDimension, TensorId, TensorTrait, TextEntry, Transform, Vec2D, Vec3D, Vec4D,
-        ViewCoordinates,
+        AnnotationContext, AnnotationInfo, Arrow3D, Box3D, ClassDescription, ClassId, ColorLut,
+        ColorRGBA, EncodedMesh3D, InstanceKey, KeypointId, Label, LineStrip2D, LineStrip3D, Mat3x3,
+        Mesh3D, MeshFormat, MeshId, Pinhole, Point2D, Point3D, Quaternion, Radius, RawMesh3D,
+        Rect2D, Rigid3, Scalar, ScalarPlotProps, Size3D, SparseVoxelCells, Tensor, TensorData,
+        TensorDataMeaning, TensorDimension, TensorId, TensorTrait, TextEntry, TextEntryField,
+        Transform, Vec2D, Vec3D, Vec4D, ViewCoordinates, VoxelCells, VoxelGrid,
     };
 }
 
@@ -81,7 +87,7 @@ pub mod external {
 
 pub use re_log_types::{
     msg_bundle::{Component, SerializableComponent},
-    ApplicationId, ComponentName, EntityPath, RecordingId,
+    ApplicationId, ComponentName, EntityPath, EntityPathPattern, RecordingId,
 };
 
 const RERUN_ENV_VAR: &str = "RERUN";
@@ -4,8 +4,9 @@ use re_log_types::external::arrow2_convert::serialize::TryIntoArrow;
 use re_log_types::msg_bundle::MsgBundleError;
 use re_log_types::{component_types::InstanceKey, msg_bundle::wrap_in_listarray};
 
+use crate::batcher::BatchQueue;
 use crate::components::Transform;
-use crate::log::{ComponentBundle, LogMsg, MsgBundle, MsgId};
+use crate::log::{ComponentBundle, MsgBundle, MsgId};
 use crate::time::{Time, TimeInt, TimePoint, Timeline};
 use crate::{Component, ComponentName, EntityPath, SerializableComponent, Session};
 
@@ -18,8 +19,10 @@ pub enum MsgSenderError {
     /// The same component were put in the same log message multiple times.
     /// E.g. `with_component()` was called multiple times for `Point3D`.
     /// We don't support that yet.
+    ///
+    /// Use [`MsgSender::send_batch`] to send several rows at once.
     #[error(
-        "All component collections must have exactly one row (i.e. no batching), got {0:?} instead. Perhaps with_component() was called multiple times with the same component type?"
+        "All component collections must have exactly one row, got {0:?} instead. Perhaps with_component() was called multiple times with the same component type? Use MsgSender::send_batch() to send several rows at once."
     )]
     MoreThanOneRow(Vec<(ComponentName, usize)>),
 
@@ -253,6 +256,8 @@ impl MsgSender {
 
     /// Consumes, packs, sanity checks and finally sends the message to the currently configured
     /// target of the SDK.
+    ///
+    /// The message goes through the [`Session`]'s batcher, see [`Session::send_msg_bundle`].
     pub fn send(self, session: &mut Session) -> Result<(), MsgSenderError> {
         if !session.is_enabled() {
             return Ok(()); // silently drop the message
@@ -261,20 +266,67 @@ impl MsgSender {
         let [msg_standard, msg_transforms, msg_splats] = self.into_messages()?;
 
         if let Some(msg_transforms) = msg_transforms {
-            session.send(LogMsg::ArrowMsg(msg_transforms.try_into()?));
+            session.send_msg_bundle(msg_transforms);
         }
         if let Some(msg_splats) = msg_splats {
-            session.send(LogMsg::ArrowMsg(msg_splats.try_into()?));
+            session.send_msg_bundle(msg_splats);
         }
         // Always the primary component last so range-based queries will include the other data. See(#1215)
         // Since the primary component can't be splatted it must be in msg_standard
         if let Some(msg_standard) = msg_standard {
-            session.send(LogMsg::ArrowMsg(msg_standard.try_into()?));
+            session.send_msg_bundle(msg_standard);
+        }
+
+        Ok(())
+    }
+
+    /// Consumes, packs, sanity checks and finally sends many messages at once.
+    ///
+    /// Messages for the same entity that carry the same set of components are concatenated into
+    /// a single multi-row [`MsgBundle`], with one time point per row. This is a lot cheaper to
+    /// send, decode and insert than one message per row, e.g. when logging thousands of small
+    /// entities every frame.
+    ///
+    /// Each `MsgSender` follows the same rules as with [`Self::send`].
+    pub fn send_batch(
+        senders: impl IntoIterator<Item = MsgSender>,
+        session: &mut Session,
+    ) -> Result<(), MsgSenderError> {
+        if !session.is_enabled() {
+            return Ok(()); // silently drop the messages
+        }
+
+        for msg in Self::into_batched_messages(senders)? {
+            session.send_msg_bundle(msg);
         }
 
         Ok(())
     }
 
+    pub(crate) fn into_batched_messages(
+        senders: impl IntoIterator<Item = MsgSender>,
+    ) -> Result<Vec<MsgBundle>, MsgSenderError> {
+        let mut queue = BatchQueue::default();
+
+        for sender in senders {
+            let [msg_standard, msg_transforms, msg_splats] = sender.into_messages()?;
+
+            // Same order as in `Self::send`: the primary component always comes last.
+            for msg in [msg_transforms, msg_splats, msg_standard]
+                .into_iter()
+                .flatten()
+            {
+                queue.push(msg);
+            }
+        }
+
+        queue
+            .take()
+            .iter()
+            .map(|msgs| MsgBundle::try_concat(msgs).map_err(Into::into))
+            .collect()
+    }
+
     fn into_messages(self) -> Result<[Option<MsgBundle>; 3], MsgSenderError> {
         let Self {
             entity_path,
@@ -468,7 +520,7 @@ mod tests {
         assert!(!sender.timepoint.is_empty()); // not yet
 
         let [standard, _, _] = sender.into_messages().unwrap();
-        assert!(standard.unwrap().time_point.is_empty());
+        assert!(standard.unwrap().time_points[0].is_empty());
 
         Ok(())
     }
@@ -486,6 +538,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn batch() -> Result<(), MsgSenderError> {
+        let my_timeline = Timeline::new("my_timeline", time::TimeType::Sequence);
+
+        let senders = (0..3)
+            .map(|frame_nr| {
+                MsgSender::new("some/path")
+                    .with_time(my_timeline, frame_nr)
+                    .with_component(&vec![components::Label("label".into()); 2])
+            })
+            .chain(std::iter::once(
+                MsgSender::new("some/other/path")
+                    .with_component(&vec![components::Label("label".into())]),
+            ))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let msgs = MsgSender::into_batched_messages(senders)?;
+        assert_eq!(2, msgs.len());
+
+        assert_eq!(EntityPath::from("some/path"), msgs[0].entity_path);
+        assert_eq!(3, msgs[0].num_rows());
+        assert_eq!(3, msgs[0].time_points.len());
+        assert_eq!(
+            TimeInt::from(2),
+            *msgs[0].time_points[2].get(&my_timeline).unwrap()
+        );
+
+        assert_eq!(EntityPath::from("some/other/path"), msgs[1].entity_path);
+        assert_eq!(1, msgs[1].num_rows());
+
+        Ok(())
+    }
+
     #[test]
     fn illegal_instance_key() -> Result<(), MsgSenderError> {
         let _ = MsgSender::new("some/path")
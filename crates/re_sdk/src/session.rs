@@ -1,10 +1,17 @@
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
 
 use re_log_types::{
-    ApplicationId, BeginRecordingMsg, LogMsg, MsgId, PathOp, RecordingId, RecordingInfo,
-    RecordingSource, Time, TimePoint,
+    msg_bundle::MsgBundle, ApplicationId, BeginRecordingMsg, EntityPath, EntityPathPattern, LogMsg,
+    MsgId, PathOp, RecordingId, RecordingInfo, RecordingSource, Time, TimePoint, TimeUnits,
+    Timeline, TimelineName,
 };
 
+use crate::batcher::{Batcher, BatcherConfig};
 use crate::file_writer::FileWriter;
 
 #[cfg(feature = "web_viewer")]
@@ -14,6 +21,12 @@ use crate::remote_viewer_server::RemoteViewerServer;
 ///
 /// You should ideally create one session object and reuse it.
 /// For convenience, there is a global [`Session`] object you can access with [`crate::global_session`].
+///
+/// Data logged with [`crate::MsgSender`] is batched before being sent, see [`BatcherConfig`].
+/// Call [`Self::flush`] to make sure everything has been sent.
+///
+/// Besides the main destination (chosen with e.g. [`Self::connect`] or [`Self::save`]), the data
+/// can be sent to any number of additional sinks, see e.g. [`Self::add_file_sink`].
 pub struct Session {
     /// Is this session enabled?
     /// If not, all calls into it are ignored!
@@ -24,13 +37,27 @@ pub struct Session {
     #[cfg(feature = "web_viewer")]
     tokio_rt: tokio::runtime::Runtime,
 
-    sender: Sender,
+    /// Shared with the thread sending the batches that are due, see [`spawn_batch_ticker`].
+    outbox: Arc<Mutex<Outbox>>,
+
+    /// Wakes up the thread sending the batches that are due, `None` if there is no such thread.
+    ///
+    /// Only started on the first [`Self::send_msg_bundle`], so disabled sessions don't have one.
+    batch_ticker: once_cell::unsync::OnceCell<Option<crossbeam::channel::Sender<()>>>,
+
+    /// Used for new TCP connections.
+    client_options: re_sdk_comms::ClientOptions,
 
     application_id: Option<ApplicationId>,
     recording_id: Option<RecordingId>,
     is_official_example: Option<bool>,
+    properties: BTreeMap<String, String>,
+    timeline_units: BTreeMap<TimelineName, TimeUnits>,
 
     has_sent_begin_recording_msg: bool,
+
+    /// The last [`BeginRecordingMsg`] we sent, for sinks added later on.
+    begin_recording_msg: Option<BeginRecordingMsg>,
 }
 
 impl Default for Session {
@@ -115,11 +142,16 @@ impl Session {
             #[cfg(feature = "web_viewer")]
             tokio_rt: tokio::runtime::Runtime::new().unwrap(),
 
-            sender: Default::default(),
+            outbox: Default::default(),
+            batch_ticker: Default::default(),
+            client_options: Default::default(),
             application_id: None,
             recording_id: None,
             is_official_example: None,
+            properties: Default::default(),
+            timeline_units: Default::default(),
             has_sent_begin_recording_msg: false,
+            begin_recording_msg: None,
         }
     }
 
@@ -168,12 +200,69 @@ impl Session {
         }
     }
 
+    /// How data logged with [`Self::send_msg_bundle`] is batched.
+    pub fn batcher_config(&self) -> BatcherConfig {
+        self.outbox.lock().batcher.config()
+    }
+
+    /// Change how data logged with [`Self::send_msg_bundle`] is batched.
+    ///
+    /// Use [`BatcherConfig::ALWAYS`] to send every message as soon as it is logged.
+    pub fn set_batcher_config(&mut self, config: BatcherConfig) {
+        let mut outbox = self.outbox.lock();
+        outbox.batcher.set_config(config);
+        if outbox.batcher.should_flush() {
+            outbox.flush_batches();
+        }
+        self.wake_batch_ticker();
+    }
+
+    /// Set a user-defined property of the recording, e.g. a git hash, a robot id or a scenario
+    /// name.
+    ///
+    /// Properties are stored in the [`RecordingInfo`], and shown in the viewer.
+    /// They can be changed at any time: the updated [`RecordingInfo`] is sent along with the
+    /// next logged message.
+    pub fn set_recording_property(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let (key, value) = (key.into(), value.into());
+        if self.properties.get(&key) != Some(&value) {
+            self.properties.insert(key, value);
+            self.has_sent_begin_recording_msg = false;
+        }
+    }
+
+    /// The user-defined properties of the recording, see [`Self::set_recording_property`].
+    pub fn recording_properties(&self) -> &BTreeMap<String, String> {
+        &self.properties
+    }
+
+    /// Set how the values of a timeline should be interpreted and displayed in the viewer,
+    /// e.g. simulation ticks at 60Hz, or hardware timestamps in µs since boot.
+    ///
+    /// Like properties, this can be changed at any time (see [`Self::set_recording_property`]).
+    pub fn set_timeline_units(&mut self, timeline: impl Into<TimelineName>, units: TimeUnits) {
+        let timeline = timeline.into();
+        if self.timeline_units.get(&timeline) != Some(&units) {
+            self.timeline_units.insert(timeline, units);
+            self.has_sent_begin_recording_msg = false;
+        }
+    }
+
     /// Set where the recording is coming from.
     /// The default is [`RecordingSource::RustSdk`].
     pub fn set_recording_source(&mut self, recording_source: RecordingSource) {
         self.recording_source = recording_source;
     }
 
+    /// How new TCP connections (see [`Self::connect`] and [`Self::add_tcp_sink`]) handle the
+    /// server going away: how often to try to reconnect, and whether to spill the data to disk
+    /// in the meantime.
+    ///
+    /// Existing connections are not affected.
+    pub fn set_client_options(&mut self, client_options: re_sdk_comms::ClientOptions) {
+        self.client_options = client_options;
+    }
+
     /// Send log data to a remote viewer/server.
     ///
     /// Usually this is done by running the `rerun` binary (`cargo install rerun`) without arguments,
@@ -199,7 +288,8 @@ impl Session {
 
         let backlog = self.drain_log_messages_buffer();
 
-        match &mut self.sender {
+        let mut outbox = self.outbox.lock();
+        match &mut outbox.sender {
             Sender::Remote(remote) => {
                 remote.set_addr(addr);
             }
@@ -211,15 +301,46 @@ impl Session {
 
             _ => {
                 re_log::debug!("Connecting to remote…");
-                let mut client = re_sdk_comms::Client::new(addr);
+                let mut client =
+                    re_sdk_comms::Client::with_options(addr, self.client_options.clone());
                 for msg in backlog {
                     client.send(msg);
                 }
-                self.sender = Sender::Remote(client);
+                outbox.sender = Sender::Remote(client);
             }
         }
     }
 
+    /// Like [`Self::connect`], but the data is sent by a task on the current [`tokio`] runtime
+    /// instead of by dedicated threads.
+    ///
+    /// Must be called from within a tokio runtime.
+    /// Use [`Self::flush_async`] and [`Self::shutdown_async`] to wait for the data to be sent
+    /// without blocking the runtime.
+    ///
+    /// Only so much data can wait to be sent: once that limit is reached, anything more that is
+    /// logged is dropped. Use [`Self::ready_async`] to wait for room instead.
+    #[cfg(feature = "async")]
+    pub fn connect_async(&mut self, addr: SocketAddr) {
+        if !self.enabled {
+            re_log::debug!("Rerun disabled - call to connect_async() ignored");
+            return;
+        }
+
+        let backlog = self.drain_log_messages_buffer();
+
+        re_log::debug!("Connecting to remote…");
+        // Making room for the whole backlog, which would get dropped otherwise.
+        let client = re_sdk_comms::AsyncClient::with_capacity(
+            addr,
+            re_sdk_comms::AsyncClient::DEFAULT_CAPACITY.max(backlog.len()),
+        );
+        for msg in backlog {
+            client.send(msg);
+        }
+        self.outbox.lock().sender = Sender::AsyncRemote(client);
+    }
+
     /// Serve log-data over WebSockets and serve a Rerun web viewer over HTTP.
     ///
     /// If the `open_browser` argument is `true`, your default browser
@@ -237,14 +358,105 @@ impl Session {
             return;
         }
 
-        self.sender = Sender::WebViewer(RemoteViewerServer::new(&self.tokio_rt, open_browser));
+        self.outbox.lock().sender =
+            Sender::WebViewer(RemoteViewerServer::new(&self.tokio_rt, open_browser));
+    }
+
+    /// Also write all logged data to an `.rrd` file, on top of wherever it is already being sent.
+    ///
+    /// If `entity_filter` is set, only the data logged at the entity paths matching it is written,
+    /// e.g. `robot/**` for everything at or below `robot`.
+    /// Recording metadata is always included.
+    ///
+    /// Unlike [`Self::save`], this doesn't include anything that was logged before the call.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_file_sink(
+        &mut self,
+        path: impl Into<std::path::PathBuf>,
+        entity_filter: Option<EntityPathPattern>,
+    ) -> anyhow::Result<()> {
+        if !self.enabled {
+            re_log::debug!("Rerun disabled - call to add_file_sink() ignored");
+            return Ok(());
+        }
+
+        let file_writer = FileWriter::new(path)?;
+        self.add_sink(Sender::File(file_writer), entity_filter);
+        Ok(())
+    }
+
+    /// Also stream all logged data to a remote viewer/server, on top of wherever it is already
+    /// being sent.
+    ///
+    /// If `entity_filter` is set, only the data logged at the entity paths matching it is sent,
+    /// e.g. `robot/**` for everything at or below `robot`.
+    /// Recording metadata is always included.
+    pub fn add_tcp_sink(&mut self, addr: SocketAddr, entity_filter: Option<EntityPathPattern>) {
+        if !self.enabled {
+            re_log::debug!("Rerun disabled - call to add_tcp_sink() ignored");
+            return;
+        }
+
+        let client = re_sdk_comms::Client::with_options(addr, self.client_options.clone());
+        self.add_sink(Sender::Remote(client), entity_filter);
+    }
+
+    /// Also keep all logged data in memory, on top of wherever it is already being sent.
+    ///
+    /// If `entity_filter` is set, only the data logged at the entity paths matching it is kept,
+    /// e.g. `robot/**` for everything at or below `robot`.
+    /// Recording metadata is always included.
+    ///
+    /// Unlike [`Self::drain_log_messages_buffer`], this works whatever the main destination is.
+    pub fn add_memory_sink(
+        &mut self,
+        entity_filter: Option<EntityPathPattern>,
+    ) -> MemorySinkStorage {
+        let storage = MemorySinkStorage::default();
+        if self.enabled {
+            self.add_sink(Sender::Memory(storage.clone()), entity_filter);
+        } else {
+            re_log::debug!("Rerun disabled - call to add_memory_sink() ignored");
+        }
+        storage
+    }
+
+    /// Flushes and closes all the sinks added with e.g. [`Self::add_file_sink`].
+    ///
+    /// The main destination is left untouched.
+    pub fn remove_sinks(&mut self) {
+        let mut outbox = self.outbox.lock();
+        outbox.flush_batches();
+        for mut sink in outbox.sinks.drain(..) {
+            sink.sender.flush_blocking();
+        }
+    }
+
+    fn add_sink(&mut self, mut sender: Sender, entity_filter: Option<EntityPathPattern>) {
+        let mut outbox = self.outbox.lock();
+
+        // Pending data was logged before this sink existed.
+        outbox.flush_batches();
+
+        if let Some(begin_recording_msg) = &self.begin_recording_msg {
+            sender.send(begin_recording_msg.clone().into());
+        }
+
+        outbox.sinks.push(Sink {
+            sender,
+            entity_filter,
+        });
     }
 
     /// Disconnects any TCP connection, shuts down any server, and closes any file.
+    ///
+    /// This only affects the main destination: use [`Self::remove_sinks`] for the additional ones.
     pub fn disconnect(&mut self) {
-        if !matches!(&self.sender, &Sender::Buffered(_)) {
+        let mut outbox = self.outbox.lock();
+        outbox.flush_batches();
+        if !matches!(&outbox.sender, &Sender::Buffered(_)) {
             re_log::debug!("Switching to buffered.");
-            self.sender = Sender::Buffered(Default::default());
+            outbox.sender = Sender::Buffered(Default::default());
         }
     }
 
@@ -257,26 +469,105 @@ impl Session {
     ///
     /// This can return true even before the connection is yet to be established.
     pub fn is_streaming_over_tcp(&self) -> bool {
-        matches!(&self.sender, &Sender::Remote(_))
+        match &self.outbox.lock().sender {
+            Sender::Remote(_) => true,
+            #[cfg(feature = "async")]
+            Sender::AsyncRemote(_) => true,
+            _ => false,
+        }
     }
 
-    /// Wait until all logged data have been sent to the remove server (if any).
+    /// Send all pending batches, then wait until all logged data have been sent to the remote
+    /// servers (if any).
+    ///
+    /// This blocks the current thread: use [`Self::flush_async`] from within async code.
     pub fn flush(&mut self) {
-        if let Sender::Remote(sender) = &mut self.sender {
-            sender.flush();
+        let mut outbox = self.outbox.lock();
+        outbox.flush_batches();
+        outbox.sender.flush_blocking();
+        for sink in &mut outbox.sinks {
+            sink.sender.flush_blocking();
+        }
+    }
+
+    /// Send all pending batches, then wait until all logged data have been sent to the remote
+    /// server (if any), without blocking the current thread.
+    ///
+    /// This is cancellation-safe: cancelling it only stops the waiting, the data is still sent.
+    #[cfg(feature = "async")]
+    pub async fn flush_async(&mut self) {
+        // Not holding on to the lock while waiting, the batch ticker needs it.
+        let flushed = {
+            let mut outbox = self.outbox.lock();
+            outbox.flush_batches();
+            for sink in &mut outbox.sinks {
+                sink.sender.flush_blocking();
+            }
+            if let Sender::AsyncRemote(client) = &outbox.sender {
+                Some(client.flush())
+            } else {
+                outbox.sender.flush_blocking();
+                None
+            }
+        };
+        if let Some(flushed) = flushed {
+            flushed.await;
+        }
+    }
+
+    /// Wait until the connection opened with [`Self::connect_async`] (if any) has room for more
+    /// data.
+    ///
+    /// Call this regularly when logging a lot of data from async code, so that a server that can't
+    /// keep up slows down the logging instead of data getting dropped.
+    #[cfg(feature = "async")]
+    pub async fn ready_async(&self) {
+        let ready = match &self.outbox.lock().sender {
+            Sender::AsyncRemote(client) => Some(client.ready()),
+            _ => None,
+        };
+        if let Some(ready) = ready {
+            ready.await;
+        }
+    }
+
+    /// Send all pending data, then close the connection opened with [`Self::connect_async`]
+    /// (if any) and wait for it to shut down.
+    ///
+    /// This is cancellation-safe: if cancelled, the remaining data is still sent in the background.
+    #[cfg(feature = "async")]
+    pub async fn shutdown_async(&mut self) {
+        let client = {
+            let mut outbox = self.outbox.lock();
+            outbox.flush_batches();
+            if matches!(&outbox.sender, Sender::AsyncRemote(_)) {
+                std::mem::take(&mut outbox.sender)
+            } else {
+                return;
+            }
+        };
+        if let Sender::AsyncRemote(client) = client {
+            // Reports the messages it had to drop by itself.
+            client.shutdown().await;
         }
     }
 
     /// If the tcp session is disconnected, allow it to quit early and drop unsent messages
     pub fn drop_msgs_if_disconnected(&mut self) {
-        if let Sender::Remote(sender) = &mut self.sender {
-            sender.drop_if_disconnected();
+        let mut outbox = self.outbox.lock();
+        outbox.sender.drop_if_disconnected();
+        for sink in &mut outbox.sinks {
+            sink.sender.drop_if_disconnected();
         }
     }
 
     /// Drain all buffered [`LogMsg`]es and return them.
+    ///
+    /// This includes any pending batches.
     pub fn drain_log_messages_buffer(&mut self) -> Vec<LogMsg> {
-        if let Sender::Buffered(log_messages) = &mut self.sender {
+        let mut outbox = self.outbox.lock();
+        outbox.flush_batches();
+        if let Sender::Buffered(log_messages) = &mut outbox.sender {
             std::mem::take(log_messages)
         } else {
             vec![]
@@ -284,6 +575,8 @@ impl Session {
     }
 
     /// Send a [`LogMsg`].
+    ///
+    /// Pending batches are sent first, so that ordering is preserved.
     pub fn send(&mut self, log_msg: LogMsg) {
         if !self.enabled {
             // It's intended that the logging SDK should drop messages earlier than this if logging is disabled. This
@@ -292,37 +585,90 @@ impl Session {
             return;
         }
 
-        if !self.has_sent_begin_recording_msg {
-            if let Some(recording_id) = self.recording_id {
-                let application_id = self
-                    .application_id
-                    .clone()
-                    .unwrap_or_else(ApplicationId::unknown);
-
-                re_log::debug!(
-                    "Beginning new recording with application_id {:?} and recording id {}",
-                    application_id.0,
-                    recording_id
-                );
-
-                self.sender.send(
-                    BeginRecordingMsg {
-                        msg_id: MsgId::random(),
-                        info: RecordingInfo {
-                            application_id,
-                            recording_id,
-                            is_official_example: self.is_official_example.unwrap_or_default(),
-                            started: Time::now(),
-                            recording_source: self.recording_source.clone(),
-                        },
-                    }
-                    .into(),
-                );
-                self.has_sent_begin_recording_msg = true;
-            }
+        let begin_recording_msg = self.next_begin_recording_msg();
+        let mut outbox = self.outbox.lock();
+        if let Some(begin_recording_msg) = begin_recording_msg {
+            outbox.begin_recording(begin_recording_msg);
         }
+        outbox.flush_batches();
+        outbox.send_to_all(log_msg);
+    }
 
-        self.sender.send(log_msg);
+    /// Send a [`MsgBundle`].
+    ///
+    /// The bundle is batched together with other bundles for the same entity, and only sent once
+    /// one of the thresholds of the [`BatcherConfig`] is reached, or on [`Self::flush`].
+    pub fn send_msg_bundle(&mut self, msg_bundle: MsgBundle) {
+        if !self.enabled {
+            re_log::debug_once!("Logging is disabled, dropping message.");
+            return;
+        }
+
+        let begin_recording_msg = self.next_begin_recording_msg();
+        let mut outbox = self.outbox.lock();
+        if let Some(begin_recording_msg) = begin_recording_msg {
+            outbox.begin_recording(begin_recording_msg);
+        }
+
+        let was_empty = outbox.batcher.is_empty();
+        if outbox.batcher.push(msg_bundle) {
+            outbox.flush_batches();
+        } else if was_empty {
+            self.batch_ticker
+                .get_or_init(|| spawn_batch_ticker(self.outbox.clone()));
+            self.wake_batch_ticker();
+        }
+    }
+
+    /// Let the batch ticker know that the deadline of the pending batches might have changed.
+    fn wake_batch_ticker(&self) {
+        if let Some(Some(batch_ticker)) = self.batch_ticker.get() {
+            // Full means it's already going to wake up.
+            batch_ticker.try_send(()).ok();
+        }
+    }
+
+    /// Returns a new [`BeginRecordingMsg`] if the recording changed since the last one was sent.
+    fn next_begin_recording_msg(&mut self) -> Option<BeginRecordingMsg> {
+        if self.has_sent_begin_recording_msg {
+            return None;
+        }
+        let recording_id = self.recording_id?;
+
+        let application_id = self
+            .application_id
+            .clone()
+            .unwrap_or_else(ApplicationId::unknown);
+
+        re_log::debug!(
+            "Beginning new recording with application_id {:?} and recording id {}",
+            application_id.0,
+            recording_id
+        );
+
+        // Keep the start time when we're only updating the properties.
+        let started = self
+            .begin_recording_msg
+            .as_ref()
+            .filter(|msg| msg.info.recording_id == recording_id)
+            .map_or_else(Time::now, |msg| msg.info.started);
+
+        let begin_recording_msg = BeginRecordingMsg {
+            msg_id: MsgId::random(),
+            info: RecordingInfo {
+                application_id,
+                recording_id,
+                is_official_example: self.is_official_example.unwrap_or_default(),
+                started,
+                recording_source: self.recording_source.clone(),
+                properties: self.properties.clone(),
+                timeline_units: self.timeline_units.clone(),
+            },
+        };
+        self.begin_recording_msg = Some(begin_recording_msg.clone());
+        self.has_sent_begin_recording_msg = true;
+
+        Some(begin_recording_msg)
     }
 
     /// Send a [`PathOp`].
@@ -334,6 +680,40 @@ impl Session {
         }));
     }
 
+    /// Permanently drop all the data logged at `entity_path` and below, including the past.
+    ///
+    /// Use this to get rid of data that was logged by mistake.
+    pub fn drop_entity_subtree(&mut self, entity_path: EntityPath) {
+        self.send_path_op(&Self::edit_time_point(), PathOp::DropRecursive(entity_path));
+    }
+
+    /// Move all the data logged at `from` and below to `to`, including the past,
+    /// e.g. to rename or reparent an entity.
+    ///
+    /// This is ignored if there is already data at `to`.
+    pub fn rename_entity_subtree(&mut self, from: EntityPath, to: EntityPath) {
+        self.send_path_op(&Self::edit_time_point(), PathOp::Rename { from, to });
+    }
+
+    /// Permanently drop all the data logged on `timeline` at `entity_path` and below,
+    /// e.g. [`EntityPath::root`] to get rid of a whole timeline.
+    pub fn drop_timeline(&mut self, entity_path: EntityPath, timeline: Timeline) {
+        self.send_path_op(
+            &Self::edit_time_point(),
+            PathOp::DropTimeline {
+                entity_path,
+                timeline,
+            },
+        );
+    }
+
+    /// History edits apply at all times, so they are timeless.
+    ///
+    /// Stamping them with e.g. `log_time` would add that timeline to recordings that never had it.
+    fn edit_time_point() -> TimePoint {
+        TimePoint::timeless()
+    }
+
     /// Drains all pending log messages and saves them to disk into an rrd file.
     // TODO(cmc): We're gonna have to properly type all these errors all the way up to the encoding
     // methods in re_log_types at some point...
@@ -349,11 +729,22 @@ impl Session {
         for log_msg in backlog {
             file_writer.write(log_msg);
         }
-        self.sender = Sender::File(file_writer);
+        self.outbox.lock().sender = Sender::File(file_writer);
         Ok(())
     }
 }
 
+impl Drop for Session {
+    fn drop(&mut self) {
+        let mut outbox = self.outbox.lock();
+        outbox.flush_batches();
+
+        // Closing the connections and files right here rather than on the batch ticker, which
+        // might not get to it before the process exits.
+        drop(std::mem::take(&mut *outbox));
+    }
+}
+
 #[cfg(feature = "native_viewer")]
 impl Session {
     fn app_env(&self) -> re_viewer::AppEnvironment {
@@ -418,7 +809,7 @@ impl Session {
             tx.send(msg).ok();
         }
 
-        self.sender = Sender::NativeViewer(tx);
+        self.outbox.lock().sender = Sender::NativeViewer(tx);
         let app_env = self.app_env();
 
         // NOTE: Forget the handle on purpose, leave that thread be.
@@ -445,13 +836,153 @@ impl Session {
     }
 }
 
+/// Where the logged data goes.
+///
+/// Shared between the [`Session`] and the thread sending the batches that are due.
+#[derive(Default)]
+struct Outbox {
+    sender: Sender,
+
+    /// Additional destinations, on top of [`Self::sender`].
+    sinks: Vec<Sink>,
+
+    /// Pending data, not yet handed to the [`Sender`].
+    batcher: Batcher,
+}
+
+impl Outbox {
+    /// Sends a new [`BeginRecordingMsg`], after the rows that are still pending for the
+    /// previous one.
+    fn begin_recording(&mut self, begin_recording_msg: BeginRecordingMsg) {
+        self.flush_batches();
+        self.send_to_all(begin_recording_msg.into());
+    }
+
+    /// Hands all pending batches over to the [`Sender`].
+    fn flush_batches(&mut self) {
+        if self.batcher.is_empty() {
+            return;
+        }
+
+        for msg_bundle in self.batcher.flush() {
+            match msg_bundle.try_into() {
+                Ok(arrow_msg) => self.send_to_all(LogMsg::ArrowMsg(arrow_msg)),
+                Err(err) => re_log::error_once!("Failed to encode message: {err}"),
+            }
+        }
+    }
+
+    fn send_to_all(&mut self, log_msg: LogMsg) {
+        for sink in &mut self.sinks {
+            if sink.accepts(&log_msg) {
+                sink.sender.send(log_msg.clone());
+            }
+        }
+        self.sender.send(log_msg);
+    }
+}
+
+/// Spawns a thread sending the pending batches of `outbox` once they are due, so that they don't
+/// wait for the next log call.
+///
+/// Returns the channel to wake it up whenever the deadline might have changed.
+/// The thread quits once that channel is dropped.
+fn spawn_batch_ticker(outbox: Arc<Mutex<Outbox>>) -> Option<crossbeam::channel::Sender<()>> {
+    use crossbeam::channel::RecvTimeoutError;
+
+    if cfg!(target_arch = "wasm32") {
+        // No threads: the deadline is only checked when something is logged.
+        return None;
+    }
+
+    let (tx, rx) = crossbeam::channel::bounded(1);
+    let spawned = std::thread::Builder::new()
+        .name("rerun_batch_ticker".into())
+        .spawn(move || loop {
+            let timeout = outbox
+                .lock()
+                .batcher
+                .time_until_flush()
+                .unwrap_or(Duration::MAX);
+            match rx.recv_timeout(timeout) {
+                Ok(()) => {}
+                Err(RecvTimeoutError::Timeout) => {
+                    let mut outbox = outbox.lock();
+                    if outbox.batcher.should_flush() {
+                        outbox.flush_batches();
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        });
+
+    match spawned {
+        Ok(_) => Some(tx),
+        Err(err) => {
+            re_log::warn!("Failed to spawn the batching thread: {err}");
+            None
+        }
+    }
+}
+
+/// Where the messages sent to a sink created with [`Session::add_memory_sink`] end up.
+#[derive(Clone, Default)]
+pub struct MemorySinkStorage(Arc<Mutex<Vec<LogMsg>>>);
+
+impl MemorySinkStorage {
+    /// Takes all the messages received so far.
+    pub fn take(&self) -> Vec<LogMsg> {
+        std::mem::take(&mut *self.0.lock())
+    }
+
+    /// How many messages are waiting to be taken?
+    pub fn num_msgs(&self) -> usize {
+        self.0.lock().len()
+    }
+}
+
+/// An additional destination for the logged data.
+struct Sink {
+    sender: Sender,
+
+    /// Only forward messages logged at entity paths matching this pattern.
+    entity_filter: Option<EntityPathPattern>,
+}
+
+impl Sink {
+    fn accepts(&self, log_msg: &LogMsg) -> bool {
+        let Some(entity_filter) = &self.entity_filter else { return true; };
+
+        let entity_path = match log_msg {
+            LogMsg::BeginRecordingMsg(_) | LogMsg::Goodbye(_) => return true,
+            LogMsg::EntityPathOpMsg(msg) => msg.path_op.entity_path().clone(),
+            LogMsg::ArrowMsg(msg) => match msg.entity_path() {
+                Ok(entity_path) => entity_path,
+                Err(err) => {
+                    re_log::warn_once!("Failed to filter message: {err}");
+                    return true;
+                }
+            },
+        };
+
+        entity_filter.matches(&entity_path)
+    }
+}
+
 enum Sender {
     Buffered(Vec<LogMsg>),
 
+    /// Shared with the user, see [`Session::add_memory_sink`].
+    Memory(MemorySinkStorage),
+
     File(FileWriter),
 
     Remote(re_sdk_comms::Client),
 
+    /// Like [`Self::Remote`], but sending from a task on a [`tokio`] runtime.
+    #[cfg(feature = "async")]
+    AsyncRemote(re_sdk_comms::AsyncClient),
+
     #[cfg(feature = "native_viewer")]
     NativeViewer(re_smart_channel::Sender<LogMsg>),
 
@@ -471,10 +1002,15 @@ impl Sender {
         match self {
             Self::Buffered(buffer) => buffer.push(msg),
 
+            Self::Memory(storage) => storage.0.lock().push(msg),
+
             Self::File(file) => file.write(msg),
 
             Self::Remote(client) => client.send(msg),
 
+            #[cfg(feature = "async")]
+            Self::AsyncRemote(client) => client.send(msg),
+
             #[cfg(feature = "native_viewer")]
             Self::NativeViewer(sender) => {
                 if let Err(err) = sender.send(msg) {
@@ -488,4 +1024,71 @@ impl Sender {
             }
         }
     }
+
+    /// Wait until all messages have been sent to the remote server (if any).
+    fn flush_blocking(&mut self) {
+        match self {
+            Self::Remote(client) => client.flush(),
+
+            #[cfg(feature = "async")]
+            Self::AsyncRemote(client) => client.flush_blocking(),
+
+            _ => {}
+        }
+    }
+
+    fn drop_if_disconnected(&mut self) {
+        match self {
+            Self::Remote(client) => client.drop_if_disconnected(),
+
+            #[cfg(feature = "async")]
+            Self::AsyncRemote(client) => client.drop_if_disconnected(),
+
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{components::Label, MsgSender};
+
+    #[test]
+    fn memory_sink_filter() {
+        let mut session = Session::with_default_enabled(true);
+        session.set_enabled(true);
+        session.set_recording_id(RecordingId::random());
+
+        let everything = session.add_memory_sink(None);
+        let robot = session.add_memory_sink(Some(EntityPathPattern::parse("robot/**").unwrap()));
+
+        for entity_path in ["robot", "robot/arm", "robot_2", "camera"] {
+            MsgSender::new(entity_path)
+                .with_component(&[Label("label".into())])
+                .unwrap()
+                .send(&mut session)
+                .unwrap();
+        }
+        session.flush();
+
+        // 1 BeginRecordingMsg, followed by one message per entity
+        assert_eq!(5, everything.num_msgs());
+
+        let msgs = robot.take();
+        assert!(matches!(msgs[0], LogMsg::BeginRecordingMsg(_)));
+        let entity_paths = msgs[1..]
+            .iter()
+            .map(|msg| match msg {
+                LogMsg::ArrowMsg(msg) => msg.entity_path().unwrap(),
+                _ => panic!("Expected an ArrowMsg"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entity_paths,
+            vec![EntityPath::from("robot"), EntityPath::from("robot/arm")]
+        );
+        assert_eq!(0, robot.num_msgs());
+    }
 }
@@ -0,0 +1,373 @@
+//! A [`tracing_subscriber::Layer`] that forwards `tracing` events and spans to Rerun.
+//!
+//! Messages are handed over to a thread of their own which sends them to the [`Session`],
+//! so that events emitted while the session is locked (e.g. by the session itself) can't deadlock.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+use re_log_types::EntityPathPart;
+
+use crate::components::{Scalar, TextEntry};
+use crate::{EntityPath, MsgSender, MsgSenderError, Session};
+
+/// Targets that are never forwarded, since logging them could feed back into the [`Session`].
+const IGNORED_TARGET_PREFIXES: &[&str] = &[
+    "re_log",
+    "re_log_types",
+    "re_sdk",
+    "re_sdk_comms",
+    "re_smart_channel",
+    "re_web_viewer_server",
+    "re_ws_comms",
+];
+
+thread_local! {
+    /// Set while the layer is forwarding something, to avoid re-entrancy.
+    ///
+    /// Always set on the thread sending to the session.
+    static IS_FORWARDING: Cell<bool> = Cell::new(false);
+}
+
+/// Sets [`IS_FORWARDING`] until dropped, even if whatever is forwarding panics.
+struct ForwardingGuard;
+
+impl ForwardingGuard {
+    /// `None` if we are already forwarding something on this thread.
+    fn new() -> Option<Self> {
+        let was_forwarding = IS_FORWARDING.with(|is_forwarding| is_forwarding.replace(true));
+        (!was_forwarding).then_some(Self)
+    }
+}
+
+impl Drop for ForwardingGuard {
+    fn drop(&mut self) {
+        IS_FORWARDING.with(|is_forwarding| is_forwarding.set(false));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+enum SessionHandle {
+    Global,
+    Shared(Arc<Mutex<Session>>),
+}
+
+/// A [`tracing_subscriber::Layer`] that logs `tracing` events and spans to a Rerun [`Session`].
+///
+/// * Events are logged as [`TextEntry`]s, with their fields preserved as structured fields.
+/// * Closed spans log the time they were busy (in seconds) as a [`Scalar`].
+///
+/// The entity path is derived from the event's target, e.g. an event with the target
+/// `my_crate::net` ends up at `logs/my_crate/net`, and a span called `handle_request` in the
+/// same module at `spans/my_crate/net/handle_request`.
+///
+/// ``` no_run
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry()
+///     .with(re_sdk::RerunLayer::global())
+///     .init();
+/// ```
+pub struct RerunLayer {
+    /// To the thread sending to the session.
+    tx: crossbeam::channel::Sender<MsgSender>,
+    /// Messages that couldn't be built or sent, reported once the layer is dropped.
+    num_failures: Arc<AtomicU64>,
+    events_prefix: EntityPath,
+    spans_prefix: EntityPath,
+    log_span_durations: bool,
+}
+
+impl RerunLayer {
+    /// Log to the global [`Session`] (see [`crate::global_session`]).
+    ///
+    /// ## Panics
+    /// If the thread sending to the session can't be spawned. This also applies to [`Self::new`].
+    pub fn global() -> Self {
+        Self::with_handle(SessionHandle::Global)
+    }
+
+    /// Log to a [`Session`] shared with the rest of the application.
+    pub fn new(session: Arc<Mutex<Session>>) -> Self {
+        Self::with_handle(SessionHandle::Shared(session))
+    }
+
+    fn with_handle(session: SessionHandle) -> Self {
+        let (tx, rx) = crossbeam::channel::unbounded();
+        let num_failures = Arc::new(AtomicU64::new(0));
+
+        // NOTE: Forget the handle on purpose, the thread exits once the layer is dropped.
+        std::thread::Builder::new()
+            .name("rerun_tracing_layer".into())
+            .spawn({
+                let num_failures = num_failures.clone();
+                move || {
+                    // Anything logged while sending is never forwarded.
+                    let _guard = ForwardingGuard::new();
+                    for msg_sender in rx {
+                        send(&session, &num_failures, msg_sender);
+                    }
+
+                    let num_failures = num_failures.load(Ordering::Relaxed);
+                    if num_failures > 0 {
+                        re_log::warn!("RerunLayer: {num_failures} message(s) couldn't be logged");
+                    }
+                }
+            })
+            .expect("Failed to spawn thread");
+
+        Self {
+            tx,
+            num_failures,
+            events_prefix: EntityPath::from("logs"),
+            spans_prefix: EntityPath::from("spans"),
+            log_span_durations: true,
+        }
+    }
+
+    /// Under what entity path events are logged. Defaults to `logs`.
+    pub fn with_events_prefix(mut self, prefix: impl Into<EntityPath>) -> Self {
+        self.events_prefix = prefix.into();
+        self
+    }
+
+    /// Under what entity path span durations are logged. Defaults to `spans`.
+    pub fn with_spans_prefix(mut self, prefix: impl Into<EntityPath>) -> Self {
+        self.spans_prefix = prefix.into();
+        self
+    }
+
+    /// Should span durations be logged as scalars? Defaults to `true`.
+    pub fn with_span_durations(mut self, log_span_durations: bool) -> Self {
+        self.log_span_durations = log_span_durations;
+        self
+    }
+
+    /// Builds a message with `f` and hands it to the sending thread, unless we are already
+    /// forwarding something on this thread.
+    fn forward(&self, f: impl FnOnce() -> Result<MsgSender, MsgSenderError>) {
+        let Some(_guard) = ForwardingGuard::new() else { return; };
+        match f() {
+            Ok(msg_sender) => {
+                // Only fails once the sending thread is gone, i.e. it panicked.
+                self.tx.send(msg_sender).ok();
+            }
+            Err(err) => {
+                self.num_failures.fetch_add(1, Ordering::Relaxed);
+                // Not forwarded: `re_sdk` is an ignored target, and we hold the guard anyway.
+                re_log::warn_once!("RerunLayer: failed to build message: {err}");
+            }
+        }
+    }
+}
+
+fn send(session: &SessionHandle, num_failures: &AtomicU64, msg_sender: MsgSender) {
+    let result = match session {
+        SessionHandle::Global => msg_sender.send(&mut crate::global_session()),
+        SessionHandle::Shared(session) => msg_sender.send(&mut session.lock()),
+    };
+    if let Err(err) = result {
+        num_failures.fetch_add(1, Ordering::Relaxed);
+        re_log::warn_once!("RerunLayer: failed to send message: {err}");
+    }
+}
+
+fn is_ignored_target(target: &str) -> bool {
+    IGNORED_TARGET_PREFIXES.iter().any(|prefix| {
+        target
+            .strip_prefix(prefix)
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+/// `my_crate::net` -> `prefix/my_crate/net`
+fn target_path(prefix: &EntityPath, target: &str) -> Vec<EntityPathPart> {
+    prefix
+        .iter()
+        .cloned()
+        .chain(
+            target
+                .split("::")
+                .filter(|part| !part.is_empty())
+                .map(EntityPathPart::from),
+        )
+        .collect()
+}
+
+fn level_str(level: &Level) -> &'static str {
+    match *level {
+        Level::TRACE => "TRACE",
+        Level::DEBUG => "DEBUG",
+        Level::INFO => "INFO",
+        Level::WARN => "WARN",
+        Level::ERROR => "ERROR",
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Collects the fields of an event or span as strings.
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Vec<(String, String)>,
+}
+
+impl FieldVisitor {
+    fn set(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else if let Some(existing) = self.fields.iter_mut().find(|(k, _)| k == field.name()) {
+            existing.1 = value;
+        } else {
+            self.fields.push((field.name().to_owned(), value));
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field, value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.set(field, format!("{value:?}"));
+    }
+}
+
+/// Stored in the extensions of each span.
+#[derive(Default)]
+struct SpanData {
+    fields: Vec<(String, String)>,
+    entered_at: Option<Instant>,
+    busy: Duration,
+}
+
+// ----------------------------------------------------------------------------
+
+impl<S> tracing_subscriber::Layer<S> for RerunLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return; };
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(SpanData {
+            fields: visitor.fields,
+            ..Default::default()
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return; };
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            let mut visitor = FieldVisitor {
+                message: None,
+                fields: std::mem::take(&mut data.fields),
+            };
+            values.record(&mut visitor);
+            data.fields = visitor.fields;
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return; };
+        if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+            data.entered_at = Some(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return; };
+        if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+            if let Some(entered_at) = data.entered_at.take() {
+                data.busy += entered_at.elapsed();
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if !self.log_span_durations {
+            return;
+        }
+        let Some(span) = ctx.span(&id) else { return; };
+        let metadata = span.metadata();
+        if is_ignored_target(metadata.target()) {
+            return;
+        }
+        let Some(busy) = span.extensions().get::<SpanData>().map(|data| data.busy) else {
+            return;
+        };
+
+        let mut path = target_path(&self.spans_prefix, metadata.target());
+        path.push(EntityPathPart::from(metadata.name()));
+
+        self.forward(|| MsgSender::new(path).with_component(&[Scalar::from(busy.as_secs_f64())]));
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if is_ignored_target(metadata.target()) {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let mut text_entry = TextEntry::new(
+            visitor.message.unwrap_or_default(),
+            Some(level_str(metadata.level()).to_owned()),
+        );
+
+        // Fields of the enclosing spans come first, outermost span first.
+        if let Some(scope) = ctx.event_scope(event) {
+            let mut span_names = Vec::new();
+            for span in scope.from_root() {
+                span_names.push(span.name());
+                if let Some(data) = span.extensions().get::<SpanData>() {
+                    for (key, value) in &data.fields {
+                        text_entry = text_entry.with_field(key.clone(), value.clone());
+                    }
+                }
+            }
+            text_entry = text_entry.with_field("span", span_names.join(":"));
+        }
+        for (key, value) in visitor.fields {
+            text_entry = text_entry.with_field(key, value);
+        }
+
+        let path = target_path(&self.events_prefix, metadata.target());
+
+        self.forward(|| MsgSender::new(path).with_component(&[text_entry]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_path() {
+        let prefix = EntityPath::from("logs");
+        assert_eq!(
+            EntityPath::from(target_path(&prefix, "my_crate::net")),
+            EntityPath::from("logs/my_crate/net")
+        );
+        assert!(is_ignored_target("re_sdk"));
+        assert!(is_ignored_target("re_sdk_comms::buffered_client"));
+        assert!(is_ignored_target("re_log_types::msg_bundle"));
+        assert!(!is_ignored_target("re_sdk_example"));
+    }
+}
@@ -0,0 +1,310 @@
+use std::net::SocketAddr;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt as _;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+
+use re_log_types::{LogMsg, MsgId};
+
+enum Cmd {
+    LogMsg(LogMsg),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Send [`LogMsg`]es to a server from within a [`tokio`] runtime.
+///
+/// Unlike [`crate::Client`], this doesn't spawn any threads: the messages are encoded and sent
+/// by a single task on the current runtime.
+///
+/// [`AsyncClient::send`] never blocks: messages are queued up and sent in the background.
+/// The queue is bounded, use [`AsyncClient::send_async`] or [`AsyncClient::ready`] to wait for
+/// room in it rather than losing messages when the server can't keep up.
+/// Use [`AsyncClient::flush`] to wait for them to be written to the socket.
+pub struct AsyncClient {
+    addr: SocketAddr,
+    cmd_tx: mpsc::Sender<Cmd>,
+    drop_if_disconnected: Arc<AtomicBool>,
+
+    /// Messages that were never sent, see [`AsyncClient::num_dropped`].
+    num_dropped: Arc<AtomicU64>,
+
+    join: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl AsyncClient {
+    /// How many messages can be queued up by default, see [`Self::with_capacity`].
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// Connects lazily to the server at `addr`.
+    ///
+    /// Must be called from within a [`tokio`] runtime.
+    pub fn new(addr: SocketAddr) -> Self {
+        Self::with_capacity(addr, Self::DEFAULT_CAPACITY)
+    }
+
+    /// Like [`Self::new`], but with room for `capacity` messages waiting to be sent.
+    pub fn with_capacity(addr: SocketAddr, capacity: usize) -> Self {
+        let (cmd_tx, cmd_rx) = mpsc::channel(capacity.max(1));
+        let drop_if_disconnected = Arc::new(AtomicBool::new(false));
+        let num_dropped = Arc::new(AtomicU64::new(0));
+        let join = tokio::spawn(tcp_sender(
+            addr,
+            cmd_rx,
+            drop_if_disconnected.clone(),
+            num_dropped.clone(),
+        ));
+
+        Self {
+            addr,
+            cmd_tx,
+            drop_if_disconnected,
+            num_dropped,
+            join: Some(join),
+        }
+    }
+
+    /// Queues up a message. Never blocks.
+    ///
+    /// If the queue is full, because the server is too slow or gone, the message is dropped.
+    pub fn send(&self, log_msg: LogMsg) {
+        match self.cmd_tx.try_send(Cmd::LogMsg(log_msg)) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.num_dropped.fetch_add(1, Ordering::Relaxed);
+                re_log::warn_once!(
+                    "Too many messages waiting to be sent to {:?}, dropping some",
+                    self.addr
+                );
+            }
+            // ignoring errors, because the task only quits once we're gone, or if the runtime is shutting down.
+            Err(mpsc::error::TrySendError::Closed(_)) => {}
+        }
+    }
+
+    /// Queues up a message, waiting for room in the queue if needed.
+    pub async fn send_async(&self, log_msg: LogMsg) {
+        // See `send` about ignoring errors.
+        self.cmd_tx.send(Cmd::LogMsg(log_msg)).await.ok();
+    }
+
+    /// Wait until there is room for at least one more message in the queue.
+    ///
+    /// The returned future doesn't borrow the client.
+    pub fn ready(&self) -> impl std::future::Future<Output = ()> {
+        let cmd_tx = self.cmd_tx.clone();
+        async move {
+            cmd_tx.reserve().await.ok();
+        }
+    }
+
+    /// How many messages were lost so far, because the queue was full or because the server was
+    /// gone after [`Self::drop_if_disconnected`].
+    pub fn num_dropped(&self) -> u64 {
+        self.num_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Wait until all messages queued so far have been sent.
+    ///
+    /// The returned future doesn't borrow the client.
+    ///
+    /// This is cancellation-safe: dropping the returned future only stops the waiting,
+    /// the messages are still sent.
+    pub fn flush(&self) -> impl std::future::Future<Output = ()> {
+        let cmd_tx = self.cmd_tx.clone();
+        async move {
+            re_log::debug!("Flushing message queue…");
+            let (flushed_tx, flushed_rx) = oneshot::channel();
+            if cmd_tx.send(Cmd::Flush(flushed_tx)).await.is_ok() && flushed_rx.await.is_ok() {
+                re_log::debug!("Flush complete.");
+            } else {
+                re_log::warn!("Failed to flush pipeline - not all messages were sent.");
+            }
+        }
+    }
+
+    /// Like [`Self::flush`], but blocks the current thread.
+    ///
+    /// This doesn't work from within a current-thread runtime, since the messages are sent from
+    /// that same thread: only a warning is logged then.
+    pub fn flush_blocking(&self) {
+        let flush = || {
+            re_log::debug!("Flushing message queue…");
+            let (flushed_tx, flushed_rx) = oneshot::channel();
+            if self.cmd_tx.blocking_send(Cmd::Flush(flushed_tx)).is_ok()
+                && flushed_rx.blocking_recv().is_ok()
+            {
+                re_log::debug!("Flush complete.");
+            } else {
+                re_log::warn!("Failed to flush pipeline - not all messages were sent.");
+            }
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            Err(_) => flush(),
+            Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(flush);
+            }
+            Ok(_) => {
+                re_log::warn_once!(
+                    "Can't block on an async connection from a current-thread runtime - use flush_async() instead"
+                );
+            }
+        }
+    }
+
+    /// Switch to a mode where we drop messages if disconnected.
+    ///
+    /// Calling this before a flush (or drop) ensures we won't get stuck trying to send
+    /// messages to a closed endpoint, but we will still send all messages to an open endpoint.
+    pub fn drop_if_disconnected(&self) {
+        self.drop_if_disconnected.store(true, Ordering::Relaxed);
+    }
+
+    /// Say goodbye to the server and wait until everything has been sent.
+    ///
+    /// If the server is gone, this keeps on trying to reconnect until
+    /// [`Self::drop_if_disconnected`] was called.
+    /// Returns how many messages were lost, see [`Self::num_dropped`].
+    ///
+    /// Dropping the client also sends everything, but without waiting for it, and giving up if
+    /// the server is gone. This means that if the returned future is cancelled, the remaining
+    /// messages are still sent in the background.
+    pub async fn shutdown(mut self) -> u64 {
+        re_log::debug!("Shutting down the client connection…");
+        self.send_async(LogMsg::Goodbye(MsgId::random())).await;
+        let join = self.join.take();
+        let num_dropped = self.num_dropped.clone();
+        drop(self); // closes the channel, which lets the task finish once everything is sent
+
+        if let Some(join) = join {
+            join.await.ok();
+        }
+        re_log::debug!("Sender has shut down.");
+        num_dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for AsyncClient {
+    fn drop(&mut self) {
+        if self.join.is_some() {
+            self.send(LogMsg::Goodbye(MsgId::random()));
+
+            // If the server is gone we would be retrying forever, in the background.
+            self.drop_if_disconnected();
+        }
+    }
+}
+
+async fn tcp_sender(
+    addr: SocketAddr,
+    mut cmd_rx: mpsc::Receiver<Cmd>,
+    drop_if_disconnected: Arc<AtomicBool>,
+    num_dropped: Arc<AtomicU64>,
+) {
+    let mut stream = None;
+
+    while let Some(cmd) = cmd_rx.recv().await {
+        match cmd {
+            Cmd::LogMsg(log_msg) => {
+                let packet = crate::encode_log_msg(&log_msg);
+                re_log::trace!("Encoded message of size {}", packet.len());
+                if !send_until_success(addr, &mut stream, &packet, &drop_if_disconnected).await {
+                    num_dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Cmd::Flush(flushed_tx) => {
+                if let Some(connected) = &mut stream {
+                    if let Err(err) = AsyncWriteExt::flush(connected).await {
+                        re_log::warn!("Failed to flush: {err}");
+                        stream = None;
+                    }
+                }
+                flushed_tx.send(()).ok();
+            }
+        }
+    }
+
+    let num_dropped = num_dropped.load(Ordering::Relaxed);
+    if num_dropped > 0 {
+        re_log::warn!("{num_dropped} message(s) could not be sent to {addr:?}");
+    }
+    re_log::debug!("Shutting down async TCP sender");
+}
+
+/// Returns `false` if the packet was dropped, see [`AsyncClient::drop_if_disconnected`].
+async fn send_until_success(
+    addr: SocketAddr,
+    stream: &mut Option<TcpStream>,
+    packet: &[u8],
+    drop_if_disconnected: &AtomicBool,
+) -> bool {
+    let Err(err) = send(addr, stream, packet).await else { return true; };
+
+    if drop_if_disconnected.load(Ordering::Relaxed) {
+        re_log::debug_once!("Dropping messages because we're disconnected.");
+        return false;
+    }
+    // If this is the first time we fail to send the message, produce a warning.
+    re_log::warn!("Failed to send message: {err}");
+
+    const MAX_SLEEP_MS: u64 = 3000;
+    let mut sleep_ms = 100;
+
+    loop {
+        tokio::time::sleep(Duration::from_millis(sleep_ms)).await;
+
+        if drop_if_disconnected.load(Ordering::Relaxed) {
+            re_log::debug_once!("Dropping messages because we're disconnected or quitting.");
+            return false;
+        }
+
+        if let Err(new_err) = send(addr, stream, packet).await {
+            sleep_ms = (sleep_ms * 2).min(MAX_SLEEP_MS);
+
+            // Only produce subsequent warnings once we've saturated the back-off
+            if sleep_ms == MAX_SLEEP_MS && new_err.to_string() != err.to_string() {
+                re_log::warn!("Still failing to send message: {new_err}");
+            }
+        } else {
+            return true;
+        }
+    }
+}
+
+/// Connects if needed, then sends the packet.
+///
+/// On failure, the connection is reset and will be re-established on the next call.
+async fn send(
+    addr: SocketAddr,
+    stream: &mut Option<TcpStream>,
+    packet: &[u8],
+) -> std::io::Result<()> {
+    if stream.is_none() {
+        re_log::debug!("Connecting to {addr:?}…");
+        let mut connected = TcpStream::connect(addr).await?;
+        connected
+            .write_all(&crate::PROTOCOL_VERSION.to_le_bytes())
+            .await?;
+        *stream = Some(connected);
+    }
+    let Some(connected) = stream.as_mut() else { unreachable!() };
+
+    re_log::trace!("Sending a packet of size {}…", packet.len());
+    let result = async {
+        connected
+            .write_all(&(packet.len() as u32).to_le_bytes())
+            .await?;
+        connected.write_all(packet).await
+    }
+    .await;
+
+    if result.is_err() {
+        *stream = None;
+    }
+    result
+}
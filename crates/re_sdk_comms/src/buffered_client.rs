@@ -1,9 +1,41 @@
-use std::{net::SocketAddr, thread::JoinHandle};
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
 
 use crossbeam::channel::{select, Receiver, Sender};
 
 use re_log_types::{LogMsg, MsgId};
 
+use crate::spill::SpillFile;
+
+/// Options for the [`Client`].
+#[derive(Clone, Debug)]
+pub struct ClientOptions {
+    /// How long to wait at most between attempts to reconnect to the server.
+    ///
+    /// The delay starts small, and doubles after each failed attempt.
+    pub max_reconnect_delay: Duration,
+
+    /// If set, messages that can't be sent while the server is unreachable are spilled to a
+    /// file in this directory, instead of being held in memory.
+    ///
+    /// They are replayed, in order, once we manage to reconnect. If we never do, the file is
+    /// left behind as an `.rrd` file, which can be opened in the viewer.
+    pub spill_dir: Option<PathBuf>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            max_reconnect_delay: Duration::from_secs(3),
+            spill_dir: None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 struct FlushedMsg;
 
@@ -32,6 +64,10 @@ enum MsgMsg {
 
 enum PacketMsg {
     Packet(Vec<u8>),
+
+    /// An encoded [`re_log_types::BeginRecordingMsg`].
+    BeginRecording(Vec<u8>),
+
     SetAddr(SocketAddr),
     Flush,
 }
@@ -59,6 +95,10 @@ impl Default for Client {
 
 impl Client {
     pub fn new(addr: SocketAddr) -> Self {
+        Self::with_options(addr, Default::default())
+    }
+
+    pub fn with_options(addr: SocketAddr, options: ClientOptions) -> Self {
         // TODO(emilk): keep track of how much memory is in each pipe
         // and apply back-pressure to not use too much RAM.
         let (msg_tx, msg_rx) = crossbeam::channel::unbounded();
@@ -80,7 +120,7 @@ impl Client {
         let send_join = std::thread::Builder::new()
             .name("tcp_sender".into())
             .spawn(move || {
-                tcp_sender(addr, &packet_rx, &send_quit_rx, &flushed_tx);
+                tcp_sender(addr, &options, &packet_rx, &send_quit_rx, &flushed_tx);
                 re_log::debug!("Shutting down TCP sender thread");
             })
             .expect("Failed to spawn thread");
@@ -194,7 +234,11 @@ fn msg_encode(
                         MsgMsg::LogMsg(log_msg) => {
                             let packet = crate::encode_log_msg(log_msg);
                             re_log::trace!("Encoded message of size {}", packet.len());
-                            PacketMsg::Packet(packet)
+                            if matches!(log_msg, LogMsg::BeginRecordingMsg(_)) {
+                                PacketMsg::BeginRecording(packet)
+                            } else {
+                                PacketMsg::Packet(packet)
+                            }
                         }
                         MsgMsg::SetAddr(new_addr) => PacketMsg::SetAddr(*new_addr),
                         MsgMsg::Flush => PacketMsg::Flush,
@@ -218,6 +262,7 @@ fn msg_encode(
 
 fn tcp_sender(
     addr: SocketAddr,
+    options: &ClientOptions,
     packet_rx: &Receiver<PacketMsg>,
     quit_rx: &Receiver<InterruptMsg>,
     flushed_tx: &Sender<FlushedMsg>,
@@ -227,24 +272,47 @@ fn tcp_sender(
     // no longer connected.
     let mut drop_if_disconnected = false;
 
+    // Only used if `options.spill_dir` is set.
+    let mut spill = Spill::new(options.max_reconnect_delay);
+
     loop {
+        // While spilling, wake up regularly to try to reconnect.
+        let reconnect_rx = if spill.file.is_some() {
+            crossbeam::channel::after(spill.backoff.time_until_next_attempt())
+        } else {
+            crossbeam::channel::never()
+        };
+
         select! {
             recv(packet_rx) -> packet_msg => {
                 if let Ok(packet_msg) = packet_msg {
+                    if let PacketMsg::BeginRecording(packet) = &packet_msg {
+                        spill.begin_recording = Some(packet.clone());
+                    }
+
                     match packet_msg {
-                        PacketMsg::Packet(packet) => {
-                            match send_until_success(&mut tcp_client, drop_if_disconnected, &packet, quit_rx) {
-                                Some(InterruptMsg::Quit) => {return;}
-                                Some(InterruptMsg::DropIfDisconnected) => {
-                                    drop_if_disconnected = true;
+                        PacketMsg::Packet(packet) | PacketMsg::BeginRecording(packet) => {
+                            if let Some(spill_dir) = &options.spill_dir {
+                                spill.send(&mut tcp_client, spill_dir, packet);
+                            } else {
+                                match send_until_success(&mut tcp_client, drop_if_disconnected, &packet, options.max_reconnect_delay, quit_rx) {
+                                    Some(InterruptMsg::Quit) => {return;}
+                                    Some(InterruptMsg::DropIfDisconnected) => {
+                                        drop_if_disconnected = true;
+                                    }
+                                    None => {}
                                 }
-                                None => {}
                             }
                         }
                         PacketMsg::SetAddr(new_addr) => {
                             tcp_client.set_addr(new_addr);
                         }
                         PacketMsg::Flush => {
+                            if let Some(spill_dir) = &options.spill_dir {
+                                // Give the server a chance: if it's still unreachable, the
+                                // messages stay in the spill file.
+                                spill.try_replay(&mut tcp_client, spill_dir);
+                            }
                             tcp_client.flush();
                             flushed_tx
                                 .send(FlushedMsg)
@@ -252,16 +320,25 @@ fn tcp_sender(
                         }
                     }
                 } else {
+                    spill.close();
                     return; // channel has closed
                 }
             },
+            recv(reconnect_rx) -> _ => {
+                if let Some(spill_dir) = &options.spill_dir {
+                    spill.try_replay(&mut tcp_client, spill_dir);
+                }
+            },
             recv(quit_rx) -> quit_msg => { match quit_msg {
                 // Don't terminate on receiving a `DropIfDisconnected`. It's a soft-quit that allows
                 // us to flush the pipeline.
                 Ok(InterruptMsg::DropIfDisconnected) => {
                     drop_if_disconnected = true;
                 }
-                _ => return,
+                _ => {
+                    spill.close();
+                    return;
+                }
             }}
         }
     }
@@ -271,6 +348,7 @@ fn send_until_success(
     tcp_client: &mut crate::tcp_client::TcpClient,
     drop_if_disconnected: bool,
     packet: &[u8],
+    max_reconnect_delay: Duration,
     quit_rx: &Receiver<InterruptMsg>,
 ) -> Option<InterruptMsg> {
     // Early exit if tcp_client is disconnected
@@ -287,7 +365,7 @@ fn send_until_success(
         // If this is the first time we fail to send the message, produce a warning.
         re_log::warn!("Failed to send message: {err}");
 
-        let mut sleep_ms = 100;
+        let mut backoff = Backoff::new(max_reconnect_delay);
 
         loop {
             select! {
@@ -295,14 +373,12 @@ fn send_until_success(
                     re_log::debug_once!("Dropping messages because we're disconnected or quitting.");
                     return Some(_quit_msg.unwrap_or(InterruptMsg::Quit));
                 }
-                default(std::time::Duration::from_millis(sleep_ms)) => {
+                default(backoff.time_until_next_attempt()) => {
                     if let Err(new_err) = tcp_client.send(packet) {
-                        const MAX_SLEEP_MS : u64 = 3000;
-
-                        sleep_ms = (sleep_ms * 2).min(MAX_SLEEP_MS);
+                        backoff.failed();
 
                         // Only produce subsequent warnings once we've saturated the back-off
-                        if sleep_ms == MAX_SLEEP_MS && new_err.to_string() != err.to_string() {
+                        if backoff.is_saturated() && new_err.to_string() != err.to_string() {
                             re_log::warn!("Still failing to send message: {err}");
                         }
                     } else {
@@ -315,3 +391,200 @@ fn send_until_success(
         None
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// Exponential back-off between reconnection attempts.
+struct Backoff {
+    delay: Duration,
+    max_delay: Duration,
+    next_attempt: Instant,
+}
+
+impl Backoff {
+    const INITIAL_DELAY: Duration = Duration::from_millis(100);
+
+    fn new(max_delay: Duration) -> Self {
+        Self {
+            delay: Self::INITIAL_DELAY,
+            max_delay,
+            next_attempt: Instant::now() + Self::INITIAL_DELAY,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.max_delay);
+    }
+
+    fn failed(&mut self) {
+        self.delay = (self.delay * 2).min(self.max_delay);
+        self.next_attempt = Instant::now() + self.delay;
+    }
+
+    fn is_saturated(&self) -> bool {
+        self.delay >= self.max_delay
+    }
+
+    fn time_until_next_attempt(&self) -> Duration {
+        self.next_attempt.saturating_duration_since(Instant::now())
+    }
+}
+
+/// While the server is unreachable, packets go to a [`SpillFile`] instead, which is replayed
+/// once we manage to reconnect.
+struct Spill {
+    /// Set while we are disconnected.
+    file: Option<SpillFile>,
+    backoff: Backoff,
+
+    /// The last [`re_log_types::BeginRecordingMsg`] packet, sent or spilled.
+    ///
+    /// The server might have been restarted while we were disconnected, so this is sent again
+    /// before the spilled packets, which might belong to a recording it doesn't know about.
+    begin_recording: Option<Vec<u8>>,
+}
+
+impl Spill {
+    fn new(max_reconnect_delay: Duration) -> Self {
+        Self {
+            file: None,
+            backoff: Backoff::new(max_reconnect_delay),
+            begin_recording: None,
+        }
+    }
+
+    /// Sends the packet, or spills it if we are (or just got) disconnected.
+    ///
+    /// Never blocks on the server.
+    fn send(
+        &mut self,
+        tcp_client: &mut crate::tcp_client::TcpClient,
+        spill_dir: &std::path::Path,
+        packet: Vec<u8>,
+    ) {
+        if self.file.is_none() {
+            let Err(err) = tcp_client.send(&packet) else { return; };
+            re_log::warn!("Failed to send message: {err}. Spilling to disk until we reconnect.");
+            self.backoff.reset();
+        }
+        self.spill(spill_dir, &packet);
+    }
+
+    fn spill(&mut self, spill_dir: &std::path::Path, packet: &[u8]) {
+        if self.file.is_none() {
+            match SpillFile::create(spill_dir) {
+                Ok(file) => self.file = Some(file),
+                Err(err) => {
+                    re_log::error_once!("Failed to create spill file: {err}. Dropping messages.");
+                    return;
+                }
+            }
+        }
+
+        if let Some(file) = &mut self.file {
+            if let Err(err) = file.append_packet(packet) {
+                re_log::error_once!("Failed to spill message to {:?}: {err}", file.path());
+            }
+        }
+    }
+
+    /// If we are spilling, try to reconnect and send everything that was spilled.
+    fn try_replay(
+        &mut self,
+        tcp_client: &mut crate::tcp_client::TcpClient,
+        spill_dir: &std::path::Path,
+    ) {
+        if self.file.is_none() {
+            return;
+        }
+        if tcp_client.connect().is_err() {
+            self.backoff.failed();
+            return;
+        }
+        if let Some(begin_recording) = &self.begin_recording {
+            if tcp_client.send(begin_recording).is_err() {
+                self.backoff.failed();
+                return;
+            }
+        }
+
+        let Some(file) = self.file.take() else { return; };
+        let num_msgs = file.num_msgs();
+        let path = match file.finish() {
+            Ok(path) => path,
+            Err(err) => {
+                re_log::error!("Failed to finish spill file: {err}. Some messages were lost.");
+                return;
+            }
+        };
+
+        let packets = match crate::spill::read_packets(&path) {
+            Ok(packets) => packets,
+            Err(err) => {
+                re_log::error!("Failed to read back spill file {path:?}: {err}");
+                return;
+            }
+        };
+
+        let mut num_sent = 0;
+        for packet in packets {
+            let packet = match packet {
+                Ok(packet) => packet,
+                Err(err) => {
+                    re_log::error!("Failed to read back spill file {path:?}: {err}");
+                    break;
+                }
+            };
+
+            if self.file.is_some() {
+                // We lost the connection again: spill the rest to a new file.
+                self.spill(spill_dir, &packet);
+            } else if tcp_client.send(&packet).is_ok() {
+                num_sent += 1;
+            } else {
+                self.backoff.failed();
+                self.spill(spill_dir, &packet);
+            }
+        }
+
+        if self.file.is_none() {
+            re_log::info!(
+                "Reconnected, and sent the {num_sent} messages that were spilled to disk."
+            );
+            self.backoff.reset();
+        } else {
+            re_log::debug!(
+                "Sent {num_sent}/{num_msgs} spilled messages before losing the connection again."
+            );
+        }
+
+        if let Err(err) = std::fs::remove_file(&path) {
+            re_log::warn!("Failed to remove spill file {path:?}: {err}");
+        }
+    }
+
+    /// Called on shutdown: whatever is left on disk stays there.
+    fn close(&mut self) {
+        let Some(file) = self.file.take() else { return; };
+        let num_msgs = file.num_msgs();
+        match file.finish() {
+            Ok(path) => match crate::spill::convert_to_rrd(&path) {
+                Ok(rrd_path) => {
+                    re_log::warn!(
+                        "{num_msgs} messages could not be sent to the server. They were saved to {rrd_path:?}"
+                    );
+                }
+                Err(err) => {
+                    re_log::warn!(
+                        "{num_msgs} messages could not be sent to the server. They were left in {path:?}, failed to convert them to an .rrd file: {err}"
+                    );
+                }
+            },
+            Err(err) => {
+                re_log::error!(
+                    "Failed to finish spill file: {err}. {num_msgs} messages were lost."
+                );
+            }
+        }
+    }
+}
@@ -11,7 +11,16 @@ pub(crate) mod tcp_client;
 mod buffered_client;
 
 #[cfg(feature = "client")]
-pub use buffered_client::Client;
+mod spill;
+
+#[cfg(feature = "client")]
+pub use buffered_client::{Client, ClientOptions};
+
+#[cfg(feature = "async_client")]
+mod async_client;
+
+#[cfg(feature = "async_client")]
+pub use async_client::AsyncClient;
 
 #[cfg(feature = "server")]
 mod server;
@@ -23,7 +32,9 @@ use re_log_types::LogMsg;
 
 pub type Result<T> = anyhow::Result<T>;
 
-pub const PROTOCOL_VERSION: u16 = 0;
+/// Sent first on every connection. Bump this whenever the encoding of [`LogMsg`] changes, so
+/// that mismatching clients and servers refuse each other rather than mis-decoding messages.
+pub const PROTOCOL_VERSION: u16 = 1;
 
 pub const DEFAULT_SERVER_PORT: u16 = 9876;
 
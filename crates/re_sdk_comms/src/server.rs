@@ -1,9 +1,8 @@
-//! TODO(emilk): use tokio instead
-
 use std::time::Instant;
 
 use anyhow::Context as _;
 use rand::{Rng as _, SeedableRng};
+use tokio::net::{TcpListener, TcpStream};
 
 use re_log_types::{LogMsg, TimePoint, TimeType, TimelineName};
 use re_smart_channel::{Receiver, Sender};
@@ -29,35 +28,25 @@ impl Default for ServerOptions {
 
 /// Listen to multiple SDK:s connecting to us over TCP.
 ///
+/// Each client is handled by its own task on the current [`tokio`] runtime,
+/// so this must be called from within one.
+///
 /// ``` no_run
+/// # async fn example() -> anyhow::Result<()> {
 /// # use re_sdk_comms::{serve, ServerOptions};
-/// let log_msg_rx = serve(80, ServerOptions::default())?;
-/// # Ok::<(), anyhow::Error>(())
+/// let log_msg_rx = serve(80, ServerOptions::default()).await?;
+/// # Ok(()) }
 /// ```
-pub fn serve(port: u16, options: ServerOptions) -> anyhow::Result<Receiver<LogMsg>> {
+pub async fn serve(port: u16, options: ServerOptions) -> anyhow::Result<Receiver<LogMsg>> {
     let bind_addr = format!("0.0.0.0:{port}");
 
-    let listener = std::net::TcpListener::bind(&bind_addr)
+    let listener = TcpListener::bind(&bind_addr)
+        .await
         .with_context(|| format!("Failed to bind address {bind_addr:?}"))?;
 
     let (tx, rx) = re_smart_channel::smart_channel(re_smart_channel::Source::TcpServer { port });
 
-    std::thread::Builder::new()
-        .name("sdk-server".into())
-        .spawn(move || {
-            for stream in listener.incoming() {
-                match stream {
-                    Ok(stream) => {
-                        let tx = tx.clone();
-                        spawn_client(stream, tx, options);
-                    }
-                    Err(err) => {
-                        re_log::warn!("Failed to accept incoming SDK client: {err}");
-                    }
-                }
-            }
-        })
-        .expect("Failed to spawn thread");
+    tokio::spawn(listen_for_new_clients(listener, options, tx));
 
     if options.quiet {
         re_log::debug!(
@@ -72,37 +61,45 @@ pub fn serve(port: u16, options: ServerOptions) -> anyhow::Result<Receiver<LogMs
     Ok(rx)
 }
 
-fn spawn_client(stream: std::net::TcpStream, tx: Sender<LogMsg>, options: ServerOptions) {
-    std::thread::Builder::new()
-        .name(format!(
-            "sdk-server-client-handler-{:?}",
-            stream.peer_addr()
-        ))
-        .spawn(move || {
-            if options.quiet {
-                re_log::debug!("New SDK client connected: {:?}", stream.peer_addr());
-            } else {
-                re_log::info!("New SDK client connected: {:?}", stream.peer_addr());
+async fn listen_for_new_clients(listener: TcpListener, options: ServerOptions, tx: Sender<LogMsg>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let tx = tx.clone();
+                spawn_client(stream, tx, options);
             }
-
-            if let Err(err) = run_client(stream, &tx, options) {
-                re_log::warn!("Closing connection to client: {err}");
+            Err(err) => {
+                re_log::warn!("Failed to accept incoming SDK client: {err}");
             }
-        })
-        .expect("Failed to spawn thread");
+        }
+    }
 }
 
-fn run_client(
-    mut stream: std::net::TcpStream,
+fn spawn_client(stream: TcpStream, tx: Sender<LogMsg>, options: ServerOptions) {
+    tokio::spawn(async move {
+        if options.quiet {
+            re_log::debug!("New SDK client connected: {:?}", stream.peer_addr());
+        } else {
+            re_log::info!("New SDK client connected: {:?}", stream.peer_addr());
+        }
+
+        if let Err(err) = run_client(stream, &tx, options).await {
+            re_log::warn!("Closing connection to client: {err}");
+        }
+    });
+}
+
+async fn run_client(
+    mut stream: TcpStream,
     tx: &Sender<LogMsg>,
     options: ServerOptions,
 ) -> anyhow::Result<()> {
     #![allow(clippy::read_zero_byte_vec)] // false positive: https://github.com/rust-lang/rust-clippy/issues/9274
 
-    use std::io::Read as _;
+    use tokio::io::AsyncReadExt as _;
 
     let mut client_version = [0_u8; 2];
-    stream.read_exact(&mut client_version)?;
+    stream.read_exact(&mut client_version).await?;
     let client_version = u16::from_le_bytes(client_version);
 
     match client_version.cmp(&crate::PROTOCOL_VERSION) {
@@ -129,11 +126,11 @@ fn run_client(
 
     loop {
         let mut packet_size = [0_u8; 4];
-        stream.read_exact(&mut packet_size)?;
+        stream.read_exact(&mut packet_size).await?;
         let packet_size = u32::from_le_bytes(packet_size);
 
         packet.resize(packet_size as usize, 0_u8);
-        stream.read_exact(&mut packet)?;
+        stream.read_exact(&mut packet).await?;
 
         re_log::trace!("Received log message of size {packet_size}.");
 
@@ -146,7 +143,7 @@ fn run_client(
             return Ok(());
         }
 
-        if congestion_manager.should_send(&msg) {
+        if let Some(msg) = congestion_manager.filter(msg) {
             tx.send(msg)?;
         } else {
             re_log::warn_once!(
@@ -185,22 +182,47 @@ impl CongestionManager {
         self.throttling.register_latency(latency_sec);
     }
 
-    pub fn should_send(&mut self, msg: &LogMsg) -> bool {
+    /// Returns the part of the message that should be sent, if any.
+    pub fn filter(&mut self, msg: LogMsg) -> Option<LogMsg> {
         if self.throttling.accept_rate == 1.0 {
-            return true; // early out for common-case
+            return Some(msg); // early out for common-case
         }
 
         #[allow(clippy::match_same_arms)]
         match msg {
-            LogMsg::BeginRecordingMsg(_) | LogMsg::EntityPathOpMsg(_) | LogMsg::Goodbye(_) => true, // we don't want to drop any of these
+            LogMsg::BeginRecordingMsg(_) | LogMsg::EntityPathOpMsg(_) | LogMsg::Goodbye(_) => {
+                Some(msg) // we don't want to drop any of these
+            }
+
+            // Every row of a batched message is decided on separately.
+            LogMsg::ArrowMsg(arrow_msg) => {
+                let time_points = match arrow_msg.time_points() {
+                    Ok(time_points) => time_points,
+                    Err(err) => {
+                        re_log::error_once!("Failed to parse an Arrow Message - dropping this message, and maybe more. {err}");
+                        return None;
+                    }
+                };
+
+                let keep = time_points
+                    .iter()
+                    .map(|time_point| self.should_send_time_point(time_point))
+                    .collect::<Vec<_>>();
 
-            LogMsg::ArrowMsg(arrow_msg) => match arrow_msg.time_point() {
-                Ok(time_point) => self.should_send_time_point(&time_point),
-                Err(err) => {
-                    re_log::error_once!("Failed to parse an Arrow Message - dropping this message, and maybe more. {err}");
-                    false
+                if keep.iter().all(|keep| *keep) {
+                    Some(LogMsg::ArrowMsg(arrow_msg))
+                } else if !keep.iter().any(|keep| *keep) {
+                    None
+                } else {
+                    match arrow_msg.filter_rows(&keep) {
+                        Ok(arrow_msg) => Some(LogMsg::ArrowMsg(arrow_msg)),
+                        Err(err) => {
+                            re_log::error_once!("Failed to filter an Arrow Message - dropping this message, and maybe more. {err}");
+                            None
+                        }
+                    }
                 }
-            },
+            }
         }
     }
 
@@ -0,0 +1,153 @@
+//! Durable buffering of the messages that couldn't be sent while the server was unreachable.
+//!
+//! The packets are written to disk as they are, each prefixed with its length, so that replaying
+//! them is only a matter of copying them back to the socket.
+//! Should the server never come back, the file is converted to a regular `.rrd` file, which can
+//! be opened in the viewer.
+
+use std::io::{Read as _, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::Context as _;
+
+use re_log_types::encoding::Encoder;
+
+/// Numbers the spill files of this process.
+static NEXT_SPILL_FILE_NR: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) struct SpillFile {
+    path: PathBuf,
+    writer: std::io::BufWriter<std::fs::File>,
+    num_msgs: usize,
+}
+
+impl SpillFile {
+    /// Creates a new, uniquely named, spill file in `dir`.
+    pub fn create(dir: &Path) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir).with_context(|| format!("Path: {dir:?}"))?;
+
+        // Skipping over the files left behind by an earlier process with the same id.
+        let (path, file) = loop {
+            let file_nr = NEXT_SPILL_FILE_NR.fetch_add(1, Ordering::Relaxed);
+            let path = dir.join(format!("spill_{}_{file_nr}.packets", std::process::id()));
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => break (path, file),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(err) => return Err(err).with_context(|| format!("Path: {path:?}")),
+            }
+        };
+
+        re_log::debug!("Spilling messages to {path:?}…");
+
+        Ok(Self {
+            path,
+            writer: std::io::BufWriter::new(file),
+            num_msgs: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn num_msgs(&self) -> usize {
+        self.num_msgs
+    }
+
+    /// Appends an encoded packet, as produced by [`crate::encode_log_msg`].
+    pub fn append_packet(&mut self, packet: &[u8]) -> anyhow::Result<()> {
+        self.writer
+            .write_all(&(packet.len() as u32).to_le_bytes())?;
+        self.writer.write_all(packet)?;
+        self.num_msgs += 1;
+        Ok(())
+    }
+
+    /// Finishes writing the file, and returns its path.
+    pub fn finish(mut self) -> anyhow::Result<PathBuf> {
+        self.writer.flush()?;
+        Ok(self.path)
+    }
+}
+
+/// Reads back a finished spill file, as packets ready to be sent.
+pub(crate) fn read_packets(
+    path: &Path,
+) -> anyhow::Result<impl Iterator<Item = anyhow::Result<Vec<u8>>>> {
+    let file = std::fs::File::open(path).with_context(|| format!("Path: {path:?}"))?;
+    let mut reader = std::io::BufReader::new(file);
+
+    Ok(std::iter::from_fn(move || {
+        let mut len = [0_u8; 4];
+        match reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return None,
+            Err(err) => return Some(Err(err.into())),
+        }
+
+        let mut packet = vec![0_u8; u32::from_le_bytes(len) as usize];
+        Some(
+            reader
+                .read_exact(&mut packet)
+                .map(|()| packet)
+                .context("Truncated packet"),
+        )
+    }))
+}
+
+/// Converts a finished spill file to an `.rrd` file next to it, and removes it.
+///
+/// Returns the path of the `.rrd` file.
+pub(crate) fn convert_to_rrd(path: &Path) -> anyhow::Result<PathBuf> {
+    let rrd_path = path.with_extension("rrd");
+    let file = std::fs::File::create(&rrd_path).with_context(|| format!("Path: {rrd_path:?}"))?;
+    let mut encoder = Encoder::new(std::io::BufWriter::new(file))?;
+    for packet in read_packets(path)? {
+        encoder.append(&crate::decode_log_msg(&packet?)?)?;
+    }
+    encoder.finish()?;
+
+    std::fs::remove_file(path).with_context(|| format!("Path: {path:?}"))?;
+    Ok(rrd_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use re_log_types::{LogMsg, MsgId};
+
+    #[test]
+    fn spill_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let msg_ids = [MsgId::random(), MsgId::random()];
+
+        let mut file = SpillFile::create(dir.path()).unwrap();
+        for msg_id in msg_ids {
+            file.append_packet(&crate::encode_log_msg(&LogMsg::Goodbye(msg_id)))
+                .unwrap();
+        }
+        assert_eq!(2, file.num_msgs());
+        let path = file.finish().unwrap();
+
+        let read_back = read_packets(&path)
+            .unwrap()
+            .map(|packet| crate::decode_log_msg(&packet.unwrap()).unwrap().id())
+            .collect::<Vec<_>>();
+        assert_eq!(msg_ids.to_vec(), read_back);
+
+        let rrd_path = convert_to_rrd(&path).unwrap();
+        assert!(!path.exists());
+        let decoded = re_log_types::encoding::Decoder::new(std::fs::File::open(rrd_path).unwrap())
+            .unwrap()
+            .map(|log_msg| log_msg.unwrap().id())
+            .collect::<Vec<_>>();
+        assert_eq!(msg_ids.to_vec(), decoded);
+    }
+}
@@ -35,7 +35,7 @@ fn apply_design_tokens(ctx: &egui::Context) -> DesignTokens {
         let mut font_definitions = egui::FontDefinitions::default();
         font_definitions.font_data.insert(
             "Inter-Medium".into(),
-            egui::FontData::from_static(include_bytes!("../data/Inter-Medium.otf")),
+            egui::FontData::from_static(crate::FONT_DATA),
         );
         font_definitions
             .families
@@ -29,6 +29,11 @@ pub const CUSTOM_WINDOW_DECORATIONS: bool = false; // !FULLSIZE_CONTENT; // TODO
 /// close/maximize/minimize buttons and app title.
 pub const NATIVE_WINDOW_BAR: bool = !FULLSIZE_CONTENT && !CUSTOM_WINDOW_DECORATIONS;
 
+/// The font of all ui text, as the contents of an OpenType file.
+///
+/// Also used for text that is not drawn by egui, like labels in 3D views.
+pub const FONT_DATA: &[u8] = include_bytes!("../data/Inter-Medium.otf");
+
 // ----------------------------------------------------------------------------
 
 pub struct TopBarStyle {
@@ -45,6 +45,11 @@ enum TimeControlCommand {
 #[derive(Clone, Copy, Default)]
 pub struct StartupOptions {
     pub memory_limit: re_memory::MemoryLimit,
+
+    /// How much memory to spend on keeping data on the gpu across frames.
+    ///
+    /// When unlimited, a default budget of 2GB is used.
+    pub gpu_memory_limit: re_memory::MemoryLimit,
 }
 
 // ----------------------------------------------------------------------------
@@ -457,7 +462,9 @@ impl eframe::App for App {
 
         self.purge_memory_if_needed();
 
-        self.state.cache.new_frame();
+        self.state
+            .cache
+            .new_frame(&self.startup_options.gpu_memory_limit);
 
         self.receive_messages(egui_ctx);
 
@@ -525,6 +532,14 @@ impl eframe::App for App {
 
                     render_ctx.before_submit();
                 }
+
+                if let Some(rec_cfg) = self
+                    .state
+                    .recording_configs
+                    .get_mut(&self.state.selected_rec_id)
+                {
+                    apply_pending_path_ops(log_db, &mut rec_cfg.pending_path_ops);
+                }
             });
 
         if re_ui::CUSTOM_WINDOW_DECORATIONS {
@@ -548,6 +563,23 @@ impl eframe::App for App {
     }
 }
 
+/// Applies the edits requested from the UI, e.g. from the entity context menu.
+///
+/// They are added as regular messages, so that they are part of the recording when it is saved.
+/// They apply at all times, so they are timeless.
+fn apply_pending_path_ops(log_db: &mut LogDb, path_ops: &mut Vec<re_log_types::PathOp>) {
+    for path_op in path_ops.drain(..) {
+        let msg = LogMsg::EntityPathOpMsg(re_log_types::EntityPathOpMsg {
+            msg_id: re_log_types::MsgId::random(),
+            time_point: re_log_types::TimePoint::timeless(),
+            path_op,
+        });
+        if let Err(err) = log_db.add(msg) {
+            re_log::error!("Failed to edit the recording: {err}");
+        }
+    }
+}
+
 fn paint_background_fill(ui: &mut egui::Ui) {
     // This is required because the streams view (time panel)
     // has rounded top corners, which leaves a gap.
@@ -630,7 +662,8 @@ impl App {
             let is_new_recording = if let LogMsg::BeginRecordingMsg(msg) = &msg {
                 re_log::debug!("Opening a new recording: {:?}", msg.info);
                 self.state.selected_rec_id = msg.info.recording_id;
-                true
+                // The SDK sends a new `BeginRecordingMsg` when the recording properties change.
+                !self.log_dbs.contains_key(&msg.info.recording_id)
             } else {
                 false
             };
@@ -921,6 +954,11 @@ impl AppState {
 
         let rec_cfg =
             recording_config_entry(recording_configs, *selected_rec_id, data_source, log_db);
+        if let Some(rec_info) = log_db.recording_info() {
+            rec_cfg
+                .time_ctrl
+                .set_timeline_units(&rec_info.timeline_units);
+        }
         let selected_app_id = log_db
             .recording_info()
             .map_or_else(ApplicationId::unknown, |rec_info| {
@@ -1487,10 +1525,15 @@ fn recordings_menu(ui: &mut egui::Ui, app: &mut App) {
         } else {
             "<UNKNOWN>".to_owned()
         };
-        if ui
-            .radio(app.state.selected_rec_id == log_db.recording_id(), info)
-            .clicked()
-        {
+        let mut response = ui.radio(app.state.selected_rec_id == log_db.recording_id(), info);
+        if let Some(rec_info) = log_db.recording_info() {
+            if !rec_info.properties.is_empty() {
+                response = response.on_hover_ui(|ui| {
+                    crate::ui::data_ui::recording_properties_ui(ui, &rec_info.properties);
+                });
+            }
+        }
+        if response.clicked() {
             app.state.selected_rec_id = log_db.recording_id();
         }
     }
@@ -129,14 +129,23 @@ pub(crate) fn customize_eframe(cc: &eframe::CreationContext<'_>) -> re_ui::ReUi
 
         let paint_callback_resources = &mut render_state.renderer.write().paint_callback_resources;
 
-        paint_callback_resources.insert(RenderContext::new(
+        let render_ctx = RenderContext::new(
             render_state.device.clone(),
             render_state.queue.clone(),
             RenderContextConfig {
                 output_format_color: render_state.target_format,
                 hardware_tier: crate::hardware_tier(),
             },
-        ));
+        );
+        // Labels in 3D views are drawn by `re_renderer`, in the same font as the ui.
+        if let Err(err) = render_ctx
+            .glyph_atlas
+            .write()
+            .set_font(re_ui::FONT_DATA.to_vec())
+        {
+            re_log::error!("Failed to load the font for labels: {err}");
+        }
+        paint_callback_resources.insert(render_ctx);
     }
 
     re_ui::ReUi::load_and_apply(&cc.egui_ctx)
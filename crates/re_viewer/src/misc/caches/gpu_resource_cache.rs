@@ -0,0 +1,152 @@
+use std::any::{Any, TypeId};
+
+use re_arrow_store::RowIndex;
+use re_log_types::{component_types::TensorId, ComponentPath};
+
+// ----------------------------------------------------------------------------
+
+/// Identifies the store data a gpu resource was derived from.
+///
+/// Rows in the store never change, so a resource stays valid for as long as its row is around.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GpuResourceKey {
+    /// Derived from a single row of a component.
+    Row {
+        component_path: ComponentPath,
+        row_index: RowIndex,
+
+        /// Tells apart several resources derived from the same row.
+        ///
+        /// E.g. an instance key, or a hash of further inputs like the rows of joined components.
+        variant: u64,
+    },
+
+    /// Derived from a tensor, which may be referenced by several rows.
+    Tensor {
+        tensor_id: TensorId,
+
+        /// Tells apart several resources derived from the same tensor, e.g. with different annotations.
+        variant: u64,
+    },
+}
+
+/// A resource that can be kept in the [`GpuResourceCache`].
+pub trait GpuResource: Any + Send + Sync {
+    /// Estimate of the memory held by this resource, in bytes.
+    fn memory_used(&self) -> u64;
+}
+
+struct CachedResource {
+    /// `None` if creating the resource failed.
+    resource: Option<Box<dyn Any + Send + Sync>>,
+    memory_used: u64,
+
+    /// When [`GpuResourceCache::generation`] was we last used?
+    last_use_generation: u64,
+}
+
+/// Gpu resources derived from data of the store, kept alive while the data is in use.
+///
+/// This is the one place where the viewer keeps data on the gpu across frames:
+/// images, meshes, large point clouds and line strips, volumes and voxel grids.
+///
+/// Since the key is the store row, scrubbing back and forth on the timeline reuses
+/// resources instead of uploading the same data again.
+/// Least recently used resources are evicted once over the memory budget,
+/// resources of rows removed by the store's garbage collection are evicted on [`Self::purge_memory`].
+#[derive(Default)]
+pub struct GpuResourceCache {
+    resources: ahash::HashMap<(GpuResourceKey, TypeId), CachedResource>,
+    memory_used: u64,
+    generation: u64,
+}
+
+impl GpuResourceCache {
+    /// Memory budget used if the user didn't configure one, see [`Self::new_frame`].
+    ///
+    /// Picked to fit comfortably on most dedicated gpus.
+    /// Integrated gpus share system memory, so this also shouldn't starve the rest of the viewer.
+    pub const DEFAULT_MEMORY_BUDGET: u64 = 2_000_000_000;
+
+    /// Returns the resource for `key`, creating it if it isn't cached yet.
+    ///
+    /// Failure to create a resource is cached as well, so it isn't retried every frame.
+    pub fn get_or_create<R: GpuResource>(
+        &mut self,
+        key: GpuResourceKey,
+        create: impl FnOnce() -> Option<R>,
+    ) -> Option<&R> {
+        let generation = self.generation;
+        let memory_used = &mut self.memory_used;
+        let cached = self
+            .resources
+            .entry((key, TypeId::of::<R>()))
+            .or_insert_with(|| {
+                let resource = create();
+                let resource_memory_used = resource.as_ref().map_or(0, GpuResource::memory_used);
+                *memory_used += resource_memory_used;
+                CachedResource {
+                    resource: resource
+                        .map(|resource| Box::new(resource) as Box<dyn Any + Send + Sync>),
+                    memory_used: resource_memory_used,
+                    last_use_generation: generation,
+                }
+            });
+        cached.last_use_generation = generation;
+
+        cached
+            .resource
+            .as_ref()
+            .and_then(|resource| resource.downcast_ref::<R>())
+    }
+
+    /// Call once per frame to (potentially) evict resources that weren't used recently.
+    ///
+    /// `max_memory_use` is the budget in bytes; resources used in the last frame are kept even when over it.
+    pub fn new_frame(&mut self, max_memory_use: u64) {
+        if self.memory_used > max_memory_use {
+            self.evict_unused(max_memory_use);
+        }
+
+        self.generation += 1;
+    }
+
+    /// Evict everything not used in this frame.
+    ///
+    /// Rows removed by garbage collection can't be queried anymore, so this gets rid of their resources.
+    pub fn purge_memory(&mut self) {
+        self.evict_unused(0);
+    }
+
+    /// Evicts least recently used resources, until memory use is at most `max_memory_use`.
+    ///
+    /// Resources used in this frame are never evicted.
+    fn evict_unused(&mut self, max_memory_use: u64) {
+        crate::profile_function!();
+
+        let before = self.memory_used;
+
+        let mut unused = self
+            .resources
+            .iter()
+            .filter(|(_, cached)| cached.last_use_generation < self.generation)
+            .map(|(key, cached)| (cached.last_use_generation, key.clone()))
+            .collect::<Vec<_>>();
+        unused.sort_by_key(|(last_use_generation, _)| *last_use_generation);
+
+        for (_, key) in unused {
+            if self.memory_used <= max_memory_use {
+                break;
+            }
+            if let Some(cached) = self.resources.remove(&key) {
+                self.memory_used -= cached.memory_used;
+            }
+        }
+
+        re_log::debug!(
+            "Flushed gpu resource cache. Before: {:.2} GB. After: {:.2} GB",
+            before as f64 / 1e9,
+            self.memory_used as f64 / 1e9,
+        );
+    }
+}
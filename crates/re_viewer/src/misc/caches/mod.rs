@@ -1,35 +1,39 @@
-mod mesh_cache;
+mod gpu_resource_cache;
 mod tensor_image_cache;
 
+pub use gpu_resource_cache::{GpuResource, GpuResourceCache, GpuResourceKey};
 use re_log_types::component_types::{self, TensorTrait};
 pub use tensor_image_cache::{AsDynamicImage, TensorImageView};
 
 /// Does memoization of different things for the immediate mode UI.
 #[derive(Default)]
 pub struct Caches {
-    /// For displaying images efficiently in immediate mode.
-    pub image: tensor_image_cache::ImageCache,
-
-    /// For displaying meshes efficiently in immediate mode.
-    pub mesh: mesh_cache::MeshCache,
+    /// For keeping images, meshes, large point clouds etc. on the gpu while their data doesn't change.
+    pub gpu_resources: gpu_resource_cache::GpuResourceCache,
 
     pub tensor_stats: nohash_hasher::IntMap<component_types::TensorId, TensorStats>,
 }
 
 impl Caches {
     /// Call once per frame to potentially flush the cache(s).
-    pub fn new_frame(&mut self) {
-        let max_image_cache_use = 1_000_000_000;
-        self.image.new_frame(max_image_cache_use);
+    ///
+    /// `gpu_memory_limit` is the budget of [`Self::gpu_resources`],
+    /// [`GpuResourceCache::DEFAULT_MEMORY_BUDGET`] if unlimited.
+    pub fn new_frame(&mut self, gpu_memory_limit: &re_memory::MemoryLimit) {
+        let max_gpu_resource_cache_use = gpu_memory_limit
+            .limit
+            .map_or(GpuResourceCache::DEFAULT_MEMORY_BUDGET, |limit| {
+                limit.max(0) as u64
+            });
+        self.gpu_resources.new_frame(max_gpu_resource_cache_use);
     }
 
     pub fn purge_memory(&mut self) {
         let Self {
-            image,
+            gpu_resources,
             tensor_stats,
-            mesh: _, // TODO(emilk)
         } = self;
-        image.purge_memory();
+        gpu_resources.purge_memory();
         tensor_stats.clear();
     }
 
@@ -3,15 +3,13 @@ use std::sync::Arc;
 use egui::{Color32, ColorImage};
 use egui_extras::RetainedImage;
 use image::DynamicImage;
-use re_log_types::{
-    component_types::{self, ClassId, TensorData, TensorDataMeaning, TensorTrait},
-    MsgId,
-};
+use re_log_types::component_types::{self, ClassId, TensorData, TensorDataMeaning, TensorTrait};
 use re_renderer::{
     resource_managers::{GpuTexture2DHandle, Texture2DCreationDesc},
     RenderContext,
 };
 
+use super::{GpuResource, GpuResourceCache, GpuResourceKey};
 use crate::ui::{Annotations, DefaultColor, MISSING_ANNOTATIONS};
 
 // ---
@@ -43,102 +41,43 @@ pub struct TensorImageView<'store, 'cache> {
     pub texture_handle: Option<GpuTexture2DHandle>,
 }
 
-// Use this for the cache index so that we don't cache across
-// changes to the annotations
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct ImageCacheKey {
-    tensor_id: component_types::TensorId,
-    annotation_msg_id: MsgId,
-}
-
-impl nohash_hasher::IsEnabled for ImageCacheKey {}
-
-// required for [`nohash_hasher`].
-#[allow(clippy::derive_hash_xor_eq)]
-impl std::hash::Hash for ImageCacheKey {
-    #[inline]
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let msg_hash = self.tensor_id.0.as_u128() as u64;
-        let annotation_hash = (self.annotation_msg_id.as_u128() >> 1) as u64;
-        state.write_u64(msg_hash ^ annotation_hash);
-    }
-}
-
-#[derive(Default)]
-pub struct ImageCache {
-    images: nohash_hasher::IntMap<ImageCacheKey, CachedImage>,
-    memory_used: u64,
-    generation: u64,
-}
-
-impl ImageCache {
-    pub(crate) fn get_view_with_annotations<'store, 'cache>(
+impl GpuResourceCache {
+    pub(crate) fn tensor_image_view_with_annotations<'store, 'cache>(
         &'cache mut self,
         tensor: &'store dyn AsDynamicImage,
         annotations: &'store Arc<Annotations>,
         render_ctx: &mut RenderContext,
     ) -> TensorImageView<'store, 'cache> {
-        let ci = self
-            .images
-            .entry(ImageCacheKey {
-                tensor_id: tensor.id(),
-                annotation_msg_id: annotations.msg_id,
-            })
-            .or_insert_with(|| {
-                let debug_name = format!("tensor {:?}", tensor.shape());
-                let ci = CachedImage::from_tensor(render_ctx, debug_name, tensor, annotations);
-                self.memory_used += ci.memory_used;
-                ci
-            });
-        ci.last_use_generation = self.generation;
+        // Don't reuse images across changes to the annotations.
+        let key = GpuResourceKey::Tensor {
+            tensor_id: tensor.id(),
+            variant: annotations.msg_id.as_u128() as u64,
+        };
+        let ci = self.get_or_create(key, || {
+            let debug_name = format!("tensor {:?}", tensor.shape());
+            Some(CachedImage::from_tensor(
+                render_ctx,
+                debug_name,
+                tensor,
+                annotations,
+            ))
+        });
 
         TensorImageView::<'store, '_> {
             tensor,
             annotations,
-            dynamic_img: ci.dynamic_img.as_ref(),
-            retained_img: ci.retained_img.as_ref(),
-            texture_handle: ci.texture_handle.clone(),
+            dynamic_img: ci.and_then(|ci| ci.dynamic_img.as_ref()),
+            retained_img: ci.and_then(|ci| ci.retained_img.as_ref()),
+            texture_handle: ci.and_then(|ci| ci.texture_handle.clone()),
         }
     }
 
-    pub(crate) fn get_view<'store, 'cache, T: AsDynamicImage>(
+    pub(crate) fn tensor_image_view<'store, 'cache, T: AsDynamicImage>(
         &'cache mut self,
         tensor: &'store T,
         render_ctx: &mut RenderContext,
     ) -> TensorImageView<'store, 'cache> {
-        self.get_view_with_annotations(tensor, &MISSING_ANNOTATIONS, render_ctx)
-    }
-
-    /// Call once per frame to (potentially) flush the cache.
-    pub fn new_frame(&mut self, max_memory_use: u64) {
-        if self.memory_used > max_memory_use {
-            self.purge_memory();
-        }
-
-        self.generation += 1;
-    }
-
-    /// Attempt to free up memory.
-    pub fn purge_memory(&mut self) {
-        crate::profile_function!();
-
-        // Very aggressively flush everything not used in this frame
-
-        let before = self.memory_used;
-
-        self.images.retain(|_, ci| {
-            let retain = ci.last_use_generation == self.generation;
-            if !retain {
-                self.memory_used -= ci.memory_used;
-            }
-            retain
-        });
-
-        re_log::debug!(
-            "Flushed image cache. Before: {:.2} GB. After: {:.2} GB",
-            before as f64 / 1e9,
-            self.memory_used as f64 / 1e9,
-        );
+        self.tensor_image_view_with_annotations(tensor, &MISSING_ANNOTATIONS, render_ctx)
     }
 }
 
@@ -149,6 +88,9 @@ struct CachedImage {
 
     /// For rendering with re_renderer.
     /// `None` if the tensor was not a valid image.
+    ///
+    /// The texture manager doesn't keep textures alive on its own,
+    /// so the texture is freed once the image is evicted from the [`GpuResourceCache`].
     texture_handle: Option<GpuTexture2DHandle>,
 
     /// For easily zooming into it in the UI
@@ -157,9 +99,12 @@ struct CachedImage {
 
     /// Total memory used by this image.
     memory_used: u64,
+}
 
-    /// When [`ImageCache::generation`] was we last used?
-    last_use_generation: u64,
+impl GpuResource for CachedImage {
+    fn memory_used(&self) -> u64 {
+        self.memory_used
+    }
 }
 
 impl CachedImage {
@@ -183,7 +128,6 @@ impl CachedImage {
                     texture_handle: None,
                     dynamic_img: None,
                     memory_used: 0,
-                    last_use_generation: 0,
                 }
             }
         }
@@ -230,7 +174,6 @@ impl CachedImage {
             retained_img: Some(retained_img),
             texture_handle: Some(renderer_texture_handle),
             memory_used: memory_used as u64,
-            last_use_generation: 0,
         }
     }
 }
@@ -1,23 +1,31 @@
+use re_log_types::TimeUnits;
+
 /// Useful when showing dates/times on a timeline
 /// and you want it compact.
 ///
 /// Shows dates when zoomed out, shows times when zoomed in,
 /// shows relative millisecond when really zoomed in.
-pub fn format_time_compact(time: re_log_types::Time) -> String {
+///
+/// Dates and times are shown in the timezone `utc_offset_minutes` away from UTC.
+pub fn format_time_compact(time: re_log_types::Time, utc_offset_minutes: i32) -> String {
     let ns = time.nanos_since_epoch();
     let relative_ns = ns % 1_000_000_000;
     let is_whole_second = relative_ns == 0;
     if is_whole_second {
         if let Some(datetime) = time.to_datetime() {
-            let is_whole_minute = ns % 60_000_000_000 == 0;
-            let time_format = if time.is_exactly_midnight() {
-                "[year]-[month]-[day]Z"
-            } else if is_whole_minute {
-                "[hour]:[minute]Z"
+            let offset = time::UtcOffset::from_whole_seconds(utc_offset_minutes.saturating_mul(60))
+                .unwrap_or(time::UtcOffset::UTC);
+            let datetime = datetime.to_offset(offset);
+            let zone = if offset.is_utc() { "Z" } else { "" };
+
+            let time_format = if datetime.time() == time::Time::MIDNIGHT {
+                format!("[year]-[month]-[day]{zone}")
+            } else if datetime.second() == 0 {
+                format!("[hour]:[minute]{zone}")
             } else {
-                "[hour]:[minute]:[second]Z"
+                format!("[hour]:[minute]:[second]{zone}")
             };
-            let parsed_format = time::format_description::parse(time_format).unwrap();
+            let parsed_format = time::format_description::parse(&time_format).unwrap();
             return datetime.format(&parsed_format).unwrap();
         }
 
@@ -45,6 +53,19 @@ pub fn format_time_compact(time: re_log_types::Time) -> String {
     }
 }
 
+/// Compact formatting of a value on a timeline with the given units,
+/// e.g. for the tick labels of a time axis.
+pub fn format_time_units_compact(units: TimeUnits, time: i64) -> String {
+    match units {
+        TimeUnits::WallClock { utc_offset_minutes } => format_time_compact(
+            re_log_types::Time::from_ns_since_epoch(time),
+            utc_offset_minutes,
+        ),
+        TimeUnits::Sequence | TimeUnits::Ticks { .. } => format!("#{time}"),
+        TimeUnits::Elapsed { .. } => units.format(time.into()),
+    }
+}
+
 /// When showing grid-lines representing time.
 ///
 /// Given some spacing (e.g. 10s), return the next spacing (60s).
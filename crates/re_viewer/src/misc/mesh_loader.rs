@@ -160,6 +160,9 @@ impl LoadedMesh {
             mesh: None, // Don't need to keep cpu-mesh data around, we already have everything we wanted from it (the bounding box)
             world_from_mesh: Default::default(),
             additive_tint: egui::Color32::TRANSPARENT,
+            opacity: 1.0,
+            outline_mask_ids: Default::default(),
+            picking_layer_id: Default::default(),
         }];
 
         Ok(Self {
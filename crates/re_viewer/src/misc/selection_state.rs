@@ -92,6 +92,21 @@ impl InteractionHighlight {
             hover: self.hover.max(other.hover),
         }
     }
+
+    /// Outline mask ids to render this highlight with.
+    ///
+    /// Selection goes to outline layer a, hover to outline layer b.
+    #[inline]
+    pub fn outline_mask_ids(self) -> re_renderer::OutlineMaskPreference {
+        if self.is_some() {
+            re_renderer::OutlineMaskPreference::some(
+                self.selection.is_some() as u8,
+                self.hover.is_some() as u8,
+            )
+        } else {
+            re_renderer::OutlineMaskPreference::NONE
+        }
+    }
 }
 
 /// Highlights of a specific entity path in a specific space view.
@@ -107,6 +122,19 @@ pub struct SpaceViewEntityHighlight {
 pub struct OptionalSpaceViewEntityHighlight<'a>(Option<&'a SpaceViewEntityHighlight>);
 
 impl<'a> OptionalSpaceViewEntityHighlight<'a> {
+    /// Whether the entity or any of its instances is highlighted at all.
+    pub fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Highlight of the entity as a whole, not including highlights of individual instances.
+    pub fn overall(&self) -> InteractionHighlight {
+        self.0
+            .map_or_else(InteractionHighlight::default, |entity_highlight| {
+                entity_highlight.overall
+            })
+    }
+
     pub fn index_highlight(&self, instance_key: InstanceKey) -> InteractionHighlight {
         match self.0 {
             Some(entity_highlight) => entity_highlight
@@ -119,6 +147,20 @@ impl<'a> OptionalSpaceViewEntityHighlight<'a> {
         }
     }
 
+    /// Instances with a highlight of their own, combined with the entity's overall highlight.
+    pub fn iter_instance_highlights(
+        &self,
+    ) -> impl Iterator<Item = (InstanceKey, InteractionHighlight)> + 'a {
+        self.0.into_iter().flat_map(|entity_highlight| {
+            entity_highlight
+                .instances
+                .iter()
+                .map(move |(instance_key, highlight)| {
+                    (*instance_key, highlight.max(entity_highlight.overall))
+                })
+        })
+    }
+
     pub fn any_selection_highlight(&self) -> bool {
         match self.0 {
             Some(entity_highlight) => {
@@ -149,6 +191,11 @@ impl SpaceViewHighlights {
     ) -> OptionalSpaceViewEntityHighlight<'_> {
         OptionalSpaceViewEntityHighlight(self.highlighted_entity_paths.get(&entity_path_hash))
     }
+
+    /// Whether anything in this space view is highlighted at all.
+    pub fn any_highlights(&self) -> bool {
+        !self.highlighted_entity_paths.is_empty()
+    }
 }
 
 /// Selection and hover state
@@ -2,8 +2,10 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use egui::NumExt as _;
 
-use re_data_store::TimesPerTimeline;
-use re_log_types::{Duration, TimeInt, TimeRange, TimeRangeF, TimeReal, TimeType, Timeline};
+use re_data_store::{TimelineMapping, TimelineOffsets, TimesPerTimeline};
+use re_log_types::{
+    TimeInt, TimeRange, TimeRangeF, TimeReal, TimeType, TimeUnits, Timeline, TimelineName,
+};
 
 /// The time range we are currently zoomed in on.
 #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
@@ -13,7 +15,7 @@ pub struct TimeView {
 
     /// How much time the full view covers.
     ///
-    /// The unit is either nanoseconds or sequence numbers.
+    /// The unit is that of the timeline, e.g. nanoseconds or sequence numbers (see [`TimeUnits`]).
     ///
     /// If there is gaps in the data, the actual amount of viewed time might be less.
     pub time_spanned: f64,
@@ -97,6 +99,13 @@ pub struct TimeControl {
     speed: f32,
 
     looping: Looping,
+
+    /// User-defined clock offsets, see [`TimelineOffsets`].
+    timeline_offsets: TimelineOffsets,
+
+    /// Comes from the [`re_log_types::RecordingInfo`], see [`Self::set_timeline_units`].
+    #[serde(skip)]
+    timeline_units: BTreeMap<TimelineName, TimeUnits>,
 }
 
 impl Default for TimeControl {
@@ -108,6 +117,8 @@ impl Default for TimeControl {
             following: true,
             speed: 1.0,
             looping: Looping::Off,
+            timeline_offsets: Default::default(),
+            timeline_units: Default::default(),
         }
     }
 }
@@ -160,11 +171,12 @@ impl TimeControl {
                     state.time = state.time.max(loop_range.min);
                 }
 
-                match self.timeline.typ() {
-                    TimeType::Sequence => {
-                        state.time += TimeReal::from(state.fps * dt);
-                    }
-                    TimeType::Time => state.time += TimeReal::from(Duration::from_secs(dt)),
+                // Timelines with a known rate play back in real time, the others at `fps`.
+                let time_units = self.timeline_units(&self.timeline);
+                if let Some(ticks_per_second) = time_units.ticks_per_second() {
+                    state.time += TimeReal::from(ticks_per_second * dt as f64);
+                } else {
+                    state.time += TimeReal::from(state.fps * dt);
                 }
                 egui_ctx.request_repaint(); // keep playing next frame
 
@@ -386,10 +398,61 @@ impl TimeControl {
         self.timeline.typ()
     }
 
-    pub fn set_timeline(&mut self, timeline: Timeline) {
+    /// How the values of the currently selected timeline should be interpreted and displayed.
+    pub fn time_units(&self) -> TimeUnits {
+        self.timeline_units(&self.timeline)
+    }
+
+    fn timeline_units(&self, timeline: &Timeline) -> TimeUnits {
+        self.timeline_units
+            .get(timeline.name())
+            .copied()
+            .unwrap_or_else(|| TimeUnits::default_for(timeline.typ()))
+    }
+
+    /// Update the units of the timelines, as logged in the [`re_log_types::RecordingInfo`].
+    pub fn set_timeline_units(&mut self, timeline_units: &BTreeMap<TimelineName, TimeUnits>) {
+        if &self.timeline_units != timeline_units {
+            self.timeline_units = timeline_units.clone();
+        }
+    }
+
+    /// Switch to another timeline.
+    ///
+    /// If we know how to convert between the two timelines, the current time is converted
+    /// so that we keep looking at the same moment.
+    pub fn set_timeline(&mut self, timeline: Timeline, timeline_mapping: &TimelineMapping) {
+        if timeline != self.timeline {
+            let converted_time = self.time().and_then(|time| {
+                timeline_mapping.convert(&self.timeline, &timeline, time, &self.timeline_offsets)
+            });
+            if let Some(time) = converted_time {
+                self.states
+                    .entry(timeline)
+                    .or_insert_with(|| TimeState::new(time))
+                    .time = time;
+            }
+        }
         self.timeline = timeline;
     }
 
+    /// The user-defined clock offset of a timeline, see [`TimelineOffsets`].
+    pub fn timeline_offset(&self, timeline: &Timeline) -> TimeInt {
+        self.timeline_offsets
+            .get(timeline)
+            .copied()
+            .unwrap_or(TimeInt::from(0))
+    }
+
+    /// Set the clock offset of a timeline, see [`TimelineOffsets`].
+    pub fn set_timeline_offset(&mut self, timeline: Timeline, offset: TimeInt) {
+        if offset == TimeInt::from(0) {
+            self.timeline_offsets.remove(&timeline);
+        } else {
+            self.timeline_offsets.insert(timeline, offset);
+        }
+    }
+
     /// The current time.
     pub fn time(&self) -> Option<TimeReal> {
         self.states.get(&self.timeline).map(|state| state.time)
@@ -1,7 +1,7 @@
 use egui::NumExt as _;
 
-use re_data_store::TimesPerTimeline;
-use re_log_types::TimeType;
+use re_data_store::{TimelineMapping, TimesPerTimeline};
+use re_log_types::{Duration, TimeInt, TimeType};
 
 use super::time_control::{Looping, PlayState, TimeControl};
 
@@ -10,18 +10,24 @@ impl TimeControl {
         &mut self,
         re_ui: &re_ui::ReUi,
         times_per_timeline: &TimesPerTimeline,
+        timeline_mapping: &TimelineMapping,
         ui: &mut egui::Ui,
     ) {
         self.play_pause_ui(re_ui, times_per_timeline, ui);
-        self.timeline_selector_ui(times_per_timeline, ui);
+        self.timeline_selector_ui(times_per_timeline, timeline_mapping, ui);
         self.playback_speed_ui(ui);
         self.fps_ui(ui);
     }
 
-    fn timeline_selector_ui(&mut self, times_per_timeline: &TimesPerTimeline, ui: &mut egui::Ui) {
+    fn timeline_selector_ui(
+        &mut self,
+        times_per_timeline: &TimesPerTimeline,
+        timeline_mapping: &TimelineMapping,
+        ui: &mut egui::Ui,
+    ) {
         self.select_a_valid_timeline(times_per_timeline);
 
-        egui::ComboBox::from_id_source("timeline")
+        let response = egui::ComboBox::from_id_source("timeline")
             .selected_text(self.timeline().name().as_str())
             .show_ui(ui, |ui| {
                 ui.style_mut().wrap = Some(false);
@@ -32,14 +38,51 @@ impl TimeControl {
                         .selectable_label(timeline == self.timeline(), timeline.name().as_str())
                         .clicked()
                     {
-                        self.set_timeline(*timeline);
+                        self.set_timeline(*timeline, timeline_mapping);
                     }
                 }
-            });
+            })
+            .response;
+
+        if self.time_type() == TimeType::Time {
+            response
+                .on_hover_text("Right-click to correct the clock offset of this timeline")
+                .context_menu(|ui| self.timeline_offset_ui(ui));
+        }
+    }
+
+    /// Edit the clock offset of the current timeline, e.g. to correct for clock skew between
+    /// two machines logging on separate timelines.
+    fn timeline_offset_ui(&mut self, ui: &mut egui::Ui) {
+        let timeline = *self.timeline();
+        let mut offset_sec = Duration::from(self.timeline_offset(&timeline)).as_secs_f64();
+
+        ui.horizontal(|ui| {
+            ui.label("Clock offset:");
+            ui.add(
+                egui::DragValue::new(&mut offset_sec)
+                    .speed(0.001)
+                    .suffix("s"),
+            )
+            .on_hover_text(
+                "How far ahead the clock of this timeline is. \
+                    Used when converting times to other timelines.",
+            );
+        });
+        self.set_timeline_offset(
+            timeline,
+            TimeInt::from_nanos((offset_sec * 1e9).round() as _),
+        );
+
+        if ui.button("Reset").clicked() {
+            self.set_timeline_offset(timeline, TimeInt::from(0));
+            ui.close_menu();
+        }
     }
 
     fn fps_ui(&mut self, ui: &mut egui::Ui) {
-        if self.time_type() == TimeType::Sequence {
+        // Only timelines without a known rate are played back at a chosen FPS.
+        if self.time_units().ticks_per_second().is_none() {
             if let Some(mut fps) = self.fps() {
                 ui.add(
                     egui::DragValue::new(&mut fps)
@@ -211,7 +211,8 @@ impl<'a> ViewerContext<'a> {
     ) -> egui::Response {
         let is_selected = self.rec_cfg.time_ctrl.is_time_selected(timeline, value);
 
-        let response = ui.selectable_label(is_selected, timeline.typ().format(value));
+        let time_units = self.log_db.timeline_units(timeline);
+        let response = ui.selectable_label(is_selected, time_units.format(value));
         if response.clicked() {
             self.rec_cfg
                 .time_ctrl
@@ -237,7 +238,9 @@ impl<'a> ViewerContext<'a> {
             .selectable_label(is_selected, text)
             .on_hover_text("Click to switch to this timeline");
         if response.clicked() {
-            self.rec_cfg.time_ctrl.set_timeline(*timeline);
+            self.rec_cfg
+                .time_ctrl
+                .set_timeline(*timeline, self.log_db.timeline_mapping());
             self.rec_cfg.time_ctrl.pause();
         }
         response
@@ -337,4 +340,10 @@ pub struct RecordingConfig {
 
     /// Selection & hovering state.
     pub selection_state: super::SelectionState,
+
+    /// Edits of the recording requested from the UI, e.g. dropping an entity.
+    ///
+    /// Applied by the app at the end of the frame, since the [`LogDb`] is borrowed until then.
+    #[serde(skip)]
+    pub pending_path_ops: Vec<re_log_types::PathOp>,
 }
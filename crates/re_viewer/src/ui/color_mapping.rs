@@ -0,0 +1,236 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use egui::Color32;
+use re_data_store::{query_latest_single, ColorMap, ColorMapper, EntityPath};
+use re_log_types::component_types::ColorLut;
+
+use crate::misc::ViewerContext;
+
+/// A [`ColorMapper`] with its lookup table queried, ready to map normalized values to colors.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResolvedColorMapper {
+    ColorMap(re_renderer::ColorMap),
+
+    /// Evenly spaced sRGB colors, from a [`ColorLut`].
+    Lut(Arc<[[u8; 4]]>),
+}
+
+impl ResolvedColorMapper {
+    /// Looks up the [`ColorLut`] of `ent_path` if `color_mapper` asks for one.
+    ///
+    /// Falls back to grayscale if there is no lookup table logged to the entity.
+    pub fn resolve(
+        ctx: &ViewerContext<'_>,
+        ent_path: &EntityPath,
+        color_mapper: ColorMapper,
+    ) -> Self {
+        match color_mapper {
+            ColorMapper::ColorMap(colormap) => Self::ColorMap(renderer_colormap(colormap)),
+            ColorMapper::Lut => {
+                let lut = query_latest_single::<ColorLut>(
+                    &ctx.log_db.entity_db,
+                    ent_path,
+                    &ctx.current_query(),
+                );
+                match lut {
+                    Some(lut) if !lut.colors.is_empty() => {
+                        Self::Lut(lut.colors.iter().map(|color| color.to_array()).collect())
+                    }
+                    _ => {
+                        re_log::warn_once!("No color lookup table logged to {ent_path}");
+                        Self::ColorMap(re_renderer::ColorMap::Grayscale)
+                    }
+                }
+            }
+        }
+    }
+
+    /// The built-in color map to use, grayscale for lookup tables.
+    pub fn colormap(&self) -> re_renderer::ColorMap {
+        match self {
+            Self::ColorMap(colormap) => *colormap,
+            Self::Lut(_) => re_renderer::ColorMap::Grayscale,
+        }
+    }
+
+    pub fn lut(&self) -> Option<Arc<[[u8; 4]]>> {
+        match self {
+            Self::ColorMap(_) => None,
+            Self::Lut(lut) => Some(lut.clone()),
+        }
+    }
+
+    /// Maps `t` in `[0, 1]` to a color, values outside are clamped.
+    pub fn color_from_normalized(&self, t: f32) -> Color32 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let [r, g, b, _] = match self {
+            Self::ColorMap(colormap) => re_renderer::colormap_srgb(*colormap, t),
+            Self::Lut(lut) => re_renderer::colormap_lut_srgb(lut, t),
+        };
+        Color32::from_rgb(r, g, b)
+    }
+}
+
+/// The renderer's equivalent of a color map selected in the entity properties.
+pub fn renderer_colormap(colormap: ColorMap) -> re_renderer::ColorMap {
+    match colormap {
+        ColorMap::Grayscale => re_renderer::ColorMap::Grayscale,
+        ColorMap::Turbo => re_renderer::ColorMap::ColorMapTurbo,
+        ColorMap::Viridis => re_renderer::ColorMap::ColorMapViridis,
+        ColorMap::Plasma => re_renderer::ColorMap::ColorMapPlasma,
+        ColorMap::Magma => re_renderer::ColorMap::ColorMapMagma,
+        ColorMap::Inferno => re_renderer::ColorMap::ColorMapInferno,
+        ColorMap::CoolWarm => re_renderer::ColorMap::ColorMapCoolWarm,
+        ColorMap::RdBu => re_renderer::ColorMap::ColorMapRdBu,
+        ColorMap::Twilight => re_renderer::ColorMap::ColorMapTwilight,
+    }
+}
+
+/// Combo box for picking any of the built-in color maps or a custom lookup table.
+///
+/// Returns true if the selection changed.
+pub fn color_mapper_ui(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    color_mapper: &mut ColorMapper,
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(color_mapper.to_string())
+        .show_ui(ui, |ui| {
+            ui.style_mut().wrap = Some(false);
+            ui.set_min_width(64.0);
+
+            for colormap in ColorMap::ALL {
+                let proposed = ColorMapper::ColorMap(colormap);
+                let kind = match renderer_colormap(colormap).kind() {
+                    re_renderer::ColorMapKind::Sequential => {
+                        "Sequential, for values from low to high"
+                    }
+                    re_renderer::ColorMapKind::Diverging => {
+                        "Diverging, for values around a midpoint"
+                    }
+                    re_renderer::ColorMapKind::Cyclic => {
+                        "Cyclic, for angles and other periodic values"
+                    }
+                };
+                changed |= ui
+                    .selectable_value(color_mapper, proposed, proposed.to_string())
+                    .on_hover_text(kind)
+                    .changed();
+            }
+
+            changed |= ui
+                .selectable_value(color_mapper, ColorMapper::Lut, ColorMapper::Lut.to_string())
+                .on_hover_text("Use the color lookup table logged to the entity")
+                .changed();
+        });
+    changed
+}
+
+// ----------------------------------------------------------------------------
+
+/// Legend of a color mapped entity.
+#[derive(Clone, Debug)]
+pub struct Colorbar {
+    pub label: String,
+    pub color_mapper: ResolvedColorMapper,
+
+    /// Exponent applied to normalized values before color mapping.
+    pub gamma: f32,
+
+    /// The values mapped to the ends of the color map.
+    pub range: RangeInclusive<f64>,
+
+    /// Appended to the values, e.g. "m".
+    pub unit: Option<&'static str>,
+}
+
+impl Colorbar {
+    const SIZE: egui::Vec2 = egui::vec2(160.0, 10.0);
+
+    fn format_value(&self, value: f64) -> String {
+        let value = re_format::format_f64(value);
+        match self.unit {
+            Some(unit) => format!("{value} {unit}"),
+            None => value,
+        }
+    }
+
+    /// Shows the color gradient with the values at its ends, and the value under the pointer on hover.
+    pub fn ui(&self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            ui.label(egui::RichText::new(&self.label).small());
+
+            let (rect, response) = ui.allocate_exact_size(Self::SIZE, egui::Sense::hover());
+
+            const NUM_SEGMENTS: usize = 64;
+            let mut mesh = egui::Mesh::default();
+            for i in 0..=NUM_SEGMENTS {
+                let t = i as f32 / NUM_SEGMENTS as f32;
+                let color = self.color_mapper.color_from_normalized(t.powf(self.gamma));
+                let x = egui::lerp(rect.x_range(), t);
+                mesh.colored_vertex(egui::pos2(x, rect.top()), color);
+                mesh.colored_vertex(egui::pos2(x, rect.bottom()), color);
+                if i > 0 {
+                    let i = i as u32 * 2;
+                    mesh.add_triangle(i - 2, i - 1, i);
+                    mesh.add_triangle(i - 1, i, i + 1);
+                }
+            }
+            ui.painter().add(mesh);
+            ui.painter()
+                .rect_stroke(rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+            let font_id = egui::TextStyle::Small.resolve(ui.style());
+            let text_color = ui.visuals().text_color();
+            let (labels_rect, _) = ui.allocate_exact_size(
+                egui::vec2(Self::SIZE.x, ui.fonts(|f| f.row_height(&font_id))),
+                egui::Sense::hover(),
+            );
+            ui.painter().text(
+                labels_rect.left_top(),
+                egui::Align2::LEFT_TOP,
+                self.format_value(*self.range.start()),
+                font_id.clone(),
+                text_color,
+            );
+            ui.painter().text(
+                labels_rect.right_top(),
+                egui::Align2::RIGHT_TOP,
+                self.format_value(*self.range.end()),
+                font_id,
+                text_color,
+            );
+
+            if let Some(pointer_pos) = response.hover_pos() {
+                let t = egui::remap_clamp(pointer_pos.x, rect.x_range(), 0.0..=1.0) as f64;
+                let value = egui::lerp(self.range.clone(), t);
+                response.on_hover_text_at_pointer(self.format_value(value));
+            }
+        })
+        .response
+    }
+}
+
+/// Shows colorbars stacked in the bottom left corner of `rect`, on top of a view.
+pub fn colorbars_overlay_ui(ui: &mut egui::Ui, rect: egui::Rect, colorbars: &[Colorbar]) {
+    if colorbars.is_empty() {
+        return;
+    }
+
+    let margin = 8.0;
+    let mut overlay_ui = ui.child_ui(
+        rect.shrink(margin),
+        egui::Layout::bottom_up(egui::Align::Min),
+    );
+    overlay_ui.set_clip_rect(rect);
+    for colorbar in colorbars {
+        egui::Frame::popup(overlay_ui.style())
+            .fill(overlay_ui.visuals().panel_fill.linear_multiply(0.8))
+            .show(&mut overlay_ui, |ui| {
+                ui.set_max_width(Colorbar::SIZE.x);
+                colorbar.ui(ui);
+            });
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::BTreeSet;
 
 use nohash_hasher::{IntMap, IntSet};
 use re_data_store::{EntityPath, EntityProperties, EntityPropertyMap};
+use re_log_types::EntityPathPattern;
 use slotmap::SlotMap;
 use smallvec::{smallvec, SmallVec};
 
@@ -70,6 +71,13 @@ pub struct DataBlueprintTree {
     root_group_handle: DataBlueprintGroupHandle,
 
     data_blueprints: DataBlueprints,
+
+    /// Rules added by the user, e.g. `/robot/*/points`.
+    ///
+    /// Any compatible entity matching one of these is added, including the ones that are
+    /// logged later on.
+    #[serde(default)]
+    entity_patterns: Vec<EntityPathPattern>,
 }
 
 impl Default for DataBlueprintTree {
@@ -86,6 +94,7 @@ impl Default for DataBlueprintTree {
             entity_paths: IntSet::default(),
             root_group_handle: root_group,
             data_blueprints: DataBlueprints::default(),
+            entity_patterns: Vec::new(),
         }
     }
 }
@@ -158,6 +167,24 @@ impl DataBlueprintTree {
         &self.entity_paths
     }
 
+    /// Rules that keep adding the matching entities, see [`Self::add_entity_pattern`].
+    pub fn entity_patterns(&self) -> &[EntityPathPattern] {
+        &self.entity_patterns
+    }
+
+    /// Adds a rule that keeps adding the matching entities, including the ones that are logged
+    /// later on.
+    pub fn add_entity_pattern(&mut self, pattern: EntityPathPattern) {
+        if !self.entity_patterns.contains(&pattern) {
+            self.entity_patterns.push(pattern);
+        }
+    }
+
+    /// Removes a rule. The entities it added stay.
+    pub fn remove_entity_pattern(&mut self, pattern: &EntityPathPattern) {
+        self.entity_patterns.retain(|p| p != pattern);
+    }
+
     /// Should be called on frame start.
     ///
     /// Propagates any data blueprint changes along the tree.
@@ -64,6 +64,7 @@ impl Default for ComponentUiRegistry {
         registry.add::<re_log_types::component_types::Vec2D>();
         registry.add::<re_log_types::component_types::Vec3D>();
         registry.add::<re_log_types::ViewCoordinates>();
+        registry.add::<re_log_types::component_types::VoxelGrid>();
 
         registry
     }
@@ -142,7 +143,11 @@ impl DataUi for re_log_types::component_types::TextEntry {
     ) {
         use crate::ui::view_text::level_to_rich_text;
 
-        let Self { body, level } = self;
+        let Self {
+            body,
+            level,
+            fields,
+        } = self;
 
         match verbosity {
             UiVerbosity::Small | UiVerbosity::MaxHeight(_) => {
@@ -164,6 +169,12 @@ impl DataUi for re_log_types::component_types::TextEntry {
                     ui.label("body:");
                     ui.label(format!("{body:?}")); // Debug format to get quotes and escapes
                     ui.end_row();
+
+                    for field in fields.iter().flatten() {
+                        ui.label(format!("{}:", field.key));
+                        ui.label(&field.value);
+                        ui.end_row();
+                    }
                 });
             }
         }
@@ -211,3 +222,24 @@ impl DataUi for re_log_types::component_types::RawMesh3D {
         ));
     }
 }
+
+impl DataUi for re_log_types::component_types::VoxelGrid {
+    fn data_ui(
+        &self,
+        _ctx: &mut ViewerContext<'_>,
+        ui: &mut egui::Ui,
+        _verbosity: UiVerbosity,
+        _query: &re_arrow_store::LatestAtQuery,
+    ) {
+        let [x, y, z] = self.dimensions;
+        let kind = match self.cells {
+            re_log_types::component_types::VoxelCells::Dense(_) => "dense",
+            re_log_types::component_types::VoxelCells::Sparse(_) => "sparse",
+        };
+        ui.label(format!(
+            "{x}×{y}×{z} {kind} voxel grid ({} occupied cells of size {})",
+            re_format::format_number(self.iter_occupied().count()),
+            re_format::format_f32(self.voxel_size),
+        ));
+    }
+}
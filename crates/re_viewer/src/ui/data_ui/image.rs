@@ -24,7 +24,10 @@ impl DataUi for Tensor {
         verbosity: crate::ui::UiVerbosity,
         _query: &re_arrow_store::LatestAtQuery,
     ) {
-        let tensor_view = ctx.cache.image.get_view(self, ctx.render_ctx);
+        let tensor_view = ctx
+            .cache
+            .gpu_resources
+            .tensor_image_view(self, ctx.render_ctx);
         let tensor_stats = ctx.cache.tensor_stats.get(&self.id());
 
         match verbosity {
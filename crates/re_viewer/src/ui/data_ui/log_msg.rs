@@ -41,6 +41,8 @@ impl DataUi for BeginRecordingMsg {
             started,
             recording_source,
             is_official_example,
+            properties,
+            timeline_units,
         } = info;
 
         egui::Grid::new("fields").num_columns(2).show(ui, |ui| {
@@ -63,10 +65,38 @@ impl DataUi for BeginRecordingMsg {
             ui.monospace("is_official_example:");
             ui.label(format!("{is_official_example}"));
             ui.end_row();
+
+            for (key, value) in properties {
+                ui.monospace(format!("{key}:"));
+                ui.label(value);
+                ui.end_row();
+            }
+
+            for (timeline, units) in timeline_units {
+                ui.monospace(format!("{timeline} units:"));
+                ui.label(format!("{units:?}"));
+                ui.end_row();
+            }
         });
     }
 }
 
+/// The user-defined properties of a recording, see [`RecordingInfo::properties`].
+pub(crate) fn recording_properties_ui(
+    ui: &mut egui::Ui,
+    properties: &std::collections::BTreeMap<String, String>,
+) {
+    egui::Grid::new("recording_properties")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (key, value) in properties {
+                ui.monospace(format!("{key}:"));
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+}
+
 impl DataUi for EntityPathOpMsg {
     fn data_ui(
         &self,
@@ -105,7 +135,7 @@ impl DataUi for ArrowMsg {
             Ok(MsgBundle {
                 msg_id: _,
                 entity_path,
-                time_point,
+                time_points,
                 components,
             }) => {
                 egui::Grid::new("fields").num_columns(2).show(ui, |ui| {
@@ -113,8 +143,17 @@ impl DataUi for ArrowMsg {
                     ctx.entity_path_button(ui, None, &entity_path);
                     ui.end_row();
 
-                    ui.monospace("time_point:");
-                    time_point.data_ui(ctx, ui, verbosity, query);
+                    if let [time_point] = time_points.as_slice() {
+                        ui.monospace("time_point:");
+                        time_point.data_ui(ctx, ui, verbosity, query);
+                    } else {
+                        ui.monospace("time_points:");
+                        ui.vertical(|ui| {
+                            for time_point in &time_points {
+                                time_point.data_ui(ctx, ui, verbosity, query);
+                            }
+                        });
+                    }
                     ui.end_row();
 
                     ui.monospace("components:");
@@ -17,6 +17,7 @@ mod log_msg;
 mod msg_id;
 
 pub(crate) use component_ui_registry::ComponentUiRegistry;
+pub(crate) use log_msg::recording_properties_ui;
 
 /// Controls how mich space we use to show the data in [`DataUi`].
 #[derive(Clone, Copy, Debug)]
@@ -120,6 +121,15 @@ impl DataUi for PathOp {
             PathOp::ClearRecursive(entity_path) => {
                 ui.label(format!("ClearRecursive: {entity_path}"))
             }
+            PathOp::DropRecursive(entity_path) => ui.label(format!("DropRecursive: {entity_path}")),
+            PathOp::Rename { from, to } => ui.label(format!("Rename: {from} → {to}")),
+            PathOp::DropTimeline {
+                entity_path,
+                timeline,
+            } => ui.label(format!(
+                "DropTimeline: {entity_path} on {:?}",
+                timeline.name().as_str()
+            )),
         };
     }
 }
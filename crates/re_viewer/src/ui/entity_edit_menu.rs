@@ -0,0 +1,97 @@
+use re_log_types::{EntityPath, PathOp};
+
+use crate::misc::ViewerContext;
+
+/// Context menu for editing the recorded data of an entity: drop it, move it, etc.
+///
+/// The edits apply to the whole history of the entity and its descendants, and become part of
+/// the recording when it is saved.
+pub fn entity_edit_menu(ctx: &mut ViewerContext<'_>, ui: &mut egui::Ui, entity_path: &EntityPath) {
+    if entity_path.is_root() {
+        ui.weak("The root entity can't be edited");
+        return;
+    }
+
+    ui.menu_button("Move to…", |ui| {
+        let text_id = ui.id().with("move_to");
+        let mut text = ui.data_mut(|d| {
+            d.get_temp_mut_or_insert_with(text_id, || entity_path.to_string())
+                .clone()
+        });
+
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut text)
+                .font(egui::TextStyle::Monospace)
+                .desired_width(200.0),
+        );
+        let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+        let destination = re_log_types::parse_entity_path(text.trim().trim_start_matches('/'))
+            .ok()
+            .map(EntityPath::from)
+            .filter(|destination| destination != entity_path);
+        let check = destination.as_ref().map(|destination| {
+            ctx.log_db
+                .entity_db
+                .data_store
+                .check_rename_entity_subtree(entity_path, destination)
+        });
+        if let Some(Err(err)) = &check {
+            ui.label(ctx.re_ui.error_text(err.to_string()));
+        }
+        let is_valid = matches!(check, Some(Ok(())));
+        let move_clicked = ui
+            .add_enabled(is_valid, egui::Button::new("Move"))
+            .on_hover_text("Rename or reparent this entity and everything below it")
+            .on_disabled_hover_text("Enter another entity path")
+            .clicked();
+
+        if let (Some(destination), true) =
+            (destination, is_valid && (move_clicked || enter_pressed))
+        {
+            ctx.rec_cfg.pending_path_ops.push(PathOp::Rename {
+                from: entity_path.clone(),
+                to: destination,
+            });
+            ui.data_mut(|d| d.remove::<String>(text_id));
+            ui.close_menu();
+        } else {
+            ui.data_mut(|d| d.insert_temp(text_id, text));
+        }
+    });
+
+    ui.separator();
+
+    // Dropping can't be undone, so it takes a second click in a submenu to confirm.
+
+    let timeline = *ctx.rec_cfg.time_ctrl.timeline();
+    ui.menu_button(
+        format!("Drop data on timeline {:?}…", timeline.name().as_str()),
+        |ui| {
+            ui.label(
+                "Permanently drop everything logged on this timeline for this entity and \
+                everything below it. The data logged on other timelines is kept.",
+            );
+            if ui.button("Drop").clicked() {
+                ctx.rec_cfg.pending_path_ops.push(PathOp::DropTimeline {
+                    entity_path: entity_path.clone(),
+                    timeline,
+                });
+                ui.close_menu();
+            }
+        },
+    );
+
+    ui.menu_button("Drop entity…", |ui| {
+        ui.label(
+            "Permanently drop everything logged for this entity and everything below it, \
+            at all times.",
+        );
+        if ui.button("Drop").clicked() {
+            ctx.rec_cfg
+                .pending_path_ops
+                .push(PathOp::DropRecursive(entity_path.clone()));
+            ui.close_menu();
+        }
+    });
+}
@@ -8,7 +8,7 @@ use re_log_types::{
 
 use crate::{UiVerbosity, ViewerContext};
 
-use super::data_ui::DataUi;
+use super::data_ui::{recording_properties_ui, DataUi};
 
 /// An event log, a table of all log messages.
 #[derive(Default, serde::Deserialize, serde::Serialize)]
@@ -121,6 +121,8 @@ fn table_row(
                 started,
                 recording_source,
                 is_official_example,
+                properties,
+                timeline_units: _,
             } = info;
 
             row.col(|ui| {
@@ -130,6 +132,10 @@ fn table_row(
                 ui.monospace("BeginRecordingMsg");
                 ui.label(format!("Source: {recording_source}"));
                 ui.label(format!("Official example: {is_official_example}"));
+                if !properties.is_empty() {
+                    ui.label(format!("Properties: {}", properties.len()))
+                        .on_hover_ui(|ui| recording_properties_ui(ui, properties));
+                }
             });
             for _ in ctx.log_db.timelines() {
                 row.col(|ui| {
@@ -179,14 +185,21 @@ fn table_row(
             Ok(MsgBundle {
                 msg_id,
                 entity_path,
-                time_point,
+                time_points,
                 components,
             }) => {
+                // Batched messages are shown at the time of their first row.
+                let time_point = time_points.first().cloned().unwrap_or_default();
+
                 row.col(|ui| {
                     ctx.msg_id_button(ui, msg_id);
                 });
                 row.col(|ui| {
-                    ui.monospace("ArrowMsg");
+                    if time_points.len() > 1 {
+                        ui.monospace(format!("ArrowMsg ({} rows)", time_points.len()));
+                    } else {
+                        ui.monospace("ArrowMsg");
+                    }
                 });
                 for timeline in ctx.log_db.timelines() {
                     row.col(|ui| {
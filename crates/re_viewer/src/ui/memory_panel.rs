@@ -366,7 +366,7 @@ fn summarize_callstack(callstack: &str) -> String {
         ("EntityTree", "EntityTree"),
         ("::LogMsg>::deserialize", "LogMsg"),
         ("::TimePoint>::deserialize", "TimePoint"),
-        ("ImageCache", "ImageCache"),
+        ("GpuResourceCache", "GpuResourceCache"),
         ("gltf", "gltf"),
         ("image::image", "image"),
         // -----
@@ -1,7 +1,9 @@
 mod annotations;
 mod auto_layout;
 mod blueprint;
+mod color_mapping;
 mod data_blueprint;
+mod entity_edit_menu;
 mod scene;
 mod selection_history;
 mod selection_history_ui;
@@ -10,6 +12,8 @@ mod space_view_entity_picker;
 mod space_view_heuristics;
 mod view_bar_chart;
 mod view_category;
+mod view_histogram;
+mod view_scatter_plot;
 mod view_tensor;
 mod view_text;
 mod view_time_series;
@@ -28,10 +32,14 @@ pub mod view_spatial;
 use self::scene::SceneQuery;
 
 pub(crate) use self::blueprint::Blueprint;
+pub(crate) use self::color_mapping::{
+    color_mapper_ui, colorbars_overlay_ui, Colorbar, ResolvedColorMapper,
+};
 pub(crate) use self::space_view::{SpaceView, SpaceViewId};
 
 pub use self::annotations::{Annotations, DefaultColor, MISSING_ANNOTATIONS};
 pub use self::data_blueprint::DataBlueprintGroupHandle;
+pub(crate) use self::entity_edit_menu::entity_edit_menu;
 pub use self::selection_history::{HistoricalSelection, SelectionHistory};
 pub use self::view_category::ViewCategory;
 pub use self::viewport::Viewport;
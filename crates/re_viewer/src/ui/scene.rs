@@ -1,6 +1,14 @@
 use nohash_hasher::IntSet;
 
+use re_arrow_store::{DataStore, RangeQuery, TimeRange};
 use re_data_store::{EntityPath, EntityProperties, EntityPropertyMap, TimeInt, Timeline};
+use re_log_types::{
+    component_types::{InstanceKey, Scalar},
+    msg_bundle::Component,
+};
+use re_query::{range_entity_with_primary, QueryError};
+
+use crate::misc::TimeControl;
 
 // ---
 
@@ -22,3 +30,48 @@ impl<'s> SceneQuery<'s> {
             .filter(|(_entity_path, props)| props.visible)
     }
 }
+
+// ---
+
+/// The time range covered by the loop selection, or the whole timeline if there is none.
+pub(crate) fn loop_selection_range(time_ctrl: &TimeControl) -> TimeRange {
+    time_ctrl.loop_selection().map_or_else(
+        || TimeRange::new(i64::MIN.into(), i64::MAX.into()),
+        |selection| TimeRange::new(selection.min.floor(), selection.max.ceil()),
+    )
+}
+
+/// Collects all [`Scalar`]s logged at `ent_path` within `range`, sorted by time.
+///
+/// Timeless scalars, as well as the latest-at state that precedes `range`, are skipped.
+pub(crate) fn range_scalars(
+    store: &DataStore,
+    timeline: Timeline,
+    range: TimeRange,
+    ent_path: &EntityPath,
+) -> Vec<(TimeInt, f64)> {
+    crate::profile_function!();
+
+    let query = RangeQuery::new(timeline, range);
+    let components = [InstanceKey::name(), Scalar::name()];
+    let ent_views = range_entity_with_primary::<Scalar, 2>(store, &query, ent_path, components);
+
+    let mut scalars = Vec::new();
+    for (time, ent_view) in ent_views {
+        let Some(time) = time.filter(|time| range.min <= *time) else {
+            continue;
+        };
+
+        match ent_view.visit1(|_instance, scalar: Scalar| {
+            scalars.push((time, scalar.into()));
+        }) {
+            Ok(_) | Err(QueryError::PrimaryNotFound) => {}
+            Err(err) => {
+                re_log::error_once!("Unexpected error querying {ent_path:?}: {err}");
+            }
+        }
+    }
+
+    scalars.sort_by_key(|(time, _)| *time);
+    scalars
+}
@@ -1,14 +1,14 @@
 use egui::NumExt as _;
 use re_data_store::{
-    query_latest_single, ColorMap, ColorMapper, EditableAutoValue, EntityPath, EntityProperties,
+    query_latest_single, EditableAutoValue, EntityPath, EntityProperties, VolumeRenderMode,
 };
 use re_log_types::{
-    component_types::{Tensor, TensorDataMeaning},
+    component_types::{Tensor, TensorDataMeaning, TensorTrait, VoxelGrid},
     TimeType, Transform,
 };
 
 use crate::{
-    ui::{view_spatial::SpatialNavigationMode, Blueprint},
+    ui::{color_mapper_ui, view_spatial::SpatialNavigationMode, Blueprint},
     Item, UiVerbosity, ViewerContext,
 };
 
@@ -411,10 +411,23 @@ fn entity_props_ui(
             }
             ui.end_row();
 
+            ui.label("Opacity");
+            ui.add(
+                egui::Slider::new(&mut entity_props.opacity, 0.0..=1.0)
+                    .clamp_to_range(true)
+                    .fixed_decimals(2),
+            )
+            .on_hover_text(
+                "Makes meshes, points and images of this Entity and its children see-through",
+            );
+            ui.end_row();
+
             if view_state.state_spatial.nav_mode == SpatialNavigationMode::ThreeD {
                 if let Some(entity_path) = entity_path {
                     pinhole_props_ui(ctx, ui, entity_path, entity_props);
                     depth_props_ui(ctx, ui, entity_path, entity_props);
+                    volume_props_ui(ctx, ui, entity_path, entity_props);
+                    voxel_grid_props_ui(ctx, ui, entity_path, entity_props);
                 }
             }
         });
@@ -429,33 +442,12 @@ fn colormap_props_ui(ui: &mut egui::Ui, entity_props: &mut EntityProperties) {
         return;
     }
 
-    let current = *entity_props.color_mapper.get();
+    let mut color_mapper = *entity_props.color_mapper.get();
 
     ui.label("Color map");
-    egui::ComboBox::from_id_source("color_mapper")
-        .selected_text(current.to_string())
-        .show_ui(ui, |ui| {
-            ui.style_mut().wrap = Some(false);
-            ui.set_min_width(64.0);
-
-            // TODO(cmc): that is not ideal but I don't want to import yet another proc-macro...
-            let mut add_label = |proposed| {
-                if ui
-                    .selectable_label(current == proposed, proposed.to_string())
-                    .clicked()
-                {
-                    entity_props.color_mapper = EditableAutoValue::Auto(proposed);
-                }
-            };
-
-            add_label(ColorMapper::ColorMap(ColorMap::Grayscale));
-            add_label(ColorMapper::ColorMap(ColorMap::Turbo));
-            add_label(ColorMapper::ColorMap(ColorMap::Viridis));
-            add_label(ColorMapper::ColorMap(ColorMap::Plasma));
-            add_label(ColorMapper::ColorMap(ColorMap::Magma));
-            add_label(ColorMapper::ColorMap(ColorMap::Inferno));
-        });
-
+    if color_mapper_ui(ui, "color_mapper", &mut color_mapper) {
+        entity_props.color_mapper = EditableAutoValue::Auto(color_mapper);
+    }
     ui.end_row();
 }
 
@@ -570,3 +562,98 @@ fn depth_props_ui(
         }
     }
 }
+
+fn volume_props_ui(
+    ctx: &mut ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    entity_path: &EntityPath,
+    entity_props: &mut EntityProperties,
+) {
+    let query = ctx.current_query();
+    let tensor = query_latest_single::<Tensor>(&ctx.log_db.entity_db, entity_path, &query);
+    if !tensor.map_or(false, |tensor| tensor.is_shaped_like_a_volume()) {
+        return;
+    }
+
+    ui.label("Volume rendering");
+    let mut render_mode = *entity_props.volume_render_mode.get();
+    egui::ComboBox::from_id_source("volume_render_mode")
+        .selected_text(render_mode.to_string())
+        .show_ui(ui, |ui| {
+            ui.style_mut().wrap = Some(false);
+            for proposed in VolumeRenderMode::ALL {
+                // Selecting the current mode again keeps its iso value.
+                let is_selected =
+                    std::mem::discriminant(&render_mode) == std::mem::discriminant(&proposed);
+                if ui
+                    .selectable_label(is_selected, proposed.to_string())
+                    .clicked()
+                    && !is_selected
+                {
+                    render_mode = proposed;
+                }
+            }
+        });
+    ui.end_row();
+
+    if let VolumeRenderMode::Isosurface { iso_value } = &mut render_mode {
+        ui.label("Iso value");
+        ui.add(
+            egui::Slider::new(iso_value, 0.0..=1.0)
+                .clamp_to_range(true)
+                .fixed_decimals(3),
+        )
+        .on_hover_text("Fraction of the value range at which the surface is drawn");
+        ui.end_row();
+    } else {
+        ui.label("Threshold");
+        let mut threshold = *entity_props.volume_threshold.get();
+        if ui
+            .add(
+                egui::Slider::new(&mut threshold, 0.0..=1.0)
+                    .clamp_to_range(true)
+                    .fixed_decimals(3),
+            )
+            .on_hover_text("Values below this fraction of the value range are transparent")
+            .changed()
+        {
+            entity_props.volume_threshold = EditableAutoValue::UserEdited(threshold);
+        }
+        ui.end_row();
+    }
+
+    if render_mode != *entity_props.volume_render_mode.get() {
+        entity_props.volume_render_mode = EditableAutoValue::UserEdited(render_mode);
+    }
+
+    colormap_props_ui(ui, entity_props);
+}
+
+fn voxel_grid_props_ui(
+    ctx: &mut ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    entity_path: &EntityPath,
+    entity_props: &mut EntityProperties,
+) {
+    let query = ctx.current_query();
+    if query_latest_single::<VoxelGrid>(&ctx.log_db.entity_db, entity_path, &query).is_none() {
+        return;
+    }
+
+    ui.label("Threshold");
+    let mut threshold = *entity_props.volume_threshold.get();
+    if ui
+        .add(
+            egui::Slider::new(&mut threshold, 0.0..=1.0)
+                .clamp_to_range(true)
+                .fixed_decimals(3),
+        )
+        .on_hover_text("Cells with values below this fraction of the value range are hidden")
+        .changed()
+    {
+        entity_props.volume_threshold = EditableAutoValue::UserEdited(threshold);
+    }
+    ui.end_row();
+
+    colormap_props_ui(ui, entity_props);
+}
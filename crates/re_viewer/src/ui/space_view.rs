@@ -1,5 +1,6 @@
 use re_arrow_store::Timeline;
 use re_data_store::{EntityPath, EntityTree, InstancePath, TimeInt};
+use re_log_types::EntityPathPattern;
 
 use crate::{
     misc::{space_info::SpaceInfoCollection, SpaceViewHighlights, TransformCache, ViewerContext},
@@ -11,6 +12,7 @@ use super::{
     space_view_heuristics::default_queried_entities,
     view_bar_chart,
     view_category::ViewCategory,
+    view_histogram, view_scatter_plot,
     view_spatial::{self},
     view_tensor, view_text, view_time_series,
 };
@@ -103,6 +105,23 @@ impl SpaceView {
             self.data_blueprint
                 .insert_entities_according_to_hierarchy(queries_entities.iter(), &self.space_path);
         }
+
+        if !self.data_blueprint.entity_patterns().is_empty() {
+            crate::profile_scope!("entity_patterns");
+            let tree = &ctx.log_db.entity_db.tree;
+            let mut entities = Vec::new();
+            for pattern in self.data_blueprint.entity_patterns() {
+                tree.visit_matching(pattern, &mut |entity_path: &EntityPath| {
+                    if self.can_add_entity(entity_path, spaces_info, ctx.log_db) {
+                        entities.push(entity_path.clone());
+                    }
+                });
+            }
+            if !entities.is_empty() {
+                self.data_blueprint
+                    .insert_entities_according_to_hierarchy(entities.iter(), &self.space_path);
+            }
+        }
     }
 
     pub fn selection_ui(&mut self, ctx: &mut ViewerContext<'_>, ui: &mut egui::Ui) {
@@ -111,7 +130,21 @@ impl SpaceView {
             ViewCategory::Text => {
                 self.view_state.state_text.selection_ui(ctx.re_ui, ui);
             }
-            ViewCategory::TimeSeries => {}
+            ViewCategory::TimeSeries => {
+                self.view_state
+                    .state_time_series
+                    .selection_ui(ctx.re_ui, ui);
+            }
+            ViewCategory::ScatterPlot => {
+                self.view_state.state_scatter_plot.selection_ui(
+                    ctx.re_ui,
+                    ui,
+                    self.data_blueprint.entity_paths().iter(),
+                );
+            }
+            ViewCategory::Histogram => {
+                self.view_state.state_histogram.selection_ui(ctx.re_ui, ui);
+            }
             ViewCategory::BarChart => {}
             ViewCategory::Spatial => {
                 self.view_state.state_spatial.selection_ui(
@@ -153,16 +186,28 @@ impl SpaceView {
         match self.category {
             ViewCategory::Text => {
                 let mut scene = view_text::SceneText::default();
-                scene.load(ctx, &query, &self.view_state.state_text.filters);
+                scene.load(ctx, &query, &mut self.view_state.state_text.filters);
                 self.view_state.ui_text(ctx, ui, &scene);
             }
 
             ViewCategory::TimeSeries => {
                 let mut scene = view_time_series::SceneTimeSeries::default();
-                scene.load(ctx, &query);
+                scene.load(ctx, &query, &self.view_state.state_time_series);
                 self.view_state.ui_time_series(ctx, ui, &scene);
             }
 
+            ViewCategory::ScatterPlot => {
+                let mut scene = view_scatter_plot::SceneScatterPlot::default();
+                scene.load(ctx, &query, &self.view_state.state_scatter_plot);
+                self.view_state.ui_scatter_plot(ctx, ui, &scene);
+            }
+
+            ViewCategory::Histogram => {
+                let mut scene = view_histogram::SceneHistogram::default();
+                scene.load(ctx, &query, &self.view_state.state_histogram);
+                self.view_state.ui_histogram(ctx, ui, &scene);
+            }
+
             ViewCategory::BarChart => {
                 let mut scene = view_bar_chart::SceneBarChart::default();
                 scene.load(ctx, &query);
@@ -218,15 +263,7 @@ impl SpaceView {
 
         let mut entities = Vec::new();
         tree.visit_children_recursively(&mut |entity_path: &EntityPath| {
-            let entity_categories =
-                categorize_entity_path(Timeline::log_time(), log_db, entity_path);
-
-            if entity_categories.contains(self.category)
-                && !self.data_blueprint.contains_entity(entity_path)
-                && spaces_info
-                    .is_reachable_by_transform(entity_path, &self.space_path)
-                    .is_ok()
-            {
+            if self.can_add_entity(entity_path, spaces_info, log_db) {
                 entities.push(entity_path.clone());
             }
         });
@@ -237,6 +274,27 @@ impl SpaceView {
             self.entities_determined_by_user = true;
         }
     }
+
+    /// Adds a rule that keeps adding the matching entities to this space view.
+    pub fn add_entity_pattern(&mut self, pattern: EntityPathPattern) {
+        self.data_blueprint.add_entity_pattern(pattern);
+        self.entities_determined_by_user = true;
+    }
+
+    /// Is this entity compatible with this space view, and not yet part of it?
+    fn can_add_entity(
+        &self,
+        entity_path: &EntityPath,
+        spaces_info: &SpaceInfoCollection,
+        log_db: &re_data_store::LogDb,
+    ) -> bool {
+        !self.data_blueprint.contains_entity(entity_path)
+            && categorize_entity_path(Timeline::log_time(), log_db, entity_path)
+                .contains(self.category)
+            && spaces_info
+                .is_reachable_by_transform(entity_path, &self.space_path)
+                .is_ok()
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -249,6 +307,8 @@ pub struct ViewState {
 
     state_text: view_text::ViewTextState,
     state_time_series: view_time_series::ViewTimeSeriesState,
+    state_scatter_plot: view_scatter_plot::ViewScatterPlotState,
+    state_histogram: view_histogram::ViewHistogramState,
     state_bar_chart: view_bar_chart::BarChartState,
     pub state_spatial: view_spatial::ViewSpatialState,
     state_tensors: ahash::HashMap<InstancePath, view_tensor::ViewTensorState>,
@@ -309,7 +369,13 @@ impl ViewState {
                         .state_tensors
                         .entry(selected_tensor.clone())
                         .or_insert_with(|| view_tensor::ViewTensorState::create(tensor));
-                    view_tensor::view_tensor(ctx, ui, state_tensor, tensor);
+                    view_tensor::view_tensor(
+                        ctx,
+                        ui,
+                        state_tensor,
+                        &selected_tensor.entity_path,
+                        tensor,
+                    );
                 }
             }
         }
@@ -343,6 +409,32 @@ impl ViewState {
         });
     }
 
+    fn ui_scatter_plot(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        ui: &mut egui::Ui,
+        scene: &view_scatter_plot::SceneScatterPlot,
+    ) {
+        ui.vertical(|ui| {
+            ui.scope(|ui| {
+                view_scatter_plot::view_scatter_plot(ctx, ui, &mut self.state_scatter_plot, scene);
+            });
+        });
+    }
+
+    fn ui_histogram(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        ui: &mut egui::Ui,
+        scene: &view_histogram::SceneHistogram,
+    ) {
+        ui.vertical(|ui| {
+            ui.scope(|ui| {
+                view_histogram::view_histogram(ctx, ui, &mut self.state_histogram, scene);
+            });
+        });
+    }
+
     fn ui_time_series(
         &mut self,
         ctx: &mut ViewerContext<'_>,
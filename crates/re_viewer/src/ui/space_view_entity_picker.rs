@@ -57,6 +57,8 @@ impl SpaceViewEntityPicker {
             .title_bar(false)
             .show(ui.ctx(), |ui| {
                 title_bar(ctx.re_ui, ui, title, &mut open);
+                entity_patterns_ui(ui, space_view);
+                ui.separator();
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     add_entities_ui(ctx, ui, space_view);
                 });
@@ -77,6 +79,59 @@ impl SpaceViewEntityPicker {
     }
 }
 
+/// Rules like `/robot/*/points` that keep adding the matching entities, see
+/// `DataBlueprintTree::entity_patterns`.
+fn entity_patterns_ui(ui: &mut egui::Ui, space_view: &mut SpaceView) {
+    let mut pattern_to_remove = None;
+    for pattern in space_view.data_blueprint.entity_patterns() {
+        ui.horizontal(|ui| {
+            ui.monospace(pattern.to_string());
+            if ui
+                .small_button("➖")
+                .on_hover_text("Stop adding the entities matching this pattern")
+                .clicked()
+            {
+                pattern_to_remove = Some(pattern.clone());
+            }
+        });
+    }
+    if let Some(pattern) = pattern_to_remove {
+        space_view.data_blueprint.remove_entity_pattern(&pattern);
+    }
+
+    let text_id = egui::Id::new(space_view.id).with("entity_pattern");
+    let mut text = ui.data_mut(|d| d.get_temp_mut_or_default::<String>(text_id).clone());
+
+    ui.horizontal(|ui| {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut text)
+                .hint_text("/robot/*/points")
+                .font(egui::TextStyle::Monospace),
+        );
+        let pattern = re_log_types::EntityPathPattern::parse(text.trim());
+        let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+        let mut add_button = ui
+            .add_enabled(pattern.is_ok(), egui::Button::new("Add pattern"))
+            .on_hover_text(
+                "Add all entities matching this pattern, including the ones logged later.\n\
+                 `*` matches any single part of a path, `**` any number of parts.",
+            );
+        if let (Err(err), false) = (&pattern, text.is_empty()) {
+            add_button = add_button.on_disabled_hover_text(err.to_string());
+        }
+
+        if let Ok(pattern) = pattern {
+            if enter_pressed || add_button.clicked() {
+                space_view.add_entity_pattern(pattern);
+                text.clear();
+            }
+        }
+    });
+
+    ui.data_mut(|d| d.insert_temp(text_id, text));
+}
+
 fn add_entities_ui(ctx: &mut ViewerContext<'_>, ui: &mut egui::Ui, space_view: &mut SpaceView) {
     let spaces_info = SpaceInfoCollection::new(&ctx.log_db.entity_db);
     let tree = &ctx.log_db.entity_db.tree;
@@ -147,6 +147,15 @@ fn default_created_space_views_from_candidates(
     // Main pass through all candidates.
     // We first check if a candidate is "interesting" and then split it up/modify it further if required.
     for candidate in candidates {
+        // Scatter plots and histograms would just duplicate every time series view,
+        // so they are only ever created on demand.
+        if matches!(
+            candidate.category,
+            ViewCategory::ScatterPlot | ViewCategory::Histogram
+        ) {
+            continue;
+        }
+
         if candidate.space_path.is_root() {
             if !categories_with_interesting_roots.contains(candidate.category) {
                 continue;
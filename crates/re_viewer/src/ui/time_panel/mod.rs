@@ -137,9 +137,12 @@ impl TimePanel {
     fn collapsed_ui(&mut self, ctx: &mut ViewerContext<'_>, ui: &mut egui::Ui) {
         ui.spacing_mut().item_spacing.x = 18.0; // from figma
 
-        ctx.rec_cfg
-            .time_ctrl
-            .time_control_ui(ctx.re_ui, ctx.log_db.times_per_timeline(), ui);
+        ctx.rec_cfg.time_ctrl.time_control_ui(
+            ctx.re_ui,
+            ctx.log_db.times_per_timeline(),
+            ctx.log_db.timeline_mapping(),
+            ui,
+        );
 
         {
             let mut time_range_rect = ui.available_rect_before_wrap();
@@ -249,7 +252,7 @@ impl TimePanel {
             ui,
             &time_area_painter,
             timeline_rect.top()..=timeline_rect.bottom(),
-            ctx.rec_cfg.time_ctrl.time_type(),
+            ctx.rec_cfg.time_ctrl.time_units(),
         );
         paint_time_ranges_gaps(
             &self.time_ranges_ui,
@@ -392,6 +395,7 @@ impl TimePanel {
             )
             .show_header(ui, |ui| {
                 ctx.entity_path_button_to(ui, None, &tree.path, text)
+                    .context_menu(|ui| crate::ui::entity_edit_menu(ctx, ui, &tree.path))
             })
             .body(|ui| {
                 self.show_children(
@@ -560,9 +564,12 @@ impl TimePanel {
 fn top_row_ui(ctx: &mut ViewerContext<'_>, ui: &mut egui::Ui) {
     ui.spacing_mut().item_spacing.x = 18.0; // from figma
 
-    ctx.rec_cfg
-        .time_ctrl
-        .time_control_ui(ctx.re_ui, ctx.log_db.times_per_timeline(), ui);
+    ctx.rec_cfg.time_ctrl.time_control_ui(
+        ctx.re_ui,
+        ctx.log_db.times_per_timeline(),
+        ctx.log_db.timeline_mapping(),
+        ui,
+    );
 
     current_time_ui(ctx, ui);
 
@@ -618,8 +625,8 @@ fn current_time_ui(ctx: &ViewerContext<'_>, ui: &mut egui::Ui) {
     if let Some(time_int) = ctx.rec_cfg.time_ctrl.time_int() {
         let timeline = ctx.rec_cfg.time_ctrl.timeline();
         if is_time_safe_to_show(ctx.log_db, timeline, time_int.into()) {
-            let time_type = ctx.rec_cfg.time_ctrl.time_type();
-            ui.monospace(time_type.format(time_int));
+            let time_units = ctx.rec_cfg.time_ctrl.time_units();
+            ui.monospace(time_units.format(time_int));
         }
     }
 }
@@ -872,7 +879,7 @@ fn initialize_time_ranges_ui(
         .prefix_times
         .get(ctx.rec_cfg.time_ctrl.timeline())
     {
-        let timeline_axis = TimelineAxis::new(ctx.rec_cfg.time_ctrl.time_type(), times);
+        let timeline_axis = TimelineAxis::new(ctx.rec_cfg.time_ctrl.time_units(), times);
         time_view = time_view.or_else(|| Some(view_everything(&time_x_range, &timeline_axis)));
         time_range.extend(timeline_axis.ranges);
     }
@@ -2,9 +2,9 @@ use std::ops::RangeInclusive;
 
 use egui::{lerp, pos2, remap_clamp, Align2, Color32, Rect, Rgba, Shape, Stroke};
 
-use re_log_types::{Time, TimeRangeF, TimeReal, TimeType};
+use re_log_types::{TimeRangeF, TimeReal, TimeUnits};
 
-use crate::misc::format_time::next_grid_tick_magnitude_ns;
+use crate::misc::format_time::{format_time_units_compact, next_grid_tick_magnitude_ns};
 
 use super::time_ranges_ui::TimeRangesUi;
 
@@ -13,7 +13,7 @@ pub fn paint_time_ranges_and_ticks(
     ui: &mut egui::Ui,
     time_area_painter: &egui::Painter,
     line_y_range: RangeInclusive<f32>,
-    time_type: TimeType,
+    time_units: TimeUnits,
 ) {
     let clip_rect = ui.clip_rect();
     let clip_left = clip_rect.left() as f64;
@@ -47,20 +47,20 @@ pub fn paint_time_ranges_and_ticks(
         let rect = Rect::from_x_y_ranges(x_range, line_y_range.clone());
         time_area_painter
             .with_clip_rect(rect)
-            .extend(paint_time_range_ticks(ui, &rect, time_type, &time_range));
+            .extend(paint_time_range_ticks(ui, &rect, time_units, &time_range));
     }
 }
 
 fn paint_time_range_ticks(
     ui: &mut egui::Ui,
     rect: &Rect,
-    time_type: TimeType,
+    time_units: TimeUnits,
     time_range: &TimeRangeF,
 ) -> Vec<Shape> {
     let font_id = egui::TextStyle::Small.resolve(ui.style());
 
-    match time_type {
-        TimeType::Time => {
+    match time_units {
+        TimeUnits::WallClock { .. } => {
             paint_ticks(
                 ui.ctx(),
                 ui.visuals().dark_mode,
@@ -69,10 +69,10 @@ fn paint_time_range_ticks(
                 &ui.clip_rect(),
                 time_range, // ns
                 next_grid_tick_magnitude_ns,
-                |ns| crate::misc::format_time::format_time_compact(Time::from_ns_since_epoch(ns)),
+                &|ns| format_time_units_compact(time_units, ns),
             )
         }
-        TimeType::Sequence => {
+        TimeUnits::Sequence | TimeUnits::Ticks { .. } | TimeUnits::Elapsed { .. } => {
             fn next_power_of_10(i: i64) -> i64 {
                 i * 10
             }
@@ -84,7 +84,7 @@ fn paint_time_range_ticks(
                 &ui.clip_rect(),
                 time_range,
                 next_power_of_10,
-                |seq| format!("#{seq}"),
+                &|ticks| format_time_units_compact(time_units, ticks),
             )
         }
     }
@@ -99,7 +99,7 @@ fn paint_ticks(
     clip_rect: &Rect,
     time_range: &TimeRangeF,
     next_time_step: fn(i64) -> i64,
-    format_tick: fn(i64) -> String,
+    format_tick: &dyn Fn(i64) -> String,
 ) -> Vec<egui::Shape> {
     crate::profile_function!();
 
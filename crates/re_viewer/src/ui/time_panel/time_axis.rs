@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use itertools::Itertools as _;
 
-use re_log_types::{TimeInt, TimeRange, TimeType};
+use re_log_types::{TimeInt, TimeRange, TimeUnits};
 
 /// A piece-wise linear view of a single timeline.
 ///
@@ -14,10 +14,10 @@ pub(crate) struct TimelineAxis {
 }
 
 impl TimelineAxis {
-    pub fn new<T>(time_type: TimeType, times: &BTreeMap<TimeInt, T>) -> Self {
+    pub fn new<T>(time_units: TimeUnits, times: &BTreeMap<TimeInt, T>) -> Self {
         crate::profile_function!();
         assert!(!times.is_empty());
-        let gap_threshold = gap_size_heuristic(time_type, times);
+        let gap_threshold = gap_size_heuristic(time_units, times);
         Self {
             ranges: create_ranges(times, gap_threshold),
         }
@@ -54,7 +54,7 @@ fn time_abs_diff(a: TimeInt, b: TimeInt) -> u64 {
 /// When looking at data recorded over hours, a few minutes of pause may be nothing.
 /// We also don't want to produce a timeline of only gaps.
 /// Finding a perfect heuristic is impossible, but we do our best!
-fn gap_size_heuristic<T>(time_type: TimeType, times: &BTreeMap<TimeInt, T>) -> u64 {
+fn gap_size_heuristic<T>(time_units: TimeUnits, times: &BTreeMap<TimeInt, T>) -> u64 {
     crate::profile_function!();
 
     assert!(!times.is_empty());
@@ -70,9 +70,9 @@ fn gap_size_heuristic<T>(time_type: TimeType, times: &BTreeMap<TimeInt, T>) -> u
 
     // We start off by a minimum gap size - any gap smaller than this will never be collapsed.
     // This is partially an optimization, and partially something that "feels right".
-    let min_gap_size: u64 = match time_type {
-        TimeType::Sequence => 9,
-        TimeType::Time => 100_000_000, // nanos!
+    let min_gap_size: u64 = match time_units.ticks_per_second() {
+        None => 9,
+        Some(ticks_per_second) => (0.1 * ticks_per_second) as u64, // a tenth of a second
     };
 
     // Collect all gaps larger than our minimum gap size.
@@ -142,7 +142,7 @@ mod tests {
             .iter()
             .map(|&seq| (TimeInt::from_sequence(seq), ()))
             .collect();
-        TimelineAxis::new(TimeType::Sequence, &times).ranges
+        TimelineAxis::new(TimeUnits::Sequence, &times).ranges
     }
 
     #[test]
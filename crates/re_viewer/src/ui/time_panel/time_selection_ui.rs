@@ -1,7 +1,7 @@
 use egui::{CursorIcon, Id, NumExt as _, Rect};
 
 use re_data_store::LogDb;
-use re_log_types::{Duration, TimeInt, TimeRangeF, TimeReal, TimeType};
+use re_log_types::{TimeInt, TimeRangeF, TimeReal, TimeUnits};
 
 use crate::{misc::time_control::Looping, TimeControl};
 
@@ -19,7 +19,7 @@ pub fn loop_selection_ui(
 
     if time_ctrl.loop_selection().is_none() && time_ctrl.looping() == Looping::Selection {
         // Helpfully select a time slice
-        if let Some(selection) = initial_time_selection(time_ranges_ui, time_ctrl.time_type()) {
+        if let Some(selection) = initial_time_selection(time_ranges_ui, time_ctrl.time_units()) {
             time_ctrl.set_loop_selection(selection);
         }
     }
@@ -163,7 +163,7 @@ pub fn loop_selection_ui(
 
 fn initial_time_selection(
     time_ranges_ui: &TimeRangesUi,
-    time_type: TimeType,
+    time_units: TimeUnits,
 ) -> Option<TimeRangeF> {
     let ranges = &time_ranges_ui.segments;
 
@@ -172,21 +172,18 @@ fn initial_time_selection(
         for segment in ranges {
             let range = &segment.tight_time;
             if range.min < range.max {
-                match time_type {
-                    TimeType::Time => {
-                        let seconds = Duration::from(range.max - range.min).as_secs_f64();
-                        if seconds > min_duration {
-                            let one_sec = TimeInt::from(Duration::from_secs(1.0));
-                            return Some(TimeRangeF::new(range.min, range.min + one_sec));
-                        }
-                    }
-                    TimeType::Sequence => {
-                        return Some(TimeRangeF::new(
-                            range.min,
-                            TimeReal::from(range.min)
-                                + TimeReal::from((range.max - range.min).as_f64() / 2.0),
-                        ));
+                if let Some(ticks_per_second) = time_units.ticks_per_second() {
+                    let seconds = (range.max - range.min).as_f64() / ticks_per_second;
+                    if seconds > min_duration {
+                        let one_sec = TimeReal::from(ticks_per_second);
+                        return Some(TimeRangeF::new(range.min, range.min + one_sec));
                     }
+                } else {
+                    return Some(TimeRangeF::new(
+                        range.min,
+                        TimeReal::from(range.min)
+                            + TimeReal::from((range.max - range.min).as_f64() / 2.0),
+                    ));
                 }
             }
         }
@@ -320,7 +317,9 @@ fn paint_range_text(
         painter.line_segment([tip, tip - tip_length * (rot.inverse() * dir)], stroke);
     }
 
-    let range_text = format_duration(time_ctrl.time_type(), selected_range.length().abs());
+    let range_text = time_ctrl
+        .time_units()
+        .format_duration(selected_range.length().abs());
     if range_text.is_empty() {
         return;
     }
@@ -355,11 +354,3 @@ fn paint_range_text(
         );
     }
 }
-
-/// Human-readable description of a duration
-fn format_duration(time_typ: TimeType, duration: TimeReal) -> String {
-    match time_typ {
-        TimeType::Time => Duration::from(duration).to_string(),
-        TimeType::Sequence => duration.round().as_i64().to_string(), // TODO(emilk): show real part?
-    }
-}
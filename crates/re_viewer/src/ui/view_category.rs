@@ -3,7 +3,7 @@ use re_data_store::{query_latest_single, EntityPath, LogDb, Timeline};
 use re_log_types::{
     component_types::{
         Box3D, LineStrip2D, LineStrip3D, Point2D, Point3D, Rect2D, Scalar, Tensor, TensorTrait,
-        TextEntry,
+        TextEntry, VoxelGrid,
     },
     msg_bundle::Component,
     Arrow3D, Mesh3D, Transform,
@@ -22,6 +22,12 @@ pub enum ViewCategory {
     /// Time series plot (scalar over time)
     TimeSeries,
 
+    /// Scatter plot (scalar against scalar, paired up by time)
+    ScatterPlot,
+
+    /// Histogram (distribution of a scalar over time)
+    Histogram,
+
     /// Bar-chart plots made from 1D tensor data
     BarChart,
 
@@ -37,7 +43,9 @@ impl ViewCategory {
     pub fn icon(self) -> &'static re_ui::Icon {
         match self {
             ViewCategory::Text => &re_ui::icons::SPACE_VIEW_TEXT,
-            ViewCategory::TimeSeries => &re_ui::icons::SPACE_VIEW_SCATTERPLOT,
+            ViewCategory::TimeSeries => &re_ui::icons::SPACE_VIEW_CHART,
+            ViewCategory::ScatterPlot => &re_ui::icons::SPACE_VIEW_SCATTERPLOT,
+            ViewCategory::Histogram => &re_ui::icons::SPACE_VIEW_HISTOGRAM,
             ViewCategory::BarChart => &re_ui::icons::SPACE_VIEW_HISTOGRAM,
             ViewCategory::Spatial => &re_ui::icons::SPACE_VIEW_3D,
             ViewCategory::Tensor => &re_ui::icons::SPACE_VIEW_TENSOR,
@@ -50,6 +58,8 @@ impl std::fmt::Display for ViewCategory {
         f.write_str(match self {
             ViewCategory::Text => "Text",
             ViewCategory::TimeSeries => "Time Series",
+            ViewCategory::ScatterPlot => "Scatter Plot",
+            ViewCategory::Histogram => "Histogram",
             ViewCategory::BarChart => "Bar Chart",
             ViewCategory::Spatial => "Spatial",
             ViewCategory::Tensor => "Tensor",
@@ -93,6 +103,8 @@ pub fn categorize_entity_path(
             set.insert(ViewCategory::Text);
         } else if component == Scalar::name() {
             set.insert(ViewCategory::TimeSeries);
+            set.insert(ViewCategory::ScatterPlot);
+            set.insert(ViewCategory::Histogram);
         } else if component == Point2D::name()
             || component == Point3D::name()
             || component == Rect2D::name()
@@ -102,6 +114,7 @@ pub fn categorize_entity_path(
             || component == Mesh3D::name()
             || component == Arrow3D::name()
             || component == Transform::name()
+            || component == VoxelGrid::name()
         {
             set.insert(ViewCategory::Spatial);
         } else if component == Tensor::name() {
@@ -119,6 +132,10 @@ pub fn categorize_entity_path(
                             set.insert(ViewCategory::BarChart);
                         } else if tensor.is_shaped_like_an_image() {
                             set.insert(ViewCategory::Spatial);
+                        } else if tensor.is_shaped_like_a_volume() {
+                            // Rendered as a volume in spatial views, sliced in tensor views.
+                            set.insert(ViewCategory::Spatial);
+                            set.insert(ViewCategory::Tensor);
                         } else {
                             set.insert(ViewCategory::Tensor);
                         }
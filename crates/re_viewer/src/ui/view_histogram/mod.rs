@@ -0,0 +1,5 @@
+mod scene;
+pub(crate) use self::scene::SceneHistogram;
+
+mod ui;
+pub(crate) use self::ui::{view_histogram, ViewHistogramState, HELP_TEXT};
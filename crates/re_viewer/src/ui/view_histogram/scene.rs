@@ -0,0 +1,137 @@
+use re_data_store::EntityPath;
+
+use crate::{
+    ui::{
+        annotations::auto_color,
+        scene::{loop_selection_range, range_scalars},
+        SceneQuery,
+    },
+    ViewerContext,
+};
+
+use super::ViewHistogramState;
+
+// ---
+
+#[derive(Clone, Debug)]
+pub struct HistogramSeries {
+    pub ent_path: EntityPath,
+    pub color: egui::Color32,
+
+    /// Number of values that fell into each bin.
+    pub counts: Vec<u64>,
+}
+
+/// A histogram scene, with everything needed to render it.
+///
+/// All series share the same bins, so that they can be compared with each other.
+#[derive(Default, Debug)]
+pub struct SceneHistogram {
+    /// Lower bound of the first bin.
+    pub min: f64,
+
+    /// Width of every single bin.
+    pub bin_width: f64,
+
+    pub series: Vec<HistogramSeries>,
+}
+
+impl SceneHistogram {
+    /// Loads and bins all scalars within the loop selection according to the given query.
+    pub(crate) fn load(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        state: &ViewHistogramState,
+    ) {
+        crate::profile_function!();
+
+        let store = &ctx.log_db.entity_db.data_store;
+        let range = loop_selection_range(&ctx.rec_cfg.time_ctrl);
+
+        let mut ent_paths = query
+            .iter_entities()
+            .map(|(ent_path, _)| ent_path)
+            .collect::<Vec<_>>();
+        ent_paths.sort(); // determinism
+
+        let values_per_entity = ent_paths
+            .into_iter()
+            .map(|ent_path| {
+                let values = range_scalars(store, query.timeline, range, ent_path)
+                    .into_iter()
+                    .map(|(_, value)| value)
+                    .filter(|value| value.is_finite())
+                    .collect::<Vec<_>>();
+                (ent_path, values)
+            })
+            .filter(|(_, values)| !values.is_empty())
+            .collect::<Vec<_>>();
+
+        let (min, max) = values_per_entity
+            .iter()
+            .flat_map(|(_, values)| values.iter().copied())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        if min > max {
+            return; // no data
+        }
+
+        let num_bins = state.num_bins.max(1);
+        self.min = min;
+        self.bin_width = bin_width(min, max, num_bins);
+
+        for (ent_path, values) in values_per_entity {
+            self.series.push(HistogramSeries {
+                ent_path: ent_path.clone(),
+                color: auto_color(egui::util::hash(ent_path) as _),
+                counts: bin_values(&values, min, self.bin_width, num_bins),
+            });
+        }
+    }
+}
+
+/// Width of each of the `num_bins` bins needed to cover `[min, max]`.
+///
+/// Never zero, so that a range made of a single value still results in a visible bin.
+fn bin_width(min: f64, max: f64, num_bins: usize) -> f64 {
+    let width = (max - min) / num_bins as f64;
+    if width > 0.0 {
+        width
+    } else {
+        1.0
+    }
+}
+
+/// Counts how many `values` fall into each of the `num_bins` bins starting at `min`.
+///
+/// The upper bound is inclusive for the last bin.
+fn bin_values(values: &[f64], min: f64, bin_width: f64, num_bins: usize) -> Vec<u64> {
+    let mut counts = vec![0; num_bins];
+    for value in values {
+        let bin = ((value - min) / bin_width) as usize; // saturating cast
+        counts[bin.min(num_bins - 1)] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binning() {
+        let values = [0.0, 0.5, 1.0, 2.5, 3.9, 4.0];
+        let width = bin_width(0.0, 4.0, 4);
+        assert_eq!(width, 1.0);
+        assert_eq!(bin_values(&values, 0.0, width, 4), vec![2, 1, 1, 2]);
+    }
+
+    #[test]
+    fn binning_single_value() {
+        let width = bin_width(3.0, 3.0, 8);
+        assert_eq!(width, 1.0);
+        assert_eq!(bin_values(&[3.0, 3.0], 3.0, width, 8)[0], 2);
+    }
+}
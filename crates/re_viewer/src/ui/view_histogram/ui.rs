@@ -0,0 +1,74 @@
+use egui::plot::{Bar, BarChart, Legend, Plot};
+
+use crate::ViewerContext;
+
+use super::SceneHistogram;
+
+// ---
+
+pub(crate) const HELP_TEXT: &str = "\
+    Distribution of scalar values within the loop selection (or the whole timeline if there is none).\n\
+    Pan by dragging, or scroll (+ shift = horizontal).\n\
+    Box zooming: Right click to zoom in and zoom out using a selection.\n\
+    Reset view with double-click.";
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ViewHistogramState {
+    pub num_bins: usize,
+}
+
+impl Default for ViewHistogramState {
+    fn default() -> Self {
+        Self { num_bins: 32 }
+    }
+}
+
+impl ViewHistogramState {
+    pub fn selection_ui(&mut self, re_ui: &re_ui::ReUi, ui: &mut egui::Ui) {
+        re_ui
+            .selection_grid(ui, "histogram_config")
+            .num_columns(2)
+            .show(ui, |ui| {
+                re_ui.grid_left_hand_label(ui, "Bins");
+                ui.add(egui::Slider::new(&mut self.num_bins, 1..=256).logarithmic(true));
+                ui.end_row();
+            });
+    }
+}
+
+pub(crate) fn view_histogram(
+    _ctx: &mut ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    _state: &mut ViewHistogramState,
+    scene: &SceneHistogram,
+) -> egui::Response {
+    crate::profile_function!();
+
+    Plot::new("histogram_plot")
+        .legend(Legend::default())
+        .clamp_grid(true)
+        .show(ui, |plot_ui| {
+            for series in &scene.series {
+                let name = series.ent_path.to_string();
+                let fill = series.color.gamma_multiply(0.75).additive(); // make sure overlapping bars are obvious
+                let bars = series
+                    .counts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, count)| {
+                        let min = scene.min + i as f64 * scene.bin_width;
+                        let max = min + scene.bin_width;
+                        Bar::new(min + 0.5 * scene.bin_width, *count as f64)
+                            .width(scene.bin_width)
+                            .name(format!("{name} [{min:.3}, {max:.3}]"))
+                            .fill(fill)
+                            .stroke(egui::Stroke::NONE)
+                    })
+                    .collect();
+
+                plot_ui.bar_chart(BarChart::new(bars).name(&name).color(series.color));
+            }
+        })
+        .response
+}
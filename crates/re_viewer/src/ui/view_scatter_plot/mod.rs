@@ -0,0 +1,5 @@
+mod scene;
+pub(crate) use self::scene::SceneScatterPlot;
+
+mod ui;
+pub(crate) use self::ui::{view_scatter_plot, ViewScatterPlotState, HELP_TEXT};
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use re_data_store::EntityPath;
+
+use crate::{
+    ui::{
+        annotations::auto_color,
+        scene::{loop_selection_range, range_scalars},
+        SceneQuery,
+    },
+    ViewerContext,
+};
+
+use super::ViewScatterPlotState;
+
+// ---
+
+#[derive(Clone, Debug)]
+pub struct ScatterSeries {
+    pub ent_path: EntityPath,
+    pub color: egui::Color32,
+
+    /// `[x, y]` pairs, one for each time at which both scalars were logged.
+    pub points: Vec<[f64; 2]>,
+}
+
+/// A scatter plot scene, with everything needed to render it.
+///
+/// Every scalar entity in the view is plotted against the scalar entity used as the x-axis,
+/// pairing up the values that were logged at the exact same time.
+#[derive(Default, Debug)]
+pub struct SceneScatterPlot {
+    /// The entity whose values are used for the x-axis.
+    pub x_ent_path: Option<EntityPath>,
+
+    pub series: Vec<ScatterSeries>,
+}
+
+impl SceneScatterPlot {
+    /// Loads all scalar pairs into the scene according to the given query.
+    pub(crate) fn load(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        state: &ViewScatterPlotState,
+    ) {
+        crate::profile_function!();
+
+        let store = &ctx.log_db.entity_db.data_store;
+        let range = loop_selection_range(&ctx.rec_cfg.time_ctrl);
+
+        let mut ent_paths = query
+            .iter_entities()
+            .map(|(ent_path, _)| ent_path)
+            .collect::<Vec<_>>();
+        ent_paths.sort(); // determinism
+
+        let x_ent_path = match &state.x_ent_path {
+            Some(x_ent_path) if ent_paths.contains(&x_ent_path) => x_ent_path.clone(),
+            _ => {
+                let Some(first) = ent_paths.first() else {
+                    return;
+                };
+                (*first).clone()
+            }
+        };
+
+        // If several scalars share the same time, the last one wins.
+        let x_values: BTreeMap<_, _> = range_scalars(store, query.timeline, range, &x_ent_path)
+            .into_iter()
+            .collect();
+
+        let y_ent_paths = ent_paths
+            .into_iter()
+            .filter(|ent_path| **ent_path != x_ent_path);
+
+        for ent_path in y_ent_paths {
+            let points = range_scalars(store, query.timeline, range, ent_path)
+                .into_iter()
+                .filter_map(|(time, y)| x_values.get(&time).map(|x| [*x, y]))
+                .collect::<Vec<_>>();
+
+            if points.is_empty() {
+                continue;
+            }
+
+            self.series.push(ScatterSeries {
+                ent_path: ent_path.clone(),
+                color: auto_color(egui::util::hash(ent_path) as _),
+                points,
+            });
+        }
+
+        self.x_ent_path = Some(x_ent_path);
+    }
+}
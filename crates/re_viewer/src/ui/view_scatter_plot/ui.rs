@@ -0,0 +1,98 @@
+use egui::plot::{Legend, Plot, Points};
+
+use re_data_store::EntityPath;
+
+use crate::ViewerContext;
+
+use super::SceneScatterPlot;
+
+// ---
+
+pub(crate) const HELP_TEXT: &str = "\
+    Plots scalars against each other, pairing up values logged at the same time.\n\
+    Only the loop selection is shown, if there is one.\n\
+    Pan by dragging, or scroll (+ shift = horizontal).\n\
+    Box zooming: Right click to zoom in and zoom out using a selection.\n\
+    Reset view with double-click.";
+
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ViewScatterPlotState {
+    /// The entity used for the x-axis.
+    ///
+    /// `None` means the first entity of the space view.
+    pub x_ent_path: Option<EntityPath>,
+}
+
+impl ViewScatterPlotState {
+    pub fn selection_ui<'a>(
+        &mut self,
+        re_ui: &re_ui::ReUi,
+        ui: &mut egui::Ui,
+        ent_paths: impl Iterator<Item = &'a EntityPath>,
+    ) {
+        crate::profile_function!();
+
+        let mut ent_paths = ent_paths.collect::<Vec<_>>();
+        ent_paths.sort();
+
+        re_ui
+            .selection_grid(ui, "scatter_plot_config")
+            .num_columns(2)
+            .show(ui, |ui| {
+                re_ui.grid_left_hand_label(ui, "X axis");
+                let selected_text = self
+                    .x_ent_path
+                    .as_ref()
+                    .map_or_else(|| "(first entity)".to_owned(), |path| path.to_string());
+                egui::ComboBox::from_id_source("scatter_plot_x_axis")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.x_ent_path, None, "(first entity)");
+                        for ent_path in ent_paths {
+                            ui.selectable_value(
+                                &mut self.x_ent_path,
+                                Some(ent_path.clone()),
+                                ent_path.to_string(),
+                            );
+                        }
+                    });
+                ui.end_row();
+            });
+    }
+}
+
+pub(crate) fn view_scatter_plot(
+    _ctx: &mut ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    _state: &mut ViewScatterPlotState,
+    scene: &SceneScatterPlot,
+) -> egui::Response {
+    crate::profile_function!();
+
+    let x_name = scene
+        .x_ent_path
+        .as_ref()
+        .map_or_else(|| "x".to_owned(), |path| path.to_string());
+
+    Plot::new("scatter_plot")
+        .legend(Legend {
+            position: egui::plot::Corner::RightBottom,
+            ..Default::default()
+        })
+        .label_formatter(move |name, value| {
+            let name = if name.is_empty() { "y" } else { name };
+            format!("{x_name}: {:.5}\n{name}: {:.5}", value.x, value.y)
+        })
+        .show(ui, |plot_ui| {
+            for series in &scene.series {
+                plot_ui.points(
+                    Points::new(series.points.clone())
+                        .name(series.ent_path.to_string())
+                        .color(series.color)
+                        .radius(1.5),
+                );
+            }
+        })
+        .response
+}
@@ -0,0 +1,491 @@
+//! Interactive measurement and annotation tools for the spatial views.
+
+use egui::{pos2, Align2, Color32, Pos2, Rect, Shape, Stroke, WidgetText};
+use glam::{Vec2, Vec3};
+use re_format::format_f32;
+use re_log_types::{
+    component_types::{Label, LineStrip2D, Rect2D},
+    msg_bundle::{ComponentBundle, MsgBundle, MsgBundleError},
+    EntityPath, EntityPathPart, Index, LogMsg, MsgId, TimePoint,
+};
+
+use crate::misc::ViewerContext;
+
+/// How close (in ui points) a click has to be to the first point of a polygon to close it.
+const CLOSE_POLYGON_DISTANCE: f32 = 8.0;
+
+const MEASUREMENT_COLOR: Color32 = Color32::from_rgb(255, 200, 0);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum MeasurementTool {
+    /// Clicks select things, as usual.
+    #[default]
+    None,
+    Distance,
+    Angle,
+    Rectangle,
+    Polygon,
+}
+
+impl MeasurementTool {
+    pub const ALL: [Self; 5] = [
+        Self::None,
+        Self::Distance,
+        Self::Angle,
+        Self::Rectangle,
+        Self::Polygon,
+    ];
+
+    /// Rectangles and polygons are drawn in image space and only make sense in 2D.
+    pub fn is_2d_only(self) -> bool {
+        matches!(self, Self::Rectangle | Self::Polygon)
+    }
+
+    /// Number of points needed to complete a measurement.
+    /// `None` for polygons, which are closed explicitly.
+    fn num_points(self) -> Option<usize> {
+        match self {
+            Self::None => Some(0),
+            Self::Distance | Self::Rectangle => Some(2),
+            Self::Angle => Some(3),
+            Self::Polygon => None,
+        }
+    }
+
+    fn hint(self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Distance => "Click two points to measure the distance between them.",
+            Self::Angle => "Click three points to measure the angle at the second one.",
+            Self::Rectangle => "Click two opposite corners to draw a box.",
+            Self::Polygon => {
+                "Click to add vertices. Click the first vertex again or press Enter to close the polygon."
+            }
+        }
+    }
+}
+
+impl From<MeasurementTool> for WidgetText {
+    fn from(val: MeasurementTool) -> Self {
+        match val {
+            MeasurementTool::None => "Select".into(),
+            MeasurementTool::Distance => "Distance".into(),
+            MeasurementTool::Angle => "Angle".into(),
+            MeasurementTool::Rectangle => "Box".into(),
+            MeasurementTool::Polygon => "Polygon".into(),
+        }
+    }
+}
+
+/// A finished measurement or annotation, in space coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Measurement {
+    Distance([Vec3; 2]),
+
+    /// The angle is measured at the middle point.
+    Angle([Vec3; 3]),
+
+    /// Two opposite corners.
+    Rectangle([Vec2; 2]),
+
+    Polygon(Vec<Vec2>),
+}
+
+impl Measurement {
+    fn from_points(tool: MeasurementTool, points: &[Vec3]) -> Option<Self> {
+        match (tool, points) {
+            (MeasurementTool::Distance, [a, b]) => Some(Self::Distance([*a, *b])),
+            (MeasurementTool::Angle, [a, b, c]) => Some(Self::Angle([*a, *b, *c])),
+            (MeasurementTool::Rectangle, [a, b]) => {
+                Some(Self::Rectangle([a.truncate(), b.truncate()]))
+            }
+            (MeasurementTool::Polygon, points) if points.len() >= 3 => {
+                Some(Self::Polygon(points.iter().map(|p| p.truncate()).collect()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The vertices of the outline, closed shapes repeat their first vertex.
+    fn outline(&self) -> Vec<Vec3> {
+        match self {
+            Self::Distance(points) => points.to_vec(),
+            Self::Angle(points) => points.to_vec(),
+            Self::Rectangle([a, b]) => [
+                Vec2::new(a.x, a.y),
+                Vec2::new(b.x, a.y),
+                Vec2::new(b.x, b.y),
+                Vec2::new(a.x, b.y),
+                Vec2::new(a.x, a.y),
+            ]
+            .iter()
+            .map(|p| p.extend(0.0))
+            .collect(),
+            Self::Polygon(points) => points
+                .iter()
+                .chain(points.first())
+                .map(|p| p.extend(0.0))
+                .collect(),
+        }
+    }
+
+    /// Where to put the label.
+    fn anchor(&self) -> Vec3 {
+        match self {
+            Self::Distance([a, b]) => a.lerp(*b, 0.5),
+            Self::Angle([_, b, _]) => *b,
+            Self::Rectangle([a, b]) => Vec2::new(a.x.min(b.x), a.y.max(b.y)).extend(0.0),
+            Self::Polygon(points) => {
+                (points.iter().sum::<Vec2>() / points.len().max(1) as f32).extend(0.0)
+            }
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Distance([a, b]) => format_f32(a.distance(*b)),
+            Self::Angle(points) => format!("{:.1}°", angle_at(points).to_degrees()),
+            Self::Rectangle([a, b]) => {
+                let size = (*b - *a).abs();
+                format!("{} × {}", format_f32(size.x), format_f32(size.y))
+            }
+            Self::Polygon(points) => format!("area {}", format_f32(polygon_area(points))),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Distance(_) => "Distance",
+            Self::Angle(_) => "Angle",
+            Self::Rectangle(_) => "Box",
+            Self::Polygon(_) => "Polygon",
+        }
+    }
+
+    /// The components this measurement is exported as.
+    fn component_bundles(&self) -> Result<Vec<ComponentBundle>, MsgBundleError> {
+        let label = ComponentBundle::try_from(&[Label(self.label())][..])?;
+        let shape = if let Self::Rectangle([a, b]) = self {
+            let min = a.min(*b);
+            let size = (*b - *a).abs();
+            ComponentBundle::try_from(&[Rect2D::from_xywh(min.x, min.y, size.x, size.y)][..])?
+        } else {
+            let strip = LineStrip2D(self.outline().iter().map(|p| p.truncate().into()).collect());
+            ComponentBundle::try_from(&[strip][..])?
+        };
+        Ok(vec![shape, label])
+    }
+}
+
+/// The angle at `b` between `a` and `c`, in radians.
+fn angle_at([a, b, c]: &[Vec3; 3]) -> f32 {
+    (*a - *b).angle_between(*c - *b)
+}
+
+/// Shoelace formula.
+fn polygon_area(points: &[Vec2]) -> f32 {
+    let twice_area: f32 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(p, q)| p.perp_dot(*q))
+        .sum();
+    twice_area.abs() * 0.5
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct MeasurementState {
+    pub tool: MeasurementTool,
+
+    /// Points placed so far for the measurement currently being drawn.
+    #[serde(skip)]
+    pending: Vec<Vec3>,
+
+    #[serde(skip)]
+    measurements: Vec<Measurement>,
+}
+
+impl MeasurementState {
+    pub fn is_active(&self) -> bool {
+        self.tool != MeasurementTool::None
+    }
+
+    /// Places points on click.
+    ///
+    /// `hovered_point` is the point under the pointer in space coordinates (if any),
+    /// `ui_from_space` projects space coordinates onto the screen.
+    ///
+    /// Returns `true` if the input was used by the active tool,
+    /// in which case it should not be used for selection.
+    pub fn handle_input(
+        &mut self,
+        response: &egui::Response,
+        hovered_point: Option<Vec3>,
+        ui_from_space: &dyn Fn(Vec3) -> Option<Pos2>,
+    ) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+
+        if response.hovered() {
+            response.ctx.input(|i| {
+                if i.key_pressed(egui::Key::Escape) {
+                    self.pending.clear();
+                }
+                if i.key_pressed(egui::Key::Enter) {
+                    self.finish_pending();
+                }
+            });
+        }
+
+        if response.secondary_clicked() {
+            self.pending.pop();
+        }
+
+        if !response.clicked() {
+            return response.secondary_clicked();
+        }
+        let Some(point) = hovered_point else { return true; };
+
+        if self.tool == MeasurementTool::Polygon && self.pending.len() >= 3 {
+            let closes_polygon = response.interact_pointer_pos().map_or(false, |pointer| {
+                ui_from_space(self.pending[0]).map_or(false, |first| {
+                    first.distance(pointer) < CLOSE_POLYGON_DISTANCE
+                })
+            });
+            if closes_polygon {
+                self.finish_pending();
+                return true;
+            }
+        }
+
+        self.pending.push(point);
+        if self.tool.num_points() == Some(self.pending.len()) {
+            self.finish_pending();
+        }
+        true
+    }
+
+    fn finish_pending(&mut self) {
+        let points = std::mem::take(&mut self.pending);
+        if let Some(measurement) = Measurement::from_points(self.tool, &points) {
+            self.measurements.push(measurement);
+        }
+    }
+
+    /// Shapes for all measurements, plus a preview of the one being drawn.
+    pub fn shapes(
+        &self,
+        ui: &egui::Ui,
+        hovered_point: Option<Vec3>,
+        ui_from_space: &dyn Fn(Vec3) -> Option<Pos2>,
+    ) -> Vec<Shape> {
+        let stroke = Stroke::new(2.0, MEASUREMENT_COLOR);
+        let mut shapes = Vec::new();
+
+        let add_polyline = |shapes: &mut Vec<Shape>, points: &[Vec3]| {
+            let points_in_ui: Vec<Pos2> = points.iter().filter_map(|p| ui_from_space(*p)).collect();
+            for p in &points_in_ui {
+                shapes.push(Shape::circle_filled(*p, 3.0, MEASUREMENT_COLOR));
+            }
+            shapes.push(Shape::line(points_in_ui, stroke));
+        };
+
+        for measurement in &self.measurements {
+            add_polyline(&mut shapes, &measurement.outline());
+            if let Some(anchor) = ui_from_space(measurement.anchor()) {
+                shapes.extend(label_shapes(ui, anchor, measurement.label()));
+            }
+        }
+
+        if self.is_active() && !self.pending.is_empty() {
+            let mut preview = self.pending.clone();
+            preview.extend(hovered_point);
+            if self.tool == MeasurementTool::Rectangle && preview.len() == 2 {
+                let rect = Measurement::Rectangle([preview[0].truncate(), preview[1].truncate()]);
+                add_polyline(&mut shapes, &rect.outline());
+            } else {
+                add_polyline(&mut shapes, &preview);
+            }
+        }
+
+        shapes
+    }
+
+    pub fn selection_ui(
+        &mut self,
+        ctx: &ViewerContext<'_>,
+        ui: &mut egui::Ui,
+        space: &EntityPath,
+        is_2d: bool,
+    ) {
+        if !is_2d && self.tool.is_2d_only() {
+            self.tool = MeasurementTool::None;
+        }
+
+        ui.vertical(|ui| {
+            let tool_before = self.tool;
+            egui::ComboBox::from_id_source("measurement_tool")
+                .selected_text(self.tool)
+                .show_ui(ui, |ui| {
+                    ui.style_mut().wrap = Some(false);
+                    ui.set_min_width(64.0);
+                    for tool in MeasurementTool::ALL {
+                        if is_2d || !tool.is_2d_only() {
+                            ui.selectable_value(&mut self.tool, tool, tool);
+                        }
+                    }
+                });
+            if self.tool != tool_before {
+                self.pending.clear();
+            }
+            if self.is_active() {
+                ui.label(self.tool.hint());
+            }
+
+            let mut removed = None;
+            for (i, measurement) in self.measurements.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ctx.re_ui.small_icon_button(ui, &re_ui::icons::REMOVE).clicked() {
+                        removed = Some(i);
+                    }
+                    ui.label(format!("{}: {}", measurement.kind(), measurement.label()));
+                });
+            }
+            if let Some(i) = removed {
+                self.measurements.remove(i);
+            }
+
+            if !self.measurements.is_empty() {
+                ui.horizontal(|ui| {
+                    if ui.button("Clear").clicked() {
+                        self.measurements.clear();
+                    }
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if is_2d
+                        && ui
+                            .button("Export…")
+                            .on_hover_text(
+                                "Save the annotations as Rect2D and LineStrip2D components to an .rrd file",
+                            )
+                            .clicked()
+                    {
+                        export_measurements(ctx, space, &self.measurements);
+                    }
+                });
+            }
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        let _ = space;
+    }
+}
+
+/// A label with a dark background, centered below `anchor`.
+fn label_shapes(ui: &egui::Ui, anchor: Pos2, text: String) -> [Shape; 2] {
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let galley = ui.fonts(|fonts| fonts.layout_no_wrap(text, font_id, MEASUREMENT_COLOR));
+    let rect = Align2::CENTER_TOP.anchor_rect(Rect::from_min_size(
+        anchor + egui::vec2(0.0, 5.0),
+        galley.size(),
+    ));
+    [
+        Shape::rect_filled(rect.expand(2.0), 2.0, Color32::from_black_alpha(196)),
+        Shape::galley(rect.min, galley),
+    ]
+}
+
+/// Projects a 2D space position onto the screen.
+pub fn ui_from_space_2d(transform: egui::emath::RectTransform) -> impl Fn(Vec3) -> Option<Pos2> {
+    move |p| Some(transform.transform_pos(pos2(p.x, p.y)))
+}
+
+/// Projects a 3D world position onto the screen, `None` if it is behind the camera.
+pub fn ui_from_world_3d(ui_from_world: glam::Mat4) -> impl Fn(Vec3) -> Option<Pos2> {
+    move |p| {
+        let p = ui_from_world * p.extend(1.0);
+        (p.w > 0.0).then(|| pos2(p.x / p.w, p.y / p.w))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(not(target_arch = "wasm32"))]
+fn export_measurements(ctx: &ViewerContext<'_>, space: &EntityPath, measurements: &[Measurement]) {
+    let Some(path) = rfd::FileDialog::new()
+        .set_file_name("annotations.rrd")
+        .set_title("Export annotations")
+        .save_file()
+    else {
+        return;
+    };
+
+    match write_measurements(ctx, space, measurements, &path) {
+        Ok(()) => re_log::info!("Exported {} annotations to {path:?}", measurements.len()),
+        Err(err) => re_log::error!("Failed to export annotations to {path:?}: {err}"),
+    }
+}
+
+/// Writes the measurements as `space/annotations/#i`, at the current time.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_measurements(
+    ctx: &ViewerContext<'_>,
+    space: &EntityPath,
+    measurements: &[Measurement],
+    path: &std::path::Path,
+) -> anyhow::Result<()> {
+    use anyhow::Context as _;
+
+    let time_ctrl = &ctx.rec_cfg.time_ctrl;
+    let time_point = match time_ctrl.time_int() {
+        Some(time) => TimePoint::from([(*time_ctrl.timeline(), time)]),
+        None => TimePoint::timeless(),
+    };
+
+    // Keep the recording id, so the annotations can be loaded alongside the original data.
+    let mut msgs = Vec::new();
+    if let Some(info) = ctx.log_db.recording_info() {
+        msgs.push(LogMsg::BeginRecordingMsg(re_log_types::BeginRecordingMsg {
+            msg_id: MsgId::random(),
+            info: info.clone(),
+        }));
+    }
+
+    for (i, measurement) in measurements.iter().enumerate() {
+        let ent_path = space.join(&EntityPath::new(vec![
+            EntityPathPart::from("annotations"),
+            EntityPathPart::from(Index::Sequence(i as _)),
+        ]));
+        let bundle = MsgBundle::new(
+            MsgId::random(),
+            ent_path,
+            time_point.clone(),
+            measurement.component_bundles()?,
+        );
+        msgs.push(LogMsg::ArrowMsg(bundle.try_into()?));
+    }
+
+    let file = std::fs::File::create(path).with_context(|| format!("Failed to create {path:?}"))?;
+    re_log_types::encoding::encode(msgs.iter(), file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measurements() {
+        let angle = Measurement::Angle([Vec3::X, Vec3::ZERO, Vec3::Y]);
+        assert_eq!(angle.label(), "90.0°");
+
+        let square = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| (p * 2.0).extend(0.0));
+        let polygon = Measurement::from_points(MeasurementTool::Polygon, &square).unwrap();
+        assert_eq!(polygon.outline().len(), 5);
+        assert_eq!(polygon_area(&square.map(|p| p.truncate())), 4.0);
+
+        assert!(Measurement::from_points(MeasurementTool::Polygon, &square[..2]).is_none());
+    }
+}
@@ -1,4 +1,5 @@
 mod eye;
+mod measurement;
 mod scene;
 mod space_camera_3d;
 
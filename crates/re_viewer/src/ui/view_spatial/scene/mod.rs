@@ -1,12 +1,16 @@
 use std::sync::Arc;
 
 use ahash::HashMap;
+use nohash_hasher::IntMap;
 use re_data_store::{EntityPath, InstancePathHash};
 use re_log_types::{
     component_types::{ClassId, KeypointId, Tensor},
-    MeshId,
+    EntityPathHash, MeshId,
+};
+use re_renderer::{
+    Color32, OutlineConfig, OutlineMaskPreference, PickingLayerId, PickingLayerInstanceId,
+    PickingLayerObjectId, Size,
 };
-use re_renderer::{Color32, Size};
 
 use super::{eye::Eye, SpaceCamera3D, SpatialNavigationMode};
 use crate::{
@@ -16,7 +20,7 @@ use crate::{
     },
     ui::{
         annotations::{auto_color, AnnotationMap},
-        Annotations, SceneQuery,
+        Annotations, Colorbar, SceneQuery,
     },
 };
 
@@ -25,7 +29,9 @@ mod primitives;
 mod scene_part;
 
 pub use self::picking::{AdditionalPickingInfo, PickingRayHit, PickingResult};
-pub use self::primitives::SceneSpatialPrimitives;
+pub use self::primitives::{
+    SceneSpatialPrimitives, StaticLineStripsSource, StaticPointCloudSource, VoxelGridSource,
+};
 use scene_part::ScenePart;
 
 // ----------------------------------------------------------------------------
@@ -40,15 +46,6 @@ pub enum MeshSourceData {
     StaticGlb(MeshId, &'static [u8]),
 }
 
-impl MeshSourceData {
-    pub fn mesh_id(&self) -> MeshId {
-        match self {
-            MeshSourceData::Mesh3D(mesh) => mesh.mesh_id(),
-            MeshSourceData::StaticGlb(id, _) => *id,
-        }
-    }
-}
-
 /// TODO(andreas): Scene should only care about converted rendering primitive.
 pub struct MeshSource {
     pub instance_path_hash: InstancePathHash,
@@ -56,6 +53,12 @@ pub struct MeshSource {
     pub world_from_mesh: macaw::Affine3A,
     pub mesh: Arc<LoadedMesh>,
     pub additive_tint: Color32,
+
+    /// See [`re_data_store::EntityProperties::opacity`].
+    pub opacity: f32,
+
+    /// Whether this mesh is outlined because it is selected or hovered.
+    pub outline_mask_ids: OutlineMaskPreference,
 }
 
 pub struct Image {
@@ -107,6 +110,9 @@ pub struct SceneSpatialUiData {
 
     /// Images are a special case of rects where we're storing some extra information to allow miniature previews etc.
     pub images: Vec<Image>,
+
+    /// Legends of color mapped entities, shown on top of the view.
+    pub colorbars: Vec<Colorbar>,
 }
 
 pub struct SceneSpatial {
@@ -123,6 +129,9 @@ pub struct SceneSpatial {
     /// All space cameras in this scene.
     /// TODO(andreas): Does this belong to [`SceneSpatialUiData`]?
     pub space_cameras: Vec<SpaceCamera3D>,
+
+    /// Entities of all picking object ids in this scene, see [`picking_object_id`].
+    picking_object_ids: IntMap<u64, EntityPathHash>,
 }
 
 fn instance_path_hash_if_interactive(
@@ -136,6 +145,35 @@ fn instance_path_hash_if_interactive(
     }
 }
 
+/// Picking object id of an entity, `0` (i.e. not pickable) if the entity isn't interactive.
+///
+/// The entity can be looked up again from the picking object ids collected in [`SceneSpatial::load`].
+fn picking_object_id(entity_path: &EntityPath, interactive: bool) -> PickingLayerObjectId {
+    if interactive {
+        PickingLayerObjectId(entity_path.hash64())
+    } else {
+        PickingLayerObjectId(0)
+    }
+}
+
+/// Picking instance id for an instance path hash as used for CPU picking,
+/// see [`scene_part::instance_path_hash_for_picking`].
+fn picking_instance_id(instance_path_hash: InstancePathHash) -> PickingLayerInstanceId {
+    PickingLayerInstanceId(instance_path_hash.instance_key.0)
+}
+
+/// Full picking layer id for an instance path hash, not pickable if the hash is [`InstancePathHash::NONE`].
+pub(super) fn picking_layer_id(instance_path_hash: InstancePathHash) -> PickingLayerId {
+    if instance_path_hash.is_some() {
+        PickingLayerId {
+            object: PickingLayerObjectId(instance_path_hash.entity_path_hash.hash64()),
+            instance: picking_instance_id(instance_path_hash),
+        }
+    } else {
+        PickingLayerId::default()
+    }
+}
+
 pub type Keypoints = HashMap<(ClassId, i64), HashMap<KeypointId, glam::Vec3>>;
 
 impl SceneSpatial {
@@ -147,6 +185,7 @@ impl SceneSpatial {
             num_logged_2d_objects: Default::default(),
             num_logged_3d_objects: Default::default(),
             space_cameras: Default::default(),
+            picking_object_ids: Default::default(),
         }
     }
 
@@ -161,15 +200,24 @@ impl SceneSpatial {
         crate::profile_function!();
 
         self.annotation_map.load(ctx, query);
+        self.primitives.any_outlines = highlights.any_highlights();
+        self.picking_object_ids = query
+            .iter_entities()
+            .filter(|(_, props)| props.interactive)
+            .map(|(entity_path, _)| (entity_path.hash64(), entity_path.hash()))
+            .collect();
 
         let parts: Vec<&dyn ScenePart> = vec![
-            &scene_part::Points3DPart { max_labels: 10 },
+            // Labels of 3D points are culled by `re_renderer` if they would overlap.
+            &scene_part::Points3DPart { max_labels: 10_000 },
             // --
             &scene_part::Boxes3DPart,
             &scene_part::Lines3DPart,
             &scene_part::Arrows3DPart,
             &scene_part::MeshPart,
             &scene_part::ImagesPart,
+            &scene_part::VolumesPart,
+            &scene_part::VoxelGridsPart,
             // --
             &scene_part::Boxes2DPart,
             // --
@@ -193,6 +241,18 @@ impl SceneSpatial {
     const SIBLING_SELECTION_COLOR: Color32 = Color32::from_rgb(255, 140, 140);
     const CAMERA_COLOR: Color32 = Color32::from_rgb(150, 150, 150);
 
+    /// Outline radius in ui points.
+    const OUTLINE_RADIUS_POINTS: f32 = 1.5;
+
+    /// Outlines for selected (layer a) & hovered (layer b) objects, if there are any.
+    pub fn outline_config(&self, pixels_from_point: f32) -> Option<OutlineConfig> {
+        self.primitives.any_outlines.then(|| OutlineConfig {
+            outline_radius_pixel: (Self::OUTLINE_RADIUS_POINTS * pixels_from_point).max(1.0),
+            color_layer_a: Self::SELECTION_COLOR.into(),
+            color_layer_b: Self::HOVER_COLOR.into(),
+        })
+    }
+
     fn size_boost(size: Size) -> Size {
         if size.is_auto() {
             Size::AUTO_LARGE
@@ -206,8 +266,8 @@ impl SceneSpatial {
         color: &mut Color32,
         highlight: InteractionHighlight,
     ) {
-        // TODO(#889):
-        // We want to use outlines instead of color highlighting, but this is a bigger endeavour, so for now:
+        // Selected & hovered objects are outlined as well (see `InteractionHighlight::outline_mask_ids`),
+        // the color & size change is kept as an additional cue for primitives that are very small on screen.
 
         let mut highlight_color = *color;
         if highlight.selection.is_some() {
@@ -263,7 +323,11 @@ impl SceneSpatial {
         // Generate keypoint connections if any.
         let instance_path_hash = instance_path_hash_if_interactive(entity_path, interactive);
 
-        let mut line_batch = self.primitives.line_strips.batch("keypoint connections");
+        let mut line_batch = self
+            .primitives
+            .line_strips
+            .batch("keypoint connections")
+            .picking_object_id(picking_object_id(entity_path, interactive));
 
         for ((class_id, _time), keypoints_in_class) in keypoints {
             let Some(class_description) = annotations.context.class_map.get(&class_id) else {
@@ -287,6 +351,7 @@ impl SceneSpatial {
                     .add_segment(*a, *b)
                     .radius(Size::AUTO)
                     .color(color)
+                    .picking_instance_id(picking_instance_id(instance_path_hash))
                     .user_data(instance_path_hash);
             }
         }
@@ -313,12 +378,17 @@ impl SceneSpatial {
         SpatialNavigationMode::ThreeD
     }
 
+    /// Picks everything under the pointer.
+    ///
+    /// Points, lines, meshes and depth clouds are picked using the given `gpu_picking_result`,
+    /// which lags behind by a few frames. Rects are picked on the CPU.
     pub fn picking(
         &self,
         pointer_in_ui: glam::Vec2,
         ui_rect: &egui::Rect,
         eye: &Eye,
         ui_interaction_radius: f32,
+        gpu_picking_result: Option<&re_renderer::PickingResult<()>>,
     ) -> PickingResult {
         picking::picking(
             pointer_in_ui,
@@ -327,6 +397,8 @@ impl SceneSpatial {
             &self.primitives,
             &self.ui,
             ui_interaction_radius,
+            gpu_picking_result,
+            &self.picking_object_ids,
         )
     }
 }
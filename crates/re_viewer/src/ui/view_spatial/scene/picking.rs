@@ -1,12 +1,10 @@
-use itertools::Itertools as _;
+use nohash_hasher::IntMap;
 
 use re_data_store::InstancePathHash;
+use re_log_types::{component_types::InstanceKey, EntityPathHash};
 
 use super::{SceneSpatialPrimitives, SceneSpatialUiData};
-use crate::{
-    math::{line_segment_distance_sq_to_point_2d, ray_closest_t_line_segment},
-    ui::view_spatial::eye::Eye,
-};
+use crate::ui::view_spatial::eye::Eye;
 
 pub enum AdditionalPickingInfo {
     /// No additional picking information.
@@ -75,7 +73,6 @@ const RAY_T_EPSILON: f32 = f32::EPSILON;
 struct PickingContext {
     pointer_in_ui: glam::Vec2,
     ray_in_world: macaw::Ray3,
-    ui_from_world: glam::Mat4,
     max_side_ui_dist_sq: f32,
 }
 
@@ -136,6 +133,8 @@ pub fn picking(
     primitives: &SceneSpatialPrimitives,
     ui_data: &SceneSpatialUiData,
     ui_interaction_radius: f32,
+    gpu_picking_result: Option<&re_renderer::PickingResult<()>>,
+    picking_object_ids: &IntMap<u64, EntityPathHash>,
 ) -> PickingResult {
     crate::profile_function!();
 
@@ -143,7 +142,6 @@ pub fn picking(
 
     let context = PickingContext {
         pointer_in_ui,
-        ui_from_world: eye.ui_from_world(ui_rect),
         ray_in_world: eye.picking_ray(ui_rect, pointer_in_ui),
         max_side_ui_dist_sq,
     };
@@ -163,15 +161,22 @@ pub fn picking(
         bounding_box: _,
         textured_rectangles,
         textured_rectangles_ids,
-        line_strips,
-        points,
-        meshes,
-        depth_clouds: _, // no picking for depth clouds yet
+        // Picked on the gpu.
+        line_strips: _,
+        static_line_strips: _,
+        points: _,
+        static_point_clouds: _,
+        meshes: _,
+        depth_clouds: _,
+        volumes: _,
+        voxel_grids: _,
+        labels: _,
+        any_outlines: _,
     } = primitives;
 
-    picking_points(&context, &mut state, points);
-    picking_lines(&context, &mut state, line_strips);
-    picking_meshes(&context, &mut state, meshes);
+    if let Some(gpu_picking_result) = gpu_picking_result {
+        picking_gpu(&context, &mut state, gpu_picking_result, picking_object_ids);
+    }
     picking_textured_rects(
         &context,
         &mut state,
@@ -193,109 +198,57 @@ pub fn picking(
     }
 }
 
-fn picking_points(
+fn picking_gpu(
     context: &PickingContext,
     state: &mut PickingState,
-    points: &re_renderer::PointCloudBuilder<InstancePathHash>,
+    gpu_picking_result: &re_renderer::PickingResult<()>,
+    picking_object_ids: &IntMap<u64, EntityPathHash>,
 ) {
     crate::profile_function!();
 
-    for (batch, vertex_iter) in points.iter_vertices_and_userdata_by_batch() {
-        // For getting the closest point we could transform the mouse ray into the "batch space".
-        // However, we want to determine the closest point in *screen space*, meaning that we need to project all points.
-        let ui_from_batch = context.ui_from_world * batch.world_from_obj;
-
-        for (point, instance_hash) in vertex_iter {
-            if instance_hash.is_none() {
+    // The picking rect is centered at the pointer position at the time it was scheduled,
+    // and its radius corresponds to the interaction radius.
+    let extent = gpu_picking_result.rect.extent;
+    let pointer_on_picking_rect = (extent / 2).as_vec2();
+    let ui_from_pixel = context.max_side_ui_dist_sq.sqrt() / (extent.x / 2).max(1) as f32;
+
+    // Only look at the closest pixel that has anything on it.
+    let mut closest = None;
+    for y in 0..extent.y {
+        for x in 0..extent.x {
+            let pos_on_picking_rect = glam::uvec2(x, y);
+            if gpu_picking_result.picked_id(pos_on_picking_rect).is_none() {
                 continue;
             }
-
-            // TODO(emilk): take point radius into account
-            let pos_in_ui = ui_from_batch.project_point3(point.position);
-            let dist_sq = pos_in_ui.truncate().distance_squared(context.pointer_in_ui);
-            if dist_sq <= state.closest_opaque_side_ui_dist_sq {
-                let t = context
-                    .ray_in_world
-                    .closest_t_to_point(batch.world_from_obj.transform_point3(point.position));
-                state.check_hit(
-                    dist_sq,
-                    PickingRayHit::from_instance_and_t(*instance_hash, t),
-                    false,
-                );
+            let pixel_dist_sq = pos_on_picking_rect
+                .as_vec2()
+                .distance_squared(pointer_on_picking_rect);
+            if closest.map_or(true, |(closest_dist_sq, _)| pixel_dist_sq < closest_dist_sq) {
+                closest = Some((pixel_dist_sq, pos_on_picking_rect));
             }
         }
     }
-}
-
-fn picking_lines(
-    context: &PickingContext,
-    state: &mut PickingState,
-    line_strips: &re_renderer::LineStripSeriesBuilder<InstancePathHash>,
-) {
-    crate::profile_function!();
-
-    for (batch, vertices) in line_strips.iter_vertices_by_batch() {
-        // For getting the closest point we could transform the mouse ray into the "batch space".
-        // However, we want to determine the closest point in *screen space*, meaning that we need to project all points.
-        let ui_from_batch = context.ui_from_world * batch.world_from_obj;
-
-        for (start, end) in vertices.tuple_windows() {
-            // Skip unconnected tuples.
-            if start.strip_index != end.strip_index {
-                continue;
-            }
-
-            let instance_hash = line_strips.strip_user_data[start.strip_index as usize];
-            if instance_hash.is_none() {
-                continue;
-            }
-
-            // TODO(emilk): take line segment radius into account
-            let a = ui_from_batch.project_point3(start.position);
-            let b = ui_from_batch.project_point3(end.position);
-            let side_ui_dist_sq = line_segment_distance_sq_to_point_2d(
-                [a.truncate(), b.truncate()],
-                context.pointer_in_ui,
-            );
-
-            if side_ui_dist_sq < context.max_side_ui_dist_sq {
-                let start_world = batch.world_from_obj.transform_point3(start.position);
-                let end_world = batch.world_from_obj.transform_point3(end.position);
-                let t = ray_closest_t_line_segment(&context.ray_in_world, [start_world, end_world]);
-
-                state.check_hit(
-                    side_ui_dist_sq,
-                    PickingRayHit::from_instance_and_t(instance_hash, t),
-                    false,
-                );
-            }
-        }
-    }
-}
+    let Some((pixel_dist_sq, pos_on_picking_rect)) = closest else {
+        return;
+    };
 
-fn picking_meshes(
-    context: &PickingContext,
-    state: &mut PickingState,
-    meshes: &[super::MeshSource],
-) {
-    crate::profile_function!();
+    let picked_id = gpu_picking_result.picked_id(pos_on_picking_rect);
+    let Some(entity_path_hash) = picking_object_ids.get(&picked_id.object.0) else {
+        return; // Entity went away or isn't interactive anymore since the picking rect was scheduled.
+    };
+    let instance_path_hash = InstancePathHash {
+        entity_path_hash: *entity_path_hash,
+        instance_key: InstanceKey(picked_id.instance.0),
+    };
 
-    for mesh in meshes {
-        if !mesh.instance_path_hash.is_some() {
-            continue;
-        }
-        let ray_in_mesh = (mesh.world_from_mesh.inverse() * context.ray_in_world).normalize();
-        let t = crate::math::ray_bbox_intersect(&ray_in_mesh, mesh.mesh.bbox());
-
-        if t < 0.0 {
-            let side_ui_dist_sq = 0.0;
-            state.check_hit(
-                side_ui_dist_sq,
-                PickingRayHit::from_instance_and_t(mesh.instance_path_hash, t),
-                false,
-            );
-        }
-    }
+    let t = context
+        .ray_in_world
+        .closest_t_to_point(gpu_picking_result.picked_world_position(pos_on_picking_rect));
+    state.check_hit(
+        pixel_dist_sq * ui_from_pixel * ui_from_pixel,
+        PickingRayHit::from_instance_and_t(instance_path_hash, t),
+        false,
+    );
 }
 
 fn picking_textured_rects(
@@ -1,15 +1,21 @@
 use egui::Color32;
 use re_data_store::InstancePathHash;
 use re_renderer::{
-    renderer::{DepthCloud, MeshInstance},
+    renderer::{
+        DepthCloud, LabelInstance, LineDrawData, MeshInstance, StaticPointCloudInstance,
+        VolumeInstance, VoxelGridInstance,
+    },
     LineStripSeriesBuilder, PointCloudBuilder,
 };
 
-use super::MeshSource;
+use super::{picking_instance_id, picking_layer_id, MeshSource};
 
 /// Primitives sent off to `re_renderer`.
 /// (Some meta information still relevant to ui setup as well)
 ///
+/// Static line strips and point clouds, meshes, volumes and voxel grids are owned by the
+/// [`crate::misc::caches::GpuResourceCache`] and stay on the gpu across frames.
+///
 /// TODO(andreas): Right now we're using `re_renderer` data structures for reading (bounding box & picking).
 ///                 In the future, this will be more limited as we're going to gpu staging data as soon as possible
 ///                 which is very slow to read. See [#594](https://github.com/rerun-io/rerun/pull/594)
@@ -23,10 +29,53 @@ pub struct SceneSpatialPrimitives {
     pub textured_rectangles: Vec<re_renderer::renderer::TexturedRect>,
 
     pub line_strips: LineStripSeriesBuilder<InstancePathHash>,
+
+    /// Large line strips of entities that aren't highlighted.
+    pub static_line_strips: Vec<StaticLineStripsSource>,
+
     pub points: PointCloudBuilder<InstancePathHash>,
 
+    /// Large point clouds, drawn with level of detail.
+    pub static_point_clouds: Vec<StaticPointCloudSource>,
+
     pub meshes: Vec<MeshSource>,
     pub depth_clouds: Vec<DepthCloud>,
+    pub volumes: Vec<VolumeInstance>,
+    pub voxel_grids: Vec<VoxelGridSource>,
+
+    /// Labels of positions in 3D, drawn with depth aware occlusion & collision avoidance.
+    pub labels: Vec<LabelInstance>,
+
+    /// Whether any of the primitives is drawn with an outline, i.e. anything is selected or hovered.
+    pub any_outlines: bool,
+}
+
+/// A point cloud kept on the gpu, placed in the scene.
+pub struct StaticPointCloudSource {
+    pub instance: StaticPointCloudInstance,
+
+    /// Bounding box of all points, in object space.
+    pub bounding_box: macaw::BoundingBox,
+    pub num_points: usize,
+}
+
+/// Line strips kept on the gpu, placed in the scene by the transform baked into their draw data.
+pub struct StaticLineStripsSource {
+    pub draw_data: LineDrawData,
+    pub world_from_obj: glam::Mat4,
+
+    /// Bounding box of all vertices, in object space.
+    pub bounding_box: macaw::BoundingBox,
+    pub num_vertices: usize,
+}
+
+/// A voxel grid kept on the gpu, placed in the scene.
+pub struct VoxelGridSource {
+    pub instance: VoxelGridInstance,
+
+    /// Bounding box of all occupied cells, in grid space.
+    pub bounding_box: macaw::BoundingBox,
+    pub num_cells: usize,
 }
 
 const AXIS_COLOR_X: Color32 = Color32::from_rgb(255, 25, 25);
@@ -40,9 +89,15 @@ impl SceneSpatialPrimitives {
             textured_rectangles_ids: Default::default(),
             textured_rectangles: Default::default(),
             line_strips: Default::default(),
+            static_line_strips: Default::default(),
             points: PointCloudBuilder::new(re_ctx),
+            static_point_clouds: Default::default(),
             meshes: Default::default(),
             depth_clouds: Default::default(),
+            volumes: Default::default(),
+            voxel_grids: Default::default(),
+            labels: Default::default(),
+            any_outlines: false,
         }
     }
 
@@ -58,16 +113,35 @@ impl SceneSpatialPrimitives {
             textured_rectangles,
             textured_rectangles_ids: _,
             line_strips,
+            static_line_strips,
             points,
+            static_point_clouds,
             meshes,
             depth_clouds,
+            volumes,
+            voxel_grids,
+            labels: _,
+            any_outlines: _,
         } = &self;
 
         textured_rectangles.len()
             + line_strips.vertices.len()
+            + static_line_strips
+                .iter()
+                .map(|line_strips| line_strips.num_vertices)
+                .sum::<usize>()
             + points.vertices.len()
+            + static_point_clouds
+                .iter()
+                .map(|point_cloud| point_cloud.num_points)
+                .sum::<usize>()
             + meshes.len()
             + depth_clouds.len()
+            + volumes.len()
+            + voxel_grids
+                .iter()
+                .map(|voxel_grid| voxel_grid.num_cells)
+                .sum::<usize>()
     }
 
     pub fn recalculate_bounding_box(&mut self) {
@@ -78,9 +152,15 @@ impl SceneSpatialPrimitives {
             textured_rectangles_ids: _,
             textured_rectangles,
             line_strips,
+            static_line_strips,
             points,
+            static_point_clouds,
             meshes,
             depth_clouds: _, // no bbox for depth clouds
+            volumes,
+            voxel_grids,
+            labels: _, // labels are attached to other primitives
+            any_outlines: _,
         } = self;
 
         *bounding_box = macaw::BoundingBox::nothing();
@@ -104,6 +184,17 @@ impl SceneSpatialPrimitives {
                 *bounding_box = bounding_box.union(batch_bb.transform_affine3(&transform.into()));
             }
         }
+        for point_cloud in static_point_clouds.iter() {
+            if let Some(transform) =
+                macaw::IsoTransform::from_mat4(&point_cloud.instance.world_from_obj)
+            {
+                *bounding_box = bounding_box.union(
+                    point_cloud
+                        .bounding_box
+                        .transform_affine3(&transform.into()),
+                );
+            }
+        }
         for (batch, vertex_iter) in line_strips.iter_vertices_by_batch() {
             // Only use points which are an IsoTransform to update the bounding box
             // This prevents crazy bounds-increases when projecting 3d to 2d
@@ -113,12 +204,48 @@ impl SceneSpatialPrimitives {
                 *bounding_box = bounding_box.union(batch_bb.transform_affine3(&transform.into()));
             }
         }
+        for line_strips in static_line_strips.iter() {
+            if let Some(transform) = macaw::IsoTransform::from_mat4(&line_strips.world_from_obj) {
+                *bounding_box = bounding_box.union(
+                    line_strips
+                        .bounding_box
+                        .transform_affine3(&transform.into()),
+                );
+            }
+        }
 
         for mesh in meshes {
             // TODO(jleibs): is this safe for meshes or should we be doing the equivalent of the above?
             *bounding_box =
                 bounding_box.union(mesh.mesh.bbox().transform_affine3(&mesh.world_from_mesh));
         }
+
+        for volume in volumes {
+            let unit_cube = macaw::BoundingBox::from_min_max(glam::Vec3::ZERO, glam::Vec3::ONE);
+            *bounding_box = bounding_box.union(
+                unit_cube.transform_affine3(&glam::Affine3A::from_mat4(volume.world_from_volume)),
+            );
+        }
+
+        for voxel_grid in voxel_grids {
+            *bounding_box = bounding_box.union(voxel_grid.bounding_box.transform_affine3(
+                &glam::Affine3A::from_mat4(voxel_grid.instance.world_from_grid),
+            ));
+        }
+    }
+
+    pub fn static_point_cloud_instances(&self) -> Vec<StaticPointCloudInstance> {
+        self.static_point_clouds
+            .iter()
+            .map(|point_cloud| point_cloud.instance.clone())
+            .collect()
+    }
+
+    pub fn voxel_grid_instances(&self) -> Vec<VoxelGridInstance> {
+        self.voxel_grids
+            .iter()
+            .map(|voxel_grid| voxel_grid.instance.clone())
+            .collect()
     }
 
     pub fn mesh_instances(&self) -> Vec<MeshInstance> {
@@ -139,6 +266,9 @@ impl SceneSpatialPrimitives {
                         mesh: None, // Don't care.
                         world_from_mesh: base_transform * mesh_instance.world_from_mesh,
                         additive_tint: mesh.additive_tint,
+                        opacity: mesh.opacity,
+                        outline_mask_ids: mesh.outline_mask_ids,
+                        picking_layer_id: picking_layer_id(mesh.instance_path_hash),
                     })
             })
             .collect()
@@ -156,7 +286,12 @@ impl SceneSpatialPrimitives {
         let line_radius = re_renderer::Size::new_scene(axis_length * 0.05);
         let origin = transform.translation();
 
-        let mut line_batch = self.line_strips.batch("origin axis");
+        let picking_instance_id = picking_instance_id(instance_path_hash);
+
+        let mut line_batch = self
+            .line_strips
+            .batch("origin axis")
+            .picking_object_id(picking_layer_id(instance_path_hash).object);
         line_batch
             .add_segment(
                 origin,
@@ -165,6 +300,7 @@ impl SceneSpatialPrimitives {
             .radius(line_radius)
             .color(AXIS_COLOR_X)
             .flags(LineStripFlags::CAP_END_TRIANGLE | LineStripFlags::CAP_START_ROUND)
+            .picking_instance_id(picking_instance_id)
             .user_data(instance_path_hash);
         line_batch
             .add_segment(
@@ -174,6 +310,7 @@ impl SceneSpatialPrimitives {
             .radius(line_radius)
             .color(AXIS_COLOR_Y)
             .flags(LineStripFlags::CAP_END_TRIANGLE | LineStripFlags::CAP_START_ROUND)
+            .picking_instance_id(picking_instance_id)
             .user_data(instance_path_hash);
         line_batch
             .add_segment(
@@ -183,6 +320,7 @@ impl SceneSpatialPrimitives {
             .radius(line_radius)
             .color(AXIS_COLOR_Z)
             .flags(LineStripFlags::CAP_END_TRIANGLE | LineStripFlags::CAP_START_ROUND)
+            .picking_instance_id(picking_instance_id)
             .user_data(instance_path_hash);
     }
 }
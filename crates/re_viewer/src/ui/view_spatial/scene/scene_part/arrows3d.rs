@@ -13,7 +13,7 @@ use crate::{
     ui::{scene::SceneQuery, view_spatial::SceneSpatial, DefaultColor},
 };
 
-use super::{instance_path_hash_for_picking, ScenePart};
+use super::{instance_path_hash_for_picking, picking_instance_id, picking_object_id, ScenePart};
 
 pub struct Arrows3DPart;
 
@@ -39,7 +39,8 @@ impl Arrows3DPart {
             .primitives
             .line_strips
             .batch("arrows")
-            .world_from_obj(world_from_obj);
+            .world_from_obj(world_from_obj)
+            .picking_object_id(picking_object_id(ent_path, props.interactive));
 
         let visitor = |instance_key: InstanceKey,
                        arrow: Arrow3D,
@@ -71,17 +72,16 @@ impl Arrows3DPart {
             let vector_len = vector.length();
             let end = origin + vector * ((vector_len - tip_length) / vector_len);
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
+            let highlight = entity_highlight.index_highlight(instance_hash.instance_key);
+            SceneSpatial::apply_hover_and_selection_effect(&mut radius, &mut color, highlight);
 
             line_batch
                 .add_segment(origin, end)
                 .radius(radius)
                 .color(color)
                 .flags(re_renderer::renderer::LineStripFlags::CAP_END_TRIANGLE)
+                .outline_mask_ids(highlight.outline_mask_ids())
+                .picking_instance_id(picking_instance_id(instance_hash))
                 .user_data(instance_hash);
         };
 
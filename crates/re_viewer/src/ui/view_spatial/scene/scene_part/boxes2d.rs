@@ -18,7 +18,7 @@ use crate::{
     },
 };
 
-use super::ScenePart;
+use super::{picking_instance_id, picking_layer_id, ScenePart};
 
 pub struct Boxes2DPart;
 
@@ -48,17 +48,15 @@ impl Boxes2DPart {
         let mut radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
         let label = annotation_info.label(label.map(|l| l.0).as_ref());
 
-        SceneSpatial::apply_hover_and_selection_effect(
-            &mut radius,
-            &mut color,
-            entity_highlight.index_highlight(instance_path_hash.instance_key),
-        );
+        let highlight = entity_highlight.index_highlight(instance_path_hash.instance_key);
+        SceneSpatial::apply_hover_and_selection_effect(&mut radius, &mut color, highlight);
 
         let mut line_batch = scene
             .primitives
             .line_strips
             .batch("2d box")
-            .world_from_obj(world_from_obj);
+            .world_from_obj(world_from_obj)
+            .picking_object_id(picking_layer_id(instance_path_hash).object);
 
         line_batch
             .add_rectangle_outline_2d(
@@ -68,6 +66,8 @@ impl Boxes2DPart {
             )
             .color(color)
             .radius(radius)
+            .outline_mask_ids(highlight.outline_mask_ids())
+            .picking_instance_id(picking_instance_id(instance_path_hash))
             .user_data(instance_path_hash);
 
         if let Some(label) = label {
@@ -17,7 +17,7 @@ use crate::{
     },
 };
 
-use super::{instance_path_hash_for_picking, ScenePart};
+use super::{instance_path_hash_for_picking, picking_instance_id, picking_object_id, ScenePart};
 
 pub struct Boxes3DPart;
 
@@ -39,7 +39,8 @@ impl Boxes3DPart {
             .primitives
             .line_strips
             .batch("box 3d")
-            .world_from_obj(world_from_obj);
+            .world_from_obj(world_from_obj)
+            .picking_object_id(picking_object_id(ent_path, props.interactive));
 
         let visitor = |instance_key: InstanceKey,
                        half_size: Box3D,
@@ -64,11 +65,8 @@ impl Boxes3DPart {
             let mut color =
                 annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
+            let highlight = entity_highlight.index_highlight(instance_hash.instance_key);
+            SceneSpatial::apply_hover_and_selection_effect(&mut radius, &mut color, highlight);
 
             let scale = glam::Vec3::from(half_size);
             let rot = rotation.map(glam::Quat::from).unwrap_or_default();
@@ -79,6 +77,8 @@ impl Boxes3DPart {
                 .add_box_outline(transform)
                 .radius(radius)
                 .color(color)
+                .outline_mask_ids(highlight.outline_mask_ids())
+                .picking_instance_id(picking_instance_id(instance_hash))
                 .user_data(instance_hash);
 
             if let Some(label) = annotation_info.label(label.as_ref().map(|s| &s.0)) {
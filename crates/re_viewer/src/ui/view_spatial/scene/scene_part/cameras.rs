@@ -19,7 +19,7 @@ use crate::{
     },
 };
 
-use super::ScenePart;
+use super::{picking_instance_id, picking_layer_id, ScenePart};
 
 /// Determine the view coordinates (i.e.) the axis semantics.
 ///
@@ -143,17 +143,15 @@ impl CamerasPart {
 
         let mut radius = re_renderer::Size::new_points(1.0);
         let mut color = SceneSpatial::CAMERA_COLOR;
-        SceneSpatial::apply_hover_and_selection_effect(
-            &mut radius,
-            &mut color,
-            entity_highlight.index_highlight(instance_path_hash.instance_key),
-        );
+        let highlight = entity_highlight.index_highlight(instance_path_hash.instance_key);
+        SceneSpatial::apply_hover_and_selection_effect(&mut radius, &mut color, highlight);
 
         scene
             .primitives
             .line_strips
             .batch("camera frustum")
             .world_from_obj(world_from_parent)
+            .picking_object_id(picking_layer_id(instance_path_hash).object)
             .add_segments(segments.into_iter())
             .radius(radius)
             .color(color)
@@ -162,6 +160,8 @@ impl CamerasPart {
                     | LineStripFlags::CAP_END_ROUND
                     | LineStripFlags::CAP_START_ROUND,
             )
+            .outline_mask_ids(highlight.outline_mask_ids())
+            .picking_instance_id(picking_instance_id(instance_path_hash))
             .user_data(instance_path_hash);
     }
 }
@@ -13,19 +13,22 @@ use re_log_types::{
 use re_query::{query_primary_with_history, EntityView, QueryError};
 use re_renderer::{
     renderer::{DepthCloud, DepthCloudDepthData},
-    ColorMap, Size,
+    ColorMap, OutlineMaskPreference,
 };
 
 use crate::{
-    misc::{caches::AsDynamicImage, SpaceViewHighlights, TransformCache, ViewerContext},
+    misc::{
+        caches::AsDynamicImage, InteractionHighlight, SpaceViewHighlights, TransformCache,
+        ViewerContext,
+    },
     ui::{
         scene::SceneQuery,
         view_spatial::{scene::scene_part::instance_path_hash_for_picking, Image, SceneSpatial},
-        Annotations, DefaultColor,
+        Annotations, Colorbar, DefaultColor, ResolvedColorMapper,
     },
 };
 
-use super::ScenePart;
+use super::{picking_layer_id, ScenePart};
 
 fn push_tensor_texture<T: AsDynamicImage>(
     scene: &mut SceneSpatial,
@@ -35,13 +38,15 @@ fn push_tensor_texture<T: AsDynamicImage>(
     instance_path_hash: InstancePathHash,
     tensor: &T,
     tint: egui::Rgba,
+    outline_mask: OutlineMaskPreference,
 ) {
     crate::profile_function!();
 
-    let tensor_view =
-        ctx.cache
-            .image
-            .get_view_with_annotations(tensor, annotations, ctx.render_ctx);
+    let tensor_view = ctx.cache.gpu_resources.tensor_image_view_with_annotations(
+        tensor,
+        annotations,
+        ctx.render_ctx,
+    );
 
     if let Some(texture_handle) = tensor_view.texture_handle {
         let (h, w) = (tensor.shape()[0].size as f32, tensor.shape()[1].size as f32);
@@ -58,6 +63,7 @@ fn push_tensor_texture<T: AsDynamicImage>(
                 multiplicative_tint: tint,
                 // Push to background. Mostly important for mouse picking order!
                 depth_offset: -1,
+                outline_mask,
             });
         scene
             .primitives
@@ -157,13 +163,24 @@ impl ImagesPart {
                         // NOTE: we don't pass in `world_from_obj` because this corresponds to the
                         // transform of the projection plane, which is of no use to us here.
                         // What we want are the extrinsics of the depth camera!
+                        let entity_highlight = highlights.entity_highlight(ent_path.hash());
+                        let instance_path_hash = instance_path_hash_for_picking(
+                            ent_path,
+                            instance_key,
+                            entity_view,
+                            properties,
+                            entity_highlight,
+                        );
                         Self::process_entity_view_as_depth_cloud(
                             scene,
                             ctx,
                             transforms,
                             properties,
+                            ent_path,
                             &tensor,
                             pinhole_ent_path,
+                            instance_path_hash,
+                            entity_highlight.index_highlight(instance_key),
                         );
                         return Ok(());
                     };
@@ -220,21 +237,6 @@ impl ImagesPart {
         );
 
         let highlight = entity_highlight.index_highlight(instance_path_hash.instance_key);
-        if highlight.is_some() {
-            let color = SceneSpatial::apply_hover_and_selection_effect_color(
-                re_renderer::Color32::TRANSPARENT,
-                highlight,
-            );
-            let rect = glam::vec2(tensor.shape()[1].size as f32, tensor.shape()[0].size as f32);
-            scene
-                .primitives
-                .line_strips
-                .batch("image outlines")
-                .world_from_obj(world_from_obj)
-                .add_axis_aligned_rectangle_outline_2d(glam::Vec2::ZERO, rect)
-                .color(color)
-                .radius(Size::new_points(1.0));
-        }
 
         push_tensor_texture(
             scene,
@@ -243,7 +245,8 @@ impl ImagesPart {
             world_from_obj,
             instance_path_hash,
             &tensor,
-            color.into(),
+            egui::Rgba::from(color).multiply(properties.opacity),
+            highlight.outline_mask_ids(),
         );
 
         // TODO(jleibs): Meter should really be its own component
@@ -263,8 +266,11 @@ impl ImagesPart {
         ctx: &mut ViewerContext<'_>,
         transforms: &TransformCache,
         properties: &EntityProperties,
+        ent_path: &EntityPath,
         tensor: &Tensor,
         pinhole_ent_path: &EntityPath,
+        instance_path_hash: InstancePathHash,
+        highlight: InteractionHighlight,
     ) {
         crate::profile_function!();
 
@@ -307,19 +313,31 @@ impl ImagesPart {
 
         let world_from_obj = extrinsics * glam::Mat4::from_scale(glam::Vec3::splat(scale));
 
-        let colormap = properties
-            .color_mapping
-            .then(|| match *properties.color_mapper.get() {
-                re_data_store::ColorMapper::ColorMap(colormap) => match colormap {
-                    re_data_store::ColorMap::Grayscale => ColorMap::Grayscale,
-                    re_data_store::ColorMap::Turbo => ColorMap::ColorMapTurbo,
-                    re_data_store::ColorMap::Viridis => ColorMap::ColorMapViridis,
-                    re_data_store::ColorMap::Plasma => ColorMap::ColorMapPlasma,
-                    re_data_store::ColorMap::Magma => ColorMap::ColorMapMagma,
-                    re_data_store::ColorMap::Inferno => ColorMap::ColorMapInferno,
-                },
-            })
-            .unwrap_or(ColorMap::Grayscale);
+        let color_mapper = if properties.color_mapping {
+            let color_mapper =
+                ResolvedColorMapper::resolve(ctx, ent_path, *properties.color_mapper.get());
+
+            // The shader maps normalized depth, i.e. `u16` values are divided by `u16::MAX`.
+            let max_depth = match &tensor.data {
+                TensorData::U16(_) => u16::MAX as f64,
+                _ => 1.0,
+            };
+            let (range, unit) = match tensor.meter {
+                Some(meter) => (0.0..=max_depth / meter as f64, Some("m")),
+                None => (0.0..=max_depth, None),
+            };
+            scene.ui.colorbars.push(Colorbar {
+                label: ent_path.to_string(),
+                color_mapper: color_mapper.clone(),
+                gamma: 1.0,
+                range,
+                unit,
+            });
+
+            color_mapper
+        } else {
+            ResolvedColorMapper::ColorMap(ColorMap::Grayscale)
+        };
 
         scene.primitives.depth_clouds.push(DepthCloud {
             depth_camera_extrinsics: world_from_obj,
@@ -327,7 +345,10 @@ impl ImagesPart {
             radius_scale,
             depth_dimensions: dimensions,
             depth_data: data,
-            colormap,
+            colormap: color_mapper.colormap(),
+            colormap_lut: color_mapper.lut(),
+            outline_mask_id: highlight.outline_mask_ids(),
+            picking_layer_id: picking_layer_id(instance_path_hash),
         });
     }
 }
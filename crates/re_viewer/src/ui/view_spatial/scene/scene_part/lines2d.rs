@@ -1,19 +1,22 @@
 use glam::Mat4;
 
-use re_data_store::{EntityPath, EntityProperties};
+use re_data_store::{EntityPath, EntityProperties, InstancePathHash};
 use re_log_types::{
     component_types::{ColorRGBA, InstanceKey, LineStrip2D, Radius},
     msg_bundle::Component,
 };
 use re_query::{query_primary_with_history, EntityView, QueryError};
-use re_renderer::{renderer::LineStripFlags, Size};
+use re_renderer::{renderer::LineStripFlags, LineBatchBuilder, Size};
 
 use crate::{
     misc::{OptionalSpaceViewEntityHighlight, SpaceViewHighlights, TransformCache, ViewerContext},
-    ui::{scene::SceneQuery, view_spatial::SceneSpatial, DefaultColor},
+    ui::{scene::SceneQuery, view_spatial::SceneSpatial, Annotations, DefaultColor},
 };
 
-use super::{instance_path_hash_for_picking, ScenePart};
+use super::{
+    add_cached_line_strips, instance_path_hash_for_picking, picking_instance_id, picking_object_id,
+    ScenePart,
+};
 
 pub struct Lines2DPart;
 
@@ -21,6 +24,7 @@ impl Lines2DPart {
     #[allow(clippy::too_many_arguments)]
     fn process_entity_view(
         scene: &mut SceneSpatial,
+        ctx: &mut ViewerContext<'_>,
         _query: &SceneQuery<'_>,
         props: &EntityProperties,
         entity_view: &EntityView<LineStrip2D>,
@@ -31,13 +35,57 @@ impl Lines2DPart {
         scene.num_logged_2d_objects += 1;
 
         let annotations = scene.annotation_map.find(ent_path);
-        let default_color = DefaultColor::EntityPath(ent_path);
 
-        let mut line_batch = scene
+        // Highlights change from frame to frame, only entities without any are kept on the gpu.
+        if !entity_highlight.is_some()
+            && add_cached_line_strips(
+                scene,
+                ctx,
+                props,
+                entity_view,
+                ent_path,
+                world_from_obj,
+                annotations.msg_id,
+                |line_batch| {
+                    Self::add_strips(
+                        line_batch,
+                        props,
+                        entity_view,
+                        ent_path,
+                        &annotations,
+                        entity_highlight,
+                    )
+                },
+            )
+        {
+            return Ok(());
+        }
+
+        let line_batch = scene
             .primitives
             .line_strips
             .batch("lines 2d")
-            .world_from_obj(world_from_obj);
+            .world_from_obj(world_from_obj)
+            .picking_object_id(picking_object_id(ent_path, props.interactive));
+        Self::add_strips(
+            line_batch,
+            props,
+            entity_view,
+            ent_path,
+            &annotations,
+            entity_highlight,
+        )
+    }
+
+    fn add_strips(
+        mut line_batch: LineBatchBuilder<'_, InstancePathHash>,
+        props: &EntityProperties,
+        entity_view: &EntityView<LineStrip2D>,
+        ent_path: &EntityPath,
+        annotations: &Annotations,
+        entity_highlight: OptionalSpaceViewEntityHighlight<'_>,
+    ) -> Result<(), QueryError> {
+        let default_color = DefaultColor::EntityPath(ent_path);
 
         let visitor = |instance_key: InstanceKey,
                        strip: LineStrip2D,
@@ -57,23 +105,20 @@ impl Lines2DPart {
             let mut color =
                 annotation_info.color(color.map(move |c| c.to_array()).as_ref(), default_color);
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
+            let highlight = entity_highlight.index_highlight(instance_hash.instance_key);
+            SceneSpatial::apply_hover_and_selection_effect(&mut radius, &mut color, highlight);
 
             line_batch
                 .add_strip_2d(strip.0.into_iter().map(|v| v.into()))
                 .color(color)
                 .radius(radius)
                 .flags(LineStripFlags::NO_COLOR_GRADIENT)
+                .outline_mask_ids(highlight.outline_mask_ids())
+                .picking_instance_id(picking_instance_id(instance_hash))
                 .user_data(instance_hash);
         };
 
-        entity_view.visit3(visitor)?;
-
-        Ok(())
+        entity_view.visit3(visitor)
     }
 }
 
@@ -111,6 +156,7 @@ impl ScenePart for Lines2DPart {
                 for entity in entities {
                     Self::process_entity_view(
                         scene,
+                        ctx,
                         query,
                         &props,
                         &entity,
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use egui::Color32;
 use glam::Mat4;
 
@@ -5,12 +7,14 @@ use re_data_store::{EntityPath, EntityProperties};
 use re_log_types::{
     component_types::{ColorRGBA, InstanceKey},
     msg_bundle::Component,
-    Mesh3D,
+    Mesh3D, MeshFormat,
 };
 use re_query::{query_primary_with_history, EntityView, QueryError};
+use re_renderer::RenderContext;
 
 use crate::{
-    misc::{SpaceViewHighlights, TransformCache, ViewerContext},
+    mesh_loader::LoadedMesh,
+    misc::{caches::GpuResource, SpaceViewHighlights, TransformCache, ViewerContext},
     ui::{
         scene::SceneQuery,
         view_spatial::{MeshSource, MeshSourceData, SceneSpatial},
@@ -18,7 +22,65 @@ use crate::{
     },
 };
 
-use super::{instance_path_hash_for_picking, ScenePart};
+use super::{get_or_load_instance, instance_path_hash_for_picking, ScenePart};
+
+/// A mesh uploaded to the gpu.
+#[derive(Clone)]
+struct CachedMesh {
+    mesh: Arc<LoadedMesh>,
+    memory_used: u64,
+}
+
+impl GpuResource for CachedMesh {
+    fn memory_used(&self) -> u64 {
+        self.memory_used
+    }
+}
+
+impl CachedMesh {
+    fn load(
+        name: &str,
+        mesh_data: &MeshSourceData,
+        render_ctx: &mut RenderContext,
+    ) -> Option<Self> {
+        crate::profile_function!();
+
+        re_log::debug!("Loading CPU mesh {name:?}…");
+
+        let (result, memory_used) = match mesh_data {
+            MeshSourceData::Mesh3D(mesh3d) => {
+                let memory_used = match mesh3d {
+                    Mesh3D::Encoded(mesh) => mesh.bytes.len(),
+                    Mesh3D::Raw(mesh) => {
+                        let num_indices = mesh.indices.as_ref().map_or(0, Vec::len);
+                        let num_normals = mesh.normals.as_ref().map_or(0, Vec::len);
+                        std::mem::size_of::<f32>() * (mesh.positions.len() + num_normals)
+                            + std::mem::size_of::<u32>() * num_indices
+                    }
+                };
+                (
+                    LoadedMesh::load(name.to_owned(), mesh3d, render_ctx),
+                    memory_used,
+                )
+            }
+            MeshSourceData::StaticGlb(_mesh_id, glb_bytes) => (
+                LoadedMesh::load_raw(name.to_owned(), MeshFormat::Glb, glb_bytes, render_ctx),
+                glb_bytes.len(),
+            ),
+        };
+
+        match result {
+            Ok(cpu_mesh) => Some(Self {
+                mesh: Arc::new(cpu_mesh),
+                memory_used: memory_used as u64,
+            }),
+            Err(err) => {
+                re_log::warn!("Failed to load mesh {name:?}: {}", re_error::format(&err));
+                None
+            }
+        }
+    }
+}
 
 pub struct MeshPart;
 
@@ -40,41 +102,46 @@ impl MeshPart {
         let world_from_obj_affine = glam::Affine3A::from_mat4(world_from_obj);
         let entity_highlight = highlights.entity_highlight(ent_path.hash());
 
-        let visitor =
-            |instance_key: InstanceKey, mesh: re_log_types::Mesh3D, _color: Option<ColorRGBA>| {
-                let instance_path_hash = instance_path_hash_for_picking(
-                    ent_path,
-                    instance_key,
-                    entity_view,
-                    props,
-                    entity_highlight,
-                );
-
-                let additive_tint = SceneSpatial::apply_hover_and_selection_effect_color(
-                    Color32::TRANSPARENT,
-                    entity_highlight.index_highlight(instance_path_hash.instance_key),
-                );
-
-                if let Some(mesh) = ctx
-                    .cache
-                    .mesh
-                    .load(
+        for instance_key in entity_view.iter_instance_keys()? {
+            let instance_path_hash = instance_path_hash_for_picking(
+                ent_path,
+                instance_key,
+                entity_view,
+                props,
+                entity_highlight,
+            );
+
+            let highlight = entity_highlight.index_highlight(instance_path_hash.instance_key);
+            let additive_tint = SceneSpatial::apply_hover_and_selection_effect_color(
+                Color32::TRANSPARENT,
+                highlight,
+            );
+
+            let cached_mesh = get_or_load_instance(
+                ctx,
+                ent_path,
+                entity_view,
+                instance_key,
+                |mesh, render_ctx| {
+                    Ok(CachedMesh::load(
                         &ent_path.to_string(),
                         &MeshSourceData::Mesh3D(mesh),
-                        ctx.render_ctx,
-                    )
-                    .map(|cpu_mesh| MeshSource {
-                        instance_path_hash,
-                        world_from_mesh: world_from_obj_affine,
-                        mesh: cpu_mesh,
-                        additive_tint,
-                    })
-                {
-                    scene.primitives.meshes.push(mesh);
-                };
-            };
+                        render_ctx,
+                    ))
+                },
+            );
 
-        entity_view.visit2(visitor)?;
+            if let Some(cached_mesh) = cached_mesh {
+                scene.primitives.meshes.push(MeshSource {
+                    instance_path_hash,
+                    world_from_mesh: world_from_obj_affine,
+                    mesh: cached_mesh.mesh.clone(),
+                    additive_tint,
+                    opacity: props.opacity,
+                    outline_mask_ids: highlight.outline_mask_ids(),
+                });
+            }
+        }
 
         Ok(())
     }
@@ -10,6 +10,8 @@ mod lines3d;
 mod meshes;
 mod points2d;
 mod points3d;
+mod volumes;
+mod voxel_grids;
 
 pub(crate) use arrows3d::Arrows3DPart;
 pub(crate) use boxes2d::Boxes2DPart;
@@ -21,10 +23,17 @@ pub(crate) use lines3d::Lines3DPart;
 pub(crate) use meshes::MeshPart;
 pub(crate) use points2d::Points2DPart;
 pub(crate) use points3d::Points3DPart;
+pub(crate) use volumes::VolumesPart;
+pub(crate) use voxel_grids::VoxelGridsPart;
 
-use super::SceneSpatial;
+use super::{
+    picking_instance_id, picking_layer_id, picking_object_id, SceneSpatial, StaticLineStripsSource,
+};
 use crate::{
-    misc::{OptionalSpaceViewEntityHighlight, SpaceViewHighlights, TransformCache, ViewerContext},
+    misc::{
+        caches::{GpuResource, GpuResourceKey},
+        OptionalSpaceViewEntityHighlight, SpaceViewHighlights, TransformCache, ViewerContext,
+    },
     ui::scene::SceneQuery,
 };
 use re_data_store::{EntityPath, EntityProperties, InstancePathHash};
@@ -60,3 +69,162 @@ pub fn instance_path_hash_for_picking<T: re_log_types::msg_bundle::Component>(
         InstancePathHash::NONE
     }
 }
+
+/// Entities with at least this many line vertices are kept on the gpu across frames, see [`CachedLineStrips`].
+const MIN_NUM_LINE_VERTICES_STATIC: usize = 10_000;
+
+/// Line strips of an entity that stay on the gpu for as long as its store rows are in use.
+struct CachedLineStrips {
+    draw_data: re_renderer::renderer::LineDrawData,
+
+    /// Bounding box of all vertices, in object space.
+    bounding_box: macaw::BoundingBox,
+    num_vertices: usize,
+}
+
+impl GpuResource for CachedLineStrips {
+    fn memory_used(&self) -> u64 {
+        re_renderer::renderer::LineDrawData::GPU_MEMORY_USED
+    }
+}
+
+/// Adds the line strips of an entity without any highlight from the [`crate::misc::caches::GpuResourceCache`],
+/// so large line strips aren't uploaded again every frame.
+///
+/// `add_strips` adds the entity's strips to a batch and is only called if they aren't cached yet.
+/// Returns `false` if the entity has too few vertices to be worth caching,
+/// in which case the caller should add its strips to [`super::SceneSpatialPrimitives::line_strips`].
+#[allow(clippy::too_many_arguments)]
+fn add_cached_line_strips<T: re_log_types::msg_bundle::Component>(
+    scene: &mut SceneSpatial,
+    ctx: &mut ViewerContext<'_>,
+    props: &EntityProperties,
+    entity_view: &re_query::EntityView<T>,
+    ent_path: &EntityPath,
+    world_from_obj: glam::Mat4,
+    annotations_msg_id: re_log_types::MsgId,
+    add_strips: impl FnOnce(
+        re_renderer::LineBatchBuilder<'_, InstancePathHash>,
+    ) -> Result<(), re_query::QueryError>,
+) -> bool {
+    crate::profile_function!();
+
+    let Some(row_index) = entity_view.primary_row_index() else {
+        return false;
+    };
+    let cache_key = GpuResourceKey::Row {
+        component_path: re_log_types::ComponentPath::new(ent_path.clone(), T::name()),
+        row_index,
+        // The transform and picking ids are baked into the draw data,
+        // colors and radii may come from other rows.
+        variant: {
+            use std::hash::{Hash as _, Hasher as _};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            annotations_msg_id.hash(&mut hasher);
+            for row_index in entity_view.row_indices() {
+                row_index.hash(&mut hasher);
+            }
+            for value in world_from_obj.to_cols_array() {
+                value.to_bits().hash(&mut hasher);
+            }
+            props.interactive.hash(&mut hasher);
+            hasher.finish()
+        },
+    };
+
+    let render_ctx = &mut *ctx.render_ctx;
+    let Some(line_strips) = ctx.cache.gpu_resources.get_or_create(cache_key, || {
+        let mut builder = re_renderer::LineStripSeriesBuilder::<InstancePathHash>::default();
+        let batch = builder
+            .batch(ent_path.to_string())
+            .world_from_obj(world_from_obj)
+            .picking_object_id(picking_object_id(ent_path, props.interactive));
+        if let Err(err) = add_strips(batch) {
+            re_log::error_once!("Failed to create line strips for {ent_path:?}: {err}");
+            return None;
+        }
+
+        // Below the threshold, uploading every frame is cheaper than holding on to the line textures.
+        if builder.vertices.len() < MIN_NUM_LINE_VERTICES_STATIC {
+            return None;
+        }
+
+        match re_renderer::renderer::LineDrawData::new(
+            render_ctx,
+            &builder.vertices,
+            &builder.strips,
+            &builder.batches,
+        ) {
+            Ok(draw_data) => Some(CachedLineStrips {
+                draw_data,
+                bounding_box: macaw::BoundingBox::from_points(
+                    builder.vertices.iter().map(|vertex| vertex.position),
+                ),
+                num_vertices: builder.vertices.len(),
+            }),
+            Err(err) => {
+                re_log::error_once!("Failed to create line strips for {ent_path:?}: {err}");
+                None
+            }
+        }
+    }) else {
+        return false;
+    };
+
+    scene
+        .primitives
+        .static_line_strips
+        .push(StaticLineStripsSource {
+            draw_data: line_strips.draw_data.clone(),
+            world_from_obj,
+            bounding_box: line_strips.bounding_box,
+            num_vertices: line_strips.num_vertices,
+        });
+
+    true
+}
+
+/// Returns the gpu resource made from the primary component of `entity_view` at `instance_key`,
+/// see [`crate::misc::caches::GpuResourceCache`].
+///
+/// The component is only deserialized and passed to `load` if the resource isn't on the gpu already.
+/// Returns a clone, which is cheap since cached resources hold on to gpu handles,
+/// so that `ctx` can be used again right away.
+fn get_or_load_instance<C, R>(
+    ctx: &mut ViewerContext<'_>,
+    ent_path: &EntityPath,
+    entity_view: &re_query::EntityView<C>,
+    instance_key: re_log_types::component_types::InstanceKey,
+    load: impl FnOnce(C, &mut re_renderer::RenderContext) -> anyhow::Result<Option<R>>,
+) -> Option<R>
+where
+    C: re_log_types::msg_bundle::SerializableComponent
+        + re_log_types::msg_bundle::DeserializableComponent,
+    for<'a> &'a C::ArrayType: IntoIterator,
+    R: GpuResource + Clone,
+{
+    let cache_key = GpuResourceKey::Row {
+        component_path: re_log_types::ComponentPath::new(ent_path.clone(), C::name()),
+        row_index: entity_view.primary_row_index()?,
+        variant: instance_key.0,
+    };
+
+    let render_ctx = &mut *ctx.render_ctx;
+    ctx.cache
+        .gpu_resources
+        .get_or_create(cache_key, || {
+            let component = entity_view.lookup::<C>(&instance_key).ok()?;
+            match load(component, render_ctx) {
+                Ok(resource) => resource,
+                Err(err) => {
+                    re_log::warn_once!(
+                        "Failed to load {} of {ent_path:?}: {}",
+                        C::name(),
+                        re_error::format(&err)
+                    );
+                    None
+                }
+            }
+        })
+        .cloned()
+}
@@ -17,7 +17,7 @@ use crate::{
     },
 };
 
-use super::{instance_path_hash_for_picking, ScenePart};
+use super::{instance_path_hash_for_picking, picking_instance_id, picking_object_id, ScenePart};
 
 pub struct Points2DPart;
 
@@ -44,11 +44,18 @@ impl Points2DPart {
         // We include time in the key, so that the "Visible history" (time range queries) feature works.
         let mut keypoints: Keypoints = Default::default();
 
+        let overall_outline_mask_ids = entity_highlight.overall().outline_mask_ids();
+        let mut additional_outline_mask_ids = Vec::new();
+        let mut point_index = 0;
+
         let mut point_batch = scene
             .primitives
             .points
             .batch("2d points")
-            .world_from_obj(world_from_obj);
+            .world_from_obj(world_from_obj)
+            .opacity(props.opacity)
+            .outline_mask_ids(overall_outline_mask_ids)
+            .picking_object_id(picking_object_id(ent_path, props.interactive));
 
         let visitor = |instance_key: InstanceKey,
                        pos: Point2D,
@@ -87,16 +94,20 @@ impl Points2DPart {
             let mut radius = radius.map_or(Size::AUTO, |r| Size::new_scene(r.0));
             let label = annotation_info.label(label.map(|l| l.0).as_ref());
 
-            SceneSpatial::apply_hover_and_selection_effect(
-                &mut radius,
-                &mut color,
-                entity_highlight.index_highlight(instance_hash.instance_key),
-            );
+            let highlight = entity_highlight.index_highlight(instance_hash.instance_key);
+            SceneSpatial::apply_hover_and_selection_effect(&mut radius, &mut color, highlight);
+
+            let outline_mask_ids = highlight.outline_mask_ids();
+            if outline_mask_ids != overall_outline_mask_ids {
+                additional_outline_mask_ids.push((point_index..point_index + 1, outline_mask_ids));
+            }
+            point_index += 1;
 
             point_batch
                 .add_point_2d(pos)
                 .color(color)
                 .radius(radius)
+                .picking_instance_id(picking_instance_id(instance_hash))
                 .user_data(instance_hash);
 
             if let Some(label) = label {
@@ -112,6 +123,10 @@ impl Points2DPart {
         };
 
         entity_view.visit6(visitor)?;
+        for (range, outline_mask_ids) in additional_outline_mask_ids {
+            point_batch =
+                point_batch.push_additional_outline_mask_ids_for_range(range, outline_mask_ids);
+        }
         drop(point_batch); // Drop batch so we have access to the scene again (batches need to be dropped before starting new ones).
 
         if label_batch.len() < max_num_labels {
@@ -7,12 +7,18 @@ use re_data_store::{EntityPath, EntityProperties, InstancePathHash};
 use re_log_types::{
     component_types::{ClassId, ColorRGBA, InstanceKey, KeypointId, Label, Point3D, Radius},
     msg_bundle::Component,
+    ComponentPath,
 };
 use re_query::{query_primary_with_history, EntityView, QueryError};
-use re_renderer::Size;
+use re_renderer::{
+    renderer::{PointCloudBatchFlags, StaticPointCloudInstance},
+    resource_managers::{GpuPointCloudHandle, PointCloudCreationDesc, ResourceLifeTime},
+    RenderContext, Size,
+};
 
 use crate::{
     misc::{
+        caches::{GpuResource, GpuResourceKey},
         InteractionHighlight, OptionalSpaceViewEntityHighlight, SpaceViewHighlights,
         TransformCache, ViewerContext,
     },
@@ -20,14 +26,32 @@ use crate::{
         annotations::ResolvedAnnotationInfo,
         scene::SceneQuery,
         view_spatial::{
-            scene::{scene_part::instance_path_hash_for_picking, Keypoints},
+            scene::{
+                scene_part::instance_path_hash_for_picking, Keypoints, StaticPointCloudSource,
+            },
             SceneSpatial, UiLabel, UiLabelTarget,
         },
         Annotations, DefaultColor,
     },
 };
 
-use super::ScenePart;
+use super::{picking_instance_id, picking_object_id, ScenePart};
+
+/// A point cloud that stays on the gpu for as long as its store rows are in use.
+struct CachedPointCloud {
+    gpu_point_cloud: GpuPointCloudHandle,
+
+    /// Bounding box of all points, in object space.
+    bounding_box: macaw::BoundingBox,
+    num_points: usize,
+}
+
+impl GpuResource for CachedPointCloud {
+    fn memory_used(&self) -> u64 {
+        // Position & radius, color and picking instance id.
+        (self.num_points * (16 + 4 + 8)) as u64
+    }
+}
 
 pub struct Points3DPart {
     /// If the number of points in the batch is > max_labels, don't render point labels.
@@ -35,6 +59,10 @@ pub struct Points3DPart {
 }
 
 impl Points3DPart {
+    /// Entities with at least this many points are kept on the gpu across frames
+    /// and drawn with level of detail, see [`CachedPointCloud`].
+    const MIN_NUM_POINTS_STATIC: usize = 100_000;
+
     fn process_annotations(
         query: &SceneQuery<'_>,
         entity_view: &EntityView<Point3D>,
@@ -152,6 +180,7 @@ impl Points3DPart {
     fn process_entity_view(
         &self,
         scene: &mut SceneSpatial,
+        ctx: &mut ViewerContext<'_>,
         query: &SceneQuery<'_>,
         properties: &EntityProperties,
         entity_view: &EntityView<Point3D>,
@@ -161,6 +190,22 @@ impl Points3DPart {
     ) -> Result<(), QueryError> {
         crate::profile_function!();
 
+        // Keypoint connections need to be gathered every frame, which would defeat the purpose.
+        if entity_view.num_instances() >= Self::MIN_NUM_POINTS_STATIC
+            && !entity_view.has_component::<KeypointId>()
+        {
+            return Self::process_entity_view_static(
+                scene,
+                ctx,
+                query,
+                properties,
+                entity_view,
+                ent_path,
+                world_from_obj,
+                entity_highlight,
+            );
+        }
+
         scene.num_logged_3d_objects += 1;
 
         let annotations = scene.annotation_map.find(ent_path);
@@ -218,20 +263,249 @@ impl Points3DPart {
             )?);
         }
 
-        scene
+        let overall_outline_mask_ids = entity_highlight.overall().outline_mask_ids();
+        let mut point_batch = scene
             .primitives
             .points
             .batch("3d points")
             .world_from_obj(world_from_obj)
+            .opacity(properties.opacity)
+            .outline_mask_ids(overall_outline_mask_ids)
+            .picking_object_id(picking_object_id(ent_path, properties.interactive));
+        for (point_index, highlight) in highlights.iter().enumerate() {
+            let outline_mask_ids = highlight.outline_mask_ids();
+            if outline_mask_ids != overall_outline_mask_ids {
+                let point_index = point_index as u32;
+                point_batch = point_batch.push_additional_outline_mask_ids_for_range(
+                    point_index..point_index + 1,
+                    outline_mask_ids,
+                );
+            }
+        }
+        point_batch
             .add_points(entity_view.num_instances(), point_positions)
             .colors(colors)
             .radii(radii)
+            .picking_instance_ids(
+                instance_path_hashes
+                    .iter()
+                    .copied()
+                    .map(picking_instance_id),
+            )
             .user_data(instance_path_hashes.into_iter());
 
         scene.load_keypoint_connections(ent_path, keypoints, &annotations, properties.interactive);
 
         Ok(())
     }
+
+    /// Like [`Self::process_entity_view`], but keeps the points on the gpu for as long as the
+    /// underlying store rows don't change.
+    ///
+    /// Hover and selection effects are not baked in:
+    /// The entity as a whole is outlined, highlighted instances are drawn again on top.
+    #[allow(clippy::too_many_arguments)]
+    fn process_entity_view_static(
+        scene: &mut SceneSpatial,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        properties: &EntityProperties,
+        entity_view: &EntityView<Point3D>,
+        ent_path: &EntityPath,
+        world_from_obj: Mat4,
+        entity_highlight: OptionalSpaceViewEntityHighlight<'_>,
+    ) -> Result<(), QueryError> {
+        crate::profile_function!();
+
+        scene.num_logged_3d_objects += 1;
+
+        let annotations = scene.annotation_map.find(ent_path);
+
+        let Some(row_index) = entity_view.primary_row_index() else {
+            return Ok(());
+        };
+        let cache_key = GpuResourceKey::Row {
+            component_path: ComponentPath::new(ent_path.clone(), Point3D::name()),
+            row_index,
+            // Colors, radii etc. may come from other rows.
+            variant: {
+                use std::hash::{Hash as _, Hasher as _};
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                annotations.msg_id.hash(&mut hasher);
+                for row_index in entity_view.row_indices() {
+                    row_index.hash(&mut hasher);
+                }
+                hasher.finish()
+            },
+        };
+
+        let render_ctx = &mut *ctx.render_ctx;
+        let Some(point_cloud) = ctx.cache.gpu_resources.get_or_create(cache_key, || {
+            match Self::create_static_point_cloud(
+                render_ctx,
+                query,
+                entity_view,
+                ent_path,
+                &annotations,
+            ) {
+                Ok(point_cloud) => Some(point_cloud),
+                Err(err) => {
+                    re_log::error_once!("Failed to create point cloud for {ent_path:?}: {err}");
+                    None
+                }
+            }
+        }) else {
+            return Ok(());
+        };
+
+        scene
+            .primitives
+            .static_point_clouds
+            .push(StaticPointCloudSource {
+                instance: StaticPointCloudInstance {
+                    gpu_point_cloud: point_cloud.gpu_point_cloud.clone(),
+                    world_from_obj,
+                    flags: PointCloudBatchFlags::ENABLE_SHADING,
+                    opacity: properties.opacity,
+                    outline_mask_ids: entity_highlight.overall().outline_mask_ids(),
+                    picking_object_id: picking_object_id(ent_path, properties.interactive),
+                },
+                bounding_box: point_cloud.bounding_box,
+                num_points: point_cloud.num_points,
+            });
+
+        // Only few instances are ever highlighted individually, draw them again with their highlight applied.
+        let default_color = DefaultColor::EntityPath(ent_path);
+        let highlighted_instances = entity_highlight
+            .iter_instance_highlights()
+            .filter_map(|(instance_key, highlight)| {
+                let position = entity_view.lookup::<Point3D>(&instance_key).ok()?;
+                let color = entity_view.lookup::<ColorRGBA>(&instance_key).ok();
+                let radius = entity_view.lookup::<Radius>(&instance_key).ok();
+                let class_id = entity_view.lookup::<ClassId>(&instance_key).ok();
+                let color = annotations
+                    .class_description(class_id)
+                    .annotation_info()
+                    .color(color.map(|c| c.to_array()).as_ref(), default_color);
+                Some((instance_key, highlight, position, color, radius))
+            })
+            .collect::<Vec<_>>();
+        if highlighted_instances.is_empty() {
+            return Ok(());
+        }
+
+        let mut point_batch = scene
+            .primitives
+            .points
+            .batch("3d points - highlighted")
+            .world_from_obj(world_from_obj)
+            .opacity(properties.opacity)
+            .picking_object_id(picking_object_id(ent_path, properties.interactive));
+        for (point_index, (_, highlight, ..)) in highlighted_instances.iter().enumerate() {
+            let point_index = point_index as u32;
+            point_batch = point_batch.push_additional_outline_mask_ids_for_range(
+                point_index..point_index + 1,
+                highlight.outline_mask_ids(),
+            );
+        }
+        let instance_path_hashes = highlighted_instances
+            .iter()
+            .map(|(instance_key, ..)| InstancePathHash::instance(ent_path, *instance_key))
+            .collect::<Vec<_>>();
+        point_batch
+            .add_points(
+                highlighted_instances.len(),
+                highlighted_instances
+                    .iter()
+                    .map(|(_, _, position, ..)| glam::Vec3::from(*position)),
+            )
+            .colors(
+                highlighted_instances
+                    .iter()
+                    .map(|(_, highlight, _, color, _)| {
+                        SceneSpatial::apply_hover_and_selection_effect_color(*color, *highlight)
+                    }),
+            )
+            .radii(
+                highlighted_instances
+                    .iter()
+                    .map(|(_, highlight, _, _, radius)| {
+                        SceneSpatial::apply_hover_and_selection_effect_size(
+                            radius.map_or(Size::AUTO, |radius| Size::new_scene(radius.0)),
+                            *highlight,
+                        )
+                    }),
+            )
+            .picking_instance_ids(
+                instance_path_hashes
+                    .iter()
+                    .copied()
+                    .map(picking_instance_id),
+            )
+            .user_data(instance_path_hashes.into_iter());
+
+        Ok(())
+    }
+
+    fn create_static_point_cloud(
+        render_ctx: &mut RenderContext,
+        query: &SceneQuery<'_>,
+        entity_view: &EntityView<Point3D>,
+        ent_path: &EntityPath,
+        annotations: &Arc<Annotations>,
+    ) -> anyhow::Result<CachedPointCloud> {
+        crate::profile_function!();
+
+        let (annotation_infos, _) = Self::process_annotations(query, entity_view, annotations)?;
+        let default_color = DefaultColor::EntityPath(ent_path);
+
+        let num_instances = entity_view.num_instances();
+        let mut positions = Vec::with_capacity(num_instances);
+        let mut radii = Vec::with_capacity(num_instances);
+        let mut colors = Vec::with_capacity(num_instances);
+        let mut picking_instance_ids = Vec::with_capacity(num_instances);
+        {
+            crate::profile_scope!("collect_points");
+            for (position, instance_key, color, radius, annotation_info) in itertools::izip!(
+                entity_view.iter_primary()?,
+                entity_view.iter_instance_keys()?,
+                entity_view.iter_component::<ColorRGBA>()?,
+                entity_view.iter_component::<Radius>()?,
+                annotation_infos.iter(),
+            ) {
+                let Some(position) = position else {
+                    continue;
+                };
+                positions.push(glam::Vec3::from(position));
+                radii.push(radius.map_or(Size::AUTO, |radius| Size::new_scene(radius.0)));
+                colors.push(
+                    annotation_info.color(color.map(|c| c.to_array()).as_ref(), default_color),
+                );
+                picking_instance_ids.push(picking_instance_id(InstancePathHash::instance(
+                    ent_path,
+                    instance_key,
+                )));
+            }
+        }
+
+        let gpu_point_cloud = render_ctx.point_cloud_manager.write().create(
+            render_ctx,
+            &PointCloudCreationDesc {
+                label: ent_path.to_string().into(),
+                positions: &positions,
+                radii: &radii,
+                colors: &colors,
+                picking_instance_ids: &picking_instance_ids,
+            },
+            ResourceLifeTime::LongLived,
+        )?;
+
+        Ok(CachedPointCloud {
+            gpu_point_cloud,
+            bounding_box: macaw::BoundingBox::from_points(positions.iter().copied()),
+            num_points: positions.len(),
+        })
+    }
 }
 
 impl ScenePart for Points3DPart {
@@ -271,6 +545,7 @@ impl ScenePart for Points3DPart {
                 for entity in entities {
                     self.process_entity_view(
                         scene,
+                        ctx,
                         query,
                         &props,
                         &entity,
@@ -0,0 +1,268 @@
+use std::ops::RangeInclusive;
+
+use glam::Mat4;
+
+use re_data_store::{EntityPath, EntityProperties, VolumeRenderMode};
+use re_log_types::{
+    component_types::{InstanceKey, Tensor, TensorData, TensorTrait},
+    msg_bundle::Component,
+};
+use re_query::{query_primary_with_history, EntityView, QueryError};
+use re_renderer::{
+    renderer::VolumeInstance,
+    resource_managers::{GpuVolumeHandle, ResourceLifeTime, VolumeCreationDesc, VolumeData},
+    ColorMap, RenderContext,
+};
+
+use crate::{
+    misc::{caches::GpuResource, SpaceViewHighlights, TransformCache, ViewerContext},
+    ui::{scene::SceneQuery, view_spatial::SceneSpatial, Colorbar, ResolvedColorMapper},
+};
+
+use super::{get_or_load_instance, instance_path_hash_for_picking, picking_layer_id, ScenePart};
+
+/// A rank 3 tensor uploaded to the gpu as a 3D texture.
+#[derive(Clone)]
+struct CachedVolume {
+    gpu_volume: GpuVolumeHandle,
+
+    /// Number of voxels along x, y and z.
+    dimensions: glam::UVec3,
+
+    /// The tensor values that are mapped to 0 and 1 on the gpu.
+    value_range: RangeInclusive<f64>,
+    memory_used: u64,
+}
+
+impl GpuResource for CachedVolume {
+    fn memory_used(&self) -> u64 {
+        self.memory_used
+    }
+}
+
+impl CachedVolume {
+    fn load(
+        ent_path: &EntityPath,
+        tensor: &Tensor,
+        render_ctx: &mut RenderContext,
+    ) -> anyhow::Result<Self> {
+        crate::profile_function!();
+
+        // The last dimension varies fastest, same as for images where it is the width.
+        let shape = tensor.shape();
+        let dimensions = glam::UVec3::new(
+            shape[2].size as u32,
+            shape[1].size as u32,
+            shape[0].size as u32,
+        );
+
+        // Anything but `u8` is normalized to the range of its values, which is plenty for display.
+        let (normalized, value_range) = match &tensor.data {
+            TensorData::U8(_) => (Vec::new(), 0.0..=u8::MAX as f64),
+            TensorData::U16(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::U32(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::U64(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::I8(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::I16(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::I32(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::I64(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::F32(data) => normalize(data.as_slice(), |v| v as f64),
+            TensorData::F64(data) => normalize(data.as_slice(), |v| v),
+            TensorData::JPEG(_) => anyhow::bail!("JPEG tensors can't be rendered as volumes"),
+        };
+        let data = match &tensor.data {
+            TensorData::U8(data) => VolumeData::U8(data.as_slice()),
+            _ => VolumeData::F16(normalized.as_slice()),
+        };
+        let memory_used = match data {
+            VolumeData::U8(data) => data.len(),
+            VolumeData::F16(data) => std::mem::size_of_val(data),
+        } as u64;
+
+        let gpu_volume = render_ctx.volume_manager.write().create(
+            render_ctx,
+            &VolumeCreationDesc {
+                label: ent_path.to_string().into(),
+                dimensions,
+                data,
+            },
+            ResourceLifeTime::LongLived,
+        )?;
+
+        Ok(Self {
+            gpu_volume,
+            dimensions,
+            value_range,
+            memory_used,
+        })
+    }
+}
+
+/// Maps values to `[0, 1]` by their minimum and maximum.
+fn normalize<T: Copy>(
+    data: &[T],
+    to_f64: impl Fn(T) -> f64,
+) -> (Vec<half::f16>, RangeInclusive<f64>) {
+    crate::profile_function!();
+
+    let (min, max) = data
+        .iter()
+        .map(|&v| to_f64(v))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    let (min, max) = if min < max {
+        (min, max)
+    } else if min.is_finite() {
+        // All values are the same.
+        (min, min + 1.0)
+    } else {
+        (0.0, 1.0)
+    };
+
+    let normalized = data
+        .iter()
+        .map(|&v| half::f16::from_f64((to_f64(v) - min) / (max - min)))
+        .collect();
+    (normalized, min..=max)
+}
+
+pub struct VolumesPart;
+
+impl VolumesPart {
+    #[allow(clippy::too_many_arguments)]
+    fn process_entity_view(
+        scene: &mut SceneSpatial,
+        props: &EntityProperties,
+        entity_view: &EntityView<Tensor>,
+        ent_path: &EntityPath,
+        world_from_obj: Mat4,
+        ctx: &mut ViewerContext<'_>,
+        highlights: &SpaceViewHighlights,
+    ) -> Result<(), QueryError> {
+        let entity_highlight = highlights.entity_highlight(ent_path.hash());
+
+        for instance_key in entity_view.iter_instance_keys()? {
+            let volume = get_or_load_instance(
+                ctx,
+                ent_path,
+                entity_view,
+                instance_key,
+                |tensor: Tensor, render_ctx| {
+                    if !tensor.is_shaped_like_a_volume() {
+                        return Ok(None);
+                    }
+                    CachedVolume::load(ent_path, &tensor, render_ctx).map(Some)
+                },
+            );
+            let Some(CachedVolume {
+                gpu_volume,
+                dimensions,
+                value_range,
+                memory_used: _,
+            }) = volume else {
+                continue;
+            };
+
+            scene.num_logged_3d_objects += 1;
+
+            let instance_path_hash = instance_path_hash_for_picking(
+                ent_path,
+                instance_key,
+                entity_view,
+                props,
+                entity_highlight,
+            );
+            let highlight = entity_highlight.index_highlight(instance_path_hash.instance_key);
+
+            let color_mapper = if props.color_mapping {
+                let color_mapper =
+                    ResolvedColorMapper::resolve(ctx, ent_path, *props.color_mapper.get());
+                scene.ui.colorbars.push(Colorbar {
+                    label: ent_path.to_string(),
+                    color_mapper: color_mapper.clone(),
+                    gamma: 1.0,
+                    range: value_range,
+                    unit: None,
+                });
+                color_mapper
+            } else {
+                ResolvedColorMapper::ColorMap(ColorMap::Grayscale)
+            };
+
+            let render_mode = match *props.volume_render_mode.get() {
+                VolumeRenderMode::Composite => re_renderer::renderer::VolumeRenderMode::Composite,
+                VolumeRenderMode::MaxIntensity => {
+                    re_renderer::renderer::VolumeRenderMode::MaxIntensity
+                }
+                VolumeRenderMode::Isosurface { iso_value } => {
+                    re_renderer::renderer::VolumeRenderMode::Isosurface { iso_value }
+                }
+            };
+
+            // One voxel per unit, like one pixel per unit for images.
+            let world_from_volume = world_from_obj * Mat4::from_scale(dimensions.as_vec3());
+
+            scene.primitives.volumes.push(VolumeInstance {
+                gpu_volume,
+                world_from_volume,
+                render_mode,
+                colormap: color_mapper.colormap(),
+                colormap_lut: color_mapper.lut(),
+                threshold: *props.volume_threshold.get(),
+                opacity: props.opacity,
+                outline_mask_ids: highlight.outline_mask_ids(),
+                picking_layer_id: picking_layer_id(instance_path_hash),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl ScenePart for VolumesPart {
+    fn load(
+        &self,
+        scene: &mut SceneSpatial,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        transforms: &TransformCache,
+        highlights: &SpaceViewHighlights,
+    ) {
+        crate::profile_scope!("VolumesPart");
+
+        for (ent_path, props) in query.iter_entities() {
+            let Some(world_from_obj) = transforms.reference_from_entity(ent_path) else {
+                continue;
+            };
+
+            match query_primary_with_history::<Tensor, 2>(
+                &ctx.log_db.entity_db.data_store,
+                &query.timeline,
+                &query.latest_at,
+                &props.visible_history,
+                ent_path,
+                [Tensor::name(), InstanceKey::name()],
+            )
+            .and_then(|entities| {
+                for entity in entities {
+                    Self::process_entity_view(
+                        scene,
+                        &props,
+                        &entity,
+                        ent_path,
+                        world_from_obj,
+                        ctx,
+                        highlights,
+                    )?;
+                }
+                Ok(())
+            }) {
+                Ok(_) | Err(QueryError::PrimaryNotFound) => {}
+                Err(err) => {
+                    re_log::error_once!("Unexpected error querying {ent_path:?}: {err}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::ops::RangeInclusive;
+
+use glam::Mat4;
+
+use re_data_store::{EntityPath, EntityProperties};
+use re_log_types::{
+    component_types::{InstanceKey, VoxelGrid},
+    msg_bundle::Component,
+};
+use re_query::{query_primary_with_history, EntityView, QueryError};
+use re_renderer::{
+    renderer::VoxelGridInstance,
+    resource_managers::{GpuVoxelGridHandle, ResourceLifeTime, VoxelCell, VoxelGridCreationDesc},
+    ColorMap, RenderContext,
+};
+
+use crate::{
+    misc::{caches::GpuResource, SpaceViewHighlights, TransformCache, ViewerContext},
+    ui::{
+        scene::SceneQuery,
+        view_spatial::{scene::VoxelGridSource, SceneSpatial},
+        Colorbar, ResolvedColorMapper,
+    },
+};
+
+use super::{get_or_load_instance, instance_path_hash_for_picking, picking_layer_id, ScenePart};
+
+/// The occupied cells of a [`VoxelGrid`], uploaded to the gpu. Empty cells are skipped.
+#[derive(Clone)]
+struct CachedVoxelGrid {
+    gpu_voxel_grid: GpuVoxelGridHandle,
+
+    /// Maps cell indices to the grid's object space, from its origin and voxel size.
+    obj_from_grid: Mat4,
+
+    /// Bounding box of all occupied cells, in cell indices.
+    bounding_box: macaw::BoundingBox,
+    num_cells: usize,
+
+    /// The cell values that are mapped to 0 and 1 on the gpu.
+    value_range: RangeInclusive<f64>,
+}
+
+impl GpuResource for CachedVoxelGrid {
+    fn memory_used(&self) -> u64 {
+        (self.num_cells * std::mem::size_of::<VoxelCell>()) as u64
+    }
+}
+
+impl CachedVoxelGrid {
+    /// Returns `None` if there are no occupied cells.
+    fn load(
+        ent_path: &EntityPath,
+        grid: &VoxelGrid,
+        render_ctx: &mut RenderContext,
+    ) -> anyhow::Result<Option<Self>> {
+        crate::profile_function!();
+
+        let mut cells = grid
+            .iter_occupied()
+            .map(|(index, value)| VoxelCell { index, value })
+            .collect::<Vec<_>>();
+        if cells.is_empty() {
+            return Ok(None);
+        }
+
+        // Normalized to the range of the values for color mapping, like volumes.
+        let (min, max) = cells
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), cell| {
+                (min.min(cell.value), max.max(cell.value))
+            });
+        let (min, max) = if min < max {
+            (min, max)
+        } else {
+            // All values are the same, e.g. a binary occupancy map.
+            (min - 1.0, min)
+        };
+        for cell in &mut cells {
+            cell.value = (cell.value - min) / (max - min);
+        }
+
+        let bounding_box = cells
+            .iter()
+            .fold(macaw::BoundingBox::nothing(), |bbox, cell| {
+                let corner = glam::UVec3::from(cell.index).as_vec3();
+                bbox.union(macaw::BoundingBox::from_min_max(
+                    corner,
+                    corner + glam::Vec3::ONE,
+                ))
+            });
+
+        let gpu_voxel_grid = render_ctx.voxel_grid_manager.write().create(
+            render_ctx,
+            &VoxelGridCreationDesc {
+                label: ent_path.to_string().into(),
+                cells: &cells,
+            },
+            ResourceLifeTime::LongLived,
+        )?;
+
+        Ok(Some(Self {
+            gpu_voxel_grid,
+            obj_from_grid: Mat4::from_translation(grid.origin.into())
+                * Mat4::from_scale(glam::Vec3::splat(grid.voxel_size)),
+            bounding_box,
+            num_cells: cells.len(),
+            value_range: min as f64..=max as f64,
+        }))
+    }
+}
+
+pub struct VoxelGridsPart;
+
+impl VoxelGridsPart {
+    #[allow(clippy::too_many_arguments)]
+    fn process_entity_view(
+        scene: &mut SceneSpatial,
+        props: &EntityProperties,
+        entity_view: &EntityView<VoxelGrid>,
+        ent_path: &EntityPath,
+        world_from_obj: Mat4,
+        ctx: &mut ViewerContext<'_>,
+        highlights: &SpaceViewHighlights,
+    ) -> Result<(), QueryError> {
+        let entity_highlight = highlights.entity_highlight(ent_path.hash());
+
+        for instance_key in entity_view.iter_instance_keys()? {
+            let voxel_grid = get_or_load_instance(
+                ctx,
+                ent_path,
+                entity_view,
+                instance_key,
+                |grid: VoxelGrid, render_ctx| CachedVoxelGrid::load(ent_path, &grid, render_ctx),
+            );
+            let Some(CachedVoxelGrid {
+                gpu_voxel_grid,
+                obj_from_grid,
+                bounding_box,
+                num_cells,
+                value_range,
+            }) = voxel_grid else {
+                continue;
+            };
+
+            scene.num_logged_3d_objects += 1;
+
+            let instance_path_hash = instance_path_hash_for_picking(
+                ent_path,
+                instance_key,
+                entity_view,
+                props,
+                entity_highlight,
+            );
+            let highlight = entity_highlight.index_highlight(instance_path_hash.instance_key);
+
+            let color_mapper = if props.color_mapping {
+                let color_mapper =
+                    ResolvedColorMapper::resolve(ctx, ent_path, *props.color_mapper.get());
+                scene.ui.colorbars.push(Colorbar {
+                    label: ent_path.to_string(),
+                    color_mapper: color_mapper.clone(),
+                    gamma: 1.0,
+                    range: value_range,
+                    unit: None,
+                });
+                color_mapper
+            } else {
+                ResolvedColorMapper::ColorMap(ColorMap::Grayscale)
+            };
+
+            scene.primitives.voxel_grids.push(VoxelGridSource {
+                instance: VoxelGridInstance {
+                    gpu_voxel_grid,
+                    world_from_grid: world_from_obj * obj_from_grid,
+                    colormap: color_mapper.colormap(),
+                    colormap_lut: color_mapper.lut(),
+                    threshold: *props.volume_threshold.get(),
+                    opacity: props.opacity,
+                    outline_mask_ids: highlight.outline_mask_ids(),
+                    picking_layer_id: picking_layer_id(instance_path_hash),
+                },
+                bounding_box,
+                num_cells,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl ScenePart for VoxelGridsPart {
+    fn load(
+        &self,
+        scene: &mut SceneSpatial,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        transforms: &TransformCache,
+        highlights: &SpaceViewHighlights,
+    ) {
+        crate::profile_scope!("VoxelGridsPart");
+
+        for (ent_path, props) in query.iter_entities() {
+            let Some(world_from_obj) = transforms.reference_from_entity(ent_path) else {
+                continue;
+            };
+
+            match query_primary_with_history::<VoxelGrid, 2>(
+                &ctx.log_db.entity_db.data_store,
+                &query.timeline,
+                &query.latest_at,
+                &props.visible_history,
+                ent_path,
+                [VoxelGrid::name(), InstanceKey::name()],
+            )
+            .and_then(|entities| {
+                for entity in entities {
+                    Self::process_entity_view(
+                        scene,
+                        &props,
+                        &entity,
+                        ent_path,
+                        world_from_obj,
+                        ctx,
+                        highlights,
+                    )?;
+                }
+                Ok(())
+            }) {
+                Ok(_) | Err(QueryError::PrimaryNotFound) => {}
+                Err(err) => {
+                    re_log::error_once!("Unexpected error querying {ent_path:?}: {err}");
+                }
+            }
+        }
+    }
+}
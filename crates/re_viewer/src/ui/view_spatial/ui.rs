@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use eframe::epaint::text::TextWrapping;
 use re_data_store::{query_latest_single, EditableAutoValue, EntityPath};
 use re_format::format_f32;
@@ -5,17 +7,23 @@ use re_format::format_f32;
 use egui::{NumExt, WidgetText};
 use macaw::BoundingBox;
 use re_log_types::component_types::{Tensor, TensorData, TensorDataMeaning};
+use re_renderer::{renderer::LabelInstance, Size};
 
 use crate::{
     misc::{
-        space_info::query_view_coordinates, SelectionHighlight, SpaceViewHighlights, ViewerContext,
+        space_info::query_view_coordinates, HoverHighlight, InteractionHighlight,
+        SelectionHighlight, SpaceViewHighlights, ViewerContext,
     },
     ui::{data_blueprint::DataBlueprintTree, view_spatial::UiLabelTarget, SpaceViewId},
 };
 
 use super::{
-    eye::Eye, scene::SceneSpatialUiData, ui_2d::View2DState, ui_3d::View3DState, SceneSpatial,
-    SpaceSpecs,
+    eye::Eye,
+    measurement::MeasurementState,
+    scene::{picking_layer_id, SceneSpatialUiData},
+    ui_2d::View2DState,
+    ui_3d::View3DState,
+    SceneSpatial, SpaceSpecs,
 };
 
 /// Describes how the scene is navigated, determining if it is a 2D or 3D experience.
@@ -74,8 +82,15 @@ pub struct ViewSpatialState {
     pub(super) state_2d: View2DState,
     pub(super) state_3d: View3DState,
 
+    /// Interactive measurement & annotation tools.
+    pub(super) measurements: MeasurementState,
+
     /// Size of automatically sized objects. None if it wasn't configured.
     auto_size_config: re_renderer::AutoSizeConfig,
+
+    /// Last gpu picking result that arrived, see [`Self::gpu_picking_result`].
+    #[serde(skip)]
+    previous_picking_result: Option<Arc<re_renderer::PickingResult<()>>>,
 }
 
 impl Default for ViewSpatialState {
@@ -87,15 +102,51 @@ impl Default for ViewSpatialState {
             scene_num_primitives: 0,
             state_2d: Default::default(),
             state_3d: Default::default(),
+            measurements: Default::default(),
             auto_size_config: re_renderer::AutoSizeConfig {
                 point_radius: re_renderer::Size::AUTO, // let re_renderer decide
                 line_radius: re_renderer::Size::AUTO,  // let re_renderer decide
             },
+            previous_picking_result: None,
         }
     }
 }
 
 impl ViewSpatialState {
+    /// Newest gpu picking result for the picking rects scheduled with `readback_identifier`.
+    ///
+    /// Results arrive a few frames after the picking rect was scheduled. Until a newer one
+    /// arrives the previous one is reused, and repaints are requested so we pick it up.
+    pub(super) fn gpu_picking_result(
+        &mut self,
+        ctx: &ViewerContext<'_>,
+        egui_ctx: &egui::Context,
+        readback_identifier: u64,
+        is_hovered: bool,
+    ) -> Option<Arc<re_renderer::PickingResult<()>>> {
+        let newest = re_renderer::PickingLayerProcessor::next_readback_result::<()>(
+            ctx.render_ctx,
+            readback_identifier,
+        );
+
+        if !is_hovered {
+            // Don't resurrect a stale result once the pointer comes back.
+            self.previous_picking_result = None;
+            return None;
+        }
+
+        if let Some(newest) = newest {
+            self.previous_picking_result = Some(Arc::new(newest));
+        } else if re_renderer::PickingLayerProcessor::has_pending_readback(
+            ctx.render_ctx,
+            readback_identifier,
+        ) {
+            egui_ctx.request_repaint();
+        }
+
+        self.previous_picking_result.clone()
+    }
+
     pub fn auto_size_config(
         &self,
         viewport_size_in_points: egui::Vec2,
@@ -359,8 +410,23 @@ impl ViewSpatialState {
                     ui.checkbox(&mut self.state_3d.show_axes, "Show origin axes").on_hover_text("Show X-Y-Z axes");
                 });
                 ui.end_row();
+
+                ctx.re_ui.grid_left_hand_label(ui, "Lighting")
+                    .on_hover_text("How meshes in this view are lit.");
+                self.state_3d.lighting.ui(ui);
+                ui.end_row();
             }
 
+            ctx.re_ui.grid_left_hand_label(ui, "Measure")
+                .on_hover_text("Measure distances and angles, or draw boxes and polygons in 2D.");
+            self.measurements.selection_ui(
+                ctx,
+                ui,
+                space_path,
+                self.nav_mode == SpatialNavigationMode::TwoD,
+            );
+            ui.end_row();
+
             ctx.re_ui.grid_left_hand_label(ui, "Bounding box")
                 .on_hover_text("The bounding box encompassing all Entities in the view right now.");
             ui.vertical(|ui| {
@@ -529,6 +595,56 @@ fn axis_name(axis: Option<glam::Vec3>) -> String {
     }
 }
 
+/// Background color of a label, depending on whether the labeled instance is hovered or selected.
+fn label_background_color(style: &egui::Style, highlight: InteractionHighlight) -> egui::Color32 {
+    match highlight.hover {
+        HoverHighlight::None => match highlight.selection {
+            SelectionHighlight::None => style.visuals.widgets.inactive.bg_fill,
+            SelectionHighlight::SiblingSelection | SelectionHighlight::Selection => {
+                style.visuals.widgets.active.bg_fill
+            }
+        },
+        HoverHighlight::Hovered => style.visuals.widgets.hovered.bg_fill,
+    }
+}
+
+/// Moves all labels of 3D positions over to `re_renderer`, which draws them depth aware and without overlaps.
+///
+/// All other labels are left for [`create_labels`].
+pub fn move_3d_labels_to_renderer(
+    scene: &mut SceneSpatial,
+    parent_ui: &egui::Ui,
+    highlights: &SpaceViewHighlights,
+) {
+    crate::profile_function!();
+
+    let style = parent_ui.style();
+    let size = Size::new_points(egui::TextStyle::Body.resolve(style).size);
+
+    for label in std::mem::take(&mut scene.ui.labels) {
+        let UiLabelTarget::Position3D(anchor) = label.target else {
+            scene.ui.labels.push(label);
+            continue;
+        };
+
+        let highlight = highlights
+            .entity_highlight(label.labeled_instance.entity_path_hash)
+            .index_highlight(label.labeled_instance.instance_key);
+
+        scene.primitives.labels.push(LabelInstance {
+            text: label.text,
+            anchor,
+            size,
+            text_color: label.color,
+            background_color: label_background_color(style, highlight),
+            // Hovered & selected labels are placed first.
+            priority: highlight.is_some() as u8 as f32,
+            outline_mask_ids: highlight.outline_mask_ids(),
+            picking_layer_id: picking_layer_id(label.labeled_instance),
+        });
+    }
+}
+
 pub fn create_labels(
     scene_ui: &mut SceneSpatialUiData,
     ui_from_space2d: egui::emath::RectTransform,
@@ -595,18 +711,7 @@ pub fn create_labels(
         let highlight = highlights
             .entity_highlight(label.labeled_instance.entity_path_hash)
             .index_highlight(label.labeled_instance.instance_key);
-        let fill_color = match highlight.hover {
-            crate::misc::HoverHighlight::None => match highlight.selection {
-                SelectionHighlight::None => parent_ui.style().visuals.widgets.inactive.bg_fill,
-                SelectionHighlight::SiblingSelection => {
-                    parent_ui.style().visuals.widgets.active.bg_fill
-                }
-                SelectionHighlight::Selection => parent_ui.style().visuals.widgets.active.bg_fill,
-            },
-            crate::misc::HoverHighlight::Hovered => {
-                parent_ui.style().visuals.widgets.hovered.bg_fill
-            }
-        };
+        let fill_color = label_background_color(parent_ui.style(), highlight);
 
         label_shapes.push(egui::Shape::rect_filled(bg_rect, 3.0, fill_color));
         label_shapes.push(egui::Shape::galley(text_rect.center_top(), galley));
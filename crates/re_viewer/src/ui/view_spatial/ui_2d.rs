@@ -7,13 +7,17 @@ use re_data_store::EntityPath;
 use re_log_types::component_types::TensorTrait;
 use re_renderer::view_builder::TargetConfiguration;
 
-use super::{eye::Eye, scene::AdditionalPickingInfo, ui::create_labels, ViewSpatialState};
+use super::{
+    eye::Eye, measurement, scene::AdditionalPickingInfo, ui::create_labels, ViewSpatialState,
+};
 use crate::{
     misc::{HoveredSpace, Item, SpaceViewHighlights},
     ui::{
         data_ui::{self, DataUi},
         view_spatial::{
-            ui_renderer_bridge::{create_scene_paint_callback, get_viewport, ScreenBackground},
+            ui_renderer_bridge::{
+                create_scene_paint_callback, get_viewport, PickingRectRequest, ScreenBackground,
+            },
             SceneSpatial,
         },
         SpaceViewId, UiVerbosity,
@@ -214,7 +218,10 @@ impl View2DState {
 
 pub const HELP_TEXT_2D: &str = "Ctrl-scroll  to zoom (⌘-scroll or Mac).\n\
     Drag to pan.\n\
-    Double-click to reset the view.";
+    Double-click to reset the view.\n\
+    \n\
+    Pick a measurement tool in the selection panel to measure or draw annotations.\n\
+    Right-click removes the last point, Escape cancels.";
 
 /// Create the outer 2D view, which consists of a scrollable region
 /// TODO(andreas): Split into smaller parts, more re-use with `ui_3d`
@@ -313,16 +320,26 @@ fn view_2d_scrollable(
         highlights,
     );
 
+    // Gpu picking results arrive a few frames after the picking rect was scheduled.
+    let picking_readback_identifier = egui::Id::new(space_view_id).value();
+    let gpu_picking_result = state.gpu_picking_result(
+        ctx,
+        parent_ui.ctx(),
+        picking_readback_identifier,
+        response.hovered(),
+    );
+
     // Check if we're hovering any hover primitive.
     let mut depth_at_pointer = None;
     if let Some(pointer_pos_ui) = response.hover_pos() {
         let pointer_pos_space = space_from_ui.transform_pos(pointer_pos_ui);
-        let hover_radius = space_from_ui.scale().y * 5.0; // TODO(emilk): from egui?
+        let hover_radius = space_from_ui.scale().y * PickingRectRequest::RADIUS_POINTS; // TODO(emilk): from egui?
         let picking_result = scene.picking(
             glam::vec2(pointer_pos_space.x, pointer_pos_space.y),
             &scene_rect_accum,
             &eye,
             hover_radius,
+            gpu_picking_result.as_deref(),
         );
 
         for hit in picking_result.iter_hits() {
@@ -367,11 +384,12 @@ fn view_2d_scrollable(
                                 &ctx.current_query(),
                             );
 
-                            let tensor_view = ctx.cache.image.get_view_with_annotations(
-                                &image.tensor,
-                                &image.annotations,
-                                ctx.render_ctx,
-                            );
+                            let tensor_view =
+                                ctx.cache.gpu_resources.tensor_image_view_with_annotations(
+                                    &image.tensor,
+                                    &image.annotations,
+                                    ctx.render_ctx,
+                                );
 
                             if let [h, w, ..] = image.tensor.shape() {
                                 ui.separator();
@@ -417,7 +435,18 @@ fn view_2d_scrollable(
         }
     }
 
-    ctx.select_hovered_on_click(&response);
+    // Clicks place measurement points instead of selecting while a measurement tool is active.
+    let ui_from_space_2d = measurement::ui_from_space_2d(ui_from_space);
+    let hovered_point = response.hover_pos().map(|pointer_pos_ui| {
+        let pointer_pos_space = space_from_ui.transform_pos(pointer_pos_ui);
+        glam::vec3(pointer_pos_space.x, pointer_pos_space.y, 0.0)
+    });
+    if !state
+        .measurements
+        .handle_input(&response, hovered_point, &ui_from_space_2d)
+    {
+        ctx.select_hovered_on_click(&response);
+    }
 
     // ------------------------------------------------------------------------
 
@@ -432,15 +461,26 @@ fn view_2d_scrollable(
             space_from_pixel,
             &space.to_string(),
             state.auto_size_config(response.rect.size()),
+            scene.outline_config(parent_ui.ctx().pixels_per_point()),
         ) else {
             return response;
         };
 
+        let picking_rect_request = response.hover_pos().map(|pointer_pos_ui| {
+            PickingRectRequest::around_pointer(
+                pointer_pos_ui,
+                painter.clip_rect(),
+                parent_ui.ctx().pixels_per_point(),
+                picking_readback_identifier,
+            )
+        });
+
         let Ok(callback) = create_scene_paint_callback(
             ctx.render_ctx,
             target_config, painter.clip_rect(),
             scene.primitives,
             &ScreenBackground::ClearColor(parent_ui.visuals().extreme_bg_color.into()),
+            picking_rect_request,
         ) else {
             return response;
         };
@@ -458,6 +498,11 @@ fn view_2d_scrollable(
 
     // Add egui driven labels on top of re_renderer content.
     painter.extend(label_shapes);
+    painter.extend(
+        state
+            .measurements
+            .shapes(parent_ui, hovered_point, &ui_from_space_2d),
+    );
 
     response
 }
@@ -468,6 +513,7 @@ fn setup_target_config(
     space_from_pixel: f32,
     space_name: &str,
     auto_size_config: re_renderer::AutoSizeConfig,
+    outline_config: Option<re_renderer::OutlineConfig>,
 ) -> anyhow::Result<TargetConfiguration> {
     let pixels_from_points = painter.ctx().pixels_per_point();
     let resolution_in_pixel = get_viewport(painter.clip_rect(), pixels_from_points);
@@ -490,6 +536,8 @@ fn setup_target_config(
             },
             pixels_from_point: pixels_from_points,
             auto_size_config,
+            outline_config,
+            lighting_config: Default::default(),
         }
     })
 }
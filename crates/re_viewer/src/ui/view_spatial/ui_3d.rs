@@ -13,11 +13,14 @@ use re_renderer::{
 use crate::{
     misc::{HoveredSpace, Item, SpaceViewHighlights},
     ui::{
+        colorbars_overlay_ui,
         data_ui::{self, DataUi},
         view_spatial::{
             scene::AdditionalPickingInfo,
-            ui::create_labels,
-            ui_renderer_bridge::{create_scene_paint_callback, get_viewport, ScreenBackground},
+            ui::{create_labels, move_3d_labels_to_renderer},
+            ui_renderer_bridge::{
+                create_scene_paint_callback, get_viewport, PickingRectRequest, ScreenBackground,
+            },
             SceneSpatial, SpaceCamera3D,
         },
         SpaceViewId, UiVerbosity,
@@ -27,7 +30,7 @@ use crate::{
 
 use super::{
     eye::{Eye, OrbitEye},
-    ViewSpatialState,
+    measurement, ViewSpatialState,
 };
 
 // ---
@@ -53,6 +56,7 @@ pub struct View3DState {
     // options:
     pub spin: bool,
     pub show_axes: bool,
+    pub lighting: LightingOptions,
 
     #[serde(skip)]
     last_eye_interact_time: f64,
@@ -74,6 +78,7 @@ impl Default for View3DState {
             hovered_point: Default::default(),
             spin: false,
             show_axes: false,
+            lighting: Default::default(),
             last_eye_interact_time: f64::NEG_INFINITY,
             space_specs: Default::default(),
             space_camera: Default::default(),
@@ -81,6 +86,123 @@ impl Default for View3DState {
     }
 }
 
+/// How meshes in a 3D view are lit, see [`re_renderer::LightingConfig`].
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct LightingOptions {
+    /// Angle of the key light around the up axis, in degrees.
+    pub key_light_azimuth: f32,
+
+    /// Angle of the key light above the horizon, in degrees.
+    pub key_light_elevation: f32,
+
+    pub key_light_intensity: f32,
+    pub ambient_intensity: f32,
+
+    /// Meshes cast shadows from the key light.
+    pub shadows: bool,
+
+    /// Screen space ambient occlusion.
+    pub ambient_occlusion: bool,
+}
+
+impl Default for LightingOptions {
+    fn default() -> Self {
+        Self {
+            key_light_azimuth: 30.0,
+            key_light_elevation: 60.0,
+            key_light_intensity: 1.0,
+            ambient_intensity: 0.2,
+            shadows: false,
+            ambient_occlusion: false,
+        }
+    }
+}
+
+impl LightingOptions {
+    /// Shadow map resolution, enough for a sharp overview of the whole scene.
+    const SHADOW_MAP_RESOLUTION: u32 = 2048;
+
+    pub fn lighting_config(
+        &self,
+        space_specs: &SpaceSpecs,
+        scene_bbox_accum: &BoundingBox,
+    ) -> re_renderer::LightingConfig {
+        let up = space_specs.up.unwrap_or(Vec3::Z);
+        let forward = up.any_orthonormal_vector();
+        let right = up.cross(forward);
+        let azimuth = self.key_light_azimuth.to_radians();
+        let elevation = self.key_light_elevation.to_radians();
+        let key_light_direction = elevation.cos()
+            * (azimuth.cos() * forward + azimuth.sin() * right)
+            + elevation.sin() * up;
+
+        // Shadows & ambient occlusion are scaled to the scene.
+        let scene_radius = if scene_bbox_accum.is_nothing() || scene_bbox_accum.is_nan() {
+            0.0
+        } else {
+            scene_bbox_accum.size().length() * 0.5
+        };
+        let scene_is_valid = scene_radius.is_finite() && scene_radius > 0.0;
+
+        re_renderer::LightingConfig {
+            key_light_direction,
+            key_light_color: re_renderer::Rgba::WHITE,
+            key_light_intensity: self.key_light_intensity,
+            ambient_intensity: self.ambient_intensity,
+            shadows: (self.shadows && scene_is_valid).then(|| re_renderer::ShadowConfig {
+                bounds_center: scene_bbox_accum.center(),
+                bounds_radius: scene_radius,
+                resolution: Self::SHADOW_MAP_RESOLUTION,
+            }),
+            ssao: (self.ambient_occlusion && scene_is_valid).then(|| re_renderer::SsaoConfig {
+                radius: scene_radius * 0.05,
+                intensity: 1.0,
+            }),
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut self.key_light_azimuth)
+                        .clamp_range(0.0..=360.0)
+                        .suffix("°"),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut self.key_light_elevation)
+                        .clamp_range(-90.0..=90.0)
+                        .suffix("°"),
+                );
+                ui.label("Key light direction")
+                    .on_hover_text("Angle around and above the horizon of the view's up axis.");
+            });
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut self.key_light_intensity)
+                        .clamp_range(0.0..=10.0)
+                        .speed(0.01),
+                );
+                ui.label("Key light intensity");
+            });
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut self.ambient_intensity)
+                        .clamp_range(0.0..=10.0)
+                        .speed(0.01),
+                );
+                ui.label("Ambient intensity")
+                    .on_hover_text("Light that reaches all surfaces equally.");
+            });
+            ui.checkbox(&mut self.shadows, "Shadows")
+                .on_hover_text("Meshes cast shadows from the key light.");
+            ui.checkbox(&mut self.ambient_occlusion, "Ambient occlusion")
+                .on_hover_text("Darkens creases and corners of opaque geometry.");
+        });
+    }
+}
+
 impl View3DState {
     pub fn reset_camera(&mut self, scene_bbox_accum: &BoundingBox) {
         self.interpolate_to_eye(default_eye(scene_bbox_accum, &self.space_specs).to_eye());
@@ -298,7 +420,10 @@ pub fn view_3d(
         }
     }
 
-    // Create labels now since their shapes participate are added to scene.ui for picking.
+    // Labels of 3D positions are drawn by re_renderer, picked on the gpu like everything else.
+    move_3d_labels_to_renderer(&mut scene, ui, highlights);
+
+    // Create the remaining labels now since their shapes are added to scene.ui for picking.
     let label_shapes = create_labels(
         &mut scene.ui,
         RectTransform::from_to(rect, rect),
@@ -308,11 +433,25 @@ pub fn view_3d(
         highlights,
     );
 
+    // Gpu picking results arrive a few frames after the picking rect was scheduled.
+    let picking_readback_identifier = egui::Id::new(space_view_id).value();
+    let gpu_picking_result = state.gpu_picking_result(
+        ctx,
+        ui.ctx(),
+        picking_readback_identifier,
+        response.hovered(),
+    );
+
     // TODO(andreas): We're very close making the hover reaction of ui2d and ui3d the same. Finish the job!
     // Check if we're hovering any hover primitive.
     if let Some(pointer_pos) = response.hover_pos() {
-        let picking_result =
-            scene.picking(glam::vec2(pointer_pos.x, pointer_pos.y), &rect, &eye, 5.0);
+        let picking_result = scene.picking(
+            glam::vec2(pointer_pos.x, pointer_pos.y),
+            &rect,
+            &eye,
+            PickingRectRequest::RADIUS_POINTS,
+            gpu_picking_result.as_deref(),
+        );
 
         for hit in picking_result.iter_hits() {
             let Some(instance_path) = hit.instance_path_hash.resolve(&ctx.log_db.entity_db)
@@ -344,11 +483,12 @@ pub fn view_3d(
                                 &ctx.current_query(),
                             );
 
-                            let tensor_view = ctx.cache.image.get_view_with_annotations(
-                                &image.tensor,
-                                &image.annotations,
-                                ctx.render_ctx,
-                            );
+                            let tensor_view =
+                                ctx.cache.gpu_resources.tensor_image_view_with_annotations(
+                                    &image.tensor,
+                                    &image.annotations,
+                                    ctx.render_ctx,
+                                );
 
                             if let [h, w, ..] = &image.tensor.shape[..] {
                                 ui.separator();
@@ -393,7 +533,14 @@ pub fn view_3d(
         project_onto_other_spaces(ctx, &scene.space_cameras, &mut state.state_3d, space);
     }
 
-    ctx.select_hovered_on_click(&response);
+    // Clicks place measurement points instead of selecting while a measurement tool is active.
+    let ui_from_world_3d = measurement::ui_from_world_3d(eye.ui_from_world(&rect));
+    if !state
+        .measurements
+        .handle_input(&response, state.state_3d.hovered_point, &ui_from_world_3d)
+    {
+        ctx.select_hovered_on_click(&response);
+    }
 
     // Double click changes camera
     if response.double_clicked() {
@@ -483,6 +630,16 @@ pub fn view_3d(
         }
     }
 
+    let colorbars = std::mem::take(&mut scene.ui.colorbars);
+
+    let picking_rect_request = response.hover_pos().map(|pointer_pos| {
+        PickingRectRequest::around_pointer(
+            pointer_pos,
+            rect,
+            ui.ctx().pixels_per_point(),
+            picking_readback_identifier,
+        )
+    });
     paint_view(
         ui,
         eye,
@@ -491,13 +648,26 @@ pub fn view_3d(
         ctx.render_ctx,
         &space.to_string(),
         state.auto_size_config(rect.size()),
+        state
+            .state_3d
+            .lighting
+            .lighting_config(&state.state_3d.space_specs, &state.scene_bbox_accum),
+        picking_rect_request,
     );
 
     // Add egui driven labels on top of re_renderer content.
     let painter = ui.painter().with_clip_rect(ui.max_rect());
     painter.extend(label_shapes);
+    painter.extend(
+        state
+            .measurements
+            .shapes(ui, state.state_3d.hovered_point, &ui_from_world_3d),
+    );
+
+    colorbars_overlay_ui(ui, rect, &colorbars);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn paint_view(
     ui: &mut egui::Ui,
     eye: Eye,
@@ -506,6 +676,8 @@ fn paint_view(
     render_ctx: &mut RenderContext,
     name: &str,
     auto_size_config: re_renderer::AutoSizeConfig,
+    lighting_config: re_renderer::LightingConfig,
+    picking_rect_request: Option<PickingRectRequest>,
 ) {
     crate::profile_function!();
 
@@ -528,13 +700,17 @@ fn paint_view(
 
         pixels_from_point,
         auto_size_config,
+
+        outline_config: scene.outline_config(pixels_from_point),
+        lighting_config,
     };
 
     let Ok(callback) = create_scene_paint_callback(
         render_ctx,
         target_config,
         rect,
-        scene.primitives, &ScreenBackground::GenericSkybox)
+        scene.primitives, &ScreenBackground::GenericSkybox,
+        picking_rect_request)
     else {
         return;
     };
@@ -1,8 +1,11 @@
-use egui::mutex::Mutex;
+use egui::{mutex::Mutex, NumExt as _};
 use re_renderer::{
-    renderer::{DepthCloudDrawData, GenericSkyboxDrawData, MeshDrawData, RectangleDrawData},
+    renderer::{
+        DepthCloudDrawData, GenericSkyboxDrawData, LabelDrawData, MeshDrawData, PointCloudDrawData,
+        RectangleDrawData, VolumeDrawData, VoxelGridDrawData,
+    },
     view_builder::{TargetConfiguration, ViewBuilder},
-    RenderContext,
+    GpuReadbackIdentifier, PickingLayerRect, RenderContext,
 };
 
 use super::scene::SceneSpatialPrimitives;
@@ -14,16 +17,57 @@ pub fn get_viewport(clip_rect: egui::Rect, pixels_from_point: f32) -> [u32; 2] {
     [resolution.x as u32, resolution.y as u32]
 }
 
+/// Rectangle of a view that should be picked on the gpu, see [`ViewBuilder::schedule_picking_rect`].
+pub struct PickingRectRequest {
+    pub rect: PickingLayerRect,
+    pub readback_identifier: GpuReadbackIdentifier,
+}
+
+impl PickingRectRequest {
+    /// Picking radius around the pointer in ui points.
+    pub const RADIUS_POINTS: f32 = 5.0;
+
+    /// Requests picking around the given pointer position.
+    ///
+    /// `viewport_rect` is the rect of the ui the view is drawn to.
+    pub fn around_pointer(
+        pointer_pos: egui::Pos2,
+        viewport_rect: egui::Rect,
+        pixels_from_point: f32,
+        readback_identifier: GpuReadbackIdentifier,
+    ) -> Self {
+        let pointer_in_pixel = (pointer_pos - viewport_rect.min) * pixels_from_point;
+        Self {
+            rect: PickingLayerRect::from_center_and_radius(
+                glam::ivec2(
+                    pointer_in_pixel.x.round() as i32,
+                    pointer_in_pixel.y.round() as i32,
+                ),
+                (Self::RADIUS_POINTS * pixels_from_point)
+                    .round()
+                    .at_least(1.0) as u32,
+            ),
+            readback_identifier,
+        }
+    }
+}
+
 pub fn create_scene_paint_callback(
     render_ctx: &mut RenderContext,
     target_config: TargetConfiguration,
     clip_rect: egui::Rect,
     primitives: SceneSpatialPrimitives,
     background: &ScreenBackground,
+    picking_rect_request: Option<PickingRectRequest>,
 ) -> anyhow::Result<egui::PaintCallback> {
     let pixels_from_point = target_config.pixels_from_point;
-    let (command_buffer, view_builder) =
-        create_and_fill_view_builder(render_ctx, target_config, primitives, background)?;
+    let (command_buffer, view_builder) = create_and_fill_view_builder(
+        render_ctx,
+        target_config,
+        primitives,
+        background,
+        picking_rect_request,
+    )?;
     Ok(renderer_paint_callback(
         render_ctx,
         command_buffer,
@@ -43,19 +87,49 @@ fn create_and_fill_view_builder(
     target_config: TargetConfiguration,
     primitives: SceneSpatialPrimitives,
     background: &ScreenBackground,
+    picking_rect_request: Option<PickingRectRequest>,
 ) -> anyhow::Result<(wgpu::CommandBuffer, ViewBuilder)> {
     let mut view_builder = ViewBuilder::default();
     view_builder.setup_view(render_ctx, target_config)?;
+    let point_cloud_lod_view = view_builder.point_cloud_lod_view()?;
+    let label_view = view_builder.label_view()?;
+
+    if let Some(PickingRectRequest {
+        rect,
+        readback_identifier,
+    }) = picking_rect_request
+    {
+        view_builder.schedule_picking_rect(render_ctx, rect, readback_identifier, ())?;
+    }
 
     view_builder
         .queue_draw(&DepthCloudDrawData::new(render_ctx, &primitives.depth_clouds).unwrap())
         .queue_draw(&MeshDrawData::new(render_ctx, &primitives.mesh_instances()).unwrap())
         .queue_draw(&primitives.line_strips.to_draw_data(render_ctx))
         .queue_draw(&primitives.points.to_draw_data(render_ctx)?)
+        .queue_draw(&PointCloudDrawData::new_static(
+            render_ctx,
+            &point_cloud_lod_view,
+            &primitives.static_point_cloud_instances(),
+        )?)
         .queue_draw(&RectangleDrawData::new(
             render_ctx,
             &primitives.textured_rectangles,
-        )?);
+        )?)
+        .queue_draw(&VolumeDrawData::new(render_ctx, &primitives.volumes)?)
+        .queue_draw(&VoxelGridDrawData::new(
+            render_ctx,
+            &primitives.voxel_grid_instances(),
+        )?)
+        .queue_draw(&LabelDrawData::new(
+            render_ctx,
+            &label_view,
+            &primitives.labels,
+        ));
+
+    for line_strips in &primitives.static_line_strips {
+        view_builder.queue_draw(&line_strips.draw_data);
+    }
 
     if matches!(background, ScreenBackground::GenericSkybox) {
         view_builder.queue_draw(&GenericSkyboxDrawData::new(render_ctx));
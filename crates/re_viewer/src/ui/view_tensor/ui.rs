@@ -4,13 +4,17 @@ use eframe::emath::Align2;
 use egui::{epaint::TextShape, Color32, ColorImage, NumExt as _, Vec2};
 use ndarray::{Axis, Ix2};
 
+use re_data_store::{ColorMap, ColorMapper, EntityPath};
 use re_log_types::{
     component_types::{self, Tensor, TensorTrait},
     TensorDataType,
 };
 use re_tensor_ops::dimension_mapping::{DimensionMapping, DimensionSelector};
 
-use crate::ui::data_ui::image::tensor_dtype_and_shape_ui_grid_contents;
+use crate::ui::{
+    color_mapper_ui, data_ui::image::tensor_dtype_and_shape_ui_grid_contents, Colorbar,
+    ResolvedColorMapper,
+};
 
 use super::dimension_mapping_ui;
 
@@ -88,6 +92,7 @@ pub(crate) fn view_tensor(
     ctx: &mut crate::misc::ViewerContext<'_>,
     ui: &mut egui::Ui,
     state: &mut ViewTensorState,
+    ent_path: &EntityPath,
     tensor: &Tensor,
 ) {
     crate::profile_function!();
@@ -117,20 +122,44 @@ pub(crate) fn view_tensor(
         });
     }
 
-    tensor_ui(ctx, ui, state, tensor);
+    tensor_ui(ctx, ui, state, ent_path, tensor);
 }
 
 fn tensor_ui(
     ctx: &mut crate::misc::ViewerContext<'_>,
     ui: &mut egui::Ui,
     state: &mut ViewTensorState,
+    ent_path: &EntityPath,
     tensor: &Tensor,
 ) {
     let tensor_shape = tensor.shape();
 
     let tensor_stats = ctx.cache.tensor_stats(tensor);
     let range = tensor_stats.range;
-    let color_mapping = &state.color_mapping;
+    let color_mapping = state.color_mapping.resolve(ctx, ent_path);
+
+    // We always use the full range for u8
+    let colorbar_range = match tensor.dtype() {
+        TensorDataType::U8 => Some((0.0, u8::MAX as f64)),
+        _ => range,
+    };
+    if let Some((min, max)) = colorbar_range {
+        egui::Frame {
+            inner_margin: egui::Margin::symmetric(16.0, 8.0),
+            ..Default::default()
+        }
+        .show(ui, |ui| {
+            Colorbar {
+                label: ent_path.to_string(),
+                color_mapper: color_mapping.color_mapper.clone(),
+                gamma: color_mapping.gamma,
+                range: min..=max,
+                unit: None,
+            }
+            .ui(ui);
+        });
+    }
+    let color_mapping = &color_mapping;
 
     match tensor.dtype() {
         TensorDataType::U8 => match ndarray::ArrayViewD::<u8>::try_from(tensor) {
@@ -347,71 +376,45 @@ fn tensor_ui(
 
 // ----------------------------------------------------------------------------
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
-enum ColorMap {
-    Greyscale,
-    Turbo,
-    Virdis,
-}
-
-impl std::fmt::Display for ColorMap {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(match self {
-            ColorMap::Greyscale => "Greyscale",
-            ColorMap::Turbo => "Turbo",
-            ColorMap::Virdis => "Viridis",
-        })
-    }
-}
-
 /// How we map values to colors.
 #[derive(Copy, Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
 struct ColorMapping {
-    map: ColorMap,
+    /// Not called `map` anymore, since that held the tensor view's own color map enum before.
+    /// Stored state from back then ignores it and falls back to the default.
+    color_mapper: ColorMapper,
     gamma: f32,
 }
 
 impl Default for ColorMapping {
     fn default() -> Self {
         Self {
-            map: ColorMap::Virdis,
+            color_mapper: ColorMapper::ColorMap(ColorMap::Viridis),
             gamma: 1.0,
         }
     }
 }
 
 impl ColorMapping {
-    pub fn color_from_normalized(&self, f: f32) -> Color32 {
-        let f = f.powf(self.gamma);
-
-        match self.map {
-            ColorMap::Greyscale => {
-                let lum = (f * 255.0 + 0.5) as u8;
-                Color32::from_gray(lum)
-            }
-            ColorMap::Turbo => {
-                let [r, g, b, _] = re_renderer::colormap_turbo_srgb(f);
-                Color32::from_rgb(r, g, b)
-            }
-            ColorMap::Virdis => {
-                let [r, g, b, _] = re_renderer::colormap_viridis_srgb(f);
-                Color32::from_rgb(r, g, b)
-            }
+    fn resolve(
+        &self,
+        ctx: &crate::misc::ViewerContext<'_>,
+        ent_path: &EntityPath,
+    ) -> ResolvedColorMapping {
+        ResolvedColorMapping {
+            color_mapper: ResolvedColorMapper::resolve(ctx, ent_path, self.color_mapper),
+            gamma: self.gamma,
         }
     }
 
     fn ui(&mut self, re_ui: &re_ui::ReUi, ui: &mut egui::Ui) {
-        let ColorMapping { map, gamma } = self;
+        let ColorMapping {
+            color_mapper,
+            gamma,
+        } = self;
 
         re_ui.grid_left_hand_label(ui, "Color map");
-        egui::ComboBox::from_id_source("color map select")
-            .selected_text(map.to_string())
-            .show_ui(ui, |ui| {
-                ui.style_mut().wrap = Some(false);
-                ui.selectable_value(map, ColorMap::Greyscale, ColorMap::Greyscale.to_string());
-                ui.selectable_value(map, ColorMap::Virdis, ColorMap::Virdis.to_string());
-                ui.selectable_value(map, ColorMap::Turbo, ColorMap::Turbo.to_string());
-            });
+        color_mapper_ui(ui, "color map select", color_mapper);
         ui.end_row();
 
         re_ui.grid_left_hand_label(ui, "Brightness");
@@ -422,6 +425,18 @@ impl ColorMapping {
     }
 }
 
+/// [`ColorMapping`] with the lookup table of the tensor's entity, if any.
+struct ResolvedColorMapping {
+    color_mapper: ResolvedColorMapper,
+    gamma: f32,
+}
+
+impl ResolvedColorMapping {
+    fn color_from_normalized(&self, f: f32) -> Color32 {
+        self.color_mapper.color_from_normalized(f.powf(self.gamma))
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// Should we scale the rendered texture, and if so, how?
@@ -1,7 +1,7 @@
 use re_arrow_store::TimeRange;
 use re_data_store::EntityPath;
 use re_log_types::{
-    component_types::{self, InstanceKey},
+    component_types::{self, InstanceKey, TextEntryField},
     msg_bundle::Component,
     MsgId,
 };
@@ -28,6 +28,8 @@ pub struct TextEntry {
     pub level: Option<String>,
 
     pub body: String,
+
+    pub fields: Vec<TextEntryField>,
 }
 
 /// A text scene, with everything needed to render it.
@@ -42,12 +44,19 @@ impl SceneText {
         &mut self,
         ctx: &ViewerContext<'_>,
         query: &SceneQuery<'_>,
-        filters: &ViewTextFilters,
+        filters: &mut ViewTextFilters,
     ) {
         crate::profile_function!();
 
         let store = &ctx.log_db.entity_db.data_store;
 
+        // An invalid search pattern is reported in the view itself: just show everything.
+        let search = filters
+            .search_regex()
+            .and_then(|regex| regex.as_ref().ok())
+            .cloned();
+        let filters = &*filters;
+
         for entity_path in query.entity_paths {
             let ent_path = entity_path;
 
@@ -78,12 +87,21 @@ impl SceneText {
                      text_entry: component_types::TextEntry,
                      msg_id: Option<MsgId>,
                      color: Option<component_types::ColorRGBA>| {
-                        let component_types::TextEntry { body, level } = text_entry;
+                        let component_types::TextEntry {
+                            body,
+                            level,
+                            fields,
+                        } = text_entry;
+                        let fields = fields.unwrap_or_default();
 
                         // Early filtering once more, see above.
                         let is_visible = level
                             .as_ref()
-                            .map_or(true, |lvl| filters.is_log_level_visible(lvl));
+                            .map_or(true, |lvl| filters.is_log_level_visible(lvl))
+                            && search.as_ref().map_or(true, |search| {
+                                search.is_match(&body)
+                                    || fields.iter().any(|field| search.is_match(&field.value))
+                            });
 
                         if is_visible {
                             self.text_entries.push(TextEntry {
@@ -93,6 +111,7 @@ impl SceneText {
                                 color: color.map(|c| c.to_array()),
                                 level,
                                 body,
+                                fields,
                             });
                         }
                     },
@@ -104,5 +123,9 @@ impl SceneText {
                 }
             }
         }
+
+        // Entries from different entities are interleaved: the view expects them in time order.
+        self.text_entries
+            .sort_by_key(|te| te.time.unwrap_or(i64::MIN));
     }
 }
@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 use egui::{Color32, RichText};
 
 use re_data_store::{EntityPath, Timeline};
-use re_log_types::TimePoint;
+use re_log_types::{component_types::TextEntryField, TimePoint};
 
 use crate::ViewerContext;
 
@@ -23,6 +23,11 @@ pub struct ViewTextState {
     pub filters: ViewTextFilters,
 
     monospace: bool,
+
+    /// Sort the rows by the value of this structured field, instead of by time.
+    sort_by_field: Option<String>,
+
+    sort_descending: bool,
 }
 
 impl ViewTextState {
@@ -33,8 +38,14 @@ impl ViewTextState {
             col_timelines,
             col_entity_path,
             col_log_level,
+            col_fields,
             row_entity_paths,
             row_log_levels,
+            row_min_level,
+            row_max_level,
+            row_search: _,
+            row_search_is_regex: _,
+            search_regex: _,
         } = &mut self.filters;
 
         re_ui
@@ -48,6 +59,9 @@ impl ViewTextState {
                     }
                     ui.checkbox(col_entity_path, "Entity path");
                     ui.checkbox(col_log_level, "Log level");
+                    for (key, visible) in col_fields {
+                        ui.checkbox(visible, key.as_str());
+                    }
                 });
                 ui.end_row();
 
@@ -67,6 +81,13 @@ impl ViewTextState {
                 });
                 ui.end_row();
 
+                re_ui.grid_left_hand_label(ui, "Level Range");
+                ui.vertical(|ui| {
+                    level_combo_box(ui, "text_min_level", "Min", row_min_level);
+                    level_combo_box(ui, "text_max_level", "Max", row_max_level);
+                });
+                ui.end_row();
+
                 re_ui.grid_left_hand_label(ui, "Text style");
                 ui.vertical(|ui| {
                     ui.radio_value(&mut self.monospace, false, "Proportional");
@@ -75,6 +96,18 @@ impl ViewTextState {
                 ui.end_row();
             });
     }
+
+    /// Cycles through sorting by `key` in ascending order, descending order, and not at all.
+    fn cycle_sort_by_field(&mut self, key: String) {
+        if self.sort_by_field.as_ref() != Some(&key) {
+            self.sort_by_field = Some(key);
+            self.sort_descending = false;
+        } else if !self.sort_descending {
+            self.sort_descending = true;
+        } else {
+            self.sort_by_field = None;
+        }
+    }
 }
 
 pub(crate) fn view_text(
@@ -88,6 +121,8 @@ pub(crate) fn view_text(
     // Update filters if necessary.
     state.filters.update(ctx, &scene.text_entries);
 
+    search_bar_ui(ctx, ui, state, scene);
+
     let time = ctx
         .rec_cfg
         .time_ctrl
@@ -97,8 +132,10 @@ pub(crate) fn view_text(
     // Did the time cursor move since last time?
     // - If it did, autoscroll to the text log to reveal the current time.
     // - Otherwise, let the user scroll around freely!
+    //
+    // Scrolling to the current time only makes sense when the rows are in time order.
     let time_cursor_moved = state.latest_time != time;
-    let scroll_to_row = time_cursor_moved.then(|| {
+    let scroll_to_row = (time_cursor_moved && state.sort_by_field.is_none()).then(|| {
         crate::profile_scope!("TextEntryState - search scroll time");
         scene
             .text_entries
@@ -118,19 +155,42 @@ pub(crate) fn view_text(
 
 // --- Filters ---
 
-// TODO(cmc): implement "body contains <value>" filter.
 // TODO(cmc): beyond filters, it'd be nice to be able to swap columns at some point.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
 pub struct ViewTextFilters {
     // Column filters: which columns should be visible?
     // Timelines are special: each one has a dedicated column.
     pub col_timelines: BTreeMap<Timeline, bool>,
     pub col_entity_path: bool,
     pub col_log_level: bool,
+    // So are structured fields, keyed by name.
+    pub col_fields: BTreeMap<String, bool>,
 
     // Row filters: which rows should be visible?
     pub row_entity_paths: BTreeMap<EntityPath, bool>,
     pub row_log_levels: BTreeMap<String, bool>,
+
+    // Inclusive severity range, see [`LOG_LEVELS`].
+    // Levels that aren't part of [`LOG_LEVELS`] are never filtered out by it.
+    pub row_min_level: Option<String>,
+    pub row_max_level: Option<String>,
+
+    // Only show rows whose body or fields match this (case-insensitive, unless it is a regex).
+    pub row_search: String,
+    pub row_search_is_regex: bool,
+
+    // `row_search` compiled, see `ViewTextFilters::search_regex`.
+    #[serde(skip)]
+    search_regex: Option<CompiledSearch>,
+}
+
+/// A search pattern along with what it was compiled from.
+#[derive(Debug, Clone)]
+struct CompiledSearch {
+    pattern: String,
+    is_regex: bool,
+    regex: Result<regex::Regex, regex::Error>,
 }
 
 impl Default for ViewTextFilters {
@@ -139,8 +199,14 @@ impl Default for ViewTextFilters {
             col_entity_path: true,
             col_log_level: true,
             col_timelines: Default::default(),
+            col_fields: Default::default(),
             row_entity_paths: Default::default(),
             row_log_levels: Default::default(),
+            row_min_level: None,
+            row_max_level: None,
+            row_search: Default::default(),
+            row_search_is_regex: false,
+            search_regex: None,
         }
     }
 }
@@ -154,7 +220,43 @@ impl ViewTextFilters {
     }
 
     pub fn is_log_level_visible(&self, level: &str) -> bool {
-        self.row_log_levels.get(level).copied().unwrap_or(true)
+        let is_in_range = level_severity(level).map_or(true, |severity| {
+            let min = self.row_min_level.as_deref().and_then(level_severity);
+            let max = self.row_max_level.as_deref().and_then(level_severity);
+            min.map_or(true, |min| min <= severity) && max.map_or(true, |max| severity <= max)
+        });
+
+        is_in_range && self.row_log_levels.get(level).copied().unwrap_or(true)
+    }
+
+    /// The compiled search filter, if there is one.
+    ///
+    /// Only recompiled when the search changes.
+    pub fn search_regex(&mut self) -> Option<&Result<regex::Regex, regex::Error>> {
+        if self.row_search.is_empty() {
+            return None;
+        }
+
+        let is_up_to_date = self.search_regex.as_ref().map_or(false, |search| {
+            search.pattern == self.row_search && search.is_regex == self.row_search_is_regex
+        });
+        if !is_up_to_date {
+            crate::profile_scope!("compile search regex");
+            let regex = if self.row_search_is_regex {
+                regex::Regex::new(&self.row_search)
+            } else {
+                regex::RegexBuilder::new(&regex::escape(&self.row_search))
+                    .case_insensitive(true)
+                    .build()
+            };
+            self.search_regex = Some(CompiledSearch {
+                pattern: self.row_search.clone(),
+                is_regex: self.row_search_is_regex,
+                regex,
+            });
+        }
+
+        self.search_regex.as_ref().map(|search| &search.regex)
     }
 
     // Checks whether new values are available for any of the filters, and updates everything
@@ -166,8 +268,14 @@ impl ViewTextFilters {
             col_timelines,
             col_entity_path: _,
             col_log_level: _,
+            col_fields,
             row_entity_paths,
             row_log_levels,
+            row_min_level: _,
+            row_max_level: _,
+            row_search: _,
+            row_search_is_regex: _,
+            search_regex: _,
         } = self;
 
         for timeline in ctx.log_db.timelines() {
@@ -181,9 +289,71 @@ impl ViewTextFilters {
         for level in text_entries.iter().filter_map(|te| te.level.as_ref()) {
             row_log_levels.entry(level.clone()).or_insert(true);
         }
+
+        for field in text_entries.iter().flat_map(|te| &te.fields) {
+            if !col_fields.contains_key(&field.key) {
+                col_fields.insert(field.key.clone(), true);
+            }
+        }
     }
 }
 
+// --- Search ---
+
+fn search_bar_ui(
+    ctx: &mut ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    state: &mut ViewTextState,
+    scene: &SceneText,
+) {
+    let filters = &mut state.filters;
+
+    ui.horizontal(|ui| {
+        ui.label("Search:");
+        ui.text_edit_singleline(&mut filters.row_search);
+        ui.checkbox(&mut filters.row_search_is_regex, "Regex");
+
+        if let Some(Err(err)) = filters.search_regex() {
+            ui.colored_label(ui.visuals().error_fg_color, "Invalid regex")
+                .on_hover_text(err.to_string());
+            return;
+        }
+
+        // All the entries in the scene already match the search, and are sorted by time.
+        let time_ctrl = &mut ctx.rec_cfg.time_ctrl;
+        let current_time = time_ctrl.time_i64();
+        let times = || scene.text_entries.iter().filter_map(|te| te.time);
+        let prev_time = current_time.and_then(|t| times().filter(|time| *time < t).last());
+        let next_time = times().find(|time| current_time.map_or(true, |t| t < *time));
+
+        let enabled = !filters.row_search.is_empty();
+        if ui
+            .add_enabled(enabled && prev_time.is_some(), egui::Button::new("⏴"))
+            .on_hover_text("Move the time cursor to the previous match")
+            .clicked()
+        {
+            if let Some(time) = prev_time {
+                time_ctrl.set_time(time);
+                time_ctrl.pause();
+            }
+        }
+        if ui
+            .add_enabled(enabled && next_time.is_some(), egui::Button::new("⏵"))
+            .on_hover_text("Move the time cursor to the next match")
+            .clicked()
+        {
+            if let Some(time) = next_time {
+                time_ctrl.set_time(time);
+                time_ctrl.pause();
+            }
+        }
+
+        if enabled {
+            ui.weak(format!("{} matches", scene.text_entries.len()));
+        }
+    });
+}
+
 // ---
 
 fn get_time_point(ctx: &ViewerContext<'_>, entry: &TextEntry) -> Option<TimePoint> {
@@ -219,8 +389,38 @@ fn table_ui(
 
     use egui_extras::Column;
 
+    let fields = state
+        .filters
+        .col_fields
+        .iter()
+        .filter_map(|(key, visible)| visible.then(|| key.clone()))
+        .collect::<Vec<_>>();
+
+    // Row order, if it isn't simply the time order.
+    let sorted_rows = state.sort_by_field.as_ref().map(|key| {
+        let mut rows = (0..text_entries.len()).collect::<Vec<_>>();
+        // Stable, so that rows with equal values stay in time order in either direction.
+        rows.sort_by(|a, b| {
+            let a = TextEntryField::find(&text_entries[*a].fields, key);
+            let b = TextEntryField::find(&text_entries[*b].fields, key);
+            let ordering = compare_field_values(a, b);
+            if state.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        rows
+    });
+    let row_index = |index: usize| sorted_rows.as_ref().map_or(index, |rows| rows[index]);
+
     let global_timeline = *ctx.rec_cfg.time_ctrl.timeline();
-    let global_time = ctx.rec_cfg.time_ctrl.time_int();
+    // The current time indicator only makes sense when the rows are in time order.
+    let global_time = if sorted_rows.is_none() {
+        ctx.rec_cfg.time_ctrl.time_int()
+    } else {
+        None
+    };
 
     let mut table_builder = egui_extras::TableBuilder::new(ui)
         .resizable(true)
@@ -236,6 +436,7 @@ fn table_ui(
 
     let mut body_clip_rect = None;
     let mut current_time_y = None; // where to draw the current time indicator cursor
+    let mut clicked_field = None;
 
     {
         // timeline(s)
@@ -250,6 +451,9 @@ fn table_ui(
         if state.filters.col_log_level {
             table_builder = table_builder.column(Column::auto().at_least(30.0));
         }
+        // field(s)
+        table_builder =
+            table_builder.columns(Column::auto().clip(true).at_least(32.0), fields.len());
         // body
         table_builder = table_builder.column(Column::remainder().at_least(100.0));
     }
@@ -271,6 +475,13 @@ fn table_ui(
                     ui.strong("Level");
                 });
             }
+            for key in &fields {
+                header.col(|ui| {
+                    if field_header_ui(ui, state, key) {
+                        clicked_field = Some(key.clone());
+                    }
+                });
+            }
             header.col(|ui| {
                 ui.strong("Body");
             });
@@ -280,9 +491,10 @@ fn table_ui(
 
             body_clip_rect = Some(body.max_rect());
 
-            let row_heights = text_entries.iter().map(calc_row_height);
+            let row_heights = (0..text_entries.len())
+                .map(|index| calc_row_height(&text_entries[row_index(index)]));
             body.heterogeneous_rows(row_heights, |index, mut row| {
-                let text_entry = &text_entries[index];
+                let text_entry = &text_entries[row_index(index)];
 
                 // NOTE: `try_from_props` is where we actually fetch data from the underlying
                 // store, which is a costly operation.
@@ -341,6 +553,13 @@ fn table_ui(
                     });
                 }
 
+                // field(s)
+                for key in &fields {
+                    row.col(|ui| {
+                        ui.label(TextEntryField::find(&text_entry.fields, key).unwrap_or("-"));
+                    });
+                }
+
                 // body
                 row.col(|ui| {
                     let mut text = egui::RichText::new(&text_entry.body);
@@ -357,6 +576,10 @@ fn table_ui(
             });
         });
 
+    if let Some(key) = clicked_field {
+        state.cycle_sort_by_field(key);
+    }
+
     // TODO(cmc): this draws on top of the headers :(
     if let (Some(body_clip_rect), Some(current_time_y)) = (body_clip_rect, current_time_y) {
         // Show that the current time is here:
@@ -368,6 +591,33 @@ fn table_ui(
     }
 }
 
+/// Returns true if the header was clicked.
+fn field_header_ui(ui: &mut egui::Ui, state: &ViewTextState, key: &str) -> bool {
+    let text = match (
+        state.sort_by_field.as_deref() == Some(key),
+        state.sort_descending,
+    ) {
+        (false, _) => key.to_owned(),
+        (true, false) => format!("{key} ⏶"),
+        (true, true) => format!("{key} ⏷"),
+    };
+
+    ui.add(egui::Label::new(RichText::new(text).strong()).sense(egui::Sense::click()))
+        .on_hover_text("Click to sort by this field")
+        .clicked()
+}
+
+/// Numbers are compared as such, everything else as text. Missing values come first.
+fn compare_field_values(a: Option<&str>, b: Option<&str>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.total_cmp(&b),
+            _ => a.cmp(b),
+        },
+        _ => a.cmp(&b),
+    }
+}
+
 fn calc_row_height(entry: &TextEntry) -> f32 {
     // Simple, fast, ugly, and functional
     let num_newlines = entry.body.bytes().filter(|&c| c == b'\n').count();
@@ -375,6 +625,32 @@ fn calc_row_height(entry: &TextEntry) -> f32 {
     num_rows as f32 * re_ui::ReUi::table_line_height()
 }
 
+/// The well-known log levels, from least to most severe.
+pub const LOG_LEVELS: [&str; 6] = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR", "CRITICAL"];
+
+/// The position of the level in [`LOG_LEVELS`], if it is a well-known one.
+fn level_severity(lvl: &str) -> Option<usize> {
+    LOG_LEVELS.iter().position(|known| *known == lvl)
+}
+
+fn level_combo_box(ui: &mut egui::Ui, id_source: &str, label: &str, level: &mut Option<String>) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        egui::ComboBox::from_id_source(id_source)
+            .selected_text(level.as_deref().unwrap_or("Any"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(level, None, "Any");
+                for known in LOG_LEVELS {
+                    ui.selectable_value(
+                        level,
+                        Some(known.to_owned()),
+                        level_to_rich_text(ui, known),
+                    );
+                }
+            });
+    });
+}
+
 pub fn level_to_rich_text(ui: &egui::Ui, lvl: &str) -> RichText {
     match lvl {
         "CRITICAL" => RichText::new(lvl)
@@ -0,0 +1,309 @@
+//! Reduces the number of points of a time series down to roughly one per pixel column.
+//!
+//! Plotting millions of points that mostly end up on top of each other is both slow and
+//! unreadable, so instead we bucket them by time while loading them and only plot a summary
+//! of each bucket.
+
+use std::collections::BTreeMap;
+
+use re_arrow_store::{TimeInt, TimeRange};
+use re_log_types::Timeline;
+
+/// How to aggregate the points of a time series that fall into the same pixel column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum TimeSeriesAggregation {
+    /// Plot every single point, no matter how many end up on the same pixel.
+    Off,
+
+    /// Keep the lowest and highest point of each pixel column.
+    ///
+    /// Looks the same as plotting every point, at a fraction of the cost.
+    #[default]
+    MinMax,
+
+    /// Plot the average of each pixel column.
+    Average,
+
+    /// Plot the average of each pixel column, with a band covering its lowest and highest value.
+    MinMaxBand,
+}
+
+impl TimeSeriesAggregation {
+    pub const ALL: [Self; 4] = [Self::Off, Self::MinMax, Self::Average, Self::MinMaxBand];
+}
+
+impl std::fmt::Display for TimeSeriesAggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TimeSeriesAggregation::Off => "Off",
+            TimeSeriesAggregation::MinMax => "Min/max",
+            TimeSeriesAggregation::Average => "Average",
+            TimeSeriesAggregation::MinMaxBand => "Average with min/max band",
+        })
+    }
+}
+
+/// The part of the time axis a plot showed in the previous frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VisibleTimeRange {
+    pub timeline: Timeline,
+    pub min: i64,
+    pub max: i64,
+
+    /// How much time a single ui point covers, i.e. the width of an aggregation bucket.
+    pub time_per_point: f64,
+}
+
+/// The time ranges that need to be loaded to plot a series with the given `times` in `visible`.
+///
+/// On top of everything within the visible range, this includes the closest point on either
+/// side of it so that lines still reach the edges of the plot, as well as the very first and
+/// last points so that the bounds of the series remain the same (which is what auto-bounds
+/// are based on).
+pub fn time_ranges_to_load(
+    times: &BTreeMap<TimeInt, usize>,
+    visible: &VisibleTimeRange,
+) -> Vec<TimeRange> {
+    let (Some(first), Some(last)) = (times.keys().next(), times.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    let visible_min = TimeInt::from(visible.min);
+    let visible_max = TimeInt::from(visible.max);
+    let min = times
+        .range(..visible_min)
+        .next_back()
+        .map_or(visible_min, |(time, _)| *time);
+    let max = times
+        .range(visible_max..)
+        .find(|(time, _)| visible_max < **time)
+        .map_or(visible_max, |(time, _)| *time);
+
+    let mut ranges = Vec::with_capacity(3);
+    if *first < min {
+        ranges.push(TimeRange::point(*first));
+    }
+    if min <= max {
+        ranges.push(TimeRange::new(min, max));
+    }
+    if max < *last {
+        ranges.push(TimeRange::point(*last));
+    }
+    ranges
+}
+
+/// Summary of all the points that fall into a single bucket.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bucket {
+    /// Average time of all the points.
+    pub time: i64,
+
+    /// Average value of all the points.
+    pub mean: f64,
+
+    /// The point with the lowest value.
+    pub min: (i64, f64),
+
+    /// The point with the highest value.
+    pub max: (i64, f64),
+}
+
+impl Bucket {
+    /// The lowest and highest points, in time order.
+    pub fn min_max_points(&self) -> impl Iterator<Item = (i64, f64)> {
+        let (first, second) = if self.min.0 <= self.max.0 {
+            (self.min, self.max)
+        } else {
+            (self.max, self.min)
+        };
+        std::iter::once(first).chain((first != second).then_some(second))
+    }
+}
+
+/// Accumulates the points of the current bucket.
+struct BucketAccumulator {
+    index: i64,
+    first_time: i64,
+    count: u32,
+
+    /// Sum of the times relative to `first_time`, which keeps it from overflowing.
+    time_offset_sum: i128,
+    value_sum: f64,
+    min: (i64, f64),
+    max: (i64, f64),
+}
+
+impl BucketAccumulator {
+    fn new(index: i64, time: i64, value: f64) -> Self {
+        Self {
+            index,
+            first_time: time,
+            count: 1,
+            time_offset_sum: 0,
+            value_sum: value,
+            min: (time, value),
+            max: (time, value),
+        }
+    }
+
+    fn add(&mut self, time: i64, value: f64) {
+        self.count += 1;
+        self.time_offset_sum += (time - self.first_time) as i128;
+        self.value_sum += value;
+        if value < self.min.1 {
+            self.min = (time, value);
+        }
+        if self.max.1 < value {
+            self.max = (time, value);
+        }
+    }
+
+    fn bucket(&self) -> Bucket {
+        Bucket {
+            time: self.first_time + (self.time_offset_sum / self.count as i128) as i64,
+            mean: self.value_sum / self.count as f64,
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+/// Aggregated points of a series, see [`Aggregator`].
+#[derive(Clone, Debug, Default)]
+pub struct AggregatedPoints {
+    pub points: Vec<(i64, f64)>,
+
+    /// Time, lowest and highest value of every bucket, only used by [`TimeSeriesAggregation::MinMaxBand`].
+    pub min_max_band: Vec<(i64, f64, f64)>,
+}
+
+/// Aggregates points into buckets of a fixed width in time as they are loaded.
+///
+/// Points need to be added in time order.
+pub struct Aggregator {
+    aggregation: TimeSeriesAggregation,
+    bucket_width: i64,
+    current: Option<BucketAccumulator>,
+}
+
+impl Aggregator {
+    pub fn new(aggregation: TimeSeriesAggregation, time_per_point: f64) -> Self {
+        let aggregation = if time_per_point.is_finite() && 0.0 < time_per_point {
+            aggregation
+        } else {
+            TimeSeriesAggregation::Off
+        };
+        Self {
+            aggregation,
+            bucket_width: (time_per_point.ceil() as i64).max(1),
+            current: None,
+        }
+    }
+
+    pub fn add(&mut self, time: i64, value: f64, out: &mut AggregatedPoints) {
+        if self.aggregation == TimeSeriesAggregation::Off {
+            out.points.push((time, value));
+            return;
+        }
+
+        let index = time.div_euclid(self.bucket_width);
+        match &mut self.current {
+            Some(current) if current.index == index => current.add(time, value),
+            _ => {
+                self.flush(out);
+                self.current = Some(BucketAccumulator::new(index, time, value));
+            }
+        }
+    }
+
+    /// Outputs the bucket that is still being accumulated, if any.
+    pub fn flush(&mut self, out: &mut AggregatedPoints) {
+        let Some(bucket) = self.current.take().map(|current| current.bucket()) else {
+            return;
+        };
+
+        match self.aggregation {
+            TimeSeriesAggregation::Off => {}
+            TimeSeriesAggregation::MinMax => out.points.extend(bucket.min_max_points()),
+            TimeSeriesAggregation::Average => out.points.push((bucket.time, bucket.mean)),
+            TimeSeriesAggregation::MinMaxBand => {
+                out.points.push((bucket.time, bucket.mean));
+                out.min_max_band
+                    .push((bucket.time, bucket.min.1, bucket.max.1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aggregate(aggregation: TimeSeriesAggregation, points: &[(i64, f64)]) -> AggregatedPoints {
+        let mut aggregator = Aggregator::new(aggregation, 10.0);
+        let mut out = AggregatedPoints::default();
+        for &(time, value) in points {
+            aggregator.add(time, value, &mut out);
+        }
+        aggregator.flush(&mut out);
+        out
+    }
+
+    #[test]
+    fn aggregate_min_max() {
+        let points = [(0, 1.0), (5, 3.0), (9, -1.0), (10, 2.0), (35, 0.0)];
+
+        let min_max = aggregate(TimeSeriesAggregation::MinMax, &points);
+        assert_eq!(
+            min_max.points,
+            vec![(5, 3.0), (9, -1.0), (10, 2.0), (35, 0.0)]
+        );
+        assert!(min_max.min_max_band.is_empty());
+
+        let average = aggregate(TimeSeriesAggregation::Average, &points);
+        assert_eq!(average.points, vec![(4, 1.0), (10, 2.0), (35, 0.0)]);
+
+        let band = aggregate(TimeSeriesAggregation::MinMaxBand, &points);
+        assert_eq!(band.points, average.points);
+        assert_eq!(
+            band.min_max_band,
+            vec![(4, -1.0, 3.0), (10, 2.0, 2.0), (35, 0.0, 0.0)]
+        );
+
+        assert_eq!(
+            aggregate(TimeSeriesAggregation::Off, &points).points,
+            points.to_vec()
+        );
+    }
+
+    #[test]
+    fn aggregate_large_times() {
+        let points = [(i64::MAX - 2, 1.0), (i64::MAX - 1, 3.0)];
+        let average = aggregate(TimeSeriesAggregation::Average, &points);
+        assert_eq!(average.points, vec![(i64::MAX - 2, 2.0)]);
+    }
+
+    #[test]
+    fn ranges_keep_neighbors_and_extremes() {
+        let times = (0..10)
+            .map(|i| (TimeInt::from(i * 10), 1))
+            .collect::<BTreeMap<_, _>>();
+        let visible = |min, max| VisibleTimeRange {
+            timeline: Timeline::new_sequence("frame"),
+            min,
+            max,
+            time_per_point: 1.0,
+        };
+        let ranges = |min, max| {
+            time_ranges_to_load(&times, &visible(min, max))
+                .into_iter()
+                .map(|range| (range.min.as_i64(), range.max.as_i64()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ranges(45, 55), vec![(0, 0), (40, 60), (90, 90)]);
+        assert_eq!(ranges(-10, 200), vec![(-10, 200)]);
+        assert_eq!(ranges(0, 90), vec![(0, 90)]);
+        assert_eq!(ranges(200, 300), vec![(0, 0), (90, 300)]);
+    }
+}
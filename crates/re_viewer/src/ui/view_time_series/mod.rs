@@ -1,3 +1,4 @@
+mod aggregation;
 mod scene;
 pub(crate) use self::scene::SceneTimeSeries;
 
@@ -3,12 +3,18 @@ use crate::{
     ViewerContext,
 };
 use re_arrow_store::TimeRange;
+use re_data_store::EntityPath;
 use re_log_types::{
     component_types::{self, InstanceKey},
     msg_bundle::Component,
 };
 use re_query::{range_entity_with_primary, QueryError};
 
+use super::{
+    aggregation::{time_ranges_to_load, AggregatedPoints, Aggregator, TimeSeriesAggregation},
+    ViewTimeSeriesState,
+};
+
 // ---
 
 #[derive(Clone, Debug)]
@@ -36,13 +42,6 @@ impl PartialEq for PlotPointAttrs {
 
 impl Eq for PlotPointAttrs {}
 
-#[derive(Clone, Debug)]
-struct PlotPoint {
-    time: i64,
-    value: f64,
-    attrs: PlotPointAttrs,
-}
-
 #[derive(Clone, Copy, Debug)]
 pub enum PlotSeriesKind {
     Continuous,
@@ -51,11 +50,15 @@ pub enum PlotSeriesKind {
 
 #[derive(Clone, Debug)]
 pub struct PlotSeries {
+    pub ent_path: EntityPath,
     pub label: String,
     pub color: egui::Color32,
     pub width: f32,
     pub kind: PlotSeriesKind,
     pub points: Vec<(i64, f64)>,
+
+    /// Time, lowest and highest value of every aggregated bucket, see [`TimeSeriesAggregation::MinMaxBand`].
+    pub min_max_band: Vec<(i64, f64, f64)>,
 }
 
 /// A scene for a time series plot, with everything needed to render it.
@@ -67,31 +70,62 @@ pub struct SceneTimeSeries {
 
 impl SceneTimeSeries {
     /// Loads all plots into the scene according to the given query.
-    pub(crate) fn load(&mut self, ctx: &mut ViewerContext<'_>, query: &SceneQuery<'_>) {
+    ///
+    /// If the plot was shown before, only what is visible is loaded and aggregated according
+    /// to `state`.
+    pub(crate) fn load(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        state: &ViewTimeSeriesState,
+    ) {
         crate::profile_function!();
 
         self.annotation_map.load(ctx, query);
 
-        self.load_scalars(ctx, query);
+        self.load_scalars(ctx, query, state);
     }
 
-    fn load_scalars(&mut self, ctx: &mut ViewerContext<'_>, query: &SceneQuery<'_>) {
+    fn load_scalars(
+        &mut self,
+        ctx: &mut ViewerContext<'_>,
+        query: &SceneQuery<'_>,
+        state: &ViewTimeSeriesState,
+    ) {
         crate::profile_function!();
 
         let store = &ctx.log_db.entity_db.data_store;
+        let tree = &ctx.log_db.entity_db.tree;
+
+        let visible = state
+            .visible_time_range
+            .filter(|visible| visible.timeline == query.timeline)
+            .filter(|_| state.aggregation != TimeSeriesAggregation::Off);
 
         for entity_path in query.entity_paths {
             let ent_path = entity_path;
 
-            let mut points = Vec::new();
             let annotations = self.annotation_map.find(ent_path);
             let annotation_info = annotations.class_description(None).annotation_info();
             let default_color = DefaultColor::EntityPath(ent_path);
 
-            let query = re_arrow_store::RangeQuery::new(
-                query.timeline,
-                TimeRange::new(i64::MIN.into(), i64::MAX.into()),
+            let time_ranges = if let Some(visible) = &visible {
+                tree.subtree(ent_path)
+                    .and_then(|tree| tree.components.get(&component_types::Scalar::name()))
+                    .and_then(|stats| stats.times.get(&query.timeline))
+                    .map_or_else(Vec::new, |times| time_ranges_to_load(times, visible))
+            } else {
+                vec![TimeRange::new(i64::MIN.into(), i64::MAX.into())]
+            };
+
+            let mut aggregator = Aggregator::new(
+                state.aggregation,
+                visible.map_or(0.0, |visible| visible.time_per_point),
             );
+            let mut segments = Vec::<(PlotPointAttrs, AggregatedPoints)>::new();
+
+            // `None` before the first point, `Some(None)` once points don't share the same label.
+            let mut common_label: Option<Option<String>> = None;
 
             let components = [
                 InstanceKey::name(),
@@ -101,130 +135,137 @@ impl SceneTimeSeries {
                 component_types::Radius::name(),
                 component_types::Label::name(),
             ];
-            let ent_views = range_entity_with_primary::<component_types::Scalar, 6>(
-                store, &query, ent_path, components,
-            );
 
-            for (time, ent_view) in ent_views {
-                match ent_view.visit5(
-                    |_instance,
-                     scalar: component_types::Scalar,
-                     props: Option<component_types::ScalarPlotProps>,
-                     color: Option<component_types::ColorRGBA>,
-                     radius: Option<component_types::Radius>,
-                     label: Option<component_types::Label>| {
-                        // TODO(andreas): Support entity path
-                        let color = annotation_info
-                            .color(color.map(|c| c.to_array()).as_ref(), default_color);
-                        let label = annotation_info.label(label.map(|l| l.into()).as_ref());
-
-                        const DEFAULT_RADIUS: f32 = 0.75;
-
-                        points.push(PlotPoint {
-                            time: time.unwrap().as_i64(), // scalars cannot be timeless
-                            value: scalar.into(),
-                            attrs: PlotPointAttrs {
+            for time_range in time_ranges {
+                let range_query = re_arrow_store::RangeQuery::new(query.timeline, time_range);
+                let ent_views = range_entity_with_primary::<component_types::Scalar, 6>(
+                    store,
+                    &range_query,
+                    ent_path,
+                    components,
+                );
+
+                for (time, ent_view) in ent_views {
+                    let time = time.unwrap(); // scalars cannot be timeless
+
+                    // Range queries start with the latest state before their range, which is
+                    // either covered by the previous range or culled on purpose.
+                    if time < time_range.min {
+                        continue;
+                    }
+
+                    match ent_view.visit5(
+                        |_instance,
+                         scalar: component_types::Scalar,
+                         props: Option<component_types::ScalarPlotProps>,
+                         color: Option<component_types::ColorRGBA>,
+                         radius: Option<component_types::Radius>,
+                         label: Option<component_types::Label>| {
+                            // TODO(andreas): Support entity path
+                            let color = annotation_info
+                                .color(color.map(|c| c.to_array()).as_ref(), default_color);
+                            let label = annotation_info.label(label.map(|l| l.into()).as_ref());
+
+                            const DEFAULT_RADIUS: f32 = 0.75;
+
+                            let attrs = PlotPointAttrs {
                                 label,
                                 color,
                                 radius: radius.map_or(DEFAULT_RADIUS, |r| r.0),
                                 scattered: props.map_or(false, |props| props.scattered),
-                            },
-                        });
-                    },
-                ) {
-                    Ok(_) | Err(QueryError::PrimaryNotFound) => {}
-                    Err(err) => {
-                        re_log::error_once!("Unexpected error querying {ent_path:?}: {err}");
+                            };
+
+                            match &common_label {
+                                None => common_label = Some(attrs.label.clone()),
+                                Some(common) if *common != attrs.label => {
+                                    common_label = Some(None);
+                                }
+                                Some(_) => {}
+                            }
+
+                            add_point(
+                                &mut segments,
+                                &mut aggregator,
+                                time.as_i64(),
+                                scalar.into(),
+                                attrs,
+                            );
+                        },
+                    ) {
+                        Ok(_) | Err(QueryError::PrimaryNotFound) => {}
+                        Err(err) => {
+                            re_log::error_once!("Unexpected error querying {ent_path:?}: {err}");
+                        }
                     }
                 }
             }
 
-            points.sort_by_key(|s| s.time);
-
-            if points.is_empty() {
-                continue;
+            if let Some((_, points)) = segments.last_mut() {
+                aggregator.flush(points);
             }
 
             // If all points within a line share the label (and it isn't `None`), then we use it
             // as the whole line label for the plot legend.
             // Otherwise, we just use the entity path as-is.
-            let same_label = |points: &[PlotPoint]| {
-                let label = points[0].attrs.label.as_ref();
-                (label.is_some() && points.iter().all(|p| p.attrs.label.as_ref() == label))
-                    .then(|| label.cloned().unwrap())
-            };
-            let line_label = same_label(&points).unwrap_or_else(|| entity_path.to_string());
-
-            self.add_line_segments(&line_label, points);
+            let line_label = common_label
+                .flatten()
+                .unwrap_or_else(|| entity_path.to_string());
+
+            self.lines
+                .extend(segments.into_iter().map(|(attrs, points)| PlotSeries {
+                    ent_path: ent_path.clone(),
+                    label: line_label.clone(),
+                    color: attrs.color,
+                    width: 2.0 * attrs.radius,
+                    kind: if attrs.scattered {
+                        PlotSeriesKind::Scatter
+                    } else {
+                        PlotSeriesKind::Continuous
+                    },
+                    points: points.points,
+                    min_max_band: points.min_max_band,
+                }));
         }
     }
+}
 
-    // We have a bunch of raw points, and now we need to group them into actual line
-    // segments.
-    // A line segment is a continuous run of points with identical attributes: each time
-    // we notice a change in attributes, we need a new line segment.
-    fn add_line_segments(&mut self, line_label: &str, points: Vec<PlotPoint>) {
-        crate::profile_function!();
-
-        let num_points = points.len();
-        let mut attrs = points[0].attrs.clone();
-        let mut line: PlotSeries = PlotSeries {
-            label: line_label.to_owned(),
-            color: attrs.color,
-            width: 2.0 * attrs.radius,
-            kind: if attrs.scattered {
-                PlotSeriesKind::Scatter
-            } else {
-                PlotSeriesKind::Continuous
-            },
-            points: Vec::with_capacity(num_points),
-        };
-
-        for (i, p) in points.into_iter().enumerate() {
-            if p.attrs == attrs {
-                // Same attributes, just add to the current line segment.
-
-                line.points.push((p.time, p.value));
-            } else {
-                // Attributes changed since last point, break up the current run into a
-                // line segment, and start the next one.
-
-                attrs = p.attrs.clone();
-                let kind = if attrs.scattered {
-                    PlotSeriesKind::Scatter
-                } else {
-                    PlotSeriesKind::Continuous
-                };
-
-                let prev_line = std::mem::replace(
-                    &mut line,
-                    PlotSeries {
-                        label: line_label.to_owned(),
-                        color: attrs.color,
-                        width: 2.0 * attrs.radius,
-                        kind,
-                        points: Vec::with_capacity(num_points - i),
-                    },
-                );
-                let prev_point = *prev_line.points.last().unwrap();
-                self.lines.push(prev_line);
-
-                // If the previous point was continuous and the current point is continuous
-                // too, then we want the 2 segments to appear continuous even though they
-                // are actually split from a data standpoint.
-                let cur_continuous = matches!(kind, PlotSeriesKind::Continuous);
-                let prev_continuous = matches!(kind, PlotSeriesKind::Continuous);
-                if cur_continuous && prev_continuous {
-                    line.points.push(prev_point);
-                }
-
-                // Add the point that triggered the split to the new segment.
-                line.points.push((p.time, p.value));
-            }
+/// Adds a point to the line segment it belongs to.
+///
+/// A line segment is a continuous run of points with identical attributes: each time
+/// we notice a change in attributes, we need a new line segment.
+fn add_point(
+    segments: &mut Vec<(PlotPointAttrs, AggregatedPoints)>,
+    aggregator: &mut Aggregator,
+    time: i64,
+    value: f64,
+    attrs: PlotPointAttrs,
+) {
+    if let Some((prev_attrs, prev_points)) = segments.last_mut() {
+        if *prev_attrs == attrs {
+            // Same attributes, just add to the current line segment.
+            aggregator.add(time, value, prev_points);
+            return;
         }
 
-        if !line.points.is_empty() {
-            self.lines.push(line);
+        // Attributes changed since last point, break up the current run into a
+        // line segment, and start the next one.
+        aggregator.flush(prev_points);
+
+        // If the previous point was continuous and the current point is continuous
+        // too, then we want the 2 segments to appear continuous even though they
+        // are actually split from a data standpoint.
+        let prev_point = prev_points.points.last().copied();
+        let mut points = AggregatedPoints::default();
+        if !prev_attrs.scattered && !attrs.scattered {
+            points.points.extend(prev_point);
         }
+        segments.push((attrs, points));
+    } else {
+        segments.push((attrs, AggregatedPoints::default()));
+    }
+
+    // Add the point that triggered the split to the new segment.
+    if let Some((_, points)) = segments.last_mut() {
+        aggregator.add(time, value, points);
     }
 }
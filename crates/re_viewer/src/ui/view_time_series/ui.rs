@@ -1,16 +1,23 @@
+use std::collections::BTreeMap;
+
 use egui::{
-    plot::{Legend, Line, Plot, Points},
+    plot::{Legend, Line, LinkedAxisGroup, Plot, Points},
     Color32,
 };
 
-use re_arrow_store::TimeType;
+use re_data_store::EntityPath;
+use re_log_types::TimeUnits;
 
 use crate::{
-    misc::format_time::next_grid_tick_magnitude_ns, ui::view_time_series::scene::PlotSeriesKind,
+    misc::format_time::{format_time_units_compact, next_grid_tick_magnitude_ns},
+    ui::view_time_series::scene::{PlotSeries, PlotSeriesKind},
     ViewerContext,
 };
 
-use super::SceneTimeSeries;
+use super::{
+    aggregation::{TimeSeriesAggregation, VisibleTimeRange},
+    SceneTimeSeries,
+};
 
 // ---
 
@@ -20,23 +27,77 @@ pub(crate) const HELP_TEXT: &str = "Pan by dragging, or scroll (+ shift = horizo
     Reset view with double-click.\n\
     Right click to move the time cursor to the current position.";
 
-#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
-pub struct ViewTimeSeriesState;
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ViewTimeSeriesState {
+    /// How to reduce the points that end up on the same pixel column.
+    pub aggregation: TimeSeriesAggregation,
+
+    /// If true, every entity gets a plot and y-axis of its own, e.g. for series with different units.
+    ///
+    /// The time axes of all these plots are kept in sync.
+    pub separate_y_axes: bool,
+
+    #[serde(skip, default = "LinkedAxisGroup::x")]
+    linked_time_axis: LinkedAxisGroup,
+
+    /// What the plot showed in the previous frame, so that only that needs to be loaded.
+    #[serde(skip)]
+    pub(super) visible_time_range: Option<VisibleTimeRange>,
+}
+
+impl Default for ViewTimeSeriesState {
+    fn default() -> Self {
+        Self {
+            aggregation: TimeSeriesAggregation::default(),
+            separate_y_axes: false,
+            linked_time_axis: LinkedAxisGroup::x(),
+            visible_time_range: None,
+        }
+    }
+}
+
+impl ViewTimeSeriesState {
+    pub fn selection_ui(&mut self, re_ui: &re_ui::ReUi, ui: &mut egui::Ui) {
+        crate::profile_function!();
+
+        re_ui
+            .selection_grid(ui, "time_series_config")
+            .num_columns(2)
+            .show(ui, |ui| {
+                re_ui.grid_left_hand_label(ui, "Aggregation");
+                egui::ComboBox::from_id_source("time_series_aggregation")
+                    .selected_text(self.aggregation.to_string())
+                    .show_ui(ui, |ui| {
+                        for aggregation in TimeSeriesAggregation::ALL {
+                            ui.selectable_value(
+                                &mut self.aggregation,
+                                aggregation,
+                                aggregation.to_string(),
+                            );
+                        }
+                    });
+                ui.end_row();
+
+                re_ui.grid_left_hand_label(ui, "Y axes");
+                ui.vertical(|ui| {
+                    ui.radio_value(&mut self.separate_y_axes, false, "Shared");
+                    ui.radio_value(&mut self.separate_y_axes, true, "One per entity");
+                });
+                ui.end_row();
+            });
+    }
+}
 
 pub(crate) fn view_time_series(
     ctx: &mut ViewerContext<'_>,
     ui: &mut egui::Ui,
-    _state: &mut ViewTimeSeriesState,
+    state: &mut ViewTimeSeriesState,
     scene: &SceneTimeSeries,
 ) -> egui::Response {
     crate::profile_function!();
 
-    let time_ctrl = &ctx.rec_cfg.time_ctrl;
-    let current_time = time_ctrl.time_i64();
-    let time_type = time_ctrl.time_type();
-    let timeline = time_ctrl.timeline();
-
-    let timeline_name = timeline.name().to_string();
+    let time_units = ctx.rec_cfg.time_ctrl.time_units();
 
     // Compute the minimum time/X value for the entire plot…
     let min_time = scene
@@ -48,7 +109,7 @@ pub(crate) fn view_time_series(
 
     // …then use that as an offset to avoid nasty precision issues with
     // large times (nanos since epoch does not fit into an f64).
-    let time_offset = if timeline.typ() == TimeType::Time {
+    let time_offset = if matches!(time_units, TimeUnits::WallClock { .. }) {
         // In order to make the tick-marks on the time axis fall on whole days, hours, minutes etc,
         // we need to round to a whole day:
         round_ns_to_start_of_day(min_time)
@@ -56,28 +117,97 @@ pub(crate) fn view_time_series(
         min_time
     };
 
+    let mut lines_per_entity: BTreeMap<&EntityPath, Vec<&PlotSeries>> = Default::default();
+    for line in &scene.lines {
+        lines_per_entity
+            .entry(&line.ent_path)
+            .or_default()
+            .push(line);
+    }
+
+    if !state.separate_y_axes || lines_per_entity.len() <= 1 {
+        return time_series_plot(
+            ctx,
+            ui,
+            "plot",
+            &scene.lines.iter().collect::<Vec<_>>(),
+            time_offset,
+            None,
+            &mut state.visible_time_range,
+        );
+    }
+
+    let num_plots = lines_per_entity.len();
+    let height = (ui.available_height() - ui.spacing().item_spacing.y * (num_plots - 1) as f32)
+        / num_plots as f32;
+
+    ui.vertical(|ui| {
+        for (ent_path, lines) in lines_per_entity {
+            ui.allocate_ui(egui::vec2(ui.available_width(), height), |ui| {
+                time_series_plot(
+                    ctx,
+                    ui,
+                    ent_path,
+                    &lines,
+                    time_offset,
+                    Some(state.linked_time_axis.clone()),
+                    &mut state.visible_time_range,
+                );
+            });
+        }
+    })
+    .response
+}
+
+/// Shows a single plot with all the given `lines`, sharing the same y-axis.
+///
+/// Stores the part of the time axis that ended up being visible in `visible_time_range`.
+fn time_series_plot(
+    ctx: &mut ViewerContext<'_>,
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    lines: &[&PlotSeries],
+    time_offset: i64,
+    linked_time_axis: Option<LinkedAxisGroup>,
+    visible_time_range: &mut Option<VisibleTimeRange>,
+) -> egui::Response {
+    crate::profile_function!();
+
+    let time_ctrl = &ctx.rec_cfg.time_ctrl;
+    let current_time = time_ctrl.time_i64();
+    let time_units = time_ctrl.time_units();
+    let timeline = *time_ctrl.timeline();
+
+    let timeline_name = timeline.name().to_string();
+
     // use timeline_name as part of id, so that egui stores different pan/zoom for different timelines
-    let plot_id_src = ("plot", &timeline_name);
+    let plot_id_src = (id_source, &timeline_name);
 
     let mut plot = Plot::new(plot_id_src)
         .legend(Legend {
             position: egui::plot::Corner::RightBottom,
             ..Default::default()
         })
-        .x_axis_formatter(move |time, _| format_time(time_type, time as i64 + time_offset))
+        .x_axis_formatter(move |time, _| {
+            format_time_units_compact(time_units, time as i64 + time_offset)
+        })
         .label_formatter(move |name, value| {
             let name = if name.is_empty() { "y" } else { name };
             let is_integer = value.y.round() == value.y;
             let decimals = if is_integer { 0 } else { 5 };
             format!(
                 "{timeline_name}: {}\n{name}: {:.*}",
-                time_type.format((value.x as i64 + time_offset).into()),
+                time_units.format((value.x as i64 + time_offset).into()),
                 decimals,
                 value.y,
             )
         });
 
-    if timeline.typ() == TimeType::Time {
+    if let Some(linked_time_axis) = linked_time_axis {
+        plot = plot.link_axis(linked_time_axis);
+    }
+
+    if matches!(time_units, TimeUnits::WallClock { .. }) {
         let canvas_size = ui.available_size();
         plot = plot.x_grid_spacer(move |spacer| ns_grid_spacer(canvas_size, &spacer));
     }
@@ -95,7 +225,7 @@ pub(crate) fn view_time_series(
             ctx.rec_cfg.time_ctrl.pause();
         }
 
-        for line in &scene.lines {
+        for line in lines {
             let points = line
                 .points
                 .iter()
@@ -105,6 +235,28 @@ pub(crate) fn view_time_series(
             let c = line.color;
             let color = Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]);
 
+            if !line.min_max_band.is_empty() {
+                // `Polygon`s are filled as convex shapes, so the band is drawn as a single line
+                // zig-zagging between the lowest and highest value of each bucket instead.
+                // Buckets are a single ui point wide, so this covers the whole band.
+                let band = line
+                    .min_max_band
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &(time, min, max))| {
+                        let x = (time - time_offset) as f64;
+                        let (first, second) = if i % 2 == 0 { (min, max) } else { (max, min) };
+                        [[x, first], [x, second]]
+                    })
+                    .collect::<Vec<_>>();
+                plot_ui.line(
+                    Line::new(band)
+                        .name(&line.label)
+                        .color(color.gamma_multiply(0.25))
+                        .width(1.0),
+                );
+            }
+
             match line.kind {
                 PlotSeriesKind::Continuous => plot_ui.line(
                     Line::new(points)
@@ -121,6 +273,19 @@ pub(crate) fn view_time_series(
             }
         }
 
+        // The scene of the next frame only loads & aggregates what is visible in this one.
+        let bounds = plot_ui.plot_bounds();
+        *visible_time_range = bounds.is_valid_x().then(|| {
+            let left = plot_ui.screen_from_plot([bounds.min()[0], 0.0].into()).x;
+            let right = plot_ui.screen_from_plot([bounds.max()[0], 0.0].into()).x;
+            VisibleTimeRange {
+                timeline,
+                min: bounds.min()[0].floor() as i64 + time_offset,
+                max: bounds.max()[0].ceil() as i64 + time_offset,
+                time_per_point: (bounds.max()[0] - bounds.min()[0]) / (right - left) as f64,
+            }
+        });
+
         current_time.map(|current_time| {
             let time_x = (current_time - time_offset) as f64;
             plot_ui.screen_from_plot([time_x, 0.0].into()).x
@@ -141,15 +306,6 @@ pub(crate) fn view_time_series(
     response
 }
 
-fn format_time(time_type: TimeType, time_int: i64) -> String {
-    if time_type == TimeType::Time {
-        let time = re_log_types::Time::from_ns_since_epoch(time_int);
-        crate::misc::format_time::format_time_compact(time)
-    } else {
-        time_type.format(re_log_types::TimeInt::from(time_int))
-    }
-}
-
 fn ns_grid_spacer(
     canvas_size: egui::Vec2,
     input: &egui::plot::GridInput,
@@ -682,6 +682,8 @@ impl<'a, 'b> egui_dock::TabViewer for TabViewer<'a, 'b> {
 fn help_text_ui(ui: &mut egui::Ui, space_view: &SpaceView) {
     let help_text = match space_view.category {
         ViewCategory::TimeSeries => Some(crate::ui::view_time_series::HELP_TEXT),
+        ViewCategory::ScatterPlot => Some(crate::ui::view_scatter_plot::HELP_TEXT),
+        ViewCategory::Histogram => Some(crate::ui::view_histogram::HELP_TEXT),
         ViewCategory::BarChart => Some(crate::ui::view_bar_chart::HELP_TEXT),
         ViewCategory::Spatial => Some(space_view.view_state.state_spatial.help_text()),
         ViewCategory::Text | ViewCategory::Tensor => None,
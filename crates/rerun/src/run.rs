@@ -65,6 +65,14 @@ struct Args {
     #[clap(long)]
     memory_limit: Option<String>,
 
+    /// An upper limit on how much gpu memory the Rerun Viewer should use for keeping data on the gpu across frames.
+    ///
+    /// When over this limit, the least recently used gpu data is freed and uploaded again when needed.
+    ///
+    /// Default: `2GB`
+    #[clap(long)]
+    gpu_memory_limit: Option<String>,
+
     /// Set a maximum input latency, e.g. "200ms" or "10s".
     ///
     /// If we go over this, we start dropping packets.
@@ -85,6 +93,36 @@ enum Commands {
     #[cfg(all(feature = "analytics"))]
     #[command(subcommand)]
     Analytics(AnalyticsCommands),
+
+    /// Print the recording info of `.rrd` files, including their user-defined properties.
+    ///
+    /// Use `--property` to only list the recordings that match, e.g. to search an archive of
+    /// recordings: `rerun info --property robot_id=42 recordings/*.rrd`
+    Info {
+        /// The `.rrd` files to inspect.
+        paths: Vec<std::path::PathBuf>,
+
+        /// Only list the recordings with this property, as `key=value`. Can be repeated.
+        #[clap(long = "property")]
+        properties: Vec<String>,
+    },
+
+    /// Copy an `.rrd` file, keeping only the entities that match the given patterns.
+    ///
+    /// Example: `rerun filter --entity "/robot/*/camera/**" in.rrd out.rrd`
+    Filter {
+        /// The `.rrd` file to read.
+        input: std::path::PathBuf,
+
+        /// The `.rrd` file to write.
+        output: std::path::PathBuf,
+
+        /// Keep the entities matching this pattern, e.g. `/robot/*/points`. Can be repeated.
+        ///
+        /// `*` matches any single part of a path, and `**` any number of parts.
+        #[clap(long = "entity", required = true)]
+        patterns: Vec<re_log_types::EntityPathPattern>,
+    },
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -183,8 +221,12 @@ where
         match commands {
             #[cfg(all(feature = "analytics"))]
             Commands::Analytics(analytics) => run_analytics(analytics).map_err(Into::into),
-            #[cfg(not(all(feature = "analytics")))]
-            _ => Ok(()),
+            Commands::Info { paths, properties } => run_info(paths, properties),
+            Commands::Filter {
+                input,
+                output,
+                patterns,
+            } => run_filter(input, output, patterns),
         }
     } else {
         run_impl(build_info, call_source, args).await
@@ -224,6 +266,106 @@ fn run_analytics(cmd: &AnalyticsCommands) -> Result<(), re_analytics::cli::CliEr
     }
 }
 
+fn run_info(paths: &[std::path::PathBuf], properties: &[String]) -> anyhow::Result<()> {
+    let filters = properties
+        .iter()
+        .map(|property| {
+            property
+                .split_once('=')
+                .ok_or_else(|| anyhow::format_err!("Expected key=value, got {property:?}"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    for path in paths {
+        let infos = match recording_infos(path) {
+            Ok(infos) => infos,
+            Err(err) => {
+                re_log::warn!("Failed to read {path:?}: {err}");
+                continue;
+            }
+        };
+
+        for info in infos {
+            let is_match = filters
+                .iter()
+                .all(|(key, value)| info.properties.get(*key).map(String::as_str) == Some(*value));
+            if !is_match {
+                continue;
+            }
+
+            println!("{}", path.display());
+            println!("  application_id: {}", info.application_id);
+            println!("  recording_id: {}", info.recording_id);
+            println!("  started: {}", info.started.format());
+            println!("  recording_source: {}", info.recording_source);
+            for (key, value) in &info.properties {
+                println!("  {key}: {value}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The latest [`re_log_types::RecordingInfo`] of each recording in an `.rrd` file.
+fn recording_infos(path: &std::path::Path) -> anyhow::Result<Vec<re_log_types::RecordingInfo>> {
+    let file = std::fs::File::open(path).context("Failed to open file")?;
+    let decoder = re_log_types::encoding::Decoder::new(file)?;
+
+    let mut infos: Vec<re_log_types::RecordingInfo> = Vec::new();
+    for msg in decoder {
+        if let LogMsg::BeginRecordingMsg(msg) = msg? {
+            // The SDK sends an updated `RecordingInfo` when the properties change.
+            if let Some(info) = infos
+                .iter_mut()
+                .find(|info| info.recording_id == msg.info.recording_id)
+            {
+                *info = msg.info;
+            } else {
+                infos.push(msg.info);
+            }
+        }
+    }
+    Ok(infos)
+}
+
+fn run_filter(
+    input: &std::path::Path,
+    output: &std::path::Path,
+    patterns: &[re_log_types::EntityPathPattern],
+) -> anyhow::Result<()> {
+    let file = std::fs::File::open(input).with_context(|| format!("Failed to open {input:?}"))?;
+    let decoder = re_log_types::encoding::Decoder::new(file)?;
+
+    let file =
+        std::fs::File::create(output).with_context(|| format!("Failed to create {output:?}"))?;
+    let mut encoder = re_log_types::encoding::Encoder::new(file)?;
+
+    let (mut num_kept, mut num_dropped) = (0, 0);
+    for msg in decoder {
+        let msg = msg?;
+        let entity_path = match &msg {
+            LogMsg::BeginRecordingMsg(_) | LogMsg::Goodbye(_) => None,
+            LogMsg::EntityPathOpMsg(msg) => Some(msg.path_op.entity_path().clone()),
+            LogMsg::ArrowMsg(msg) => Some(msg.entity_path()?),
+        };
+
+        let keep = entity_path.map_or(true, |entity_path| {
+            patterns.iter().any(|pattern| pattern.matches(&entity_path))
+        });
+        if keep {
+            encoder.append(&msg)?;
+            num_kept += 1;
+        } else {
+            num_dropped += 1;
+        }
+    }
+    encoder.finish()?;
+
+    re_log::info!("Wrote {num_kept} messages to {output:?}, dropped {num_dropped}");
+    Ok(())
+}
+
 #[cfg(feature = "native_viewer")]
 fn profiler(args: &Args) -> re_viewer::Profiler {
     let mut profiler = re_viewer::Profiler::default();
@@ -247,6 +389,13 @@ async fn run_impl(
             re_memory::MemoryLimit::parse(l)
                 .unwrap_or_else(|err| panic!("Bad --memory-limit: {err}"))
         }),
+        gpu_memory_limit: args
+            .gpu_memory_limit
+            .as_ref()
+            .map_or(Default::default(), |l| {
+                re_memory::MemoryLimit::parse(l)
+                    .unwrap_or_else(|err| panic!("Bad --gpu-memory-limit: {err}"))
+            }),
     };
 
     // Where do we get the data from?
@@ -289,7 +438,7 @@ async fn run_impl(
                 // `rerun.spawn()` doesn't need to log that a connection has been made
                 quiet: call_source.is_python(),
             };
-            re_sdk_comms::serve(args.port, server_options)?
+            re_sdk_comms::serve(args.port, server_options).await?
         }
 
         #[cfg(not(feature = "server"))]
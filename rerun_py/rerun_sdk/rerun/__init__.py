@@ -10,6 +10,7 @@ from rerun.log.annotation import log_annotation_context
 from rerun.log.arrow import log_arrow
 from rerun.log.bounding_box import log_obb
 from rerun.log.camera import log_pinhole
+from rerun.log.color_lut import log_color_lut
 from rerun.log.extension_components import log_extension_components
 from rerun.log.file import log_image_file, log_mesh_file
 from rerun.log.image import log_depth_image, log_image, log_segmentation_image
@@ -21,6 +22,7 @@ from rerun.log.scalar import log_scalar
 from rerun.log.tensor import log_tensor
 from rerun.log.text import log_text_entry
 from rerun.log.transform import log_rigid3, log_unknown_transform, log_view_coordinates
+from rerun.log.voxel_grid import log_voxel_grid
 from rerun.script_helpers import script_add_args, script_setup, script_teardown
 
 __all__ = [
@@ -30,6 +32,7 @@ __all__ = [
     "log_annotation_context",
     "log_arrow",
     "log_cleared",
+    "log_color_lut",
     "log_depth_image",
     "log_extension_components",
     "log_image_file",
@@ -53,6 +56,7 @@ __all__ = [
     "log_text_entry",
     "log_unknown_transform",
     "log_view_coordinates",
+    "log_voxel_grid",
     "LoggingHandler",
     "script_add_args",
     "script_setup",
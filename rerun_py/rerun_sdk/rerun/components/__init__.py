@@ -13,6 +13,7 @@ all = [
     "arrow",
     "box",
     "color",
+    "color_lut",
     "label",
     "point",
     "quaternion",
@@ -23,6 +24,7 @@ all = [
     "tensor",
     "text_entry",
     "vec",
+    "voxel_grid",
 ]
 
 # Component names that are recognized by Rerun.
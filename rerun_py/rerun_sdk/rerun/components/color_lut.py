@@ -0,0 +1,28 @@
+from __future__ import annotations
+
+import numpy as np
+import numpy.typing as npt
+import pyarrow as pa
+
+from rerun.color_conversion import u8_array_to_rgba
+from rerun.components import REGISTERED_COMPONENT_NAMES, ComponentTypeFactory
+
+__all__ = [
+    "ColorLutArray",
+    "ColorLutType",
+]
+
+
+class ColorLutArray(pa.ExtensionArray):  # type: ignore[misc]
+    def from_numpy(colors: npt.NDArray[np.uint8]) -> ColorLutArray:
+        """Build a `ColorLutArray` holding a single lookup table from an Nx3 or Nx4 numpy array of colors."""
+        lut = {"colors": u8_array_to_rgba(np.atleast_2d(colors)).tolist()}
+        storage = pa.array([lut], type=ColorLutType.storage_type)
+        # TODO(john) enable extension type wrapper
+        # return cast(ColorLutArray, pa.ExtensionArray.from_storage(ColorLutType(), storage))
+        return storage  # type: ignore[no-any-return]
+
+
+ColorLutType = ComponentTypeFactory("ColorLutType", ColorLutArray, REGISTERED_COMPONENT_NAMES["rerun.color_lut"])
+
+pa.register_extension_type(ColorLutType())
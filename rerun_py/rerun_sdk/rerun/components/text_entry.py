@@ -14,8 +14,8 @@ __all__ = [
 
 class TextEntryArray(pa.ExtensionArray):  # type: ignore[misc]
     def from_bodies_and_levels(text_entries: Sequence[tuple[str, str | None]]) -> TextEntryArray:
-        """Build a `TextEntryArray` from a sequence of text bodies and log levels."""
-        storage = pa.array(text_entries, type=TextEntryType.storage_type)
+        """Build a `TextEntryArray` from a sequence of text bodies and log levels, without any structured fields."""
+        storage = pa.array([(body, level, None) for body, level in text_entries], type=TextEntryType.storage_type)
         # TODO(john) enable extension type wrapper
         # return cast(TextEntryArray, pa.ExtensionArray.from_storage(TextEntryType(), storage))
         return storage  # type: ignore[no-any-return]
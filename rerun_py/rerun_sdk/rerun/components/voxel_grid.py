@@ -0,0 +1,103 @@
+from __future__ import annotations
+
+import numpy as np
+import numpy.typing as npt
+import pyarrow as pa
+
+from rerun.components import (
+    REGISTERED_COMPONENT_NAMES,
+    ComponentTypeFactory,
+    build_dense_union,
+)
+
+__all__ = [
+    "VoxelGridArray",
+    "VoxelGridType",
+]
+
+
+class VoxelGridArray(pa.ExtensionArray):  # type: ignore[misc]
+    def from_dense(
+        origin: npt.NDArray[np.float32],
+        voxel_size: float,
+        values: npt.NDArray[np.float32],
+    ) -> VoxelGridArray:
+        """
+        Build a `VoxelGridArray` from a dense ZxYxX (or YxX) array of cell values.
+
+        `NaN` marks an empty cell.
+        """
+        values = np.asarray(values, dtype=np.float32)
+        if values.ndim == 2:
+            values = values[np.newaxis, :, :]
+        if values.ndim != 3:
+            raise ValueError(f"Expected a 2D or 3D array of cell values, got shape {values.shape}")
+
+        dimensions = [values.shape[2], values.shape[1], values.shape[0]]
+        values_storage = pa.array(values.flatten(), type=pa.float32())
+        cells = build_dense_union(
+            VoxelGridType.storage_type["cells"].type,
+            discriminant="Dense",
+            child=pa.ListArray.from_arrays(pa.array([0, len(values_storage)]), values_storage),
+        )
+        return _build_grid(origin, voxel_size, dimensions, cells)
+
+    def from_sparse(
+        origin: npt.NDArray[np.float32],
+        voxel_size: float,
+        dimensions: npt.ArrayLike,
+        indices: npt.NDArray[np.uint32],
+        values: npt.NDArray[np.float32],
+    ) -> VoxelGridArray:
+        """
+        Build a `VoxelGridArray` from the XYZ `indices` of the occupied cells and their `values`.
+
+        `dimensions` is the number of cells along X, Y and Z.
+        """
+        dimensions = [int(d) for d in np.asarray(dimensions).flatten()]
+        if len(dimensions) != 3:
+            raise ValueError(f"Expected three dimensions, got {dimensions}")
+
+        indices = np.atleast_2d(np.asarray(indices, dtype=np.uint64))
+        values = np.asarray(values, dtype=np.float32).flatten()
+        if indices.shape[1] != 3 or len(indices) != len(values):
+            raise ValueError(
+                f"Expected Nx3 indices and N values, got {indices.shape} indices and {len(values)} values"
+            )
+
+        linear_indices = indices[:, 0] + dimensions[0] * (indices[:, 1] + dimensions[1] * indices[:, 2])
+        sparse = {"indices": linear_indices.astype(np.uint32).tolist(), "values": values.tolist()}
+        sparse_type = VoxelGridType.storage_type["cells"].type[1].type
+        cells = build_dense_union(
+            VoxelGridType.storage_type["cells"].type,
+            discriminant="Sparse",
+            child=pa.array([sparse], type=sparse_type),
+        )
+        return _build_grid(origin, voxel_size, dimensions, cells)
+
+
+def _build_grid(
+    origin: npt.NDArray[np.float32],
+    voxel_size: float,
+    dimensions: list[int],
+    cells: pa.UnionArray,
+) -> VoxelGridArray:
+    origin = np.asarray(origin, dtype=np.float32).flatten()
+    storage = pa.StructArray.from_arrays(
+        [
+            pa.array([origin.tolist()], type=VoxelGridType.storage_type["origin"].type),
+            pa.array([voxel_size], type=pa.float32()),
+            pa.array([dimensions], type=VoxelGridType.storage_type["dimensions"].type),
+            cells,
+        ],
+        fields=list(VoxelGridType.storage_type),
+    ).cast(VoxelGridType.storage_type)
+    storage.validate(full=True)
+    # TODO(john) enable extension type wrapper
+    # return cast(VoxelGridArray, pa.ExtensionArray.from_storage(VoxelGridType(), storage))
+    return storage  # type: ignore[no-any-return]
+
+
+VoxelGridType = ComponentTypeFactory("VoxelGridType", VoxelGridArray, REGISTERED_COMPONENT_NAMES["rerun.voxel_grid"])
+
+pa.register_extension_type(VoxelGridType())
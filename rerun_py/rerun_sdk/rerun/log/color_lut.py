@@ -0,0 +1,45 @@
+from typing import Any, Dict
+
+import numpy.typing as npt
+
+from rerun import bindings
+from rerun.components.color_lut import ColorLutArray
+from rerun.components.instance import InstanceArray
+from rerun.log import _normalize_colors
+from rerun.log.log_decorator import log_decorator
+
+__all__ = [
+    "log_color_lut",
+]
+
+
+@log_decorator
+def log_color_lut(
+    entity_path: str,
+    colors: npt.ArrayLike,
+    *,
+    timeless: bool = False,
+) -> None:
+    """
+    Log a custom color map for a color mapped entity, e.g. a depth image.
+
+    The colors are evenly spaced over the value range of the entity, values in between are
+    linearly interpolated.
+    To use it, select "Custom LUT" as the color map of the entity in the viewer.
+
+    Parameters
+    ----------
+    entity_path:
+        Path to the color mapped entity.
+    colors:
+        Nx3 or Nx4 RGB(A) colors, either as 0-255 ints or 0.0-1.0 floats.
+    timeless:
+        If true, the lookup table will be timeless (default: False).
+
+    """
+    colors = _normalize_colors(colors)
+
+    splats: Dict[str, Any] = {}
+    splats["rerun.color_lut"] = ColorLutArray.from_numpy(colors)
+    splats["rerun.instance_key"] = InstanceArray.splat()
+    bindings.log_arrow_msg(entity_path, components=splats, timeless=timeless)
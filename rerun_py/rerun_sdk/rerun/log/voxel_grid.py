@@ -0,0 +1,96 @@
+from typing import Any, Dict, Optional
+
+import numpy as np
+import numpy.typing as npt
+
+from rerun import bindings
+from rerun.components.instance import InstanceArray
+from rerun.components.voxel_grid import VoxelGridArray
+from rerun.log.error_utils import _send_warning
+from rerun.log.extension_components import _add_extension_components
+from rerun.log.log_decorator import log_decorator
+
+__all__ = [
+    "log_voxel_grid",
+]
+
+
+@log_decorator
+def log_voxel_grid(
+    entity_path: str,
+    *,
+    voxel_size: float,
+    origin: Optional[npt.ArrayLike] = None,
+    values: Optional[npt.ArrayLike] = None,
+    dimensions: Optional[npt.ArrayLike] = None,
+    indices: Optional[npt.ArrayLike] = None,
+    ext: Optional[Dict[str, Any]] = None,
+    timeless: bool = False,
+) -> None:
+    """
+    Log a regular grid of cubic cells, e.g. an occupancy map or a 2D costmap.
+
+    Cells are either given densely or sparsely:
+
+    * Dense: `values` is a ZxYxX array (or YxX for 2D grids) with one value per cell.
+      `NaN` marks an empty cell.
+    * Sparse: `indices` is an Nx3 array with the XYZ index of every occupied cell,
+      `values` holds one value per index and `dimensions` is the number of cells along X, Y and Z.
+      Without `values`, all occupied cells have the value 1.0.
+
+    The values are color mapped in the viewer, see the entity's color map setting.
+
+    Parameters
+    ----------
+    entity_path:
+        Path to the grid in the space hierarchy.
+    voxel_size:
+        Edge length of every cell.
+    origin:
+        The corner of the first cell, i.e. the minimum of the grid's bounding box (default: [0, 0, 0]).
+    values:
+        Cell values, see above.
+    dimensions:
+        Number of cells along X, Y and Z, only used with `indices`.
+    indices:
+        Nx3 indices of the occupied cells, see above.
+    ext:
+        Optional dictionary of extension components. See [rerun.log_extension_components][]
+    timeless:
+        If true, the grid will be timeless (default: False).
+
+    """
+    origin = np.zeros(3, dtype=np.float32) if origin is None else np.asarray(origin, dtype=np.float32)
+    if origin.size != 3:
+        _send_warning(f"Expected a 3D origin, got shape {origin.shape}. Skipping this voxel grid.", 1)
+        return
+
+    if indices is not None:
+        if dimensions is None:
+            _send_warning("Sparse voxel grids need `dimensions`. Skipping this voxel grid.", 1)
+            return
+        indices = np.asarray(indices)
+        if values is None:
+            values = np.ones(len(np.atleast_2d(indices)), dtype=np.float32)
+        grid = VoxelGridArray.from_sparse(origin, voxel_size, dimensions, indices, np.asarray(values))
+    elif values is not None:
+        grid = VoxelGridArray.from_dense(origin, voxel_size, np.asarray(values))
+    else:
+        _send_warning("Expected either `values` or `indices`. Skipping this voxel grid.", 1)
+        return
+
+    instanced: Dict[str, Any] = {}
+    splats: Dict[str, Any] = {}
+
+    instanced["rerun.voxel_grid"] = grid
+
+    if ext:
+        _add_extension_components(instanced, splats, ext, None)
+
+    if splats:
+        splats["rerun.instance_key"] = InstanceArray.splat()
+        bindings.log_arrow_msg(entity_path, components=splats, timeless=timeless)
+
+    # Always the primary component last so range-based queries will include the other data. See(#1215)
+    if instanced:
+        bindings.log_arrow_msg(entity_path, components=instanced, timeless=timeless)